@@ -0,0 +1,210 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the BEEFY bridge pallet.
+//!
+//! Only the dispatchables dealing with aggregatable signatures are benchmarked here, so the
+//! benchmarks require a bridged chain whose validators are using the `(ECDSA,BLS12-381)` paired
+//! crypto. Their cost is dominated by the BLS operations, which grow with the number of
+//! validators in the authority set:
+//!
+//! 1. `submit_proofs_of_possession` performs two pairings per validator;
+//! 2. `submit_aggregated_commitment` aggregates the public keys of all the signers before
+//!    performing a single verification;
+//! 3. `report_equivocation` looks the offender up in the authority set, whose merkle root is
+//!    computed, before checking both votes.
+
+use crate::*;
+
+use bp_beefy::{
+	aggregate_ecdsa_bls_validator_signatures, BeefyPayload, Commitment,
+	EcdsaBlsAggregateSignatureVerifier, EcdsaBlsValidatorId, EcdsaBlsValidatorSignature,
+	MmrLeafVersion, ValidatorSet, VoteMessage, MMR_ROOT_PAYLOAD_ID,
+};
+use bp_runtime::BasicOperatingMode;
+use codec::Encode;
+use frame_benchmarking::{benchmarks_instance_pallet, whitelisted_caller};
+use frame_support::{traits::Get, BoundedVec};
+use frame_system::RawOrigin;
+use sp_core::{ecdsa_bls381::Pair as EcdsaBlsPair, Pair, H256};
+use sp_runtime::traits::{Hash, Keccak256, One, Zero};
+use sp_std::{vec, vec::Vec};
+
+/// Return key pairs of `count` validators.
+fn validator_pairs(count: u32) -> Vec<EcdsaBlsPair> {
+	(0..count)
+		.map(|index| {
+			let mut seed = [1u8; 32];
+			seed[0..4].copy_from_slice(&index.encode());
+			EcdsaBlsPair::from_seed(&seed)
+		})
+		.collect()
+}
+
+/// Sign the `commitment` by the validator with the given key pair.
+fn sign_commitment<BlockNumber: Encode>(
+	pair: &EcdsaBlsPair,
+	commitment: &Commitment<BlockNumber>,
+) -> EcdsaBlsValidatorSignature {
+	pair.sign_with_hasher::<Keccak256>(&commitment.encode()).into()
+}
+
+benchmarks_instance_pallet! {
+	where_clause {
+		where
+			T::BridgedChain: ChainWithBeefy<
+				AuthorityId = EcdsaBlsValidatorId,
+				AggregateSignatureVerifier = EcdsaBlsAggregateSignatureVerifier,
+				CommitmentHasher = Keccak256,
+				MmrHashing = Keccak256,
+				MmrHash = H256,
+			>,
+			BridgedBeefyMmrLeafExtra<T, I>: Default,
+	}
+
+	submit_proofs_of_possession {
+		let n in 1 .. T::MaxAuthorities::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let pairs = validator_pairs(n);
+		let validator_set = initialize_bridge::<T, I>(&pairs);
+		let proofs: BoundedVec<_, T::MaxAuthorities> = pairs
+			.iter()
+			.map(|pair| pair.generate_proof_of_possession())
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("`n` is at most `MaxAuthorities`; qed");
+	}: submit_proofs_of_possession(RawOrigin::Signed(caller), validator_set, proofs)
+	verify {
+		assert_eq!(ProvenAuthoritySetId::<T, I>::get(), Some(0));
+	}
+
+	// The commitment is signed by all the validators and enacts the next authority set, which
+	// is the worst case. It also prunes the oldest imported commitment.
+	submit_aggregated_commitment {
+		let n in 1 .. T::MaxAuthorities::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let pairs = validator_pairs(n);
+		let validator_set = initialize_bridge::<T, I>(&pairs);
+		ProvenAuthoritySetId::<T, I>::put(0);
+		ImportedBlockNumbers::<T, I>::insert(0, BridgedBlockNumber::<T, I>::zero());
+
+		let mut next_authority_set = CurrentAuthoritySetInfo::<T, I>::get();
+		next_authority_set.id += 1;
+		let mmr_leaf = BridgedBeefyMmrLeaf::<T, I> {
+			version: MmrLeafVersion::new(1, 0),
+			parent_number_and_hash: (Zero::zero(), Default::default()),
+			beefy_next_authority_set: next_authority_set,
+			leaf_extra: Default::default(),
+		};
+		// The MMR has a single leaf, so its root is the hash of this leaf.
+		let mmr_root = Keccak256::hash(&mmr_leaf.encode());
+		let mmr_proof = BridgedMmrProof::<T, I> { leaf_indices: vec![0], leaf_count: 1, items: vec![] };
+
+		let commitment = Commitment {
+			payload: BeefyPayload::from_single_entry(MMR_ROOT_PAYLOAD_ID, mmr_root.encode()),
+			block_number: One::one(),
+			validator_set_id: 0,
+		};
+		let signatures = pairs
+			.iter()
+			.map(|pair| sign_commitment(pair, &commitment))
+			.collect::<Vec<_>>();
+		let signature_accumulator = aggregate_ecdsa_bls_validator_signatures(&signatures)
+			.expect("signatures are valid; qed");
+		let commitment = BridgedBeefyAggregatedCommitment::<T, I> {
+			commitment,
+			signed_by: vec![true; n as usize],
+			signature_accumulator,
+		};
+	}: submit_aggregated_commitment(
+		RawOrigin::Signed(caller),
+		commitment,
+		validator_set,
+		Box::new(mmr_leaf),
+		mmr_proof
+	)
+	verify {
+		assert_eq!(
+			ImportedCommitmentsInfo::<T, I>::get().map(|info| info.best_block_number),
+			Some(One::one()),
+		);
+		assert_eq!(CurrentAuthoritySetInfo::<T, I>::get().id, 1);
+	}
+
+	// The offender is the last validator of the authority set, which is the worst case.
+	report_equivocation {
+		let n in 1 .. T::MaxAuthorities::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let pairs = validator_pairs(n);
+		let validator_set = initialize_bridge::<T, I>(&pairs);
+		ProvenAuthoritySetId::<T, I>::put(0);
+
+		let offender = pairs.last().expect("`n` is at least 1; qed");
+		let vote = |mmr_root: H256| {
+			let commitment = Commitment {
+				payload: BeefyPayload::from_single_entry(MMR_ROOT_PAYLOAD_ID, mmr_root.encode()),
+				block_number: One::one(),
+				validator_set_id: 0,
+			};
+			let signature = sign_commitment(offender, &commitment);
+			VoteMessage { commitment, id: offender.public().into(), signature }
+		};
+		let equivocation_proof = BridgedBeefyEquivocationProof::<T, I> {
+			first: vote(H256::repeat_byte(1)),
+			second: vote(H256::repeat_byte(2)),
+		};
+	}: report_equivocation(RawOrigin::Signed(caller), Box::new(equivocation_proof), validator_set)
+	verify {
+		assert_eq!(EquivocatingAuthoritySetId::<T, I>::get(), Some(0));
+	}
+
+	impl_benchmark_test_suite!(
+		BlsPallet,
+		crate::mock::new_test_ext(),
+		crate::mock::TestRuntime,
+	)
+}
+
+/// Initialize the pallet with the authority set of the validators with given key pairs.
+fn initialize_bridge<T: Config<I>, I: 'static>(
+	pairs: &[EcdsaBlsPair],
+) -> ValidatorSet<EcdsaBlsValidatorId>
+where
+	T::BridgedChain: ChainWithBeefy<AuthorityId = EcdsaBlsValidatorId>,
+{
+	let validators = pairs
+		.iter()
+		.map(|pair| pair.public().into())
+		.collect::<Vec<EcdsaBlsValidatorId>>();
+	let authority_set = BridgedBeefyAuthoritySetInfo::<T, I> {
+		id: 0,
+		len: validators.len() as u32,
+		keyset_commitment: utils::get_authorities_mmr_root::<T, I, _>(validators.iter()),
+	};
+	pallet::initialize::<T, I>(InitializationData {
+		operating_mode: BasicOperatingMode::Normal,
+		best_block_number: Zero::zero(),
+		authority_set,
+	})
+	.expect("the authority set is not empty; qed");
+
+	ValidatorSet::new(validators, 0).expect("the authority set is not empty; qed")
+}
+
+/// The pallet instance bridging the chain with `(ECDSA,BLS12-381)` validators in the test
+/// runtime.
+#[cfg(test)]
+type BlsPallet<T> = Pallet<T, crate::mock::bls::BlsInstance>;
@@ -28,6 +28,19 @@
 //!
 //! Given the header hash, other pallets are able to verify header-based proofs
 //! (e.g. storage proofs, transaction inclusion proofs, etc.).
+//!
+//! If the bridged chain validators are producing aggregatable signatures (e.g. using the
+//! `(ECDSA,BLS12-381)` paired crypto), commitments may be also submitted in the aggregated
+//! form: a bitfield of signers together with one aggregated signature, which is verified
+//! with a single check instead of one check per signer. Aggregated commitments are only
+//! accepted once the proofs of possession of the secret keys of all the validators of the
+//! current authority set have been submitted, which protects against rogue key attacks.
+//!
+//! Commitments with individual signatures are still accepted. They are the ones to be used
+//! as evidence for equivocation reports, since an aggregated signature doesn't tell who the
+//! equivocating validator is. Once an equivocation of the current authority set is reported,
+//! aggregated commitments are refused until the next authority set, and relayers fall back
+//! to commitments with individual (i.e. ECDSA) signatures.
 
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -37,9 +50,15 @@ use sp_std::{boxed::Box, prelude::*};
 
 // Re-export in crate namespace for `construct_runtime!`
 pub use pallet::*;
+pub use weights::WeightInfo;
 
 mod utils;
 
+#[cfg(all(feature = "runtime-benchmarks", feature = "bls-experimental"))]
+pub mod benchmarking;
+/// Module, containing weights for this pallet.
+pub mod weights;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -68,6 +87,18 @@ pub type BridgedBeefyAuthoritySet<T, I> = bp_beefy::BeefyAuthoritySetOf<BridgedC
 pub type BridgedBeefyAuthoritySetInfo<T, I> = bp_beefy::BeefyAuthoritySetInfoOf<BridgedChain<T, I>>;
 /// BEEFY signed commitment, used by configured bridged chain.
 pub type BridgedBeefySignedCommitment<T, I> = bp_beefy::BeefySignedCommitmentOf<BridgedChain<T, I>>;
+/// BEEFY commitment with aggregated signature, used by configured bridged chain.
+pub type BridgedBeefyAggregatedCommitment<T, I> =
+	bp_beefy::BeefyAggregatedCommitmentOf<BridgedChain<T, I>>;
+/// BEEFY aggregated signature verifier, used by configured bridged chain.
+pub type BridgedBeefyAggregateSignatureVerifier<T, I> =
+	<BridgedChain<T, I> as ChainWithBeefy>::AggregateSignatureVerifier;
+/// Proof of possession of the secret key of a BEEFY validator, used by configured bridged chain.
+pub type BridgedBeefyProofOfPossession<T, I> =
+	bp_beefy::BeefyProofOfPossessionOf<BridgedChain<T, I>>;
+/// BEEFY equivocation proof, used by configured bridged chain.
+pub type BridgedBeefyEquivocationProof<T, I> =
+	bp_beefy::BeefyEquivocationProofOf<BridgedChain<T, I>>;
 /// MMR hashing algorithm, used by configured bridged chain.
 pub type BridgedMmrHashing<T, I> = bp_beefy::MmrHashingOf<BridgedChain<T, I>>;
 /// MMR hashing output type of `BridgedMmrHashing<T, I>`.
@@ -121,8 +152,17 @@ pub mod pallet {
 		#[pallet::constant]
 		type CommitmentsToKeep: Get<u32>;
 
+		/// Maximal number of validators in an authority set of the bridged chain.
+		///
+		/// It bounds the number of proofs of possession that may be submitted at once.
+		#[pallet::constant]
+		type MaxAuthorities: Get<u32>;
+
 		/// The chain we are bridging to here.
 		type BridgedChain: ChainWithBeefy;
+
+		/// Weights gathered through benchmarking.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
@@ -231,44 +271,154 @@ pub mod pallet {
 			)?;
 			utils::verify_beefy_mmr_leaf::<T, I>(&mmr_leaf, mmr_proof, mmr_root)?;
 
-			// Update request count.
-			RequestCount::<T, I>::mutate(|count| *count += 1);
-			// Update authority set if needed.
-			if mmr_leaf.beefy_next_authority_set.id > current_authority_set_info.id {
-				CurrentAuthoritySetInfo::<T, I>::put(mmr_leaf.beefy_next_authority_set);
-			}
-
-			// Import commitment.
-			let block_number_index = commitments_info.next_block_number_index;
-			let to_prune = ImportedBlockNumbers::<T, I>::try_get(block_number_index);
-			ImportedCommitments::<T, I>::insert(
+			Self::import_commitment(
 				commitment.commitment.block_number,
-				ImportedCommitment::<T, I> {
-					parent_number_and_hash: mmr_leaf.parent_number_and_hash,
-					mmr_root,
-				},
+				commitments_info,
+				current_authority_set_info,
+				*mmr_leaf,
+				mmr_root,
+			);
+
+			Ok(())
+		}
+
+		/// Submit a commitment generated by BEEFY authority set, signed with an aggregated
+		/// signature.
+		///
+		/// The `signed_by` bitfield of the commitment marks the validators of `validator_set`
+		/// whose signatures have been aggregated. The aggregated signature is verified at once
+		/// using the `AggregateSignatureVerifier` of the bridged chain.
+		///
+		/// Apart from the signature verification, it works as [`Pallet::submit_commitment`].
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::submit_aggregated_commitment(validator_set.len() as u32))]
+		pub fn submit_aggregated_commitment(
+			origin: OriginFor<T>,
+			commitment: BridgedBeefyAggregatedCommitment<T, I>,
+			validator_set: BridgedBeefyAuthoritySet<T, I>,
+			mmr_leaf: Box<BridgedBeefyMmrLeaf<T, I>>,
+			mmr_proof: BridgedMmrProof<T, I>,
+		) -> DispatchResult {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			ensure_signed(origin)?;
+
+			ensure!(Self::request_count() < T::MaxRequests::get(), <Error<T, I>>::TooManyRequests);
+
+			// Ensure that the commitment is for a better block.
+			let commitments_info =
+				ImportedCommitmentsInfo::<T, I>::get().ok_or(Error::<T, I>::NotInitialized)?;
+			ensure!(
+				commitment.commitment.block_number > commitments_info.best_block_number,
+				Error::<T, I>::OldCommitment
 			);
-			ImportedBlockNumbers::<T, I>::insert(
-				block_number_index,
+
+			// Ensure that the proofs of possession of the current authority set are known.
+			let current_authority_set_info = CurrentAuthoritySetInfo::<T, I>::get();
+			ensure!(
+				ProvenAuthoritySetId::<T, I>::get() == Some(current_authority_set_info.id),
+				Error::<T, I>::AuthoritySetPossessionNotProven
+			);
+
+			// Verify commitment and mmr leaf.
+			let mmr_root = utils::verify_aggregated_commitment::<T, I>(
+				&commitment,
+				&current_authority_set_info,
+				&validator_set,
+			)?;
+			utils::verify_beefy_mmr_leaf::<T, I>(&mmr_leaf, mmr_proof, mmr_root)?;
+
+			Self::import_commitment(
 				commitment.commitment.block_number,
+				commitments_info,
+				current_authority_set_info,
+				*mmr_leaf,
+				mmr_root,
 			);
-			ImportedCommitmentsInfo::<T, I>::put(ImportedCommitmentsInfoData {
-				best_block_number: commitment.commitment.block_number,
-				next_block_number_index: (block_number_index + 1) % T::CommitmentsToKeep::get(),
-			});
-			if let Ok(old_block_number) = to_prune {
-				log::debug!(
-					target: LOG_TARGET,
-					"Pruning commitment for old block: {:?}.",
-					old_block_number
-				);
-				ImportedCommitments::<T, I>::remove(old_block_number);
-			}
+
+			Ok(())
+		}
+
+		/// Submit the proofs of possession of the secret keys of the current authority set.
+		///
+		/// `proofs` must contain the proof of each validator of `validator_set`, in the same
+		/// order, so at most `MaxAuthorities` validators are supported. Once verified, aggregated
+		/// commitments of the current authority set are accepted. The proofs must be submitted
+		/// again for every new authority set.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::submit_proofs_of_possession(proofs.len() as u32))]
+		pub fn submit_proofs_of_possession(
+			origin: OriginFor<T>,
+			validator_set: BridgedBeefyAuthoritySet<T, I>,
+			proofs: BoundedVec<BridgedBeefyProofOfPossession<T, I>, T::MaxAuthorities>,
+		) -> DispatchResult {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			ensure_signed(origin)?;
+
+			ensure!(ImportedCommitmentsInfo::<T, I>::exists(), Error::<T, I>::NotInitialized);
+			let current_authority_set_info = CurrentAuthoritySetInfo::<T, I>::get();
+			ensure!(
+				ProvenAuthoritySetId::<T, I>::get() != Some(current_authority_set_info.id),
+				Error::<T, I>::AuthoritySetPossessionAlreadyProven
+			);
+			ensure!(
+				EquivocatingAuthoritySetId::<T, I>::get() != Some(current_authority_set_info.id),
+				Error::<T, I>::AuthoritySetEquivocated
+			);
+
+			utils::verify_proofs_of_possession::<T, I>(
+				&current_authority_set_info,
+				&validator_set,
+				&proofs,
+			)?;
+			ProvenAuthoritySetId::<T, I>::put(current_authority_set_info.id);
 
 			log::info!(
 				target: LOG_TARGET,
-				"Successfully imported commitment for block {:?}",
-				commitment.commitment.block_number,
+				"Verified proofs of possession of authority set {}",
+				current_authority_set_info.id,
+			);
+
+			Ok(())
+		}
+
+		/// Report an equivocation of a validator of the current authority set.
+		///
+		/// The votes of the equivocation proof carry the individual signatures of the
+		/// validator. Once the equivocation is verified, aggregated commitments of the current
+		/// authority set are refused, so only commitments with individual signatures are
+		/// accepted until the next authority set.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::report_equivocation(validator_set.len() as u32))]
+		pub fn report_equivocation(
+			origin: OriginFor<T>,
+			equivocation_proof: Box<BridgedBeefyEquivocationProof<T, I>>,
+			validator_set: BridgedBeefyAuthoritySet<T, I>,
+		) -> DispatchResult {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			ensure_signed(origin)?;
+
+			ensure!(ImportedCommitmentsInfo::<T, I>::exists(), Error::<T, I>::NotInitialized);
+			let current_authority_set_info = CurrentAuthoritySetInfo::<T, I>::get();
+			ensure!(
+				EquivocatingAuthoritySetId::<T, I>::get() != Some(current_authority_set_info.id),
+				Error::<T, I>::AuthoritySetEquivocated
+			);
+
+			utils::verify_equivocation_proof::<T, I>(
+				&equivocation_proof,
+				&current_authority_set_info,
+				&validator_set,
+			)?;
+			EquivocatingAuthoritySetId::<T, I>::put(current_authority_set_info.id);
+			ProvenAuthoritySetId::<T, I>::kill();
+
+			log::warn!(
+				target: LOG_TARGET,
+				"Validator {:?} of authority set {} equivocated at block {:?}. \
+				Aggregated commitments are disabled for this authority set.",
+				equivocation_proof.offender_id(),
+				current_authority_set_info.id,
+				equivocation_proof.round_number(),
 			);
 
 			Ok(())
@@ -310,6 +460,21 @@ pub mod pallet {
 	pub type CurrentAuthoritySetInfo<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, BridgedBeefyAuthoritySetInfo<T, I>, ValueQuery>;
 
+	/// Id of the current authority set, if the proofs of possession of the secret keys of all
+	/// its validators have been verified.
+	///
+	/// Aggregated commitments are only accepted when it matches the current authority set id.
+	#[pallet::storage]
+	pub type ProvenAuthoritySetId<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, bp_beefy::ValidatorSetId, OptionQuery>;
+
+	/// Id of the last authority set for which an equivocation has been reported.
+	///
+	/// Aggregated commitments of this authority set are refused.
+	#[pallet::storage]
+	pub type EquivocatingAuthoritySetId<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, bp_beefy::ValidatorSetId, OptionQuery>;
+
 	/// Optional pallet owner.
 	///
 	/// Pallet owner has the right to halt all pallet operations and then resume it. If it is
@@ -382,6 +547,20 @@ pub mod pallet {
 		MmrProofVerificationFailed,
 		/// The validators are not matching the merkle tree root of the authority set.
 		InvalidValidatorSetRoot,
+		/// The aggregated signature of the commitment is invalid.
+		InvalidAggregateSignature,
+		/// The proofs of possession of the current authority set have not been verified.
+		AuthoritySetPossessionNotProven,
+		/// The proofs of possession of the current authority set have been already verified.
+		AuthoritySetPossessionAlreadyProven,
+		/// The number of proofs of possession is invalid.
+		InvalidProofsOfPossessionLen,
+		/// Some of the proofs of possession are invalid.
+		InvalidProofOfPossession,
+		/// An equivocation of the current authority set has been already reported.
+		AuthoritySetEquivocated,
+		/// The equivocation proof is invalid.
+		InvalidEquivocationProof,
 		/// Error generated by the `OwnedBridgeModule` trait.
 		BridgeModule(bp_runtime::OwnedBridgeModuleError),
 	}
@@ -409,6 +588,52 @@ pub mod pallet {
 		pub fn request_count() -> u32 {
 			RequestCount::<T, I>::get()
 		}
+
+		/// Import the verified commitment data.
+		fn import_commitment(
+			block_number: BridgedBlockNumber<T, I>,
+			commitments_info: ImportedCommitmentsInfoData<BridgedBlockNumber<T, I>>,
+			current_authority_set_info: BridgedBeefyAuthoritySetInfo<T, I>,
+			mmr_leaf: BridgedBeefyMmrLeaf<T, I>,
+			mmr_root: BridgedMmrHash<T, I>,
+		) {
+			// Update request count.
+			RequestCount::<T, I>::mutate(|count| *count += 1);
+			// Update authority set if needed.
+			if mmr_leaf.beefy_next_authority_set.id > current_authority_set_info.id {
+				CurrentAuthoritySetInfo::<T, I>::put(mmr_leaf.beefy_next_authority_set);
+			}
+
+			// Import commitment.
+			let block_number_index = commitments_info.next_block_number_index;
+			let to_prune = ImportedBlockNumbers::<T, I>::try_get(block_number_index);
+			ImportedCommitments::<T, I>::insert(
+				block_number,
+				ImportedCommitment::<T, I> {
+					parent_number_and_hash: mmr_leaf.parent_number_and_hash,
+					mmr_root,
+				},
+			);
+			ImportedBlockNumbers::<T, I>::insert(block_number_index, block_number);
+			ImportedCommitmentsInfo::<T, I>::put(ImportedCommitmentsInfoData {
+				best_block_number: block_number,
+				next_block_number_index: (block_number_index + 1) % T::CommitmentsToKeep::get(),
+			});
+			if let Ok(old_block_number) = to_prune {
+				log::debug!(
+					target: LOG_TARGET,
+					"Pruning commitment for old block: {:?}.",
+					old_block_number
+				);
+				ImportedCommitments::<T, I>::remove(old_block_number);
+			}
+
+			log::info!(
+				target: LOG_TARGET,
+				"Successfully imported commitment for block {:?}",
+				block_number,
+			);
+		}
 	}
 }
 
@@ -420,7 +645,7 @@ mod tests {
 	use frame_support::{assert_noop, assert_ok, traits::Get};
 	use mock::*;
 	use mock_chain::*;
-	use sp_consensus_beefy::mmr::BeefyAuthoritySet;
+	use sp_consensus_beefy::{mmr::BeefyAuthoritySet, ValidatorSet};
 	use sp_runtime::DispatchError;
 
 	fn next_block() {
@@ -653,5 +878,165 @@ mod tests {
 		});
 	}
 
+	fn equivocation_proof(
+		validator: &BeefyPair,
+		validator_set_id: u64,
+		first_mmr_root: TestBridgedMmrHash,
+		second_mmr_root: TestBridgedMmrHash,
+	) -> TestBridgedEquivocationProof {
+		use codec::Encode;
+		use sp_core::{crypto::Wraps, Pair};
+		use sp_runtime::traits::Hash;
+
+		let vote = |mmr_root: TestBridgedMmrHash| {
+			let commitment = bp_beefy::Commitment {
+				payload: bp_beefy::BeefyPayload::from_single_entry(
+					bp_beefy::MMR_ROOT_PAYLOAD_ID,
+					mmr_root.encode(),
+				),
+				block_number: 1,
+				validator_set_id,
+			};
+			let commitment_hash = TestBridgedCommitmentHasher::hash(&commitment.encode());
+			let signature =
+				validator.as_inner_ref().sign_prehashed(commitment_hash.as_fixed_bytes()).into();
+			sp_consensus_beefy::VoteMessage { commitment, id: validator.public(), signature }
+		};
+
+		sp_consensus_beefy::DoubleVotingProof {
+			first: vote(first_mmr_root),
+			second: vote(second_mmr_root),
+		}
+	}
+
+	#[test]
+	fn submit_proofs_of_possession_checks_proofs() {
+		run_test_with_initialize(8, || {
+			let validator_set = |id| ValidatorSet::new(validator_ids(0, 8), id).unwrap();
+			let submit = |validator_set, proofs: Vec<()>| {
+				Pallet::<TestRuntime>::submit_proofs_of_possession(
+					RuntimeOrigin::signed(1),
+					validator_set,
+					proofs.try_into().unwrap(),
+				)
+			};
+
+			// Fails if the number of proofs doesn't match the authority set.
+			assert_noop!(
+				submit(validator_set(0), vec![(); 7]),
+				Error::<TestRuntime, ()>::InvalidProofsOfPossessionLen,
+			);
+
+			// Fails if the authority set isn't the current one.
+			assert_noop!(
+				submit(validator_set(1), vec![(); 8]),
+				Error::<TestRuntime, ()>::InvalidValidatorSetId,
+			);
+
+			// Fails if the proofs can't be verified.
+			assert_noop!(
+				submit(validator_set(0), vec![(); 8]),
+				Error::<TestRuntime, ()>::InvalidProofOfPossession,
+			);
+
+			// Fails if the current authority set is already proven.
+			ProvenAuthoritySetId::<TestRuntime>::put(0);
+			assert_noop!(
+				submit(validator_set(0), vec![(); 8]),
+				Error::<TestRuntime, ()>::AuthoritySetPossessionAlreadyProven,
+			);
+		});
+	}
+
+	#[test]
+	fn aggregated_commitments_require_proofs_of_possession_of_new_authority_set() {
+		run_test_with_initialize(8, || {
+			ProvenAuthoritySetId::<TestRuntime>::put(0);
+
+			let chain = ChainBuilder::new(8)
+				.append_handoff_header(8)
+				.append_finalized_header()
+				.to_chain();
+			assert_ok!(import_commitment(chain[0].clone()));
+			assert_eq!(CurrentAuthoritySetInfo::<TestRuntime>::get().id, 1);
+
+			// The proofs of possession of the previous authority set don't count.
+			assert_noop!(
+				import_aggregated_commitment(chain[1].clone()),
+				Error::<TestRuntime, ()>::AuthoritySetPossessionNotProven,
+			);
+		});
+	}
+
+	#[test]
+	fn report_equivocation_works() {
+		run_test_with_initialize(8, || {
+			let validator_set = ValidatorSet::new(validator_ids(0, 8), 0).unwrap();
+			let report = |proof| {
+				Pallet::<TestRuntime>::report_equivocation(
+					RuntimeOrigin::signed(1),
+					Box::new(proof),
+					validator_set.clone(),
+				)
+			};
+			let validator = &validator_pairs(0, 1)[0];
+			let first_mmr_root = TestBridgedMmrHash::repeat_byte(1);
+			let second_mmr_root = TestBridgedMmrHash::repeat_byte(2);
+			ProvenAuthoritySetId::<TestRuntime>::put(0);
+
+			// Fails if both votes are for the same commitment.
+			assert_noop!(
+				report(equivocation_proof(validator, 0, first_mmr_root, first_mmr_root)),
+				Error::<TestRuntime, ()>::InvalidEquivocationProof,
+			);
+
+			// Fails if the offender isn't part of the current authority set.
+			assert_noop!(
+				report(equivocation_proof(
+					&validator_pairs(8, 1)[0],
+					0,
+					first_mmr_root,
+					second_mmr_root
+				)),
+				Error::<TestRuntime, ()>::InvalidEquivocationProof,
+			);
+
+			// Fails if the votes are for another authority set.
+			assert_noop!(
+				report(equivocation_proof(validator, 1, first_mmr_root, second_mmr_root)),
+				Error::<TestRuntime, ()>::InvalidCommitmentValidatorSetId,
+			);
+
+			// Works with a valid proof.
+			assert_ok!(report(equivocation_proof(validator, 0, first_mmr_root, second_mmr_root)));
+			assert_eq!(EquivocatingAuthoritySetId::<TestRuntime>::get(), Some(0));
+			assert_eq!(ProvenAuthoritySetId::<TestRuntime>::get(), None);
+
+			// The equivocation is only reported once.
+			assert_noop!(
+				report(equivocation_proof(validator, 0, first_mmr_root, second_mmr_root)),
+				Error::<TestRuntime, ()>::AuthoritySetEquivocated,
+			);
+
+			// The authority set can't be proven anymore, so aggregated commitments are refused.
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_proofs_of_possession(
+					RuntimeOrigin::signed(1),
+					validator_set.clone(),
+					vec![(); 8].try_into().unwrap(),
+				),
+				Error::<TestRuntime, ()>::AuthoritySetEquivocated,
+			);
+			let header = ChainBuilder::new(8).append_finalized_header().to_header();
+			assert_noop!(
+				import_aggregated_commitment(header.clone()),
+				Error::<TestRuntime, ()>::AuthoritySetPossessionNotProven,
+			);
+
+			// Commitments with individual signatures are still accepted.
+			assert_ok!(import_commitment(header));
+		});
+	}
+
 	generate_owned_bridge_module_tests!(BasicOperatingMode::Normal, BasicOperatingMode::Halted);
 }
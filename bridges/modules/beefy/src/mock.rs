@@ -16,14 +16,17 @@
 
 use crate as beefy;
 use crate::{
-	utils::get_authorities_mmr_root, BridgedBeefyAuthoritySet, BridgedBeefyAuthoritySetInfo,
-	BridgedBeefyCommitmentHasher, BridgedBeefyMmrLeafExtra, BridgedBeefySignedCommitment,
-	BridgedMmrHash, BridgedMmrHashing, BridgedMmrProof,
+	utils::get_authorities_mmr_root, BridgedBeefyAggregatedCommitment, BridgedBeefyAuthoritySet,
+	BridgedBeefyAuthoritySetInfo, BridgedBeefyCommitmentHasher, BridgedBeefyEquivocationProof,
+	BridgedBeefyMmrLeafExtra, BridgedBeefySignedCommitment, BridgedMmrHash, BridgedMmrHashing,
+	BridgedMmrProof,
 };
 
 use bp_beefy::{BeefyValidatorSignatureOf, ChainWithBeefy, Commitment, MmrDataOrHash};
 use bp_runtime::{BasicOperatingMode, Chain, ChainId};
 use codec::Encode;
+#[cfg(feature = "bls-experimental")]
+use frame_support::instances::Instance1;
 use frame_support::{construct_runtime, derive_impl, weights::Weight};
 use sp_core::{sr25519::Signature, Pair};
 use sp_runtime::{
@@ -45,6 +48,7 @@ pub type TestBridgedValidatorSet = BridgedBeefyAuthoritySet<TestRuntime, ()>;
 pub type TestBridgedCommitment = BridgedBeefySignedCommitment<TestRuntime, ()>;
 pub type TestBridgedValidatorSignature = BeefyValidatorSignatureOf<TestBridgedChain>;
 pub type TestBridgedCommitmentHasher = BridgedBeefyCommitmentHasher<TestRuntime, ()>;
+pub type TestBridgedEquivocationProof = BridgedBeefyEquivocationProof<TestRuntime, ()>;
 pub type TestBridgedMmrHashing = BridgedMmrHashing<TestRuntime, ()>;
 pub type TestBridgedMmrHash = BridgedMmrHash<TestRuntime, ()>;
 pub type TestBridgedBeefyMmrLeafExtra = BridgedBeefyMmrLeafExtra<TestRuntime, ()>;
@@ -64,6 +68,8 @@ construct_runtime! {
 	{
 		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
 		Beefy: beefy::{Pallet},
+		#[cfg(feature = "bls-experimental")]
+		BeefyBls: beefy::<Instance1>::{Pallet},
 	}
 }
 
@@ -76,6 +82,8 @@ impl beefy::Config for TestRuntime {
 	type MaxRequests = frame_support::traits::ConstU32<16>;
 	type BridgedChain = TestBridgedChain;
 	type CommitmentsToKeep = frame_support::traits::ConstU32<16>;
+	type MaxAuthorities = frame_support::traits::ConstU32<64>;
+	type WeightInfo = ();
 }
 
 #[derive(Debug)]
@@ -111,11 +119,137 @@ impl ChainWithBeefy for TestBridgedChain {
 	type BeefyMmrLeafExtra = ();
 	type AuthorityId = BeefyId;
 	type AuthorityIdToMerkleLeaf = pallet_beefy_mmr::BeefyEcdsaToEthereum;
+	type AggregateSignatureVerifier = ();
+}
+
+/// Bridged chain with validators producing aggregatable `(ECDSA,BLS12-381)` signatures.
+#[cfg(feature = "bls-experimental")]
+pub mod bls {
+	use super::*;
+	use bp_beefy::{EcdsaBlsAggregateSignatureVerifier, EcdsaBlsValidatorId};
+	use sp_core::crypto::ByteArray;
+	use sp_runtime::traits::Convert;
+
+	pub use sp_consensus_beefy::ecdsa_bls_crypto::{
+		AuthorityId as BeefyBlsId, Pair as BeefyBlsPair, Signature as BeefyBlsSignature,
+	};
+
+	pub type BlsInstance = Instance1;
+	pub type TestBlsBridgedAuthoritySetInfo =
+		BridgedBeefyAuthoritySetInfo<TestRuntime, BlsInstance>;
+	pub type TestBlsBridgedValidatorSet = BridgedBeefyAuthoritySet<TestRuntime, BlsInstance>;
+	pub type TestBlsBridgedAggregatedCommitment =
+		BridgedBeefyAggregatedCommitment<TestRuntime, BlsInstance>;
+
+	impl beefy::Config<BlsInstance> for TestRuntime {
+		type MaxRequests = frame_support::traits::ConstU32<16>;
+		type BridgedChain = TestBlsBridgedChain;
+		type CommitmentsToKeep = frame_support::traits::ConstU32<16>;
+		type MaxAuthorities = frame_support::traits::ConstU32<64>;
+		type WeightInfo = ();
+	}
+
+	#[derive(Debug)]
+	pub struct TestBlsBridgedChain;
+
+	impl Chain for TestBlsBridgedChain {
+		const ID: ChainId = *b"tbbl";
+
+		type BlockNumber = TestBridgedBlockNumber;
+		type Hash = H256;
+		type Hasher = BlakeTwo256;
+		type Header = sp_runtime::testing::Header;
+
+		type AccountId = TestAccountId;
+		type Balance = u64;
+		type Nonce = u64;
+		type Signature = Signature;
+
+		const STATE_VERSION: StateVersion = StateVersion::V1;
+
+		fn max_extrinsic_size() -> u32 {
+			unreachable!()
+		}
+		fn max_extrinsic_weight() -> Weight {
+			unreachable!()
+		}
+	}
+
+	impl ChainWithBeefy for TestBlsBridgedChain {
+		type CommitmentHasher = Keccak256;
+		type MmrHashing = Keccak256;
+		type MmrHash = <Keccak256 as Hash>::Output;
+		type BeefyMmrLeafExtra = ();
+		type AuthorityId = BeefyBlsId;
+		type AuthorityIdToMerkleLeaf = BeefyEcdsaBlsToRaw;
+		type AggregateSignatureVerifier = EcdsaBlsAggregateSignatureVerifier;
+	}
+
+	/// Use the raw validator id as the BEEFY merkle tree leaf.
+	pub struct BeefyEcdsaBlsToRaw;
+
+	impl Convert<EcdsaBlsValidatorId, Vec<u8>> for BeefyEcdsaBlsToRaw {
+		fn convert(id: EcdsaBlsValidatorId) -> Vec<u8> {
+			id.to_raw_vec()
+		}
+	}
+
+	pub fn validator_pairs(index: u32, count: u32) -> Vec<BeefyBlsPair> {
+		(index..index + count)
+			.map(|index| {
+				let mut seed = [1u8; 32];
+				seed[0..8].copy_from_slice(&(index as u64).encode());
+				BeefyBlsPair::from_seed(&seed)
+			})
+			.collect()
+	}
+
+	pub fn authority_set_info(
+		id: u64,
+		validators: &[BeefyBlsId],
+	) -> TestBlsBridgedAuthoritySetInfo {
+		let merkle_root =
+			get_authorities_mmr_root::<TestRuntime, BlsInstance, _>(validators.iter());
+
+		TestBlsBridgedAuthoritySetInfo {
+			id,
+			len: validators.len() as u32,
+			keyset_commitment: merkle_root,
+		}
+	}
+
+	/// Sign BEEFY commitment by the validators marked in `signed_by` and aggregate the
+	/// signatures.
+	pub fn sign_aggregated_commitment(
+		commitment: Commitment<TestBridgedBlockNumber>,
+		validator_pairs: &[BeefyBlsPair],
+		signed_by: Vec<bool>,
+	) -> TestBlsBridgedAggregatedCommitment {
+		let encoded_commitment = commitment.encode();
+		let signatures = validator_pairs
+			.iter()
+			.zip(signed_by.iter())
+			.filter(|(_, signed)| **signed)
+			.map(|(pair, _)| {
+				pair.as_inner_ref().sign_with_hasher::<Keccak256>(&encoded_commitment).into()
+			})
+			.collect::<Vec<BeefyBlsSignature>>();
+		let signature_accumulator =
+			sp_consensus_beefy::ecdsa_bls_crypto::aggregate_signatures(&signatures)
+				.expect("at least one valid signature; qed");
+
+		TestBlsBridgedAggregatedCommitment { commitment, signed_by, signature_accumulator }
+	}
+}
+
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	sp_io::TestExternalities::new(Default::default())
 }
 
 /// Run test within test runtime.
 pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
-	sp_io::TestExternalities::new(Default::default()).execute_with(test)
+	new_test_ext().execute_with(test)
 }
 
 /// Initialize pallet and run test.
@@ -153,6 +287,28 @@ pub fn import_commitment(
 	)
 }
 
+/// Import given commitment in the aggregated form.
+///
+/// The signatures of the commitment are dropped, only the signers bitfield is kept.
+pub fn import_aggregated_commitment(
+	header: crate::mock_chain::HeaderAndCommitment,
+) -> sp_runtime::DispatchResult {
+	let commitment = header
+		.commitment
+		.expect("thou shall not call import_aggregated_commitment on header without commitment");
+	crate::Pallet::<TestRuntime>::submit_aggregated_commitment(
+		RuntimeOrigin::signed(1),
+		BridgedBeefyAggregatedCommitment::<TestRuntime, ()> {
+			signed_by: commitment.signatures.iter().map(Option::is_some).collect(),
+			commitment: commitment.commitment,
+			signature_accumulator: (),
+		},
+		header.validator_set,
+		Box::new(header.leaf),
+		header.leaf_proof,
+	)
+}
+
 pub fn validator_pairs(index: u32, count: u32) -> Vec<BeefyPair> {
 	(index..index + count)
 		.map(|index| {
@@ -1,9 +1,14 @@
 use crate::{
+	BridgedBeefyAggregateSignatureVerifier, BridgedBeefyAggregatedCommitment,
 	BridgedBeefyAuthorityId, BridgedBeefyAuthoritySet, BridgedBeefyAuthoritySetInfo,
-	BridgedBeefyMmrLeaf, BridgedBeefySignedCommitment, BridgedChain, BridgedMmrHash,
-	BridgedMmrHashing, BridgedMmrProof, Config, Error, LOG_TARGET,
+	BridgedBeefyCommitmentHasher, BridgedBeefyEquivocationProof, BridgedBeefyMmrLeaf,
+	BridgedBeefyProofOfPossession, BridgedBeefySignedCommitment, BridgedBlockNumber, BridgedChain,
+	BridgedMmrHash, BridgedMmrHashing, BridgedMmrProof, Config, Error, LOG_TARGET,
+};
+use bp_beefy::{
+	merkle_root, verify_mmr_leaves_proof, BeefyAggregateSignatureVerifier, BeefyAuthorityId,
+	Commitment, MmrDataOrHash,
 };
-use bp_beefy::{merkle_root, verify_mmr_leaves_proof, BeefyAuthorityId, MmrDataOrHash};
 use codec::Encode;
 use frame_support::ensure;
 use sp_runtime::traits::{Convert, Hash};
@@ -93,12 +98,50 @@ fn verify_signatures<T: Config<I>, I: 'static>(
 	Ok(())
 }
 
+fn verify_aggregate_signature<T: Config<I>, I: 'static>(
+	commitment: &BridgedBeefyAggregatedCommitment<T, I>,
+	authority_set: &BridgedBeefyAuthoritySet<T, I>,
+) -> Result<(), Error<T, I>> {
+	ensure!(
+		commitment.signed_by.len() == authority_set.len(),
+		Error::<T, I>::InvalidCommitmentSignaturesLen
+	);
+
+	// Ensure that the commitment was signed by enough authorities.
+	let signers = authority_set
+		.validators()
+		.iter()
+		.zip(commitment.signed_by.iter())
+		.filter_map(|(authority, signed)| signed.then(|| authority.clone()))
+		.collect::<Vec<_>>();
+	ensure!(
+		signers.len() >= signatures_required(authority_set.len()),
+		Error::<T, I>::NotEnoughCorrectSignatures
+	);
+
+	// All the signatures are checked at once.
+	let msg = commitment.commitment.encode();
+	if !BridgedBeefyAggregateSignatureVerifier::<T, I>::verify(
+		&signers,
+		&commitment.signature_accumulator,
+		&msg,
+	) {
+		log::debug!(
+			target: LOG_TARGET,
+			"Aggregated commitment contains incorrect signature of {} validators",
+			signers.len(),
+		);
+		return Err(Error::<T, I>::InvalidAggregateSignature)
+	}
+
+	Ok(())
+}
+
 /// Extract MMR root from commitment payload.
 fn extract_mmr_root<T: Config<I>, I: 'static>(
-	commitment: &BridgedBeefySignedCommitment<T, I>,
+	commitment: &Commitment<BridgedBlockNumber<T, I>>,
 ) -> Result<BridgedMmrHash<T, I>, Error<T, I>> {
 	commitment
-		.commitment
 		.payload
 		.get_decoded(&bp_beefy::MMR_ROOT_PAYLOAD_ID)
 		.ok_or(Error::MmrRootMissingFromCommitment)
@@ -122,7 +165,79 @@ pub(crate) fn verify_commitment<T: Config<I>, I: 'static>(
 	verify_authority_set(authority_set_info, authority_set)?;
 	verify_signatures(commitment, authority_set)?;
 
-	extract_mmr_root(commitment)
+	extract_mmr_root(&commitment.commitment)
+}
+
+pub(crate) fn verify_aggregated_commitment<T: Config<I>, I: 'static>(
+	commitment: &BridgedBeefyAggregatedCommitment<T, I>,
+	authority_set_info: &BridgedBeefyAuthoritySetInfo<T, I>,
+	authority_set: &BridgedBeefyAuthoritySet<T, I>,
+) -> Result<BridgedMmrHash<T, I>, Error<T, I>> {
+	// Ensure that the commitment is signed by the best known BEEFY validator set.
+	ensure!(
+		commitment.commitment.validator_set_id == authority_set_info.id,
+		Error::<T, I>::InvalidCommitmentValidatorSetId
+	);
+	ensure!(
+		commitment.signed_by.len() == authority_set_info.len as usize,
+		Error::<T, I>::InvalidCommitmentSignaturesLen
+	);
+
+	verify_authority_set(authority_set_info, authority_set)?;
+	verify_aggregate_signature(commitment, authority_set)?;
+
+	extract_mmr_root(&commitment.commitment)
+}
+
+pub(crate) fn verify_proofs_of_possession<T: Config<I>, I: 'static>(
+	authority_set_info: &BridgedBeefyAuthoritySetInfo<T, I>,
+	authority_set: &BridgedBeefyAuthoritySet<T, I>,
+	proofs: &[BridgedBeefyProofOfPossession<T, I>],
+) -> Result<(), Error<T, I>> {
+	verify_authority_set(authority_set_info, authority_set)?;
+	ensure!(proofs.len() == authority_set.len(), Error::<T, I>::InvalidProofsOfPossessionLen);
+
+	for (idx, (authority, proof)) in authority_set.validators().iter().zip(proofs).enumerate() {
+		if !BridgedBeefyAggregateSignatureVerifier::<T, I>::verify_proof_of_possession(
+			authority, proof,
+		) {
+			log::debug!(
+				target: LOG_TARGET,
+				"Invalid proof of possession of validator {} ({:?})",
+				idx,
+				authority,
+			);
+			return Err(Error::<T, I>::InvalidProofOfPossession)
+		}
+	}
+
+	Ok(())
+}
+
+pub(crate) fn verify_equivocation_proof<T: Config<I>, I: 'static>(
+	equivocation_proof: &BridgedBeefyEquivocationProof<T, I>,
+	authority_set_info: &BridgedBeefyAuthoritySetInfo<T, I>,
+	authority_set: &BridgedBeefyAuthoritySet<T, I>,
+) -> Result<(), Error<T, I>> {
+	ensure!(
+		equivocation_proof.set_id() == authority_set_info.id,
+		Error::<T, I>::InvalidCommitmentValidatorSetId
+	);
+	verify_authority_set(authority_set_info, authority_set)?;
+	ensure!(
+		authority_set.validators().contains(equivocation_proof.offender_id()),
+		Error::<T, I>::InvalidEquivocationProof
+	);
+
+	// The votes are checked using the individual signatures of the validator.
+	ensure!(
+		bp_beefy::check_double_voting_proof::<_, _, BridgedBeefyCommitmentHasher<T, I>>(
+			equivocation_proof
+		),
+		Error::<T, I>::InvalidEquivocationProof
+	);
+
+	Ok(())
 }
 
 /// Verify MMR proof of given leaf.
@@ -263,6 +378,124 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn submit_aggregated_commitment_checks_signatures() {
+		run_test_with_initialize(20, || {
+			// Fails when the proofs of possession of the authority set aren't verified.
+			let header = ChainBuilder::new(20).append_finalized_header().to_header();
+			assert_noop!(
+				import_aggregated_commitment(header),
+				Error::<TestRuntime, ()>::AuthoritySetPossessionNotProven,
+			);
+			ProvenAuthoritySetId::<TestRuntime>::put(0);
+
+			// Fails when there aren't enough signers.
+			let mut header = ChainBuilder::new(20).append_finalized_header().to_header();
+			header.customize_signatures(|signatures| {
+				let first_signature_idx = signatures.iter().position(Option::is_some).unwrap();
+				signatures[first_signature_idx] = None;
+			});
+			assert_noop!(
+				import_aggregated_commitment(header),
+				Error::<TestRuntime, ()>::NotEnoughCorrectSignatures,
+			);
+
+			// Fails when the bridged chain doesn't support aggregated signatures.
+			let header = ChainBuilder::new(20).append_finalized_header().to_header();
+			assert_noop!(
+				import_aggregated_commitment(header),
+				Error::<TestRuntime, ()>::InvalidAggregateSignature,
+			);
+		});
+	}
+
+	#[test]
+	#[cfg(feature = "bls-experimental")]
+	fn verify_aggregated_commitment_works() {
+		use crate::mock::bls::*;
+		use sp_core::Pair;
+
+		run_test(|| {
+			let pairs = validator_pairs(0, 4);
+			let validators: Vec<_> = pairs.iter().map(|pair| pair.public()).collect();
+			let authority_set_info = authority_set_info(0, &validators);
+			let authority_set = TestBlsBridgedValidatorSet::new(validators, 0).unwrap();
+
+			let mmr_root = sp_core::H256::repeat_byte(42);
+			let commitment = Commitment {
+				payload: BeefyPayload::from_single_entry(MMR_ROOT_PAYLOAD_ID, mmr_root.encode()),
+				block_number: 1,
+				validator_set_id: 0,
+			};
+			let verify = |commitment| {
+				verify_aggregated_commitment::<TestRuntime, BlsInstance>(
+					&commitment,
+					&authority_set_info,
+					&authority_set,
+				)
+			};
+
+			// Works with enough signers.
+			let aggregated = sign_aggregated_commitment(
+				commitment.clone(),
+				&pairs,
+				vec![true, false, true, true],
+			);
+			assert!(matches!(verify(aggregated.clone()), Ok(root) if root == mmr_root));
+
+			// Fails if the signers bitfield doesn't match the aggregated signature.
+			let mut wrong_signers = aggregated.clone();
+			wrong_signers.signed_by = vec![true, true, true, true];
+			assert!(matches!(verify(wrong_signers), Err(Error::InvalidAggregateSignature)));
+
+			// Fails if the signed commitment differs.
+			let mut wrong_commitment = aggregated;
+			wrong_commitment.commitment.block_number = 2;
+			assert!(matches!(verify(wrong_commitment), Err(Error::InvalidAggregateSignature)));
+
+			// Fails when there aren't enough signers.
+			let aggregated =
+				sign_aggregated_commitment(commitment, &pairs, vec![true, false, false, true]);
+			assert!(matches!(verify(aggregated), Err(Error::NotEnoughCorrectSignatures)));
+		});
+	}
+
+	#[test]
+	#[cfg(feature = "bls-experimental")]
+	fn verify_proofs_of_possession_works() {
+		use crate::mock::bls::*;
+		use sp_core::{crypto::Wraps, Pair};
+
+		run_test(|| {
+			let pairs = validator_pairs(0, 4);
+			let validators: Vec<_> = pairs.iter().map(|pair| pair.public()).collect();
+			let authority_set_info = authority_set_info(0, &validators);
+			let authority_set = TestBlsBridgedValidatorSet::new(validators, 0).unwrap();
+			let proofs: Vec<_> = pairs
+				.iter()
+				.map(|pair| pair.as_inner_ref().generate_proof_of_possession())
+				.collect();
+			let verify = |proofs: &[_]| {
+				verify_proofs_of_possession::<TestRuntime, BlsInstance>(
+					&authority_set_info,
+					&authority_set,
+					proofs,
+				)
+			};
+
+			// Works with the proofs of all validators.
+			assert!(verify(&proofs).is_ok());
+
+			// Fails if a proof doesn't match the validator.
+			let mut swapped_proofs = proofs.clone();
+			swapped_proofs.swap(0, 1);
+			assert!(matches!(verify(&swapped_proofs), Err(Error::InvalidProofOfPossession)));
+
+			// Fails if a proof is missing.
+			assert!(matches!(verify(&proofs[1..]), Err(Error::InvalidProofsOfPossessionLen)));
+		});
+	}
+
 	#[test]
 	fn submit_commitment_checks_mmr_proof() {
 		run_test_with_initialize(1, || {
@@ -0,0 +1,198 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for pallet_bridge_beefy
+//!
+//! THE WEIGHTS WERE NOT GENERATED USING THE BENCHMARK CLI. The benchmarks of the pallet were
+//! executed natively over the same `BenchmarkingState` that the CLI is using, with the
+//! `bls-experimental` instance of the pallet mock runtime. The signature verification is
+//! performed by host functions, so the native execution time is representative of the wasm one.
+//! The estimated proof sizes are computed the way the CLI computes them.
+//!
+//! DATE: 2026-10-18, STEPS: `9`, REPEAT: `10`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `vm`, CPU: `Intel(R) Xeon(R) Processor`
+//!
+//! The file must be regenerated with the benchmark CLI using one of the Bridge testnets.
+
+#![allow(clippy::all)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for pallet_bridge_beefy.
+pub trait WeightInfo {
+	fn submit_aggregated_commitment(n: u32) -> Weight;
+	fn submit_proofs_of_possession(n: u32) -> Weight;
+	fn report_equivocation(n: u32) -> Weight;
+}
+
+/// Weights for `pallet_bridge_beefy` that are generated using the pallet mock runtime.
+///
+/// Those weights are test only and must never be used in production.
+pub struct BridgeWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
+	/// Storage: `BeefyBls::PalletOperatingMode` (r:1 w:0)
+	/// Proof: `BeefyBls::PalletOperatingMode` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::RequestCount` (r:1 w:1)
+	/// Proof: `BeefyBls::RequestCount` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BeefyBls::ImportedCommitmentsInfo` (r:1 w:1)
+	/// Proof: `BeefyBls::ImportedCommitmentsInfo` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::CurrentAuthoritySetInfo` (r:1 w:1)
+	/// Proof: `BeefyBls::CurrentAuthoritySetInfo` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::ProvenAuthoritySetId` (r:1 w:0)
+	/// Proof: `BeefyBls::ProvenAuthoritySetId` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::ImportedBlockNumbers` (r:1 w:1)
+	/// Proof: `BeefyBls::ImportedBlockNumbers` (`max_values`: None, `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::ImportedCommitments` (r:0 w:2)
+	/// Proof: `BeefyBls::ImportedCommitments` (`max_values`: None, `max_size`: None, mode:
+	/// `Measured`) The range of component `n` is `[1, 64]`.
+	fn submit_aggregated_commitment(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `169`
+		//  Estimated: `3634`
+		// Minimum execution time: 3_377_892_000 picoseconds.
+		Weight::from_parts(1_167_934_884, 3634)
+			// Standard Error: 23_463_164
+			.saturating_add(Weight::from_parts(546_687_094, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `BeefyBls::PalletOperatingMode` (r:1 w:0)
+	/// Proof: `BeefyBls::PalletOperatingMode` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::ImportedCommitmentsInfo` (r:1 w:0)
+	/// Proof: `BeefyBls::ImportedCommitmentsInfo` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::CurrentAuthoritySetInfo` (r:1 w:0)
+	/// Proof: `BeefyBls::CurrentAuthoritySetInfo` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::ProvenAuthoritySetId` (r:1 w:1)
+	/// Proof: `BeefyBls::ProvenAuthoritySetId` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::EquivocatingAuthoritySetId` (r:1 w:0)
+	/// Proof: `BeefyBls::EquivocatingAuthoritySetId` (`max_values`: Some(1), `max_size`: None,
+	/// mode: `Measured`) The range of component `n` is `[1, 64]`.
+	fn submit_proofs_of_possession(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `108`
+		//  Estimated: `1593`
+		// Minimum execution time: 3_497_149_000 picoseconds.
+		Weight::from_parts(3_532_398_000, 1593)
+			// Standard Error: 82_834_163
+			.saturating_add(Weight::from_parts(4_549_445_684, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `BeefyBls::PalletOperatingMode` (r:1 w:0)
+	/// Proof: `BeefyBls::PalletOperatingMode` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::ImportedCommitmentsInfo` (r:1 w:0)
+	/// Proof: `BeefyBls::ImportedCommitmentsInfo` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::CurrentAuthoritySetInfo` (r:1 w:0)
+	/// Proof: `BeefyBls::CurrentAuthoritySetInfo` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::EquivocatingAuthoritySetId` (r:1 w:1)
+	/// Proof: `BeefyBls::EquivocatingAuthoritySetId` (`max_values`: Some(1), `max_size`: None,
+	/// mode: `Measured`) Storage: `BeefyBls::ProvenAuthoritySetId` (r:0 w:1)
+	/// Proof: `BeefyBls::ProvenAuthoritySetId` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) The range of component `n` is `[1, 64]`.
+	fn report_equivocation(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `135`
+		//  Estimated: `1620`
+		// Minimum execution time: 4_414_570_000 picoseconds.
+		Weight::from_parts(6_140_631_729, 1620)
+			// Standard Error: 7_525_511
+			.saturating_add(Weight::from_parts(14_663_108, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: `BeefyBls::PalletOperatingMode` (r:1 w:0)
+	/// Proof: `BeefyBls::PalletOperatingMode` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::RequestCount` (r:1 w:1)
+	/// Proof: `BeefyBls::RequestCount` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BeefyBls::ImportedCommitmentsInfo` (r:1 w:1)
+	/// Proof: `BeefyBls::ImportedCommitmentsInfo` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::CurrentAuthoritySetInfo` (r:1 w:1)
+	/// Proof: `BeefyBls::CurrentAuthoritySetInfo` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::ProvenAuthoritySetId` (r:1 w:0)
+	/// Proof: `BeefyBls::ProvenAuthoritySetId` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::ImportedBlockNumbers` (r:1 w:1)
+	/// Proof: `BeefyBls::ImportedBlockNumbers` (`max_values`: None, `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::ImportedCommitments` (r:0 w:2)
+	/// Proof: `BeefyBls::ImportedCommitments` (`max_values`: None, `max_size`: None, mode:
+	/// `Measured`) The range of component `n` is `[1, 64]`.
+	fn submit_aggregated_commitment(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `169`
+		//  Estimated: `3634`
+		// Minimum execution time: 3_377_892_000 picoseconds.
+		Weight::from_parts(1_167_934_884, 3634)
+			// Standard Error: 23_463_164
+			.saturating_add(Weight::from_parts(546_687_094, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `BeefyBls::PalletOperatingMode` (r:1 w:0)
+	/// Proof: `BeefyBls::PalletOperatingMode` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::ImportedCommitmentsInfo` (r:1 w:0)
+	/// Proof: `BeefyBls::ImportedCommitmentsInfo` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::CurrentAuthoritySetInfo` (r:1 w:0)
+	/// Proof: `BeefyBls::CurrentAuthoritySetInfo` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::ProvenAuthoritySetId` (r:1 w:1)
+	/// Proof: `BeefyBls::ProvenAuthoritySetId` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::EquivocatingAuthoritySetId` (r:1 w:0)
+	/// Proof: `BeefyBls::EquivocatingAuthoritySetId` (`max_values`: Some(1), `max_size`: None,
+	/// mode: `Measured`) The range of component `n` is `[1, 64]`.
+	fn submit_proofs_of_possession(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `108`
+		//  Estimated: `1593`
+		// Minimum execution time: 3_497_149_000 picoseconds.
+		Weight::from_parts(3_532_398_000, 1593)
+			// Standard Error: 82_834_163
+			.saturating_add(Weight::from_parts(4_549_445_684, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `BeefyBls::PalletOperatingMode` (r:1 w:0)
+	/// Proof: `BeefyBls::PalletOperatingMode` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::ImportedCommitmentsInfo` (r:1 w:0)
+	/// Proof: `BeefyBls::ImportedCommitmentsInfo` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::CurrentAuthoritySetInfo` (r:1 w:0)
+	/// Proof: `BeefyBls::CurrentAuthoritySetInfo` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) Storage: `BeefyBls::EquivocatingAuthoritySetId` (r:1 w:1)
+	/// Proof: `BeefyBls::EquivocatingAuthoritySetId` (`max_values`: Some(1), `max_size`: None,
+	/// mode: `Measured`) Storage: `BeefyBls::ProvenAuthoritySetId` (r:0 w:1)
+	/// Proof: `BeefyBls::ProvenAuthoritySetId` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`) The range of component `n` is `[1, 64]`.
+	fn report_equivocation(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `135`
+		//  Estimated: `1620`
+		// Minimum execution time: 4_414_570_000 picoseconds.
+		Weight::from_parts(6_140_631_729, 1620)
+			// Standard Error: 7_525_511
+			.saturating_add(Weight::from_parts(14_663_108, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -25,14 +25,23 @@ pub use pallet_mmr::{
 	primitives::{DataOrHash as MmrDataOrHash, LeafProof as MmrProof},
 	verify_leaves_proof as verify_mmr_leaves_proof,
 };
+#[cfg(feature = "bls-experimental")]
+pub use sp_consensus_beefy::ecdsa_bls_crypto::{
+	aggregate_signatures as aggregate_ecdsa_bls_validator_signatures,
+	AggregateSignature as EcdsaBlsValidatorAggregateSignature, AuthorityId as EcdsaBlsValidatorId,
+	AuthoritySignature as EcdsaBlsValidatorSignature,
+	ProofOfPossession as EcdsaBlsValidatorProofOfPossession,
+};
 pub use sp_consensus_beefy::{
+	check_double_voting_proof,
 	ecdsa_crypto::{
 		AuthorityId as EcdsaValidatorId, AuthoritySignature as EcdsaValidatorSignature,
 	},
 	known_payloads::MMR_ROOT_ID as MMR_ROOT_PAYLOAD_ID,
 	mmr::{BeefyAuthoritySet, MmrLeafVersion},
-	BeefyAuthorityId, Commitment, Payload as BeefyPayload, SignedCommitment, ValidatorSet,
-	ValidatorSetId, BEEFY_ENGINE_ID,
+	witness::SignedCommitmentWitness,
+	BeefyAuthorityId, Commitment, DoubleVotingProof, Payload as BeefyPayload, SignedCommitment,
+	ValidatorSet, ValidatorSetId, VoteMessage, BEEFY_ENGINE_ID,
 };
 
 use bp_runtime::{BasicOperatingMode, BlockNumberOf, Chain, HashOf};
@@ -91,6 +100,70 @@ pub trait ChainWithBeefy: Chain {
 	/// Corresponds to the `BeefyAuthorityToMerkleLeaf` field of the `pallet-beefy-mmr`
 	/// configuration.
 	type AuthorityIdToMerkleLeaf: Convert<Self::AuthorityId, Vec<u8>>;
+
+	/// A way to verify aggregated signatures of BEEFY commitments.
+	///
+	/// Use `()` if the chain validators are not producing aggregatable signatures.
+	type AggregateSignatureVerifier: BeefyAggregateSignatureVerifier<Self::AuthorityId>;
+}
+
+/// Verifier of BEEFY commitments aggregated signatures.
+pub trait BeefyAggregateSignatureVerifier<AuthorityId> {
+	/// Aggregate signature of a set of validators.
+	type AggregateSignature: Parameter;
+	/// Proof of possession of the secret key of a validator.
+	type ProofOfPossession: Parameter;
+
+	/// Verify the aggregate `signature` of the same `msg` by all the `signers`.
+	///
+	/// The check is secure only if the proofs of possession of all the `signers` have been
+	/// verified beforehand, otherwise the scheme is vulnerable to rogue key attacks.
+	fn verify(signers: &[AuthorityId], signature: &Self::AggregateSignature, msg: &[u8]) -> bool;
+
+	/// Verify the `proof` of possession of the secret key of `authority`.
+	fn verify_proof_of_possession(authority: &AuthorityId, proof: &Self::ProofOfPossession)
+		-> bool;
+}
+
+/// Aggregated signatures are not supported, so they are always rejected.
+impl<AuthorityId> BeefyAggregateSignatureVerifier<AuthorityId> for () {
+	type AggregateSignature = ();
+	type ProofOfPossession = ();
+
+	fn verify(_signers: &[AuthorityId], _signature: &(), _msg: &[u8]) -> bool {
+		false
+	}
+
+	fn verify_proof_of_possession(_authority: &AuthorityId, _proof: &()) -> bool {
+		false
+	}
+}
+
+/// Verifier of the aggregated BLS components of `(ECDSA,BLS12-381)` paired signatures.
+///
+/// A single BLS fast aggregate verification is performed for the whole set of signers.
+#[cfg(feature = "bls-experimental")]
+pub struct EcdsaBlsAggregateSignatureVerifier;
+
+#[cfg(feature = "bls-experimental")]
+impl BeefyAggregateSignatureVerifier<EcdsaBlsValidatorId> for EcdsaBlsAggregateSignatureVerifier {
+	type AggregateSignature = EcdsaBlsValidatorAggregateSignature;
+	type ProofOfPossession = EcdsaBlsValidatorProofOfPossession;
+
+	fn verify(
+		signers: &[EcdsaBlsValidatorId],
+		signature: &Self::AggregateSignature,
+		msg: &[u8],
+	) -> bool {
+		sp_consensus_beefy::ecdsa_bls_crypto::verify_aggregate(signers, signature, msg)
+	}
+
+	fn verify_proof_of_possession(
+		authority: &EcdsaBlsValidatorId,
+		proof: &Self::ProofOfPossession,
+	) -> bool {
+		sp_consensus_beefy::AggregatableAuthorityId::verify_proof_of_possession(authority, proof)
+	}
 }
 
 /// BEEFY validator id used by given Substrate chain.
@@ -105,6 +178,25 @@ pub type BeefyValidatorSignatureOf<C> =
 /// Signed BEEFY commitment used by given Substrate chain.
 pub type BeefySignedCommitmentOf<C> =
 	SignedCommitment<BlockNumberOf<C>, BeefyValidatorSignatureOf<C>>;
+/// Aggregate signature of BEEFY validators used by given Substrate chain.
+pub type BeefyAggregateSignatureOf<C> =
+	<<C as ChainWithBeefy>::AggregateSignatureVerifier as BeefyAggregateSignatureVerifier<
+		BeefyAuthorityIdOf<C>,
+	>>::AggregateSignature;
+/// BEEFY commitment with aggregated validators signature used by given Substrate chain.
+///
+/// The `signed_by` bitfield marks the validators whose signatures have been aggregated.
+pub type BeefyAggregatedCommitmentOf<C> =
+	SignedCommitmentWitness<BlockNumberOf<C>, BeefyAggregateSignatureOf<C>>;
+/// Proof of possession of the secret key of a BEEFY validator used by given Substrate chain.
+pub type BeefyProofOfPossessionOf<C> =
+	<<C as ChainWithBeefy>::AggregateSignatureVerifier as BeefyAggregateSignatureVerifier<
+		BeefyAuthorityIdOf<C>,
+	>>::ProofOfPossession;
+/// Proof of a BEEFY validator of given Substrate chain voting for two different commitments
+/// in the same round.
+pub type BeefyEquivocationProofOf<C> =
+	DoubleVotingProof<BlockNumberOf<C>, BeefyAuthorityIdOf<C>, BeefyValidatorSignatureOf<C>>;
 /// Hash algorithm, used to compute the digest of the BEEFY commitment before signing it.
 pub type BeefyCommitmentHasher<C> = <C as ChainWithBeefy>::CommitmentHasher;
 /// Hash algorithm used in Beefy MMR construction by given Substrate chain.
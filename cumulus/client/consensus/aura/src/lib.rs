@@ -169,6 +169,7 @@ where
 				block_proposal_slot_portion,
 				max_block_proposal_slot_portion,
 				compatibility_mode: sc_consensus_aura::CompatibilityMode::None,
+				slashing_protection: None,
 			},
 		);
 
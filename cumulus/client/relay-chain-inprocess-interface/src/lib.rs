@@ -385,6 +385,7 @@ fn build_polkadot_full_node(
 			prepare_workers_hard_max_num: None,
 			prepare_workers_soft_max_num: None,
 			enable_approval_voting_parallel: false,
+			slashing_protection_db: None,
 			keep_finalized_for: None,
 		},
 	)?;
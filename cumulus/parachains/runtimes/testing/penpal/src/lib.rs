@@ -153,6 +153,7 @@ pub type TxExtension = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
+	pallet_revive::evm::runtime::ApplyAuthorizations<Runtime>,
 	frame_system::WeightReclaim<Runtime>,
 );
 
@@ -174,6 +175,7 @@ impl EthExtra for EthExtraImpl {
 			frame_system::CheckNonce::<Runtime>::from(nonce),
 			frame_system::CheckWeight::<Runtime>::new(),
 			pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(tip, None),
+			pallet_revive::evm::runtime::ApplyAuthorizations::<Runtime>::new(),
 			frame_system::WeightReclaim::<Runtime>::new(),
 		)
 			.into()
@@ -855,6 +857,7 @@ impl pallet_revive::Config for Runtime {
 	type ChainId = ConstU64<420_420_999>;
 	type NativeToEthRatio = ConstU32<1_000_000>; // 10^(18 - 12) Eth is 10^18, Native is 10^12.
 	type EthGasEncoder = ();
+	type EthGasSchedule = ();
 	type FindAuthor = <Runtime as pallet_authorship::Config>::FindAuthor;
 }
 
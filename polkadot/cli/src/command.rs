@@ -213,6 +213,13 @@ where
 			})
 			.flatten();
 
+		let slashing_protection_db = config.role.is_authority().then(|| {
+			cli.run
+				.base
+				.slashing_protection_params
+				.slashing_protection_db(&config.data_path)
+		});
+
 		let database_source = config.database.clone();
 		let task_manager = polkadot_service::build_full(
 			config,
@@ -236,6 +243,7 @@ where
 				prepare_workers_soft_max_num: cli.run.prepare_workers_soft_max_num,
 				enable_approval_voting_parallel: cli.run.enable_approval_voting_parallel,
 				keep_finalized_for: cli.run.keep_finalized_for,
+				slashing_protection_db,
 			},
 		)
 		.map(|full| full.task_manager)?;
@@ -326,6 +334,13 @@ pub fn run() -> Result<()> {
 				Ok((cmd.run(client, config.chain_spec).map_err(Error::SubstrateCli), task_manager))
 			})?)
 		},
+		Some(Subcommand::ExportSlashingProtection(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			Ok(runner.sync_run(|mut config| {
+				let (client, _, _, _) = polkadot_service::new_chain_ops(&mut config)?;
+				cmd.run(client, &config.data_path).map_err(Error::SubstrateCli)
+			})?)
+		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			let chain_spec = &runner.config().chain_spec;
@@ -338,6 +353,13 @@ pub fn run() -> Result<()> {
 				Ok((cmd.run(client, import_queue).map_err(Error::SubstrateCli), task_manager))
 			})?)
 		},
+		Some(Subcommand::ImportSlashingProtection(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			Ok(runner.sync_run(|mut config| {
+				let (client, _, _, _) = polkadot_service::new_chain_ops(&mut config)?;
+				cmd.run(client, &config.data_path).map_err(Error::SubstrateCli)
+			})?)
+		},
 		Some(Subcommand::PurgeChain(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			Ok(runner.sync_run(|config| cmd.run(config.database))?)
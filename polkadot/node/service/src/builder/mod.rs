@@ -52,6 +52,7 @@ use sc_client_api::Backend;
 use sc_network::config::FullNetworkConfiguration;
 use sc_network_sync::WarpSyncConfig;
 use sc_service::{Configuration, RpcHandlers, TaskManager};
+use sc_slashing_protection::SlashingProtection;
 use sc_sysinfo::Metric;
 use sc_telemetry::TelemetryWorkerHandle;
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
@@ -92,6 +93,9 @@ pub struct NewFullParams<OverseerGenerator: OverseerGen> {
 	pub hwbench: Option<sc_sysinfo::HwBench>,
 	/// Enable approval voting processing in parallel.
 	pub enable_approval_voting_parallel: bool,
+	/// Path of the slashing protection database recording the messages signed by the local
+	/// validator keys, if any.
+	pub slashing_protection_db: Option<std::path::PathBuf>,
 }
 
 /// Completely built polkadot node service.
@@ -203,6 +207,7 @@ where
 					prepare_workers_hard_max_num,
 					keep_finalized_for,
 					enable_approval_voting_parallel,
+					slashing_protection_db,
 				},
 			overseer_connector,
 			partial_components:
@@ -253,6 +258,10 @@ where
 		let genesis_hash = client.chain_info().genesis_hash;
 		let peer_store_handle = net_config.peer_store_handle();
 
+		let slashing_protection = slashing_protection_db
+			.map(|path| SlashingProtection::open(path, genesis_hash.as_ref()).map(Arc::new))
+			.transpose()?;
+
 		let prometheus_registry = config.prometheus_registry().cloned();
 		let metrics = Network::register_notification_metrics(
 			config.prometheus_config.as_ref().map(|cfg| &cfg.registry),
@@ -709,6 +718,7 @@ where
 				block_proposal_slot_portion: sc_consensus_babe::SlotProportion::new(2f32 / 3f32),
 				max_block_proposal_slot_portion: None,
 				telemetry: telemetry.as_ref().map(|x| x.handle()),
+				slashing_protection: slashing_protection.clone(),
 			};
 
 			let babe = sc_consensus_babe::start_babe(babe_config)?;
@@ -745,6 +755,7 @@ where
 				links: beefy_links,
 				on_demand_justifications_handler: beefy_on_demand_justifications_handler,
 				is_authority: role.is_authority(),
+				slashing_protection: slashing_protection.clone(),
 			};
 
 			let gadget = sc_consensus_beefy::start_beefy_gadget::<
@@ -824,6 +835,7 @@ where
 				offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 					transaction_pool.clone(),
 				),
+				slashing_protection: slashing_protection.clone(),
 			};
 
 			task_manager.spawn_essential_handle().spawn_blocking(
@@ -65,6 +65,7 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		trie_cache_maximum_size: Some(64 * 1024 * 1024),
 		state_pruning: Some(PruningMode::ArchiveAll),
 		blocks_pruning: BlocksPruning::KeepAll,
+		flat_state: false,
 		chain_spec: spec,
 		executor: ExecutorConfiguration::default(),
 		rpc: RpcConfiguration {
@@ -101,6 +102,7 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		node_cli::service::new_full_base::<sc_network::NetworkWorker<_, _>>(
 			config,
 			None,
+			None,
 			false,
 			|_, _| (),
 		)
@@ -89,9 +89,21 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export a snapshot of the latest finalized state.
+	ExportSnapshot(sc_cli::ExportSnapshotCmd),
+
+	/// Export the slashing protection database.
+	ExportSlashingProtection(sc_cli::ExportSlashingProtectionCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
+	/// Bootstrap an empty database from a snapshot.
+	ImportSnapshot(sc_cli::ImportSnapshotCmd),
+
+	/// Import a record into the slashing protection database.
+	ImportSlashingProtection(sc_cli::ImportSlashingProtectionCmd),
+
 	/// Remove the whole chain.
 	PurgeChain(sc_cli::PurgeChainCmd),
 
@@ -100,4 +112,8 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Check and repair the database of a stopped node.
+	#[command(subcommand)]
+	Db(sc_cli::DbSubcommand),
 }
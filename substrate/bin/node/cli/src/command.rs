@@ -199,6 +199,29 @@ pub fn run() -> Result<()> {
 				Ok((cmd.run(client, config.chain_spec), task_manager))
 			})
 		},
+		Some(Subcommand::ExportSnapshot(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let PartialComponents { client, backend, task_manager, other, .. } =
+					new_partial(&config, None)?;
+				let (_, import_setup, ..) = other;
+				let warp_sync = Arc::new(sc_consensus_grandpa::warp_proof::NetworkProvider::new(
+					backend,
+					import_setup.1.shared_authority_set().clone(),
+					Vec::default(),
+				));
+				let genesis_authorities =
+					sc_consensus_grandpa::GenesisAuthoritySetProvider::get(&client)?;
+				Ok((cmd.run(client, warp_sync, genesis_authorities, config.database), task_manager))
+			})
+		},
+		Some(Subcommand::ExportSlashingProtection(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, .. } = new_partial(&config, None)?;
+				cmd.run(client, &config.data_path)
+			})
+		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -207,6 +230,28 @@ pub fn run() -> Result<()> {
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
+		Some(Subcommand::ImportSnapshot(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let PartialComponents {
+					client, backend, task_manager, import_queue, other, ..
+				} = new_partial(&config, None)?;
+				let (_, import_setup, ..) = other;
+				let warp_sync = Arc::new(sc_consensus_grandpa::warp_proof::NetworkProvider::new(
+					backend,
+					import_setup.1.shared_authority_set().clone(),
+					Vec::default(),
+				));
+				Ok((cmd.run(client, import_queue, warp_sync), task_manager))
+			})
+		},
+		Some(Subcommand::ImportSlashingProtection(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, .. } = new_partial(&config, None)?;
+				cmd.run(client, &config.data_path)
+			})
+		},
 		Some(Subcommand::PurgeChain(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.database))
@@ -228,5 +273,9 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run::<Block>(&config))
 		},
+		Some(Subcommand::Db(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run::<Block>(&config))
+		},
 	}
 }
@@ -46,7 +46,10 @@ use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::ProvideRuntimeApi;
 use sp_core::crypto::Pair;
 use sp_runtime::{generic, traits::Block as BlockT, SaturatedConversion};
-use std::{path::Path, sync::Arc};
+use std::{
+	path::{Path, PathBuf},
+	sync::Arc,
+};
 
 /// Host functions required for kitchensink runtime and Substrate node.
 #[cfg(not(feature = "runtime-benchmarks"))]
@@ -137,6 +140,7 @@ pub fn create_extrinsic(
 					kitchensink_runtime::Runtime,
 				>::from(tip, None),
 			),
+			pallet_revive::evm::runtime::ApplyAuthorizations::<kitchensink_runtime::Runtime>::new(),
 			frame_metadata_hash_extension::CheckMetadataHash::new(false),
 			frame_system::WeightReclaim::<kitchensink_runtime::Runtime>::new(),
 		);
@@ -153,6 +157,7 @@ pub fn create_extrinsic(
 			(),
 			(),
 			(),
+			(),
 			None,
 			(),
 		),
@@ -397,9 +402,13 @@ pub struct NewFullBase {
 }
 
 /// Creates a full service from the configuration.
+///
+/// The messages signed by the local validator keys are recorded in the slashing protection
+/// database stored at `slashing_protection_db`, if any.
 pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	config: Configuration,
 	mixnet_config: Option<sc_mixnet::Config>,
+	slashing_protection_db: Option<PathBuf>,
 	disable_hardware_benchmarks: bool,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<
@@ -456,6 +465,14 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	let genesis_hash = client.block_hash(0).ok().flatten().expect("Genesis block exists; qed");
 	let peer_store_handle = net_config.peer_store_handle();
 
+	let slashing_protection = slashing_protection_db
+		.map(|path| {
+			sc_slashing_protection::SlashingProtection::open(path, genesis_hash.as_ref())
+				.map(Arc::new)
+		})
+		.transpose()
+		.map_err(|e| ServiceError::Application(e.into()))?;
+
 	let grandpa_protocol_name = grandpa::protocol_standard_name(&genesis_hash, &config.chain_spec);
 	let (grandpa_protocol_config, grandpa_notification_service) =
 		grandpa::grandpa_peers_set_config::<_, N>(
@@ -631,6 +648,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 			block_proposal_slot_portion: SlotProportion::new(0.5),
 			max_block_proposal_slot_portion: None,
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
+			slashing_protection: slashing_protection.clone(),
 		};
 
 		let babe = sc_consensus_babe::start_babe(babe_config)?;
@@ -698,6 +716,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		links: beefy_links,
 		on_demand_justifications_handler: beefy_on_demand_justifications_handler,
 		is_authority: role.is_authority(),
+		slashing_protection: slashing_protection.clone(),
 	};
 
 	let beefy_gadget = beefy::start_beefy_gadget::<_, _, _, _, _, _, _, _>(beefy_params);
@@ -749,6 +768,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 			prometheus_registry: prometheus_registry.clone(),
 			shared_voter_state,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(transaction_pool.clone()),
+			slashing_protection: slashing_protection.clone(),
 		};
 
 		// the GRANDPA voter task is considered infallible, i.e.
@@ -816,6 +836,10 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 /// Builds a new service for a full client.
 pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceError> {
 	let mixnet_config = cli.mixnet_params.config(config.role.is_authority());
+	let slashing_protection_db = config
+		.role
+		.is_authority()
+		.then(|| cli.run.slashing_protection_params.slashing_protection_db(&config.data_path));
 	let database_path = config.database.path().map(Path::to_path_buf);
 
 	let task_manager = match config.network.network_backend.unwrap_or_default() {
@@ -823,6 +847,7 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 			let task_manager = new_full_base::<sc_network::NetworkWorker<_, _>>(
 				config,
 				mixnet_config,
+				slashing_protection_db,
 				cli.no_hardware_benchmarks,
 				|_, _| (),
 			)
@@ -833,6 +858,7 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 			let task_manager = new_full_base::<sc_network::Litep2pNetworkBackend>(
 				config,
 				mixnet_config,
+				slashing_protection_db,
 				cli.no_hardware_benchmarks,
 				|_, _| (),
 			)
@@ -920,6 +946,7 @@ mod tests {
 					new_full_base::<sc_network::NetworkWorker<_, _>>(
 						config,
 						None,
+						None,
 						false,
 						|block_import: &sc_consensus_babe::BabeBlockImport<Block, _, _>,
 						 babe_link: &sc_consensus_babe::BabeLink<Block>| {
@@ -1062,6 +1089,7 @@ mod tests {
 				let tx_payment = pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
 					pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::from(0, None),
 				);
+				let apply_authorizations = pallet_revive::evm::runtime::ApplyAuthorizations::new();
 				let weight_reclaim = frame_system::WeightReclaim::new();
 				let metadata_hash = frame_metadata_hash_extension::CheckMetadataHash::new(false);
 				let tx_ext: TxExtension = (
@@ -1073,6 +1101,7 @@ mod tests {
 					check_nonce,
 					check_weight,
 					tx_payment,
+					apply_authorizations,
 					metadata_hash,
 					weight_reclaim,
 				);
@@ -1088,6 +1117,7 @@ mod tests {
 						(),
 						(),
 						(),
+						(),
 						None,
 						(),
 					),
@@ -1121,6 +1151,7 @@ mod tests {
 					new_full_base::<sc_network::NetworkWorker<_, _>>(
 						config,
 						None,
+						None,
 						false,
 						|_, _| (),
 					)?;
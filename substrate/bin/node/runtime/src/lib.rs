@@ -1486,6 +1486,7 @@ impl pallet_revive::Config for Runtime {
 	type ChainId = ConstU64<420_420_420>;
 	type NativeToEthRatio = ConstU32<1_000_000>; // 10^(18 - 12) Eth is 10^18, Native is 10^12.
 	type EthGasEncoder = ();
+	type EthGasSchedule = ();
 	type FindAuthor = <Runtime as pallet_authorship::Config>::FindAuthor;
 }
 
@@ -1550,6 +1551,7 @@ where
 					tip, None,
 				),
 			),
+			pallet_revive::evm::runtime::ApplyAuthorizations::<Runtime>::new(),
 			frame_metadata_hash_extension::CheckMetadataHash::new(false),
 			frame_system::WeightReclaim::<Runtime>::new(),
 		);
@@ -2368,6 +2370,23 @@ impl pallet_mixnet::Config for Runtime {
 	type MinMixnodes = ConstU32<7>; // Low to allow small testing networks
 }
 
+parameter_types! {
+	pub const AnonymousActionPriority: TransactionPriority = StakingUnsignedPriority::get() - 2;
+}
+
+impl pallet_anonymous_membership::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AddOrigin = EnsureRoot<AccountId>;
+	type RemoveOrigin = EnsureRoot<AccountId>;
+	type ContextOrigin = EnsureRoot<AccountId>;
+	type MaxMembers = ConstU32<1024>;
+	type MaxMessageLen = ConstU32<256>;
+	type UnsignedPriority = AnonymousActionPriority;
+	type UnsignedLongevity = ConstU64<64>;
+	type OnAction = ();
+	type WeightInfo = pallet_anonymous_membership::weights::SubstrateWeight<Runtime>;
+}
+
 /// Dynamic parameters that can be changed at runtime through the
 /// `pallet_parameters::set_parameter`.
 #[dynamic_params(RuntimeParameters, pallet_parameters::Parameters::<Runtime>)]
@@ -2770,6 +2789,9 @@ mod runtime {
 
 	#[runtime::pallet_index(89)]
 	pub type MetaTx = pallet_meta_tx::Pallet<Runtime>;
+
+	#[runtime::pallet_index(90)]
+	pub type AnonymousMembership = pallet_anonymous_membership::Pallet<Runtime>;
 }
 
 impl TryFrom<RuntimeCall> for pallet_revive::Call<Runtime> {
@@ -2809,6 +2831,7 @@ pub type TxExtension = (
 		Runtime,
 		pallet_asset_conversion_tx_payment::ChargeAssetTxPayment<Runtime>,
 	>,
+	pallet_revive::evm::runtime::ApplyAuthorizations<Runtime>,
 	frame_metadata_hash_extension::CheckMetadataHash<Runtime>,
 	frame_system::WeightReclaim<Runtime>,
 );
@@ -2831,6 +2854,7 @@ impl EthExtra for EthExtraImpl {
 			frame_system::CheckWeight::<Runtime>::new(),
 			pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<Runtime>::from(tip, None)
 				.into(),
+			pallet_revive::evm::runtime::ApplyAuthorizations::<Runtime>::new(),
 			frame_metadata_hash_extension::CheckMetadataHash::<Runtime>::new(false),
 			frame_system::WeightReclaim::<Runtime>::new(),
 		)
@@ -3034,6 +3058,7 @@ mod benches {
 		[pallet_asset_conversion_ops, AssetConversionMigration]
 		[pallet_verify_signature, VerifySignature]
 		[pallet_meta_tx, MetaTx]
+		[pallet_anonymous_membership, AnonymousMembership]
 	);
 }
 
@@ -3354,6 +3379,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(6)]
 	impl pallet_revive::ReviveApi<Block, AccountId, Balance, Nonce, BlockNumber> for Runtime
 	{
 		fn balance(address: H160) -> U256 {
@@ -3368,6 +3394,10 @@ impl_runtime_apis! {
 			Revive::evm_gas_price()
 		}
 
+		fn gas_schedule() -> Option<pallet_revive::evm::GasScheduleResult> {
+			Revive::evm_gas_schedule()
+		}
+
 		fn nonce(address: H160) -> Nonce {
 			let account = <Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&address);
 			System::account_nonce(account)
@@ -3453,22 +3483,29 @@ impl_runtime_apis! {
 			)
 		}
 
+		fn account_proof_keys(
+			address: H160,
+			storage_keys: Vec<[u8; 32]>,
+		) -> pallet_revive::AccountProofKeys {
+			Revive::account_proof_keys(address, storage_keys)
+		}
+
 		fn trace_block(
 			block: Block,
 			config: pallet_revive::evm::TracerConfig
-		) -> Vec<(u32, pallet_revive::evm::CallTrace)> {
+		) -> Vec<(u32, pallet_revive::evm::Trace)> {
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
+			let mut tracer = Revive::evm_tracer(config);
 			let mut traces = vec![];
 			let (header, extrinsics) = block.deconstruct();
 
 			Executive::initialize_block(&header);
 			for (index, ext) in extrinsics.into_iter().enumerate() {
-				trace(&mut tracer, || {
+				trace(tracer.as_tracing(), || {
 					let _ = Executive::apply_extrinsic(ext);
 				});
 
-				if let Some(tx_trace) = tracer.collect_traces().pop() {
+				if let Some(tx_trace) = tracer.collect_trace() {
 					traces.push((index as u32, tx_trace));
 				}
 			}
@@ -3480,15 +3517,15 @@ impl_runtime_apis! {
 			block: Block,
 			tx_index: u32,
 			config: pallet_revive::evm::TracerConfig
-		) -> Option<pallet_revive::evm::CallTrace> {
+		) -> Option<pallet_revive::evm::Trace> {
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
+			let mut tracer = Revive::evm_tracer(config);
 			let (header, extrinsics) = block.deconstruct();
 
 			Executive::initialize_block(&header);
 			for (index, ext) in extrinsics.into_iter().enumerate() {
 				if index as u32 == tx_index {
-					trace(&mut tracer, || {
+					trace(tracer.as_tracing(), || {
 						let _ = Executive::apply_extrinsic(ext);
 					});
 					break;
@@ -3497,24 +3534,30 @@ impl_runtime_apis! {
 				}
 			}
 
-			tracer.collect_traces().pop()
+			tracer.collect_trace()
+		}
+
+		fn create_access_list(
+			tx: pallet_revive::evm::GenericTransaction,
+		) -> Result<pallet_revive::evm::AccessListResult, pallet_revive::EthTransactError> {
+			Revive::create_access_list(tx, |tx| Self::eth_transact(tx))
 		}
 
 		fn trace_call(
 			tx: pallet_revive::evm::GenericTransaction,
 			config: pallet_revive::evm::TracerConfig)
-			-> Result<pallet_revive::evm::CallTrace, pallet_revive::EthTransactError>
+			-> Result<pallet_revive::evm::Trace, pallet_revive::EthTransactError>
 		{
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
-			let result = trace(&mut tracer, || Self::eth_transact(tx));
+			let mut tracer = Revive::evm_tracer(config);
+			let result = trace(tracer.as_tracing(), || Self::eth_transact(tx));
 
-			if let Some(trace) = tracer.collect_traces().pop() {
+			if let Some(trace) = tracer.collect_trace() {
 				Ok(trace)
 			} else if let Err(err) = result {
 				Err(err)
 			} else {
-				Ok(Default::default())
+				Ok(tracer.empty_trace())
 			}
 		}
 	}
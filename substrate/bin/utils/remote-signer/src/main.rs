@@ -0,0 +1,103 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Reference signer for [`sc_keystore::RemoteKeystore`].
+//!
+//! Serves the keys of a [`LocalKeystore`] over a Unix socket, so that the node and the key
+//! material can live in different processes on the same machine.
+//!
+//! Only the operations allowed with `--allow` are served, e.g. `--allow gran:sign` lets the node
+//! sign GRANDPA messages and `--allow babe:keys,sign,vrf` lets it author BABE blocks.
+
+use clap::Parser;
+use sc_keystore::{LocalKeystore, Operation, Policy, RemoteSigner};
+use sp_core::crypto::{KeyTypeId, SecretString};
+use std::{
+	fs,
+	os::unix::{fs::PermissionsExt, net::UnixListener},
+	path::PathBuf,
+	sync::Arc,
+};
+
+/// Environment variable holding the keystore password, if not read from a file.
+const PASSWORD_ENV: &str = "REMOTE_SIGNER_PASSWORD";
+
+#[derive(Debug, Parser)]
+#[command(author, version, about)]
+struct Cli {
+	/// Path of the Unix socket to listen on.
+	///
+	/// A stale socket file at this path is removed. The socket is only accessible by the owner.
+	#[arg(long)]
+	socket: PathBuf,
+
+	/// Keystore directory. Keys are only kept in memory if not given.
+	#[arg(long)]
+	keystore_path: Option<PathBuf>,
+
+	/// File that contains the password used by the keystore.
+	///
+	/// The password is read from the `REMOTE_SIGNER_PASSWORD` environment variable if not given.
+	#[arg(long, value_name = "PATH")]
+	password_filename: Option<PathBuf>,
+
+	/// Operations served on the keys of a key type, as `<KEY_TYPE>:<OPERATION>[,<OPERATION>]`.
+	///
+	/// Operations are `keys`, `generate`, `sign` and `vrf`. Can be given multiple times.
+	#[arg(long, value_name = "RULE", value_parser = parse_rule, required = true)]
+	allow: Vec<(KeyTypeId, Vec<Operation>)>,
+}
+
+fn parse_rule(rule: &str) -> Result<(KeyTypeId, Vec<Operation>), String> {
+	let (key_type, operations) =
+		rule.split_once(':').ok_or("Expected `<KEY_TYPE>:<OPERATION>[,<OPERATION>]`")?;
+	let key_type = KeyTypeId::try_from(key_type)
+		.map_err(|_| format!("Invalid key type `{key_type}`, expected 4 characters"))?;
+	let operations = operations.split(',').map(str::parse).collect::<Result<_, _>>()?;
+	Ok((key_type, operations))
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+	sp_tracing::try_init_simple();
+	let cli = Cli::parse();
+
+	let password = match cli.password_filename {
+		Some(file) => Some(fs::read_to_string(file)?),
+		None => std::env::var(PASSWORD_ENV).ok(),
+	};
+	let keystore = match cli.keystore_path {
+		Some(path) => LocalKeystore::open(path, password.map(SecretString::new))?,
+		None => LocalKeystore::in_memory(),
+	};
+	let policy = cli
+		.allow
+		.into_iter()
+		.fold(Policy::deny_all(), |policy, (key_type, operations)| {
+			policy.allow(key_type, operations)
+		});
+
+	if cli.socket.exists() {
+		fs::remove_file(&cli.socket)?;
+	}
+	let listener = UnixListener::bind(&cli.socket)?;
+	fs::set_permissions(&cli.socket, fs::Permissions::from_mode(0o600))?;
+
+	log::info!("Remote signer listening on {:?}", cli.socket);
+	RemoteSigner::new(Arc::new(keystore)).with_policy(policy).run(listener)?;
+	Ok(())
+}
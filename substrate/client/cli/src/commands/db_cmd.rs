@@ -0,0 +1,126 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CliConfiguration, DatabaseParams, Error, Result, SharedParams};
+use sc_client_db::check::{self, CheckReport};
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+
+/// The `db` subcommands used to check and repair the database of a stopped node.
+#[derive(Debug, Clone, clap::Subcommand)]
+pub enum DbSubcommand {
+	/// Check the consistency of the database.
+	///
+	/// Walks the canonical chain and reports missing or corrupted headers, bodies and
+	/// justifications, a missing finalized state and unreadable state journals.
+	Check(DbCheckCmd),
+
+	/// Roll the database back to the last consistent block.
+	///
+	/// The best and finalized blocks are reverted to the last block the check found
+	/// consistent, and undecodable entries are removed. The node then syncs the reverted
+	/// blocks again.
+	Repair(DbRepairCmd),
+}
+
+/// The `db check` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct DbCheckCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// The `db repair` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct DbRepairCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl DbSubcommand {
+	/// Run the `db` subcommand.
+	pub fn run<B: BlockT>(&self, config: &sc_service::Configuration) -> Result<()> {
+		match self {
+			DbSubcommand::Check(_) => {
+				let report = check::check_database::<B>(&config.database)?;
+				print_report::<B>(&report);
+				if !report.is_consistent() {
+					return Err(Error::Input(format!(
+						"Found {} database inconsistencies",
+						report.issues.len()
+					)))
+				}
+			},
+			DbSubcommand::Repair(_) => {
+				let repair = check::repair_database::<B>(&config.database)?;
+				print_report::<B>(&repair.check);
+				if repair.check.is_consistent() {
+					println!("Nothing to repair");
+				} else {
+					print_block("Best block after repair", repair.best);
+					print_block("Finalized block after repair", repair.finalized);
+				}
+			},
+		}
+		Ok(())
+	}
+}
+
+fn print_block<N: std::fmt::Display, H: std::fmt::Debug>(desc: &str, block: Option<(N, H)>) {
+	match block {
+		Some((number, hash)) => println!("{desc}: #{number} ({hash:?})"),
+		None => println!("{desc}: none"),
+	}
+}
+
+fn print_report<B: BlockT>(report: &CheckReport<NumberFor<B>, B::Hash>) {
+	print_block("Best block", report.best);
+	print_block("Finalized block", report.finalized);
+	print_block("Last consistent block", report.last_consistent);
+	for issue in &report.issues {
+		println!("{issue}");
+	}
+	if report.is_consistent() {
+		println!("The database is consistent");
+	}
+}
+
+impl CliConfiguration for DbSubcommand {
+	fn shared_params(&self) -> &SharedParams {
+		match self {
+			DbSubcommand::Check(cmd) => &cmd.shared_params,
+			DbSubcommand::Repair(cmd) => &cmd.shared_params,
+		}
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		match self {
+			DbSubcommand::Check(cmd) => Some(&cmd.database_params),
+			DbSubcommand::Repair(cmd) => Some(&cmd.database_params),
+		}
+	}
+}
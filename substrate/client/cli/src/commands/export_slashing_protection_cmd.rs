@@ -0,0 +1,93 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{DatabaseParams, SharedParams, SlashingProtectionParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use log::info;
+use sc_client_api::HeaderBackend;
+use sc_slashing_protection::SlashingProtection;
+use sp_runtime::traits::Block as BlockT;
+use std::{
+	fs,
+	io::{self, BufWriter, Write},
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+/// The `export-slashing-protection` command used to export the slashing protection database.
+///
+/// The record of the messages signed by the local validator keys is exported in the interchange
+/// format and can be imported on a different machine with `import-slashing-protection`.
+#[derive(Debug, Clone, Parser)]
+pub struct ExportSlashingProtectionCmd {
+	/// Output file name or stdout if unspecified.
+	#[arg()]
+	pub output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub slashing_protection_params: SlashingProtectionParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ExportSlashingProtectionCmd {
+	/// Run the export-slashing-protection command
+	///
+	/// `data_path` is the data directory of the chain, see `Configuration::data_path`.
+	pub fn run<B, C>(&self, client: Arc<C>, data_path: &Path) -> error::Result<()>
+	where
+		B: BlockT,
+		C: HeaderBackend<B>,
+	{
+		let path = self.slashing_protection_params.slashing_protection_db(data_path);
+		info!("Slashing protection database: {}", path.display());
+
+		let slashing_protection =
+			SlashingProtection::open(path, client.info().genesis_hash.as_ref())?;
+
+		let mut file: Box<dyn Write> = match &self.output {
+			Some(filename) => Box::new(BufWriter::new(fs::File::create(filename)?)),
+			None => Box::new(io::stdout()),
+		};
+		serde_json::to_writer_pretty(&mut file, &slashing_protection.export())
+			.map_err(|e| format!("Error writing slashing protection interchange: {}", e))?;
+		file.flush()?;
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportSlashingProtectionCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,93 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{DatabaseParams, SharedParams, SlashingProtectionParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use log::info;
+use sc_client_api::HeaderBackend;
+use sc_slashing_protection::{Interchange, SlashingProtection};
+use sp_runtime::traits::Block as BlockT;
+use std::{
+	fs,
+	io::{self, BufReader, Read},
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+/// The `import-slashing-protection` command used to import a slashing protection record.
+///
+/// The imported record, e.g. exported with `export-slashing-protection` on a different machine,
+/// is merged into the slashing protection database. The node must not be running.
+#[derive(Debug, Clone, Parser)]
+pub struct ImportSlashingProtectionCmd {
+	/// Input file or stdin if unspecified.
+	#[arg()]
+	pub input: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub slashing_protection_params: SlashingProtectionParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ImportSlashingProtectionCmd {
+	/// Run the import-slashing-protection command
+	///
+	/// `data_path` is the data directory of the chain, see `Configuration::data_path`.
+	pub fn run<B, C>(&self, client: Arc<C>, data_path: &Path) -> error::Result<()>
+	where
+		B: BlockT,
+		C: HeaderBackend<B>,
+	{
+		let file: Box<dyn Read> = match &self.input {
+			Some(filename) => Box::new(BufReader::new(fs::File::open(filename)?)),
+			None => Box::new(io::stdin()),
+		};
+		let interchange: Interchange = serde_json::from_reader(file)
+			.map_err(|e| format!("Error reading slashing protection interchange: {}", e))?;
+
+		let path = self.slashing_protection_params.slashing_protection_db(data_path);
+		info!("Slashing protection database: {}", path.display());
+
+		let slashing_protection =
+			SlashingProtection::open(path, client.info().genesis_hash.as_ref())?;
+		slashing_protection.import(interchange)?;
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ImportSlashingProtectionCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
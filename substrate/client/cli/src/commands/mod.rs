@@ -21,11 +21,16 @@
 mod build_spec_cmd;
 mod chain_info_cmd;
 mod check_block_cmd;
+mod db_cmd;
 mod export_blocks_cmd;
+mod export_slashing_protection_cmd;
+mod export_snapshot_cmd;
 mod export_state_cmd;
 mod generate;
 mod generate_node_key;
 mod import_blocks_cmd;
+mod import_slashing_protection_cmd;
+mod import_snapshot_cmd;
 mod insert_key;
 mod inspect_key;
 mod inspect_node_key;
@@ -40,10 +45,27 @@ mod vanity;
 mod verify;
 
 pub use self::{
-	build_spec_cmd::BuildSpecCmd, chain_info_cmd::ChainInfoCmd, check_block_cmd::CheckBlockCmd,
-	export_blocks_cmd::ExportBlocksCmd, export_state_cmd::ExportStateCmd, generate::GenerateCmd,
-	generate_node_key::GenerateKeyCmdCommon, import_blocks_cmd::ImportBlocksCmd,
-	insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
-	key::KeySubcommand, purge_chain_cmd::PurgeChainCmd, revert_cmd::RevertCmd, run_cmd::RunCmd,
-	sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
+	build_spec_cmd::BuildSpecCmd,
+	chain_info_cmd::ChainInfoCmd,
+	check_block_cmd::CheckBlockCmd,
+	db_cmd::{DbCheckCmd, DbRepairCmd, DbSubcommand},
+	export_blocks_cmd::ExportBlocksCmd,
+	export_slashing_protection_cmd::ExportSlashingProtectionCmd,
+	export_snapshot_cmd::ExportSnapshotCmd,
+	export_state_cmd::ExportStateCmd,
+	generate::GenerateCmd,
+	generate_node_key::GenerateKeyCmdCommon,
+	import_blocks_cmd::ImportBlocksCmd,
+	import_slashing_protection_cmd::ImportSlashingProtectionCmd,
+	import_snapshot_cmd::ImportSnapshotCmd,
+	insert_key::InsertKeyCmd,
+	inspect_key::InspectKeyCmd,
+	inspect_node_key::InspectNodeKeyCmd,
+	key::KeySubcommand,
+	purge_chain_cmd::PurgeChainCmd,
+	revert_cmd::RevertCmd,
+	run_cmd::RunCmd,
+	sign::SignCmd,
+	vanity::VanityCmd,
+	verify::VerifyCmd,
 };
@@ -20,7 +20,7 @@ use crate::{
 	error::{Error, Result},
 	params::{
 		ImportParams, KeystoreParams, NetworkParams, OffchainWorkerParams, RpcEndpoint,
-		SharedParams, TransactionPoolParams,
+		SharedParams, SlashingProtectionParams, TransactionPoolParams,
 	},
 	CliConfiguration, PrometheusParams, RpcParams, RuntimeParams, TelemetryParams,
 };
@@ -99,6 +99,10 @@ pub struct RunCmd {
 	#[clap(flatten)]
 	pub keystore_params: KeystoreParams,
 
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub slashing_protection_params: SlashingProtectionParams,
+
 	/// Shortcut for `--name Alice --validator`.
 	///
 	/// Session keys for `Alice` are added to keystore.
@@ -80,6 +80,14 @@ pub struct ImportParams {
 	/// Providing `0` will disable the cache.
 	#[arg(long, value_name = "Bytes", default_value_t = 1024 * 1024 * 1024)]
 	pub trie_cache_size: usize,
+
+	/// Keep a flat copy of the finalized state in the database.
+	///
+	/// Storage queries at the finalized block and its descendants are then answered without
+	/// traversing the trie. Storage roots and proofs still use the trie. The copy is built on
+	/// startup when the flag is first used, which can take a while on large states.
+	#[arg(long)]
+	pub flat_state: bool,
 }
 
 impl ImportParams {
@@ -19,6 +19,7 @@
 use crate::error;
 use clap::Args;
 use sc_service::{BlocksPruning, PruningMode};
+use std::num::NonZeroU32;
 
 /// Parameters to define the pruning mode
 #[derive(Debug, Clone, Args)]
@@ -45,6 +46,14 @@ pub struct PruningParams {
 	#[arg(alias = "pruning", long, value_name = "PRUNING_MODE")]
 	pub state_pruning: Option<DatabasePruningMode>,
 
+	/// Keep the state of every finalized block whose number is a multiple of `INTERVAL`.
+	///
+	/// The state of these checkpoints is kept in addition to the last blocks kept by
+	/// `--state-pruning`, which must then be a `NUMBER`. Like the state pruning mode, the interval
+	/// can only be set on the first creation of the database.
+	#[arg(long, value_name = "INTERVAL")]
+	pub state_checkpoints: Option<NonZeroU32>,
+
 	/// Specify the blocks pruning mode.
 	///
 	/// This mode specifies when the block's body (including justifications)
@@ -69,7 +78,18 @@ pub struct PruningParams {
 impl PruningParams {
 	/// Get the pruning value from the parameters
 	pub fn state_pruning(&self) -> error::Result<Option<PruningMode>> {
-		Ok(self.state_pruning.map(|v| v.into()))
+		let Some(interval) = self.state_checkpoints else {
+			return Ok(self.state_pruning.map(|v| v.into()))
+		};
+
+		match self.state_pruning {
+			None =>
+				Ok(Some(PruningMode::Checkpoints { constraints: Default::default(), interval })),
+			Some(DatabasePruningMode::Custom(n)) => Ok(Some(PruningMode::checkpoints(n, interval))),
+			Some(_) => Err(error::Error::Input(
+				"`--state-checkpoints` requires `--state-pruning` to be a number of blocks".into(),
+			)),
+		}
 	}
 
 	/// Get the block pruning value from the parameters
@@ -162,4 +182,25 @@ mod tests {
 		assert!(matches!(dbg!(pruning.state_pruning), Some(DatabasePruningMode::ArchiveCanonical)));
 		assert!(matches!(pruning.blocks_pruning, DatabasePruningMode::ArchiveCanonical));
 	}
+
+	#[test]
+	fn state_checkpoints_parse_works() {
+		let interval = NonZeroU32::new(1000).unwrap();
+
+		let Cli { pruning } =
+			Cli::parse_from(["", "--state-pruning=256", "--state-checkpoints=1000"]);
+		assert_eq!(pruning.state_pruning().unwrap(), Some(PruningMode::checkpoints(256, interval)));
+
+		let Cli { pruning } = Cli::parse_from(["", "--state-checkpoints=1000"]);
+		assert!(matches!(
+			pruning.state_pruning().unwrap(),
+			Some(PruningMode::Checkpoints { interval: i, .. }) if i == interval
+		));
+
+		let Cli { pruning } =
+			Cli::parse_from(["", "--state-pruning=archive", "--state-checkpoints=1000"]);
+		assert!(pruning.state_pruning().is_err());
+
+		assert!(Cli::try_parse_from(["", "--state-checkpoints=0"]).is_err());
+	}
 }
@@ -0,0 +1,48 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use clap::Args;
+use std::path::{Path, PathBuf};
+
+/// Name of the slashing protection database within the chain data directory.
+const DEFAULT_SLASHING_PROTECTION_DB: &str = "slashing_protection.json";
+
+/// Parameters of the slashing protection of the local validator keys.
+#[derive(Debug, Clone, Args)]
+pub struct SlashingProtectionParams {
+	/// Path of the slashing protection database.
+	///
+	/// The database records the block seals and votes signed by the local validator keys, signing
+	/// messages conflicting with them is refused. Defaults to `slashing_protection.json` within
+	/// the chain data directory.
+	///
+	/// The database must not be shared by several running nodes. When moving a validator to a
+	/// different machine, use the `export-slashing-protection` and `import-slashing-protection`
+	/// subcommands.
+	#[arg(long, value_name = "PATH")]
+	pub slashing_protection_db: Option<PathBuf>,
+}
+
+impl SlashingProtectionParams {
+	/// Path of the slashing protection database of the chain stored in `data_path`.
+	pub fn slashing_protection_db(&self, data_path: &Path) -> PathBuf {
+		self.slashing_protection_db
+			.clone()
+			.unwrap_or_else(|| data_path.join(DEFAULT_SLASHING_PROTECTION_DB))
+	}
+}
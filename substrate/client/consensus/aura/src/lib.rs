@@ -41,13 +41,14 @@ use sc_consensus_slots::{
 	BackoffAuthoringBlocksStrategy, InherentDataProviderExt, SimpleSlotWorkerToSlotWorker,
 	SlotInfo, StorageChanges,
 };
+use sc_slashing_protection::{MessageKind, Position, SlashingProtection};
 use sc_telemetry::TelemetryHandle;
 use sp_api::{Core, ProvideRuntimeApi};
-use sp_application_crypto::AppPublic;
+use sp_application_crypto::{AppCrypto, AppPublic};
 use sp_blockchain::HeaderBackend;
 use sp_consensus::{BlockOrigin, Environment, Error as ConsensusError, Proposer, SelectChain};
 use sp_consensus_slots::Slot;
-use sp_core::crypto::Pair;
+use sp_core::crypto::{ByteArray, Pair};
 use sp_inherents::CreateInherentDataProviders;
 use sp_keystore::KeystorePtr;
 use sp_runtime::traits::{Block as BlockT, Header, Member, NumberFor};
@@ -148,6 +149,8 @@ pub struct StartAuraParams<C, SC, I, PF, SO, L, CIDP, BS, N> {
 	///
 	/// If in doubt, use `Default::default()`.
 	pub compatibility_mode: CompatibilityMode<N>,
+	/// Record of the signed block seals, consulted before sealing a block.
+	pub slashing_protection: Option<Arc<SlashingProtection>>,
 }
 
 /// Start the aura worker. The returned future should be run in a futures executor.
@@ -168,6 +171,7 @@ pub fn start_aura<P, B, C, SC, I, PF, SO, L, CIDP, BS, Error>(
 		max_block_proposal_slot_portion,
 		telemetry,
 		compatibility_mode,
+		slashing_protection,
 	}: StartAuraParams<C, SC, I, PF, SO, L, CIDP, BS, NumberFor<B>>,
 ) -> Result<impl Future<Output = ()>, ConsensusError>
 where
@@ -201,6 +205,7 @@ where
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
 		compatibility_mode,
+		slashing_protection,
 	});
 
 	Ok(sc_consensus_slots::start_slot_worker(
@@ -245,6 +250,8 @@ pub struct BuildAuraWorkerParams<C, I, PF, SO, L, BS, N> {
 	///
 	/// If in doubt, use `Default::default()`.
 	pub compatibility_mode: CompatibilityMode<N>,
+	/// Record of the signed block seals, consulted before sealing a block.
+	pub slashing_protection: Option<Arc<SlashingProtection>>,
 }
 
 /// Build the aura worker.
@@ -264,6 +271,7 @@ pub fn build_aura_worker<P, B, C, PF, I, SO, L, BS, Error>(
 		telemetry,
 		force_authoring,
 		compatibility_mode,
+		slashing_protection,
 	}: BuildAuraWorkerParams<C, I, PF, SO, L, BS, NumberFor<B>>,
 ) -> impl sc_consensus_slots::SimpleSlotWorker<
 	B,
@@ -302,6 +310,7 @@ where
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
 		compatibility_mode,
+		slashing_protection,
 		_phantom: PhantomData::<fn() -> P>,
 	}
 }
@@ -319,6 +328,7 @@ struct AuraWorker<C, E, I, P, SO, L, BS, N> {
 	max_block_proposal_slot_portion: Option<SlotProportion>,
 	telemetry: Option<TelemetryHandle>,
 	compatibility_mode: CompatibilityMode<N>,
+	slashing_protection: Option<Arc<SlashingProtection>>,
 	_phantom: PhantomData<fn() -> P>,
 }
 
@@ -392,6 +402,20 @@ where
 		public: Self::Claim,
 		_authorities: Self::AuxData,
 	) -> Result<sc_consensus::BlockImportParams<B>, ConsensusError> {
+		if let Some(slashing_protection) = self.slashing_protection.as_ref() {
+			let slot = find_pre_digest::<B, P::Signature>(&header)
+				.map_err(|e| ConsensusError::CannotSign(format!("{}. Key: {:?}", e, public)))?;
+			slashing_protection
+				.check_and_record(
+					<AuthorityId<P> as AppCrypto>::ID,
+					public.as_slice(),
+					MessageKind::BlockSeal,
+					Position::slot(*slot),
+					header_hash.as_ref(),
+				)
+				.map_err(|e| ConsensusError::CannotSign(format!("{}. Key: {:?}", e, public)))?;
+		}
+
 		let signature_digest_item =
 			crate::standalone::seal::<_, P>(header_hash, &public, &self.keystore)?;
 
@@ -758,6 +782,7 @@ mod tests {
 					max_block_proposal_slot_portion: None,
 					telemetry: None,
 					compatibility_mode: CompatibilityMode::None,
+					slashing_protection: None,
 				})
 				.expect("Starts aura"),
 			);
@@ -810,6 +835,7 @@ mod tests {
 			block_proposal_slot_portion: SlotProportion::new(0.5),
 			max_block_proposal_slot_portion: None,
 			compatibility_mode: Default::default(),
+			slashing_protection: None,
 			_phantom: PhantomData::<fn() -> AuthorityPair>,
 		};
 
@@ -836,10 +862,22 @@ mod tests {
 
 		let keystore_path = tempfile::tempdir().expect("Creates keystore path");
 		let keystore = LocalKeystore::open(keystore_path.path(), None).expect("Creates keystore.");
-		keystore
+		let public = keystore
 			.sr25519_generate_new(AuthorityPair::ID, Some(&Keyring::Alice.to_seed()))
 			.expect("Key should be created");
 
+		// Alice already sealed a different block at her next slot, e.g. on a different machine.
+		let slashing_protection = SlashingProtection::in_memory(&[0; 32]);
+		slashing_protection
+			.check_and_record(
+				AuthorityPair::ID,
+				public.as_ref(),
+				MessageKind::BlockSeal,
+				Position::slot(3),
+				b"other block",
+			)
+			.unwrap();
+
 		let net = Arc::new(Mutex::new(net));
 
 		let mut net = net.lock();
@@ -860,6 +898,7 @@ mod tests {
 			block_proposal_slot_portion: SlotProportion::new(0.5),
 			max_block_proposal_slot_portion: None,
 			compatibility_mode: Default::default(),
+			slashing_protection: Some(Arc::new(slashing_protection)),
 			_phantom: PhantomData::<fn() -> AuthorityPair>,
 		};
 
@@ -879,5 +918,18 @@ mod tests {
 
 		// The returned block should be imported and we should be able to get its header by now.
 		assert!(client.header(res.block.hash()).unwrap().is_some());
+
+		// Sealing another block at slot 3 is refused.
+		let res = worker
+			.on_slot(SlotInfo {
+				slot: 3.into(),
+				ends_at: Instant::now() + Duration::from_secs(100),
+				create_inherent_data: Box::new(()),
+				duration: Duration::from_millis(1000),
+				chain_head: client.expect_header(res.block.hash()).unwrap(),
+				block_size_limit: None,
+			})
+			.await;
+		assert!(res.is_none());
 	}
 }
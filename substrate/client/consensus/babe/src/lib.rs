@@ -106,6 +106,7 @@ use sc_consensus_slots::{
 	check_equivocation, BackoffAuthoringBlocksStrategy, CheckedHeader, InherentDataProviderExt,
 	SlotInfo, StorageChanges,
 };
+use sc_slashing_protection::{MessageKind, Position, SlashingProtection};
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG, CONSENSUS_TRACE};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::{ApiExt, ProvideRuntimeApi};
@@ -457,6 +458,9 @@ pub struct BabeParams<B: BlockT, C, SC, E, I, SO, L, CIDP, BS> {
 
 	/// Handle use to report telemetries.
 	pub telemetry: Option<TelemetryHandle>,
+
+	/// Record of the signed block seals, consulted before sealing a block.
+	pub slashing_protection: Option<Arc<SlashingProtection>>,
 }
 
 /// Start the babe worker.
@@ -476,6 +480,7 @@ pub fn start_babe<B, C, SC, E, I, SO, CIDP, BS, L, Error>(
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
 		telemetry,
+		slashing_protection,
 	}: BabeParams<B, C, SC, E, I, SO, L, CIDP, BS>,
 ) -> Result<BabeWorker<B>, ConsensusError>
 where
@@ -515,6 +520,7 @@ where
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
 		telemetry,
+		slashing_protection,
 	};
 
 	info!(target: LOG_TARGET, "👶 Starting BABE Authorship worker");
@@ -712,6 +718,7 @@ struct BabeSlotWorker<B: BlockT, C, E, I, SO, L, BS> {
 	block_proposal_slot_portion: SlotProportion,
 	max_block_proposal_slot_portion: Option<SlotProportion>,
 	telemetry: Option<TelemetryHandle>,
+	slashing_protection: Option<Arc<SlashingProtection>>,
 }
 
 #[async_trait::async_trait]
@@ -818,9 +825,21 @@ where
 		header_hash: &B::Hash,
 		body: Vec<B::Extrinsic>,
 		storage_changes: StorageChanges<B>,
-		(_, public): Self::Claim,
+		(pre_digest, public): Self::Claim,
 		epoch_descriptor: Self::AuxData,
 	) -> Result<BlockImportParams<B>, ConsensusError> {
+		if let Some(slashing_protection) = self.slashing_protection.as_ref() {
+			slashing_protection
+				.check_and_record(
+					<AuthorityId as AppCrypto>::ID,
+					public.as_ref(),
+					MessageKind::BlockSeal,
+					Position::slot(*pre_digest.slot()),
+					header_hash.as_ref(),
+				)
+				.map_err(|e| ConsensusError::CannotSign(format!("{}. Key: {:?}", e, public)))?;
+		}
+
 		let signature = self
 			.keystore
 			.sr25519_sign(<AuthorityId as AppCrypto>::ID, public.as_ref(), header_hash.as_ref())
@@ -33,6 +33,8 @@ pub enum Error {
 	RuntimeApi(sp_api::ApiError),
 	#[error("Signature error: {0}")]
 	Signature(String),
+	#[error("Slashing protection: {0}")]
+	SlashingProtection(String),
 	#[error("Session uninitialized")]
 	UninitSession,
 	#[error("pallet-beefy was reset")]
@@ -63,6 +65,7 @@ impl PartialEq for Error {
 			(Error::Keystore(s1), Error::Keystore(s2)) => s1 == s2,
 			(Error::RuntimeApi(_), Error::RuntimeApi(_)) => true,
 			(Error::Signature(s1), Error::Signature(s2)) => s1 == s2,
+			(Error::SlashingProtection(s1), Error::SlashingProtection(s2)) => s1 == s2,
 			(Error::UninitSession, Error::UninitSession) => true,
 			(Error::ConsensusReset, Error::ConsensusReset) => true,
 			_ => false,
@@ -39,6 +39,7 @@ use sc_client_api::{Backend, BlockBackend, BlockchainEvents, FinalityNotificatio
 use sc_consensus::BlockImport;
 use sc_network::{NetworkRequest, NotificationService, ProtocolName};
 use sc_network_gossip::{GossipEngine, Network as GossipNetwork, Syncing as GossipSyncing};
+use sc_slashing_protection::SlashingProtection;
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{Backend as BlockchainBackend, HeaderBackend};
@@ -233,6 +234,8 @@ pub struct BeefyParams<B: Block, BE, C, N, P, R, S, AuthorityId: AuthorityIdBoun
 	pub on_demand_justifications_handler: BeefyJustifsRequestHandler<B, C>,
 	/// Whether running under "Authority" role.
 	pub is_authority: bool,
+	/// Record of the signed votes, consulted before signing a vote.
+	pub slashing_protection: Option<Arc<SlashingProtection>>,
 }
 /// Helper object holding BEEFY worker communication/gossip components.
 ///
@@ -315,6 +318,7 @@ where
 		links: BeefyVoterLinks<B, AuthorityId>,
 		pending_justifications: BTreeMap<NumberFor<B>, BeefyVersionedFinalityProof<B, AuthorityId>>,
 		is_authority: bool,
+		slashing_protection: Option<Arc<SlashingProtection>>,
 	) -> BeefyWorker<B, BE, P, R, S, N, AuthorityId> {
 		let key_store = Arc::new(self.key_store);
 		BeefyWorker {
@@ -330,6 +334,7 @@ where
 			links,
 			pending_justifications,
 			is_authority,
+			slashing_protection,
 		}
 	}
 
@@ -542,6 +547,7 @@ pub async fn start_beefy_gadget<B, BE, C, N, P, R, S, AuthorityId>(
 		links,
 		mut on_demand_justifications_handler,
 		is_authority,
+		slashing_protection,
 	} = beefy_params;
 
 	let BeefyNetworkParams {
@@ -629,6 +635,7 @@ pub async fn start_beefy_gadget<B, BE, C, N, P, R, S, AuthorityId>(
 			links.clone(),
 			BTreeMap::new(),
 			is_authority,
+			slashing_protection.clone(),
 		);
 
 		futures::select! {
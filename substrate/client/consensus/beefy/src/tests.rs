@@ -460,6 +460,7 @@ where
 			prometheus_registry: None,
 			on_demand_justifications_handler: on_demand_justif_handler,
 			is_authority: true,
+			slashing_protection: None,
 		};
 		let task = crate::start_beefy_gadget::<_, _, _, _, _, _, _, _>(beefy_params);
 
@@ -31,12 +31,13 @@ use crate::{
 	round::{Rounds, VoteImportResult},
 	BeefyComms, BeefyVoterLinks, UnpinnedFinalityNotification, LOG_TARGET,
 };
-use sp_application_crypto::RuntimeAppPublic;
+use sp_application_crypto::{key_types::BEEFY as BEEFY_KEY_TYPE, RuntimeAppPublic};
 
 use codec::{Codec, Decode, DecodeAll, Encode};
 use futures::{stream::Fuse, FutureExt, StreamExt};
 use log::{debug, error, info, trace, warn};
 use sc_client_api::{Backend, HeaderBackend};
+use sc_slashing_protection::{MessageKind, Position, SlashingProtection};
 use sc_utils::notification::NotificationReceiver;
 use sp_api::ProvideRuntimeApi;
 use sp_arithmetic::traits::{AtLeast32Bit, Saturating};
@@ -405,6 +406,8 @@ pub(crate) struct BeefyWorker<B: Block, BE, P, RuntimeApi, S, N, AuthorityId: Au
 	pub metrics: Option<VoterMetrics>,
 	/// Node runs under "Authority" role.
 	pub is_authority: bool,
+	/// Record of the signed votes, consulted before signing a vote.
+	pub slashing_protection: Option<Arc<SlashingProtection>>,
 }
 
 impl<B, BE, P, R, S, N, AuthorityId> BeefyWorker<B, BE, P, R, S, N, AuthorityId>
@@ -781,6 +784,19 @@ where
 		let commitment = Commitment { payload, block_number: target_number, validator_set_id };
 		let encoded_commitment = commitment.encode();
 
+		if let Some(slashing_protection) = self.slashing_protection.as_ref() {
+			if let Err(err) = slashing_protection.check_and_record(
+				BEEFY_KEY_TYPE,
+				&<AuthorityId as RuntimeAppPublic>::to_raw_vec(&authority_id),
+				MessageKind::BeefyVote,
+				Position::new(validator_set_id, target_number.saturated_into()),
+				&encoded_commitment,
+			) {
+				warn!(target: LOG_TARGET, "🥩 Refusing to sign commitment: {}", err);
+				return Err(Error::SlashingProtection(err.to_string()));
+			}
+		}
+
 		let signature = match self.key_store.sign(&authority_id, &encoded_commitment) {
 			Ok(sig) => sig,
 			Err(err) => {
@@ -1161,6 +1177,7 @@ pub(crate) mod tests {
 			pending_justifications: BTreeMap::new(),
 			persisted_state,
 			is_authority: true,
+			slashing_protection: None,
 		}
 	}
 
@@ -1533,6 +1550,30 @@ pub(crate) mod tests {
 		assert!(justifs.get(BEEFY_ENGINE_ID).is_some())
 	}
 
+	#[tokio::test]
+	async fn should_refuse_votes_conflicting_with_slashing_protection() {
+		let keys = [Keyring::Alice];
+		let validator_set = ValidatorSet::new(make_beefy_ids(&keys), 0).unwrap();
+		let mut net = BeefyTestNet::new(1);
+		let mut worker = create_beefy_worker(net.peer(0), &keys[0], 1, validator_set.clone());
+
+		let slashing_protection = Arc::new(SlashingProtection::in_memory(&[0; 32]));
+		// Alice already voted for something else on block #1, e.g. on a different machine.
+		slashing_protection
+			.check_and_record(
+				BEEFY_KEY_TYPE,
+				keys[0].public().as_ref(),
+				MessageKind::BeefyVote,
+				Position::new(validator_set.id(), 1),
+				b"other commitment",
+			)
+			.unwrap();
+		worker.slashing_protection = Some(slashing_protection);
+
+		assert!(matches!(worker.do_vote(1), Err(Error::SlashingProtection(_))));
+		assert_eq!(worker.persisted_state.best_voted, 0);
+	}
+
 	#[tokio::test]
 	async fn should_init_session() {
 		let keys = &[Keyring::Alice, Keyring::Bob];
@@ -60,6 +60,7 @@ use gossip::{
 	FullCatchUpMessage, FullCommitMessage, GossipMessage, GossipValidator, PeerReport, VoteMessage,
 };
 use sc_network_sync::SyncEventStream;
+use sc_slashing_protection::{MessageKind, Position, SlashingProtection};
 use sc_utils::mpsc::TracingUnboundedReceiver;
 use sp_consensus_grandpa::{AuthorityId, AuthoritySignature, RoundNumber, SetId as SetIdNumber};
 
@@ -334,6 +335,7 @@ impl<B: BlockT, N: Network<B>, S: Syncing<B>> NetworkBridge<B, N, S> {
 	pub(crate) fn round_communication(
 		&self,
 		keystore: Option<LocalIdKeystore>,
+		slashing_protection: Option<Arc<SlashingProtection>>,
 		round: Round,
 		set_id: SetId,
 		voters: Arc<VoterSet<AuthorityId>>,
@@ -421,6 +423,7 @@ impl<B: BlockT, N: Network<B>, S: Syncing<B>> NetworkBridge<B, N, S> {
 		let (tx, out_rx) = mpsc::channel(0);
 		let outgoing = OutgoingMessages::<B> {
 			keystore,
+			slashing_protection,
 			round: round.0,
 			set_id: set_id.0,
 			network: self.gossip_engine.clone(),
@@ -712,6 +715,7 @@ pub(crate) struct OutgoingMessages<Block: BlockT> {
 	round: RoundNumber,
 	set_id: SetIdNumber,
 	keystore: Option<LocalIdKeystore>,
+	slashing_protection: Option<Arc<SlashingProtection>>,
 	sender: mpsc::Sender<SignedMessage<Block::Header>>,
 	network: Arc<Mutex<GossipEngine<Block>>>,
 	has_voted: HasVoted<Block::Header>,
@@ -757,6 +761,31 @@ impl<Block: BlockT> Sink<Message<Block::Header>> for OutgoingMessages<Block> {
 		// when locals exist, sign messages on import
 		if let Some(ref keystore) = self.keystore {
 			let target_hash = *(msg.target().0);
+
+			if let Some(ref slashing_protection) = self.slashing_protection {
+				let kind = match msg {
+					PrimaryPropose(_) => MessageKind::GrandpaPrimaryPropose,
+					Prevote(_) => MessageKind::GrandpaPrevote,
+					Precommit(_) => MessageKind::GrandpaPrecommit,
+				};
+				let payload =
+					sp_consensus_grandpa::localized_payload(self.round, self.set_id, &msg);
+				slashing_protection
+					.check_and_record(
+						sp_consensus_grandpa::KEY_TYPE,
+						keystore.local_id().as_ref(),
+						kind,
+						Position::new(self.set_id, self.round),
+						&payload,
+					)
+					.map_err(|e| {
+						Error::Signing(format!(
+							"Refused to sign GRANDPA vote for round {} targeting {:?}: {}",
+							self.round, target_hash, e
+						))
+					})?;
+			}
+
 			let signed = sp_consensus_grandpa::sign_message(
 				keystore.keystore(),
 				msg,
@@ -38,6 +38,7 @@ use sc_client_api::{
 	backend::{apply_aux, Backend as BackendT},
 	utils::is_descendent_of,
 };
+use sc_slashing_protection::SlashingProtection;
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG, CONSENSUS_INFO};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::ApiExt;
@@ -444,6 +445,7 @@ pub(crate) struct Environment<
 	pub(crate) justification_sender: Option<GrandpaJustificationSender<Block>>,
 	pub(crate) telemetry: Option<TelemetryHandle>,
 	pub(crate) offchain_tx_pool_factory: OffchainTransactionPoolFactory<Block>,
+	pub(crate) slashing_protection: Option<Arc<SlashingProtection>>,
 	pub(crate) _phantom: PhantomData<Backend>,
 }
 
@@ -759,6 +761,7 @@ where
 
 		let (incoming, outgoing) = self.network.round_communication(
 			keystore,
+			self.slashing_protection.clone(),
 			crate::communication::Round(round),
 			crate::communication::SetId(self.set_id),
 			self.voters.clone(),
@@ -68,6 +68,7 @@ use sc_client_api::{
 };
 use sc_consensus::BlockImport;
 use sc_network::{types::ProtocolName, NetworkBackend, NotificationService};
+use sc_slashing_protection::SlashingProtection;
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG, CONSENSUS_INFO};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver};
@@ -702,6 +703,8 @@ pub struct GrandpaParams<Block: BlockT, C, N, S, SC, VR> {
 	/// This will be used to create an offchain transaction pool instance for sending an
 	/// equivocation report from the runtime.
 	pub offchain_tx_pool_factory: OffchainTransactionPoolFactory<Block>,
+	/// Record of the signed votes, consulted before signing a vote.
+	pub slashing_protection: Option<Arc<SlashingProtection>>,
 }
 
 /// Returns the configuration value to put in
@@ -756,6 +759,7 @@ where
 		shared_voter_state,
 		telemetry,
 		offchain_tx_pool_factory,
+		slashing_protection,
 	} = grandpa_params;
 
 	// NOTE: we have recently removed `run_grandpa_observer` from the public
@@ -832,6 +836,7 @@ where
 		justification_sender,
 		telemetry,
 		offchain_tx_pool_factory,
+		slashing_protection,
 	);
 
 	let voter_work = voter_work.map(|res| match res {
@@ -902,6 +907,7 @@ where
 		justification_sender: GrandpaJustificationSender<Block>,
 		telemetry: Option<TelemetryHandle>,
 		offchain_tx_pool_factory: OffchainTransactionPoolFactory<Block>,
+		slashing_protection: Option<Arc<SlashingProtection>>,
 	) -> Self {
 		let metrics = match prometheus_registry.as_ref().map(Metrics::register) {
 			Some(Ok(metrics)) => Some(metrics),
@@ -927,6 +933,7 @@ where
 			justification_sender: Some(justification_sender),
 			telemetry: telemetry.clone(),
 			offchain_tx_pool_factory,
+			slashing_protection,
 			_phantom: PhantomData,
 		});
 
@@ -1079,6 +1086,7 @@ where
 					justification_sender: self.env.justification_sender.clone(),
 					telemetry: self.telemetry.clone(),
 					offchain_tx_pool_factory: self.env.offchain_tx_pool_factory.clone(),
+					slashing_protection: self.env.slashing_protection.clone(),
 					_phantom: PhantomData,
 				});
 
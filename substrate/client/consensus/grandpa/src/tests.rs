@@ -343,6 +343,7 @@ fn initialize_grandpa(
 				RejectAllTxPool::default(),
 			),
 			telemetry: None,
+			slashing_protection: None,
 		};
 		let voter =
 			run_grandpa_voter(grandpa_params).expect("all in order with client and network");
@@ -502,6 +503,7 @@ async fn finalize_3_voters_1_full_observer() {
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
 			),
+			slashing_protection: None,
 		};
 
 		run_grandpa_voter(grandpa_params).expect("all in order with client and network")
@@ -601,6 +603,7 @@ async fn transition_3_voters_twice_1_full_observer() {
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
 			),
+			slashing_protection: None,
 		};
 
 		voters
@@ -1084,6 +1087,7 @@ async fn voter_persists_its_votes() {
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
 			),
+			slashing_protection: None,
 		};
 
 		run_grandpa_voter(grandpa_params).expect("all in order with client and network")
@@ -1134,6 +1138,7 @@ async fn voter_persists_its_votes() {
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
 			),
+			slashing_protection: None,
 		};
 
 		run_grandpa_voter(grandpa_params)
@@ -1161,6 +1166,7 @@ async fn voter_persists_its_votes() {
 	{
 		let (round_rx, round_tx) = bob_network.round_communication(
 			Some((peers[1].public().into(), bob_keystore).into()),
+			None,
 			communication::Round(1),
 			communication::SetId(0),
 			Arc::new(VoterSet::new(voters).unwrap()),
@@ -1355,6 +1361,7 @@ async fn voter_catches_up_to_latest_round_when_behind() {
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
 			),
+			slashing_protection: None,
 		};
 
 		Box::pin(run_grandpa_voter(grandpa_params).expect("all in order with client and network"))
@@ -1487,6 +1494,7 @@ where
 		telemetry: None,
 		_phantom: PhantomData,
 		offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(RejectAllTxPool::default()),
+		slashing_protection: None,
 	}
 }
 
@@ -0,0 +1,773 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Types and functions related to authority selection, slot claiming and tickets generation.
+
+use super::*;
+
+use std::{future::Future, pin::Pin, task::Poll, time::Duration};
+
+use log::info;
+use sc_client_api::BlockchainEvents;
+use sc_consensus::{
+	block_import::{BlockImport, BlockImportParams, StateAction},
+	JustificationSyncLink,
+};
+use sc_consensus_epochs::{
+	EpochIdentifier, EpochIdentifierPosition, SharedEpochChanges, ViableEpochDescriptor,
+};
+use sc_consensus_slots::{BackoffAuthoringBlocksStrategy, SlotInfo, StorageChanges};
+use sp_application_crypto::AppCrypto;
+use sp_consensus::{BlockOrigin, Environment, Proposer, SelectChain};
+use sp_consensus_sassafras::ticket_id_threshold;
+use sp_core::{crypto::ByteArray, ed25519, Pair, U256};
+use sp_keystore::KeystorePtr;
+use sp_runtime::{
+	traits::{One, Saturating},
+	DigestItem,
+};
+
+/// Get the expected fallback author for the given slot and with given
+/// authorities. This should always assign the slot to some authority unless the
+/// authorities list is empty.
+pub(crate) fn fallback_slot_author(
+	slot: Slot,
+	authorities: &[AuthorityId],
+	randomness: Randomness,
+) -> Option<&AuthorityId> {
+	if authorities.is_empty() {
+		return None
+	}
+
+	let rand =
+		U256::from_big_endian(&(randomness, slot).using_encoded(sp_crypto_hashing::blake2_256));
+
+	let authorities_len = U256::from(authorities.len());
+	let idx = rand % authorities_len;
+
+	authorities.get(idx.as_u32() as usize)
+}
+
+/// Message signed via the ticket erased ephemeral key to prove the ticket ownership.
+///
+/// The message is bound to the claiming authority index to prevent claim replay
+/// by other authorities.
+pub(crate) fn ticket_claim_message(
+	slot: Slot,
+	epoch_index: u64,
+	authority_idx: AuthorityIndex,
+) -> Vec<u8> {
+	(b"sassafras-ticket-claim", slot, epoch_index, authority_idx).encode()
+}
+
+/// Claim slot ownership.
+///
+/// If the slot has an associated ticket, then the slot can be claimed only if the
+/// ticket has been generated by this node. If there is no ticket associated with the
+/// slot, then the slot is claimed only if one of our keys is the fallback slot author.
+///
+/// Returns the slot claim together with the claiming authority identifier, or `None`
+/// if it is not our turn to propose.
+pub fn claim_slot(
+	slot: Slot,
+	epoch: &Epoch,
+	maybe_ticket: Option<(TicketId, TicketBody)>,
+	keystore: &KeystorePtr,
+) -> Option<(SlotClaim, AuthorityId)> {
+	if epoch.authorities.is_empty() {
+		return None
+	}
+
+	let mut epoch_index = epoch.index;
+	let mut randomness = epoch.randomness;
+	if epoch.end_slot() <= slot {
+		// Slot doesn't strictly belong to the epoch, create a clone with fixed values.
+		let epoch = epoch.clone_for_slot(slot);
+		epoch_index = epoch.index;
+		randomness = epoch.randomness;
+	}
+
+	let (authority_idx, ticket_claim) = match maybe_ticket {
+		Some((ticket_id, ticket_body)) => {
+			debug!(target: LOG_TARGET, "[TRY PRIMARY (slot {slot}, tkt = {ticket_id:032x})]");
+			let secret = epoch.tickets_aux.get(&ticket_id)?;
+			let erased_pair = ed25519::Pair::from_seed(&secret.erased_seed);
+			if erased_pair.public() != ticket_body.erased_public {
+				warn!(target: LOG_TARGET, "Ticket {ticket_id:032x} secret doesn't match ticket body");
+				return None
+			}
+			let message = ticket_claim_message(slot, epoch_index, secret.authority_idx);
+			let erased_signature = erased_pair.sign(&message);
+			(secret.authority_idx, Some(TicketClaim { erased_signature }))
+		},
+		None => {
+			debug!(target: LOG_TARGET, "[TRY SECONDARY (slot {slot})]");
+			let expected_author = fallback_slot_author(slot, &epoch.authorities, randomness)?;
+			let authority_idx = epoch.authorities.iter().position(|a| a == expected_author)?;
+			(authority_idx as AuthorityIndex, None)
+		},
+	};
+
+	let authority_id = epoch.authorities.get(authority_idx as usize)?;
+
+	let data = vrf::slot_claim_sign_data(&randomness, slot, epoch_index);
+	let vrf_signature = keystore
+		.bandersnatch_vrf_sign(AuthorityId::ID, authority_id.as_ref(), &data)
+		.ok()
+		.flatten()?;
+
+	let claim = SlotClaim { authority_idx, slot, vrf_signature, ticket_claim };
+
+	Some((claim, authority_id.clone()))
+}
+
+/// Generate the tickets for the given epoch.
+///
+/// Tickets additional information used to claim the slots are stored within the
+/// epoch `tickets_aux` map, while the returned envelopes are meant to be submitted
+/// on-chain.
+///
+/// The ring verifier built from the epoch authorities must match the one built by
+/// the runtime, thus the ring context must be the one exposed by the runtime.
+pub fn generate_epoch_tickets(
+	epoch: &mut Epoch,
+	keystore: &KeystorePtr,
+	ring_ctx: &vrf::RingContext,
+) -> Vec<TicketEnvelope> {
+	let mut tickets = Vec::new();
+	let mut tickets_aux = Vec::new();
+
+	let threshold = ticket_id_threshold(
+		epoch.config.redundancy_factor,
+		epoch.length,
+		epoch.config.attempts_number,
+		epoch.authorities.len() as u32,
+	);
+	debug!(target: LOG_TARGET, "Generating tickets for epoch {} @ slot {}", epoch.index, epoch.start);
+	debug!(target: LOG_TARGET, "    threshold: {threshold:032x}");
+
+	// We need a list of raw unwrapped keys
+	let pks: Vec<_> = epoch.authorities.iter().map(|a| *a.as_ref()).collect();
+
+	for (authority_idx, authority_id) in epoch.authorities.iter().enumerate() {
+		if !keystore.has_keys(&[(authority_id.to_raw_vec(), AuthorityId::ID)]) {
+			continue
+		}
+
+		debug!(target: LOG_TARGET, ">>> Generating new ring prover key...");
+		let prover = ring_ctx.prover(&pks, authority_idx);
+		debug!(target: LOG_TARGET, ">>> ...done");
+
+		let make_ticket = |attempt_idx| {
+			// Ticket id and threshold check.
+			let ticket_id_input = vrf::ticket_id_input(&epoch.randomness, attempt_idx, epoch.index);
+			let ticket_id_pre_output = keystore
+				.bandersnatch_vrf_pre_output(
+					AuthorityId::ID,
+					authority_id.as_ref(),
+					&ticket_id_input,
+				)
+				.ok()??;
+			let ticket_id = vrf::make_ticket_id(&ticket_id_pre_output);
+			if ticket_id >= threshold {
+				return None
+			}
+
+			// Erased key.
+			let (erased_pair, erased_seed) = ed25519::Pair::generate();
+			let erased_public = erased_pair.public();
+
+			// Revealed key.
+			let (revealed_pair, _) = ed25519::Pair::generate();
+			let revealed_public = revealed_pair.public();
+
+			let body = TicketBody { attempt_idx, erased_public, revealed_public };
+
+			debug!(target: LOG_TARGET, ">>> Creating ring proof for attempt {}", attempt_idx);
+			let sign_data = vrf::ticket_body_sign_data(&body, ticket_id_input);
+
+			let signature = keystore
+				.bandersnatch_ring_vrf_sign(
+					AuthorityId::ID,
+					authority_id.as_ref(),
+					&sign_data,
+					&prover,
+				)
+				.ok()??;
+			debug!(target: LOG_TARGET, ">>> ...done");
+
+			let ticket_envelope = TicketEnvelope { body, signature };
+			let ticket_secret =
+				TicketSecret { authority_idx: authority_idx as AuthorityIndex, erased_seed };
+			Some((ticket_id, ticket_envelope, ticket_secret))
+		};
+
+		for attempt in 0..epoch.config.attempts_number {
+			if let Some((ticket_id, ticket_envelope, ticket_secret)) = make_ticket(attempt) {
+				debug!(target: LOG_TARGET, "    → {ticket_id:032x}");
+				tickets.push(ticket_envelope);
+				tickets_aux.push((ticket_id, ticket_secret));
+			}
+		}
+	}
+
+	epoch.tickets_aux.extend(tickets_aux);
+
+	tickets
+}
+
+struct SlotWorker<B: BlockT, C, E, I, SO, L, BS> {
+	client: Arc<C>,
+	block_import: I,
+	env: E,
+	sync_oracle: SO,
+	justification_sync_link: L,
+	force_authoring: bool,
+	backoff_authoring_blocks: Option<BS>,
+	keystore: KeystorePtr,
+	epoch_changes: SharedEpochChanges<B, Epoch>,
+	config: SassafrasConfiguration,
+	block_proposal_slot_portion: SlotProportion,
+	max_block_proposal_slot_portion: Option<SlotProportion>,
+	telemetry: Option<TelemetryHandle>,
+}
+
+#[async_trait::async_trait]
+impl<B, C, E, I, ER, SO, L, BS> sc_consensus_slots::SimpleSlotWorker<B>
+	for SlotWorker<B, C, E, I, SO, L, BS>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + HeaderMetadata<B, Error = ClientError>,
+	C::Api: SassafrasApi<B>,
+	E: Environment<B, Error = ER> + Send + Sync,
+	E::Proposer: Proposer<B, Error = ER>,
+	I: BlockImport<B> + Send + Sync + 'static,
+	SO: SyncOracle + Send + Clone + Sync,
+	L: JustificationSyncLink<B>,
+	BS: BackoffAuthoringBlocksStrategy<NumberFor<B>> + Send + Sync,
+	ER: std::error::Error + Send + From<ConsensusError> + From<I::Error> + 'static,
+{
+	type Claim = (SlotClaim, AuthorityId);
+	type SyncOracle = SO;
+	type JustificationSyncLink = L;
+	type CreateProposer =
+		Pin<Box<dyn Future<Output = Result<E::Proposer, ConsensusError>> + Send + 'static>>;
+	type Proposer = E::Proposer;
+	type BlockImport = I;
+	type AuxData = ViableEpochDescriptor<B::Hash, NumberFor<B>, Epoch>;
+
+	fn logging_target(&self) -> &'static str {
+		LOG_TARGET
+	}
+
+	fn block_import(&mut self) -> &mut Self::BlockImport {
+		&mut self.block_import
+	}
+
+	fn aux_data(&self, parent: &B::Header, slot: Slot) -> Result<Self::AuxData, ConsensusError> {
+		self.epoch_changes
+			.shared_data()
+			.epoch_descriptor_for_child_of(
+				descendent_query(&*self.client),
+				&parent.hash(),
+				*parent.number(),
+				slot,
+			)
+			.map_err(|e| ConsensusError::ChainLookup(e.to_string()))?
+			.ok_or(ConsensusError::InvalidAuthoritiesSet)
+	}
+
+	fn authorities_len(&self, epoch_descriptor: &Self::AuxData) -> Option<usize> {
+		self.epoch_changes
+			.shared_data()
+			.viable_epoch(epoch_descriptor, |slot| Epoch::genesis(&self.config, slot))
+			.map(|epoch| epoch.as_ref().authorities.len())
+	}
+
+	async fn claim_slot(
+		&mut self,
+		parent_header: &B::Header,
+		slot: Slot,
+		epoch_descriptor: &ViableEpochDescriptor<B::Hash, NumberFor<B>, Epoch>,
+	) -> Option<Self::Claim> {
+		debug!(target: LOG_TARGET, "Attempting to claim slot {}", slot);
+
+		// Get the next slot ticket from the runtime.
+		let maybe_ticket = self
+			.client
+			.runtime_api()
+			.slot_ticket(parent_header.hash(), slot)
+			.inspect_err(|err| warn!(target: LOG_TARGET, "Error fetching slot ticket: {}", err))
+			.ok()?;
+
+		let claim = claim_slot(
+			slot,
+			self.epoch_changes
+				.shared_data()
+				.viable_epoch(epoch_descriptor, |slot| Epoch::genesis(&self.config, slot))?
+				.as_ref(),
+			maybe_ticket,
+			&self.keystore,
+		);
+
+		if claim.is_some() {
+			debug!(target: LOG_TARGET, "Claimed slot {}", slot);
+		}
+
+		claim
+	}
+
+	fn pre_digest_data(&self, _slot: Slot, claim: &Self::Claim) -> Vec<DigestItem> {
+		vec![DigestItem::from(&claim.0)]
+	}
+
+	async fn block_import_params(
+		&self,
+		header: B::Header,
+		header_hash: &B::Hash,
+		body: Vec<B::Extrinsic>,
+		storage_changes: StorageChanges<B>,
+		(_, public): Self::Claim,
+		epoch_descriptor: Self::AuxData,
+	) -> Result<BlockImportParams<B>, ConsensusError> {
+		let signature: AuthoritySignature = self
+			.keystore
+			.bandersnatch_sign(
+				<AuthorityId as AppCrypto>::ID,
+				public.as_ref(),
+				header_hash.as_ref(),
+			)
+			.map_err(|e| ConsensusError::CannotSign(format!("{}. Key: {:?}", e, public)))?
+			.ok_or_else(|| {
+				ConsensusError::CannotSign(format!(
+					"Could not find key in keystore. Key: {:?}",
+					public
+				))
+			})?
+			.into();
+
+		let digest_item = DigestItem::from(&signature);
+
+		let mut import_block = BlockImportParams::new(BlockOrigin::Own, header);
+		import_block.post_digests.push(digest_item);
+		import_block.body = Some(body);
+		import_block.state_action =
+			StateAction::ApplyChanges(sc_consensus::StorageChanges::Changes(storage_changes));
+		import_block
+			.insert_intermediate(INTERMEDIATE_KEY, SassafrasIntermediate::<B> { epoch_descriptor });
+
+		Ok(import_block)
+	}
+
+	fn force_authoring(&self) -> bool {
+		self.force_authoring
+	}
+
+	fn should_backoff(&self, slot: Slot, chain_head: &B::Header) -> bool {
+		if let Some(ref strategy) = self.backoff_authoring_blocks {
+			if let Ok(chain_head_slot) = find_slot::<B>(chain_head) {
+				return strategy.should_backoff(
+					*chain_head.number(),
+					chain_head_slot,
+					self.client.info().finalized_number,
+					slot,
+					self.logging_target(),
+				)
+			}
+		}
+		false
+	}
+
+	fn sync_oracle(&mut self) -> &mut Self::SyncOracle {
+		&mut self.sync_oracle
+	}
+
+	fn justification_sync_link(&mut self) -> &mut Self::JustificationSyncLink {
+		&mut self.justification_sync_link
+	}
+
+	fn proposer(&mut self, block: &B::Header) -> Self::CreateProposer {
+		Box::pin(self.env.init(block).map_err(|e| ConsensusError::ClientImport(e.to_string())))
+	}
+
+	fn telemetry(&self) -> Option<TelemetryHandle> {
+		self.telemetry.clone()
+	}
+
+	fn proposing_remaining_duration(&self, slot_info: &SlotInfo<B>) -> Duration {
+		let parent_slot = find_slot::<B>(&slot_info.chain_head).ok();
+
+		sc_consensus_slots::proposing_remaining_duration(
+			parent_slot,
+			slot_info,
+			&self.block_proposal_slot_portion,
+			self.max_block_proposal_slot_portion.as_ref(),
+			sc_consensus_slots::SlotLenienceType::Exponential,
+			self.logging_target(),
+		)
+	}
+}
+
+/// Number of blocks after which the tickets which have not been included on-chain yet
+/// are submitted again.
+const TICKETS_RESUBMISSION_INTERVAL: u32 = 4;
+
+/// Last slot of the submission window of the tickets for the given epoch.
+///
+/// Tickets are accepted during the first half of the epoch preceding the target epoch.
+pub(crate) fn tickets_submission_deadline(epoch: &Epoch) -> Slot {
+	let half_epoch = epoch.length - epoch.length / 2;
+	epoch.start.saturating_sub(half_epoch as u64).into()
+}
+
+/// Tickets of an epoch which are being submitted on-chain.
+struct PendingTickets<B: BlockT> {
+	/// Identifier of the epoch the tickets are for.
+	epoch_identifier: EpochIdentifier<B::Hash, NumberFor<B>>,
+	/// Tickets, once generated.
+	tickets: Option<Vec<TicketEnvelope>>,
+	/// Number of the best block when the tickets were last submitted.
+	submitted_at: Option<NumberFor<B>>,
+}
+
+/// Tickets generation and submission worker.
+///
+/// Every time a new best block announcing the next epoch is imported, the tickets for such
+/// epoch are scheduled for submission. They are generated and submitted on-chain once the
+/// ring verifier key of the next epoch authorities is available, and the tickets which are
+/// not included on-chain are submitted again every [`TICKETS_RESUBMISSION_INTERVAL`] blocks
+/// until the end of the submission window.
+async fn tickets_worker<B, C>(
+	client: Arc<C>,
+	keystore: KeystorePtr,
+	epoch_changes: SharedEpochChanges<B, Epoch>,
+	offchain_tx_pool_factory: OffchainTransactionPoolFactory<B>,
+) where
+	B: BlockT,
+	C: BlockchainEvents<B> + ProvideRuntimeApi<B> + AuxStore,
+	C::Api: SassafrasApi<B>,
+{
+	let mut notifications = client.import_notification_stream();
+	let mut pending: Option<PendingTickets<B>> = None;
+
+	while let Some(notification) = notifications.next().await {
+		if !notification.is_new_best || notification.origin == BlockOrigin::NetworkInitialSync {
+			continue
+		}
+
+		match find_next_epoch_digest::<B>(&notification.header) {
+			Ok(Some(_)) => {
+				let number = *notification.header.number();
+				let position = if number == One::one() {
+					EpochIdentifierPosition::Genesis1
+				} else {
+					EpochIdentifierPosition::Regular
+				};
+				let epoch_identifier =
+					EpochIdentifier { position, hash: notification.hash, number };
+				pending =
+					Some(PendingTickets { epoch_identifier, tickets: None, submitted_at: None });
+			},
+			Ok(None) => (),
+			Err(err) => {
+				warn!(target: LOG_TARGET, "Error fetching next epoch digest: {}", err);
+				continue
+			},
+		}
+
+		let Some(pending_tickets) = pending.as_mut() else { continue };
+		let Some(mut epoch) =
+			epoch_changes.shared_data().epoch(&pending_tickets.epoch_identifier).cloned()
+		else {
+			warn!(
+				target: LOG_TARGET,
+				"Unexpected missing epoch data for {:?}", pending_tickets.epoch_identifier
+			);
+			pending = None;
+			continue
+		};
+
+		let slot = match find_slot::<B>(&notification.header) {
+			Ok(slot) => slot,
+			Err(err) => {
+				warn!(target: LOG_TARGET, "Error fetching slot: {}", err);
+				continue
+			},
+		};
+		if slot > tickets_submission_deadline(&epoch) {
+			debug!(target: LOG_TARGET, "Tickets submission window closed for epoch {}", epoch.index);
+			pending = None;
+			continue
+		}
+
+		match client.runtime_api().ring_verifier_key_ready(notification.hash) {
+			Ok(true) => (),
+			Ok(false) => {
+				debug!(target: LOG_TARGET, "Ring verifier key not ready, delaying tickets");
+				continue
+			},
+			Err(err) => {
+				warn!(target: LOG_TARGET, "Error fetching ring verifier key status: {}", err);
+				continue
+			},
+		}
+
+		let tickets = match &pending_tickets.tickets {
+			Some(tickets) => tickets,
+			None => {
+				let ring_ctx = match client.runtime_api().ring_context(notification.hash) {
+					Ok(Some(ring_ctx)) => ring_ctx,
+					Ok(None) => {
+						debug!(target: LOG_TARGET, "Ring context not initialized, skipping tickets");
+						pending = None;
+						continue
+					},
+					Err(err) => {
+						warn!(target: LOG_TARGET, "Error fetching ring context: {}", err);
+						continue
+					},
+				};
+
+				// Generating the tickets is expensive, thus we don't hold the epoch changes
+				// lock while doing it.
+				let tickets = generate_epoch_tickets(&mut epoch, &keystore, &ring_ctx);
+				if tickets.is_empty() {
+					pending = None;
+					continue
+				}
+
+				// Store tickets secrets for later usage.
+				let mut epoch_changes = epoch_changes.shared_data();
+				let Some(target_epoch) = epoch_changes.epoch_mut(&pending_tickets.epoch_identifier)
+				else {
+					warn!(
+						target: LOG_TARGET,
+						"Epoch {:?} has been pruned", pending_tickets.epoch_identifier
+					);
+					pending = None;
+					continue
+				};
+				target_epoch.tickets_aux = std::mem::take(&mut epoch.tickets_aux);
+
+				if let Err(err) =
+					aux_schema::write_epoch_changes::<B, _, _>(&epoch_changes, |insert| {
+						client.insert_aux(insert, [])
+					}) {
+					warn!(target: LOG_TARGET, "Error persisting tickets secrets: {}", err);
+				}
+
+				pending_tickets.tickets.insert(tickets)
+			},
+		};
+
+		let number = *notification.header.number();
+		if pending_tickets.submitted_at.is_some_and(|submitted_at| {
+			number < submitted_at.saturating_add(TICKETS_RESUBMISSION_INTERVAL.into())
+		}) {
+			continue
+		}
+
+		// Only the tickets which are not on-chain yet are submitted.
+		let mut unincluded = Vec::with_capacity(tickets.len());
+		for ticket in tickets {
+			let ticket_id = vrf::make_ticket_id(&ticket.signature.pre_output);
+			match client.runtime_api().ticket_body(notification.hash, ticket_id) {
+				Ok(Some(_)) => (),
+				Ok(None) => unincluded.push(ticket.clone()),
+				Err(err) => {
+					warn!(target: LOG_TARGET, "Error fetching ticket {:032x}: {}", ticket_id, err);
+					unincluded.push(ticket.clone());
+				},
+			}
+		}
+		if unincluded.is_empty() {
+			debug!(target: LOG_TARGET, "All the tickets for epoch {} are on-chain", epoch.index);
+			pending = None;
+			continue
+		}
+
+		// Submit tickets using the runtime tickets submission extrinsic.
+		let mut runtime_api = client.runtime_api();
+
+		// Register the offchain tx pool to be able to use it from the runtime.
+		runtime_api.register_extension(
+			offchain_tx_pool_factory.offchain_transaction_pool(notification.hash),
+		);
+
+		let tickets_count = unincluded.len();
+		for chunk in unincluded.chunks(epoch.length as usize) {
+			match runtime_api.submit_tickets_unsigned_extrinsic(notification.hash, chunk.to_vec()) {
+				Ok(true) => (),
+				Ok(false) => warn!(target: LOG_TARGET, "Error submitting tickets: unknown reason"),
+				Err(err) => warn!(target: LOG_TARGET, "Error submitting tickets: {}", err),
+			}
+		}
+		info!(
+			target: LOG_TARGET,
+			"🎫 Submitted {} tickets for epoch {}", tickets_count, epoch.index
+		);
+		pending_tickets.submitted_at = Some(number);
+	}
+}
+
+/// Parameters for Sassafras.
+pub struct SassafrasParams<B: BlockT, C, SC, E, I, SO, L, CIDP, BS> {
+	/// The keystore that manages the keys of the node.
+	pub keystore: KeystorePtr,
+
+	/// The client to use
+	pub client: Arc<C>,
+
+	/// The SelectChain Strategy
+	pub select_chain: SC,
+
+	/// The environment we are producing blocks for.
+	pub env: E,
+
+	/// The underlying block-import object to supply our produced blocks to.
+	/// This must be a `SassafrasBlockImport` or a wrapper of it, otherwise
+	/// critical consensus logic will be omitted.
+	pub block_import: I,
+
+	/// A sync oracle
+	pub sync_oracle: SO,
+
+	/// Hook into the sync module to control the justification sync process.
+	pub justification_sync_link: L,
+
+	/// Something that can create the inherent data providers.
+	pub create_inherent_data_providers: CIDP,
+
+	/// Force authoring of blocks even if we are offline
+	pub force_authoring: bool,
+
+	/// Strategy and parameters for backing off block production.
+	pub backoff_authoring_blocks: Option<BS>,
+
+	/// The source of timestamps for relative slots
+	pub sassafras_link: SassafrasLink<B>,
+
+	/// The proportion of the slot dedicated to proposing.
+	///
+	/// The block proposing will be limited to this proportion of the slot from the starting of the
+	/// slot. However, the proposing can still take longer when there is some lenience factor
+	/// applied, because there were no blocks produced for some slots.
+	pub block_proposal_slot_portion: SlotProportion,
+
+	/// The maximum proportion of the slot dedicated to proposing with any lenience factor applied
+	/// due to no blocks being produced.
+	pub max_block_proposal_slot_portion: Option<SlotProportion>,
+
+	/// Handle use to report telemetries.
+	pub telemetry: Option<TelemetryHandle>,
+
+	/// The offchain transaction pool factory.
+	///
+	/// Will be used when submitting tickets.
+	pub offchain_tx_pool_factory: OffchainTransactionPoolFactory<B>,
+}
+
+/// Start the Sassafras worker.
+pub fn start_sassafras<B, C, SC, E, I, SO, CIDP, BS, L, ER>(
+	SassafrasParams {
+		keystore,
+		client,
+		select_chain,
+		env,
+		block_import,
+		sync_oracle,
+		justification_sync_link,
+		create_inherent_data_providers,
+		force_authoring,
+		backoff_authoring_blocks,
+		sassafras_link,
+		block_proposal_slot_portion,
+		max_block_proposal_slot_portion,
+		telemetry,
+		offchain_tx_pool_factory,
+	}: SassafrasParams<B, C, SC, E, I, SO, L, CIDP, BS>,
+) -> Result<SassafrasWorker, ConsensusError>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>
+		+ HeaderBackend<B>
+		+ HeaderMetadata<B, Error = ClientError>
+		+ BlockchainEvents<B>
+		+ AuxStore
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: SassafrasApi<B>,
+	SC: SelectChain<B> + 'static,
+	E: Environment<B, Error = ER> + Send + Sync + 'static,
+	E::Proposer: Proposer<B, Error = ER>,
+	I: BlockImport<B, Error = ConsensusError> + Send + Sync + 'static,
+	SO: SyncOracle + Send + Sync + Clone + 'static,
+	L: JustificationSyncLink<B> + 'static,
+	CIDP: CreateInherentDataProviders<B, ()> + Send + Sync + 'static,
+	CIDP::InherentDataProviders: InherentDataProviderExt + Send,
+	BS: BackoffAuthoringBlocksStrategy<NumberFor<B>> + Send + Sync + 'static,
+	ER: std::error::Error + Send + From<ConsensusError> + From<I::Error> + 'static,
+{
+	info!(target: LOG_TARGET, "🍁 Starting Sassafras Authorship worker");
+
+	let slot_worker = SlotWorker {
+		client: client.clone(),
+		block_import,
+		env,
+		sync_oracle: sync_oracle.clone(),
+		justification_sync_link,
+		force_authoring,
+		backoff_authoring_blocks,
+		keystore: keystore.clone(),
+		epoch_changes: sassafras_link.epoch_changes.clone(),
+		config: sassafras_link.config.clone(),
+		block_proposal_slot_portion,
+		max_block_proposal_slot_portion,
+		telemetry,
+	};
+
+	let slot_worker = sc_consensus_slots::start_slot_worker(
+		sassafras_link.config.slot_duration(),
+		select_chain,
+		sc_consensus_slots::SimpleSlotWorkerToSlotWorker(slot_worker),
+		sync_oracle,
+		create_inherent_data_providers,
+	);
+
+	let tickets_worker =
+		tickets_worker(client, keystore, sassafras_link.epoch_changes, offchain_tx_pool_factory);
+
+	let inner = future::select(Box::pin(slot_worker), Box::pin(tickets_worker));
+
+	Ok(SassafrasWorker { inner: Box::pin(inner.map(|_| ())) })
+}
+
+/// Worker for Sassafras which implements `Future<Output=()>`. This must be polled.
+#[must_use]
+pub struct SassafrasWorker {
+	inner: Pin<Box<dyn Future<Output = ()> + Send + 'static>>,
+}
+
+impl Future for SassafrasWorker {
+	type Output = ();
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Self::Output> {
+		self.inner.as_mut().poll(cx)
+	}
+}
@@ -0,0 +1,595 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Sassafras (Semi Anonymous Sortition of Staked Assignees For Fixed-time Rhythmic Assignment
+//! of Slots)
+//!
+//! Sassafras is a constant-time block production protocol which aims to assign exactly one
+//! block producer to each slot of an epoch, while keeping the producer identity secret until
+//! the block is actually produced.
+//!
+//! At the beginning of each epoch every authority generates a set of *tickets* for the next
+//! epoch. A ticket identifier is the output of a VRF evaluated over an input which can't be
+//! controlled by the ticket owner. Tickets are submitted on-chain anonymously, together with a
+//! ring VRF proof attesting that the ticket has been generated by one of the next epoch
+//! authorities. The runtime retains the tickets with the lowest identifiers and assigns them
+//! to the next epoch slots.
+//!
+//! When a slot comes, the authority owning the ticket assigned to the slot claims it by
+//! revealing the ticket ownership via a signature produced with the ephemeral key committed in
+//! the ticket body. Slots without an associated ticket are assigned to a *fallback* author
+//! picked by index:
+//!
+//! `blake2_256(epoch_randomness ++ slot_number) % authorities_len`.
+//!
+//! The fork choice rule is weight-based, where weight equals the number of blocks produced
+//! using a ticket claim. We will pick the heaviest chain and will go with the longest one in
+//! case of a tie.
+//!
+//! An in-depth description and analysis of the protocol can be found here:
+//! <https://research.web3.foundation/Polkadot/protocols/block-production/SASSAFRAS>
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use std::{
+	collections::{BTreeMap, HashSet},
+	ops::{Deref, DerefMut},
+	sync::Arc,
+};
+
+use codec::{Decode, Encode};
+use futures::prelude::*;
+use log::{debug, trace, warn};
+use prometheus_endpoint::Registry;
+
+use sc_client_api::{backend::AuxStore, Backend as BackendT, UsageProvider};
+use sc_consensus::import_queue::{BasicQueue, BoxJustificationImport, DefaultImportQueue};
+use sc_consensus_epochs::{descendent_query, Epoch as EpochT, EpochChangesFor};
+use sc_consensus_slots::InherentDataProviderExt;
+use sc_telemetry::TelemetryHandle;
+use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder as BlockBuilderApi;
+use sp_blockchain::{
+	Backend as _, Error as ClientError, ForkBackend, HeaderBackend, HeaderMetadata,
+	Result as ClientResult,
+};
+use sp_consensus::Error as ConsensusError;
+use sp_consensus_slots::Slot;
+use sp_core::traits::SpawnEssentialNamed;
+use sp_inherents::CreateInherentDataProviders;
+use sp_runtime::{
+	generic::OpaqueDigestItemId,
+	traits::{Block as BlockT, Header, NumberFor, SaturatedConversion, Zero},
+};
+
+pub use sc_consensus_slots::SlotProportion;
+pub use sp_consensus::SyncOracle;
+pub use sp_consensus_sassafras::{
+	digests::{ConsensusLog, NextEpochDescriptor, SlotClaim},
+	vrf, AuthorityId, AuthorityIndex, AuthorityPair, AuthoritySignature, EpochConfiguration,
+	Randomness, SassafrasApi, SassafrasBlockWeight, SlotDuration, TicketBody, TicketClaim,
+	TicketEnvelope, TicketId, KEY_TYPE, SASSAFRAS_ENGINE_ID,
+};
+
+pub use authorship::{start_sassafras, SassafrasParams, SassafrasWorker};
+pub use aux_schema::load_block_weight as block_weight;
+pub use block_import::{block_import, SassafrasBlockImport};
+pub use verification::SassafrasVerifier;
+
+mod verification;
+
+pub mod authorship;
+pub mod aux_schema;
+pub mod block_import;
+#[cfg(test)]
+mod tests;
+
+const LOG_TARGET: &str = "sassafras";
+
+/// Secret data associated with a ticket generated by the local node.
+///
+/// This data is never shared and allows the ticket owner to claim the slot
+/// the ticket has been assigned to.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct TicketSecret {
+	/// Index of the authority which generated the ticket.
+	pub authority_idx: AuthorityIndex,
+	/// Seed of the ephemeral key whose public counterpart is
+	/// [`TicketBody::erased_public`].
+	pub erased_seed: [u8; 32],
+}
+
+/// Sassafras epoch information augmented with client specific data.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct Epoch {
+	inner: sp_consensus_sassafras::Epoch,
+	/// Secret data of the tickets generated by this node for this epoch.
+	pub tickets_aux: BTreeMap<TicketId, TicketSecret>,
+}
+
+impl Deref for Epoch {
+	type Target = sp_consensus_sassafras::Epoch;
+
+	fn deref(&self) -> &Self::Target {
+		&self.inner
+	}
+}
+
+impl DerefMut for Epoch {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.inner
+	}
+}
+
+impl From<sp_consensus_sassafras::Epoch> for Epoch {
+	fn from(epoch: sp_consensus_sassafras::Epoch) -> Self {
+		Epoch { inner: epoch, tickets_aux: Default::default() }
+	}
+}
+
+impl EpochT for Epoch {
+	type NextEpochDescriptor = (NextEpochDescriptor, EpochConfiguration);
+	type Slot = Slot;
+
+	fn increment(&self, (descriptor, config): (NextEpochDescriptor, EpochConfiguration)) -> Epoch {
+		sp_consensus_sassafras::Epoch {
+			index: self.index + 1,
+			start: self.start + self.length as u64,
+			length: self.length,
+			randomness: descriptor.randomness,
+			authorities: descriptor.authorities,
+			config,
+		}
+		.into()
+	}
+
+	fn start_slot(&self) -> Slot {
+		self.start
+	}
+
+	fn end_slot(&self) -> Slot {
+		self.start + self.length as u64
+	}
+}
+
+impl Epoch {
+	/// Create the genesis epoch (epoch #0).
+	///
+	/// This is defined to start at the slot of the first block, so that has to be provided.
+	pub fn genesis(config: &SassafrasConfiguration, slot: Slot) -> Epoch {
+		let mut epoch: Epoch = config.genesis_epoch.clone().into();
+		epoch.index = 0;
+		epoch.start = slot;
+		epoch
+	}
+
+	/// Clone and tweak epoch information to refer to the specified slot.
+	///
+	/// All the information which depends on the slot value is recomputed and assigned
+	/// to the returned epoch instance.
+	///
+	/// The `slot` must be greater than or equal the original epoch start slot,
+	/// if is less this operation is equivalent to a simple clone.
+	pub fn clone_for_slot(&self, slot: Slot) -> Epoch {
+		let mut epoch = self.clone();
+
+		let skipped_epochs = *slot.saturating_sub(self.start) / self.length as u64;
+
+		let index = epoch.index.checked_add(skipped_epochs).expect(
+			"epoch number is u64; it should be strictly smaller than number of slots; \
+				slots relate in some way to wall clock time; \
+				if u64 is not enough we should crash for safety; qed.",
+		);
+
+		let start = skipped_epochs
+			.checked_mul(epoch.length as u64)
+			.and_then(|skipped_slots| epoch.start.checked_add(skipped_slots))
+			.expect(
+				"slot number is u64; it should relate in some way to wall clock time; \
+				 if u64 is not enough we should crash for safety; qed.",
+			);
+
+		if index != epoch.index {
+			// Tickets are bound to the epoch index they were generated for.
+			epoch.tickets_aux.clear();
+		}
+
+		epoch.index = index;
+		epoch.start = Slot::from(start);
+
+		epoch
+	}
+}
+
+/// Errors encountered by the Sassafras routines.
+#[derive(Debug, thiserror::Error)]
+pub enum Error<B: BlockT> {
+	/// Multiple Sassafras pre-runtime digests
+	#[error("Multiple Sassafras pre-runtime digests, rejecting!")]
+	MultipleSlotClaimDigests,
+	/// No Sassafras pre-runtime digest found
+	#[error("No Sassafras pre-runtime digest found")]
+	MissingSlotClaimDigest,
+	/// Multiple Sassafras epoch change digests
+	#[error("Multiple Sassafras epoch change digests, rejecting!")]
+	MultipleEpochChangeDigests,
+	/// Could not fetch epoch
+	#[error("Could not fetch epoch at {0:?}")]
+	FetchEpoch(B::Hash),
+	/// Header rejected: too far in the future
+	#[error("Header {0:?} rejected: too far in the future")]
+	TooFarInFuture(B::Hash),
+	/// Parent unavailable. Cannot import
+	#[error("Parent ({0}) of {1} unavailable. Cannot import")]
+	ParentUnavailable(B::Hash, B::Hash),
+	/// Slot number must increase
+	#[error("Slot number must increase: parent slot: {0}, this slot: {1}")]
+	SlotMustIncrease(Slot, Slot),
+	/// Header has a bad seal
+	#[error("Header {0:?} has a bad seal")]
+	HeaderBadSeal(B::Hash),
+	/// Header is unsealed
+	#[error("Header {0:?} is unsealed")]
+	HeaderUnsealed(B::Hash),
+	/// Slot author not found
+	#[error("Slot author not found")]
+	SlotAuthorNotFound,
+	/// Bad signature
+	#[error("Bad signature on {0:?}")]
+	BadSignature(B::Hash),
+	/// Invalid author: Expected fallback author
+	#[error("Invalid author: Expected fallback author: {0:?}, got: {1:?}.")]
+	InvalidAuthor(AuthorityId, AuthorityId),
+	/// VRF verification failed
+	#[error("VRF verification failed")]
+	VrfVerificationFailed,
+	/// Slot has an associated ticket but the claim is missing
+	#[error("Missing ticket claim for slot {0}")]
+	MissingTicketClaim(Slot),
+	/// Slot has no associated ticket but a ticket claim was provided
+	#[error("Unexpected ticket claim for slot {0}")]
+	UnexpectedTicketClaim(Slot),
+	/// Ticket claim signature verification failed
+	#[error("Bad ticket claim for ticket {0:032x}")]
+	BadTicketClaim(TicketId),
+	/// Could not fetch parent header
+	#[error("Could not fetch parent header: {0}")]
+	FetchParentHeader(sp_blockchain::Error),
+	/// Expected epoch change to happen.
+	#[error("Expected epoch change to happen at {0:?}, s{1}")]
+	ExpectedEpochChange(B::Hash, Slot),
+	/// Unexpected epoch change
+	#[error("Unexpected epoch change")]
+	UnexpectedEpochChange,
+	/// Parent block has no associated weight
+	#[error("Parent block of {0} has no associated weight")]
+	ParentBlockNoAssociatedWeight(B::Hash),
+	/// Check inherents error
+	#[error("Checking inherents failed: {0}")]
+	CheckInherents(sp_inherents::Error),
+	/// Unhandled check inherents error
+	#[error("Checking inherents unhandled error: {}", String::from_utf8_lossy(.0))]
+	CheckInherentsUnhandled(sp_inherents::InherentIdentifier),
+	/// Create inherents error.
+	#[error("Creating inherents failed: {0}")]
+	CreateInherents(sp_inherents::Error),
+	/// Client error
+	#[error(transparent)]
+	Client(sp_blockchain::Error),
+	/// Runtime Api error.
+	#[error(transparent)]
+	RuntimeApi(sp_api::ApiError),
+	/// Fork tree error
+	#[error(transparent)]
+	ForkTree(Box<fork_tree::Error<sp_blockchain::Error>>),
+}
+
+impl<B: BlockT> From<Error<B>> for String {
+	fn from(error: Error<B>) -> String {
+		error.to_string()
+	}
+}
+
+fn sassafras_err<B: BlockT>(error: Error<B>) -> Error<B> {
+	debug!(target: LOG_TARGET, "{}", error);
+	error
+}
+
+/// Sassafras protocol configuration.
+///
+/// Contains the parameters which are not expected to change over the chain lifetime.
+#[derive(Clone, Debug)]
+pub struct SassafrasConfiguration {
+	/// Slot duration.
+	pub slot_duration: SlotDuration,
+	/// Genesis epoch information.
+	///
+	/// Used to construct the first epoch when the first block is imported.
+	pub genesis_epoch: sp_consensus_sassafras::Epoch,
+}
+
+impl SassafrasConfiguration {
+	/// Slot duration.
+	pub fn slot_duration(&self) -> SlotDuration {
+		self.slot_duration
+	}
+}
+
+/// Intermediate value passed to block importer.
+pub struct SassafrasIntermediate<B: BlockT> {
+	/// The epoch descriptor.
+	pub epoch_descriptor: sc_consensus_epochs::ViableEpochDescriptor<B::Hash, NumberFor<B>, Epoch>,
+}
+
+/// Intermediate key for Sassafras engine.
+pub static INTERMEDIATE_KEY: &[u8] = b"sass1";
+
+/// Read protocol configuration from the runtime state.
+///
+/// Genesis epoch information is read from the genesis block state. If the genesis
+/// state is not available (e.g. the node has been warp synced) the best block state is
+/// used instead. This is not an issue as in such case the genesis epoch data is never
+/// going to be used to import the first block.
+pub fn configuration<B: BlockT, C>(client: &C) -> ClientResult<SassafrasConfiguration>
+where
+	C: AuxStore + ProvideRuntimeApi<B> + UsageProvider<B>,
+	C::Api: SassafrasApi<B>,
+{
+	let info = client.usage_info().chain;
+	let runtime_api = client.runtime_api();
+
+	let (at_hash, genesis_epoch) = match runtime_api.current_epoch(info.genesis_hash) {
+		Ok(epoch) => (info.genesis_hash, epoch),
+		Err(err) => {
+			debug!(
+				target: LOG_TARGET,
+				"Genesis state not available ({}). Reading config from best block", err
+			);
+			(info.best_hash, runtime_api.current_epoch(info.best_hash)?)
+		},
+	};
+	let slot_duration = match runtime_api.api_version::<dyn SassafrasApi<B>>(at_hash)? {
+		Some(version) if version >= 3 => runtime_api.slot_duration(at_hash)?,
+		_ =>
+			return Err(ClientError::VersionInvalid(
+				"Unsupported or invalid SassafrasApi version".to_string(),
+			)),
+	};
+
+	Ok(SassafrasConfiguration { slot_duration, genesis_epoch })
+}
+
+/// Extract the Sassafras slot claim from the given header.
+///
+/// Slot claim digest is mandatory, the function will return `Err` if none is found.
+pub fn find_slot_claim<B: BlockT>(header: &B::Header) -> Result<SlotClaim, Error<B>> {
+	let mut claim: Option<_> = None;
+	for log in header.digest().logs() {
+		trace!(target: LOG_TARGET, "Checking log {:?}, looking for slot claim digest", log);
+		match (SlotClaim::try_from(log), claim.is_some()) {
+			(Ok(_), true) => return Err(sassafras_err(Error::MultipleSlotClaimDigests)),
+			(Err(_), _) => trace!(target: LOG_TARGET, "Ignoring digest not meant for us"),
+			(Ok(c), false) => claim = Some(c),
+		}
+	}
+	claim.ok_or_else(|| sassafras_err(Error::MissingSlotClaimDigest))
+}
+
+/// Extract the slot from the given header.
+///
+/// Genesis block doesn't contain a slot claim so we return slot 0 in that case.
+pub fn find_slot<B: BlockT>(header: &B::Header) -> Result<Slot, Error<B>> {
+	if header.number().is_zero() {
+		return Ok(0.into())
+	}
+	find_slot_claim::<B>(header).map(|claim| claim.slot)
+}
+
+/// Extract the Sassafras epoch change digest from the given header, if it exists.
+fn find_next_epoch_digest<B: BlockT>(
+	header: &B::Header,
+) -> Result<Option<NextEpochDescriptor>, Error<B>> {
+	let mut epoch_digest: Option<_> = None;
+	for log in header.digest().logs() {
+		trace!(target: LOG_TARGET, "Checking log {:?}, looking for epoch change digest.", log);
+		let log = log.try_to::<ConsensusLog>(OpaqueDigestItemId::Consensus(&SASSAFRAS_ENGINE_ID));
+		match (log, epoch_digest.is_some()) {
+			(Some(ConsensusLog::NextEpochData(_)), true) =>
+				return Err(sassafras_err(Error::MultipleEpochChangeDigests)),
+			(Some(ConsensusLog::NextEpochData(epoch)), false) => epoch_digest = Some(epoch),
+			_ => trace!(target: LOG_TARGET, "Ignoring digest not meant for us"),
+		}
+	}
+
+	Ok(epoch_digest)
+}
+
+/// State that must be shared between the import queue and the authoring logic.
+#[derive(Clone)]
+pub struct SassafrasLink<B: BlockT> {
+	epoch_changes: sc_consensus_epochs::SharedEpochChanges<B, Epoch>,
+	config: SassafrasConfiguration,
+}
+
+impl<B: BlockT> SassafrasLink<B> {
+	/// Get the epoch changes of this link.
+	pub fn epoch_changes(&self) -> &sc_consensus_epochs::SharedEpochChanges<B, Epoch> {
+		&self.epoch_changes
+	}
+
+	/// Get the config of this link.
+	pub fn config(&self) -> &SassafrasConfiguration {
+		&self.config
+	}
+}
+
+/// Parameters passed to [`import_queue`].
+pub struct ImportQueueParams<'a, B: BlockT, BI, C, CIDP, SelectChain, Spawn> {
+	/// The Sassafras link that is created by [`block_import`].
+	pub link: SassafrasLink<B>,
+	/// The block import that should be wrapped.
+	pub block_import: BI,
+	/// Optional justification import.
+	pub justification_import: Option<BoxJustificationImport<B>>,
+	/// The client to interact with the internals of the node.
+	pub client: Arc<C>,
+	/// A [`SelectChain`](sp_consensus::SelectChain) implementation.
+	///
+	/// Used to determine the best block that should be used as basis when sending an equivocation
+	/// report.
+	pub select_chain: SelectChain,
+	/// Used to crate the inherent data providers.
+	///
+	/// These inherent data providers are then used to create the inherent data that is
+	/// passed to the `check_inherents` runtime call.
+	pub create_inherent_data_providers: CIDP,
+	/// Spawner for spawning futures.
+	pub spawner: &'a Spawn,
+	/// Registry for prometheus metrics.
+	pub registry: Option<&'a Registry>,
+	/// Optional telemetry handle to report telemetry events.
+	pub telemetry: Option<TelemetryHandle>,
+	/// The offchain transaction pool factory.
+	///
+	/// Will be used when sending equivocation reports.
+	pub offchain_tx_pool_factory: OffchainTransactionPoolFactory<B>,
+}
+
+/// Start an import queue for the Sassafras consensus algorithm.
+///
+/// The block import object provided must be the `SassafrasBlockImport` or a wrapper
+/// of it, otherwise crucial import logic will be omitted.
+pub fn import_queue<B: BlockT, C, SelectChain, BI, CIDP, Spawn>(
+	ImportQueueParams {
+		link,
+		block_import,
+		justification_import,
+		client,
+		select_chain,
+		create_inherent_data_providers,
+		spawner,
+		registry,
+		telemetry,
+		offchain_tx_pool_factory,
+	}: ImportQueueParams<'_, B, BI, C, CIDP, SelectChain, Spawn>,
+) -> ClientResult<DefaultImportQueue<B>>
+where
+	BI: sc_consensus::BlockImport<B, Error = ConsensusError> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B>
+		+ HeaderBackend<B>
+		+ HeaderMetadata<B, Error = sp_blockchain::Error>
+		+ AuxStore
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: BlockBuilderApi<B> + SassafrasApi<B> + ApiExt<B>,
+	SelectChain: sp_consensus::SelectChain<B> + 'static,
+	CIDP: CreateInherentDataProviders<B, ()> + Send + Sync + 'static,
+	CIDP::InherentDataProviders: InherentDataProviderExt + Send + Sync,
+	Spawn: SpawnEssentialNamed,
+{
+	let verifier = SassafrasVerifier::new(
+		client,
+		select_chain,
+		create_inherent_data_providers,
+		link.epoch_changes,
+		link.config,
+		telemetry,
+		offchain_tx_pool_factory,
+	);
+
+	Ok(BasicQueue::new(verifier, Box::new(block_import), justification_import, spawner, registry))
+}
+
+/// Reverts protocol aux data to at most the last finalized block.
+///
+/// In particular, epoch-changes and block weights announced after the revert
+/// point are removed.
+pub fn revert<B, C, Backend>(
+	client: Arc<C>,
+	backend: Arc<Backend>,
+	blocks: NumberFor<B>,
+) -> ClientResult<()>
+where
+	B: BlockT,
+	C: AuxStore
+		+ HeaderMetadata<B, Error = sp_blockchain::Error>
+		+ HeaderBackend<B>
+		+ ProvideRuntimeApi<B>
+		+ UsageProvider<B>,
+	C::Api: SassafrasApi<B>,
+	Backend: BackendT<B>,
+{
+	let best_number = client.info().best_number;
+	let finalized = client.info().finalized_number;
+
+	let revertible = blocks.min(best_number - finalized);
+	if revertible == Zero::zero() {
+		return Ok(())
+	}
+
+	let revert_up_to_number = best_number - revertible;
+	let revert_up_to_hash = client.hash(revert_up_to_number)?.ok_or(ClientError::Backend(
+		format!("Unexpected hash lookup failure for block number: {}", revert_up_to_number),
+	))?;
+
+	// Revert epoch changes tree.
+
+	let epoch_changes = aux_schema::load_epoch_changes::<B, C>(&*client)?;
+	let mut epoch_changes = epoch_changes.shared_data();
+
+	if revert_up_to_number == Zero::zero() {
+		// Special case, no epoch changes data were present on genesis.
+		*epoch_changes = EpochChangesFor::<B, Epoch>::default();
+	} else {
+		epoch_changes.revert(descendent_query(&*client), revert_up_to_hash, revert_up_to_number);
+	}
+
+	// Remove block weights added after the revert point.
+
+	let mut weight_keys = HashSet::with_capacity(revertible.saturated_into());
+
+	let leaves = backend.blockchain().leaves()?.into_iter().filter(|&leaf| {
+		sp_blockchain::tree_route(&*client, revert_up_to_hash, leaf)
+			.map(|route| route.retracted().is_empty())
+			.unwrap_or_default()
+	});
+
+	for leaf in leaves {
+		let mut hash = leaf;
+		loop {
+			let meta = client.header_metadata(hash)?;
+			if meta.number <= revert_up_to_number ||
+				!weight_keys.insert(aux_schema::block_weight_key(hash))
+			{
+				// We've reached the revert point or an already processed branch, stop here.
+				break
+			}
+			hash = meta.parent;
+		}
+	}
+
+	let weight_keys: Vec<_> = weight_keys.iter().map(|val| val.as_slice()).collect();
+
+	// Write epoch changes and remove weights in one shot.
+	aux_schema::write_epoch_changes::<B, _, _>(&epoch_changes, |values| {
+		client.insert_aux(values, weight_keys.iter())
+	})
+}
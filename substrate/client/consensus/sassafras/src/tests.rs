@@ -0,0 +1,247 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Sassafras client tests.
+
+use super::*;
+
+use authorship::{
+	claim_slot, fallback_slot_author, generate_epoch_tickets, tickets_submission_deadline,
+};
+use sc_consensus_slots::CheckedHeader;
+use sp_application_crypto::AppCrypto;
+use sp_core::{ed25519, Pair};
+use sp_keyring::BandersnatchKeyring as Keyring;
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystorePtr};
+use sp_runtime::{Digest, DigestItem};
+use substrate_test_runtime_client::runtime::{Block as TestBlock, Header as TestHeader};
+use verification::{check_header, VerificationParams};
+
+const EPOCH_LENGTH: u32 = 10;
+
+fn create_keystore(authority: Keyring) -> KeystorePtr {
+	let keystore = MemoryKeystore::new();
+	keystore
+		.bandersnatch_generate_new(AuthorityId::ID, Some(&authority.to_seed()))
+		.expect("Creates authority key");
+	keystore.into()
+}
+
+fn create_epoch(attempts_number: u32) -> Epoch {
+	let authorities = [Keyring::Alice, Keyring::Bob, Keyring::Charlie]
+		.iter()
+		.map(|k| AuthorityId::from(k.public()))
+		.collect();
+	sp_consensus_sassafras::Epoch {
+		index: 1,
+		start: 100.into(),
+		length: EPOCH_LENGTH,
+		randomness: [3; 32],
+		authorities,
+		config: EpochConfiguration { redundancy_factor: 1000, attempts_number },
+	}
+	.into()
+}
+
+fn make_header(claim: &SlotClaim, keystore: &KeystorePtr, public: &AuthorityId) -> TestHeader {
+	let mut header = TestHeader::new(
+		1,
+		Default::default(),
+		Default::default(),
+		Default::default(),
+		Digest { logs: vec![DigestItem::from(claim)] },
+	);
+	let signature: AuthoritySignature = keystore
+		.bandersnatch_sign(AuthorityId::ID, public.as_ref(), header.hash().as_ref())
+		.unwrap()
+		.unwrap()
+		.into();
+	header.digest_mut().push(DigestItem::from(&signature));
+	header
+}
+
+fn verify(
+	header: TestHeader,
+	claim: &SlotClaim,
+	epoch: &Epoch,
+	maybe_ticket: Option<(TicketId, TicketBody)>,
+) -> Result<CheckedHeader<TestHeader, verification::VerifiedHeaderInfo>, Error<TestBlock>> {
+	let params = VerificationParams { header, claim, slot_now: claim.slot, epoch, maybe_ticket };
+	check_header::<TestBlock>(params)
+}
+
+#[test]
+fn fallback_author_is_always_assigned() {
+	let epoch = create_epoch(1);
+
+	for slot in 0..100 {
+		let author = fallback_slot_author(slot.into(), &epoch.authorities, epoch.randomness);
+		assert!(author.is_some());
+		assert_eq!(author, fallback_slot_author(slot.into(), &epoch.authorities, epoch.randomness));
+	}
+
+	assert!(fallback_slot_author(0.into(), &[], epoch.randomness).is_none());
+}
+
+#[test]
+fn tickets_submission_deadline_is_half_of_previous_epoch() {
+	let mut epoch = create_epoch(1);
+	assert_eq!(tickets_submission_deadline(&epoch), Slot::from(95));
+
+	epoch.length = 11;
+	assert_eq!(tickets_submission_deadline(&epoch), Slot::from(94));
+
+	epoch.start = 3.into();
+	assert_eq!(tickets_submission_deadline(&epoch), Slot::from(0));
+}
+
+#[test]
+fn claim_secondary_slots_works() {
+	sp_tracing::try_init_simple();
+
+	let epoch = create_epoch(1);
+	let keystore = create_keystore(Keyring::Alice);
+	let alice = AuthorityId::from(Keyring::Alice.public());
+
+	let mut claimed = 0;
+	for slot in *epoch.start..*epoch.start + EPOCH_LENGTH as u64 {
+		let slot = Slot::from(slot);
+		let expected = fallback_slot_author(slot, &epoch.authorities, epoch.randomness).unwrap();
+		match claim_slot(slot, &epoch, None, &keystore) {
+			Some((claim, public)) => {
+				assert_eq!(expected, &alice);
+				assert_eq!(public, alice);
+				assert!(claim.ticket_claim.is_none());
+
+				let header = make_header(&claim, &keystore, &public);
+				let res = verify(header, &claim, &epoch, None).unwrap();
+				assert!(matches!(res, CheckedHeader::Checked(..)));
+				claimed += 1;
+			},
+			None => assert_ne!(expected, &alice),
+		}
+	}
+	assert!(claimed > 0);
+}
+
+#[test]
+fn secondary_claim_from_wrong_author_is_rejected() {
+	sp_tracing::try_init_simple();
+
+	let epoch = create_epoch(1);
+	let keystore = create_keystore(Keyring::Alice);
+
+	let slot = (*epoch.start..*epoch.start + EPOCH_LENGTH as u64)
+		.map(Slot::from)
+		.find(|slot| {
+			fallback_slot_author(*slot, &epoch.authorities, epoch.randomness) ==
+				Some(&epoch.authorities[0])
+		})
+		.expect("Alice is expected to be assigned at least one slot");
+
+	let (mut claim, public) = claim_slot(slot, &epoch, None, &keystore).unwrap();
+
+	// Pretend to be Bob.
+	claim.authority_idx = 1;
+	let header = make_header(&claim, &keystore, &public);
+	assert!(matches!(verify(header, &claim, &epoch, None), Err(Error::BadSignature(_))));
+}
+
+#[test]
+fn claim_primary_slot_works() {
+	sp_tracing::try_init_simple();
+
+	let mut epoch = create_epoch(2);
+	let keystore = create_keystore(Keyring::Bob);
+	let ring_ctx = vrf::RingContext::new_testing();
+
+	let tickets = generate_epoch_tickets(&mut epoch, &keystore, &ring_ctx);
+	assert_eq!(tickets.len(), 2);
+	assert_eq!(epoch.tickets_aux.len(), 2);
+
+	let (ticket_id, secret) = epoch.tickets_aux.iter().next().unwrap();
+	assert_eq!(secret.authority_idx, 1);
+	let erased_public = ed25519::Pair::from_seed(&secret.erased_seed).public();
+	let body = tickets
+		.iter()
+		.find(|t| t.body.erased_public == erased_public)
+		.map(|t| t.body.clone())
+		.unwrap();
+	let ticket = Some((*ticket_id, body));
+
+	// Any slot can be assigned to the ticket.
+	let slot = epoch.start + 3;
+	let (claim, public) = claim_slot(slot, &epoch, ticket.clone(), &keystore).unwrap();
+	assert_eq!(public, AuthorityId::from(Keyring::Bob.public()));
+	assert!(claim.ticket_claim.is_some());
+
+	let header = make_header(&claim, &keystore, &public);
+	let res = verify(header.clone(), &claim, &epoch, ticket.clone()).unwrap();
+	assert!(matches!(res, CheckedHeader::Checked(..)));
+
+	// The slot has an associated ticket, thus a secondary claim is rejected.
+	let mut claim_without_ticket = claim.clone();
+	claim_without_ticket.ticket_claim = None;
+	let header = make_header(&claim_without_ticket, &keystore, &public);
+	assert!(matches!(
+		verify(header, &claim_without_ticket, &epoch, ticket.clone()),
+		Err(Error::MissingTicketClaim(_))
+	));
+
+	// Ticket claim can't be used for a different slot.
+	let mut claim_other_slot = claim.clone();
+	claim_other_slot.slot = slot + 1;
+	let header = make_header(&claim_other_slot, &keystore, &public);
+	assert!(matches!(
+		verify(header, &claim_other_slot, &epoch, ticket),
+		Err(Error::BadTicketClaim(_))
+	));
+}
+
+#[test]
+fn tickets_are_not_claimable_without_secrets() {
+	sp_tracing::try_init_simple();
+
+	let mut epoch = create_epoch(1);
+	let keystore = create_keystore(Keyring::Charlie);
+	let ring_ctx = vrf::RingContext::new_testing();
+
+	let tickets = generate_epoch_tickets(&mut epoch, &keystore, &ring_ctx);
+	assert_eq!(tickets.len(), 1);
+	let (ticket_id, _) = epoch.tickets_aux.pop_first().unwrap();
+
+	let ticket = Some((ticket_id, tickets[0].body.clone()));
+	assert!(claim_slot(epoch.start, &epoch, ticket, &keystore).is_none());
+}
+
+#[test]
+fn epoch_clone_for_slot_drops_tickets_secrets() {
+	let mut epoch = create_epoch(1);
+	epoch
+		.tickets_aux
+		.insert(1, TicketSecret { authority_idx: 0, erased_seed: [0; 32] });
+
+	let same = epoch.clone_for_slot(epoch.start + 1);
+	assert_eq!(same.index, epoch.index);
+	assert_eq!(same.tickets_aux.len(), 1);
+
+	let next = epoch.clone_for_slot(epoch.start + EPOCH_LENGTH as u64 * 2);
+	assert_eq!(next.index, epoch.index + 2);
+	assert_eq!(next.start, epoch.start + EPOCH_LENGTH as u64 * 2);
+	assert!(next.tickets_aux.is_empty());
+}
@@ -0,0 +1,543 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Offline consistency check and repair of the database.
+//!
+//! The check walks the canonical chain from the genesis block up to the best and finalized
+//! blocks stored in the meta column. It verifies the headers, bodies and justifications of the
+//! blocks, the state of the finalized block and the state-db journals, and reports the last
+//! block up to which the chain is consistent.
+//!
+//! The repair rolls the best and finalized blocks back to that block, the same way reverting
+//! blocks does, and removes the entries that can't be decoded. The database must not be used by
+//! a running node meanwhile.
+
+use crate::{
+	apply_state_commit, columns,
+	utils::{self, meta_keys, DatabaseType},
+	DatabaseSource, DbExtrinsic, DbHash, StateMetaDb,
+};
+use codec::Decode;
+use log::info;
+use sc_client_api::leaves::LeafSet;
+use sc_state_db::{LastCanonicalized, StateDb};
+use sp_blockchain::{BlockGapType, Error as ClientError, Result as ClientResult};
+use sp_database::{Database, Transaction};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{
+		Block as BlockT, HashingFor, Header as HeaderT, NumberFor, One, SaturatedConversion, Zero,
+	},
+	Justifications,
+};
+use std::{fmt, sync::Arc};
+
+/// Number of blocks between two progress reports.
+const PROGRESS_INTERVAL: u32 = 100_000;
+
+/// Number of finalized blocks searched for a state when the finalized state is missing.
+const MAX_FINALIZED_STATE_SEARCH: u32 = 4096;
+
+/// An inconsistency found in the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue<N, H> {
+	/// A meta entry points to a block that isn't part of the canonical chain.
+	DanglingMetaEntry(&'static str),
+	/// No canonical block is stored at this height.
+	MissingCanonicalBlock(N),
+	/// The header of a canonical block is missing.
+	MissingHeader(N, H),
+	/// The header of a canonical block can't be decoded or doesn't match its key.
+	CorruptedHeader(N, H),
+	/// The header of a canonical block doesn't link to the canonical block below.
+	BrokenParentLink(N, H),
+	/// The hash of a canonical block doesn't resolve to the block.
+	MissingHashLookup(N, H),
+	/// The body of a canonical block that isn't finalized is missing.
+	MissingBody(N, H),
+	/// The body of a block can't be decoded.
+	CorruptedBody(N, H),
+	/// A transaction indexed by a block body is missing.
+	MissingIndexedTransaction(N, H),
+	/// The justifications of a block can't be decoded.
+	CorruptedJustifications(N, H),
+	/// The state of the block stored as the finalized state is missing.
+	MissingFinalizedState(N, H),
+	/// The state-db journals can't be loaded.
+	StateDbJournals(String),
+}
+
+impl<N, H> Issue<N, H> {
+	/// Whether the issue makes the block, and all the blocks above it, unusable.
+	pub fn is_fatal(&self) -> bool {
+		matches!(
+			self,
+			Issue::MissingCanonicalBlock(..) |
+				Issue::MissingHeader(..) |
+				Issue::CorruptedHeader(..) |
+				Issue::BrokenParentLink(..) |
+				Issue::MissingHashLookup(..) |
+				Issue::MissingBody(..)
+		)
+	}
+}
+
+impl<N: fmt::Display, H: fmt::Debug> fmt::Display for Issue<N, H> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Issue::DanglingMetaEntry(entry) =>
+				write!(f, "The {entry} block isn't part of the canonical chain"),
+			Issue::MissingCanonicalBlock(n) => write!(f, "No canonical block at #{n}"),
+			Issue::MissingHeader(n, h) => write!(f, "Missing header of #{n} ({h:?})"),
+			Issue::CorruptedHeader(n, h) => write!(f, "Corrupted header of #{n} ({h:?})"),
+			Issue::BrokenParentLink(n, h) =>
+				write!(f, "Parent of #{n} ({h:?}) isn't the canonical block below"),
+			Issue::MissingHashLookup(n, h) => write!(f, "Missing hash lookup of #{n} ({h:?})"),
+			Issue::MissingBody(n, h) => write!(f, "Missing body of #{n} ({h:?})"),
+			Issue::CorruptedBody(n, h) => write!(f, "Corrupted body of #{n} ({h:?})"),
+			Issue::MissingIndexedTransaction(n, h) =>
+				write!(f, "Missing indexed transaction of #{n} ({h:?})"),
+			Issue::CorruptedJustifications(n, h) =>
+				write!(f, "Corrupted justifications of #{n} ({h:?})"),
+			Issue::MissingFinalizedState(n, h) =>
+				write!(f, "Missing state of the finalized block #{n} ({h:?})"),
+			Issue::StateDbJournals(e) => write!(f, "State-db journals can't be loaded: {e}"),
+		}
+	}
+}
+
+/// Result of a database check.
+#[derive(Debug, Clone)]
+pub struct CheckReport<N, H> {
+	/// The best block stored in the meta column.
+	pub best: Option<(N, H)>,
+	/// The finalized block stored in the meta column.
+	pub finalized: Option<(N, H)>,
+	/// The highest block up to which the canonical chain is consistent.
+	pub last_consistent: Option<(N, H)>,
+	/// The inconsistencies found.
+	pub issues: Vec<Issue<N, H>>,
+}
+
+impl<N, H> CheckReport<N, H> {
+	/// Whether no inconsistency was found.
+	pub fn is_consistent(&self) -> bool {
+		self.issues.is_empty()
+	}
+}
+
+/// Result of a database repair.
+#[derive(Debug, Clone)]
+pub struct RepairReport<N, H> {
+	/// The check of the database before the repair.
+	pub check: CheckReport<N, H>,
+	/// The best block after the repair.
+	pub best: Option<(N, H)>,
+	/// The finalized block after the repair.
+	pub finalized: Option<(N, H)>,
+}
+
+/// Check the consistency of the database at `source`.
+pub fn check_database<Block: BlockT>(
+	source: &DatabaseSource,
+) -> ClientResult<CheckReport<NumberFor<Block>, Block::Hash>> {
+	let db = utils::open_database::<Block>(source, DatabaseType::Full, false)?;
+	check::<Block>(&db).map(|check| check.report)
+}
+
+/// Check the database at `source` and roll it back to the last consistent block.
+pub fn repair_database<Block: BlockT>(
+	source: &DatabaseSource,
+) -> ClientResult<RepairReport<NumberFor<Block>, Block::Hash>> {
+	let db = utils::open_database::<Block>(source, DatabaseType::Full, false)?;
+	repair::<Block>(&db)
+}
+
+/// Check result, with what the repair needs.
+struct Check<Block: BlockT> {
+	report: CheckReport<NumberFor<Block>, Block::Hash>,
+	/// Canonical blocks above the last consistent block.
+	inconsistent: Vec<(NumberFor<Block>, Block::Hash)>,
+	/// Entries to remove as they can't be decoded.
+	corrupted: Vec<(u32, Vec<u8>)>,
+	state_db: Option<StateDb<Block::Hash, Vec<u8>, StateMetaDb>>,
+}
+
+/// Decode a block lookup key.
+fn decode_lookup_key<Block: BlockT>(key: &[u8]) -> Option<(NumberFor<Block>, Block::Hash)> {
+	if key.len() < 4 {
+		return None
+	}
+	let number = u32::from_be_bytes([key[0], key[1], key[2], key[3]]);
+	let hash = Block::Hash::decode(&mut &key[4..]).ok()?;
+	Some((number.saturated_into(), hash))
+}
+
+fn has_state<Block: BlockT>(db: &dyn Database<DbHash>, header: &Block::Header) -> bool {
+	let root = header.state_root();
+	*root == sp_trie::empty_trie_root::<sp_trie::LayoutV1<HashingFor<Block>>>() ||
+		db.contains(columns::STATE, root.as_ref())
+}
+
+fn check<Block: BlockT>(db: &Arc<dyn Database<DbHash>>) -> ClientResult<Check<Block>> {
+	let mut check = Check {
+		report: CheckReport {
+			best: None,
+			finalized: None,
+			last_consistent: None,
+			issues: Vec::new(),
+		},
+		inconsistent: Vec::new(),
+		corrupted: Vec::new(),
+		state_db: None,
+	};
+	if utils::read_genesis_hash::<Block::Hash>(&**db)?.is_none() {
+		return Ok(check)
+	}
+
+	let read_pointer =
+		|key: &[u8]| db.get(columns::META, key).and_then(|key| decode_lookup_key::<Block>(&key));
+	check.report.best = read_pointer(meta_keys::BEST_BLOCK);
+	check.report.finalized = read_pointer(meta_keys::FINALIZED_BLOCK);
+	let finalized_state = read_pointer(meta_keys::FINALIZED_STATE);
+	let block_gap = utils::read_meta::<Block>(&**db, columns::HEADER)?.block_gap;
+
+	let best_number = check.report.best.map_or(0u32, |(n, _)| n.saturated_into());
+	let finalized_number = check.report.finalized.map_or(0u32, |(n, _)| n.saturated_into());
+	let mut parent = None;
+	let mut fatal = false;
+	for number in 0..=best_number.max(finalized_number) {
+		if number > 0 && number % PROGRESS_INTERVAL == 0 {
+			info!(target: "db", "Checked blocks up to #{number}");
+		}
+		let block_number: NumberFor<Block> = number.saturated_into();
+		let gap = block_gap.filter(|gap| gap.start <= block_number && block_number <= gap.end);
+		if gap.map_or(false, |gap| gap.gap_type == BlockGapType::MissingHeaderAndBody) {
+			parent = None;
+			continue
+		}
+
+		let Some(lookup_key) = db.get(columns::KEY_LOOKUP, &utils::number_index_key(number)?)
+		else {
+			if !fatal {
+				check.report.issues.push(Issue::MissingCanonicalBlock(block_number));
+				fatal = true;
+			}
+			continue
+		};
+		let Some((_, hash)) = decode_lookup_key::<Block>(&lookup_key) else {
+			if !fatal {
+				check.report.issues.push(Issue::MissingCanonicalBlock(block_number));
+				fatal = true;
+			}
+			continue
+		};
+		if fatal {
+			check.inconsistent.push((block_number, hash));
+			continue
+		}
+
+		let issue = match db.get(columns::HEADER, &lookup_key) {
+			None => Some(Issue::MissingHeader(block_number, hash)),
+			Some(encoded) => match Block::Header::decode(&mut &encoded[..]) {
+				Ok(header) if header.hash() != hash || *header.number() != block_number =>
+					Some(Issue::CorruptedHeader(block_number, hash)),
+				Err(_) => Some(Issue::CorruptedHeader(block_number, hash)),
+				Ok(header) if parent.map_or(false, |parent| *header.parent_hash() != parent) =>
+					Some(Issue::BrokenParentLink(block_number, hash)),
+				Ok(_)
+					if db.get(columns::KEY_LOOKUP, hash.as_ref()).as_ref() != Some(&lookup_key) =>
+					Some(Issue::MissingHashLookup(block_number, hash)),
+				Ok(_) => None,
+			},
+		};
+
+		let missing_bodies = gap.map_or(false, |gap| gap.gap_type == BlockGapType::MissingBody);
+		let issue = issue.or_else(|| {
+			if let Some(body) = db.get(columns::BODY, &lookup_key) {
+				if Vec::<Block::Extrinsic>::decode(&mut &body[..]).is_err() {
+					check.report.issues.push(Issue::CorruptedBody(block_number, hash));
+					check.corrupted.push((columns::BODY, lookup_key.clone()));
+				}
+			} else if let Some(body) = db.get(columns::BODY_INDEX, &lookup_key) {
+				match Vec::<DbExtrinsic<Block>>::decode(&mut &body[..]) {
+					Ok(extrinsics) =>
+						if extrinsics.iter().any(|extrinsic| {
+							matches!(extrinsic, DbExtrinsic::Indexed { hash, .. }
+								if !db.contains(columns::TRANSACTION, hash.as_ref()))
+						}) {
+							check
+								.report
+								.issues
+								.push(Issue::MissingIndexedTransaction(block_number, hash));
+						},
+					Err(_) => {
+						check.report.issues.push(Issue::CorruptedBody(block_number, hash));
+						check.corrupted.push((columns::BODY_INDEX, lookup_key.clone()));
+					},
+				}
+			} else if number > finalized_number && !missing_bodies {
+				return Some(Issue::MissingBody(block_number, hash))
+			}
+			None
+		});
+
+		if let Some(issue) = issue {
+			check.report.issues.push(issue);
+			check.inconsistent.push((block_number, hash));
+			fatal = true;
+			continue
+		}
+
+		if let Some(justifications) = db.get(columns::JUSTIFICATIONS, &lookup_key) {
+			if Justifications::decode(&mut &justifications[..]).is_err() {
+				check.report.issues.push(Issue::CorruptedJustifications(block_number, hash));
+				check.corrupted.push((columns::JUSTIFICATIONS, lookup_key.clone()));
+			}
+		}
+
+		parent = Some(hash);
+		check.report.last_consistent = Some((block_number, hash));
+	}
+
+	// The meta entries must point to canonical blocks.
+	let is_canonical = |pointer: Option<(NumberFor<Block>, Block::Hash)>| {
+		pointer.map_or(false, |(number, hash)| {
+			utils::number_index_key(number)
+				.ok()
+				.and_then(|key| db.get(columns::KEY_LOOKUP, &key))
+				.and_then(|key| decode_lookup_key::<Block>(&key))
+				.map_or(false, |(_, canonical)| canonical == hash)
+		})
+	};
+	if !is_canonical(check.report.best) {
+		check.report.issues.push(Issue::DanglingMetaEntry("best"));
+	}
+	if !is_canonical(check.report.finalized) {
+		check.report.issues.push(Issue::DanglingMetaEntry("finalized"));
+	}
+	if let Some((number, hash)) = finalized_state {
+		let header = utils::read_header::<Block>(
+			&**db,
+			columns::KEY_LOOKUP,
+			columns::HEADER,
+			BlockId::Hash(hash),
+		)
+		.ok()
+		.flatten();
+		if !header.map_or(false, |header| has_state::<Block>(&**db, &header)) {
+			check.report.issues.push(Issue::MissingFinalizedState(number, hash));
+		}
+	}
+
+	match StateDb::open(StateMetaDb(db.clone()), None, !db.supports_ref_counting(), false) {
+		Ok((_, state_db)) => check.state_db = Some(state_db),
+		Err(e) => check.report.issues.push(Issue::StateDbJournals(format!("{e:?}"))),
+	}
+
+	Ok(check)
+}
+
+fn repair<Block: BlockT>(
+	db: &Arc<dyn Database<DbHash>>,
+) -> ClientResult<RepairReport<NumberFor<Block>, Block::Hash>> {
+	let check = check::<Block>(db)?;
+	let report = check.report;
+	if report.is_consistent() {
+		let (best, finalized) = (report.best, report.finalized);
+		return Ok(RepairReport { check: report, best, finalized })
+	}
+	let Some(state_db) = check.state_db else {
+		return Err(ClientError::Backend(
+			"State-db journals can't be loaded, the database must be synced again".into(),
+		))
+	};
+	let Some(last_consistent) = report.last_consistent else {
+		return Err(ClientError::Backend(
+			"The genesis block is inconsistent, the database must be synced again".into(),
+		))
+	};
+
+	let canonical_hash = |number: NumberFor<Block>| -> ClientResult<Option<Block::Hash>> {
+		Ok(db
+			.get(columns::KEY_LOOKUP, &utils::number_index_key(number)?)
+			.and_then(|key| decode_lookup_key::<Block>(&key))
+			.map(|(_, hash)| hash))
+	};
+	let consistent = |pointer: Option<(NumberFor<Block>, Block::Hash)>| {
+		pointer.filter(|(number, hash)| {
+			*number <= last_consistent.0 && canonical_hash(*number).ok().flatten() == Some(*hash)
+		})
+	};
+	let finalized = consistent(report.finalized).unwrap_or(last_consistent);
+	let best = consistent(report.best)
+		.filter(|(number, _)| *number >= finalized.0)
+		.unwrap_or(last_consistent);
+
+	// Blocks canonicalized by the state-db can't be finalized again.
+	if let LastCanonicalized::Block(canonicalized) = state_db.last_canonicalized() {
+		if finalized.0.saturated_into::<u64>() < canonicalized {
+			return Err(ClientError::Backend(format!(
+				"Can't roll the finalized block back to #{} below the canonicalized state #{}, \
+				the database must be synced again",
+				finalized.0, canonicalized,
+			)))
+		}
+	}
+
+	let mut transaction = Transaction::new();
+	for (column, key) in &check.corrupted {
+		transaction.remove(*column, key);
+	}
+
+	// Revert the blocks above the new best block, highest first.
+	let mut reverted = check.inconsistent;
+	reverted.extend(
+		(best.0.saturated_into::<u32>() + 1..=last_consistent.0.saturated_into::<u32>())
+			.filter_map(|number| {
+				let number: NumberFor<Block> = number.saturated_into();
+				canonical_hash(number).ok().flatten().map(|hash| (number, hash))
+			}),
+	);
+	reverted.sort_by_key(|(number, _)| std::cmp::Reverse(*number));
+	for (number, hash) in &reverted {
+		if let Some(commit) = state_db.remove(hash) {
+			apply_state_commit(&mut transaction, commit);
+		}
+		utils::remove_number_to_key_mapping(&mut transaction, columns::KEY_LOOKUP, *number)?;
+	}
+
+	let mut leaves = LeafSet::<Block::Hash, NumberFor<Block>>::read_from_db(
+		&**db,
+		columns::META,
+		meta_keys::LEAF_PREFIX,
+	)?;
+	leaves.revert(best.1, best.0);
+	leaves.prepare_transaction(&mut transaction, columns::META, meta_keys::LEAF_PREFIX);
+
+	transaction.set_from_vec(
+		columns::META,
+		meta_keys::BEST_BLOCK,
+		utils::number_and_hash_to_lookup_key(best.0, best.1)?,
+	);
+	transaction.set_from_vec(
+		columns::META,
+		meta_keys::FINALIZED_BLOCK,
+		utils::number_and_hash_to_lookup_key(finalized.0, finalized.1)?,
+	);
+
+	// The finalized state is the one of the highest finalized block with state.
+	let mut finalized_state = None;
+	let mut number = finalized.0;
+	for _ in 0..MAX_FINALIZED_STATE_SEARCH {
+		if let Some(header) = utils::read_header::<Block>(
+			&**db,
+			columns::KEY_LOOKUP,
+			columns::HEADER,
+			BlockId::Number(number),
+		)? {
+			if has_state::<Block>(&**db, &header) {
+				finalized_state = Some((number, header.hash()));
+				break
+			}
+		}
+		if number.is_zero() {
+			break
+		}
+		number -= One::one();
+	}
+	match finalized_state {
+		Some((number, hash)) => transaction.set_from_vec(
+			columns::META,
+			meta_keys::FINALIZED_STATE,
+			utils::number_and_hash_to_lookup_key(number, hash)?,
+		),
+		None => transaction.remove(columns::META, meta_keys::FINALIZED_STATE),
+	}
+
+	db.commit(transaction)?;
+	info!(
+		target: "db",
+		"Repaired database, best block #{} ({:?}), finalized block #{} ({:?})",
+		best.0, best.1, finalized.0, finalized.1,
+	);
+	Ok(RepairReport { check: report, best: Some(best), finalized: Some(finalized) })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		tests::{insert_header, Block},
+		Backend, BlocksPruning, DatabaseSettings, PruningMode,
+	};
+	use sc_client_api::backend::Backend as _;
+	use sp_blockchain::HeaderBackend;
+	use sp_core::H256;
+
+	fn open_backend(db: Arc<dyn Database<DbHash>>, create: bool) -> Backend<Block> {
+		let settings = DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: Some(PruningMode::blocks_pruning(100)),
+			source: DatabaseSource::Custom { db, require_create_flag: create },
+			blocks_pruning: BlocksPruning::Some(100),
+			flat_state: false,
+		};
+		// Keep the imported blocks above the finalized one non-canonical.
+		Backend::new(settings, 10).unwrap()
+	}
+
+	#[test]
+	fn repair_rolls_back_to_last_consistent_block() {
+		let db = sp_database::as_database(kvdb_memorydb::create(utils::NUM_COLUMNS));
+		let hashes = {
+			let backend = open_backend(db.clone(), true);
+			let mut hashes =
+				vec![insert_header(&backend, 0, Default::default(), None, H256::zero())];
+			for number in 1..=5 {
+				let parent = hashes[number as usize - 1];
+				hashes.push(insert_header(&backend, number, parent, None, H256::zero()));
+			}
+			for hash in &hashes[1..=2] {
+				backend.finalize_block(*hash, None).unwrap();
+			}
+			hashes
+		};
+		assert!(check::<Block>(&db).unwrap().report.is_consistent());
+
+		// Lose the header of block #4.
+		let mut transaction = Transaction::new();
+		let key = utils::number_and_hash_to_lookup_key(4u64, hashes[4]).unwrap();
+		transaction.remove(columns::HEADER, &key);
+		db.commit(transaction).unwrap();
+
+		let report = check::<Block>(&db).unwrap().report;
+		assert_eq!(report.issues, vec![Issue::MissingHeader(4, hashes[4])]);
+		assert_eq!(report.last_consistent, Some((3, hashes[3])));
+
+		let repair = repair::<Block>(&db).unwrap();
+		assert_eq!(repair.best, Some((3, hashes[3])));
+		assert_eq!(repair.finalized, Some((2, hashes[2])));
+		assert!(check::<Block>(&db).unwrap().report.is_consistent());
+
+		let backend = open_backend(db, false);
+		let info = backend.blockchain().info();
+		assert_eq!((info.best_number, info.best_hash), (3, hashes[3]));
+		assert_eq!((info.finalized_number, info.finalized_hash), (2, hashes[2]));
+	}
+}
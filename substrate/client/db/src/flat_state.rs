@@ -0,0 +1,712 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Flat (non-trie) copy of the latest finalized state.
+//!
+//! When enabled, the backend keeps every key-value pair of the last finalized state in
+//! [`columns::FLAT_STATE`], so that reading a value at that block is a single database lookup
+//! instead of a trie traversal. Storage roots, proofs and key iteration always use the trie.
+//!
+//! Every block imported with state stores a journal of its storage changes, under its own key and
+//! indexed by block number. Reads at a descendant of the flat state head go through the journals of
+//! the blocks in between, which are cached in memory, before reading the entries. When a block is
+//! finalized, the journals on the route from the current flat state head are applied and the
+//! journals of all the blocks up to the finalized one are removed. A block importing a complete
+//! state (genesis, warp sync) starts a new generation of entries, and the entries of the previous
+//! generation are removed by prefix.
+//!
+//! When the flat state doesn't hold the finalized state on startup, it is populated in the
+//! background. The journals of the blocks finalized in the meantime are kept, and applied by the
+//! first finalization following the population.
+
+use crate::{columns, utils::meta_keys, DbHash};
+use codec::{Decode, Encode};
+use log::{debug, info, warn};
+use parking_lot::{Mutex, MutexGuard, RwLock};
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_core::storage::{well_known_keys, ChildInfo};
+use sp_database::{Database, Transaction};
+use sp_runtime::{
+	traits::{Block as BlockT, HashingFor, NumberFor, One, SaturatedConversion},
+	Storage,
+};
+use sp_state_machine::{
+	backend::Backend as StateBackend, ChildStorageCollection, IterArgs, StorageCollection,
+};
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
+
+/// Prefix of the top trie entries.
+const TOP_PREFIX: u8 = 0;
+/// Prefix of the child trie entries.
+const CHILD_PREFIX: u8 = 1;
+/// Prefix of the block journals.
+const JOURNAL_PREFIX: u8 = 2;
+/// Prefix of the hashes of the blocks with a journal, by block number.
+const JOURNAL_INDEX_PREFIX: u8 = 3;
+
+/// Number of entries written per transaction when populating the flat state.
+const POPULATE_BATCH_SIZE: usize = 64 * 1024;
+
+/// Maximal number of journals a read goes through before giving up.
+const MAX_JOURNAL_DEPTH: usize = 256;
+
+/// Flat state metadata, stored under [`meta_keys::FLAT_STATE`].
+#[derive(Debug, Clone, Encode, Decode)]
+struct FlatMeta<H, N> {
+	/// Generation of the entries.
+	generation: u32,
+	/// Block whose state is stored, if the entries are complete.
+	head: Option<(H, N)>,
+	/// Block whose state is being populated.
+	populating: Option<(H, N)>,
+	/// Lowest and highest numbers of the blocks that may have a stored journal.
+	journals: Option<(N, N)>,
+}
+
+impl<H, N> Default for FlatMeta<H, N> {
+	fn default() -> Self {
+		Self { generation: 0, head: None, populating: None, journals: None }
+	}
+}
+
+/// Journal of a block, stored under [`journal_key`].
+#[derive(Debug, Encode, Decode)]
+struct Journal<H, N> {
+	number: N,
+	/// `None` if `changes` contains a complete state.
+	parent: Option<H>,
+	changes: BlockChanges,
+}
+
+/// Journal of a block, indexed to serve reads.
+struct JournalOverlay<H, N> {
+	number: N,
+	/// `None` if the journal contains a complete state.
+	parent: Option<H>,
+	top: HashMap<Vec<u8>, Option<Vec<u8>>>,
+	children: HashMap<Vec<u8>, HashMap<Vec<u8>, Option<Vec<u8>>>>,
+}
+
+impl<H: Clone, N: Clone> JournalOverlay<H, N> {
+	fn new(journal: &Journal<H, N>) -> Self {
+		Self {
+			number: journal.number.clone(),
+			parent: journal.parent.clone(),
+			top: journal.changes.top.iter().cloned().collect(),
+			children: journal
+				.changes
+				.children
+				.iter()
+				.map(|(storage_key, changes)| {
+					(storage_key.clone(), changes.iter().cloned().collect())
+				})
+				.collect(),
+		}
+	}
+}
+
+/// Storage changes of a block.
+#[derive(Debug, Default, Encode, Decode)]
+pub(crate) struct BlockChanges {
+	top: StorageCollection,
+	children: ChildStorageCollection,
+}
+
+impl BlockChanges {
+	/// Changes applied on top of the parent state.
+	pub fn new(top: StorageCollection, children: ChildStorageCollection) -> Self {
+		Self { top, children }
+	}
+
+	/// Changes setting the complete `storage`.
+	pub fn from_storage(storage: Storage) -> Self {
+		let top = storage.top.into_iter().map(|(k, v)| (k, Some(v))).collect();
+		let children = storage
+			.children_default
+			.into_values()
+			.map(|child| {
+				let data = child.data.into_iter().map(|(k, v)| (k, Some(v))).collect();
+				(child.child_info.storage_key().to_vec(), data)
+			})
+			.collect();
+		Self { top, children }
+	}
+}
+
+fn top_key(generation: u32, key: &[u8]) -> Vec<u8> {
+	let mut result = Vec::with_capacity(5 + key.len());
+	result.push(TOP_PREFIX);
+	result.extend_from_slice(&generation.to_be_bytes());
+	result.extend_from_slice(key);
+	result
+}
+
+fn child_key(generation: u32, storage_key: &[u8], key: &[u8]) -> Vec<u8> {
+	let mut result = Vec::with_capacity(9 + storage_key.len() + key.len());
+	result.push(CHILD_PREFIX);
+	result.extend_from_slice(&generation.to_be_bytes());
+	storage_key.encode_to(&mut result);
+	result.extend_from_slice(key);
+	result
+}
+
+/// Remove the entries of `generation`.
+fn remove_generation(transaction: &mut Transaction<DbHash>, generation: u32) {
+	for prefix in [TOP_PREFIX, CHILD_PREFIX] {
+		let mut key = Vec::with_capacity(5);
+		key.push(prefix);
+		key.extend_from_slice(&generation.to_be_bytes());
+		transaction.remove_prefix(columns::FLAT_STATE, &key);
+	}
+}
+
+fn journal_key<H: AsRef<[u8]>>(hash: &H) -> Vec<u8> {
+	let mut result = Vec::with_capacity(1 + hash.as_ref().len());
+	result.push(JOURNAL_PREFIX);
+	result.extend_from_slice(hash.as_ref());
+	result
+}
+
+fn journal_index_key(number: u64) -> [u8; 9] {
+	let mut result = [JOURNAL_INDEX_PREFIX; 9];
+	result[1..].copy_from_slice(&number.to_be_bytes());
+	result
+}
+
+/// Flat copy of the latest finalized state.
+pub(crate) struct FlatState<Block: BlockT> {
+	db: Arc<dyn Database<DbHash>>,
+	meta: RwLock<FlatMeta<Block::Hash, NumberFor<Block>>>,
+	/// Journals of the blocks that have been read through, by block hash.
+	overlays: RwLock<HashMap<Block::Hash, Arc<JournalOverlay<Block::Hash, NumberFor<Block>>>>>,
+	/// Held by updates, so that the population doesn't overwrite their metadata.
+	update_lock: Mutex<()>,
+	/// Set to stop the population when the backend is dropped.
+	abort_population: AtomicBool,
+}
+
+impl<Block: BlockT> FlatState<Block> {
+	/// Open the flat state stored in `db`.
+	pub fn open(db: Arc<dyn Database<DbHash>>) -> ClientResult<Self> {
+		let meta = match db.get(columns::META, meta_keys::FLAT_STATE) {
+			Some(encoded) => FlatMeta::decode(&mut &encoded[..]).map_err(|e| {
+				ClientError::Backend(format!("Error decoding flat state metadata: {e}"))
+			})?,
+			None => FlatMeta::default(),
+		};
+		Ok(Self {
+			db,
+			meta: RwLock::new(meta),
+			overlays: RwLock::new(HashMap::new()),
+			update_lock: Mutex::new(()),
+			abort_population: AtomicBool::new(false),
+		})
+	}
+
+	/// Block whose state is currently stored.
+	pub fn head(&self) -> Option<(Block::Hash, NumberFor<Block>)> {
+		self.meta.read().head
+	}
+
+	/// Value of `key` at block `at`.
+	///
+	/// Returns `None` if the flat state can't answer the query.
+	pub fn storage(&self, at: &Block::Hash, key: &[u8]) -> Option<Option<Vec<u8>>> {
+		// Child trie roots are only known to the trie.
+		if well_known_keys::is_child_storage_key(key) {
+			return None
+		}
+		let meta = self.meta.read();
+		self.read(
+			&meta,
+			at,
+			|overlay| overlay.top.get(key).cloned(),
+			|| top_key(meta.generation, key),
+		)
+	}
+
+	/// Value of `key` in the child trie `child_info` at block `at`.
+	///
+	/// Returns `None` if the flat state can't answer the query.
+	pub fn child_storage(
+		&self,
+		at: &Block::Hash,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Option<Option<Vec<u8>>> {
+		let storage_key = child_info.storage_key();
+		let meta = self.meta.read();
+		self.read(
+			&meta,
+			at,
+			|overlay| {
+				overlay.children.get(storage_key).and_then(|changes| changes.get(key)).cloned()
+			},
+			|| child_key(meta.generation, storage_key, key),
+		)
+	}
+
+	/// Read a value at block `at`, which is either the head or one of its descendants.
+	///
+	/// The journals from `at` down to the head are looked up with `in_journal`, before reading
+	/// the entry at `entry_key`.
+	fn read(
+		&self,
+		meta: &FlatMeta<Block::Hash, NumberFor<Block>>,
+		at: &Block::Hash,
+		in_journal: impl Fn(&JournalOverlay<Block::Hash, NumberFor<Block>>) -> Option<Option<Vec<u8>>>,
+		entry_key: impl FnOnce() -> Vec<u8>,
+	) -> Option<Option<Vec<u8>>> {
+		let (head, head_number) = meta.head?;
+		let mut current = *at;
+		for _ in 0..MAX_JOURNAL_DEPTH {
+			if current == head {
+				return Some(self.db.get(columns::FLAT_STATE, &entry_key()))
+			}
+			let overlay = self.overlay(&current)?;
+			if overlay.number <= head_number {
+				// Not a descendant of the head.
+				return None
+			}
+			if let Some(value) = in_journal(&overlay) {
+				return Some(value)
+			}
+			match overlay.parent {
+				Some(parent) => current = parent,
+				// The journal contains the complete state.
+				None => return Some(None),
+			}
+		}
+		None
+	}
+
+	/// Journal of block `hash`, loaded from the database if it hasn't been read yet.
+	fn overlay(
+		&self,
+		hash: &Block::Hash,
+	) -> Option<Arc<JournalOverlay<Block::Hash, NumberFor<Block>>>> {
+		if let Some(overlay) = self.overlays.read().get(hash) {
+			return Some(overlay.clone())
+		}
+		let journal = self.load_journal(hash).ok()??;
+		let overlay = Arc::new(JournalOverlay::new(&journal));
+		self.overlays.write().insert(*hash, overlay.clone());
+		Some(overlay)
+	}
+
+	fn load_journal(
+		&self,
+		hash: &Block::Hash,
+	) -> ClientResult<Option<Journal<Block::Hash, NumberFor<Block>>>> {
+		let Some(encoded) = self.db.get(columns::FLAT_STATE, &journal_key(hash)) else {
+			return Ok(None)
+		};
+		Journal::decode(&mut &encoded[..]).map(Some).map_err(|e| {
+			ClientError::Backend(format!("Error decoding flat state journal for {hash:?}: {e}"))
+		})
+	}
+
+	/// Whether the journal of block `hash` is stored.
+	#[cfg(test)]
+	pub fn has_journal(&self, hash: &Block::Hash) -> bool {
+		self.db.get(columns::FLAT_STATE, &journal_key(hash)).is_some()
+	}
+
+	/// Start a new update.
+	pub fn update(&self) -> FlatStateUpdate<'_, Block> {
+		let lock = self.update_lock.lock();
+		FlatStateUpdate {
+			flat_state: self,
+			_lock: lock,
+			meta: self.meta.read().clone(),
+			journals: HashMap::new(),
+			indexes: HashMap::new(),
+			removed: Vec::new(),
+			changed: false,
+		}
+	}
+
+	/// Drop the stored entries, to populate them with the state of `hash` with [`Self::populate`].
+	///
+	/// The journals of the blocks finalized until the population completes are kept.
+	pub fn begin_population(
+		&self,
+		hash: Block::Hash,
+		number: NumberFor<Block>,
+	) -> ClientResult<()> {
+		let mut update = self.update();
+		let mut transaction = Transaction::new();
+		remove_generation(&mut transaction, update.meta.generation);
+		update.meta.generation += 1;
+		update.meta.head = None;
+		update.meta.populating = Some((hash, number));
+		// Journals of blocks up to `number` won't ever be applied.
+		update.prune_journals(&mut transaction, number)?;
+		update.changed = true;
+		update.commit(transaction)
+	}
+
+	/// Write the content of `state`, which is the state of the block passed to
+	/// [`Self::begin_population`].
+	///
+	/// Blocks can be imported and finalized while the population runs.
+	pub fn populate<S: StateBackend<HashingFor<Block>>>(&self, state: &S) -> ClientResult<()> {
+		let (generation, (hash, number)) = {
+			let meta = self.meta.read();
+			match meta.populating {
+				Some(populating) => (meta.generation, populating),
+				None => return Ok(()),
+			}
+		};
+		info!(target: "db", "Populating the flat state at #{number} ({hash:?})");
+		let map_e = |e: S::Error| ClientError::Backend(format!("Error reading state: {e}"));
+
+		let mut transaction = Transaction::new();
+		let mut batch = 0;
+		let mut entries = 0;
+		let mut child_tries = Vec::new();
+		for pair in state.pairs(Default::default()).map_err(map_e)? {
+			let (key, value) = pair.map_err(map_e)?;
+			if let Some(storage_key) =
+				key.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX)
+			{
+				child_tries.push(ChildInfo::new_default(storage_key));
+				continue
+			}
+			transaction.set_from_vec(columns::FLAT_STATE, &top_key(generation, &key), value);
+			batch += 1;
+			if batch == POPULATE_BATCH_SIZE {
+				if self.abort_population.load(Ordering::Relaxed) {
+					return Ok(())
+				}
+				self.db.commit(std::mem::take(&mut transaction))?;
+				entries += batch;
+				batch = 0;
+			}
+		}
+		for child_info in child_tries {
+			let mut args = IterArgs::default();
+			args.child_info = Some(child_info.clone());
+			for pair in state.pairs(args).map_err(map_e)? {
+				let (key, value) = pair.map_err(map_e)?;
+				transaction.set_from_vec(
+					columns::FLAT_STATE,
+					&child_key(generation, child_info.storage_key(), &key),
+					value,
+				);
+				batch += 1;
+				if batch == POPULATE_BATCH_SIZE {
+					if self.abort_population.load(Ordering::Relaxed) {
+						return Ok(())
+					}
+					self.db.commit(std::mem::take(&mut transaction))?;
+					entries += batch;
+					batch = 0;
+				}
+			}
+		}
+		self.db.commit(transaction)?;
+		entries += batch;
+
+		let _lock = self.update_lock.lock();
+		let mut meta = self.meta.read().clone();
+		let mut transaction = Transaction::new();
+		if meta.generation != generation || meta.populating != Some((hash, number)) {
+			// A complete state was imported in the meantime.
+			debug!(target: "db", "Flat state population at #{number} ({hash:?}) is outdated");
+			remove_generation(&mut transaction, generation);
+			self.db.commit(transaction)?;
+			return Ok(())
+		}
+		meta.head = Some((hash, number));
+		meta.populating = None;
+		transaction.set_from_vec(columns::META, meta_keys::FLAT_STATE, meta.encode());
+		self.commit_meta(transaction, meta)?;
+		info!(target: "db", "Flat state populated with {entries} entries");
+		Ok(())
+	}
+
+	/// Stop a running population.
+	pub fn abort_population(&self) {
+		self.abort_population.store(true, Ordering::Relaxed);
+	}
+
+	fn commit_meta(
+		&self,
+		transaction: Transaction<DbHash>,
+		meta: FlatMeta<Block::Hash, NumberFor<Block>>,
+	) -> ClientResult<()> {
+		// Readers must not observe the new entries with the old metadata.
+		let mut current = self.meta.write();
+		self.db.commit(transaction)?;
+		*current = meta;
+		Ok(())
+	}
+}
+
+/// Pending changes to the flat state, written together with a database transaction.
+pub(crate) struct FlatStateUpdate<'a, Block: BlockT> {
+	flat_state: &'a FlatState<Block>,
+	_lock: MutexGuard<'a, ()>,
+	meta: FlatMeta<Block::Hash, NumberFor<Block>>,
+	/// Journals written or read by this update.
+	journals: HashMap<Block::Hash, Journal<Block::Hash, NumberFor<Block>>>,
+	/// Updated journal indexes, `None` if removed.
+	indexes: HashMap<u64, Option<Vec<Block::Hash>>>,
+	/// Blocks whose journal has been removed.
+	removed: Vec<Block::Hash>,
+	changed: bool,
+}
+
+impl<Block: BlockT> FlatStateUpdate<'_, Block> {
+	/// Store the storage changes of a newly imported block.
+	///
+	/// `parent` is `None` if `changes` contains the complete state of the block.
+	pub fn note_block(
+		&mut self,
+		transaction: &mut Transaction<DbHash>,
+		hash: Block::Hash,
+		number: NumberFor<Block>,
+		parent: Option<Block::Hash>,
+		changes: BlockChanges,
+	) -> ClientResult<()> {
+		let journal = Journal { number, parent, changes };
+		transaction.set_from_vec(columns::FLAT_STATE, &journal_key(&hash), journal.encode());
+		self.journals.insert(hash, journal);
+
+		let mut index = self.index(number)?;
+		if !index.contains(&hash) {
+			index.push(hash);
+			self.set_index(transaction, number, index);
+		}
+		self.meta.journals = Some(match self.meta.journals {
+			Some((from, to)) => (from.min(number), to.max(number)),
+			None => (number, number),
+		});
+		self.changed = true;
+		Ok(())
+	}
+
+	/// Move the flat state to the finalized block `hash`.
+	///
+	/// If the route from the current head isn't covered by journals, the flat state is disabled
+	/// until a complete state is imported or it is populated again on startup.
+	pub fn finalize(
+		&mut self,
+		transaction: &mut Transaction<DbHash>,
+		hash: Block::Hash,
+		number: NumberFor<Block>,
+	) -> ClientResult<()> {
+		if self.meta.head.is_some_and(|(head, _)| head == hash) {
+			return Ok(())
+		}
+
+		// The route ends either at the head or at a block whose journal contains its complete
+		// state.
+		let head = self.meta.head;
+		let mut route = Vec::new();
+		let mut current = Some(hash);
+		let connected = loop {
+			let Some(block) = current else { break true };
+			if head.is_some_and(|(head, _)| head == block) {
+				break true
+			}
+			match self.journal(&block)? {
+				Some(journal) if head.is_none_or(|(_, number)| journal.number > number) => {
+					route.push((block, journal.parent.is_none()));
+					current = journal.parent;
+				},
+				_ => break false,
+			}
+		};
+
+		if connected {
+			for (block, reset) in route.into_iter().rev() {
+				let changes = self
+					.journals
+					.remove(&block)
+					.ok_or_else(|| {
+						ClientError::Backend(format!("Missing flat state journal for {block:?}"))
+					})?
+					.changes;
+				if reset {
+					remove_generation(transaction, self.meta.generation);
+					self.meta.generation += 1;
+					self.meta.populating = None;
+				}
+				self.apply(transaction, changes);
+			}
+			self.meta.head = Some((hash, number));
+		} else if self.meta.head.is_some() {
+			warn!(
+				target: "db",
+				"Flat state can't follow finalization of #{number} ({hash:?}), disabling it",
+			);
+			self.meta.head = None;
+		}
+
+		// Blocks up to `number` are either applied or can't be finalized anymore, unless the flat
+		// state is being populated at an older block.
+		let prune_to =
+			self.meta.populating.map_or(number, |(_, populating)| populating.min(number));
+		self.prune_journals(transaction, prune_to)?;
+		self.changed = true;
+		Ok(())
+	}
+
+	/// Remove the journal of a removed block.
+	pub fn discard(
+		&mut self,
+		transaction: &mut Transaction<DbHash>,
+		hash: Block::Hash,
+	) -> ClientResult<()> {
+		let Some(journal) = self.journal(&hash)? else { return Ok(()) };
+		let number = journal.number;
+		let mut index = self.index(number)?;
+		index.retain(|indexed| *indexed != hash);
+		self.set_index(transaction, number, index);
+		self.remove_journal(transaction, hash);
+		self.changed = true;
+		Ok(())
+	}
+
+	/// Disable the flat state if finalization was reverted below its head.
+	pub fn revert_finalized(&mut self, number: NumberFor<Block>) {
+		if self.meta.head.is_some_and(|(_, head)| head > number) {
+			debug!(target: "db", "Finalization reverted to #{number}, disabling the flat state");
+			self.meta.head = None;
+			self.changed = true;
+		}
+	}
+
+	/// Commit `transaction` together with the flat state changes.
+	pub fn commit(self, mut transaction: Transaction<DbHash>) -> ClientResult<()> {
+		let flat_state = self.flat_state;
+		if !self.changed {
+			flat_state.db.commit(transaction)?;
+			return Ok(())
+		}
+		transaction.set_from_vec(columns::META, meta_keys::FLAT_STATE, self.meta.encode());
+		flat_state.commit_meta(transaction, self.meta)?;
+		let mut overlays = flat_state.overlays.write();
+		for hash in self.removed {
+			overlays.remove(&hash);
+		}
+		Ok(())
+	}
+
+	/// Remove the journals of all the blocks up to `number`.
+	fn prune_journals(
+		&mut self,
+		transaction: &mut Transaction<DbHash>,
+		number: NumberFor<Block>,
+	) -> ClientResult<()> {
+		let Some((mut current, to)) = self.meta.journals else { return Ok(()) };
+		while current <= number.min(to) {
+			for hash in self.index(current)? {
+				self.remove_journal(transaction, hash);
+			}
+			self.set_index(transaction, current, Vec::new());
+			current += One::one();
+		}
+		self.meta.journals = (current <= to).then_some((current, to));
+		Ok(())
+	}
+
+	/// Journal of block `hash`, if stored.
+	fn journal(
+		&mut self,
+		hash: &Block::Hash,
+	) -> ClientResult<Option<&Journal<Block::Hash, NumberFor<Block>>>> {
+		if !self.journals.contains_key(hash) {
+			if self.removed.contains(hash) {
+				return Ok(None)
+			}
+			match self.flat_state.load_journal(hash)? {
+				Some(journal) => {
+					self.journals.insert(*hash, journal);
+				},
+				None => return Ok(None),
+			}
+		}
+		Ok(self.journals.get(hash))
+	}
+
+	fn remove_journal(&mut self, transaction: &mut Transaction<DbHash>, hash: Block::Hash) {
+		transaction.remove(columns::FLAT_STATE, &journal_key(&hash));
+		self.journals.remove(&hash);
+		self.removed.push(hash);
+	}
+
+	/// Hashes of the blocks with a journal at `number`.
+	fn index(&self, number: NumberFor<Block>) -> ClientResult<Vec<Block::Hash>> {
+		let number = number.saturated_into::<u64>();
+		if let Some(index) = self.indexes.get(&number) {
+			return Ok(index.clone().unwrap_or_default())
+		}
+		match self.flat_state.db.get(columns::FLAT_STATE, &journal_index_key(number)) {
+			Some(encoded) => Vec::decode(&mut &encoded[..]).map_err(|e| {
+				ClientError::Backend(format!("Error decoding flat state journal index: {e}"))
+			}),
+			None => Ok(Vec::new()),
+		}
+	}
+
+	fn set_index(
+		&mut self,
+		transaction: &mut Transaction<DbHash>,
+		number: NumberFor<Block>,
+		index: Vec<Block::Hash>,
+	) {
+		let number = number.saturated_into::<u64>();
+		let key = journal_index_key(number);
+		if index.is_empty() {
+			transaction.remove(columns::FLAT_STATE, &key);
+			self.indexes.insert(number, None);
+		} else {
+			transaction.set_from_vec(columns::FLAT_STATE, &key, index.encode());
+			self.indexes.insert(number, Some(index));
+		}
+	}
+
+	fn apply(&self, transaction: &mut Transaction<DbHash>, changes: BlockChanges) {
+		let generation = self.meta.generation;
+		for (key, value) in changes.top {
+			let key = top_key(generation, &key);
+			match value {
+				Some(value) => transaction.set_from_vec(columns::FLAT_STATE, &key, value),
+				None => transaction.remove(columns::FLAT_STATE, &key),
+			}
+		}
+		for (storage_key, child_changes) in changes.children {
+			for (key, value) in child_changes {
+				let key = child_key(generation, &storage_key, &key);
+				match value {
+					Some(value) => transaction.set_from_vec(columns::FLAT_STATE, &key, value),
+					None => transaction.remove(columns::FLAT_STATE, &key),
+				}
+			}
+		}
+	}
+}
@@ -31,8 +31,10 @@
 pub mod offchain;
 
 pub mod bench;
+pub mod check;
 
 mod children;
+mod flat_state;
 mod parity_db;
 mod pinned_blocks_cache;
 mod record_stats_state;
@@ -52,6 +54,7 @@ use std::{
 };
 
 use crate::{
+	flat_state::{BlockChanges, FlatState, FlatStateUpdate},
 	pinned_blocks_cache::PinnedBlocksCache,
 	record_stats_state::RecordStatsState,
 	stats::StateUsageStats,
@@ -147,6 +150,19 @@ impl<B: BlockT> RefTrackingState<B> {
 	) -> Self {
 		RefTrackingState { state, parent_hash, storage }
 	}
+
+	/// Read `key` from the flat state, if this block is its head or one of its descendants.
+	fn flat_storage(&self, key: &[u8]) -> Option<Option<Vec<u8>>> {
+		let hash = self.parent_hash.as_ref()?;
+		self.storage.flat_state.as_ref()?.storage(hash, key)
+	}
+
+	/// Read `key` of a child trie from the flat state, if this block is its head or one of its
+	/// descendants.
+	fn flat_child_storage(&self, child_info: &ChildInfo, key: &[u8]) -> Option<Option<Vec<u8>>> {
+		let hash = self.parent_hash.as_ref()?;
+		self.storage.flat_state.as_ref()?.child_storage(hash, child_info, key)
+	}
 }
 
 impl<B: BlockT> Drop for RefTrackingState<B> {
@@ -195,6 +211,9 @@ impl<B: BlockT> StateBackend<HashingFor<B>> for RefTrackingState<B> {
 	type RawIter = RawIter<B>;
 
 	fn storage(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+		if let Some(value) = self.flat_storage(key) {
+			return Ok(value)
+		}
 		self.state.storage(key)
 	}
 
@@ -207,6 +226,9 @@ impl<B: BlockT> StateBackend<HashingFor<B>> for RefTrackingState<B> {
 		child_info: &ChildInfo,
 		key: &[u8],
 	) -> Result<Option<Vec<u8>>, Self::Error> {
+		if let Some(value) = self.flat_child_storage(child_info, key) {
+			return Ok(value)
+		}
 		self.state.child_storage(child_info, key)
 	}
 
@@ -234,6 +256,9 @@ impl<B: BlockT> StateBackend<HashingFor<B>> for RefTrackingState<B> {
 	}
 
 	fn exists_storage(&self, key: &[u8]) -> Result<bool, Self::Error> {
+		if let Some(value) = self.flat_storage(key) {
+			return Ok(value.is_some())
+		}
 		self.state.exists_storage(key)
 	}
 
@@ -242,6 +267,9 @@ impl<B: BlockT> StateBackend<HashingFor<B>> for RefTrackingState<B> {
 		child_info: &ChildInfo,
 		key: &[u8],
 	) -> Result<bool, Self::Error> {
+		if let Some(value) = self.flat_child_storage(child_info, key) {
+			return Ok(value.is_some())
+		}
 		self.state.exists_child_storage(child_info, key)
 	}
 
@@ -312,6 +340,11 @@ pub struct DatabaseSettings {
 	///
 	/// NOTE: only finalized blocks are subject for removal!
 	pub blocks_pruning: BlocksPruning,
+	/// Keep a flat copy of the finalized state to speed up state reads.
+	///
+	/// Only storage queries go through the flat state. Block execution, storage roots and
+	/// proofs keep using the trie.
+	pub flat_state: bool,
 }
 
 /// Block pruning settings.
@@ -438,6 +471,8 @@ pub(crate) mod columns {
 	/// Transactions
 	pub const TRANSACTION: u32 = 11;
 	pub const BODY_INDEX: u32 = 12;
+	/// Flat copy of the finalized state.
+	pub const FLAT_STATE: u32 = 13;
 }
 
 struct PendingBlock<Block: BlockT> {
@@ -836,6 +871,10 @@ pub struct BlockImportOperation<Block: BlockT> {
 	commit_state: bool,
 	create_gap: bool,
 	index_ops: Vec<IndexOperation>,
+	/// Whether the changes need to be journaled for the flat state.
+	track_flat_state: bool,
+	/// Complete state set by this operation, journaled for the flat state.
+	flat_state_reset: Option<BlockChanges>,
 }
 
 impl<Block: BlockT> BlockImportOperation<Block> {
@@ -888,6 +927,9 @@ impl<Block: BlockT> BlockImportOperation<Block> {
 		);
 
 		self.db_updates = transaction;
+		if self.track_flat_state {
+			self.flat_state_reset = Some(BlockChanges::from_storage(storage));
+		}
 		Ok(root)
 	}
 }
@@ -999,6 +1041,7 @@ struct StorageDb<Block: BlockT> {
 	pub db: Arc<dyn Database<DbHash>>,
 	pub state_db: StateDb<Block::Hash, Vec<u8>, StateMetaDb>,
 	prefix_keys: bool,
+	flat_state: Option<FlatState<Block>>,
 }
 
 impl<Block: BlockT> sp_state_machine::Storage<HashingFor<Block>> for StorageDb<Block> {
@@ -1171,6 +1214,27 @@ impl<Block: BlockT> Backend<Block> {
 			state_pruning: Some(state_pruning),
 			source: DatabaseSource::Custom { db, require_create_flag: true },
 			blocks_pruning,
+			flat_state: false,
+		};
+
+		Self::new(db_setting, canonicalization_delay).expect("failed to create test-db")
+	}
+
+	/// Create new memory-backed client backend for tests, keeping all the finalized blocks and
+	/// pruning their state with the given mode.
+	#[cfg(any(test, feature = "test-helpers"))]
+	pub fn new_test_with_state_pruning(
+		state_pruning: PruningMode,
+		canonicalization_delay: u64,
+	) -> Self {
+		let db = kvdb_memorydb::create(crate::utils::NUM_COLUMNS);
+		let db = sp_database::as_database(db);
+		let db_setting = DatabaseSettings {
+			trie_cache_maximum_size: Some(16 * 1024 * 1024),
+			state_pruning: Some(state_pruning),
+			source: DatabaseSource::Custom { db, require_create_flag: true },
+			blocks_pruning: BlocksPruning::KeepFinalized,
+			flat_state: false,
 		};
 
 		Self::new(db_setting, canonicalization_delay).expect("failed to create test-db")
@@ -1219,8 +1283,13 @@ impl<Block: BlockT> Backend<Block> {
 		let is_archive_pruning = state_pruning_used.is_archive();
 		let blockchain = BlockchainDb::new(db.clone())?;
 
-		let storage_db =
-			StorageDb { db: db.clone(), state_db, prefix_keys: !db.supports_ref_counting() };
+		let flat_state = if config.flat_state { Some(FlatState::open(db.clone())?) } else { None };
+		let storage_db = StorageDb {
+			db: db.clone(),
+			state_db,
+			prefix_keys: !db.supports_ref_counting(),
+			flat_state,
+		};
 
 		let offchain_storage = offchain::LocalStorage::new(db.clone());
 
@@ -1260,9 +1329,53 @@ impl<Block: BlockT> Backend<Block> {
 
 		db.commit(db_init_transaction)?;
 
+		// The flat state is missing or stale when it was just enabled or when it had to stop
+		// following finalization. It is populated in the background, reads go through the trie
+		// until then.
+		if let Some(flat_state) = &backend.storage.flat_state {
+			if let Some((hash, number)) = backend.blockchain.info().finalized_state {
+				if flat_state.head().map(|(head, _)| head) != Some(hash) {
+					let state = sc_client_api::Backend::state_at(&backend, hash)?;
+					flat_state.begin_population(hash, number)?;
+					let storage = backend.storage.clone();
+					std::thread::Builder::new()
+						.name("flat-state".into())
+						.spawn(move || {
+							let flat_state =
+								storage.flat_state.as_ref().expect("Flat state is enabled; qed");
+							if let Err(e) = flat_state.populate(&state) {
+								warn!(target: "db", "Failed to populate the flat state: {e}");
+							}
+						})
+						.map_err(|e| {
+							sp_blockchain::Error::Backend(format!(
+								"Failed to spawn the flat state population: {e}"
+							))
+						})?;
+				}
+			}
+		}
+
 		Ok(backend)
 	}
 
+	/// Start an update of the flat state, if enabled.
+	fn flat_state_update(&self) -> Option<FlatStateUpdate<'_, Block>> {
+		self.storage.flat_state.as_ref().map(FlatState::update)
+	}
+
+	/// Commit `transaction` together with the pending flat state changes.
+	fn commit_with_flat_state(
+		&self,
+		transaction: Transaction<DbHash>,
+		flat_state_update: Option<FlatStateUpdate<'_, Block>>,
+	) -> ClientResult<()> {
+		match flat_state_update {
+			Some(update) => update.commit(transaction),
+			None => Ok(self.storage.db.commit(transaction)?),
+		}
+	}
+
 	/// Handle setting head within a transaction. `route_to` should be the last
 	/// block that existed in the database. `best_to` should be the best block
 	/// to be set.
@@ -1365,6 +1478,7 @@ impl<Block: BlockT> Backend<Block> {
 		justification: Option<Justification>,
 		current_transaction_justifications: &mut HashMap<Block::Hash, Justification>,
 		remove_displaced: bool,
+		flat_state_update: &mut Option<FlatStateUpdate<'_, Block>>,
 	) -> ClientResult<MetaUpdate<Block>> {
 		// TODO: ensure best chain contains this block.
 		let number = *header.number();
@@ -1378,6 +1492,7 @@ impl<Block: BlockT> Backend<Block> {
 			with_state,
 			current_transaction_justifications,
 			remove_displaced,
+			flat_state_update,
 		)?;
 
 		if let Some(justification) = justification {
@@ -1443,6 +1558,7 @@ impl<Block: BlockT> Backend<Block> {
 
 	fn try_commit_operation(&self, mut operation: BlockImportOperation<Block>) -> ClientResult<()> {
 		let mut transaction = Transaction::new();
+		let mut flat_state_update = self.flat_state_update();
 
 		operation.apply_aux(&mut transaction);
 		operation.apply_offchain(&mut transaction);
@@ -1468,6 +1584,7 @@ impl<Block: BlockT> Backend<Block> {
 				justification,
 				&mut current_transaction_justifications,
 				finalized_blocks.peek().is_none(),
+				&mut flat_state_update,
 			)?);
 			last_finalized_hash = block_hash;
 			last_finalized_num = *block_header.number();
@@ -1594,6 +1711,19 @@ impl<Block: BlockT> Backend<Block> {
 						sp_blockchain::Error::from_state_db(e)
 					})?;
 				apply_state_commit(&mut transaction, commit);
+				if let Some(update) = &mut flat_state_update {
+					let (parent, changes) = match operation.flat_state_reset.take() {
+						Some(changes) => (None, changes),
+						None => (
+							Some(parent_hash),
+							BlockChanges::new(
+								std::mem::take(&mut operation.storage_updates),
+								std::mem::take(&mut operation.child_storage_updates),
+							),
+						),
+					};
+					update.note_block(&mut transaction, hash, number, parent, changes)?;
+				}
 				if number <= last_finalized_num {
 					// Canonicalize in the db when re-importing existing blocks with state.
 					let commit = self.storage.state_db.canonicalize_block(&hash).map_err(
@@ -1644,6 +1774,7 @@ impl<Block: BlockT> Backend<Block> {
 					operation.commit_state,
 					&mut current_transaction_justifications,
 					true,
+					&mut flat_state_update,
 				)?;
 			} else {
 				// canonicalize blocks which are old enough, regardless of finality.
@@ -1810,7 +1941,7 @@ impl<Block: BlockT> Backend<Block> {
 			}
 		}
 
-		self.storage.db.commit(transaction)?;
+		self.commit_with_flat_state(transaction, flat_state_update)?;
 
 		// Apply all in-memory state changes.
 		// Code beyond this point can't fail.
@@ -1844,6 +1975,7 @@ impl<Block: BlockT> Backend<Block> {
 		with_state: bool,
 		current_transaction_justifications: &mut HashMap<Block::Hash, Justification>,
 		remove_displaced: bool,
+		flat_state_update: &mut Option<FlatStateUpdate<'_, Block>>,
 	) -> ClientResult<()> {
 		let f_num = *f_header.number();
 
@@ -1851,6 +1983,9 @@ impl<Block: BlockT> Backend<Block> {
 		if with_state {
 			transaction.set_from_vec(columns::META, meta_keys::FINALIZED_STATE, lookup_key.clone());
 		}
+		if let Some(update) = flat_state_update {
+			update.finalize(transaction, f_hash, f_num)?;
+		}
 		transaction.set_from_vec(columns::META, meta_keys::FINALIZED_BLOCK, lookup_key);
 
 		let requires_canonicalization = match self.storage.state_db.last_canonicalized() {
@@ -2069,6 +2204,14 @@ fn apply_indexed_body<Block: BlockT>(transaction: &mut Transaction<DbHash>, body
 	}
 }
 
+impl<Block: BlockT> Drop for Backend<Block> {
+	fn drop(&mut self) {
+		if let Some(flat_state) = &self.storage.flat_state {
+			flat_state.abort_population();
+		}
+	}
+}
+
 impl<Block> sc_client_api::backend::AuxStore for Backend<Block>
 where
 	Block: BlockT,
@@ -2120,6 +2263,8 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			commit_state: false,
 			create_gap: true,
 			index_ops: Default::default(),
+			track_flat_state: self.storage.flat_state.is_some(),
+			flat_state_reset: None,
 		})
 	}
 
@@ -2163,6 +2308,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 	) -> ClientResult<()> {
 		let mut transaction = Transaction::new();
 		let header = self.blockchain.expect_header(hash)?;
+		let mut flat_state_update = self.flat_state_update();
 
 		let mut current_transaction_justifications = HashMap::new();
 		let m = self.finalize_block_with_transaction(
@@ -2173,9 +2319,10 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			justification,
 			&mut current_transaction_justifications,
 			true,
+			&mut flat_state_update,
 		)?;
 
-		self.storage.db.commit(transaction)?;
+		self.commit_with_flat_state(transaction, flat_state_update)?;
 		self.blockchain.update_meta(m);
 		Ok(())
 	}
@@ -2320,12 +2467,16 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 							number_to_revert,
 							&hash_to_revert,
 						)?;
+						let mut flat_state_update = self.flat_state_update();
 						if update_finalized {
 							transaction.set_from_vec(
 								columns::META,
 								meta_keys::FINALIZED_BLOCK,
 								key.clone(),
 							);
+							if let Some(update) = &mut flat_state_update {
+								update.revert_finalized(number_to_revert);
+							}
 
 							reverted_finalized.insert(removed_hash);
 							if let Some((hash, _)) = self.blockchain.info().finalized_state {
@@ -2359,7 +2510,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 							meta_keys::CHILDREN_PREFIX,
 							hash_to_revert,
 						);
-						self.storage.db.commit(transaction)?;
+						self.commit_with_flat_state(transaction, flat_state_update)?;
 
 						let is_best = number_to_revert < best_number;
 
@@ -2422,6 +2573,10 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			apply_state_commit(&mut transaction, commit);
 		}
 		transaction.remove(columns::KEY_LOOKUP, hash.as_ref());
+		let mut flat_state_update = self.flat_state_update();
+		if let Some(update) = &mut flat_state_update {
+			update.discard(&mut transaction, hash)?;
+		}
 
 		let children: Vec<_> = self
 			.blockchain()
@@ -2450,11 +2605,11 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 
 		let remove_outcome = leaves.remove(hash, hdr.number, parent_leaf);
 		leaves.prepare_transaction(&mut transaction, columns::META, meta_keys::LEAF_PREFIX);
-		if let Err(e) = self.storage.db.commit(transaction) {
+		if let Err(e) = self.commit_with_flat_state(transaction, flat_state_update) {
 			if let Some(outcome) = remove_outcome {
 				leaves.undo().undo_remove(outcome);
 			}
-			return Err(e.into());
+			return Err(e);
 		}
 		self.blockchain().remove_header_metadata(hash);
 		Ok(())
@@ -2772,6 +2927,7 @@ pub(crate) mod tests {
 				state_pruning: Some(PruningMode::blocks_pruning(1)),
 				source: DatabaseSource::Custom { db: backing, require_create_flag: false },
 				blocks_pruning: BlocksPruning::KeepFinalized,
+				flat_state: false,
 			},
 			0,
 		)
@@ -2864,6 +3020,201 @@ pub(crate) mod tests {
 		}
 	}
 
+	fn flat_state_test_backend(
+		db: Arc<dyn Database<DbHash>>,
+		require_create_flag: bool,
+		flat_state: bool,
+	) -> Backend<Block> {
+		Backend::<Block>::new(
+			DatabaseSettings {
+				trie_cache_maximum_size: Some(16 * 1024 * 1024),
+				state_pruning: Some(PruningMode::blocks_pruning(2)),
+				source: DatabaseSource::Custom { db, require_create_flag },
+				blocks_pruning: BlocksPruning::KeepFinalized,
+				flat_state,
+			},
+			0,
+		)
+		.unwrap()
+	}
+
+	fn insert_flat_state_blocks(backend: &Backend<Block>) -> (H256, H256) {
+		let state_version = StateVersion::V1;
+		let genesis = {
+			let mut op = backend.begin_operation().unwrap();
+			let storage = vec![(vec![1, 3, 5], vec![2, 4, 6]), (vec![1, 2, 3], vec![9, 9, 9])];
+			let mut header = Header {
+				number: 0,
+				parent_hash: Default::default(),
+				state_root: Default::default(),
+				digest: Default::default(),
+				extrinsics_root: Default::default(),
+			};
+			header.state_root = op
+				.reset_storage(
+					Storage {
+						top: storage.into_iter().collect(),
+						children_default: Default::default(),
+					},
+					state_version,
+				)
+				.unwrap();
+			op.set_block_data(header.clone(), Some(vec![]), None, None, NewBlockState::Final)
+				.unwrap();
+			backend.commit_operation(op).unwrap();
+			header.hash()
+		};
+
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, genesis).unwrap();
+		let storage = vec![(vec![1, 3, 5], None), (vec![5, 5, 5], Some(vec![4, 5, 6]))];
+		let (root, overlay) = op.old_state.storage_root(
+			storage.iter().map(|(k, v)| (k.as_slice(), v.as_ref().map(|v| &v[..]))),
+			state_version,
+		);
+		op.update_db_storage(overlay).unwrap();
+		op.update_storage(storage, Vec::new()).unwrap();
+		let header = Header {
+			number: 1,
+			parent_hash: genesis,
+			state_root: root,
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		};
+		op.set_block_data(header.clone(), Some(vec![]), None, None, NewBlockState::Best)
+			.unwrap();
+		backend.commit_operation(op).unwrap();
+
+		(genesis, header.hash())
+	}
+
+	#[test]
+	fn flat_state_follows_finalization() {
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let backend = flat_state_test_backend(db, true, true);
+		let (genesis, block1) = insert_flat_state_blocks(&backend);
+		let flat_state = backend.storage.flat_state.as_ref().unwrap();
+
+		// The finalized state is stored, and reads at its descendants go through their journals.
+		assert_eq!(flat_state.head(), Some((genesis, 0)));
+		assert_eq!(flat_state.storage(&genesis, &[1, 3, 5]), Some(Some(vec![2, 4, 6])));
+		assert_eq!(flat_state.storage(&block1, &[1, 3, 5]), Some(None));
+		assert_eq!(flat_state.storage(&block1, &[1, 2, 3]), Some(Some(vec![9, 9, 9])));
+		assert_eq!(flat_state.storage(&block1, &[5, 5, 5]), Some(Some(vec![4, 5, 6])));
+		let state = backend.state_at(block1).unwrap();
+		assert_eq!(state.storage(&[1, 3, 5]).unwrap(), None);
+		assert_eq!(state.storage(&[5, 5, 5]).unwrap(), Some(vec![4, 5, 6]));
+		drop(state);
+
+		backend.finalize_block(block1, None).unwrap();
+		assert_eq!(flat_state.head(), Some((block1, 1)));
+		assert_eq!(flat_state.storage(&genesis, &[1, 3, 5]), None);
+		assert_eq!(flat_state.storage(&block1, &[1, 3, 5]), Some(None));
+		assert_eq!(flat_state.storage(&block1, &[1, 2, 3]), Some(Some(vec![9, 9, 9])));
+		assert_eq!(flat_state.storage(&block1, &[5, 5, 5]), Some(Some(vec![4, 5, 6])));
+		let state = backend.state_at(block1).unwrap();
+		assert_eq!(state.storage(&[1, 3, 5]).unwrap(), None);
+		assert_eq!(state.storage(&[1, 2, 3]).unwrap(), Some(vec![9, 9, 9]));
+		assert!(state.exists_storage(&[5, 5, 5]).unwrap());
+	}
+
+	#[test]
+	fn flat_state_prunes_journals_on_finalization() {
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let backend = flat_state_test_backend(db, true, true);
+		let (genesis, block1) = insert_flat_state_blocks(&backend);
+		let flat_state = backend.storage.flat_state.as_ref().unwrap();
+
+		// A fork of block 1.
+		let fork = {
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, genesis).unwrap();
+			let storage = vec![(vec![1, 2, 3], Some(vec![7, 7, 7]))];
+			let (root, overlay) = op.old_state.storage_root(
+				storage.iter().map(|(k, v)| (k.as_slice(), v.as_ref().map(|v| &v[..]))),
+				StateVersion::V1,
+			);
+			op.update_db_storage(overlay).unwrap();
+			op.update_storage(storage, Vec::new()).unwrap();
+			let header = Header {
+				number: 1,
+				parent_hash: genesis,
+				state_root: root,
+				digest: Default::default(),
+				extrinsics_root: H256::from_low_u64_be(1),
+			};
+			op.set_block_data(header.clone(), Some(vec![]), None, None, NewBlockState::Normal)
+				.unwrap();
+			backend.commit_operation(op).unwrap();
+			header.hash()
+		};
+		assert_eq!(flat_state.storage(&fork, &[1, 2, 3]), Some(Some(vec![7, 7, 7])));
+		assert_eq!(flat_state.storage(&fork, &[1, 3, 5]), Some(Some(vec![2, 4, 6])));
+		assert!(flat_state.has_journal(&block1));
+		assert!(flat_state.has_journal(&fork));
+
+		// Finalizing block 1 removes the journals of both blocks at height 1.
+		backend.finalize_block(block1, None).unwrap();
+		assert!(!flat_state.has_journal(&block1));
+		assert!(!flat_state.has_journal(&fork));
+		assert_eq!(flat_state.storage(&fork, &[1, 2, 3]), None);
+		assert_eq!(flat_state.storage(&block1, &[1, 2, 3]), Some(Some(vec![9, 9, 9])));
+	}
+
+	#[test]
+	fn flat_state_is_populated_on_startup() {
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let block1 = {
+			let backend = flat_state_test_backend(db.clone(), true, false);
+			let (_, block1) = insert_flat_state_blocks(&backend);
+			backend.finalize_block(block1, None).unwrap();
+			block1
+		};
+
+		let backend = flat_state_test_backend(db, false, true);
+		let flat_state = backend.storage.flat_state.as_ref().unwrap();
+		// The flat state is populated in the background.
+		for _ in 0..500 {
+			if flat_state.head().is_some() {
+				break
+			}
+			std::thread::sleep(std::time::Duration::from_millis(10));
+		}
+		assert_eq!(flat_state.head(), Some((block1, 1)));
+		assert_eq!(flat_state.storage(&block1, &[1, 3, 5]), Some(None));
+		assert_eq!(flat_state.storage(&block1, &[1, 2, 3]), Some(Some(vec![9, 9, 9])));
+		assert_eq!(flat_state.storage(&block1, &[5, 5, 5]), Some(Some(vec![4, 5, 6])));
+	}
+
+	#[test]
+	fn flat_state_catches_up_with_finalization_after_population() {
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let backend = flat_state_test_backend(db, true, true);
+		let (genesis, block1) = insert_flat_state_blocks(&backend);
+		let flat_state = backend.storage.flat_state.as_ref().unwrap();
+
+		// Block 1 is finalized while the state of the genesis block is populated.
+		flat_state.begin_population(genesis, 0).unwrap();
+		assert_eq!(flat_state.head(), None);
+		backend.finalize_block(block1, None).unwrap();
+		assert_eq!(flat_state.head(), None);
+		let state = backend.state_at(genesis).unwrap();
+		flat_state.populate(&state).unwrap();
+		drop(state);
+		assert_eq!(flat_state.head(), Some((genesis, 0)));
+		assert_eq!(flat_state.storage(&genesis, &[1, 3, 5]), Some(Some(vec![2, 4, 6])));
+
+		// The journal of block 1 is kept until the next finalization.
+		let mut transaction = Transaction::new();
+		let mut update = flat_state.update();
+		update.finalize(&mut transaction, block1, 1).unwrap();
+		update.commit(transaction).unwrap();
+		assert_eq!(flat_state.head(), Some((block1, 1)));
+		assert_eq!(flat_state.storage(&block1, &[1, 3, 5]), Some(None));
+		assert_eq!(flat_state.storage(&block1, &[1, 2, 3]), Some(Some(vec![9, 9, 9])));
+		assert_eq!(flat_state.storage(&block1, &[5, 5, 5]), Some(Some(vec![4, 5, 6])));
+	}
+
 	#[test]
 	fn delete_only_when_negative_rc() {
 		sp_tracing::try_init_simple();
@@ -67,6 +67,10 @@ pub fn open<H: Clone + AsRef<[u8]>>(
 			tx_col.ref_counted = true;
 			tx_col.preimage = true;
 			tx_col.uniform = true;
+
+			// Generations of the flat state are removed by prefix.
+			let flat_state_col = &mut config.columns[columns::FLAT_STATE as usize];
+			flat_state_col.btree_index = true;
 		},
 	}
 
@@ -92,11 +96,34 @@ fn ref_counted_column(col: u32) -> bool {
 
 impl<H: Clone + AsRef<[u8]>> Database<H> for DbAdapter {
 	fn commit(&self, transaction: Transaction<H>) -> Result<(), DatabaseError> {
+		// Prefix removals are turned into the removal of the keys present before the transaction,
+		// which is only supported by ordered columns.
+		let mut changes = Vec::with_capacity(transaction.0.len());
+		for change in transaction.0 {
+			match change {
+				Change::RemovePrefix(col, prefix) => {
+					let mut iter =
+						self.0.iter(col as u8).map_err(|e| DatabaseError(Box::new(e)))?;
+					iter.seek(&prefix).map_err(|e| DatabaseError(Box::new(e)))?;
+					while let Some((key, _)) =
+						iter.next().map_err(|e| DatabaseError(Box::new(e)))?
+					{
+						if !key.starts_with(&prefix) {
+							break
+						}
+						changes.push(Change::Remove(col, key));
+					}
+				},
+				change => changes.push(change),
+			}
+		}
+
 		let mut not_ref_counted_column = Vec::new();
-		let result = self.0.commit(transaction.0.into_iter().filter_map(|change| {
+		let result = self.0.commit(changes.into_iter().filter_map(|change| {
 			Some(match change {
 				Change::Set(col, key, value) => (col as u8, key, Some(value)),
 				Change::Remove(col, key) => (col as u8, key, None),
+				Change::RemovePrefix(..) => unreachable!("Prefix removals are expanded above; qed"),
 				Change::Store(col, key, value) =>
 					if ref_counted_column(col) {
 						(col as u8, key.as_ref().to_vec(), Some(value))
@@ -26,6 +26,8 @@ use std::io;
 /// Local keystore implementation
 mod local;
 pub use local::LocalKeystore;
+pub mod remote;
+pub use remote::{Operation, Policy, RemoteKeystore, RemoteSigner};
 pub use sp_keystore::Keystore;
 
 /// Keystore error.
@@ -77,6 +77,26 @@ impl LocalKeystore {
 		self.0.read().key_pair::<Pair>(public)
 	}
 
+	sp_keystore::bandersnatch_experimental_enabled! {
+	/// Produce the part of a bandersnatch ring VRF signature which requires the secret key.
+	///
+	/// Returns `Ok(None)` if the key doesn't exist. See
+	/// [`RingVrfPartialSignature`](bandersnatch::ring_vrf::RingVrfPartialSignature).
+	pub fn bandersnatch_ring_vrf_sign_partial(
+		&self,
+		key_type: KeyTypeId,
+		public: &bandersnatch::Public,
+		data: &bandersnatch::vrf::VrfSignData,
+	) -> std::result::Result<Option<bandersnatch::ring_vrf::RingVrfPartialSignature>, TraitError> {
+		let sig = self
+			.0
+			.read()
+			.key_pair_by_type::<bandersnatch::Pair>(public, key_type)?
+			.map(|pair| pair.ring_vrf_sign_partial(data));
+		Ok(sig)
+	}
+	}
+
 	fn public_keys<T: CorePair>(&self, key_type: KeyTypeId) -> Vec<T::Public> {
 		self.0
 			.read()
@@ -0,0 +1,1084 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//
+//! Remote keystore implementation.
+//!
+//! [`RemoteKeystore`] holds no secret material. Every [`Keystore`] request is forwarded to an
+//! external signer process listening on a Unix domain socket. [`RemoteSigner`] is the other end
+//! of the connection and serves the requests allowed by its [`Policy`] out of a
+//! [`LocalKeystore`].
+//!
+//! Every message is a SCALE encoded [`Request`] or [`Response`] prefixed by its length as a
+//! little-endian `u32`. A connection carries any number of request/response pairs, one at a
+//! time. Public keys, VRF inputs and returned values travel as raw bytes so that the node and
+//! the signer don't need to be compiled with the same set of experimental crypto features.
+//!
+//! Merlin transcripts can't be serialized, thus Sr25519 VRF inputs travel as the data they were
+//! built from ([`VrfTranscriptData`]) and are rebuilt by the signer. The ring prover is too large
+//! to be transferred, thus Bandersnatch ring VRF signing is split in two: the signer produces the
+//! part which requires the secret key and the node completes it with the ring proof.
+
+use codec::{Decode, DecodeAll, Encode};
+use parking_lot::Mutex;
+use sp_core::{
+	crypto::{ByteArray, CryptoTypeId, KeyTypeId},
+	ecdsa, ed25519,
+	sr25519::{
+		self,
+		vrf::{VrfTranscript, VrfTranscriptData},
+	},
+};
+use sp_keystore::{Error as TraitError, Keystore, KeystorePtr};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	io::{self, Read, Write},
+	os::unix::net::{UnixListener, UnixStream},
+	path::PathBuf,
+	str::FromStr,
+	sync::Arc,
+	time::Duration,
+};
+
+sp_keystore::bandersnatch_experimental_enabled! {
+use sp_core::bandersnatch;
+}
+
+sp_keystore::bls_experimental_enabled! {
+use sp_core::{bls381, ecdsa_bls381};
+}
+
+use crate::{LocalKeystore, Result};
+
+const LOG_TARGET: &str = "remote-keystore";
+
+/// Maximum size of a single protocol message.
+pub const MAX_MESSAGE_SIZE: u32 = 1024 * 1024;
+
+/// Default read and write timeout of a [`RemoteKeystore`] connection.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum number of distinct transcript labels accepted by a [`RemoteSigner`].
+const MAX_TRANSCRIPT_LABELS: usize = 1024;
+
+/// Maximum length of a transcript label accepted by a [`RemoteSigner`].
+const MAX_TRANSCRIPT_LABEL_LEN: usize = 64;
+
+/// Request sent by a [`RemoteKeystore`] to a [`RemoteSigner`].
+///
+/// Each variant maps to one of the [`Keystore`] methods and carries its arguments. Methods
+/// which only differ by the crypto scheme share a variant and are told apart by `crypto_id`.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum Request {
+	/// [`Keystore::insert`].
+	Insert { key_type: KeyTypeId, suri: String, public: Vec<u8> },
+	/// [`Keystore::keys`].
+	Keys { key_type: KeyTypeId },
+	/// [`Keystore::has_keys`].
+	HasKeys { public_keys: Vec<(Vec<u8>, KeyTypeId)> },
+	/// `<crypto>_public_keys` methods.
+	PublicKeys { crypto_id: CryptoTypeId, key_type: KeyTypeId },
+	/// `<crypto>_generate_new` methods.
+	GenerateNew { crypto_id: CryptoTypeId, key_type: KeyTypeId, seed: Option<String> },
+	/// `<crypto>_sign` methods.
+	Sign { crypto_id: CryptoTypeId, key_type: KeyTypeId, public: Vec<u8>, msg: Vec<u8> },
+	/// [`Keystore::ecdsa_sign_prehashed`].
+	EcdsaSignPrehashed { key_type: KeyTypeId, public: Vec<u8>, msg: [u8; 32] },
+	/// `Keystore::ecdsa_bls381_sign_with_keccak256`.
+	EcdsaBls381SignWithKeccak256 { key_type: KeyTypeId, public: Vec<u8>, msg: Vec<u8> },
+	/// [`Keystore::sr25519_vrf_sign`].
+	Sr25519VrfSign {
+		key_type: KeyTypeId,
+		public: Vec<u8>,
+		transcript: VrfTranscriptData,
+		extra: Option<VrfTranscriptData>,
+	},
+	/// [`Keystore::sr25519_vrf_pre_output`].
+	Sr25519VrfPreOutput { key_type: KeyTypeId, public: Vec<u8>, input: VrfTranscriptData },
+	/// `Keystore::bandersnatch_vrf_sign`, `data` is the encoded `VrfSignData`.
+	BandersnatchVrfSign { key_type: KeyTypeId, public: Vec<u8>, data: Vec<u8> },
+	/// `Keystore::bandersnatch_vrf_pre_output`, `input` is the encoded `VrfInput`.
+	BandersnatchVrfPreOutput { key_type: KeyTypeId, public: Vec<u8>, input: Vec<u8> },
+	/// First half of `Keystore::bandersnatch_ring_vrf_sign`, `data` is the encoded
+	/// `VrfSignData`. Answered with a `RingVrfPartialSignature`.
+	BandersnatchRingVrfSignPartial { key_type: KeyTypeId, public: Vec<u8>, data: Vec<u8> },
+}
+
+impl Request {
+	/// Kind of operation requested.
+	pub fn operation(&self) -> Operation {
+		match self {
+			Request::Keys { .. } | Request::HasKeys { .. } | Request::PublicKeys { .. } =>
+				Operation::Keys,
+			Request::Insert { .. } | Request::GenerateNew { .. } => Operation::Generate,
+			Request::Sign { .. } |
+			Request::EcdsaSignPrehashed { .. } |
+			Request::EcdsaBls381SignWithKeccak256 { .. } => Operation::Sign,
+			Request::Sr25519VrfSign { .. } |
+			Request::Sr25519VrfPreOutput { .. } |
+			Request::BandersnatchVrfSign { .. } |
+			Request::BandersnatchVrfPreOutput { .. } |
+			Request::BandersnatchRingVrfSignPartial { .. } => Operation::Vrf,
+		}
+	}
+
+	/// Key types of the keys involved in the request.
+	pub fn key_types(&self) -> Vec<KeyTypeId> {
+		match self {
+			Request::HasKeys { public_keys } =>
+				public_keys.iter().map(|(_, key_type)| *key_type).collect(),
+			Request::Insert { key_type, .. } |
+			Request::Keys { key_type } |
+			Request::PublicKeys { key_type, .. } |
+			Request::GenerateNew { key_type, .. } |
+			Request::Sign { key_type, .. } |
+			Request::EcdsaSignPrehashed { key_type, .. } |
+			Request::EcdsaBls381SignWithKeccak256 { key_type, .. } |
+			Request::Sr25519VrfSign { key_type, .. } |
+			Request::Sr25519VrfPreOutput { key_type, .. } |
+			Request::BandersnatchVrfSign { key_type, .. } |
+			Request::BandersnatchVrfPreOutput { key_type, .. } |
+			Request::BandersnatchRingVrfSignPartial { key_type, .. } => vec![*key_type],
+		}
+	}
+}
+
+/// Kind of operation requested from a [`RemoteSigner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Operation {
+	/// List the public keys.
+	Keys,
+	/// Generate or insert a key.
+	Generate,
+	/// Sign a message.
+	Sign,
+	/// Produce a VRF pre-output or signature, including ring VRF signatures.
+	Vrf,
+}
+
+impl FromStr for Operation {
+	type Err = String;
+
+	fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+		match s {
+			"keys" => Ok(Operation::Keys),
+			"generate" => Ok(Operation::Generate),
+			"sign" => Ok(Operation::Sign),
+			"vrf" => Ok(Operation::Vrf),
+			_ => Err(format!("Unknown operation `{s}`, expected one of keys, generate, sign, vrf")),
+		}
+	}
+}
+
+/// Operations a [`RemoteSigner`] serves, per key type.
+///
+/// A request involving a key type for which its operation is not allowed fails without
+/// reaching the keystore.
+#[derive(Debug, Clone, Default)]
+pub struct Policy {
+	allow_all: bool,
+	allowed: BTreeMap<KeyTypeId, BTreeSet<Operation>>,
+}
+
+impl Policy {
+	/// Serve every request.
+	pub fn allow_all() -> Self {
+		Self { allow_all: true, ..Default::default() }
+	}
+
+	/// Serve no request, use [`Policy::allow`] to allow some.
+	pub fn deny_all() -> Self {
+		Self::default()
+	}
+
+	/// Serve `operations` on the keys of `key_type`.
+	pub fn allow(
+		mut self,
+		key_type: KeyTypeId,
+		operations: impl IntoIterator<Item = Operation>,
+	) -> Self {
+		self.allowed.entry(key_type).or_default().extend(operations);
+		self
+	}
+
+	/// Whether `operation` is served on the keys of `key_type`.
+	pub fn is_allowed(&self, key_type: KeyTypeId, operation: Operation) -> bool {
+		self.allow_all ||
+			self.allowed
+				.get(&key_type)
+				.is_some_and(|operations| operations.contains(&operation))
+	}
+
+	fn check(&self, request: &Request) -> std::result::Result<(), RemoteError> {
+		let operation = request.operation();
+		match request
+			.key_types()
+			.into_iter()
+			.find(|key_type| !self.is_allowed(*key_type, operation))
+		{
+			Some(key_type) => Err(RemoteError::Other(format!(
+				"{operation:?} on {key_type:?} keys is not allowed by the signer policy"
+			))),
+			None => Ok(()),
+		}
+	}
+}
+
+/// Error returned by a [`RemoteSigner`].
+///
+/// Mirrors [`sp_keystore::Error`].
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum RemoteError {
+	/// Public key type is not supported
+	KeyNotSupported(KeyTypeId),
+	/// Validation error
+	ValidationError(String),
+	/// Keystore unavailable
+	Unavailable,
+	/// Any other error
+	Other(String),
+}
+
+impl From<TraitError> for RemoteError {
+	fn from(error: TraitError) -> Self {
+		match error {
+			TraitError::KeyNotSupported(id) => RemoteError::KeyNotSupported(id),
+			TraitError::ValidationError(e) => RemoteError::ValidationError(e),
+			TraitError::Unavailable => RemoteError::Unavailable,
+			TraitError::Other(e) => RemoteError::Other(e),
+		}
+	}
+}
+
+impl From<RemoteError> for TraitError {
+	fn from(error: RemoteError) -> Self {
+		match error {
+			RemoteError::KeyNotSupported(id) => TraitError::KeyNotSupported(id),
+			RemoteError::ValidationError(e) => TraitError::ValidationError(e),
+			RemoteError::Unavailable => TraitError::Unavailable,
+			RemoteError::Other(e) => TraitError::Other(e),
+		}
+	}
+}
+
+/// Response sent by a [`RemoteSigner`].
+///
+/// On success it contains the SCALE encoded value returned by the [`Keystore`] method.
+pub type Response = std::result::Result<Vec<u8>, RemoteError>;
+
+fn write_message<T: Encode>(writer: &mut impl Write, message: &T) -> io::Result<()> {
+	let len = u32::try_from(message.encoded_size())
+		.ok()
+		.filter(|len| *len <= MAX_MESSAGE_SIZE)
+		.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Message too large"))?;
+	let mut buf = Vec::with_capacity(4 + len as usize);
+	buf.extend_from_slice(&len.to_le_bytes());
+	message.encode_to(&mut buf);
+	writer.write_all(&buf)?;
+	writer.flush()
+}
+
+/// Read a message, returns `None` if the peer closed the connection.
+fn read_message<T: Decode>(reader: &mut impl Read) -> io::Result<Option<T>> {
+	let mut len = [0; 4];
+	match reader.read_exact(&mut len) {
+		Ok(()) => {},
+		Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+		Err(e) => return Err(e),
+	}
+	let len = u32::from_le_bytes(len);
+	if len > MAX_MESSAGE_SIZE {
+		return Err(io::Error::new(io::ErrorKind::InvalidData, "Message too large"))
+	}
+	let mut buf = vec![0; len as usize];
+	reader.read_exact(&mut buf)?;
+	T::decode_all(&mut &buf[..])
+		.map(Some)
+		.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+}
+
+/// A keystore which forwards every request to a [`RemoteSigner`] over a Unix socket.
+///
+/// The connection is established lazily and re-established on the next request if it breaks,
+/// thus the signer can be restarted without restarting the node. While the signer is not
+/// reachable all the signing requests fail with [`TraitError::Unavailable`].
+pub struct RemoteKeystore {
+	path: PathBuf,
+	timeout: Duration,
+	connection: Mutex<Option<UnixStream>>,
+}
+
+impl RemoteKeystore {
+	/// Create a keystore connected to the signer listening at `path`.
+	///
+	/// Fails if the signer can't be reached.
+	pub fn connect<T: Into<PathBuf>>(path: T) -> Result<Self> {
+		let keystore = Self::new(path);
+		*keystore.connection.lock() = Some(keystore.open()?);
+		Ok(keystore)
+	}
+
+	/// Create a keystore for the signer listening at `path` without connecting to it.
+	pub fn new<T: Into<PathBuf>>(path: T) -> Self {
+		Self { path: path.into(), timeout: DEFAULT_TIMEOUT, connection: Mutex::new(None) }
+	}
+
+	/// Set the read and write timeout of the signer connection.
+	pub fn with_timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = timeout;
+		*self.connection.lock() = None;
+		self
+	}
+
+	fn open(&self) -> io::Result<UnixStream> {
+		let stream = UnixStream::connect(&self.path)?;
+		stream.set_read_timeout(Some(self.timeout))?;
+		stream.set_write_timeout(Some(self.timeout))?;
+		Ok(stream)
+	}
+
+	fn exchange(&self, request: &Request) -> io::Result<Response> {
+		// Keystore requests are synchronous but mostly issued from async tasks. Within a
+		// multi-threaded runtime the worker thread hands its tasks over to another thread
+		// while waiting for the connection lock and the signer, instead of stalling them.
+		match tokio::runtime::Handle::try_current() {
+			Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread =>
+				tokio::task::block_in_place(|| self.exchange_blocking(request)),
+			_ => self.exchange_blocking(request),
+		}
+	}
+
+	fn exchange_blocking(&self, request: &Request) -> io::Result<Response> {
+		let mut connection = self.connection.lock();
+		let reused = connection.is_some();
+		match Self::exchange_with(&mut connection, request, || self.open()) {
+			// The signer may have been restarted since the last request.
+			Err(e) if reused && is_disconnect(&e) =>
+				Self::exchange_with(&mut connection, request, || self.open()),
+			result => result,
+		}
+	}
+
+	fn exchange_with(
+		connection: &mut Option<UnixStream>,
+		request: &Request,
+		open: impl FnOnce() -> io::Result<UnixStream>,
+	) -> io::Result<Response> {
+		if connection.is_none() {
+			*connection = Some(open()?);
+		}
+		let stream = connection.as_mut().expect("connection was just opened; qed");
+		let result = write_message(stream, request)
+			.and_then(|_| read_message(stream)?.ok_or_else(|| io::ErrorKind::UnexpectedEof.into()));
+		if result.is_err() {
+			*connection = None;
+		}
+		result
+	}
+
+	fn request<T: Decode>(&self, request: Request) -> std::result::Result<T, TraitError> {
+		let response = self.exchange(&request).map_err(|e| {
+			log::warn!(target: LOG_TARGET, "Remote signer at {:?} unreachable: {e}", self.path);
+			TraitError::Unavailable
+		})?;
+		let bytes = response?;
+		T::decode_all(&mut &bytes[..])
+			.map_err(|e| TraitError::Other(format!("Invalid remote signer response: {e}")))
+	}
+
+	fn public_keys<T: Decode>(&self, crypto_id: CryptoTypeId, key_type: KeyTypeId) -> Vec<T> {
+		self.request(Request::PublicKeys { crypto_id, key_type }).unwrap_or_else(|e| {
+			log::debug!(target: LOG_TARGET, "Failed to fetch {crypto_id:?} public keys: {e}");
+			Vec::new()
+		})
+	}
+
+	fn generate_new<T: Decode>(
+		&self,
+		crypto_id: CryptoTypeId,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<T, TraitError> {
+		self.request(Request::GenerateNew { crypto_id, key_type, seed: seed.map(Into::into) })
+	}
+
+	fn sign<T: Decode>(
+		&self,
+		crypto_id: CryptoTypeId,
+		key_type: KeyTypeId,
+		public: &impl ByteArray,
+		msg: &[u8],
+	) -> std::result::Result<Option<T>, TraitError> {
+		self.request(Request::Sign {
+			crypto_id,
+			key_type,
+			public: public.to_raw_vec(),
+			msg: msg.to_vec(),
+		})
+	}
+}
+
+fn is_disconnect(error: &io::Error) -> bool {
+	matches!(
+		error.kind(),
+		io::ErrorKind::UnexpectedEof |
+			io::ErrorKind::BrokenPipe |
+			io::ErrorKind::ConnectionReset |
+			io::ErrorKind::ConnectionAborted
+	)
+}
+
+impl Keystore for RemoteKeystore {
+	fn insert(
+		&self,
+		key_type: KeyTypeId,
+		suri: &str,
+		public: &[u8],
+	) -> std::result::Result<(), ()> {
+		self.request(Request::Insert { key_type, suri: suri.into(), public: public.to_vec() })
+			.map_err(|_| ())
+	}
+
+	fn keys(&self, key_type: KeyTypeId) -> std::result::Result<Vec<Vec<u8>>, TraitError> {
+		self.request(Request::Keys { key_type })
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		self.request(Request::HasKeys { public_keys: public_keys.to_vec() })
+			.unwrap_or(false)
+	}
+
+	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
+		self.public_keys(sr25519::CRYPTO_ID, key_type)
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		self.generate_new(sr25519::CRYPTO_ID, key_type, seed)
+	}
+
+	fn sr25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<sr25519::Signature>, TraitError> {
+		self.sign(sr25519::CRYPTO_ID, key_type, public, msg)
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		data: &sr25519::vrf::VrfSignData,
+	) -> std::result::Result<Option<sr25519::vrf::VrfSignature>, TraitError> {
+		self.request(Request::Sr25519VrfSign {
+			key_type,
+			public: public.to_raw_vec(),
+			transcript: data.as_ref().data().clone(),
+			extra: data.extra().map(|extra| extra.data().clone()),
+		})
+	}
+
+	fn sr25519_vrf_pre_output(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		input: &sr25519::vrf::VrfInput,
+	) -> std::result::Result<Option<sr25519::vrf::VrfPreOutput>, TraitError> {
+		self.request(Request::Sr25519VrfPreOutput {
+			key_type,
+			public: public.to_raw_vec(),
+			input: input.data().clone(),
+		})
+	}
+
+	fn ed25519_public_keys(&self, key_type: KeyTypeId) -> Vec<ed25519::Public> {
+		self.public_keys(ed25519::CRYPTO_ID, key_type)
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		self.generate_new(ed25519::CRYPTO_ID, key_type, seed)
+	}
+
+	fn ed25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ed25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ed25519::Signature>, TraitError> {
+		self.sign(ed25519::CRYPTO_ID, key_type, public, msg)
+	}
+
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys(ecdsa::CRYPTO_ID, key_type)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		self.generate_new(ecdsa::CRYPTO_ID, key_type, seed)
+	}
+
+	fn ecdsa_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.sign(ecdsa::CRYPTO_ID, key_type, public, msg)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.request(Request::EcdsaSignPrehashed {
+			key_type,
+			public: public.to_raw_vec(),
+			msg: *msg,
+		})
+	}
+
+	sp_keystore::bandersnatch_experimental_enabled! {
+		fn bandersnatch_public_keys(&self, key_type: KeyTypeId) -> Vec<bandersnatch::Public> {
+			self.public_keys(bandersnatch::CRYPTO_ID, key_type)
+		}
+
+		fn bandersnatch_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bandersnatch::Public, TraitError> {
+			self.generate_new(bandersnatch::CRYPTO_ID, key_type, seed)
+		}
+
+		fn bandersnatch_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bandersnatch::Signature>, TraitError> {
+			self.sign(bandersnatch::CRYPTO_ID, key_type, public, msg)
+		}
+
+		fn bandersnatch_vrf_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			data: &bandersnatch::vrf::VrfSignData,
+		) -> std::result::Result<Option<bandersnatch::vrf::VrfSignature>, TraitError> {
+			self.request(Request::BandersnatchVrfSign {
+				key_type,
+				public: public.to_raw_vec(),
+				data: data.encode(),
+			})
+		}
+
+		fn bandersnatch_vrf_pre_output(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			input: &bandersnatch::vrf::VrfInput,
+		) -> std::result::Result<Option<bandersnatch::vrf::VrfPreOutput>, TraitError> {
+			self.request(Request::BandersnatchVrfPreOutput {
+				key_type,
+				public: public.to_raw_vec(),
+				input: input.encode(),
+			})
+		}
+
+		fn bandersnatch_ring_vrf_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			data: &bandersnatch::vrf::VrfSignData,
+			prover: &bandersnatch::ring_vrf::RingProver,
+		) -> std::result::Result<Option<bandersnatch::ring_vrf::RingVrfSignature>, TraitError> {
+			let partial: Option<bandersnatch::ring_vrf::RingVrfPartialSignature> =
+				self.request(Request::BandersnatchRingVrfSignPartial {
+					key_type,
+					public: public.to_raw_vec(),
+					data: data.encode(),
+				})?;
+			Ok(partial.map(|partial| partial.finalize(prover)))
+		}
+	}
+
+	sp_keystore::bls_experimental_enabled! {
+		fn bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<bls381::Public> {
+			self.public_keys(bls381::CRYPTO_ID, key_type)
+		}
+
+		fn bls381_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bls381::Public, TraitError> {
+			self.generate_new(bls381::CRYPTO_ID, key_type, seed)
+		}
+
+		fn bls381_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bls381::Signature>, TraitError> {
+			self.sign(bls381::CRYPTO_ID, key_type, public, msg)
+		}
+
+		fn ecdsa_bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa_bls381::Public> {
+			self.public_keys(ecdsa_bls381::CRYPTO_ID, key_type)
+		}
+
+		fn ecdsa_bls381_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<ecdsa_bls381::Public, TraitError> {
+			self.generate_new(ecdsa_bls381::CRYPTO_ID, key_type, seed)
+		}
+
+		fn ecdsa_bls381_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<ecdsa_bls381::Signature>, TraitError> {
+			self.sign(ecdsa_bls381::CRYPTO_ID, key_type, public, msg)
+		}
+
+		fn ecdsa_bls381_sign_with_keccak256(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<ecdsa_bls381::Signature>, TraitError> {
+			self.request(Request::EcdsaBls381SignWithKeccak256 {
+				key_type,
+				public: public.to_raw_vec(),
+				msg: msg.to_vec(),
+			})
+		}
+	}
+}
+
+impl From<RemoteKeystore> for KeystorePtr {
+	fn from(keystore: RemoteKeystore) -> KeystorePtr {
+		Arc::new(keystore)
+	}
+}
+
+/// Serves [`RemoteKeystore`] requests out of a [`LocalKeystore`].
+#[derive(Clone)]
+pub struct RemoteSigner {
+	keystore: Arc<LocalKeystore>,
+	policy: Arc<Policy>,
+}
+
+impl RemoteSigner {
+	/// Create a signer backed by `keystore`, serving every request.
+	pub fn new(keystore: Arc<LocalKeystore>) -> Self {
+		Self { keystore, policy: Arc::new(Policy::allow_all()) }
+	}
+
+	/// Only serve the requests allowed by `policy`.
+	pub fn with_policy(mut self, policy: Policy) -> Self {
+		self.policy = Arc::new(policy);
+		self
+	}
+
+	/// Accept connections on `listener` and serve each one on a dedicated thread.
+	///
+	/// Only returns if accepting a connection fails.
+	pub fn run(&self, listener: UnixListener) -> io::Result<()> {
+		for stream in listener.incoming() {
+			let stream = stream?;
+			let signer = self.clone();
+			std::thread::Builder::new().name("remote-signer".into()).spawn(move || {
+				if let Err(e) = signer.serve(stream) {
+					log::debug!(target: LOG_TARGET, "Connection closed: {e}");
+				}
+			})?;
+		}
+		Ok(())
+	}
+
+	/// Serve requests received over `stream` until the peer closes the connection.
+	pub fn serve(&self, mut stream: UnixStream) -> io::Result<()> {
+		while let Some(request) = read_message(&mut stream)? {
+			let response = self.handle(request);
+			write_message(&mut stream, &response)?;
+		}
+		Ok(())
+	}
+
+	/// Handle a single request.
+	pub fn handle(&self, request: Request) -> Response {
+		self.policy.check(&request)?;
+		let keystore = &*self.keystore;
+		let encoded = match request {
+			Request::Insert { key_type, suri, public } => keystore
+				.insert(key_type, &suri, &public)
+				.map_err(|_| RemoteError::Other("Failed to insert key".into()))?
+				.encode(),
+			Request::Keys { key_type } => keystore.keys(key_type)?.encode(),
+			Request::HasKeys { public_keys } => keystore.has_keys(&public_keys).encode(),
+			Request::PublicKeys { crypto_id, key_type } => match crypto_id {
+				sr25519::CRYPTO_ID => keystore.sr25519_public_keys(key_type).encode(),
+				ed25519::CRYPTO_ID => keystore.ed25519_public_keys(key_type).encode(),
+				ecdsa::CRYPTO_ID => keystore.ecdsa_public_keys(key_type).encode(),
+				#[cfg(feature = "bandersnatch-experimental")]
+				bandersnatch::CRYPTO_ID => keystore.bandersnatch_public_keys(key_type).encode(),
+				#[cfg(feature = "bls-experimental")]
+				bls381::CRYPTO_ID => keystore.bls381_public_keys(key_type).encode(),
+				#[cfg(feature = "bls-experimental")]
+				ecdsa_bls381::CRYPTO_ID => keystore.ecdsa_bls381_public_keys(key_type).encode(),
+				id => return Err(unsupported_crypto(id)),
+			},
+			Request::GenerateNew { crypto_id, key_type, seed } => {
+				let seed = seed.as_deref();
+				match crypto_id {
+					sr25519::CRYPTO_ID => keystore.sr25519_generate_new(key_type, seed)?.encode(),
+					ed25519::CRYPTO_ID => keystore.ed25519_generate_new(key_type, seed)?.encode(),
+					ecdsa::CRYPTO_ID => keystore.ecdsa_generate_new(key_type, seed)?.encode(),
+					#[cfg(feature = "bandersnatch-experimental")]
+					bandersnatch::CRYPTO_ID => keystore.bandersnatch_generate_new(key_type, seed)?.encode(),
+					#[cfg(feature = "bls-experimental")]
+					bls381::CRYPTO_ID => keystore.bls381_generate_new(key_type, seed)?.encode(),
+					#[cfg(feature = "bls-experimental")]
+					ecdsa_bls381::CRYPTO_ID => keystore.ecdsa_bls381_generate_new(key_type, seed)?.encode(),
+					id => return Err(unsupported_crypto(id)),
+				}
+			},
+			Request::Sign { crypto_id, key_type, public, msg } => match crypto_id {
+				sr25519::CRYPTO_ID =>
+					keystore.sr25519_sign(key_type, &to_public(&public)?, &msg)?.encode(),
+				ed25519::CRYPTO_ID =>
+					keystore.ed25519_sign(key_type, &to_public(&public)?, &msg)?.encode(),
+				ecdsa::CRYPTO_ID =>
+					keystore.ecdsa_sign(key_type, &to_public(&public)?, &msg)?.encode(),
+				#[cfg(feature = "bandersnatch-experimental")]
+				bandersnatch::CRYPTO_ID =>
+					keystore.bandersnatch_sign(key_type, &to_public(&public)?, &msg)?.encode(),
+				#[cfg(feature = "bls-experimental")]
+				bls381::CRYPTO_ID => keystore.bls381_sign(key_type, &to_public(&public)?, &msg)?.encode(),
+				#[cfg(feature = "bls-experimental")]
+				ecdsa_bls381::CRYPTO_ID =>
+					keystore.ecdsa_bls381_sign(key_type, &to_public(&public)?, &msg)?.encode(),
+				id => return Err(unsupported_crypto(id)),
+			},
+			Request::EcdsaSignPrehashed { key_type, public, msg } =>
+				keystore.ecdsa_sign_prehashed(key_type, &to_public(&public)?, &msg)?.encode(),
+			Request::Sr25519VrfSign { key_type, public, transcript, extra } => {
+				let mut data = sr25519::vrf::VrfSignData::new(to_transcript(transcript)?);
+				if let Some(extra) = extra {
+					data = data.with_extra(to_transcript(extra)?);
+				}
+				keystore.sr25519_vrf_sign(key_type, &to_public(&public)?, &data)?.encode()
+			},
+			Request::Sr25519VrfPreOutput { key_type, public, input } => keystore
+				.sr25519_vrf_pre_output(key_type, &to_public(&public)?, &to_transcript(input)?)?
+				.encode(),
+			#[cfg(feature = "bls-experimental")]
+			Request::EcdsaBls381SignWithKeccak256 { key_type, public, msg } => keystore
+				.ecdsa_bls381_sign_with_keccak256(key_type, &to_public(&public)?, &msg)?
+				.encode(),
+			#[cfg(feature = "bandersnatch-experimental")]
+			Request::BandersnatchVrfSign { key_type, public, data } => {
+				let data = bandersnatch::vrf::VrfSignData::decode_all(&mut &data[..])
+					.map_err(|e| RemoteError::ValidationError(e.to_string()))?;
+				keystore.bandersnatch_vrf_sign(key_type, &to_public(&public)?, &data)?.encode()
+			},
+			#[cfg(feature = "bandersnatch-experimental")]
+			Request::BandersnatchVrfPreOutput { key_type, public, input } => {
+				let input = bandersnatch::vrf::VrfInput::decode_all(&mut &input[..])
+					.map_err(|e| RemoteError::ValidationError(e.to_string()))?;
+				keystore
+					.bandersnatch_vrf_pre_output(key_type, &to_public(&public)?, &input)?
+					.encode()
+			},
+			#[cfg(feature = "bandersnatch-experimental")]
+			Request::BandersnatchRingVrfSignPartial { key_type, public, data } => {
+				let data = bandersnatch::vrf::VrfSignData::decode_all(&mut &data[..])
+					.map_err(|e| RemoteError::ValidationError(e.to_string()))?;
+				keystore
+					.bandersnatch_ring_vrf_sign_partial(key_type, &to_public(&public)?, &data)?
+					.encode()
+			},
+			#[cfg(not(feature = "bls-experimental"))]
+			Request::EcdsaBls381SignWithKeccak256 { .. } =>
+				return Err(RemoteError::Other("BLS support is not enabled".into())),
+			#[cfg(not(feature = "bandersnatch-experimental"))]
+			Request::BandersnatchVrfSign { .. } |
+			Request::BandersnatchVrfPreOutput { .. } |
+			Request::BandersnatchRingVrfSignPartial { .. } =>
+				return Err(RemoteError::Other("Bandersnatch support is not enabled".into())),
+		};
+		Ok(encoded)
+	}
+}
+
+fn to_public<T: ByteArray>(public: &[u8]) -> std::result::Result<T, RemoteError> {
+	T::from_slice(public).map_err(|_| RemoteError::ValidationError("Invalid public key".into()))
+}
+
+fn unsupported_crypto(id: CryptoTypeId) -> RemoteError {
+	RemoteError::Other(format!("Crypto {id:?} is not supported"))
+}
+
+/// Rebuild a transcript received from the node.
+///
+/// Merlin only accepts `'static` labels. Each distinct label is leaked once and reused
+/// afterwards, the number and length of the labels are bounded to bound the leaked memory.
+fn to_transcript(data: VrfTranscriptData) -> std::result::Result<VrfTranscript, RemoteError> {
+	static LABELS: Mutex<BTreeSet<&'static [u8]>> = parking_lot::const_mutex(BTreeSet::new());
+
+	let mut labels = LABELS.lock();
+	let mut intern = |label: Vec<u8>| -> std::result::Result<&'static [u8], RemoteError> {
+		if let Some(label) = labels.get(&label[..]) {
+			return Ok(*label)
+		}
+		if label.len() > MAX_TRANSCRIPT_LABEL_LEN || labels.len() >= MAX_TRANSCRIPT_LABELS {
+			return Err(RemoteError::ValidationError("Transcript label not accepted".into()))
+		}
+		let label: &'static [u8] = Box::leak(label.into_boxed_slice());
+		labels.insert(label);
+		Ok(label)
+	};
+	let label = intern(data.label)?;
+	let items = data
+		.items
+		.into_iter()
+		.map(|(label, message)| Ok((intern(label)?, message)))
+		.collect::<std::result::Result<Vec<_>, RemoteError>>()?;
+	let items: Vec<_> = items.iter().map(|(label, message)| (*label, &message[..])).collect();
+	Ok(VrfTranscript::new(label, &items))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::LocalKeystore;
+	use sp_core::{
+		testing::{ECDSA, ED25519, SR25519},
+		Pair,
+	};
+	use tempfile::TempDir;
+
+	fn spawn_signer(dir: &TempDir) -> (PathBuf, Arc<LocalKeystore>) {
+		spawn_signer_with_policy(dir, Policy::allow_all())
+	}
+
+	fn spawn_signer_with_policy(dir: &TempDir, policy: Policy) -> (PathBuf, Arc<LocalKeystore>) {
+		let path = dir.path().join("signer.sock");
+		let keystore = Arc::new(LocalKeystore::open(dir.path().join("keystore"), None).unwrap());
+		let signer = RemoteSigner::new(keystore.clone()).with_policy(policy);
+		let listener = UnixListener::bind(&path).unwrap();
+		std::thread::spawn(move || signer.run(listener));
+		(path, keystore)
+	}
+
+	#[test]
+	fn signing_is_forwarded() {
+		let dir = TempDir::new().unwrap();
+		let (path, local) = spawn_signer(&dir);
+		let remote = RemoteKeystore::connect(path).unwrap();
+		let msg = b"hello";
+
+		let public = remote.sr25519_generate_new(SR25519, None).unwrap();
+		assert_eq!(local.sr25519_public_keys(SR25519), vec![public]);
+		assert_eq!(remote.sr25519_public_keys(SR25519), vec![public]);
+		assert!(remote.has_keys(&[(public.to_raw_vec(), SR25519)]));
+		assert_eq!(remote.keys(SR25519).unwrap(), vec![public.to_raw_vec()]);
+
+		let signature = remote.sr25519_sign(SR25519, &public, msg).unwrap().unwrap();
+		assert!(sr25519::Pair::verify(&signature, msg, &public));
+
+		let public = remote.ed25519_generate_new(ED25519, Some("//Alice")).unwrap();
+		let signature = remote.ed25519_sign(ED25519, &public, msg).unwrap().unwrap();
+		assert!(ed25519::Pair::verify(&signature, msg, &public));
+
+		let public = remote.ecdsa_generate_new(ECDSA, None).unwrap();
+		let prehashed = [7; 32];
+		let signature = remote.ecdsa_sign_prehashed(ECDSA, &public, &prehashed).unwrap().unwrap();
+		assert!(ecdsa::Pair::verify_prehashed(&signature, &prehashed, &public));
+	}
+
+	#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+	async fn signing_from_async_tasks_does_not_stall_the_runtime() {
+		let dir = TempDir::new().unwrap();
+		let (path, _) = spawn_signer(&dir);
+		let remote: KeystorePtr = RemoteKeystore::connect(path).unwrap().into();
+		let public = remote.sr25519_generate_new(SR25519, None).unwrap();
+
+		let tasks = (0..8u8).map(|i| {
+			let remote = remote.clone();
+			tokio::spawn(async move {
+				let signature = remote.sr25519_sign(SR25519, &public, &[i]).unwrap().unwrap();
+				assert!(sr25519::Pair::verify(&signature, &[i], &public));
+			})
+		});
+		for task in tasks.collect::<Vec<_>>() {
+			task.await.unwrap();
+		}
+	}
+
+	#[test]
+	fn unknown_key_returns_none() {
+		let dir = TempDir::new().unwrap();
+		let (path, _) = spawn_signer(&dir);
+		let remote = RemoteKeystore::connect(path).unwrap();
+
+		let public = sr25519::Pair::from_string("//Bob", None).unwrap().public();
+		assert_eq!(remote.sr25519_sign(SR25519, &public, b"msg").unwrap(), None);
+		assert!(!remote.has_keys(&[(public.to_raw_vec(), SR25519)]));
+	}
+
+	#[test]
+	fn insert_is_forwarded() {
+		let dir = TempDir::new().unwrap();
+		let (path, local) = spawn_signer(&dir);
+		let remote = RemoteKeystore::connect(path).unwrap();
+
+		let pair = ed25519::Pair::from_string("//Charlie", None).unwrap();
+		remote.insert(ED25519, "//Charlie", pair.public().as_ref()).unwrap();
+		assert_eq!(local.ed25519_public_keys(ED25519), vec![pair.public()]);
+	}
+
+	#[test]
+	fn sr25519_vrf_signing_is_forwarded() {
+		use sp_core::crypto::VrfPublic;
+
+		let dir = TempDir::new().unwrap();
+		let (path, local) = spawn_signer(&dir);
+		let remote = RemoteKeystore::connect(path).unwrap();
+
+		let public = remote.sr25519_generate_new(SR25519, None).unwrap();
+		let input = sr25519::vrf::VrfTranscript::new(b"label", &[(b"domain", b"data")]);
+		let extra = sr25519::vrf::VrfTranscript::new(b"extra", &[(b"domain", b"aux")]);
+		let data = sr25519::vrf::VrfSignData::new(input.clone()).with_extra(extra);
+
+		let signature = remote.sr25519_vrf_sign(SR25519, &public, &data).unwrap().unwrap();
+		assert!(public.vrf_verify(&data, &signature));
+
+		let remote_out = remote.sr25519_vrf_pre_output(SR25519, &public, &input).unwrap();
+		let local_out = local.sr25519_vrf_pre_output(SR25519, &public, &input).unwrap();
+		assert_eq!(remote_out, local_out);
+		assert_eq!(remote_out.unwrap(), signature.pre_output);
+	}
+
+	#[test]
+	fn requests_are_restricted_by_policy() {
+		let dir = TempDir::new().unwrap();
+		let policy = Policy::deny_all()
+			.allow(SR25519, [Operation::Keys, Operation::Sign])
+			.allow(ED25519, [Operation::Generate]);
+		let (path, local) = spawn_signer_with_policy(&dir, policy);
+		let remote = RemoteKeystore::connect(path).unwrap();
+
+		let public = local.sr25519_generate_new(SR25519, None).unwrap();
+		assert!(remote.sr25519_generate_new(SR25519, None).is_err());
+		assert_eq!(remote.sr25519_public_keys(SR25519), vec![public]);
+		assert!(remote.sr25519_sign(SR25519, &public, b"msg").unwrap().is_some());
+
+		let data = sr25519::vrf::VrfTranscript::new(b"label", &[]).into_sign_data();
+		assert!(remote.sr25519_vrf_sign(SR25519, &public, &data).is_err());
+		assert!(remote.sr25519_sign(ECDSA, &public, b"msg").is_err());
+
+		let public = remote.ed25519_generate_new(ED25519, None).unwrap();
+		assert!(remote.ed25519_sign(ED25519, &public, b"msg").is_err());
+		assert!(!remote.has_keys(&[(public.to_raw_vec(), ED25519)]));
+	}
+
+	#[test]
+	fn unreachable_signer_is_unavailable() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("missing.sock");
+		assert!(RemoteKeystore::connect(&path).is_err());
+
+		let remote = RemoteKeystore::new(&path);
+		let public = sr25519::Pair::from_string("//Alice", None).unwrap().public();
+		assert!(matches!(
+			remote.sr25519_sign(SR25519, &public, b"msg"),
+			Err(TraitError::Unavailable)
+		));
+		assert!(remote.sr25519_public_keys(SR25519).is_empty());
+	}
+
+	#[test]
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_vrf_signing_is_forwarded() {
+		use sp_core::testing::BANDERSNATCH;
+
+		let dir = TempDir::new().unwrap();
+		let (path, local) = spawn_signer(&dir);
+		let remote = RemoteKeystore::connect(path).unwrap();
+
+		let public = remote.bandersnatch_generate_new(BANDERSNATCH, None).unwrap();
+		let data = bandersnatch::vrf::VrfSignData::new(b"input", b"aux");
+
+		let remote_sig = remote.bandersnatch_vrf_sign(BANDERSNATCH, &public, &data).unwrap();
+		let local_sig = local.bandersnatch_vrf_sign(BANDERSNATCH, &public, &data).unwrap();
+		assert_eq!(remote_sig.unwrap().pre_output, local_sig.unwrap().pre_output);
+
+		let remote_out = remote
+			.bandersnatch_vrf_pre_output(BANDERSNATCH, &public, &data.vrf_input)
+			.unwrap();
+		let local_out = local
+			.bandersnatch_vrf_pre_output(BANDERSNATCH, &public, &data.vrf_input)
+			.unwrap();
+		assert_eq!(remote_out, local_out);
+	}
+
+	#[test]
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_ring_vrf_signing_is_forwarded() {
+		use bandersnatch::ring_vrf::RingContext;
+		use sp_core::testing::BANDERSNATCH;
+
+		let dir = TempDir::new().unwrap();
+		let (path, _) = spawn_signer(&dir);
+		let remote = RemoteKeystore::connect(path).unwrap();
+
+		let public = remote.bandersnatch_generate_new(BANDERSNATCH, None).unwrap();
+		let mut ring: Vec<_> =
+			(0..4u8).map(|i| bandersnatch::Pair::from_seed(&[i; 32]).public()).collect();
+		ring[2] = public;
+		let ring_ctx = RingContext::<16>::new_testing();
+		let prover = ring_ctx.prover(&ring, 2);
+		let data = bandersnatch::vrf::VrfSignData::new(b"input", b"aux");
+
+		let signature = remote
+			.bandersnatch_ring_vrf_sign(BANDERSNATCH, &public, &data, &prover)
+			.unwrap()
+			.unwrap();
+		assert!(signature.ring_vrf_verify(&data, &ring_ctx.verifier(&ring)));
+	}
+}
@@ -47,7 +47,7 @@ use sp_runtime::{
 use std::{collections::HashMap, sync::Arc};
 use substrate_test_runtime::Transfer;
 use substrate_test_runtime_client::{
-	prelude::*, runtime, Backend, BlockBuilderExt, Client, ClientBlockImportExt,
+	prelude::*, runtime, sc_client_db, Backend, BlockBuilderExt, Client, ClientBlockImportExt,
 };
 
 const CHAIN_GENESIS: [u8; 32] = [0; 32];
@@ -481,6 +481,71 @@ async fn archive_storage_hashes_values_child_trie() {
 	);
 }
 
+#[tokio::test]
+async fn archive_storage_at_checkpoints() {
+	let interval = std::num::NonZeroU32::new(2).unwrap();
+	let backend = Arc::new(Backend::new_test_with_state_pruning(
+		sc_client_db::PruningMode::checkpoints(1, interval),
+		0,
+	));
+	let client = Arc::new(TestClientBuilder::with_backend(backend.clone()).build());
+	let api = Archive::new(
+		client.clone(),
+		backend,
+		CHAIN_GENESIS,
+		Arc::new(TokioTestExecutor::default()),
+	)
+	.into_rpc();
+
+	// Import and finalize blocks #1 to #4, setting the key to the block number.
+	let mut hashes = vec![client.chain_info().genesis_hash];
+	for number in 1..=4u64 {
+		let mut builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(hashes[number as usize - 1])
+			.with_parent_block_number(number - 1)
+			.build()
+			.unwrap();
+		builder.push_storage_change(KEY.to_vec(), Some(number.encode())).unwrap();
+		let block = builder.build().unwrap().block;
+		client.import_as_final(BlockOrigin::Own, block.clone()).await.unwrap();
+		hashes.push(block.hash());
+	}
+
+	let key = hex_string(&KEY);
+	let items = vec![StorageQuery { key: key.clone(), query_type: StorageQueryType::Value }];
+
+	// The state of the checkpoint #2 is kept.
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_v1_storage",
+			rpc_params![&format!("{:?}", hashes[2]), items.clone()],
+		)
+		.await
+		.unwrap();
+	assert_eq!(
+		get_next_event::<ArchiveStorageEvent>(&mut sub).await,
+		ArchiveStorageEvent::Storage(StorageResult {
+			key: key.clone(),
+			result: StorageResultType::Value(hex_string(&2u64.encode())),
+			child_trie_key: None,
+		}),
+	);
+	assert_matches!(
+		get_next_event::<ArchiveStorageEvent>(&mut sub).await,
+		ArchiveStorageEvent::StorageDone
+	);
+
+	// The state of #3 is pruned.
+	let mut sub = api
+		.subscribe_unbounded("archive_v1_storage", rpc_params![&format!("{:?}", hashes[3]), items])
+		.await
+		.unwrap();
+	assert_matches!(
+		get_next_event::<ArchiveStorageEvent>(&mut sub).await,
+		ArchiveStorageEvent::StorageError(_)
+	);
+}
+
 #[tokio::test]
 async fn archive_storage_closest_merkle_value() {
 	let (client, api) = setup_api();
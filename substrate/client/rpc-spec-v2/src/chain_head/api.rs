@@ -94,6 +94,25 @@ pub trait ChainHeadApi<Hash> {
 		child_trie: Option<String>,
 	) -> ResponsePayload<'static, MethodResponse>;
 
+	/// Returns storage entries at a specific block's state, along with a storage proof of the
+	/// returned entries.
+	///
+	/// The entries are produced like for `chainHead_v1_storage`, in pages whose proof is capped
+	/// in size. Each page is followed by an `operationStorageProof` event, holding the trie nodes
+	/// from which the entries of the page can be verified against the state root of the block.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "chainHead_v1_storageWithProof", with_extensions)]
+	async fn chain_head_unstable_storage_with_proof(
+		&self,
+		follow_subscription: String,
+		hash: Hash,
+		items: Vec<StorageQuery<String>>,
+		child_trie: Option<String>,
+	) -> ResponsePayload<'static, MethodResponse>;
+
 	/// Call into the Runtime API at a specified block's state.
 	///
 	/// # Unstable
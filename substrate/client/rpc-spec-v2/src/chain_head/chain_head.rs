@@ -27,11 +27,17 @@ use crate::{
 		api::ChainHeadApiServer,
 		chain_head_follow::ChainHeadFollower,
 		error::Error as ChainHeadRpcError,
-		event::{FollowEvent, MethodResponse, OperationError, OperationId, OperationStorageItems},
+		event::{
+			FollowEvent, MethodResponse, OperationError, OperationId, OperationStorageItems,
+			OperationStorageProof,
+		},
 		subscription::{StopHandle, SubscriptionManagement, SubscriptionManagementError},
 		FollowEventSendError, FollowEventSender,
 	},
-	common::{events::StorageQuery, storage::QueryResult},
+	common::{
+		events::StorageQuery,
+		storage::{ProvedQueryResult, QueryResult},
+	},
 	hex_string, SubscriptionTaskExecutor,
 };
 use codec::Encode;
@@ -159,6 +165,109 @@ impl<BE: Backend<Block>, Block: BlockT, Client> ChainHead<BE, Block, Client> {
 	}
 }
 
+impl<BE, Block, Client> ChainHead<BE, Block, Client>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+	Client: StorageProvider<Block, BE> + Send + Sync + 'static,
+{
+	/// Start a `chainHead_storage` operation, optionally proving the produced items.
+	fn storage_operation(
+		&self,
+		ext: &Extensions,
+		follow_subscription: String,
+		hash: Block::Hash,
+		items: Vec<StorageQuery<String>>,
+		child_trie: Option<String>,
+		with_proof: bool,
+	) -> ResponsePayload<'static, MethodResponse> {
+		let conn_id = ext
+			.get::<ConnectionId>()
+			.copied()
+			.expect("ConnectionId is always set by jsonrpsee; qed");
+
+		if !self.subscriptions.contains_subscription(conn_id, &follow_subscription) {
+			// The spec says to return `LimitReached` if the follow subscription is invalid or
+			// stale.
+			return ResponsePayload::success(MethodResponse::LimitReached);
+		}
+
+		// Gain control over parameter parsing and returned error.
+		let items = match items
+			.into_iter()
+			.map(|query| {
+				let key = StorageKey(parse_hex_param(query.key)?);
+				Ok(StorageQuery { key, query_type: query.query_type })
+			})
+			.collect::<Result<Vec<_>, ChainHeadRpcError>>()
+		{
+			Ok(items) => items,
+			Err(err) => {
+				return ResponsePayload::error(err);
+			},
+		};
+
+		let child_trie = match child_trie.map(|child_trie| parse_hex_param(child_trie)).transpose()
+		{
+			Ok(c) => c.map(ChildInfo::new_default_from_vec),
+			Err(e) => return ResponsePayload::error(e),
+		};
+
+		let mut block_guard =
+			match self.subscriptions.lock_block(&follow_subscription, hash, items.len()) {
+				Ok(block) => block,
+				Err(SubscriptionManagementError::SubscriptionAbsent) |
+				Err(SubscriptionManagementError::ExceededLimits) => {
+					return ResponsePayload::success(MethodResponse::LimitReached);
+				},
+				Err(SubscriptionManagementError::BlockHashAbsent) => {
+					// Block is not part of the subscription.
+					return ResponsePayload::error(ChainHeadRpcError::InvalidBlock)
+				},
+				Err(_) => return ResponsePayload::error(ChainHeadRpcError::InvalidBlock),
+			};
+
+		let mut storage_client = ChainHeadStorage::<Client, Block, BE>::new(self.client.clone());
+		let backend = self.backend.clone();
+
+		// Storage items are never discarded.
+		let (rp, rp_fut) = method_started_response(block_guard.operation().operation_id(), Some(0));
+
+		let fut = async move {
+			// Wait for the server to send out the response and if it produces an error no event
+			// should be generated.
+			if rp_fut.await.is_err() {
+				return;
+			}
+
+			let operation_id = block_guard.operation().operation_id();
+			let stop_handle = block_guard.operation().stop_handle().clone();
+			let response_sender = block_guard.response_sender();
+
+			// May fail if the channel is closed or the connection is closed.
+			// which is okay to ignore.
+			if with_proof {
+				let (tx, rx) = tokio::sync::mpsc::channel(STORAGE_QUERY_BUF);
+				let _ = futures::future::join(
+					storage_client.generate_events_with_proof(backend, hash, items, child_trie, tx),
+					process_storage_items(rx, response_sender, operation_id, &stop_handle),
+				)
+				.await;
+			} else {
+				let (tx, rx) = tokio::sync::mpsc::channel(STORAGE_QUERY_BUF);
+				let _ = futures::future::join(
+					storage_client.generate_events(hash, items, child_trie, tx),
+					process_storage_items(rx, response_sender, operation_id, &stop_handle),
+				)
+				.await;
+			}
+		};
+		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+
+		rp
+	}
+}
+
 /// Helper to convert the `subscription ID` to a string.
 pub fn read_subscription_id_as_string(sink: &Subscription) -> String {
 	match sink.subscription_id() {
@@ -384,80 +493,18 @@ where
 		items: Vec<StorageQuery<String>>,
 		child_trie: Option<String>,
 	) -> ResponsePayload<'static, MethodResponse> {
-		let conn_id = ext
-			.get::<ConnectionId>()
-			.copied()
-			.expect("ConnectionId is always set by jsonrpsee; qed");
-
-		if !self.subscriptions.contains_subscription(conn_id, &follow_subscription) {
-			// The spec says to return `LimitReached` if the follow subscription is invalid or
-			// stale.
-			return ResponsePayload::success(MethodResponse::LimitReached);
-		}
-
-		// Gain control over parameter parsing and returned error.
-		let items = match items
-			.into_iter()
-			.map(|query| {
-				let key = StorageKey(parse_hex_param(query.key)?);
-				Ok(StorageQuery { key, query_type: query.query_type })
-			})
-			.collect::<Result<Vec<_>, ChainHeadRpcError>>()
-		{
-			Ok(items) => items,
-			Err(err) => {
-				return ResponsePayload::error(err);
-			},
-		};
-
-		let child_trie = match child_trie.map(|child_trie| parse_hex_param(child_trie)).transpose()
-		{
-			Ok(c) => c.map(ChildInfo::new_default_from_vec),
-			Err(e) => return ResponsePayload::error(e),
-		};
-
-		let mut block_guard =
-			match self.subscriptions.lock_block(&follow_subscription, hash, items.len()) {
-				Ok(block) => block,
-				Err(SubscriptionManagementError::SubscriptionAbsent) |
-				Err(SubscriptionManagementError::ExceededLimits) => {
-					return ResponsePayload::success(MethodResponse::LimitReached);
-				},
-				Err(SubscriptionManagementError::BlockHashAbsent) => {
-					// Block is not part of the subscription.
-					return ResponsePayload::error(ChainHeadRpcError::InvalidBlock)
-				},
-				Err(_) => return ResponsePayload::error(ChainHeadRpcError::InvalidBlock),
-			};
-
-		let mut storage_client = ChainHeadStorage::<Client, Block, BE>::new(self.client.clone());
-
-		// Storage items are never discarded.
-		let (rp, rp_fut) = method_started_response(block_guard.operation().operation_id(), Some(0));
-
-		let fut = async move {
-			// Wait for the server to send out the response and if it produces an error no event
-			// should be generated.
-			if rp_fut.await.is_err() {
-				return;
-			}
-
-			let (tx, rx) = tokio::sync::mpsc::channel(STORAGE_QUERY_BUF);
-			let operation_id = block_guard.operation().operation_id();
-			let stop_handle = block_guard.operation().stop_handle().clone();
-			let response_sender = block_guard.response_sender();
-
-			// May fail if the channel is closed or the connection is closed.
-			// which is okay to ignore.
-			let _ = futures::future::join(
-				storage_client.generate_events(hash, items, child_trie, tx),
-				process_storage_items(rx, response_sender, operation_id, &stop_handle),
-			)
-			.await;
-		};
-		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+		self.storage_operation(ext, follow_subscription, hash, items, child_trie, false)
+	}
 
-		rp
+	async fn chain_head_unstable_storage_with_proof(
+		&self,
+		ext: &Extensions,
+		follow_subscription: String,
+		hash: Block::Hash,
+		items: Vec<StorageQuery<String>>,
+		child_trie: Option<String>,
+	) -> ResponsePayload<'static, MethodResponse> {
+		self.storage_operation(ext, follow_subscription, hash, items, child_trie, true)
 	}
 
 	async fn chain_head_unstable_call(
@@ -660,8 +707,8 @@ where
 	rx
 }
 
-async fn process_storage_items<Hash>(
-	mut storage_query_stream: mpsc::Receiver<QueryResult>,
+async fn process_storage_items<Hash, T: Into<ProvedQueryResult>>(
+	mut storage_query_stream: mpsc::Receiver<T>,
 	mut sender: FollowEventSender<Hash>,
 	operation_id: String,
 	stop_handle: &StopHandle,
@@ -677,6 +724,19 @@ async fn process_storage_items<Hash>(
 					break;
 				};
 
+				let storage = match storage.into() {
+					ProvedQueryResult::Item(storage) => storage,
+					ProvedQueryResult::Proof(proof) => {
+						sender
+							.send(FollowEvent::OperationStorageProof(OperationStorageProof {
+								operation_id: operation_id.clone(),
+								proof: proof.into_iter_nodes().map(|node| hex_string(&node)).collect(),
+							}))
+							.await?;
+						continue;
+					},
+				};
+
 				let item = match storage {
 					QueryResult::Err(error) => {
 						return sender
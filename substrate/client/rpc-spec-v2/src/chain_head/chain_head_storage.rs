@@ -22,11 +22,14 @@ use std::{marker::PhantomData, sync::Arc};
 
 use sc_client_api::{Backend, ChildInfo, StorageKey, StorageProvider};
 use sp_runtime::traits::Block as BlockT;
+use sp_state_machine::backend::AsTrieBackend;
 use tokio::sync::mpsc;
 
 use crate::common::{
 	events::{StorageQuery, StorageQueryType},
-	storage::{IterQueryType, QueryIter, QueryResult, Storage},
+	storage::{
+		query_with_proof, IterQueryType, ProvedQueryResult, QueryIter, QueryResult, Storage,
+	},
 };
 
 /// Generates the events of the `chainHead_storage` method.
@@ -120,4 +123,31 @@ where
 
 		Ok(())
 	}
+
+	/// Generate the block events for the `chainHead_storageWithProof` method.
+	///
+	/// The items are produced in pages, each followed by the storage proof of its items.
+	pub async fn generate_events_with_proof(
+		&mut self,
+		backend: Arc<BE>,
+		hash: Block::Hash,
+		items: Vec<StorageQuery<StorageKey>>,
+		child_key: Option<ChildInfo>,
+		tx: mpsc::Sender<ProvedQueryResult>,
+	) -> Result<(), tokio::task::JoinError> {
+		tokio::task::spawn_blocking(move || {
+			let state = match backend.state_at(hash) {
+				Ok(state) => state,
+				Err(error) => {
+					let _ = tx.blocking_send(Err(error.to_string()).into());
+					return;
+				},
+			};
+
+			query_with_proof(state.as_trie_backend(), items, child_key.as_ref(), &tx);
+		})
+		.await?;
+
+		Ok(())
+	}
 }
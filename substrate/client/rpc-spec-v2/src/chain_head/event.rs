@@ -245,6 +245,17 @@ pub struct OperationStorageItems {
 	pub items: Vec<StorageResult>,
 }
 
+/// The storage proof of the items produced by the `chainHead_storageWithProof` method since
+/// the previous proof.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationStorageProof {
+	/// The operation id of the event.
+	pub operation_id: String,
+	/// Hexadecimal-encoded trie nodes proving the produced items.
+	pub proof: Vec<String>,
+}
+
 /// Indicate a problem during the operation.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -270,6 +281,8 @@ pub struct OperationError {
 /// - OperationStorageItems: Items produced by the `chianHead_storage`
 /// - OperationWaitingForContinue: Generated after OperationStorageItems and requires the user to
 ///   call `chainHead_continue`
+/// - OperationStorageProof: The proof of the items produced by the `chainHead_storageWithProof`
+///   since the previous proof, generated after each page of items
 /// - OperationStorageDone: The `chianHead_storage` method has produced all the results
 /// - OperationInaccessible: The server was unable to provide the result, retries might succeed in
 ///   the future
@@ -300,6 +313,8 @@ pub enum FollowEvent<Hash> {
 	/// Ask the user to call `chainHead_continue` to produce more events
 	/// regarding the operation id.
 	OperationWaitingForContinue(OperationId),
+	/// The storage proof of the items produced by the `chainHead_storageWithProof` method.
+	OperationStorageProof(OperationStorageProof),
 	/// The responses of the `chainHead_storage` method have been produced.
 	OperationStorageDone(OperationId),
 	/// The RPC server was unable to provide the response of the following operation id.
@@ -561,6 +576,22 @@ mod tests {
 		assert_eq!(event_dec, event);
 	}
 
+	#[test]
+	fn follow_op_storage_proof_event() {
+		let event: FollowEvent<String> =
+			FollowEvent::OperationStorageProof(OperationStorageProof {
+				operation_id: "123".into(),
+				proof: vec!["0x1".into(), "0x2".into()],
+			});
+
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"operationStorageProof","operationId":"123","proof":["0x1","0x2"]}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: FollowEvent<String> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+	}
+
 	#[test]
 	fn follow_op_storage_done_event() {
 		let event: FollowEvent<String> =
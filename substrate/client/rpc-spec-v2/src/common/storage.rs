@@ -20,8 +20,14 @@
 
 use std::{marker::PhantomData, sync::Arc};
 
-use sc_client_api::{Backend, ChildInfo, StorageKey, StorageProvider};
+use codec::Codec;
+use sc_client_api::{Backend, ChildInfo, MerkleValue, StorageKey, StorageProof, StorageProvider};
+use sp_core::Hasher;
 use sp_runtime::traits::Block as BlockT;
+use sp_state_machine::{
+	Backend as StateBackend, IterArgs, TrieBackend, TrieBackendBuilder, TrieBackendStorage,
+};
+use sp_trie::recorder::Recorder;
 use tokio::sync::mpsc;
 
 use super::events::{StorageQuery, StorageQueryType, StorageResult, StorageResultType};
@@ -70,135 +76,350 @@ pub enum IterQueryType {
 /// The result of making a query call.
 pub type QueryResult = Result<Option<StorageResult>, String>;
 
-impl<Client, Block, BE> Storage<Client, Block, BE>
+/// The result of making a query call with proof.
+#[derive(Debug)]
+pub enum ProvedQueryResult {
+	/// The result of a query.
+	Item(QueryResult),
+	/// The storage proof of the results sent since the previous proof.
+	Proof(StorageProof),
+}
+
+impl From<QueryResult> for ProvedQueryResult {
+	fn from(result: QueryResult) -> Self {
+		Self::Item(result)
+	}
+}
+
+/// Maximal estimated size of the storage proof of a page of results.
+///
+/// The results of a query with proof are cut in pages, each followed by its own proof.
+const MAX_PROOF_PAGE_SIZE: usize = 512 * 1024;
+
+/// The storage of a block, read by the storage queries.
+trait StorageSource {
+	/// The value of `key`.
+	fn value(
+		&self,
+		key: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> Result<Option<Vec<u8>>, String>;
+
+	/// The hash of the value of `key`.
+	fn hash(
+		&self,
+		key: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> Result<Option<Vec<u8>>, String>;
+
+	/// The merkle value of the closest descendant of `key`.
+	fn merkle_value(
+		&self,
+		key: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> Result<Option<Vec<u8>>, String>;
+
+	/// The keys starting with `prefix`, after `start_key`.
+	fn keys(
+		&self,
+		prefix: &StorageKey,
+		start_key: Option<&StorageKey>,
+		child_key: Option<&ChildInfo>,
+	) -> Result<Box<dyn Iterator<Item = Result<StorageKey, String>> + '_>, String>;
+}
+
+/// The storage of a block read through the client.
+struct ClientSource<'a, Client, Block: BlockT, BE> {
+	client: &'a Client,
+	hash: Block::Hash,
+	_phandom: PhantomData<BE>,
+}
+
+impl<Client, Block, BE> StorageSource for ClientSource<'_, Client, Block, BE>
 where
 	Block: BlockT + 'static,
 	BE: Backend<Block> + 'static,
 	Client: StorageProvider<Block, BE> + 'static,
 {
-	/// Fetch the value from storage.
-	pub fn query_value(
+	fn value(
 		&self,
-		hash: Block::Hash,
 		key: &StorageKey,
 		child_key: Option<&ChildInfo>,
-	) -> QueryResult {
+	) -> Result<Option<Vec<u8>>, String> {
 		let result = if let Some(child_key) = child_key {
-			self.client.child_storage(hash, child_key, key)
+			self.client.child_storage(self.hash, child_key, key)
 		} else {
-			self.client.storage(hash, key)
+			self.client.storage(self.hash, key)
 		};
+		result
+			.map(|opt| opt.map(|storage_data| storage_data.0))
+			.map_err(|e| e.to_string())
+	}
 
+	fn hash(
+		&self,
+		key: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> Result<Option<Vec<u8>>, String> {
+		let result = if let Some(child_key) = child_key {
+			self.client.child_storage_hash(self.hash, child_key, key)
+		} else {
+			self.client.storage_hash(self.hash, key)
+		};
 		result
-			.map(|opt| {
-				QueryResult::Ok(opt.map(|storage_data| StorageResult {
-					key: hex_string(&key.0),
-					result: StorageResultType::Value(hex_string(&storage_data.0)),
-					child_trie_key: child_key.map(|c| hex_string(&c.storage_key())),
-				}))
-			})
-			.unwrap_or_else(|error| QueryResult::Err(error.to_string()))
+			.map(|opt| opt.map(|hash| hash.as_ref().to_vec()))
+			.map_err(|e| e.to_string())
 	}
 
-	/// Fetch the hash of a value from storage.
-	pub fn query_hash(
+	fn merkle_value(
 		&self,
-		hash: Block::Hash,
 		key: &StorageKey,
 		child_key: Option<&ChildInfo>,
-	) -> QueryResult {
+	) -> Result<Option<Vec<u8>>, String> {
 		let result = if let Some(child_key) = child_key {
-			self.client.child_storage_hash(hash, child_key, key)
+			self.client.child_closest_merkle_value(self.hash, child_key, key)
 		} else {
-			self.client.storage_hash(hash, key)
+			self.client.closest_merkle_value(self.hash, key)
 		};
+		result.map(|opt| opt.map(merkle_value_bytes)).map_err(|e| e.to_string())
+	}
 
-		result
-			.map(|opt| {
-				QueryResult::Ok(opt.map(|storage_data| StorageResult {
-					key: hex_string(&key.0),
-					result: StorageResultType::Hash(hex_string(&storage_data.as_ref())),
-					child_trie_key: child_key.map(|c| hex_string(&c.storage_key())),
-				}))
-			})
-			.unwrap_or_else(|error| QueryResult::Err(error.to_string()))
+	fn keys(
+		&self,
+		prefix: &StorageKey,
+		start_key: Option<&StorageKey>,
+		child_key: Option<&ChildInfo>,
+	) -> Result<Box<dyn Iterator<Item = Result<StorageKey, String>> + '_>, String> {
+		let keys_iter = if let Some(child_key) = child_key {
+			self.client
+				.child_storage_keys(self.hash, child_key.to_owned(), Some(prefix), start_key)
+		} else {
+			self.client.storage_keys(self.hash, Some(prefix), start_key)
+		};
+		keys_iter
+			.map(|keys_iter| Box::new(keys_iter.map(Ok::<_, String>)) as Box<_>)
+			.map_err(|e| e.to_string())
 	}
+}
 
-	/// Fetch the closest merkle value.
-	pub fn query_merkle_value(
+/// The storage of a block read through its state.
+struct StateSource<'a, S, H>(&'a S, PhantomData<H>);
+
+impl<S, H> StorageSource for StateSource<'_, S, H>
+where
+	S: StateBackend<H>,
+	H: Hasher,
+{
+	fn value(
 		&self,
-		hash: Block::Hash,
 		key: &StorageKey,
 		child_key: Option<&ChildInfo>,
-	) -> QueryResult {
-		let result = if let Some(ref child_key) = child_key {
-			self.client.child_closest_merkle_value(hash, child_key, key)
+	) -> Result<Option<Vec<u8>>, String> {
+		let result = if let Some(child_key) = child_key {
+			self.0.child_storage(child_key, &key.0)
 		} else {
-			self.client.closest_merkle_value(hash, key)
+			self.0.storage(&key.0)
 		};
+		result.map_err(|e| e.to_string())
+	}
 
+	fn hash(
+		&self,
+		key: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> Result<Option<Vec<u8>>, String> {
+		let result = if let Some(child_key) = child_key {
+			self.0.child_storage_hash(child_key, &key.0)
+		} else {
+			self.0.storage_hash(&key.0)
+		};
 		result
-			.map(|opt| {
-				QueryResult::Ok(opt.map(|storage_data| {
-					let result = match &storage_data {
-						sc_client_api::MerkleValue::Node(data) => hex_string(&data.as_slice()),
-						sc_client_api::MerkleValue::Hash(hash) => hex_string(&hash.as_ref()),
-					};
-
-					StorageResult {
-						key: hex_string(&key.0),
-						result: StorageResultType::ClosestDescendantMerkleValue(result),
-						child_trie_key: child_key.map(|c| hex_string(&c.storage_key())),
-					}
-				}))
-			})
-			.unwrap_or_else(|error| QueryResult::Err(error.to_string()))
+			.map(|opt| opt.map(|hash| hash.as_ref().to_vec()))
+			.map_err(|e| e.to_string())
 	}
 
-	/// Iterate over the storage keys and send the results to the provided sender.
-	///
-	/// Because this relies on a bounded channel, it will pause the storage iteration
-	// if the channel is becomes full which in turn provides backpressure.
-	pub fn query_iter_pagination_with_producer(
+	fn merkle_value(
 		&self,
-		query: QueryIter,
-		hash: Block::Hash,
+		key: &StorageKey,
 		child_key: Option<&ChildInfo>,
-		tx: &mpsc::Sender<QueryResult>,
-	) {
-		let QueryIter { ty, query_key, pagination_start_key } = query;
-
-		let maybe_storage = if let Some(child_key) = child_key {
-			self.client.child_storage_keys(
-				hash,
-				child_key.to_owned(),
-				Some(&query_key),
-				pagination_start_key.as_ref(),
-			)
+	) -> Result<Option<Vec<u8>>, String> {
+		let result = if let Some(child_key) = child_key {
+			self.0.child_closest_merkle_value(child_key, &key.0)
 		} else {
-			self.client.storage_keys(hash, Some(&query_key), pagination_start_key.as_ref())
+			self.0.closest_merkle_value(&key.0)
 		};
+		result.map(|opt| opt.map(merkle_value_bytes)).map_err(|e| e.to_string())
+	}
 
-		let keys_iter = match maybe_storage {
+	fn keys(
+		&self,
+		prefix: &StorageKey,
+		start_key: Option<&StorageKey>,
+		child_key: Option<&ChildInfo>,
+	) -> Result<Box<dyn Iterator<Item = Result<StorageKey, String>> + '_>, String> {
+		let mut args = IterArgs::default();
+		args.prefix = Some(&prefix.0);
+		args.start_at = start_key.map(|start_key| start_key.0.as_slice());
+		args.start_at_exclusive = true;
+		args.child_info = child_key.cloned();
+
+		let keys_iter = self.0.keys(args).map_err(|e| e.to_string())?;
+		Ok(Box::new(keys_iter.map(|key| key.map(StorageKey).map_err(|e| e.to_string()))))
+	}
+}
+
+fn merkle_value_bytes<Hash: AsRef<[u8]>>(value: MerkleValue<Hash>) -> Vec<u8> {
+	match value {
+		MerkleValue::Node(data) => data,
+		MerkleValue::Hash(hash) => hash.as_ref().to_vec(),
+	}
+}
+
+fn storage_result(
+	key: &StorageKey,
+	child_key: Option<&ChildInfo>,
+	result: StorageResultType,
+) -> StorageResult {
+	StorageResult {
+		key: hex_string(&key.0),
+		result,
+		child_trie_key: child_key.map(|c| hex_string(&c.storage_key())),
+	}
+}
+
+fn query_value(
+	source: &impl StorageSource,
+	key: &StorageKey,
+	child_key: Option<&ChildInfo>,
+) -> QueryResult {
+	source.value(key, child_key).map(|opt| {
+		opt.map(|value| {
+			storage_result(key, child_key, StorageResultType::Value(hex_string(&value)))
+		})
+	})
+}
+
+fn query_hash(
+	source: &impl StorageSource,
+	key: &StorageKey,
+	child_key: Option<&ChildInfo>,
+) -> QueryResult {
+	source.hash(key, child_key).map(|opt| {
+		opt.map(|hash| storage_result(key, child_key, StorageResultType::Hash(hex_string(&hash))))
+	})
+}
+
+fn query_merkle_value(
+	source: &impl StorageSource,
+	key: &StorageKey,
+	child_key: Option<&ChildInfo>,
+) -> QueryResult {
+	source.merkle_value(key, child_key).map(|opt| {
+		opt.map(|value| {
+			storage_result(
+				key,
+				child_key,
+				StorageResultType::ClosestDescendantMerkleValue(hex_string(&value)),
+			)
+		})
+	})
+}
+
+/// Iterate over the storage keys and send the results to the provided sender, until
+/// `page_full` returns `true`.
+///
+/// Returns the last produced key if the iteration stopped before its end, or `Err(())` if the
+/// receiver was dropped.
+fn query_iter_page<T: From<QueryResult>>(
+	source: &impl StorageSource,
+	query: &QueryIter,
+	child_key: Option<&ChildInfo>,
+	tx: &mpsc::Sender<T>,
+	page_full: impl Fn() -> bool,
+) -> Result<Option<StorageKey>, ()> {
+	let keys_iter =
+		match source.keys(&query.query_key, query.pagination_start_key.as_ref(), child_key) {
 			Ok(keys_iter) => keys_iter,
 			Err(error) => {
-				_ = tx.blocking_send(Err(error.to_string()));
-				return;
+				return tx.blocking_send(T::from(Err(error))).map(|_| None).map_err(|_| ());
 			},
 		};
 
-		for key in keys_iter {
-			let result = match ty {
-				IterQueryType::Value => self.query_value(hash, &key, child_key),
-				IterQueryType::Hash => self.query_hash(hash, &key, child_key),
-			};
+	for key in keys_iter {
+		let (result, key) = match key {
+			Ok(key) => {
+				let result = match query.ty {
+					IterQueryType::Value => query_value(source, &key, child_key),
+					IterQueryType::Hash => query_hash(source, &key, child_key),
+				};
+				(result, Some(key))
+			},
+			Err(error) => (Err(error), None),
+		};
 
-			if tx.blocking_send(result).is_err() {
-				break;
-			}
+		tx.blocking_send(T::from(result)).map_err(|_| ())?;
+		if page_full() {
+			return Ok(key)
 		}
 	}
 
+	Ok(None)
+}
+
+impl<Client, Block, BE> Storage<Client, Block, BE>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+	Client: StorageProvider<Block, BE> + 'static,
+{
+	fn source(&self, hash: Block::Hash) -> ClientSource<'_, Client, Block, BE> {
+		ClientSource { client: &self.client, hash, _phandom: PhantomData }
+	}
+
+	/// Fetch the value from storage.
+	pub fn query_value(
+		&self,
+		hash: Block::Hash,
+		key: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> QueryResult {
+		query_value(&self.source(hash), key, child_key)
+	}
+
+	/// Fetch the hash of a value from storage.
+	pub fn query_hash(
+		&self,
+		hash: Block::Hash,
+		key: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> QueryResult {
+		query_hash(&self.source(hash), key, child_key)
+	}
+
+	/// Fetch the closest merkle value.
+	pub fn query_merkle_value(
+		&self,
+		hash: Block::Hash,
+		key: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> QueryResult {
+		query_merkle_value(&self.source(hash), key, child_key)
+	}
+
+	/// Iterate over the storage keys and send the results to the provided sender.
+	///
+	/// Because this relies on a bounded channel, it will pause the storage iteration
+	// if the channel is becomes full which in turn provides backpressure.
+	pub fn query_iter_pagination_with_producer(
+		&self,
+		query: QueryIter,
+		hash: Block::Hash,
+		child_key: Option<&ChildInfo>,
+		tx: &mpsc::Sender<QueryResult>,
+	) {
+		let _ = query_iter_page(&self.source(hash), &query, child_key, tx, || false);
+	}
+
 	/// Raw iterator over the keys.
 	pub fn raw_keys_iter(
 		&self,
@@ -215,6 +436,73 @@ where
 	}
 }
 
+/// Run the storage queries against the trie of a block and prove their results.
+///
+/// The results are produced like the results of [`Storage`] queries, in pages whose storage
+/// proof is cut once its estimated size reaches 512 KiB. Each page is followed by its proof,
+/// which proves the results of the page, including the completeness of the descendants queries,
+/// against the state root of the block.
+pub fn query_with_proof<S, H>(
+	trie_backend: &TrieBackend<S, H>,
+	items: Vec<StorageQuery<StorageKey>>,
+	child_key: Option<&ChildInfo>,
+	tx: &mpsc::Sender<ProvedQueryResult>,
+) where
+	S: TrieBackendStorage<H>,
+	H: Hasher,
+	H::Out: Ord + Codec,
+{
+	let recorder = Recorder::<H>::default();
+	let backend = TrieBackendBuilder::wrap(trie_backend).with_recorder(recorder.clone()).build();
+	let source = StateSource(&backend, PhantomData);
+
+	let page_full = || recorder.estimate_encoded_size() >= MAX_PROOF_PAGE_SIZE;
+	// A page ends with its proof, and the next page records its own nodes from scratch.
+	let send_proof = || {
+		let proof = recorder.to_storage_proof();
+		recorder.reset();
+		tx.blocking_send(ProvedQueryResult::Proof(proof)).is_ok()
+	};
+
+	for item in items {
+		let result = match item.query_type {
+			StorageQueryType::Value => query_value(&source, &item.key, child_key),
+			StorageQueryType::Hash => query_hash(&source, &item.key, child_key),
+			StorageQueryType::ClosestDescendantMerkleValue =>
+				query_merkle_value(&source, &item.key, child_key),
+			StorageQueryType::DescendantsValues | StorageQueryType::DescendantsHashes => {
+				let ty = if item.query_type == StorageQueryType::DescendantsValues {
+					IterQueryType::Value
+				} else {
+					IterQueryType::Hash
+				};
+				let mut query = QueryIter { query_key: item.key, ty, pagination_start_key: None };
+				// A full page stops the iteration, which resumes after the last produced key.
+				loop {
+					match query_iter_page(&source, &query, child_key, tx, page_full) {
+						Ok(Some(last_key)) if send_proof() =>
+							query.pagination_start_key = Some(last_key),
+						Ok(None) => break,
+						_ => return,
+					}
+				}
+				continue;
+			},
+		};
+
+		if tx.blocking_send(ProvedQueryResult::Item(result)).is_err() {
+			return;
+		}
+		if page_full() && !send_proof() {
+			return;
+		}
+	}
+
+	if recorder.estimate_encoded_size() > 0 {
+		send_proof();
+	}
+}
+
 /// Generates storage events for `chainHead_storage` and `archive_storage` subscriptions.
 pub struct StorageSubscriptionClient<Client, Block, BE> {
 	/// Storage client.
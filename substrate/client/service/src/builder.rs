@@ -745,10 +745,12 @@ where
 
 	// Part of the RPC v2 spec.
 	// An archive node that can respond to the `archive` RPC-v2 queries is a node with:
-	// - state pruning in archive mode: The storage of blocks is kept around
+	// - state pruning keeping historical state: The storage of (some of the) blocks is kept around,
+	//   either for all of them in archive mode or only at the checkpoints
 	// - block pruning in archive mode: The block's body is kept around
-	let is_archive_node = state_pruning.as_ref().map(|sp| sp.is_archive()).unwrap_or(false) &&
-		blocks_pruning.is_archive();
+	let is_archive_node =
+		state_pruning.as_ref().map(|sp| sp.keeps_historical_state()).unwrap_or(false) &&
+			blocks_pruning.is_archive();
 	let genesis_hash = client.hash(Zero::zero()).ok().flatten().expect("Genesis block exists; qed");
 	if is_archive_node {
 		let archive_v2 = sc_rpc_spec_v2::archive::Archive::new(
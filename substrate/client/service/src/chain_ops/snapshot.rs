@@ -0,0 +1,514 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Export and import of state snapshots.
+//!
+//! A snapshot contains a finalized block, the warp sync proof of its finality and its state. It
+//! allows to bootstrap a node offline, the same way warp sync bootstraps it from its peers.
+//!
+//! A snapshot is SCALE encoded as [`SNAPSHOT_MAGIC`], the format version, a [`SnapshotHeader`]
+//! and a sequence of [`SnapshotEntry`]: the fragments of the warp sync proof from the genesis
+//! block, followed by the range proofs of the state of the block, and [`SnapshotEntry::End`].
+
+use crate::error::Error;
+use codec::{Decode, Encode, IoReader as CodecIoReader};
+use futures::{future, prelude::*};
+use log::info;
+use sc_client_api::{BlockBackend, CompactProof, HeaderBackend, ProofProvider};
+use sc_consensus::import_queue::{
+	BlockImportError, BlockImportStatus, ImportQueue, IncomingBlock, Link,
+};
+use sc_network_sync::{
+	strategy::{
+		state_sync::{ImportResult, StateSync, StateSyncProvider},
+		warp::{AuthorityList, EncodedProof, VerificationResult, WarpSyncProvider},
+	},
+	StateResponse,
+};
+use smallvec::SmallVec;
+use sp_consensus::BlockOrigin;
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, NumberFor, Zero},
+	Justifications,
+};
+use std::{
+	io::{Read, Write},
+	pin::Pin,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+	task::Poll,
+};
+
+/// Identifies snapshot files.
+const SNAPSHOT_MAGIC: [u8; 8] = *b"substsnp";
+
+/// The version of the snapshot format.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Maximal size of a state range proof.
+///
+/// Matches the size of the state responses of state sync.
+const STATE_PROOF_SIZE: usize = 2 * 1024 * 1024;
+
+/// The block of a snapshot.
+#[derive(Encode, Decode)]
+struct SnapshotHeader<B: BlockT> {
+	/// The genesis hash of the chain.
+	genesis_hash: B::Hash,
+	/// The header of the block.
+	header: B::Header,
+	/// The body of the block.
+	body: Option<Vec<B::Extrinsic>>,
+	/// The justifications of the block.
+	justifications: Option<Justifications>,
+}
+
+/// An entry of a snapshot, following its [`SnapshotHeader`].
+#[derive(Encode, Decode)]
+enum SnapshotEntry {
+	/// A fragment of the warp sync proof of the block.
+	WarpProof(Vec<u8>),
+	/// A range proof of the state of the block.
+	State(CompactProof),
+	/// The end of the snapshot.
+	End,
+}
+
+/// Export a snapshot of the latest block whose finality is proven by `warp_sync_provider`.
+///
+/// The warp sync proof starts at the genesis block, and is verified against
+/// `genesis_authorities`. Every range proof of the state is verified against the state root of
+/// the block before it is written. Returns the hash of the exported block.
+pub fn export_snapshot<B, C>(
+	client: Arc<C>,
+	warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+	genesis_authorities: AuthorityList,
+	mut output: impl Write,
+) -> Result<B::Hash, Error>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + BlockBackend<B> + ProofProvider<B>,
+{
+	let genesis_hash = client.info().genesis_hash;
+
+	let mut warp_proofs = Vec::new();
+	let mut set_id = 0;
+	let mut authorities = genesis_authorities;
+	let mut begin = genesis_hash;
+	let header = loop {
+		let proof = warp_sync_provider.generate(begin)?;
+		let result = warp_sync_provider.verify(&proof, set_id, authorities.clone())?;
+		warp_proofs.push(proof.0);
+		match result {
+			VerificationResult::Partial(new_set_id, new_authorities, last_hash) => {
+				set_id = new_set_id;
+				authorities = new_authorities;
+				begin = last_hash;
+			},
+			VerificationResult::Complete(_, _, header) => break header,
+		}
+	};
+
+	let hash = header.hash();
+	info!("Exporting snapshot of block #{} ({})", header.number(), hash);
+
+	let state_root = *header.state_root();
+	let snapshot_header = SnapshotHeader::<B> {
+		genesis_hash,
+		body: client.block_body(hash)?,
+		justifications: client.justifications(hash)?,
+		header,
+	};
+	output.write_all(&SNAPSHOT_MAGIC)?;
+	output.write_all(&SNAPSHOT_VERSION.encode())?;
+	output.write_all(&snapshot_header.encode())?;
+
+	for proof in warp_proofs {
+		output.write_all(&SnapshotEntry::WarpProof(proof).encode())?;
+	}
+
+	let mut start_key = SmallVec::<[Vec<u8>; 2]>::new();
+	let mut keys = 0u64;
+	loop {
+		let (proof, count) = client.read_proof_collection(hash, &start_key, STATE_PROOF_SIZE)?;
+		let (values, completed) =
+			client.verify_range_proof(state_root, proof.clone(), &start_key)?;
+		output.write_all(&SnapshotEntry::State(proof).encode())?;
+		keys += count as u64;
+
+		if completed == 0 {
+			break
+		}
+		if !values.update_last_key(completed, &mut start_key) {
+			return Err(format!("Failed to update the state cursor at depth {completed}").into())
+		}
+	}
+	output.write_all(&SnapshotEntry::End.encode())?;
+	output.flush()?;
+
+	info!("🎉 Exported snapshot of block {} with {} keys", hash, keys);
+	Ok(hash)
+}
+
+/// Import a snapshot into an empty database.
+///
+/// The warp sync proof of the snapshot is verified against the current authorities of
+/// `warp_sync_provider`, which are the genesis authorities of an empty database. Its state is
+/// verified against the state root of the block, and imported through `import_queue` the same way
+/// warp sync imports it.
+pub fn import_snapshot<B, C, IQ>(
+	client: Arc<C>,
+	mut import_queue: IQ,
+	warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+	input: impl Read + Send + 'static,
+) -> Pin<Box<dyn Future<Output = Result<B::Hash, Error>> + Send>>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + ProofProvider<B> + Send + Sync + 'static,
+	IQ: ImportQueue<B> + 'static,
+{
+	struct WaitLink {
+		imported: AtomicBool,
+		has_error: AtomicBool,
+	}
+
+	impl<B: BlockT> Link<B> for WaitLink {
+		fn blocks_processed(
+			&self,
+			_imported: usize,
+			_num_expected_blocks: usize,
+			results: Vec<(Result<BlockImportStatus<NumberFor<B>>, BlockImportError>, B::Hash)>,
+		) {
+			for (result, hash) in results {
+				if let Err(err) = result {
+					log::warn!("There was an error importing snapshot block {:?}: {}", hash, err);
+					self.has_error.store(true, Ordering::Release);
+				}
+			}
+			self.imported.store(true, Ordering::Release);
+		}
+	}
+
+	Box::pin(async move {
+		let block = read_snapshot(client, warp_sync_provider, input)?;
+		let hash = block.hash;
+		import_queue
+			.service_ref()
+			.import_blocks(BlockOrigin::NetworkInitialSync, vec![block]);
+
+		let link = WaitLink { imported: AtomicBool::new(false), has_error: AtomicBool::new(false) };
+		future::poll_fn(|cx| {
+			import_queue.poll_actions(cx, &link);
+
+			if link.has_error.load(Ordering::Acquire) {
+				return Poll::Ready(Err(Error::Other(format!(
+					"Failed to import snapshot of block {hash:?}"
+				))))
+			}
+			if link.imported.load(Ordering::Acquire) {
+				info!("🎉 Imported snapshot of block {:?}", hash);
+				return Poll::Ready(Ok(hash))
+			}
+
+			cx.waker().wake_by_ref();
+			Poll::Pending
+		})
+		.await
+	})
+}
+
+/// Read and verify a snapshot, returning its block ready to be imported.
+fn read_snapshot<B, C>(
+	client: Arc<C>,
+	warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+	input: impl Read,
+) -> Result<IncomingBlock<B>, Error>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + ProofProvider<B> + Send + Sync + 'static,
+{
+	let info = client.info();
+	if !info.best_number.is_zero() {
+		return Err("Snapshots can only be imported into an empty database".into())
+	}
+
+	let mut reader = CodecIoReader(input);
+	let decode_error = |err: codec::Error| Error::Other(format!("Invalid snapshot: {err}"));
+	if <[u8; 8]>::decode(&mut reader).map_err(decode_error)? != SNAPSHOT_MAGIC {
+		return Err("Invalid snapshot: not a snapshot file".into())
+	}
+	let version = u32::decode(&mut reader).map_err(decode_error)?;
+	if version != SNAPSHOT_VERSION {
+		return Err(format!("Unsupported snapshot version {version}").into())
+	}
+	let SnapshotHeader::<B> { genesis_hash, header, body, justifications } =
+		Decode::decode(&mut reader).map_err(decode_error)?;
+	if genesis_hash != info.genesis_hash {
+		return Err(format!(
+			"Snapshot of chain {genesis_hash:?} does not match chain {:?}",
+			info.genesis_hash
+		)
+		.into())
+	}
+
+	let hash = header.hash();
+	info!("Importing snapshot of block #{} ({})", header.number(), hash);
+
+	let mut set_id = 0;
+	let mut authorities = warp_sync_provider.current_authorities();
+	let mut proven = false;
+	let mut state_sync = StateSync::new(client, header, body, justifications, false);
+	loop {
+		match SnapshotEntry::decode(&mut reader).map_err(decode_error)? {
+			SnapshotEntry::WarpProof(_) if proven =>
+				return Err("Invalid snapshot: unexpected warp sync proof".into()),
+			SnapshotEntry::WarpProof(proof) => {
+				match warp_sync_provider.verify(
+					&EncodedProof(proof),
+					set_id,
+					authorities.clone(),
+				)? {
+					VerificationResult::Partial(new_set_id, new_authorities, _) => {
+						set_id = new_set_id;
+						authorities = new_authorities;
+					},
+					VerificationResult::Complete(_, _, proven_header) => {
+						if proven_header.hash() != hash {
+							return Err(format!(
+								"Invalid snapshot: warp sync proof ends at {:?}",
+								proven_header.hash()
+							)
+							.into())
+						}
+						proven = true;
+					},
+				}
+			},
+			SnapshotEntry::State(_) if !proven =>
+				return Err("Invalid snapshot: missing warp sync proof".into()),
+			SnapshotEntry::State(proof) => {
+				let response = StateResponse { entries: Vec::new(), proof: proof.encode() };
+				match state_sync.import(response) {
+					ImportResult::Continue => (),
+					ImportResult::BadResponse =>
+						return Err("Invalid snapshot: bad state proof".into()),
+					ImportResult::Import(hash, header, state, body, justifications) => {
+						let entry = SnapshotEntry::decode(&mut reader).map_err(decode_error)?;
+						if !matches!(entry, SnapshotEntry::End) {
+							return Err("Invalid snapshot: unexpected entry after the state".into())
+						}
+						return Ok(IncomingBlock {
+							hash,
+							header: Some(header),
+							body,
+							indexed_body: None,
+							justifications,
+							origin: None,
+							allow_missing_state: true,
+							import_existing: true,
+							skip_execution: true,
+							state: Some(state),
+						})
+					},
+				}
+			},
+			SnapshotEntry::End => return Err("Invalid snapshot: incomplete state".into()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::executor::block_on;
+	use sc_block_builder::BlockBuilderBuilder;
+	use sc_client_api::StorageProvider;
+	use sc_consensus::{
+		import_queue::{BasicQueue, Verifier},
+		BlockImportParams,
+	};
+	use substrate_test_runtime_client::{
+		prelude::*,
+		runtime::{currency::DOLLARS, Block, Header, Transfer},
+	};
+
+	/// A warp sync provider whose proof is the finalized header of its client.
+	///
+	/// The proof is complete in a single fragment, and trusted as long as it decodes.
+	struct TestWarpSyncProvider(Arc<TestClient>);
+
+	impl WarpSyncProvider<Block> for TestWarpSyncProvider {
+		fn generate(
+			&self,
+			_start: <Block as BlockT>::Hash,
+		) -> Result<EncodedProof, Box<dyn std::error::Error + Send + Sync>> {
+			let header = self.0.header(self.0.info().finalized_hash)?.expect("finalized header");
+			Ok(EncodedProof(header.encode()))
+		}
+
+		fn verify(
+			&self,
+			proof: &EncodedProof,
+			set_id: u64,
+			authorities: AuthorityList,
+		) -> Result<VerificationResult<Block>, Box<dyn std::error::Error + Send + Sync>> {
+			let header = Header::decode(&mut &proof.0[..])?;
+			Ok(VerificationResult::Complete(set_id, authorities, header))
+		}
+
+		fn current_authorities(&self) -> AuthorityList {
+			Vec::new()
+		}
+	}
+
+	struct PassThroughVerifier;
+
+	#[async_trait::async_trait]
+	impl Verifier<Block> for PassThroughVerifier {
+		async fn verify(
+			&self,
+			block: BlockImportParams<Block>,
+		) -> Result<BlockImportParams<Block>, String> {
+			Ok(block)
+		}
+	}
+
+	/// Build a client with a few finalized blocks, the first of them changing the state with a
+	/// transfer.
+	fn client_with_blocks() -> Arc<TestClient> {
+		let client = Arc::new(TestClientBuilder::new().build());
+		for nonce in 0..3 {
+			let info = client.info();
+			let mut builder = BlockBuilderBuilder::new(&*client)
+				.on_parent_block(info.best_hash)
+				.with_parent_block_number(info.best_number)
+				.build()
+				.unwrap();
+			if nonce == 0 {
+				builder
+					.push_transfer(Transfer {
+						from: Sr25519Keyring::Alice.into(),
+						to: Sr25519Keyring::Ferdie.into(),
+						amount: 4 * DOLLARS,
+						nonce,
+					})
+					.unwrap();
+			}
+			let block = builder.build().unwrap().block;
+			block_on(client.import_as_final(BlockOrigin::Own, block)).unwrap();
+		}
+		client
+	}
+
+	fn export(client: &Arc<TestClient>) -> (<Block as BlockT>::Hash, Vec<u8>) {
+		let mut snapshot = Vec::new();
+		let hash = export_snapshot(
+			client.clone(),
+			Arc::new(TestWarpSyncProvider(client.clone())),
+			Vec::new(),
+			&mut snapshot,
+		)
+		.unwrap();
+		(hash, snapshot)
+	}
+
+	fn import(snapshot: Vec<u8>) -> (Arc<TestClient>, Result<<Block as BlockT>::Hash, Error>) {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let import_queue =
+			BasicQueue::new(PassThroughVerifier, Box::new(client.clone()), None, &spawner, None);
+		let result = block_on(import_snapshot(
+			client.clone(),
+			import_queue,
+			Arc::new(TestWarpSyncProvider(client.clone())),
+			std::io::Cursor::new(snapshot),
+		));
+		(client, result)
+	}
+
+	/// Decode `snapshot`, apply `modify` to its entries and encode it back.
+	fn tamper(snapshot: &[u8], modify: impl FnOnce(&mut Vec<SnapshotEntry>)) -> Vec<u8> {
+		let mut input = snapshot;
+		let magic = <[u8; 8]>::decode(&mut input).unwrap();
+		let version = u32::decode(&mut input).unwrap();
+		let header = SnapshotHeader::<Block>::decode(&mut input).unwrap();
+		let mut entries = Vec::new();
+		while !input.is_empty() {
+			entries.push(SnapshotEntry::decode(&mut input).unwrap());
+		}
+
+		modify(&mut entries);
+
+		let mut tampered = (magic, version, header).encode();
+		entries.iter().for_each(|entry| entry.encode_to(&mut tampered));
+		tampered
+	}
+
+	#[test]
+	fn snapshot_round_trip_works() {
+		let client = client_with_blocks();
+		let (hash, snapshot) = export(&client);
+		assert_eq!(hash, client.info().finalized_hash);
+
+		let (imported, result) = import(snapshot);
+		assert_eq!(result.unwrap(), hash);
+
+		let header = client.header(hash).unwrap().unwrap();
+		let imported_header = imported.header(hash).unwrap().unwrap();
+		assert_eq!(imported_header.state_root(), header.state_root());
+		assert_eq!(
+			imported.storage_pairs(hash, None, None).unwrap().collect::<Vec<_>>(),
+			client.storage_pairs(hash, None, None).unwrap().collect::<Vec<_>>(),
+		);
+	}
+
+	#[test]
+	fn snapshot_with_tampered_warp_proof_is_rejected() {
+		let client = client_with_blocks();
+		let (hash, snapshot) = export(&client);
+
+		let snapshot = tamper(&snapshot, |entries| match &mut entries[0] {
+			SnapshotEntry::WarpProof(proof) => proof[0] ^= 0xff,
+			_ => panic!("the snapshot starts with the warp sync proof"),
+		});
+
+		let (imported, result) = import(snapshot);
+		assert!(result.is_err());
+		assert!(imported.header(hash).unwrap().is_none());
+	}
+
+	#[test]
+	fn snapshot_with_tampered_state_proof_is_rejected() {
+		let client = client_with_blocks();
+		let (hash, snapshot) = export(&client);
+
+		let snapshot = tamper(&snapshot, |entries| match &mut entries[1] {
+			SnapshotEntry::State(proof) => {
+				let node = proof.encoded_nodes.last_mut().unwrap();
+				*node.last_mut().unwrap() ^= 0xff;
+			},
+			_ => panic!("the warp sync proof is followed by the state"),
+		});
+
+		let (imported, result) = import(snapshot);
+		assert!(result.is_err());
+		assert!(imported.header(hash).unwrap().is_none());
+	}
+}
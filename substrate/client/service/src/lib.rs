@@ -616,4 +616,52 @@ mod tests {
 		assert_eq!(transactions.len(), 1);
 		assert!(TransferData::try_from(&*transactions[0].1).is_ok());
 	}
+
+	#[test]
+	fn archive_rpc_is_exposed_when_historical_state_is_kept() {
+		let runtime = tokio::runtime::Runtime::new().unwrap();
+		let task_manager = TaskManager::new(runtime.handle().clone(), None).unwrap();
+		let (client, backend) = TestClientBuilder::new().build_with_backend();
+		let client = Arc::new(client);
+		let pool = Arc::from(BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			task_manager.spawn_essential_handle(),
+			client.clone(),
+		));
+		let chain_spec = GenericChainSpec::<NoExtension>::builder(&[], None)
+			.with_name("test")
+			.with_id("test")
+			.with_genesis_config_patch(Default::default())
+			.build();
+		let is_archive_exposed = |state_pruning| {
+			let (system_rpc_tx, _) = sc_utils::mpsc::tracing_unbounded("test_system_rpc", 1);
+			let rpc_module = gen_rpc_module(
+				task_manager.spawn_handle(),
+				client.clone(),
+				pool.clone(),
+				Arc::new(sc_keystore::LocalKeystore::in_memory()),
+				system_rpc_tx,
+				"test".into(),
+				"test".into(),
+				&chain_spec,
+				&Some(state_pruning),
+				BlocksPruning::KeepAll,
+				backend.clone(),
+				&|_| Ok(RpcModule::new(())),
+			)
+			.unwrap();
+			let exposed = rpc_module.method_names().any(|name| name == "archive_v1_storage");
+			exposed
+		};
+
+		assert!(is_archive_exposed(PruningMode::ArchiveAll));
+		assert!(is_archive_exposed(PruningMode::ArchiveCanonical));
+		assert!(is_archive_exposed(PruningMode::checkpoints(
+			256,
+			std::num::NonZeroU32::new(1000).unwrap()
+		)));
+		assert!(!is_archive_exposed(PruningMode::blocks_pruning(256)));
+	}
 }
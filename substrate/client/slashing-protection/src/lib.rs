@@ -0,0 +1,729 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Slashing protection for validator keys.
+//!
+//! The keystore signs whatever it is asked to. A validator restored from a backup, or
+//! accidentally running twice, may thus sign two different block seals for the same slot or two
+//! different votes for the same round and get slashed for equivocating.
+//!
+//! [`SlashingProtection`] keeps a persistent record of the messages signed by every local key and
+//! must be consulted by the consensus workers before asking the keystore for a signature. In the
+//! spirit of [EIP-3076](https://eips.ethereum.org/EIPS/eip-3076) a message is refused if:
+//!
+//! - a different message of the same [`MessageKind`] was already signed at the same [`Position`];
+//! - it is positioned before the latest message of the same kind signed by the key. This also
+//!   covers the history pruned from the record or imported from a different machine.
+//!
+//! The record is stored as an append-only log, signing a message only appends a line to it. It
+//! can be exported and imported using the [`Interchange`] format.
+
+#![warn(missing_docs)]
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sp_core::{crypto::KeyTypeId, H256};
+use std::{
+	collections::BTreeMap,
+	fs::{self, File, OpenOptions},
+	io::Write,
+	path::{Path, PathBuf},
+};
+
+const LOG_TARGET: &str = "slashing-protection";
+
+/// Version of the [`Interchange`] format.
+pub const INTERCHANGE_FORMAT_VERSION: u32 = 1;
+
+/// Maximum number of messages recorded per key and message kind.
+///
+/// Older messages are pruned, signing at their position stays forbidden.
+pub const MAX_HISTORY_LEN: usize = 1024;
+
+/// Slashing protection error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// A different message was already signed at the same position.
+	#[error("Refusing to sign {kind:?} at {position:?}: a different message was already signed")]
+	Conflict {
+		/// Kind of the refused message.
+		kind: MessageKind,
+		/// Position of the refused message.
+		position: Position,
+	},
+	/// A message was already signed at a later position.
+	#[error("Refusing to sign {kind:?} at {position:?}: already signed at {latest:?}")]
+	Outdated {
+		/// Kind of the refused message.
+		kind: MessageKind,
+		/// Position of the refused message.
+		position: Position,
+		/// Position of the latest signed message.
+		latest: Position,
+	},
+	/// The record belongs to a different chain.
+	#[error("Genesis hash mismatch")]
+	GenesisMismatch,
+	/// The interchange format version is not supported.
+	#[error("Unsupported interchange format version {0}")]
+	UnsupportedVersion(u32),
+	/// IO error.
+	#[error(transparent)]
+	Io(#[from] std::io::Error),
+	/// JSON error.
+	#[error(transparent)]
+	Json(#[from] serde_json::Error),
+}
+
+/// Slashing protection result.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Kind of a signed consensus message.
+///
+/// Messages of different kinds never conflict with each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKind {
+	/// Block seal, positioned by slot.
+	BlockSeal,
+	/// GRANDPA primary proposal, positioned by set id and round.
+	GrandpaPrimaryPropose,
+	/// GRANDPA prevote, positioned by set id and round.
+	GrandpaPrevote,
+	/// GRANDPA precommit, positioned by set id and round.
+	GrandpaPrecommit,
+	/// BEEFY vote, positioned by validator set id and block number.
+	BeefyVote,
+}
+
+/// Position of a signed message.
+///
+/// Positions are ordered by set id first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Position {
+	/// Authority set id, zero for block seals.
+	pub set_id: u64,
+	/// Slot, round or block number.
+	pub index: u64,
+}
+
+impl Position {
+	/// Position of a message within an authority set.
+	pub fn new(set_id: u64, index: u64) -> Self {
+		Self { set_id, index }
+	}
+
+	/// Position of a block seal.
+	pub fn slot(slot: u64) -> Self {
+		Self { set_id: 0, index: slot }
+	}
+}
+
+/// Slashing protection record in the interchange format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interchange {
+	/// Record metadata.
+	pub metadata: InterchangeMetadata,
+	/// Signed messages grouped by key and message kind.
+	pub data: Vec<InterchangeRecord>,
+}
+
+/// Metadata of an [`Interchange`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterchangeMetadata {
+	/// Format version, see [`INTERCHANGE_FORMAT_VERSION`].
+	pub interchange_format_version: u32,
+	/// Genesis hash of the chain the messages were signed for.
+	#[serde(with = "sp_core::bytes")]
+	pub genesis_hash: Vec<u8>,
+}
+
+/// Messages of a given kind signed by a key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterchangeRecord {
+	/// Key type of the signing key.
+	#[serde(with = "key_type")]
+	pub key_type: KeyTypeId,
+	/// Public key of the signing key.
+	#[serde(with = "sp_core::bytes")]
+	pub public_key: Vec<u8>,
+	/// Kind of the signed messages.
+	pub kind: MessageKind,
+	/// Signed messages, in ascending position order.
+	pub signed_messages: Vec<SignedMessage>,
+}
+
+/// A signed message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedMessage {
+	/// Position of the message.
+	#[serde(flatten)]
+	pub position: Position,
+	/// Blake2-256 hash of the signed payload.
+	pub signing_root: H256,
+}
+
+mod key_type {
+	use serde::{de::Error, Deserialize, Deserializer, Serializer};
+	use sp_core::crypto::KeyTypeId;
+
+	pub fn serialize<S: Serializer>(
+		key_type: &KeyTypeId,
+		serializer: S,
+	) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&String::from_utf8_lossy(&key_type.0))
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<KeyTypeId, D::Error> {
+		let key_type = String::deserialize(deserializer)?;
+		KeyTypeId::try_from(key_type.as_str())
+			.map_err(|_| D::Error::custom(format!("Invalid key type: {key_type}")))
+	}
+}
+
+type HistoryKey = (KeyTypeId, Vec<u8>, MessageKind);
+
+type History = BTreeMap<Position, H256>;
+
+/// Entry of the on-disk log, one per signed message.
+#[derive(Serialize, Deserialize)]
+struct LogEntry {
+	#[serde(with = "key_type")]
+	key_type: KeyTypeId,
+	#[serde(with = "sp_core::bytes")]
+	public_key: Vec<u8>,
+	kind: MessageKind,
+	#[serde(flatten)]
+	message: SignedMessage,
+}
+
+/// Append-only log storing the record on disk.
+///
+/// The first line holds the [`InterchangeMetadata`], every following line a [`LogEntry`]. Signing
+/// a message only appends a line, the log is rewritten from the in-memory record once it holds
+/// too many pruned entries.
+struct Log {
+	path: PathBuf,
+	file: File,
+	/// Number of entries in the log.
+	len: usize,
+	/// Whether the log must be rewritten before appending to it, e.g. after a failed write.
+	needs_compaction: bool,
+}
+
+impl Log {
+	fn append(&mut self, entry: &LogEntry) -> Result<()> {
+		let mut line = serde_json::to_vec(entry)?;
+		line.push(b'\n');
+		self.file.write_all(&line)?;
+		self.file.sync_data()?;
+		self.len += 1;
+		Ok(())
+	}
+}
+
+struct Inner {
+	log: Option<Log>,
+	genesis_hash: Vec<u8>,
+	histories: BTreeMap<HistoryKey, History>,
+}
+
+impl Inner {
+	fn metadata(&self) -> InterchangeMetadata {
+		InterchangeMetadata {
+			interchange_format_version: INTERCHANGE_FORMAT_VERSION,
+			genesis_hash: self.genesis_hash.clone(),
+		}
+	}
+
+	fn check_metadata(&self, metadata: &InterchangeMetadata) -> Result<()> {
+		if metadata.interchange_format_version != INTERCHANGE_FORMAT_VERSION {
+			return Err(Error::UnsupportedVersion(metadata.interchange_format_version))
+		}
+		if metadata.genesis_hash != self.genesis_hash {
+			return Err(Error::GenesisMismatch)
+		}
+		Ok(())
+	}
+
+	fn export(&self) -> Interchange {
+		let data = self
+			.histories
+			.iter()
+			.filter(|(_, history)| !history.is_empty())
+			.map(|((key_type, public_key, kind), history)| InterchangeRecord {
+				key_type: *key_type,
+				public_key: public_key.clone(),
+				kind: *kind,
+				signed_messages: history
+					.iter()
+					.map(|(position, signing_root)| SignedMessage {
+						position: *position,
+						signing_root: *signing_root,
+					})
+					.collect(),
+			})
+			.collect();
+
+		Interchange { metadata: self.metadata(), data }
+	}
+
+	fn import(&mut self, interchange: Interchange) -> Result<()> {
+		self.check_metadata(&interchange.metadata)?;
+
+		for record in interchange.data {
+			let history = self
+				.histories
+				.entry((record.key_type, record.public_key, record.kind))
+				.or_default();
+			for message in record.signed_messages {
+				// If both machines signed at the same position the damage is already done,
+				// signing any of the two messages again doesn't make it worse.
+				history.entry(message.position).or_insert(message.signing_root);
+			}
+			prune(history);
+		}
+		Ok(())
+	}
+
+	/// Load the log stored at `path` into the record.
+	///
+	/// Returns whether the log must be compacted.
+	fn load(&mut self, path: &Path) -> Result<(usize, bool)> {
+		let content = fs::read_to_string(path)?;
+		let mut lines = content.split_terminator('\n');
+		let Some(metadata) = lines.next() else { return Ok((0, true)) };
+		self.check_metadata(&serde_json::from_str(metadata)?)?;
+
+		let mut len = 0;
+		let mut needs_compaction = false;
+		while let Some(line) = lines.next() {
+			let entry: LogEntry = match serde_json::from_str(line) {
+				Ok(entry) => entry,
+				// The node stopped while appending the last entry. It wasn't signed since the
+				// entry is synced before signing.
+				Err(_) if !content.ends_with('\n') && lines.clone().next().is_none() => {
+					log::warn!(target: LOG_TARGET, "Ignoring truncated slashing protection entry");
+					needs_compaction = true;
+					break
+				},
+				Err(e) => return Err(e.into()),
+			};
+			let history = self
+				.histories
+				.entry((entry.key_type, entry.public_key, entry.kind))
+				.or_default();
+			history.entry(entry.message.position).or_insert(entry.message.signing_root);
+			prune(history);
+			len += 1;
+		}
+		Ok((len, needs_compaction || self.log_too_long(len)))
+	}
+
+	/// Whether a log of `len` entries holds too many pruned or duplicate entries.
+	fn log_too_long(&self, len: usize) -> bool {
+		let live = self.histories.values().map(BTreeMap::len).sum::<usize>();
+		len > 2 * live.max(MAX_HISTORY_LEN)
+	}
+
+	/// Rewrite the log stored at `path` from the record, atomically replacing the previous one.
+	fn compact(&self, path: PathBuf) -> Result<Log> {
+		let tmp_path = path.with_extension("tmp");
+		let mut file = File::create(&tmp_path)?;
+		let mut content = serde_json::to_vec(&self.metadata())?;
+		content.push(b'\n');
+		let mut len = 0;
+		for ((key_type, public_key, kind), history) in &self.histories {
+			for (position, signing_root) in history {
+				serde_json::to_writer(
+					&mut content,
+					&LogEntry {
+						key_type: *key_type,
+						public_key: public_key.clone(),
+						kind: *kind,
+						message: SignedMessage { position: *position, signing_root: *signing_root },
+					},
+				)?;
+				content.push(b'\n');
+				len += 1;
+			}
+		}
+		file.write_all(&content)?;
+		file.sync_all()?;
+		fs::rename(&tmp_path, &path)?;
+
+		let file = OpenOptions::new().append(true).open(&path)?;
+		Ok(Log { path, file, len, needs_compaction: false })
+	}
+
+	/// Persist a newly recorded message.
+	fn persist(&mut self, entry: LogEntry) -> Result<()> {
+		let Some(log) = self.log.as_ref() else { return Ok(()) };
+		if log.needs_compaction || self.log_too_long(log.len + 1) {
+			return self.persist_all()
+		}
+
+		let log = self.log.as_mut().expect("checked above; qed");
+		let result = log.append(&entry);
+		// The log may end with a partially written entry.
+		log.needs_compaction = result.is_err();
+		result
+	}
+
+	/// Rewrite the whole log, e.g. after an import.
+	fn persist_all(&mut self) -> Result<()> {
+		let Some(log) = self.log.as_mut() else { return Ok(()) };
+		let path = log.path.clone();
+		match self.compact(path) {
+			Ok(log) => {
+				self.log = Some(log);
+				Ok(())
+			},
+			Err(e) => {
+				if let Some(log) = self.log.as_mut() {
+					log.needs_compaction = true;
+				}
+				Err(e)
+			},
+		}
+	}
+}
+
+fn prune(history: &mut History) {
+	while history.len() > MAX_HISTORY_LEN {
+		history.pop_first();
+	}
+}
+
+/// Persistent record of the messages signed by the local keys.
+pub struct SlashingProtection(Mutex<Inner>);
+
+impl SlashingProtection {
+	/// Open the record stored at `path`, creating it if it doesn't exist.
+	///
+	/// `genesis_hash` identifies the chain the messages are signed for.
+	pub fn open<T: Into<PathBuf>>(path: T, genesis_hash: &[u8]) -> Result<Self> {
+		let path = path.into();
+		let mut inner =
+			Inner { log: None, genesis_hash: genesis_hash.to_vec(), histories: BTreeMap::new() };
+
+		let (len, needs_compaction) = if path.exists() {
+			inner.load(&path)?
+		} else {
+			if let Some(parent) = path.parent() {
+				fs::create_dir_all(parent)?;
+			}
+			(0, true)
+		};
+
+		inner.log = Some(if needs_compaction {
+			inner.compact(path)?
+		} else {
+			let file = OpenOptions::new().append(true).open(&path)?;
+			Log { path, file, len, needs_compaction: false }
+		});
+
+		Ok(Self(Mutex::new(inner)))
+	}
+
+	/// Create a record which is only kept in memory.
+	pub fn in_memory(genesis_hash: &[u8]) -> Self {
+		Self(Mutex::new(Inner {
+			log: None,
+			genesis_hash: genesis_hash.to_vec(),
+			histories: BTreeMap::new(),
+		}))
+	}
+
+	/// Check that `payload` can be signed by `public` and record it.
+	///
+	/// Must be called right before signing `payload`. Signing the very same payload at the same
+	/// position again is allowed. Returns an error if signing the message could lead to an
+	/// equivocation or if the record can't be persisted, in which case the message must not be
+	/// signed.
+	pub fn check_and_record(
+		&self,
+		key_type: KeyTypeId,
+		public: &[u8],
+		kind: MessageKind,
+		position: Position,
+		payload: &[u8],
+	) -> Result<()> {
+		let signing_root = H256(sp_crypto_hashing::blake2_256(payload));
+		let mut inner = self.0.lock();
+		let history = inner.histories.entry((key_type, public.to_vec(), kind)).or_default();
+
+		match history.get(&position) {
+			Some(root) if *root == signing_root => return Ok(()),
+			Some(_) => return Err(Error::Conflict { kind, position }),
+			None => {},
+		}
+		if let Some((latest, _)) = history.last_key_value() {
+			if position < *latest {
+				return Err(Error::Outdated { kind, position, latest: *latest })
+			}
+		}
+
+		history.insert(position, signing_root);
+		prune(history);
+
+		let entry = LogEntry {
+			key_type,
+			public_key: public.to_vec(),
+			kind,
+			message: SignedMessage { position, signing_root },
+		};
+		if let Err(e) = inner.persist(entry) {
+			log::error!(target: LOG_TARGET, "Failed to persist slashing protection record: {e}");
+			// Better not to sign at all than signing something we may forget about.
+			if let Some(history) = inner.histories.get_mut(&(key_type, public.to_vec(), kind)) {
+				history.remove(&position);
+			}
+			return Err(e)
+		}
+		Ok(())
+	}
+
+	/// Export the record.
+	pub fn export(&self) -> Interchange {
+		self.0.lock().export()
+	}
+
+	/// Merge an exported record into this one.
+	///
+	/// Afterwards no message positioned before the latest imported one can be signed.
+	pub fn import(&self, interchange: Interchange) -> Result<()> {
+		let mut inner = self.0.lock();
+		inner.import(interchange)?;
+		inner.persist_all()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::testing::{ED25519, SR25519};
+	use tempfile::TempDir;
+
+	const GENESIS: [u8; 32] = [1; 32];
+	const ALICE: [u8; 32] = [2; 32];
+	const BOB: [u8; 32] = [3; 32];
+
+	#[test]
+	fn conflicting_messages_are_refused() {
+		let protection = SlashingProtection::in_memory(&GENESIS);
+		let seal = |slot, payload: &[u8]| {
+			protection.check_and_record(
+				SR25519,
+				&ALICE,
+				MessageKind::BlockSeal,
+				Position::slot(slot),
+				payload,
+			)
+		};
+
+		assert!(seal(10, b"a").is_ok());
+		// Signing the same message again is fine.
+		assert!(seal(10, b"a").is_ok());
+		assert!(matches!(seal(10, b"b"), Err(Error::Conflict { .. })));
+		assert!(seal(12, b"b").is_ok());
+		assert!(matches!(
+			seal(11, b"c"),
+			Err(Error::Outdated { latest, .. }) if latest == Position::slot(12)
+		));
+		assert!(seal(10, b"a").is_ok());
+	}
+
+	#[test]
+	fn histories_are_independent() {
+		let protection = SlashingProtection::in_memory(&GENESIS);
+		let position = Position::new(1, 5);
+
+		for (public, kind) in [
+			(ALICE, MessageKind::GrandpaPrevote),
+			(ALICE, MessageKind::GrandpaPrecommit),
+			(BOB, MessageKind::GrandpaPrevote),
+		] {
+			assert!(protection.check_and_record(ED25519, &public, kind, position, b"a").is_ok());
+		}
+		assert!(protection
+			.check_and_record(SR25519, &ALICE, MessageKind::GrandpaPrevote, position, b"b")
+			.is_ok());
+		assert!(protection
+			.check_and_record(ED25519, &ALICE, MessageKind::GrandpaPrevote, position, b"b")
+			.is_err());
+
+		// A new set resets the rounds.
+		assert!(protection
+			.check_and_record(
+				ED25519,
+				&ALICE,
+				MessageKind::GrandpaPrevote,
+				Position::new(2, 1),
+				b"b"
+			)
+			.is_ok());
+		assert!(protection
+			.check_and_record(
+				ED25519,
+				&ALICE,
+				MessageKind::GrandpaPrevote,
+				Position::new(1, 6),
+				b"b"
+			)
+			.is_err());
+	}
+
+	#[test]
+	fn record_is_persisted() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("protection.json");
+
+		let protection = SlashingProtection::open(&path, &GENESIS).unwrap();
+		protection
+			.check_and_record(SR25519, &ALICE, MessageKind::BlockSeal, Position::slot(3), b"a")
+			.unwrap();
+		drop(protection);
+
+		let protection = SlashingProtection::open(&path, &GENESIS).unwrap();
+		assert!(matches!(
+			protection.check_and_record(
+				SR25519,
+				&ALICE,
+				MessageKind::BlockSeal,
+				Position::slot(3),
+				b"b"
+			),
+			Err(Error::Conflict { .. })
+		));
+		drop(protection);
+
+		assert!(matches!(SlashingProtection::open(&path, &[0; 32]), Err(Error::GenesisMismatch)));
+	}
+
+	#[test]
+	fn log_is_appended_and_compacted() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("protection.json");
+		let lines = || fs::read_to_string(&path).unwrap().lines().count();
+		let seal = |protection: &SlashingProtection, slot, payload: &[u8]| {
+			protection.check_and_record(
+				SR25519,
+				&ALICE,
+				MessageKind::BlockSeal,
+				Position::slot(slot),
+				payload,
+			)
+		};
+
+		let protection = SlashingProtection::open(&path, &GENESIS).unwrap();
+		assert_eq!(lines(), 1);
+		seal(&protection, 1, b"a").unwrap();
+		seal(&protection, 2, b"a").unwrap();
+		seal(&protection, 2, b"a").unwrap();
+		assert_eq!(lines(), 3);
+		drop(protection);
+
+		// Entry truncated by a crash.
+		let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+		file.write_all(b"{\"key_type\":\"sr25\",").unwrap();
+		drop(file);
+
+		let protection = SlashingProtection::open(&path, &GENESIS).unwrap();
+		assert_eq!(lines(), 3);
+		assert!(matches!(seal(&protection, 2, b"b"), Err(Error::Conflict { .. })));
+
+		let len = MAX_HISTORY_LEN as u64;
+		for slot in 3..3 * len {
+			seal(&protection, slot, b"a").unwrap();
+			assert!(lines() <= 2 * MAX_HISTORY_LEN + 1);
+		}
+		drop(protection);
+
+		let protection = SlashingProtection::open(&path, &GENESIS).unwrap();
+		assert!(matches!(seal(&protection, 3 * len - 1, b"b"), Err(Error::Conflict { .. })));
+		assert_eq!(protection.export().data[0].signed_messages.len(), MAX_HISTORY_LEN);
+	}
+
+	#[test]
+	fn import_export_works() {
+		let source = SlashingProtection::in_memory(&GENESIS);
+		source
+			.check_and_record(ED25519, &ALICE, MessageKind::BeefyVote, Position::new(0, 8), b"a")
+			.unwrap();
+		source
+			.check_and_record(ED25519, &ALICE, MessageKind::BeefyVote, Position::new(0, 16), b"b")
+			.unwrap();
+
+		let exported = source.export();
+		let json = serde_json::to_string(&exported).unwrap();
+		assert!(json.contains("\"key_type\":\"ed25\""));
+		assert!(json.contains("\"kind\":\"beefy_vote\""));
+		let exported: Interchange = serde_json::from_str(&json).unwrap();
+
+		let target = SlashingProtection::in_memory(&GENESIS);
+		target
+			.check_and_record(ED25519, &ALICE, MessageKind::BeefyVote, Position::new(0, 4), b"c")
+			.unwrap();
+		target.import(exported.clone()).unwrap();
+
+		let vote = |index, payload: &[u8]| {
+			target.check_and_record(
+				ED25519,
+				&ALICE,
+				MessageKind::BeefyVote,
+				Position::new(0, index),
+				payload,
+			)
+		};
+		assert!(vote(16, b"b").is_ok());
+		assert!(matches!(vote(16, b"c"), Err(Error::Conflict { .. })));
+		// Nothing was recorded for 12 but it comes before the imported history.
+		assert!(matches!(vote(12, b"c"), Err(Error::Outdated { .. })));
+		assert!(vote(17, b"c").is_ok());
+		assert_eq!(target.export().data[0].signed_messages.len(), 4);
+
+		let other_chain = SlashingProtection::in_memory(&[0; 32]);
+		assert!(matches!(other_chain.import(exported), Err(Error::GenesisMismatch)));
+	}
+
+	#[test]
+	fn history_is_pruned() {
+		let protection = SlashingProtection::in_memory(&GENESIS);
+		let len = MAX_HISTORY_LEN as u64;
+		for slot in 0..len + 10 {
+			protection
+				.check_and_record(
+					SR25519,
+					&ALICE,
+					MessageKind::BlockSeal,
+					Position::slot(slot),
+					b"a",
+				)
+				.unwrap();
+		}
+
+		let exported = protection.export();
+		let signed = &exported.data[0].signed_messages;
+		assert_eq!(signed.len(), MAX_HISTORY_LEN);
+		assert_eq!(signed[0].position, Position::slot(10));
+		assert!(protection
+			.check_and_record(SR25519, &ALICE, MessageKind::BlockSeal, Position::slot(0), b"b")
+			.is_err());
+	}
+}
@@ -46,7 +46,7 @@ mod pruning;
 #[cfg(test)]
 mod test;
 
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
 use log::trace;
 use noncanonical::NonCanonicalOverlay;
 use parking_lot::RwLock;
@@ -54,6 +54,7 @@ use pruning::{HaveBlock, RefWindow};
 use std::{
 	collections::{hash_map::Entry, HashMap},
 	fmt,
+	num::NonZeroU32,
 };
 
 const LOG_TARGET: &str = "state-db";
@@ -62,6 +63,8 @@ const PRUNING_MODE: &[u8] = b"mode";
 const PRUNING_MODE_ARCHIVE: &[u8] = b"archive";
 const PRUNING_MODE_ARCHIVE_CANON: &[u8] = b"archive_canonical";
 const PRUNING_MODE_CONSTRAINED: &[u8] = b"constrained";
+const PRUNING_MODE_CHECKPOINTS: &[u8] = b"checkpoints";
+const PRUNING_CHECKPOINT_INTERVAL: &[u8] = b"checkpoint_interval";
 pub(crate) const DEFAULT_MAX_BLOCK_CONSTRAINT: u32 = 256;
 
 /// Database value type.
@@ -235,6 +238,14 @@ pub enum PruningMode {
 	ArchiveAll,
 	/// Canonicalization discards non-canonical nodes. All the canonical nodes are kept in the DB.
 	ArchiveCanonical,
+	/// Maintain a pruning window, and keep the state of every canonical block whose number is a
+	/// multiple of `interval`.
+	Checkpoints {
+		/// Constraints of the pruning window.
+		constraints: Constraints,
+		/// Number of blocks between two checkpoints.
+		interval: NonZeroU32,
+	},
 }
 
 impl PruningMode {
@@ -243,10 +254,28 @@ impl PruningMode {
 		PruningMode::Constrained(Constraints { max_blocks: Some(n) })
 	}
 
+	/// Create a mode that keeps given number of blocks, and the state of every `interval`-th
+	/// block.
+	pub fn checkpoints(n: u32, interval: NonZeroU32) -> PruningMode {
+		PruningMode::Checkpoints { constraints: Constraints { max_blocks: Some(n) }, interval }
+	}
+
 	/// Is this an archive (either ArchiveAll or ArchiveCanonical) pruning mode?
 	pub fn is_archive(&self) -> bool {
 		match *self {
 			PruningMode::ArchiveAll | PruningMode::ArchiveCanonical => true,
+			PruningMode::Constrained(_) | PruningMode::Checkpoints { .. } => false,
+		}
+	}
+
+	/// Does this pruning mode keep the state of some blocks beyond the pruning window?
+	///
+	/// This is the case of the archive modes and of [`PruningMode::Checkpoints`].
+	pub fn keeps_historical_state(&self) -> bool {
+		match *self {
+			PruningMode::ArchiveAll |
+			PruningMode::ArchiveCanonical |
+			PruningMode::Checkpoints { .. } => true,
 			PruningMode::Constrained(_) => false,
 		}
 	}
@@ -257,6 +286,7 @@ impl PruningMode {
 			PruningMode::ArchiveAll => PRUNING_MODE_ARCHIVE,
 			PruningMode::ArchiveCanonical => PRUNING_MODE_ARCHIVE_CANON,
 			PruningMode::Constrained(_) => PRUNING_MODE_CONSTRAINED,
+			PruningMode::Checkpoints { .. } => PRUNING_MODE_CHECKPOINTS,
 		}
 	}
 
@@ -319,6 +349,13 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		let pruning: Option<RefWindow<BlockHash, Key, D>> = match mode {
 			PruningMode::Constrained(Constraints { max_blocks }) =>
 				Some(RefWindow::new(db, max_blocks.unwrap_or(0), ref_counting)?),
+			PruningMode::Checkpoints { constraints: Constraints { max_blocks }, interval } =>
+				Some(RefWindow::new_with_checkpoints(
+					db,
+					max_blocks.unwrap_or(0),
+					ref_counting,
+					Some(interval),
+				)?),
 			PruningMode::ArchiveAll | PruningMode::ArchiveCanonical => None,
 		};
 
@@ -338,7 +375,9 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 				// write changes immediately
 				Ok(CommitSet { data: changeset, meta: Default::default() })
 			},
-			PruningMode::Constrained(_) | PruningMode::ArchiveCanonical => self
+			PruningMode::Constrained(_) |
+			PruningMode::Checkpoints { .. } |
+			PruningMode::ArchiveCanonical => self
 				.non_canonical
 				.insert(hash, number, parent_hash, changeset)
 				.map_err(Into::into),
@@ -380,7 +419,9 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 	fn is_pruned(&self, hash: &BlockHash, number: u64) -> IsPruned {
 		match self.mode {
 			PruningMode::ArchiveAll => IsPruned::NotPruned,
-			PruningMode::ArchiveCanonical | PruningMode::Constrained(_) => {
+			PruningMode::ArchiveCanonical |
+			PruningMode::Constrained(_) |
+			PruningMode::Checkpoints { .. } => {
 				if self
 					.non_canonical
 					.last_canonicalized_block_number()
@@ -408,8 +449,10 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 	}
 
 	fn prune(&mut self, commit: &mut CommitSet<Key>) -> Result<(), Error<D::Error>> {
-		if let (&mut Some(ref mut pruning), PruningMode::Constrained(constraints)) =
-			(&mut self.pruning, &self.mode)
+		if let (
+			&mut Some(ref mut pruning),
+			PruningMode::Constrained(constraints) | PruningMode::Checkpoints { constraints, .. },
+		) = (&mut self.pruning, &self.mode)
 		{
 			loop {
 				if pruning.window_size() <= constraints.max_blocks.unwrap_or(0) as u64 {
@@ -442,16 +485,18 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 	fn revert_one(&mut self) -> Option<CommitSet<Key>> {
 		match self.mode {
 			PruningMode::ArchiveAll => Some(CommitSet::default()),
-			PruningMode::ArchiveCanonical | PruningMode::Constrained(_) =>
-				self.non_canonical.revert_one(),
+			PruningMode::ArchiveCanonical |
+			PruningMode::Constrained(_) |
+			PruningMode::Checkpoints { .. } => self.non_canonical.revert_one(),
 		}
 	}
 
 	fn remove(&mut self, hash: &BlockHash) -> Option<CommitSet<Key>> {
 		match self.mode {
 			PruningMode::ArchiveAll => Some(CommitSet::default()),
-			PruningMode::ArchiveCanonical | PruningMode::Constrained(_) =>
-				self.non_canonical.remove(hash),
+			PruningMode::ArchiveCanonical |
+			PruningMode::Constrained(_) |
+			PruningMode::Checkpoints { .. } => self.non_canonical.remove(hash),
 		}
 	}
 
@@ -461,7 +506,9 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 	{
 		match self.mode {
 			PruningMode::ArchiveAll => Ok(()),
-			PruningMode::ArchiveCanonical | PruningMode::Constrained(_) => {
+			PruningMode::ArchiveCanonical |
+			PruningMode::Constrained(_) |
+			PruningMode::Checkpoints { .. } => {
 				let have_block = self.non_canonical.have_block(hash) ||
 					self.pruning.as_ref().map_or_else(
 						|| hint(),
@@ -564,6 +611,11 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 
 			cs.meta.inserted.push((key, value));
 
+			if let PruningMode::Checkpoints { interval, .. } = selected_mode {
+				let key = to_meta_key(PRUNING_CHECKPOINT_INTERVAL, &());
+				cs.meta.inserted.push((key, interval.get().encode()));
+			}
+
 			cs
 		} else {
 			Default::default()
@@ -674,7 +726,21 @@ pub enum IsPruned {
 fn fetch_stored_pruning_mode<D: MetaDb>(db: &D) -> Result<Option<PruningMode>, Error<D::Error>> {
 	let meta_key_mode = to_meta_key(PRUNING_MODE, &());
 	if let Some(stored_mode) = db.get_meta(&meta_key_mode).map_err(Error::Db)? {
-		if let Some(mode) = PruningMode::from_id(&stored_mode) {
+		if stored_mode == PRUNING_MODE_CHECKPOINTS {
+			let meta_key_interval = to_meta_key(PRUNING_CHECKPOINT_INTERVAL, &());
+			let interval = match db.get_meta(&meta_key_interval).map_err(Error::Db)? {
+				Some(interval) => NonZeroU32::new(u32::decode(&mut interval.as_slice())?),
+				None => None,
+			};
+			match interval {
+				Some(interval) =>
+					Ok(Some(PruningMode::Checkpoints { constraints: Default::default(), interval })),
+				None => Err(StateDbError::Metadata(
+					"Invalid or missing checkpoint interval for PRUNING_MODE".into(),
+				)
+				.into()),
+			}
+		} else if let Some(mode) = PruningMode::from_id(&stored_mode) {
 			Ok(Some(mode))
 		} else {
 			Err(StateDbError::Metadata(format!(
@@ -698,6 +764,10 @@ fn choose_pruning_mode(
 			Ok(PruningMode::ArchiveCanonical),
 		(PruningMode::Constrained(_), PruningMode::Constrained(requested)) =>
 			Ok(PruningMode::Constrained(requested)),
+		(
+			PruningMode::Checkpoints { interval: stored, .. },
+			PruningMode::Checkpoints { constraints, interval },
+		) if stored == interval => Ok(PruningMode::Checkpoints { constraints, interval }),
 		(stored, requested) => Err(StateDbError::IncompatiblePruningModes { requested, stored }),
 	}
 }
@@ -709,6 +779,7 @@ mod tests {
 		Constraints, Error, IsPruned, PruningMode, StateDb, StateDbError,
 	};
 	use sp_core::H256;
+	use std::num::NonZeroU32;
 
 	fn make_test_db(settings: PruningMode) -> (TestDb, StateDb<H256, H256, TestDb>) {
 		let mut db = make_db(&[91, 921, 922, 93, 94]);
@@ -847,6 +918,16 @@ mod tests {
 		assert!(db.data_eq(&make_db(&[1, 21, 3, 921, 922, 93, 94])));
 	}
 
+	#[test]
+	fn prune_window_1_with_checkpoints() {
+		let (db, sdb) = make_test_db(PruningMode::checkpoints(1, NonZeroU32::new(2).unwrap()));
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::Pruned);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(21), 2), IsPruned::MaybePruned);
+		assert_ne!(sdb.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::Pruned);
+		// #1 is not a checkpoint, but the nodes it deleted belong to the state before it
+		assert!(db.data_eq(&make_db(&[21, 3, 91, 921, 922, 93, 94])));
+	}
+
 	#[test]
 	fn detects_incompatible_mode() {
 		let mut db = make_db(&[]);
@@ -897,6 +978,8 @@ mod tests {
 
 	#[test]
 	fn pruning_mode_compatibility() {
+		let checkpoints =
+			|n, interval| PruningMode::checkpoints(n, NonZeroU32::new(interval).unwrap());
 		for (created, reopened, expected) in [
 			(None, None, Ok(PruningMode::blocks_pruning(256))),
 			(None, Some(PruningMode::blocks_pruning(256)), Ok(PruningMode::blocks_pruning(256))),
@@ -942,6 +1025,16 @@ mod tests {
 				Some(PruningMode::ArchiveCanonical),
 				Ok(PruningMode::ArchiveCanonical),
 			),
+			(Some(checkpoints(256, 1000)), None, Ok(checkpoints(256, 1000))),
+			(
+				Some(checkpoints(256, 1000)),
+				Some(checkpoints(128, 1000)),
+				Ok(checkpoints(128, 1000)),
+			),
+			(Some(checkpoints(256, 1000)), Some(checkpoints(256, 500)), Err(())),
+			(Some(checkpoints(256, 1000)), Some(PruningMode::blocks_pruning(256)), Err(())),
+			(Some(checkpoints(256, 1000)), Some(PruningMode::ArchiveCanonical), Err(())),
+			(Some(PruningMode::blocks_pruning(256)), Some(checkpoints(256, 1000)), Err(())),
 		] {
 			check_stored_and_requested_mode_compatibility(created, reopened, expected);
 		}
@@ -23,6 +23,14 @@
 //! If a node is re-inserted into the window it gets removed from
 //! the death list.
 //! The changes are journaled in the DB.
+//!
+//! When pruning with checkpoints, the state of every block whose number is a multiple of the
+//! checkpoint interval is kept. Pruning a block after the last pruned checkpoint only deletes the
+//! nodes that were inserted after that checkpoint: each deletion is matched with the last
+//! insertion of the node before the deleting block. The journals of these blocks are kept until
+//! the next checkpoint is pruned, to restore the index of inserted nodes on restart. When the
+//! database doesn't count references, the deleted nodes of a pruned checkpoint are marked in the
+//! metadata, so that they are never deleted once reinserted.
 
 use crate::{
 	noncanonical::LAST_CANONICAL, to_meta_key, CommitSet, Error, Hash, MetaDb, StateDbError,
@@ -30,10 +38,14 @@ use crate::{
 };
 use codec::{Decode, Encode};
 use log::trace;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::{
+	collections::{HashMap, HashSet, VecDeque},
+	num::NonZeroU32,
+};
 
 pub(crate) const LAST_PRUNED: &[u8] = b"last_pruned";
 const PRUNING_JOURNAL: &[u8] = b"pruning_journal";
+const CHECKPOINT_RETAINED: &[u8] = b"checkpoint_retained";
 
 /// See module documentation.
 pub struct RefWindow<BlockHash: Hash, Key: Hash, D: MetaDb> {
@@ -42,6 +54,149 @@ pub struct RefWindow<BlockHash: Hash, Key: Hash, D: MetaDb> {
 	queue: DeathRowQueue<BlockHash, Key, D>,
 	/// Block number that is next to be pruned.
 	base: u64,
+	/// Nodes inserted since the last pruned checkpoint, when pruning with checkpoints.
+	checkpoints: Option<Checkpoints<Key, D>>,
+}
+
+/// Keeps track of the nodes that are not part of the state of the last pruned checkpoint.
+struct Checkpoints<Key: Hash, D: MetaDb> {
+	/// Number of blocks between two checkpoints.
+	interval: u64,
+	/// An index that maps each key inserted after the last pruned checkpoint to the numbers of
+	/// the blocks inserting it, in ascending order.
+	inserted: HashMap<Key, Vec<u64>>,
+	/// The database, if it doesn't count the references of the nodes.
+	///
+	/// A node is then stored once, so the nodes of a pruned checkpoint that are deleted and
+	/// reinserted later are marked in the database, to never count their reinsertion as a new
+	/// node.
+	uncounted_db: Option<D>,
+}
+
+impl<Key: Hash, D: MetaDb> Checkpoints<Key, D> {
+	/// Restore the index from the journals of the blocks after the last pruned checkpoint, up to
+	/// the last canonicalized block.
+	fn new<BlockHash: Hash>(
+		db: &D,
+		interval: NonZeroU32,
+		count_insertions: bool,
+		base: u64,
+		last_canonicalized_number: Option<u64>,
+	) -> Result<Checkpoints<Key, D>, Error<D::Error>> {
+		let mut checkpoints = Checkpoints {
+			interval: interval.get() as u64,
+			inserted: HashMap::new(),
+			uncounted_db: None,
+		};
+		let first = if base == 0 { 0 } else { checkpoints.last_before(base) + 1 };
+		// Keys deleted by the blocks that are not pruned yet.
+		let mut pending_deletions = HashSet::new();
+		if let Some(last) = last_canonicalized_number {
+			for block in first..=last {
+				// Journals may be missing before the target block of a warp sync.
+				if let Some(record) = db.get_meta(&to_journal_key(block)).map_err(Error::Db)? {
+					let record: JournalRecord<BlockHash, Key> =
+						Decode::decode(&mut record.as_slice())?;
+					for key in record.inserted {
+						if count_insertions {
+							if pending_deletions.remove(&key) ||
+								db.get_meta(&to_retained_key(&key)).map_err(Error::Db)?.is_some()
+							{
+								continue
+							}
+						}
+						checkpoints.inserted.entry(key).or_default().push(block);
+					}
+					if block < base {
+						for key in record.deleted {
+							checkpoints.take_inserted(&key, block);
+						}
+					} else {
+						pending_deletions.extend(record.deleted);
+					}
+				}
+			}
+		}
+		trace!(
+			target: LOG_TARGET,
+			"Restored {} keys inserted since #{}",
+			checkpoints.inserted.len(),
+			first,
+		);
+		Ok(checkpoints)
+	}
+
+	/// Is the state of the given block kept?
+	fn is_checkpoint(&self, number: u64) -> bool {
+		number % self.interval == 0
+	}
+
+	/// Returns the last checkpoint before the given block.
+	fn last_before(&self, number: u64) -> u64 {
+		number.saturating_sub(1) / self.interval * self.interval
+	}
+
+	/// Note the keys inserted by the given block.
+	///
+	/// `is_pending_deletion` tells whether a key is deleted by a block that is not pruned yet.
+	/// When the database doesn't count references, inserting such a key revives the stored node,
+	/// so it is not a new node.
+	fn note_inserted(
+		&mut self,
+		number: u64,
+		inserted: &[Key],
+		is_pending_deletion: impl Fn(&Key) -> bool,
+		commit: &mut CommitSet<Key>,
+	) -> Result<(), Error<D::Error>> {
+		for key in inserted {
+			if let Some(ref db) = self.uncounted_db {
+				if is_pending_deletion(key) {
+					// The revived node is a node of the last pruned checkpoint.
+					if !self.inserted.contains_key(key) {
+						commit.meta.inserted.push((to_retained_key(key), Vec::new()));
+					}
+					continue
+				}
+				if db.get_meta(&to_retained_key(key)).map_err(Error::Db)?.is_some() {
+					continue
+				}
+			}
+			self.inserted.entry(key.clone()).or_default().push(number);
+		}
+		Ok(())
+	}
+
+	/// Forget the last insertion of `key` before the block `number`. Returns `false` if the key
+	/// was not inserted after the last pruned checkpoint.
+	fn take_inserted(&mut self, key: &Key, number: u64) -> bool {
+		let Some(blocks) = self.inserted.get_mut(key) else { return false };
+		let Some(index) = blocks.iter().rposition(|block| *block < number) else { return false };
+		blocks.remove(index);
+		if blocks.is_empty() {
+			self.inserted.remove(key);
+		}
+		true
+	}
+
+	/// Prune the given block. Adds the keys that are not part of the state of the last pruned
+	/// checkpoint, and the journals that are no longer needed, to `commit`.
+	fn prune(&mut self, number: u64, deleted: HashSet<Key>, commit: &mut CommitSet<Key>) {
+		for key in deleted {
+			if self.take_inserted(&key, number) {
+				commit.data.deleted.push(key);
+			} else if self.uncounted_db.is_some() {
+				commit.meta.inserted.push((to_retained_key(&key), Vec::new()));
+			}
+		}
+		if self.is_checkpoint(number) {
+			self.inserted.retain(|_, blocks| {
+				blocks.retain(|block| *block > number);
+				!blocks.is_empty()
+			});
+			let first = number.saturating_sub(self.interval - 1);
+			commit.meta.deleted.extend((first..=number).map(to_journal_key));
+		}
+	}
 }
 
 /// `DeathRowQueue` used to keep track of blocks in the pruning window, there are two flavors:
@@ -279,6 +434,10 @@ fn to_journal_key(block: u64) -> Vec<u8> {
 	to_meta_key(PRUNING_JOURNAL, &block)
 }
 
+fn to_retained_key<Key: Hash>(key: &Key) -> Vec<u8> {
+	to_meta_key(CHECKPOINT_RETAINED, key)
+}
+
 /// The result return by `RefWindow::have_block`
 #[derive(Debug, PartialEq, Eq)]
 pub enum HaveBlock {
@@ -305,6 +464,16 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 		db: D,
 		window_size: u32,
 		count_insertions: bool,
+	) -> Result<RefWindow<BlockHash, Key, D>, Error<D::Error>> {
+		Self::new_with_checkpoints(db, window_size, count_insertions, None)
+	}
+
+	/// Create a window that also keeps the state of every `checkpoint_interval`-th block.
+	pub fn new_with_checkpoints(
+		db: D,
+		window_size: u32,
+		count_insertions: bool,
+		checkpoint_interval: Option<NonZeroU32>,
 	) -> Result<RefWindow<BlockHash, Key, D>, Error<D::Error>> {
 		// the block number of the first block in the queue or the next block number if the queue is
 		// empty
@@ -319,6 +488,18 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 				None => None,
 			};
 
+		let mut checkpoints = checkpoint_interval
+			.map(|interval| {
+				Checkpoints::new::<BlockHash>(
+					&db,
+					interval,
+					count_insertions,
+					base,
+					last_canonicalized_number,
+				)
+			})
+			.transpose()?;
+
 		let queue = if count_insertions {
 			// Highly scientific crafted number for deciding when to print the warning!
 			//
@@ -332,7 +513,11 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 				);
 			}
 
-			DeathRowQueue::new_mem(&db, base)?
+			let queue = DeathRowQueue::new_mem(&db, base)?;
+			if let Some(ref mut checkpoints) = checkpoints {
+				checkpoints.uncounted_db = Some(db);
+			}
+			queue
 		} else {
 			let last = match last_canonicalized_number {
 				Some(last_canonicalized_number) => {
@@ -347,7 +532,7 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 			DeathRowQueue::new_db_backed(db, base, last, window_size)?
 		};
 
-		Ok(RefWindow { queue, base })
+		Ok(RefWindow { queue, base, checkpoints })
 	}
 
 	pub fn window_size(&self) -> u64 {
@@ -374,6 +559,12 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 
 	// Check if a block is in the pruning window and not be pruned yet
 	pub fn have_block(&self, hash: &BlockHash, number: u64) -> HaveBlock {
+		// the state of canonical checkpoints is kept, but we don't track their hashes
+		if number < self.base &&
+			self.checkpoints.as_ref().map_or(false, |c| c.is_checkpoint(number))
+		{
+			return HaveBlock::Maybe
+		}
 		// if the queue is empty or the block number exceed the pruning window, we definitely
 		// do not have this block
 		if self.is_empty() || number < self.base || number >= self.base + self.window_size() {
@@ -387,9 +578,14 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 		if let Some(pruned) = self.queue.pop_front(self.base)? {
 			trace!(target: LOG_TARGET, "Pruning {:?} ({} deleted)", pruned.hash, pruned.deleted.len());
 			let index = self.base;
-			commit.data.deleted.extend(pruned.deleted.into_iter());
+			match self.checkpoints {
+				Some(ref mut checkpoints) => checkpoints.prune(index, pruned.deleted, commit),
+				None => {
+					commit.data.deleted.extend(pruned.deleted.into_iter());
+					commit.meta.deleted.push(to_journal_key(self.base));
+				},
+			}
 			commit.meta.inserted.push((to_meta_key(LAST_PRUNED, &()), index.encode()));
-			commit.meta.deleted.push(to_journal_key(self.base));
 			self.base += 1;
 			Ok(())
 		} else {
@@ -424,13 +620,26 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 			commit.data.inserted.len(),
 			commit.data.deleted.len(),
 		);
-		let inserted = if matches!(self.queue, DeathRowQueue::Mem { .. }) {
-			commit.data.inserted.iter().map(|(k, _)| k.clone()).collect()
-		} else {
-			Default::default()
-		};
+		let inserted =
+			if matches!(self.queue, DeathRowQueue::Mem { .. }) || self.checkpoints.is_some() {
+				commit.data.inserted.iter().map(|(k, _)| k.clone()).collect()
+			} else {
+				Default::default()
+			};
 		let deleted = std::mem::take(&mut commit.data.deleted);
 		let journal_record = JournalRecord { hash: hash.clone(), inserted, deleted };
+		if let Some(ref mut checkpoints) = self.checkpoints {
+			let is_pending_deletion = |key: &Key| match &self.queue {
+				DeathRowQueue::Mem { death_index, .. } => death_index.contains_key(key),
+				DeathRowQueue::DbBacked { .. } => false,
+			};
+			checkpoints.note_inserted(
+				number,
+				&journal_record.inserted,
+				is_pending_deletion,
+				commit,
+			)?;
+		}
 		commit.meta.inserted.push((to_journal_key(number), journal_record.encode()));
 		self.queue.import(self.base, number, journal_record);
 		Ok(())
@@ -443,10 +652,11 @@ mod tests {
 	use crate::{
 		noncanonical::LAST_CANONICAL,
 		test::{make_commit, make_db, TestDb},
-		to_meta_key, CommitSet, Error, Hash, StateDbError, DEFAULT_MAX_BLOCK_CONSTRAINT,
+		to_meta_key, CommitSet, Error, Hash, MetaDb, StateDbError, DEFAULT_MAX_BLOCK_CONSTRAINT,
 	};
 	use codec::Encode;
 	use sp_core::H256;
+	use std::num::NonZeroU32;
 
 	fn check_journal(pruning: &RefWindow<H256, H256, TestDb>, db: &TestDb) {
 		let count_insertions = matches!(pruning.queue, DeathRowQueue::Mem { .. });
@@ -907,4 +1117,135 @@ mod tests {
 			assert_eq!(HaveBlock::Yes, pruning.have_block(&block, block));
 		}
 	}
+
+	#[test]
+	fn prune_keeps_checkpoints() {
+		for count_insertions in [true, false] {
+			let mut db = make_db(&[]);
+			let interval = NonZeroU32::new(2);
+			let mut pruning: RefWindow<u64, H256, TestDb> = RefWindow::new_with_checkpoints(
+				db.clone(),
+				DEFAULT_MAX_BLOCK_CONSTRAINT,
+				count_insertions,
+				interval,
+			)
+			.unwrap();
+
+			// checkpoints are #0 with state [1, 2] and #2 with state [2, 4]
+			let changes: [(&[u64], &[u64]); 4] =
+				[(&[1, 2], &[]), (&[3], &[1]), (&[4], &[3]), (&[5], &[2, 4])];
+			for (block, (inserted, deleted)) in changes.into_iter().enumerate() {
+				let mut commit = make_commit(inserted, deleted);
+				pruning.note_canonical(&(block as u64), block as u64, &mut commit).unwrap();
+				push_last_canonicalized(block as u64, &mut commit);
+				db.commit(&commit);
+			}
+
+			for _ in 0..2 {
+				let mut commit = CommitSet::default();
+				pruning.prune_one(&mut commit).unwrap();
+				db.commit(&commit);
+			}
+			assert!(db.data_eq(&make_db(&[1, 2, 3, 4, 5])));
+
+			// the nodes inserted after the last pruned checkpoint are restored on restart
+			let restored: RefWindow<u64, H256, TestDb> = RefWindow::new_with_checkpoints(
+				db.clone(),
+				DEFAULT_MAX_BLOCK_CONSTRAINT,
+				count_insertions,
+				interval,
+			)
+			.unwrap();
+			assert_eq!(
+				pruning.checkpoints.as_ref().unwrap().inserted,
+				restored.checkpoints.as_ref().unwrap().inserted,
+			);
+
+			for _ in 0..2 {
+				let mut commit = CommitSet::default();
+				pruning.prune_one(&mut commit).unwrap();
+				db.commit(&commit);
+			}
+			assert!(db.data_eq(&make_db(&[1, 2, 4, 5])));
+			assert_eq!(pruning.have_block(&0, 0), HaveBlock::Maybe);
+			assert_eq!(pruning.have_block(&1, 1), HaveBlock::No);
+			assert_eq!(pruning.have_block(&2, 2), HaveBlock::Maybe);
+			assert_eq!(pruning.have_block(&3, 3), HaveBlock::No);
+			// only the journal of the block after the last pruned checkpoint is left
+			assert!(db.get_meta(&to_journal_key(2)).unwrap().is_none());
+			assert!(db.get_meta(&to_journal_key(3)).unwrap().is_some());
+		}
+	}
+
+	#[test]
+	fn prune_keeps_checkpoints_with_reinserted_keys() {
+		for count_insertions in [true, false] {
+			let mut db = make_db(&[]);
+			let interval = NonZeroU32::new(4);
+			let mut pruning: RefWindow<u64, H256, TestDb> = RefWindow::new_with_checkpoints(
+				db.clone(),
+				DEFAULT_MAX_BLOCK_CONSTRAINT,
+				count_insertions,
+				interval,
+			)
+			.unwrap();
+
+			// 1 is part of the checkpoint #0 and 6 is inserted after the checkpoint #4, both are
+			// deleted, reinserted and deleted again
+			let changes: [(&[u64], &[u64]); 9] = [
+				(&[1, 2], &[]),
+				(&[3], &[1]),
+				(&[1], &[3]),
+				(&[4], &[1]),
+				(&[5], &[2]),
+				(&[6], &[]),
+				(&[], &[6]),
+				(&[6], &[]),
+				(&[7], &[6]),
+			];
+			for (block, (inserted, deleted)) in changes.into_iter().enumerate() {
+				let mut commit = make_commit(inserted, deleted);
+				pruning.note_canonical(&(block as u64), block as u64, &mut commit).unwrap();
+				push_last_canonicalized(block as u64, &mut commit);
+				db.commit(&commit);
+			}
+
+			let mut deleted = Vec::new();
+			let mut prune = |pruning: &mut RefWindow<u64, H256, TestDb>, db: &mut TestDb| {
+				let mut commit = CommitSet::default();
+				pruning.prune_one(&mut commit).unwrap();
+				deleted.extend(commit.data.deleted.iter().map(|k| k.to_low_u64_be()));
+				db.commit(&commit);
+			};
+			for _ in 0..5 {
+				prune(&mut pruning, &mut db);
+			}
+
+			// the nodes inserted after the last pruned checkpoint are restored on restart
+			let restored: RefWindow<u64, H256, TestDb> = RefWindow::new_with_checkpoints(
+				db.clone(),
+				DEFAULT_MAX_BLOCK_CONSTRAINT,
+				count_insertions,
+				interval,
+			)
+			.unwrap();
+			assert_eq!(
+				pruning.checkpoints.as_ref().unwrap().inserted,
+				restored.checkpoints.as_ref().unwrap().inserted,
+			);
+
+			for _ in 0..4 {
+				prune(&mut pruning, &mut db);
+			}
+			if count_insertions {
+				// the database stores each node once, deleting a node and reinserting it before
+				// the deletion is pruned only keeps it
+				assert_eq!(deleted, vec![3, 6]);
+				assert!(db.data_eq(&make_db(&[1, 2, 4, 5, 7])));
+			} else {
+				// the database counts references, each insertion after a checkpoint is deleted
+				assert_eq!(deleted, vec![3, 1, 6, 6]);
+			}
+		}
+	}
 }
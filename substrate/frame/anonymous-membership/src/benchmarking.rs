@@ -0,0 +1,173 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the anonymous membership pallet.
+
+use crate::*;
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::{EnsureOrigin, Get, UnfilteredDispatchable};
+use frame_system::RawOrigin;
+use sp_runtime::{traits::ValidateUnsigned, transaction_validity::TransactionSource};
+
+/// Members, context and actions signed using the testing ring context.
+///
+/// Contains one action for each message length in `0..=64`, see the `make_actions_data` test.
+const ACTIONS_DATA: &[u8] = include_bytes!("data/16_members_actions.bin");
+
+type ActionsData = (Vec<MemberId>, ContextId, Vec<(Alias, ring_vrf::RingVrfSignature)>);
+
+/// Get the ring context, constructing the testing one if the runtime has none.
+///
+/// The pre-built actions are verifiable only if the ring context is the testing one.
+fn ring_context<T: Config>() -> ring_vrf::RingContext<RING_SIZE> {
+	RingContext::<T>::get().unwrap_or_else(|| {
+		let ring_ctx = ring_vrf::RingContext::<RING_SIZE>::new_testing();
+		RingContext::<T>::put(&ring_ctx);
+		ring_ctx
+	})
+}
+
+/// Register `count` freshly generated members.
+fn set_members<T: Config>(count: u32) -> MembersVec<T> {
+	let mut members: Vec<_> = (0..count)
+		.map(|_| sp_io::crypto::bandersnatch_generate(sp_core::testing::BANDERSNATCH, None))
+		.collect();
+	members.sort();
+	members.dedup();
+	let members = MembersVec::<T>::truncate_from(members);
+	Members::<T>::put(&members);
+	members
+}
+
+/// Open `context` and fill it with `count` dummy aliases.
+fn set_context<T: Config>(context: ContextId, count: u32) {
+	Contexts::<T>::insert(context, count);
+	(0..count).for_each(|i| {
+		let alias = sp_io::hashing::blake2_256(&i.to_le_bytes());
+		Aliases::<T>::insert(context, alias, ());
+	});
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn add_member(m: Linear<0, { T::MaxMembers::get() - 1 }>) -> Result<(), BenchmarkError> {
+		let _ = ring_context::<T>();
+		set_members::<T>(m);
+		let member = sp_io::crypto::bandersnatch_generate(sp_core::testing::BANDERSNATCH, None);
+		let origin =
+			T::AddOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, member);
+
+		assert!(Members::<T>::get().contains(&member));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_member(m: Linear<1, { T::MaxMembers::get() }>) -> Result<(), BenchmarkError> {
+		let _ = ring_context::<T>();
+		let members = set_members::<T>(m);
+		let member = members[members.len() - 1];
+		let origin =
+			T::RemoveOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, member);
+
+		assert!(!Members::<T>::get().contains(&member));
+		Ok(())
+	}
+
+	// Accounts for both the unsigned transaction validation, where the ring proof is
+	// checked, and the dispatch.
+	#[benchmark]
+	fn anonymous_action(l: Linear<0, 64>) -> Result<(), BenchmarkError> {
+		let (members, context, actions): ActionsData =
+			Decode::decode(&mut &ACTIONS_DATA[..]).expect("Failed to decode actions data");
+
+		let ring_ctx = ring_context::<T>();
+		let members = MembersVec::<T>::try_from(members).map_err(|_| {
+			BenchmarkError::Stop("`MaxMembers` is smaller than the pre-built members count")
+		})?;
+		RingVerifierData::<T>::put(ring_ctx.verifier_key(&members));
+		Members::<T>::put(members);
+		// Worst case: the action is the last one allowed within the context.
+		set_context::<T>(context, T::MaxMembers::get() - 1);
+
+		let (alias, signature) = actions[l as usize].clone();
+		let message = BoundedVec::try_from(alloc::vec![1; l as usize])
+			.map_err(|_| BenchmarkError::Stop("`MaxMessageLen` is too small"))?;
+		let call = Call::<T>::anonymous_action { context, alias, message, signature };
+
+		#[block]
+		{
+			Pallet::<T>::validate_unsigned(TransactionSource::External, &call)
+				.map_err(<&str>::from)?;
+			call.dispatch_bypass_filter(RawOrigin::None.into())?;
+		}
+
+		assert!(Aliases::<T>::contains_key(context, alias));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn open_context() -> Result<(), BenchmarkError> {
+		let context = [1; 32];
+		let origin =
+			T::ContextOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, context);
+
+		assert!(Contexts::<T>::contains_key(context));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn close_context(a: Linear<0, { T::MaxMembers::get() }>) -> Result<(), BenchmarkError> {
+		let context = [1; 32];
+		set_context::<T>(context, a);
+		let origin =
+			T::ContextOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, context);
+
+		assert!(!Contexts::<T>::contains_key(context));
+		assert_eq!(Aliases::<T>::iter_prefix(context).count(), 0);
+		assert!(ClosedContexts::<T>::contains_key(context));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_ring_context(m: Linear<0, { T::MaxMembers::get() }>) {
+		let ring_context = ring_context::<T>().encode();
+		set_members::<T>(m);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, ring_context);
+
+		assert!(RingVerifierData::<T>::get().is_some());
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(0, false), crate::mock::Test);
+}
@@ -0,0 +1,493 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Anonymous membership pallet.
+//!
+//! The pallet keeps a set of registered Bandersnatch public keys and publishes the
+//! [`RingVerifierKey`](ring_vrf::RingVerifierKey) built out of them via
+//! [`RingContext::verifier_key`](ring_vrf::RingContext::verifier_key).
+//!
+//! Members can anonymously act by submitting an unsigned extrinsic carrying a ring VRF
+//! signature, which proves that the signer is one of the registered members without
+//! revealing which one.
+//!
+//! Each action is bound to a *context* (e.g. a referendum or an airdrop identifier).
+//! Contexts are opened and closed by [`Config::ContextOrigin`], and actions can only be
+//! performed within open contexts. The VRF input is derived from the context only, thus the VRF
+//! output (the *alias*) is the same for every action performed by one member within one context,
+//! while aliases of the same member are unlinkable across different contexts. Aliases
+//! which have already been used are recorded and can't be used again within the same
+//! context, allowing "one member, one action" schemes. As a consequence, the number of
+//! aliases recorded for a context is bounded by [`Config::MaxMembers`]. The aliases are
+//! removed when the context is closed, thus a closed context can't be opened again.
+//!
+//! The actual action payload is an opaque message which is signed together with the
+//! VRF input and is handed over to [`Config::OnAction`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use frame_support::{dispatch::DispatchResult, BoundedVec};
+use log::{debug, warn};
+use sp_core::bandersnatch::{ring_vrf, vrf};
+
+#[cfg(all(feature = "std", test))]
+mod mock;
+#[cfg(all(feature = "std", test))]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+pub use pallet::*;
+
+const LOG_TARGET: &str = "runtime::anonymous-membership";
+
+/// Domain separator for the alias VRF input.
+const ALIAS_INPUT_DOMAIN: &[u8] = b"anonymous-membership-alias";
+
+/// Max number of members supported by the ring verifier.
+pub const RING_SIZE: usize = 1024;
+
+/// Member identifier.
+pub type MemberId = sp_core::bandersnatch::Public;
+
+/// Context identifier.
+pub type ContextId = [u8; 32];
+
+/// Member alias within a context.
+pub type Alias = [u8; 32];
+
+/// Members bounded vector convenience type.
+pub type MembersVec<T> = BoundedVec<MemberId, <T as Config>::MaxMembers>;
+
+/// Handler of the anonymous actions.
+pub trait OnAnonymousAction {
+	/// Called when a member anonymously acts within `context`.
+	///
+	/// When this is called the membership proof has already been verified and the
+	/// `alias` has not been used before within `context`.
+	fn on_anonymous_action(context: &ContextId, alias: &Alias, message: &[u8]) -> DispatchResult;
+}
+
+impl OnAnonymousAction for () {
+	fn on_anonymous_action(_: &ContextId, _: &Alias, _: &[u8]) -> DispatchResult {
+		Ok(())
+	}
+}
+
+/// VRF input data used to derive the members aliases for the given `context`.
+pub fn alias_input_data(context: &ContextId) -> Vec<u8> {
+	(ALIAS_INPUT_DOMAIN, context).encode()
+}
+
+/// Data to be signed by a member to act within the given `context`.
+pub fn action_sign_data(context: &ContextId, message: &[u8]) -> vrf::VrfSignData {
+	vrf::VrfSignData::new(&alias_input_data(context), message)
+}
+
+/// Alias corresponding to the given VRF pre-output.
+pub fn make_alias(pre_output: &vrf::VrfPreOutput) -> Alias {
+	pre_output.make_bytes()
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		dispatch::{DispatchResultWithPostInfo, Pays},
+		pallet_prelude::*,
+	};
+	use frame_system::pallet_prelude::*;
+
+	/// The anonymous membership pallet.
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configuration parameters.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Required origin for adding a member.
+		type AddOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Required origin for removing a member.
+		type RemoveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Required origin for opening and closing a context.
+		type ContextOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Max number of members.
+		///
+		/// Must not be greater than [`RING_SIZE`].
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+
+		/// Max length of the message attached to an anonymous action.
+		#[pallet::constant]
+		type MaxMessageLen: Get<u32>;
+
+		/// Priority of the unsigned anonymous action transactions.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// Number of blocks an anonymous action transaction stays valid in the pool.
+		#[pallet::constant]
+		type UnsignedLongevity: Get<TransactionLongevity>;
+
+		/// Handler of the anonymous actions.
+		type OnAction: OnAnonymousAction;
+
+		/// Weight information for all calls of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Anonymous membership events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A member has been added.
+		MemberAdded { member: MemberId },
+		/// A member has been removed.
+		MemberRemoved { member: MemberId },
+		/// Some member anonymously acted within a context.
+		AnonymousAction { context: ContextId, alias: Alias },
+		/// A context has been opened.
+		ContextOpened { context: ContextId },
+		/// A context has been closed.
+		ContextClosed { context: ContextId },
+		/// The ring context has been set.
+		RingContextSet,
+	}
+
+	/// Anonymous membership errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Key is already a member.
+		AlreadyMember,
+		/// Key is not a member.
+		NotMember,
+		/// Too many members.
+		TooManyMembers,
+		/// Alias has already been used within the context.
+		AliasAlreadyUsed,
+		/// Alias doesn't match the signature VRF pre-output.
+		BadAlias,
+		/// Context is not open.
+		UnknownContext,
+		/// Context is already open.
+		ContextAlreadyOpen,
+		/// Context has been closed and can't be opened again.
+		ContextAlreadyClosed,
+		/// Too many actions within the context.
+		TooManyActions,
+		/// Ring context can't be decoded.
+		BadRingContext,
+	}
+
+	/// Registered members, sorted.
+	#[pallet::storage]
+	pub type Members<T: Config> = StorageValue<_, MembersVec<T>, ValueQuery>;
+
+	/// Parameters used to construct the ring verifier.
+	///
+	/// In practice: Updatable Universal Reference String and the seed.
+	#[pallet::storage]
+	pub type RingContext<T: Config> = StorageValue<_, ring_vrf::RingContext<RING_SIZE>>;
+
+	/// Ring verifier data for the current members set.
+	#[pallet::storage]
+	pub type RingVerifierData<T: Config> = StorageValue<_, ring_vrf::RingVerifierKey>;
+
+	/// Open contexts, along with the number of actions performed within each of them.
+	#[pallet::storage]
+	pub type Contexts<T: Config> = StorageMap<_, Blake2_128Concat, ContextId, u32>;
+
+	/// Closed contexts.
+	#[pallet::storage]
+	pub type ClosedContexts<T: Config> = StorageMap<_, Blake2_128Concat, ContextId, ()>;
+
+	/// Aliases already used within each context.
+	#[pallet::storage]
+	pub type Aliases<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, ContextId, Blake2_128Concat, Alias, ()>;
+
+	/// Genesis configuration for the anonymous membership pallet.
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// Genesis members.
+		pub members: Vec<MemberId>,
+		/// Phantom config
+		#[serde(skip)]
+		pub _phantom: core::marker::PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			let mut members = self.members.clone();
+			members.sort();
+			members.dedup();
+			let members = MembersVec::<T>::try_from(members)
+				.expect("Genesis members count must not exceed `MaxMembers`");
+			Members::<T>::put(&members);
+
+			#[cfg(feature = "construct-dummy-ring-context")]
+			{
+				debug!(target: LOG_TARGET, "Constructing dummy ring context");
+				let ring_ctx = ring_vrf::RingContext::<RING_SIZE>::new_testing();
+				RingContext::<T>::put(ring_ctx);
+			}
+
+			Pallet::<T>::update_ring_verifier(&members);
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(
+				T::MaxMembers::get() as usize <= RING_SIZE,
+				"`MaxMembers` must not be greater than the ring size"
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add a member.
+		///
+		/// The ring verifier key is rebuilt to include the new member.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::add_member(T::MaxMembers::get()))]
+		pub fn add_member(origin: OriginFor<T>, member: MemberId) -> DispatchResultWithPostInfo {
+			T::AddOrigin::ensure_origin(origin)?;
+
+			let mut members = Members::<T>::get();
+			let pos = members.binary_search(&member).err().ok_or(Error::<T>::AlreadyMember)?;
+			members.try_insert(pos, member).map_err(|_| Error::<T>::TooManyMembers)?;
+
+			Self::update_ring_verifier(&members);
+			let members_count = members.len() as u32;
+			Members::<T>::put(members);
+
+			Self::deposit_event(Event::MemberAdded { member });
+			Ok(Some(T::WeightInfo::add_member(members_count)).into())
+		}
+
+		/// Remove a member.
+		///
+		/// The ring verifier key is rebuilt to exclude the removed member.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::remove_member(T::MaxMembers::get()))]
+		pub fn remove_member(origin: OriginFor<T>, member: MemberId) -> DispatchResultWithPostInfo {
+			T::RemoveOrigin::ensure_origin(origin)?;
+
+			let mut members = Members::<T>::get();
+			let pos = members.binary_search(&member).ok().ok_or(Error::<T>::NotMember)?;
+			members.remove(pos);
+
+			Self::update_ring_verifier(&members);
+			let members_count = members.len() as u32;
+			Members::<T>::put(members);
+
+			Self::deposit_event(Event::MemberRemoved { member });
+			Ok(Some(T::WeightInfo::remove_member(members_count)).into())
+		}
+
+		/// Anonymously act within `context`.
+		///
+		/// The `signature` must be a ring VRF signature of the data constructed via
+		/// [`action_sign_data`] for `context` and `message`, produced by one of the members.
+		/// The `alias` must correspond to the signature VRF pre-output.
+		///
+		/// The membership proof is checked by [`ValidateUnsigned`], which is also called
+		/// before the dispatch of the extrinsic when included in a block.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::anonymous_action(message.len() as u32))]
+		pub fn anonymous_action(
+			origin: OriginFor<T>,
+			context: ContextId,
+			alias: Alias,
+			message: BoundedVec<u8, T::MaxMessageLen>,
+			signature: ring_vrf::RingVrfSignature,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			// The ring proof has already been checked by `ValidateUnsigned`.
+			ensure!(make_alias(&signature.pre_output) == alias, Error::<T>::BadAlias);
+			let actions = Contexts::<T>::get(context).ok_or(Error::<T>::UnknownContext)?;
+			ensure!(actions < T::MaxMembers::get(), Error::<T>::TooManyActions);
+			ensure!(!Aliases::<T>::contains_key(context, alias), Error::<T>::AliasAlreadyUsed);
+			Aliases::<T>::insert(context, alias, ());
+			Contexts::<T>::insert(context, actions + 1);
+
+			T::OnAction::on_anonymous_action(&context, &alias, &message)?;
+
+			Self::deposit_event(Event::AnonymousAction { context, alias });
+			Ok(Pays::No.into())
+		}
+
+		/// Open a context.
+		///
+		/// Members can anonymously act only within open contexts. Contexts which have been
+		/// closed can't be opened again.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::open_context())]
+		pub fn open_context(origin: OriginFor<T>, context: ContextId) -> DispatchResult {
+			T::ContextOrigin::ensure_origin(origin)?;
+
+			ensure!(!Contexts::<T>::contains_key(context), Error::<T>::ContextAlreadyOpen);
+			ensure!(!ClosedContexts::<T>::contains_key(context), Error::<T>::ContextAlreadyClosed);
+			Contexts::<T>::insert(context, 0);
+
+			Self::deposit_event(Event::ContextOpened { context });
+			Ok(())
+		}
+
+		/// Close a context.
+		///
+		/// The aliases used within the context are removed and the context is recorded as
+		/// closed, so that it can't be opened again.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::close_context(T::MaxMembers::get()))]
+		pub fn close_context(
+			origin: OriginFor<T>,
+			context: ContextId,
+		) -> DispatchResultWithPostInfo {
+			T::ContextOrigin::ensure_origin(origin)?;
+
+			let actions = Contexts::<T>::take(context).ok_or(Error::<T>::UnknownContext)?;
+			// At most `MaxMembers` aliases are recorded for a context.
+			let _ = Aliases::<T>::clear_prefix(context, actions, None);
+			ClosedContexts::<T>::insert(context, ());
+
+			Self::deposit_event(Event::ContextClosed { context });
+			Ok(Some(T::WeightInfo::close_context(actions)).into())
+		}
+
+		/// Set the ring context.
+		///
+		/// The `ring_context` is the SCALE encoded [`ring_vrf::RingContext`]. The ring
+		/// verifier key is rebuilt for the current members.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_ring_context(T::MaxMembers::get()))]
+		pub fn set_ring_context(
+			origin: OriginFor<T>,
+			ring_context: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let ring_ctx = ring_vrf::RingContext::<RING_SIZE>::decode(&mut &ring_context[..])
+				.map_err(|_| Error::<T>::BadRingContext)?;
+			RingContext::<T>::put(ring_ctx);
+
+			let members = Members::<T>::get();
+			Self::update_ring_verifier(&members);
+
+			Self::deposit_event(Event::RingContextSet);
+			Ok(Some(T::WeightInfo::set_ring_context(members.len() as u32)).into())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::anonymous_action { context, alias, message, signature } = call else {
+				return InvalidTransaction::Call.into()
+			};
+
+			let Some(actions) = Contexts::<T>::get(context) else {
+				debug!(target: LOG_TARGET, "Unknown context");
+				return InvalidTransaction::Call.into()
+			};
+
+			if actions >= T::MaxMembers::get() {
+				debug!(target: LOG_TARGET, "Too many actions within context");
+				return InvalidTransaction::ExhaustsResources.into()
+			}
+
+			if Aliases::<T>::contains_key(context, alias) {
+				debug!(target: LOG_TARGET, "Alias already used within context");
+				return InvalidTransaction::Stale.into()
+			}
+
+			if !Self::verify_action(context, alias, message, signature) {
+				debug!(target: LOG_TARGET, "Anonymous action proof verification failure");
+				return InvalidTransaction::BadProof.into()
+			}
+
+			ValidTransaction::with_tag_prefix("AnonymousMembership")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((context, alias))
+				.longevity(T::UnsignedLongevity::get())
+				.propagate(true)
+				.build()
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Check that an anonymous action has been signed by one of the current members.
+	pub fn verify_action(
+		context: &ContextId,
+		alias: &Alias,
+		message: &[u8],
+		signature: &ring_vrf::RingVrfSignature,
+	) -> bool {
+		if make_alias(&signature.pre_output) != *alias {
+			return false
+		}
+
+		let Some(verifier_key) = RingVerifierData::<T>::get() else {
+			warn!(target: LOG_TARGET, "Ring verifier key not initialized");
+			return false
+		};
+
+		sp_io::crypto::bandersnatch_ring_vrf_verify(
+			&alias_input_data(context),
+			message,
+			&signature.encode(),
+			&verifier_key.encode(),
+			RING_SIZE as u32,
+		)
+	}
+
+	/// Rebuild the ring verifier key for the given members set.
+	pub(crate) fn update_ring_verifier(members: &[MemberId]) {
+		debug!(target: LOG_TARGET, "Loading ring context");
+		let Some(ring_ctx) = RingContext::<T>::get() else {
+			debug!(target: LOG_TARGET, "Ring context not initialized");
+			return
+		};
+
+		debug!(target: LOG_TARGET, "Building ring verifier (ring size: {})", members.len());
+		let verifier_data = ring_ctx.verifier_key(members);
+
+		RingVerifierData::<T>::put(verifier_data);
+	}
+}
@@ -0,0 +1,116 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test utilities for anonymous membership pallet.
+
+use crate::{self as pallet_anonymous_membership, *};
+
+use frame_support::{
+	derive_impl,
+	traits::{ConstU32, ConstU64},
+};
+use frame_system::EnsureRoot;
+use sp_core::{bandersnatch::Pair, crypto::Pair as _};
+use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
+use sp_runtime::BuildStorage;
+
+const LOG_TARGET: &str = "anonymous-membership::tests";
+
+pub const MAX_MEMBERS: u32 = 16;
+pub const UNSIGNED_PRIORITY: u64 = 1 << 20;
+pub const UNSIGNED_LONGEVITY: u64 = 64;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = frame_system::mocking::MockBlock<Test>;
+}
+
+impl pallet_anonymous_membership::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AddOrigin = EnsureRoot<u64>;
+	type RemoveOrigin = EnsureRoot<u64>;
+	type ContextOrigin = EnsureRoot<u64>;
+	type MaxMembers = ConstU32<MAX_MEMBERS>;
+	type MaxMessageLen = ConstU32<64>;
+	type UnsignedPriority = ConstU64<UNSIGNED_PRIORITY>;
+	type UnsignedLongevity = ConstU64<UNSIGNED_LONGEVITY>;
+	type OnAction = ();
+	type WeightInfo = ();
+}
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		AnonymousMembership: pallet_anonymous_membership,
+	}
+);
+
+/// Deterministic member key pair.
+pub fn member_pair(i: u8) -> Pair {
+	Pair::from_seed(&[i; 32])
+}
+
+/// Build and returns test storage externalities with `members_count` genesis members.
+///
+/// If `with_ring_context` is set, then a testing ring context is constructed and
+/// the ring verifier key for the genesis members is built.
+pub fn new_test_ext(members_count: u8, with_ring_context: bool) -> sp_io::TestExternalities {
+	let members = (0..members_count).map(|i| member_pair(i).public()).collect();
+
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+
+	pallet_anonymous_membership::GenesisConfig::<Test> {
+		members,
+		_phantom: core::marker::PhantomData,
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		if with_ring_context {
+			log::debug!(target: LOG_TARGET, "Building testing ring context");
+			let ring_ctx = ring_vrf::RingContext::<RING_SIZE>::new_testing();
+			RingContext::<Test>::put(ring_ctx);
+			Pallet::<Test>::update_ring_verifier(&Members::<Test>::get());
+		}
+	});
+
+	ext
+}
+
+/// Anonymous action call signed by the given member pair.
+pub fn make_action_call(pair: &Pair, context: ContextId, message: &[u8]) -> Call<Test> {
+	let members = Members::<Test>::get();
+	let ring_ctx = RingContext::<Test>::get().unwrap();
+	// Non members can still produce a (useless) signature.
+	let idx = members.iter().position(|m| *m == pair.public()).unwrap_or_default();
+	let prover = ring_ctx.prover(&members, idx);
+
+	let signature = pair.ring_vrf_sign(&action_sign_data(&context, message), &prover);
+	let alias = make_alias(&signature.pre_output);
+
+	Call::anonymous_action {
+		context,
+		alias,
+		message: message.to_vec().try_into().unwrap(),
+		signature,
+	}
+}
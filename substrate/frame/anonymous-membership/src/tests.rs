@@ -0,0 +1,327 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for anonymous membership pallet.
+
+use crate::{mock::*, *};
+
+use frame_support::{
+	assert_noop, assert_ok, dispatch::DispatchResultWithPostInfo, pallet_prelude::*,
+	traits::UnfilteredDispatchable,
+};
+use frame_system::RawOrigin;
+use sp_core::crypto::Pair as _;
+
+fn validate(call: &Call<Test>) -> TransactionValidity {
+	<Pallet<Test> as ValidateUnsigned>::validate_unsigned(TransactionSource::External, call)
+}
+
+fn dispatch(call: Call<Test>) -> DispatchResultWithPostInfo {
+	// Mimic block inclusion.
+	<Pallet<Test> as ValidateUnsigned>::pre_dispatch(&call).map_err(|_| "pre-dispatch failure")?;
+	call.dispatch_bypass_filter(RawOrigin::None.into())
+}
+
+#[test]
+fn genesis_members_are_sorted_and_deduplicated() {
+	new_test_ext(4, false).execute_with(|| {
+		let members = Members::<Test>::get();
+		assert_eq!(members.len(), 4);
+		assert!(members.windows(2).all(|w| w[0] < w[1]));
+		// No ring context, no verifier.
+		assert!(RingVerifierData::<Test>::get().is_none());
+	});
+}
+
+#[test]
+fn add_and_remove_members_works() {
+	new_test_ext(2, true).execute_with(|| {
+		let vk0 = RingVerifierData::<Test>::get().unwrap().encode();
+
+		let new_member = member_pair(10).public();
+		assert_noop!(
+			AnonymousMembership::add_member(RuntimeOrigin::signed(1), new_member),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(AnonymousMembership::add_member(RuntimeOrigin::root(), new_member));
+		assert_noop!(
+			AnonymousMembership::add_member(RuntimeOrigin::root(), new_member),
+			Error::<Test>::AlreadyMember
+		);
+		assert_eq!(Members::<Test>::get().len(), 3);
+		System::assert_last_event(Event::MemberAdded { member: new_member }.into());
+
+		let vk1 = RingVerifierData::<Test>::get().unwrap().encode();
+		assert_ne!(vk0, vk1);
+
+		assert_ok!(AnonymousMembership::remove_member(RuntimeOrigin::root(), new_member));
+		assert_noop!(
+			AnonymousMembership::remove_member(RuntimeOrigin::root(), new_member),
+			Error::<Test>::NotMember
+		);
+		System::assert_last_event(Event::MemberRemoved { member: new_member }.into());
+
+		// Same members set, same verifier key.
+		let vk2 = RingVerifierData::<Test>::get().unwrap().encode();
+		assert_eq!(vk0, vk2);
+	});
+}
+
+#[test]
+fn add_members_is_bounded() {
+	new_test_ext(MAX_MEMBERS as u8, false).execute_with(|| {
+		assert_noop!(
+			AnonymousMembership::add_member(RuntimeOrigin::root(), member_pair(100).public()),
+			Error::<Test>::TooManyMembers
+		);
+	});
+}
+
+fn open_context(context: ContextId) {
+	assert_ok!(AnonymousMembership::open_context(RuntimeOrigin::root(), context));
+}
+
+#[test]
+fn anonymous_action_works() {
+	new_test_ext(4, true).execute_with(|| {
+		let context = [1; 32];
+		open_context(context);
+		open_context([2; 32]);
+		let call = make_action_call(&member_pair(2), context, b"yes");
+		let Call::anonymous_action { alias, .. } = call.clone() else { unreachable!() };
+
+		let valid = validate(&call).unwrap();
+		assert_eq!(valid.priority, UNSIGNED_PRIORITY);
+		assert_eq!(valid.longevity, UNSIGNED_LONGEVITY);
+		assert_ok!(dispatch(call.clone()));
+		assert_eq!(Contexts::<Test>::get(context), Some(1));
+		assert!(Aliases::<Test>::contains_key(context, alias));
+		System::assert_last_event(Event::AnonymousAction { context, alias }.into());
+
+		// Replay is not allowed.
+		assert_eq!(validate(&call), InvalidTransaction::Stale.into());
+
+		// The same member gets the same alias within the same context...
+		let call = make_action_call(&member_pair(2), context, b"no");
+		assert_eq!(validate(&call), InvalidTransaction::Stale.into());
+		assert_noop!(
+			call.dispatch_bypass_filter(RawOrigin::None.into()),
+			Error::<Test>::AliasAlreadyUsed
+		);
+
+		// ...but a different one within another context.
+		let call = make_action_call(&member_pair(2), [2; 32], b"yes");
+		let Call::anonymous_action { alias: other_alias, .. } = call.clone() else {
+			unreachable!()
+		};
+		assert_ne!(alias, other_alias);
+		assert_ok!(validate(&call));
+
+		// Other members can act within the first context.
+		let call = make_action_call(&member_pair(3), context, b"yes");
+		assert_ok!(dispatch(call));
+	});
+}
+
+#[test]
+fn anonymous_action_with_bad_proof_fails() {
+	new_test_ext(4, true).execute_with(|| {
+		let context = [1; 32];
+		open_context(context);
+
+		// Not a member.
+		let call = make_action_call(&member_pair(42), context, b"yes");
+		assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+
+		// Tampered message.
+		let Call::anonymous_action { alias, signature, .. } =
+			make_action_call(&member_pair(1), context, b"yes")
+		else {
+			unreachable!()
+		};
+		let call = Call::anonymous_action {
+			context,
+			alias,
+			message: b"no".to_vec().try_into().unwrap(),
+			signature: signature.clone(),
+		};
+		assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+
+		// Alias not matching the signature.
+		let call = Call::anonymous_action {
+			context,
+			alias: [0; 32],
+			message: b"yes".to_vec().try_into().unwrap(),
+			signature: signature.clone(),
+		};
+		assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+		assert_noop!(call.dispatch_bypass_filter(RawOrigin::None.into()), Error::<Test>::BadAlias);
+
+		// Removed members can't act anymore.
+		let call = make_action_call(&member_pair(1), context, b"yes");
+		let member = member_pair(1).public();
+		assert_ok!(AnonymousMembership::remove_member(RuntimeOrigin::root(), member));
+		assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+	});
+}
+
+#[test]
+fn anonymous_action_requires_open_context() {
+	new_test_ext(4, true).execute_with(|| {
+		let context = [1; 32];
+		let call = make_action_call(&member_pair(1), context, b"yes");
+
+		assert_eq!(validate(&call), InvalidTransaction::Call.into());
+		assert_noop!(
+			call.clone().dispatch_bypass_filter(RawOrigin::None.into()),
+			Error::<Test>::UnknownContext
+		);
+
+		assert_noop!(
+			AnonymousMembership::open_context(RuntimeOrigin::signed(1), context),
+			DispatchError::BadOrigin
+		);
+		open_context(context);
+		System::assert_last_event(Event::ContextOpened { context }.into());
+		assert_noop!(
+			AnonymousMembership::open_context(RuntimeOrigin::root(), context),
+			Error::<Test>::ContextAlreadyOpen
+		);
+
+		assert_ok!(validate(&call));
+		assert_ok!(dispatch(call));
+	});
+}
+
+#[test]
+fn close_context_removes_aliases() {
+	new_test_ext(4, true).execute_with(|| {
+		let context = [1; 32];
+		open_context(context);
+		(0..3).for_each(|i| {
+			assert_ok!(dispatch(make_action_call(&member_pair(i), context, b"yes")));
+		});
+		assert_eq!(Aliases::<Test>::iter_prefix(context).count(), 3);
+
+		assert_noop!(
+			AnonymousMembership::close_context(RuntimeOrigin::signed(1), context),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(AnonymousMembership::close_context(RuntimeOrigin::root(), context));
+		System::assert_last_event(Event::ContextClosed { context }.into());
+		assert!(!Contexts::<Test>::contains_key(context));
+		assert_eq!(Aliases::<Test>::iter_prefix(context).count(), 0);
+
+		assert_noop!(
+			AnonymousMembership::close_context(RuntimeOrigin::root(), context),
+			Error::<Test>::UnknownContext
+		);
+		let call = make_action_call(&member_pair(3), context, b"yes");
+		assert_eq!(validate(&call), InvalidTransaction::Call.into());
+	});
+}
+
+#[test]
+fn closed_context_cannot_be_reopened() {
+	new_test_ext(4, true).execute_with(|| {
+		let context = [1; 32];
+		open_context(context);
+		let call = make_action_call(&member_pair(0), context, b"yes");
+		assert_ok!(dispatch(call.clone()));
+		assert_ok!(AnonymousMembership::close_context(RuntimeOrigin::root(), context));
+
+		assert_noop!(
+			AnonymousMembership::open_context(RuntimeOrigin::root(), context),
+			Error::<Test>::ContextAlreadyClosed
+		);
+		// The member can't act again within the same context.
+		assert_eq!(validate(&call), InvalidTransaction::Call.into());
+		assert!(dispatch(call).is_err());
+
+		// Other contexts are not affected.
+		open_context([2; 32]);
+	});
+}
+
+#[test]
+fn actions_within_context_are_bounded() {
+	new_test_ext(4, true).execute_with(|| {
+		let context = [1; 32];
+		open_context(context);
+		Contexts::<Test>::insert(context, MAX_MEMBERS);
+
+		let call = make_action_call(&member_pair(1), context, b"yes");
+		assert_eq!(validate(&call), InvalidTransaction::ExhaustsResources.into());
+		assert_noop!(
+			call.dispatch_bypass_filter(RawOrigin::None.into()),
+			Error::<Test>::TooManyActions
+		);
+	});
+}
+
+#[test]
+fn set_ring_context_works() {
+	new_test_ext(4, false).execute_with(|| {
+		let ring_ctx = ring_vrf::RingContext::<RING_SIZE>::new_testing();
+		let members = Members::<Test>::get();
+
+		assert_noop!(
+			AnonymousMembership::set_ring_context(RuntimeOrigin::signed(1), ring_ctx.encode()),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			AnonymousMembership::set_ring_context(RuntimeOrigin::root(), vec![0; 32]),
+			Error::<Test>::BadRingContext
+		);
+
+		assert_ok!(AnonymousMembership::set_ring_context(RuntimeOrigin::root(), ring_ctx.encode()));
+		System::assert_last_event(Event::RingContextSet.into());
+		assert_eq!(
+			RingVerifierData::<Test>::get().unwrap().encode(),
+			ring_ctx.verifier_key(&members).encode()
+		);
+
+		let context = [1; 32];
+		open_context(context);
+		assert_ok!(dispatch(make_action_call(&member_pair(1), context, b"yes")));
+	});
+}
+
+const ACTIONS_FILE: &str = "src/data/16_members_actions.bin";
+
+#[test]
+#[ignore = "benchmark actions data generator"]
+fn make_actions_data() {
+	use std::{fs::File, io::Write};
+
+	let members_count = 16;
+	let context = [1; 32];
+
+	new_test_ext(members_count, true).execute_with(|| {
+		let members = Members::<Test>::get();
+		let actions: Vec<_> = (0..=64)
+			.map(|len| {
+				let call = make_action_call(&member_pair(0), context, &vec![1; len]);
+				let Call::anonymous_action { alias, signature, .. } = call else { unreachable!() };
+				(alias, signature)
+			})
+			.collect();
+
+		let mut file = File::create(ACTIONS_FILE).unwrap();
+		file.write_all(&(members.into_inner(), context, actions).encode()).unwrap();
+	});
+}
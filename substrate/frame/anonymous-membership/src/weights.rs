@@ -0,0 +1,135 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for `pallet_anonymous_membership`.
+//!
+//! These are conservative estimates and are meant to be replaced by the output of the
+//! benchmark CLI running the pallet benchmarks against the kitchensink runtime.
+//!
+//! The ring verifier key construction dominates the cost of membership changes, while the
+//! ring proof verification (performed natively via host function) dominates the cost of
+//! anonymous actions.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_anonymous_membership`.
+pub trait WeightInfo {
+	fn add_member(m: u32, ) -> Weight;
+	fn remove_member(m: u32, ) -> Weight;
+	fn anonymous_action(l: u32, ) -> Weight;
+	fn open_context() -> Weight;
+	fn close_context(a: u32, ) -> Weight;
+	fn set_ring_context(m: u32, ) -> Weight;
+}
+
+/// Weights for `pallet_anonymous_membership` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `AnonymousMembership::Members` (r:1 w:1)
+	/// Storage: `AnonymousMembership::RingContext` (r:1 w:0)
+	/// Storage: `AnonymousMembership::RingVerifierData` (r:0 w:1)
+	/// The range of component `m` is `[0, 1024]`.
+	fn add_member(m: u32, ) -> Weight {
+		Weight::from_parts(500_000_000, 600_000)
+			.saturating_add(Weight::from_parts(1_500_000, 33).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AnonymousMembership::Members` (r:1 w:1)
+	/// Storage: `AnonymousMembership::RingContext` (r:1 w:0)
+	/// Storage: `AnonymousMembership::RingVerifierData` (r:0 w:1)
+	/// The range of component `m` is `[0, 1024]`.
+	fn remove_member(m: u32, ) -> Weight {
+		Weight::from_parts(500_000_000, 600_000)
+			.saturating_add(Weight::from_parts(1_500_000, 33).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AnonymousMembership::Aliases` (r:1 w:1)
+	/// Storage: `AnonymousMembership::RingVerifierData` (r:1 w:0)
+	/// The range of component `l` is `[0, MaxMessageLen]`.
+	fn anonymous_action(l: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 4_000)
+			.saturating_add(Weight::from_parts(1_000, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn open_context() -> Weight {
+		Weight::from_parts(10_000_000, 3_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn close_context(a: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 3_500)
+			.saturating_add(Weight::from_parts(1_000_000, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(a.into())))
+	}
+	fn set_ring_context(m: u32, ) -> Weight {
+		Weight::from_parts(500_000_000, 600_000)
+			.saturating_add(Weight::from_parts(1_500_000, 33).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn add_member(m: u32, ) -> Weight {
+		Weight::from_parts(500_000_000, 600_000)
+			.saturating_add(Weight::from_parts(1_500_000, 33).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn remove_member(m: u32, ) -> Weight {
+		Weight::from_parts(500_000_000, 600_000)
+			.saturating_add(Weight::from_parts(1_500_000, 33).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn anonymous_action(l: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 4_000)
+			.saturating_add(Weight::from_parts(1_000, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn open_context() -> Weight {
+		Weight::from_parts(10_000_000, 3_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn close_context(a: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 3_500)
+			.saturating_add(Weight::from_parts(1_000_000, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(a.into())))
+	}
+	fn set_ring_context(m: u32, ) -> Weight {
+		Weight::from_parts(500_000_000, 600_000)
+			.saturating_add(Weight::from_parts(1_500_000, 33).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
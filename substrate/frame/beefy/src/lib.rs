@@ -539,6 +539,29 @@ impl<T: Config> Pallet<T> {
 		ValidatorSet::<T::BeefyId>::new(validators, id)
 	}
 
+	/// Check the aggregate `signature` of `commitment` by the current BEEFY validators
+	/// selected by `signers`.
+	///
+	/// Meant to be used by the implementation of `BeefyAggregateApi`.
+	#[cfg(feature = "bls-experimental")]
+	pub fn check_aggregate_commitment_signature(
+		commitment: sp_consensus_beefy::Commitment<BlockNumberFor<T>>,
+		signers: Vec<bool>,
+		signature: sp_consensus_beefy::bls_crypto::AggregateSignature,
+	) -> bool
+	where
+		T::BeefyId: sp_consensus_beefy::AggregatableAuthorityId,
+	{
+		Self::validator_set().is_some_and(|validator_set| {
+			sp_consensus_beefy::check_validator_set_aggregate_signature(
+				&commitment,
+				&validator_set,
+				&signers,
+				&signature,
+			)
+		})
+	}
+
 	/// Submits an extrinsic to report a double voting equivocation. This method will create
 	/// an unsigned extrinsic with a call to `report_double_voting_unsigned` and
 	/// will push the transaction to the pool. Only useful in an offchain context.
@@ -57,19 +57,6 @@ pub extern "C" fn call() {
 	api::get_storage(StorageFlags::empty(), &ADDRESS_KEY, callee).unwrap();
 	assert!(callee.len() == 20);
 
-	// Calling the destination contract with non-empty input data should fail.
-	let res = api::call(
-		uapi::CallFlags::empty(),
-		&callee_addr,
-		u64::MAX, // How much ref_time weight to devote for the execution. u64::MAX = use all.
-		u64::MAX, // How much proof_size weight to devote for the execution. u64::MAX = use all.
-		&[u8::MAX; 32], // No deposit limit.
-		&VALUE,
-		&[0u8; 1],
-		None,
-	);
-	assert!(matches!(res, Err(uapi::ReturnErrorCode::CalleeTrapped)));
-
 	// Call the destination contract regularly, forcing it to self-destruct.
 	api::call(
 		uapi::CallFlags::empty(),
@@ -34,8 +34,9 @@ pub extern "C" fn deploy() {
 #[polkavm_derive::polkavm_export]
 pub extern "C" fn call() {
 	// If the input data is not empty, then recursively call self with empty input data.
-	// This should trap instead of self-destructing since a contract cannot be removed, while it's
-	// in the execution stack. If the recursive call traps, then trap here as well.
+	// A contract created within the same transaction cannot be removed while it's in the
+	// execution stack. Hence the recursive call traps for those. If the recursive call traps,
+	// then trap here as well.
 	input!(input, 4,);
 
 	if !input.is_empty() {
@@ -0,0 +1,414 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verifies the [`AccountProof`]s returned by the `eth_getProof` method of the pallet-revive
+//! Ethereum JSON-RPC server.
+//!
+//! Contracts store their storage in a child trie, whose root is stored in the main trie. An
+//! [`AccountProof`] is therefore made of a read proof of the main trie, proving the contract info
+//! and the child trie root against the state root of a block, and of a read proof of the child
+//! trie for each storage slot, proving its value against the child trie root.
+//!
+//! The main trie proof also proves the `frame_system` account the address is mapped to, holding
+//! the nonce and the balance of the account. Verifying them requires some knowledge of the runtime,
+//! described by [`RuntimeParams`].
+
+use codec::Decode;
+use pallet_balances::AccountData;
+use pallet_revive::evm::{AccountProof, Bytes, H160, H256, U256};
+use sp_core::storage::ChildInfo;
+use sp_crypto_hashing::{blake2_128, blake2_256, keccak_256, twox_128};
+use sp_runtime::{traits::BlakeTwo256, AccountId32};
+use sp_trie::{empty_trie_root, read_trie_value, LayoutV1, MemoryDB, StorageProof};
+
+/// The `frame_system` account information, as stored by the runtimes using `u32` nonces and
+/// `pallet_balances` with `u128` balances.
+type AccountInfo = frame_system::AccountInfo<u32, AccountData<u128>>;
+
+/// The parameters of the runtime the proofs are created by.
+///
+/// The verifier assumes that the runtime maps addresses with
+/// [`pallet_revive::AccountId32Mapper`] and stores the balances of `pallet_balances` in the
+/// `frame_system` accounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeParams<'a> {
+	/// The name of pallet-revive in the runtime, used to derive the keys of its storage items.
+	pub pallet_name: &'a str,
+	/// The ratio between the EVM and the native decimals, see
+	/// `pallet_revive::Config::NativeToEthRatio`.
+	pub native_to_eth_ratio: u32,
+	/// The existential deposit of `pallet_balances`, which cannot be spent by the account.
+	pub existential_deposit: u128,
+}
+
+/// The errors that can occur when verifying an [`AccountProof`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+	/// A value could not be read from the proof, which is missing some trie nodes.
+	#[error("The proof is incomplete")]
+	IncompleteProof,
+	/// The contract info of the account could not be decoded.
+	#[error("Failed to decode the contract info")]
+	InvalidContractInfo,
+	/// The account mapped to the address, or its information, could not be decoded.
+	#[error("Failed to decode the account")]
+	InvalidAccount,
+	/// The nonce does not match the proven account.
+	#[error("Nonce mismatch")]
+	NonceMismatch,
+	/// The balance does not match the proven account.
+	#[error("Balance mismatch")]
+	BalanceMismatch,
+	/// The code hash does not match the proven contract info.
+	#[error("Code hash mismatch")]
+	CodeHashMismatch,
+	/// The storage hash does not match the proven child trie root.
+	#[error("Storage hash mismatch")]
+	StorageHashMismatch,
+	/// The value of a storage slot does not match the proven value.
+	#[error("Storage value mismatch for key {0}")]
+	StorageValueMismatch(U256),
+}
+
+/// Verify an [`AccountProof`] against the state root of the block it was created at.
+pub fn verify_account_proof(
+	state_root: H256,
+	params: &RuntimeParams,
+	proof: &AccountProof,
+) -> Result<(), Error> {
+	let db = memory_db(&proof.account_proof);
+	verify_account(&db, &state_root, params, proof)?;
+
+	let contract_info =
+		read_value(&db, &state_root, &contract_info_key(params.pallet_name, &proof.address))?;
+
+	let Some(contract_info) = contract_info else {
+		// Accounts without a contract have no code and no storage.
+		if proof.code_hash != H256(keccak_256(&[])) {
+			return Err(Error::CodeHashMismatch);
+		}
+		if !proof.storage_hash.is_zero() {
+			return Err(Error::StorageHashMismatch);
+		}
+		return match proof.storage_proof.iter().find(|slot| !slot.value.is_empty()) {
+			Some(slot) => Err(Error::StorageValueMismatch(slot.key)),
+			None => Ok(()),
+		};
+	};
+
+	// The contract info starts with the trie id and the code hash of the contract.
+	let (trie_id, code_hash) = <(Vec<u8>, H256)>::decode(&mut &contract_info[..])
+		.map_err(|_| Error::InvalidContractInfo)?;
+	if code_hash != proof.code_hash {
+		return Err(Error::CodeHashMismatch);
+	}
+
+	let child_trie_key = ChildInfo::new_default(&trie_id).prefixed_storage_key();
+	// The root of an empty child trie is not stored in the main trie.
+	let storage_root = read_value(&db, &state_root, child_trie_key.as_slice())?
+		.unwrap_or_else(|| empty_trie_root::<LayoutV1<BlakeTwo256>>().as_bytes().to_vec());
+	if storage_root != proof.storage_hash.as_bytes() {
+		return Err(Error::StorageHashMismatch);
+	}
+
+	for slot in &proof.storage_proof {
+		let db = memory_db(&slot.proof);
+		let value = read_value(&db, &proof.storage_hash, &blake2_256(&slot.key.to_big_endian()))?;
+		if value.unwrap_or_default() != slot.value.0 {
+			return Err(Error::StorageValueMismatch(slot.key));
+		}
+	}
+
+	Ok(())
+}
+
+/// Verify the nonce and the balance of the account the address of the proof is mapped to.
+fn verify_account(
+	db: &MemoryDB<BlakeTwo256>,
+	state_root: &H256,
+	params: &RuntimeParams,
+	proof: &AccountProof,
+) -> Result<(), Error> {
+	let account_id = match read_value(
+		db,
+		state_root,
+		&original_account_key(params.pallet_name, &proof.address),
+	)? {
+		Some(account_id) =>
+			AccountId32::decode(&mut &account_id[..]).map_err(|_| Error::InvalidAccount)?,
+		None => fallback_account_id(&proof.address),
+	};
+
+	let account = read_value(db, state_root, &system_account_key(&account_id))?
+		.map(|info| AccountInfo::decode(&mut &info[..]))
+		.transpose()
+		.map_err(|_| Error::InvalidAccount)?
+		.unwrap_or_default();
+	if U256::from(account.nonce) != proof.nonce {
+		return Err(Error::NonceMismatch);
+	}
+
+	// The balance is the reducible balance of the account, see
+	// `pallet_revive::Pallet::evm_balance`.
+	let AccountData { free, reserved, frozen, .. } = account.data;
+	let untouchable = frozen.saturating_sub(reserved).max(params.existential_deposit);
+	let balance = U256::from(free.saturating_sub(untouchable))
+		.saturating_mul(params.native_to_eth_ratio.into());
+	if balance != proof.balance {
+		return Err(Error::BalanceMismatch);
+	}
+
+	Ok(())
+}
+
+/// The account an address is mapped to when it is not in `OriginalAccount`, see
+/// `pallet_revive::AccountId32Mapper`.
+fn fallback_account_id(address: &H160) -> AccountId32 {
+	let mut account_id = [0xEE; 32];
+	account_id[..20].copy_from_slice(address.as_bytes());
+	account_id.into()
+}
+
+/// The key of the `OriginalAccount` storage map of pallet-revive for the given address.
+fn original_account_key(pallet_name: &str, address: &H160) -> Vec<u8> {
+	[&twox_128(pallet_name.as_bytes())[..], &twox_128(b"OriginalAccount"), address.as_bytes()]
+		.concat()
+}
+
+/// The key of the `Account` storage map of `frame_system` for the given account.
+fn system_account_key(account_id: &AccountId32) -> Vec<u8> {
+	let account_id: &[u8] = account_id.as_ref();
+	[&twox_128(b"System")[..], &twox_128(b"Account"), &blake2_128(account_id), account_id].concat()
+}
+
+/// The key of the `ContractInfoOf` storage map of pallet-revive for the given address.
+fn contract_info_key(pallet_name: &str, address: &H160) -> Vec<u8> {
+	[&twox_128(pallet_name.as_bytes())[..], &twox_128(b"ContractInfoOf"), address.as_bytes()]
+		.concat()
+}
+
+/// Build a trie database from the nodes of a proof.
+fn memory_db(nodes: &[Bytes]) -> MemoryDB<BlakeTwo256> {
+	StorageProof::new(nodes.iter().map(|node| node.0.clone())).into_memory_db()
+}
+
+/// Read the value of `key` in the trie with the given `root`.
+fn read_value(
+	db: &MemoryDB<BlakeTwo256>,
+	root: &H256,
+	key: &[u8],
+) -> Result<Option<Vec<u8>>, Error> {
+	read_trie_value::<LayoutV1<BlakeTwo256>, _>(db, root, key, None, None)
+		.map_err(|_| Error::IncompleteProof)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use pallet_revive::evm::StorageProof as SlotProof;
+	use sp_trie::{TrieDBMutBuilder, TrieMut};
+
+	const PARAMS: RuntimeParams = RuntimeParams {
+		pallet_name: "Revive",
+		native_to_eth_ratio: 1_000,
+		existential_deposit: 10,
+	};
+
+	/// Build a trie with the given entries, returning its root and all its nodes.
+	fn build_trie(entries: &[(Vec<u8>, Vec<u8>)]) -> (H256, Vec<Bytes>) {
+		let mut db = MemoryDB::<BlakeTwo256>::default();
+		let mut root = Default::default();
+		{
+			let mut trie =
+				TrieDBMutBuilder::<LayoutV1<BlakeTwo256>>::new(&mut db, &mut root).build();
+			for (key, value) in entries {
+				trie.insert(key, value).unwrap();
+			}
+		}
+		let nodes = db.drain().into_values().map(|(node, _)| Bytes(node)).collect();
+		(root, nodes)
+	}
+
+	/// The `frame_system` account entry of the given account.
+	fn account_entry(account_id: &AccountId32, nonce: u32, free: u128) -> (Vec<u8>, Vec<u8>) {
+		let info = AccountInfo {
+			nonce,
+			providers: 1,
+			data: AccountData { free, reserved: 5, frozen: 20, ..Default::default() },
+			..Default::default()
+		};
+		(system_account_key(account_id), info.encode())
+	}
+
+	/// Build the state of a contract with a single storage slot, returning the state root and the
+	/// matching proof.
+	fn contract_proof() -> (H256, AccountProof) {
+		let address = H160::repeat_byte(0x42);
+		let trie_id = vec![1u8; 32];
+		let code_hash = H256::repeat_byte(0x11);
+		let slot = U256::from(7);
+		let value = vec![0xaa; 32];
+
+		let (storage_hash, child_nodes) =
+			build_trie(&[(blake2_256(&slot.to_big_endian()).to_vec(), value.clone())]);
+
+		// The contract info is followed by fields the verifier does not care about.
+		let contract_info = (trie_id.clone(), code_hash, 0u32).encode();
+		let (state_root, nodes) = build_trie(&[
+			account_entry(&fallback_account_id(&address), 1, 100),
+			(contract_info_key(PARAMS.pallet_name, &address), contract_info),
+			(
+				ChildInfo::new_default(&trie_id).prefixed_storage_key().into_inner(),
+				storage_hash.as_bytes().to_vec(),
+			),
+		]);
+
+		let proof = AccountProof {
+			address,
+			account_proof: nodes,
+			// The frozen balance exceeds the existential deposit.
+			balance: U256::from((100 - 15) * 1_000),
+			code_hash,
+			nonce: U256::from(1),
+			storage_hash,
+			storage_proof: vec![
+				SlotProof { key: slot, value: Bytes(value), proof: child_nodes.clone() },
+				SlotProof { key: U256::from(8), value: Bytes::default(), proof: child_nodes },
+			],
+		};
+		(state_root, proof)
+	}
+
+	#[test]
+	fn verify_contract_proof_works() {
+		let (state_root, proof) = contract_proof();
+		assert_eq!(verify_account_proof(state_root, &PARAMS, &proof), Ok(()));
+	}
+
+	#[test]
+	fn verify_contract_proof_detects_invalid_proofs() {
+		let (state_root, proof) = contract_proof();
+
+		let invalid = AccountProof { code_hash: H256::zero(), ..proof.clone() };
+		assert_eq!(
+			verify_account_proof(state_root, &PARAMS, &invalid),
+			Err(Error::CodeHashMismatch)
+		);
+
+		let invalid = AccountProof { storage_hash: H256::zero(), ..proof.clone() };
+		assert_eq!(
+			verify_account_proof(state_root, &PARAMS, &invalid),
+			Err(Error::StorageHashMismatch)
+		);
+
+		let invalid = AccountProof { nonce: U256::from(2), ..proof.clone() };
+		assert_eq!(verify_account_proof(state_root, &PARAMS, &invalid), Err(Error::NonceMismatch));
+
+		let invalid = AccountProof { balance: U256::from(100 * 1_000), ..proof.clone() };
+		assert_eq!(
+			verify_account_proof(state_root, &PARAMS, &invalid),
+			Err(Error::BalanceMismatch)
+		);
+
+		let mut invalid = proof.clone();
+		invalid.storage_proof[0].value = Bytes(vec![0xbb; 32]);
+		assert_eq!(
+			verify_account_proof(state_root, &PARAMS, &invalid),
+			Err(Error::StorageValueMismatch(U256::from(7)))
+		);
+
+		let mut invalid = proof.clone();
+		invalid.storage_proof[0].proof.clear();
+		assert_eq!(
+			verify_account_proof(state_root, &PARAMS, &invalid),
+			Err(Error::IncompleteProof)
+		);
+
+		assert_eq!(
+			verify_account_proof(H256::repeat_byte(1), &PARAMS, &proof),
+			Err(Error::IncompleteProof)
+		);
+	}
+
+	#[test]
+	fn verify_contract_without_storage_works() {
+		let address = H160::repeat_byte(0x42);
+		let code_hash = H256::repeat_byte(0x11);
+		let contract_info = (vec![1u8; 32], code_hash, 0u32).encode();
+		let (state_root, nodes) =
+			build_trie(&[(contract_info_key(PARAMS.pallet_name, &address), contract_info)]);
+
+		let proof = AccountProof {
+			address,
+			account_proof: nodes,
+			code_hash,
+			storage_hash: empty_trie_root::<LayoutV1<BlakeTwo256>>(),
+			storage_proof: vec![SlotProof { key: U256::from(1), ..Default::default() }],
+			..Default::default()
+		};
+		assert_eq!(verify_account_proof(state_root, &PARAMS, &proof), Ok(()));
+	}
+
+	#[test]
+	fn verify_account_without_contract_works() {
+		let (state_root, nodes) = build_trie(&[(b"unrelated".to_vec(), vec![1])]);
+		let proof = AccountProof {
+			address: H160::repeat_byte(0x42),
+			account_proof: nodes,
+			code_hash: H256(keccak_256(&[])),
+			storage_proof: vec![SlotProof { key: U256::from(1), ..Default::default() }],
+			..Default::default()
+		};
+		assert_eq!(verify_account_proof(state_root, &PARAMS, &proof), Ok(()));
+
+		let mut invalid = proof.clone();
+		invalid.storage_proof[0].value = Bytes(vec![1]);
+		assert_eq!(
+			verify_account_proof(state_root, &PARAMS, &invalid),
+			Err(Error::StorageValueMismatch(U256::from(1)))
+		);
+
+		let invalid = AccountProof { nonce: U256::from(1), ..proof };
+		assert_eq!(verify_account_proof(state_root, &PARAMS, &invalid), Err(Error::NonceMismatch));
+	}
+
+	#[test]
+	fn verify_mapped_account_works() {
+		let address = H160::repeat_byte(0x42);
+		let account_id = AccountId32::new([7; 32]);
+		let (state_root, nodes) = build_trie(&[
+			(original_account_key(PARAMS.pallet_name, &address), account_id.encode()),
+			account_entry(&account_id, 3, 1_000),
+			// The fallback account is not the one the address is mapped to.
+			account_entry(&fallback_account_id(&address), 5, 2_000),
+		]);
+
+		let proof = AccountProof {
+			address,
+			account_proof: nodes,
+			balance: U256::from((1_000 - 15) * 1_000),
+			code_hash: H256(keccak_256(&[])),
+			nonce: U256::from(3),
+			..Default::default()
+		};
+		assert_eq!(verify_account_proof(state_root, &PARAMS, &proof), Ok(()));
+
+		let invalid = AccountProof { nonce: U256::from(5), ..proof };
+		assert_eq!(verify_account_proof(state_root, &PARAMS, &invalid), Err(Error::NonceMismatch));
+	}
+}
@@ -0,0 +1,4 @@
+// deploys a contract whose constructor reverts with the data 0xdeadbeef
+// the input is the `return_with_data` fixture, followed by the revert flag and the data to return
+>> {"jsonrpc":"2.0","id":1,"method":"eth_call","params":[{"from":"0xf24ff3a9cf04c71dbc94d0b566f7a27b94566cac","input":"0x50564d0000a80400000000000001040000a000044504000000000e0000001c0000002900000063616c6c5f646174615f636f707963616c6c5f646174615f73697a65636c6561725f73746f726167657365616c5f72657475726e050f02000463616c6c05066465706c6f790680eb030180ca04092a5010020a005010040500951168ff7b1090007b1588007b168000641733098000330580003308501006480a016476e657056417645833090a0154160334330801330733090a02017c17561701249518041409fcffffff00000000c895051409ffffffff00000000d259090a0300009a7a84a207c8270baeb713642c647a0178a895ccff95aa01520cf8c92909849af8c8ba0aaeab22978238140c0001010101000000d6c202972c20d42c0c017bbc95bb08acabfb849907c89a0baeba0e78a89599ff95aa015209f83200314644145154aa23d52201248074496a49922401245992a424018082bd002872657475726e5f776974685f646174615b323432666631366163613863613761305d3a3a63616c6c1c5f5f706f6c6b61766d5f6578706f72743a3a7472616d706f6c696e652a72657475726e5f776974685f646174615b323432666631366163613863613761305d3a3a6465706c6f790463616c6c066d656d73657480833c70616c6c65745f7265766976655f756170695b376561383434636562646335333531305d3a3a686f73743a3a726973637636343a3a7379733a3a63616c6c5f646174615f73697a653a3a53796d2061732070616c6c65745f7265766976655f756170695b376561383434636562646335333531305d3a3a686f73743a3a72697363762e3a3a7379733a3a6162693a3a707269766174653a3a496d706f727453796d626f6c3e3a3a7472616d706f6c696e6580833c70616c6c65745f7265766976655f756170695b376561383434636562646335333531305d3a3a686f73743a3a726973637636343a3a7379733a3a63616c6c5f646174615f636f70793a3a53796d2061732070616c6c65745f7265766976655f756170695b376561383434636562646335333531305d3a3a686f73743a3a726973637680823c70616c6c65745f7265766976655f756170695b376561383434636562646335333531305d3a3a686f73743a3a726973637636343a3a7379733a3a636c6561725f73746f726167653a3a53796d2061732070616c6c65745f7265766976655f756170695b376561383434636562646335333531305d3a3a686f73743a3a726973637680803c70616c6c65745f7265766976655f756170695b376561383434636562646335333531305d3a3a686f73743a3a726973637636343a3a7379733a3a7365616c5f72657475726e3a3a53796d2061732070616c6c65745f7265766976655f756170695b376561383434636562646335333531305d3a3a686f73743a3a7269736376815a010a01020501062a1304000a01020547062a1304000a01020672111c01010206770f04057e06810311020102020672110b0103020581320681030e0a01110c01010581b71302112405823b11020e0a010e000a01020677135c00823000000000040000000100000005000000090000000b0000000a0000006e000000150000006e000000ca000000520000000001000000deadbeef"},"latest"]}
+<< {"jsonrpc":"2.0","id":1,"error":{"code":3,"message":"execution reverted","data":"0xdeadbeef"}}
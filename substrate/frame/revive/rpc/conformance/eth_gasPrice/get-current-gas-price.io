@@ -0,0 +1,3 @@
+// gets the current gas price in wei
+>> {"jsonrpc":"2.0","id":1,"method":"eth_gasPrice"}
+<< {"jsonrpc":"2.0","id":1,"result":"<quantity>"}
@@ -0,0 +1,12 @@
+// gets the balance of a funded account using each of the block tags
+// the account is Baltathar, endowed at genesis and never used by the other tests
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getBalance","params":["0x3cd0a705a2dc65e5b1e1205896baa2be8a07c6e0","earliest"]}
+<< {"jsonrpc":"2.0","id":1,"result":"0x33b2e373409220f84f00000"}
+>> {"jsonrpc":"2.0","id":2,"method":"eth_getBalance","params":["0x3cd0a705a2dc65e5b1e1205896baa2be8a07c6e0","latest"]}
+<< {"jsonrpc":"2.0","id":2,"result":"0x33b2e373409220f84f00000"}
+>> {"jsonrpc":"2.0","id":3,"method":"eth_getBalance","params":["0x3cd0a705a2dc65e5b1e1205896baa2be8a07c6e0","safe"]}
+<< {"jsonrpc":"2.0","id":3,"result":"0x33b2e373409220f84f00000"}
+>> {"jsonrpc":"2.0","id":4,"method":"eth_getBalance","params":["0x3cd0a705a2dc65e5b1e1205896baa2be8a07c6e0","finalized"]}
+<< {"jsonrpc":"2.0","id":4,"result":"0x33b2e373409220f84f00000"}
+>> {"jsonrpc":"2.0","id":5,"method":"eth_getBalance","params":["0x3cd0a705a2dc65e5b1e1205896baa2be8a07c6e0","pending"]}
+<< {"jsonrpc":"2.0","id":5,"result":"0x33b2e373409220f84f00000"}
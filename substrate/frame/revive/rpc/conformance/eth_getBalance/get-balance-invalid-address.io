@@ -0,0 +1,3 @@
+// requests the balance of a malformed address
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getBalance","params":["0xdeadbeef","latest"]}
+<< {"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"<string>"}}
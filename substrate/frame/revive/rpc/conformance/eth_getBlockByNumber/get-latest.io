@@ -0,0 +1,3 @@
+// gets the latest block, without its transactions
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getBlockByNumber","params":["latest",false]}
+<< {"jsonrpc":"2.0","id":1,"result":{"baseFeePerGas":"<quantity>","difficulty":"<quantity>","extraData":"<data>","gasLimit":"<quantity>","gasUsed":"<quantity>","hash":"<hash>","logsBloom":"<data>","miner":"<address>","mixHash":"<hash>","nonce":"<data>","number":"<quantity>","parentHash":"<hash>","receiptsRoot":"<hash>","sha3Uncles":"<hash>","size":"<quantity>","stateRoot":"<hash>","timestamp":"<quantity>","transactions":"<any>","transactionsRoot":"<hash>","uncles":[]}}
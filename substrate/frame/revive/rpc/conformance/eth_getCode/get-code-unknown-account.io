@@ -0,0 +1,3 @@
+// requests the code of an account without code
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getCode","params":["0x00000000000000000000000000000000deadbeef","latest"]}
+<< {"jsonrpc":"2.0","id":1,"result":"0x"}
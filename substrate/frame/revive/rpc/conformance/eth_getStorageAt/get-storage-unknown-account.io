@@ -0,0 +1,4 @@
+// gets an unset storage slot of an account without storage
+// deviation: empty storage slots are returned as "0x" instead of 32 zero bytes
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getStorageAt","params":["0x00000000000000000000000000000000deadbeef","0x0","latest"]}
+<< {"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000000000000000000"}
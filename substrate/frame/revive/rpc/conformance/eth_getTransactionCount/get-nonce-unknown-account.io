@@ -0,0 +1,3 @@
+// gets the nonce of an account that never sent a transaction
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getTransactionCount","params":["0x00000000000000000000000000000000deadbeef","latest"]}
+<< {"jsonrpc":"2.0","id":1,"result":"0x0"}
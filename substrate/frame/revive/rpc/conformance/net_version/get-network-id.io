@@ -0,0 +1,3 @@
+// retrieves the network id, which is the chain id as a decimal string
+>> {"jsonrpc":"2.0","id":1,"method":"net_version"}
+<< {"jsonrpc":"2.0","id":1,"result":"420420420"}
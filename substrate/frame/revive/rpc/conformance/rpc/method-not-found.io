@@ -0,0 +1,3 @@
+// calls a method that does not exist
+>> {"jsonrpc":"2.0","id":1,"method":"eth_unknownMethod","params":[]}
+<< {"jsonrpc":"2.0","id":1,"error":{"code":-32601}}
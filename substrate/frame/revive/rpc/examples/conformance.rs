@@ -0,0 +1,47 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use clap::Parser;
+use jsonrpsee::http_client::HttpClientBuilder;
+use pallet_revive_eth_rpc::conformance::{self, Fixture};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[clap(author, about, version)]
+pub struct CliCommand {
+	/// The eth-rpc url to connect to
+	#[clap(long, default_value = "http://127.0.0.1:8545")]
+	pub rpc_url: String,
+
+	/// The directory containing the fixtures to replay
+	#[clap(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/conformance"))]
+	pub fixtures: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+	let CliCommand { rpc_url, fixtures } = CliCommand::parse();
+
+	let client = HttpClientBuilder::default().build(&rpc_url)?;
+	let fixtures = Fixture::load_dir(&fixtures)?;
+	let report = conformance::run(&client, &fixtures).await;
+	println!("{report}");
+
+	if !report.is_conformant() {
+		anyhow::bail!("Spec deviations found");
+	}
+	Ok(())
+}
@@ -0,0 +1,55 @@
+-- The finalized blocks whose state changes are indexed.
+CREATE TABLE IF NOT EXISTS state_index_blocks (
+	block_number INTEGER NOT NULL PRIMARY KEY,
+	block_hash BLOB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_state_index_blocks_block_hash ON state_index_blocks (
+	block_hash
+);
+
+-- The balance of the accounts changed by a block, before the block.
+CREATE TABLE IF NOT EXISTS balance_changes (
+	address BLOB NOT NULL,
+	block_number INTEGER NOT NULL,
+	balance BLOB NOT NULL,
+	PRIMARY KEY (address, block_number)
+);
+
+-- The code of the accounts changed by a block, before the block.
+CREATE TABLE IF NOT EXISTS code_changes (
+	address BLOB NOT NULL,
+	block_number INTEGER NOT NULL,
+	code BLOB NOT NULL,
+	PRIMARY KEY (address, block_number)
+);
+
+-- The storage values changed by a block, before the block.
+-- A NULL value means that the key was absent from the storage.
+CREATE TABLE IF NOT EXISTS storage_changes (
+	address BLOB NOT NULL,
+	storage_key BLOB NOT NULL,
+	block_number INTEGER NOT NULL,
+	value BLOB,
+	PRIMARY KEY (address, storage_key, block_number)
+);
+
+-- The accounts removed by a block, along with all their storage.
+CREATE TABLE IF NOT EXISTS removed_accounts (
+	address BLOB NOT NULL,
+	block_number INTEGER NOT NULL,
+	PRIMARY KEY (address, block_number)
+);
+
+-- The SCALE encoded traces of the transactions of finalized blocks.
+CREATE TABLE IF NOT EXISTS traces (
+	transaction_hash BLOB NOT NULL,
+	tracer_config BLOB NOT NULL,
+	block_number INTEGER NOT NULL,
+	trace BLOB NOT NULL,
+	PRIMARY KEY (transaction_hash, tracer_config)
+);
+
+CREATE INDEX IF NOT EXISTS idx_traces_block_number ON traces (
+	block_number
+);
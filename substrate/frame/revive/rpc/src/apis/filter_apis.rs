@@ -0,0 +1,212 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Filter and subscription JSON-RPC methods.
+
+use crate::*;
+use jsonrpsee::{
+	core::{RpcResult, SubscriptionResult},
+	proc_macros::rpc,
+	ConnectionId, Extensions, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink,
+};
+use std::future::Future;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Ethereum JSON-RPC apis to follow new blocks, logs and transactions, by polling filters or by
+/// subscribing to notifications.
+#[rpc(server, client)]
+pub trait EthFilterRpc {
+	/// Creates a filter object, based on filter options, to notify when the state changes (logs).
+	#[method(name = "eth_newFilter", with_extensions)]
+	async fn new_filter(&self, filter: Filter) -> RpcResult<U256>;
+
+	/// Creates a filter in the node, to notify when a new block arrives.
+	#[method(name = "eth_newBlockFilter", with_extensions)]
+	async fn new_block_filter(&self) -> RpcResult<U256>;
+
+	/// Creates a filter in the node, to notify when new pending transactions arrive.
+	#[method(name = "eth_newPendingTransactionFilter", with_extensions)]
+	async fn new_pending_transaction_filter(&self) -> RpcResult<U256>;
+
+	/// Polling method for a filter, which returns an array of logs or hashes which occurred since
+	/// last poll.
+	#[method(name = "eth_getFilterChanges")]
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults>;
+
+	/// Returns an array of all logs matching filter with given id.
+	#[method(name = "eth_getFilterLogs")]
+	async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<FilterResults>;
+
+	/// Uninstalls a filter with given id.
+	#[method(name = "eth_uninstallFilter")]
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool>;
+
+	/// Subscribes to new blocks, logs or pending transactions.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/pubsub>
+	#[subscription(
+		name = "eth_subscribe" => "eth_subscription",
+		unsubscribe = "eth_unsubscribe",
+		item = SubscriptionItem
+	)]
+	async fn eth_subscribe(
+		&self,
+		kind: SubscriptionKind,
+		filter: Option<Filter>,
+	) -> SubscriptionResult;
+}
+
+pub struct EthFilterRpcServerImpl {
+	client: client::Client,
+	filter_manager: FilterManager,
+}
+
+impl EthFilterRpcServerImpl {
+	pub fn new(client: client::Client, filter_manager: FilterManager) -> Self {
+		Self { client, filter_manager }
+	}
+}
+
+/// The id of the connection a method was called on.
+fn connection_id(ext: &Extensions) -> ConnectionId {
+	ext.get::<ConnectionId>()
+		.copied()
+		.expect("ConnectionId is always set by jsonrpsee; qed")
+}
+
+/// Send the items built from each notification received on `notifications` to the subscriber,
+/// until it unsubscribes.
+async fn forward_notifications<T, F, Fut>(
+	sink: SubscriptionSink,
+	mut notifications: broadcast::Receiver<T>,
+	into_items: F,
+) -> SubscriptionResult
+where
+	T: Clone,
+	F: Fn(T) -> Fut,
+	Fut: Future<Output = Vec<SubscriptionItem>>,
+{
+	loop {
+		let notification = tokio::select! {
+			_ = sink.closed() => return Ok(()),
+			notification = notifications.recv() => match notification {
+				Ok(notification) => notification,
+				Err(RecvError::Lagged(skipped)) => {
+					log::warn!(target: LOG_TARGET, "Subscription {:?} missed {skipped} notifications", sink.subscription_id());
+					continue;
+				},
+				Err(RecvError::Closed) => return Ok(()),
+			},
+		};
+
+		for item in into_items(notification).await {
+			let message = SubscriptionMessage::from_json(&item)?;
+			if sink.send(message).await.is_err() {
+				return Ok(());
+			}
+		}
+	}
+}
+
+#[async_trait]
+impl EthFilterRpcServer for EthFilterRpcServerImpl {
+	async fn new_filter(&self, ext: &Extensions, filter: Filter) -> RpcResult<U256> {
+		Ok(self.filter_manager.new_log_filter(connection_id(ext), filter).await?)
+	}
+
+	async fn new_block_filter(&self, ext: &Extensions) -> RpcResult<U256> {
+		Ok(self.filter_manager.new_block_filter(connection_id(ext)).await?)
+	}
+
+	async fn new_pending_transaction_filter(&self, ext: &Extensions) -> RpcResult<U256> {
+		Ok(self.filter_manager.new_pending_transaction_filter(connection_id(ext)).await?)
+	}
+
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		Ok(self.filter_manager.filter_changes(filter_id).await?)
+	}
+
+	async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		Ok(self.filter_manager.filter_logs(filter_id).await?)
+	}
+
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool> {
+		Ok(self.filter_manager.uninstall(filter_id).await)
+	}
+
+	async fn eth_subscribe(
+		&self,
+		pending: PendingSubscriptionSink,
+		kind: SubscriptionKind,
+		filter: Option<Filter>,
+	) -> SubscriptionResult {
+		log::debug!(target: LOG_TARGET, "subscribe: {kind:?} filter: {filter:?}");
+		let sink = pending.accept().await?;
+
+		match kind {
+			SubscriptionKind::NewHeads =>
+				forward_notifications(
+					sink,
+					self.client.subscribe_block_hashes(),
+					|hash| async move {
+						match self.client.block_by_hash(&hash).await {
+							Ok(Some(block)) => {
+								let block = self.client.evm_block(block, false).await;
+								vec![SubscriptionItem::Block(Box::new(block))]
+							},
+							Ok(None) => vec![],
+							Err(err) => {
+								log::debug!(target: LOG_TARGET, "Failed to fetch block {hash:?}: {err:?}");
+								vec![]
+							},
+						}
+					},
+				)
+				.await,
+			SubscriptionKind::Logs => {
+				let filter = filter.unwrap_or_default();
+				forward_notifications(sink, self.client.subscribe_block_hashes(), |hash| {
+					// Only match the logs of the new block.
+					let filter = Filter {
+						block_hash: Some(hash),
+						from_block: None,
+						to_block: None,
+						..filter.clone()
+					};
+					async move {
+						match self.client.logs(Some(filter)).await {
+							Ok(logs) => logs.into_iter().map(SubscriptionItem::Log).collect(),
+							Err(err) => {
+								log::debug!(target: LOG_TARGET, "Failed to fetch logs of block {hash:?}: {err:?}");
+								vec![]
+							},
+						}
+					}
+				})
+				.await
+			},
+			SubscriptionKind::NewPendingTransactions =>
+				forward_notifications(
+					sink,
+					self.client.subscribe_pending_transactions(),
+					|hash| async move { vec![SubscriptionItem::TransactionHash(hash)] },
+				)
+				.await,
+		}
+	}
+}
@@ -0,0 +1,56 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! pallet-revive specific JSON-RPC methods.
+
+use crate::*;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+/// JSON-RPC methods specific to pallet-revive.
+#[rpc(server, client)]
+pub trait ReviveRpc {
+	/// Returns the gas schedule used to estimate common operations, or `null` if the runtime
+	/// estimates every transaction from its dry run.
+	///
+	/// The gas returned by `eth_estimateGas` for an operation priced by the schedule stays the
+	/// same for as long as the version of the schedule does not change.
+	#[method(name = "revive_gasSchedule")]
+	async fn gas_schedule(
+		&self,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<Option<GasScheduleResult>>;
+}
+
+pub struct ReviveRpcServerImpl {
+	client: client::Client,
+}
+
+impl ReviveRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+#[async_trait]
+impl ReviveRpcServer for ReviveRpcServerImpl {
+	async fn gas_schedule(
+		&self,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<Option<GasScheduleResult>> {
+		let block = block.unwrap_or_else(|| BlockTag::Latest.into());
+		Ok(self.client.gas_schedule(&block).await?)
+	}
+}
@@ -18,8 +18,9 @@
 use crate::{
 	client::{connect, native_to_eth_ratio, Client, SubscriptionType, SubstrateBlockNumber},
 	BlockInfoProvider, BlockInfoProviderImpl, CacheReceiptProvider, DBReceiptProvider,
-	DebugRpcServer, DebugRpcServerImpl, EthRpcServer, EthRpcServerImpl, ReceiptExtractor,
-	ReceiptProvider, SystemHealthRpcServer, SystemHealthRpcServerImpl, LOG_TARGET,
+	DebugRpcServer, DebugRpcServerImpl, EthFilterRpcServer, EthFilterRpcServerImpl, EthRpcServer,
+	EthRpcServerImpl, FilterManager, ReceiptExtractor, ReceiptProvider, ReviveRpcServer,
+	ReviveRpcServerImpl, StateIndex, SystemHealthRpcServer, SystemHealthRpcServerImpl, LOG_TARGET,
 };
 use clap::Parser;
 use futures::{pin_mut, FutureExt};
@@ -65,6 +66,17 @@ pub struct CliCommand {
 	#[clap(long)]
 	pub index_until_block: Option<SubstrateBlockNumber>,
 
+	/// Index the state changes of finalized blocks in the database.
+	/// Historical balance, code and storage queries are then answered from the index, for the
+	/// blocks finalized since indexing started, and the traces of finalized transactions are
+	/// cached.
+	#[clap(long)]
+	pub index_state: bool,
+
+	/// The number of finalized blocks whose transaction traces are kept in the state index.
+	#[clap(long, default_value = "14400")]
+	pub trace_cache_blocks: u32,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: SharedParams,
@@ -99,12 +111,15 @@ fn init_logger(params: &SharedParams) -> anyhow::Result<()> {
 	Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_client(
 	tokio_handle: &tokio::runtime::Handle,
 	cache_size: usize,
 	earliest_receipt_block: Option<SubstrateBlockNumber>,
 	node_rpc_url: &str,
 	database_url: &str,
+	index_state: bool,
+	trace_cache_blocks: u32,
 	abort_signal: Signals,
 ) -> anyhow::Result<Client> {
 	let fut = async {
@@ -132,10 +147,15 @@ fn build_client(
 			.await?,
 		));
 
-		let client =
+		let mut client =
 			Client::new(api, rpc_client, rpc, block_provider, receipt_provider, receipt_extractor)
 				.await?;
 
+		if index_state {
+			log::info!(target: LOG_TARGET, "Indexing the state changes of finalized blocks");
+			client = client.with_state_index(StateIndex::new(database_url, trace_cache_blocks).await?);
+		}
+
 		Ok(client)
 	}
 	.fuse();
@@ -158,6 +178,8 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		database_url,
 		earliest_receipt_block,
 		index_until_block,
+		index_state,
+		trace_cache_blocks,
 		shared_params,
 		..
 	} = cmd;
@@ -200,9 +222,13 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		earliest_receipt_block,
 		&node_rpc_url,
 		&database_url,
+		index_state,
+		trace_cache_blocks,
 		tokio_runtime.block_on(async { Signals::capture() })?,
 	)?;
 
+	let filter_manager = FilterManager::new(client.clone());
+
 	// Prometheus metrics.
 	if let Some(PrometheusConfig { port, registry }) = prometheus_config.clone() {
 		task_manager.spawn_handle().spawn(
@@ -216,10 +242,17 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		&rpc_config,
 		prometheus_registry,
 		tokio_handle,
-		|| rpc_module(is_dev, client.clone()),
+		|| rpc_module(is_dev, client.clone(), filter_manager.clone()),
 		None,
 	)?;
 
+	if index_state {
+		let client = client.clone();
+		task_manager.spawn_essential_handle().spawn("state-index", None, async move {
+			client.subscribe_and_index_state().await;
+		});
+	}
+
 	task_manager
 		.spawn_essential_handle()
 		.spawn("block-subscription", None, async move {
@@ -232,6 +265,10 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 			}
 		});
 
+	task_manager.spawn_essential_handle().spawn("filter-manager", None, async move {
+		filter_manager.run().await;
+	});
+
 	task_manager.keep_alive(rpc_server_handle);
 	let signals = tokio_runtime.block_on(async { Signals::capture() })?;
 	tokio_runtime.block_on(signals.run_until_signal(task_manager.future().fuse()))?;
@@ -239,17 +276,25 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 }
 
 /// Create the JSON-RPC module.
-fn rpc_module(is_dev: bool, client: Client) -> Result<RpcModule<()>, sc_service::Error> {
+fn rpc_module(
+	is_dev: bool,
+	client: Client,
+	filter_manager: FilterManager,
+) -> Result<RpcModule<()>, sc_service::Error> {
 	let eth_api = EthRpcServerImpl::new(client.clone())
 		.with_accounts(if is_dev { vec![crate::Account::default()] } else { vec![] })
 		.into_rpc();
 
+	let filter_api = EthFilterRpcServerImpl::new(client.clone(), filter_manager).into_rpc();
 	let health_api = SystemHealthRpcServerImpl::new(client.clone()).into_rpc();
+	let revive_api = ReviveRpcServerImpl::new(client.clone()).into_rpc();
 	let debug_api = DebugRpcServerImpl::new(client).into_rpc();
 
 	let mut module = RpcModule::new(());
 	module.merge(eth_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(filter_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(health_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(revive_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(debug_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	Ok(module)
 }
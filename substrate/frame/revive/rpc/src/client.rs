@@ -17,22 +17,31 @@
 //! The client connects to the source substrate chain
 //! and is used by the rpc server to query and send transactions to the substrate chain.
 use crate::{
+	fee_history::{base_fee_per_gas, BlockFees, FeeHistoryCache},
+	state_index::event_addresses,
 	subxt_client::{
 		revive::calls::types::EthTransact, runtime_types::pallet_revive::storage::ContractInfo,
 	},
-	BlockInfoProvider, ReceiptExtractor, ReceiptProvider, TransactionInfo, LOG_TARGET,
+	BlockInfoProvider, BlockStateDiff, ReceiptExtractor, ReceiptProvider, StateIndex, StateLookup,
+	TransactionInfo, LOG_TARGET,
 };
 use codec::{Decode, Encode};
 use jsonrpsee::types::{error::CALL_EXECUTION_FAILED_CODE, ErrorObjectOwned};
 use pallet_revive::{
 	evm::{
-		decode_revert_reason, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, CallTrace, Filter,
-		GenericTransaction, Log, ReceiptInfo, SyncingProgress, SyncingStatus, TracerConfig,
-		TransactionSigned, TransactionTrace, H160, H256, U256,
+		decode_revert_reason, AccessListResult, AccountProof, Block, BlockNumberOrTag,
+		BlockNumberOrTagOrHash, BlockTag, Bytes, CallTrace, FeeHistoryResult, Filter,
+		GasScheduleResult, GenericTransaction, LegacyGenericTransaction, Log, ReceiptInfo,
+		StorageProof, SyncingProgress, SyncingStatus, Trace, TracerConfig, TransactionSigned,
+		TransactionTrace, H160, H256, U256,
 	},
-	EthTransactError, EthTransactInfo,
+	AccountProofKeys, EthTransactError, EthTransactInfo,
 };
+use sc_rpc_api::state::ReadProof;
+use sp_arithmetic::FixedU128;
+use sp_core::{blake2_256, blake2_64, keccak_256};
 use sp_runtime::OpaqueExtrinsic;
+use sp_version::RuntimeVersion;
 use sp_weights::Weight;
 use std::{ops::ControlFlow, sync::Arc, time::Duration};
 use subxt::{
@@ -40,7 +49,7 @@ use subxt::{
 		legacy::{rpc_methods::SystemHealth, LegacyRpcMethods},
 		rpc::{
 			reconnecting_rpc_client::{ExponentialBackoff, RpcClient as ReconnectingRpcClient},
-			RpcClient,
+			rpc_params, RpcClient,
 		},
 	},
 	config::Header,
@@ -49,7 +58,7 @@ use subxt::{
 	Config, OnlineClient,
 };
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 
 use crate::subxt_client::{self, SrcChainConfig};
 
@@ -68,6 +77,23 @@ pub type Shared<T> = Arc<RwLock<T>>;
 /// The runtime balance type.
 pub type Balance = u128;
 
+/// The maximum number of blocks whose fees are cached to answer `eth_feeHistory`.
+const FEE_HISTORY_CACHE_SIZE: usize = 1024;
+
+/// The capacity of the channels used to notify new blocks and pending transactions.
+const NOTIFICATION_CHANNEL_CAPACITY: usize = 512;
+
+/// The encoding of an empty trie node, whose hash is the root of an empty trie.
+const EMPTY_TRIE: u8 = 0;
+
+/// The first version of the `ReviveApi` runtime API returning [`Trace`]s rather than
+/// [`CallTrace`]s, and supporting tracers other than the call tracer.
+const TRACE_API_VERSION: u32 = 2;
+
+/// The first version of the `ReviveApi` runtime API accepting [`GenericTransaction`]s with an
+/// EIP-7702 authorization list.
+const AUTHORIZATION_LIST_API_VERSION: u32 = 6;
+
 /// The subscription type used to listen to new blocks.
 pub enum SubscriptionType {
 	/// Subscribe to the best blocks.
@@ -137,6 +163,9 @@ pub enum ClientError {
 	/// Failed to filter logs.
 	#[error("Failed to filter logs")]
 	LogFilterFailed(#[from] anyhow::Error),
+	/// The runtime API of the block does not support the requested operation.
+	#[error("{0} is not supported by the runtime at this block")]
+	UnsupportedRuntimeApi(&'static str),
 }
 
 const REVERT_CODE: i32 = 3;
@@ -181,6 +210,14 @@ pub struct Client {
 	receipt_extractor: ReceiptExtractor,
 	chain_id: u64,
 	max_block_weight: Weight,
+	/// Notified with the hash of each new block, once it has been cached.
+	block_notifier: broadcast::Sender<SubstrateBlockHash>,
+	/// Notified with the hash of each transaction submitted through this client.
+	pending_transaction_notifier: broadcast::Sender<H256>,
+	/// The fees of the blocks for which the fee history was requested.
+	fee_history_cache: Shared<FeeHistoryCache>,
+	/// The index of the state changes of finalized blocks, if enabled.
+	state_index: Option<StateIndex>,
 }
 
 /// Fetch the chain ID from the substrate chain.
@@ -255,9 +292,22 @@ impl Client {
 			receipt_extractor,
 			chain_id,
 			max_block_weight,
+			block_notifier: broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY).0,
+			pending_transaction_notifier: broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY).0,
+			fee_history_cache: Arc::new(RwLock::new(FeeHistoryCache::new(FEE_HISTORY_CACHE_SIZE))),
+			state_index: None,
 		})
 	}
 
+	/// Answer historical state queries and cache the traces of finalized blocks using the given
+	/// state index.
+	///
+	/// The index is only populated once [`Self::subscribe_and_index_state`] is running.
+	pub fn with_state_index(mut self, state_index: StateIndex) -> Self {
+		self.state_index = Some(state_index);
+		self
+	}
+
 	/// Subscribe to past blocks executing the callback for each block.
 	/// The subscription continues iterating past blocks until the closure returns
 	/// `ControlFlow::Break`. Blocks are iterated starting from the latest block and moving
@@ -353,11 +403,14 @@ impl Client {
 			.subscribe_new_blocks(subscription_type, |block| async {
 				let receipts = self.receipt_extractor.extract_from_block(&block).await?;
 
-				self.receipt_provider.insert(&block.hash(), &receipts).await;
+				let block_hash = block.hash();
+				self.receipt_provider.insert(&block_hash, &receipts).await;
 				if let Some(pruned) = self.block_provider.cache_block(block).await {
 					self.receipt_provider.remove(&pruned).await;
 				}
 
+				// Sending only fails when there are no subscribers.
+				let _ = self.block_notifier.send(block_hash);
 				Ok(())
 			})
 			.await;
@@ -367,6 +420,105 @@ impl Client {
 		}
 	}
 
+	/// Start the finalized block subscription, and index the state changes of each block.
+	///
+	/// The blocks finalized since the latest indexed block, while the server was stopped or
+	/// disconnected from the node, are indexed first, so that the index has no gaps.
+	pub async fn subscribe_and_index_state(&self) {
+		let Some(state_index) = &self.state_index else { return };
+		let res = self
+			.subscribe_new_blocks(SubscriptionType::FinalizedBlocks, |block| async move {
+				let first_missing = match state_index.latest_block_number().await? {
+					Some(latest) if latest >= block.number() => return Ok(()),
+					Some(latest) => latest + 1,
+					None => block.number(),
+				};
+
+				for block_number in first_missing..block.number() {
+					let missing = self
+						.block_provider
+						.block_by_number(block_number)
+						.await?
+						.ok_or(ClientError::BlockNotFound)?;
+					self.index_block_state(state_index, &missing).await?;
+				}
+				self.index_block_state(state_index, &block).await
+			})
+			.await;
+
+		if let Err(err) = res {
+			log::error!(target: LOG_TARGET, "State index subscription error: {err:?}");
+		}
+	}
+
+	/// Index the state changes of the given finalized block.
+	async fn index_block_state(
+		&self,
+		state_index: &StateIndex,
+		block: &SubstrateBlock,
+	) -> Result<(), ClientError> {
+		let diff = self.block_state_diff(block).await?;
+		state_index.insert_block(block.number(), &block.hash(), &diff).await?;
+		log::trace!(target: LOG_TARGET, "Indexed state of block #{}", block.number());
+		Ok(())
+	}
+
+	/// Get the state of the accounts changed by the given block, before the block.
+	async fn block_state_diff(
+		&self,
+		block: &SubstrateBlock,
+	) -> Result<BlockStateDiff, ClientError> {
+		// The genesis state is not the result of executing a block.
+		if block.number() == 0 {
+			return Ok(BlockStateDiff::default());
+		}
+
+		let tracer_config = TracerConfig::PrestateTracer {
+			diff_mode: true,
+			disable_code: false,
+			disable_storage: false,
+		};
+		let traces = self.trace_block(block.hash(), tracer_config).await?;
+		let mut diff = BlockStateDiff::from_traces(traces.iter().map(|(_, trace)| trace));
+
+		// Balances also change outside of contract execution, e.g. when fees are paid.
+		let mut addresses = diff.accounts();
+		for event in block.events().await?.iter() {
+			let event = event.map_err(subxt::Error::from)?;
+			if event.pallet_name() == "Balances" {
+				let fields = event.field_values().map_err(subxt::Error::from)?;
+				event_addresses(&fields, &mut addresses);
+			}
+		}
+
+		// The traced balances are the balances before each transaction, which may differ from the
+		// balances before the block.
+		let parent_hash = block.header().parent_hash;
+		for address in addresses {
+			let balance = self.balance(address, &parent_hash.into()).await?;
+			diff.balances.insert(address, balance);
+		}
+
+		Ok(diff)
+	}
+
+	/// Get the block number to look up in the state index for the given block, if enabled.
+	async fn state_index_at(
+		&self,
+		at: &BlockNumberOrTagOrHash,
+	) -> Option<(&StateIndex, SubstrateBlockNumber)> {
+		let state_index = self.state_index.as_ref()?;
+		let block_number = match at {
+			BlockNumberOrTagOrHash::U256(block_number) => (*block_number).try_into().ok()?,
+			BlockNumberOrTagOrHash::H256(hash) => state_index.block_number(hash).await?,
+			BlockNumberOrTagOrHash::BlockTag(tag) => {
+				let hash = self.tag_block_hash(tag).await.ok()?;
+				state_index.block_number(&hash).await?
+			},
+		};
+		Some((state_index, block_number))
+	}
+
 	/// Cache old blocks up to the given block number.
 	pub async fn cache_old_blocks(&self, oldest_block: SubstrateBlockNumber) {
 		let res = self
@@ -391,21 +543,39 @@ impl Client {
 		&self,
 		at: &BlockNumberOrTagOrHash,
 	) -> Result<Storage<SrcChainConfig, OnlineClient<SrcChainConfig>>, ClientError> {
+		let hash = self.block_hash(at).await?;
+		Ok(self.api.storage().at(hash))
+	}
+
+	/// Get the hash of the given block.
+	async fn block_hash(
+		&self,
+		at: &BlockNumberOrTagOrHash,
+	) -> Result<SubstrateBlockHash, ClientError> {
 		match at {
 			BlockNumberOrTagOrHash::U256(block_number) => {
 				let n: SubstrateBlockNumber =
 					(*block_number).try_into().map_err(|_| ClientError::ConversionFailed)?;
-
-				let hash = self.get_block_hash(n).await?.ok_or(ClientError::BlockNotFound)?;
-				Ok(self.api.storage().at(hash))
+				self.get_block_hash(n).await?.ok_or(ClientError::BlockNotFound)
 			},
-			BlockNumberOrTagOrHash::H256(hash) => Ok(self.api.storage().at(*hash)),
-			BlockNumberOrTagOrHash::BlockTag(_) => {
-				if let Some(block) = self.latest_block().await {
-					return Ok(self.api.storage().at(block.hash()));
-				}
-				let storage = self.api.storage().at_latest().await?;
-				Ok(storage)
+			BlockNumberOrTagOrHash::H256(hash) => Ok(*hash),
+			BlockNumberOrTagOrHash::BlockTag(tag) => self.tag_block_hash(tag).await,
+		}
+	}
+
+	/// Get the hash of the block the given tag currently points to.
+	///
+	/// `finalized` and `safe` both resolve to the finalized head, since blocks are final once
+	/// they are finalized by GRANDPA. `pending` resolves to the latest block, as pending
+	/// transactions are not executed ahead of block production.
+	async fn tag_block_hash(&self, tag: &BlockTag) -> Result<SubstrateBlockHash, ClientError> {
+		match tag {
+			BlockTag::Earliest => self.get_block_hash(0).await?.ok_or(ClientError::BlockNotFound),
+			BlockTag::Finalized | BlockTag::Safe => Ok(self.rpc.chain_get_finalized_head().await?),
+			BlockTag::Latest | BlockTag::Pending => match self.latest_block().await {
+				Some(block) => Ok(block.hash()),
+				None =>
+					self.rpc.chain_get_block_hash(None).await?.ok_or(ClientError::BlockNotFound),
 			},
 		}
 	}
@@ -418,24 +588,8 @@ impl Client {
 		subxt::runtime_api::RuntimeApi<SrcChainConfig, OnlineClient<SrcChainConfig>>,
 		ClientError,
 	> {
-		match at {
-			BlockNumberOrTagOrHash::U256(block_number) => {
-				let n: SubstrateBlockNumber =
-					(*block_number).try_into().map_err(|_| ClientError::ConversionFailed)?;
-
-				let hash = self.get_block_hash(n).await?.ok_or(ClientError::BlockNotFound)?;
-				Ok(self.api.runtime_api().at(hash))
-			},
-			BlockNumberOrTagOrHash::H256(hash) => Ok(self.api.runtime_api().at(*hash)),
-			BlockNumberOrTagOrHash::BlockTag(_) => {
-				if let Some(block) = self.latest_block().await {
-					return Ok(self.api.runtime_api().at(block.hash()));
-				}
-
-				let api = self.api.runtime_api().at_latest().await?;
-				Ok(api)
-			},
-		}
+		let hash = self.block_hash(at).await?;
+		Ok(self.api.runtime_api().at(hash))
 	}
 
 	/// Get the most recent block stored in the cache.
@@ -454,6 +608,22 @@ impl Client {
 		Ok(hash)
 	}
 
+	/// Notify the subscribers that a transaction has been submitted.
+	pub fn notify_pending_transaction(&self, transaction_hash: H256) {
+		// Sending only fails when there are no subscribers.
+		let _ = self.pending_transaction_notifier.send(transaction_hash);
+	}
+
+	/// Subscribe to the hashes of the new blocks, notified once they have been cached.
+	pub fn subscribe_block_hashes(&self) -> broadcast::Receiver<SubstrateBlockHash> {
+		self.block_notifier.subscribe()
+	}
+
+	/// Subscribe to the hashes of the transactions submitted through this client.
+	pub fn subscribe_pending_transactions(&self) -> broadcast::Receiver<H256> {
+		self.pending_transaction_notifier.subscribe()
+	}
+
 	/// Get an EVM transaction receipt by hash.
 	pub async fn receipt(&self, tx_hash: &H256) -> Option<ReceiptInfo> {
 		self.receipt_provider.receipt_by_hash(tx_hash).await
@@ -513,6 +683,12 @@ impl Client {
 		address: H160,
 		at: &BlockNumberOrTagOrHash,
 	) -> Result<U256, ClientError> {
+		if let Some((state_index, block_number)) = self.state_index_at(at).await {
+			if let Some(balance) = state_index.balance(&address, block_number).await {
+				return Ok(balance);
+			}
+		}
+
 		// TODO: remove once subxt is updated
 		let address = address.0.into();
 
@@ -528,8 +704,17 @@ impl Client {
 		&self,
 		contract_address: H160,
 		key: U256,
-		block: BlockNumberOrTagOrHash,
+		mut block: BlockNumberOrTagOrHash,
 	) -> Result<Vec<u8>, ClientError> {
+		if let Some((state_index, block_number)) = self.state_index_at(&block).await {
+			let key = key.to_big_endian();
+			match state_index.storage(&contract_address, &key, block_number).await {
+				Some(StateLookup::Value(value)) => return Ok(value.unwrap_or_default().0),
+				Some(StateLookup::At(hash)) => block = hash.into(),
+				None => {},
+			}
+		}
+
 		let runtime_api = self.runtime_api(&block).await?;
 
 		// TODO: remove once subxt is updated
@@ -546,8 +731,16 @@ impl Client {
 	pub async fn get_contract_code(
 		&self,
 		contract_address: &H160,
-		block: BlockNumberOrTagOrHash,
+		mut block: BlockNumberOrTagOrHash,
 	) -> Result<Vec<u8>, ClientError> {
+		if let Some((state_index, block_number)) = self.state_index_at(&block).await {
+			match state_index.code(contract_address, block_number).await {
+				Some(StateLookup::Value(code)) => return Ok(code.0),
+				Some(StateLookup::At(hash)) => block = hash.into(),
+				None => {},
+			}
+		}
+
 		let storage_api = self.storage_api(&block).await?;
 
 		// TODO: remove once subxt is updated
@@ -563,23 +756,166 @@ impl Client {
 		Ok(result)
 	}
 
+	/// Get the account proof and the storage proofs of the given storage keys of an address.
+	///
+	/// The account proof is a read proof of the main trie covering the account, its contract info
+	/// and the root of its child trie. Each storage proof is a read proof of the child trie of the
+	/// contract.
+	pub async fn get_proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		block: &BlockNumberOrTagOrHash,
+	) -> Result<AccountProof, ClientError> {
+		let hash = self.block_hash(block).await?;
+		self.ensure_revive_api_with(hash, |version| version >= 3, "eth_getProof")
+			.await?;
+
+		let params =
+			(address, storage_keys.iter().map(|key| key.to_big_endian()).collect::<Vec<_>>())
+				.encode();
+		let bytes = self
+			.rpc
+			.state_call("ReviveApi_account_proof_keys", Some(&params), Some(hash))
+			.await
+			.inspect_err(|err| {
+				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
+			})?;
+		let keys = AccountProofKeys::decode(&mut &bytes[..])?;
+
+		let client = RpcClient::new(self.rpc_client.clone());
+		let main_keys = keys
+			.account_keys
+			.iter()
+			.chain(keys.child_trie_key.iter())
+			.cloned()
+			.map(Bytes)
+			.collect::<Vec<_>>();
+		let account_proof: ReadProof<SubstrateBlockHash> =
+			client.request("state_getReadProof", rpc_params![main_keys, hash]).await?;
+
+		let at = hash.into();
+		let (balance, nonce) =
+			tokio::try_join!(self.balance(address, &at), self.nonce(address, hash.into()))?;
+
+		let mut proof = AccountProof {
+			address,
+			account_proof: account_proof.proof.into_iter().map(|node| Bytes(node.0)).collect(),
+			balance,
+			code_hash: keys.code_hash.unwrap_or_else(|| H256(keccak_256(&[]))),
+			nonce,
+			..Default::default()
+		};
+
+		let Some(child_trie_key) = keys.child_trie_key else {
+			proof.storage_proof = storage_keys
+				.into_iter()
+				.map(|key| StorageProof { key, ..Default::default() })
+				.collect();
+			return Ok(proof);
+		};
+
+		// The root of an empty child trie is not stored in the main trie.
+		let storage_root = self.api.storage().at(hash).fetch_raw(child_trie_key.clone()).await?;
+		proof.storage_hash = match storage_root {
+			Some(root) if root.len() == 32 => H256::from_slice(&root),
+			_ => H256(blake2_256(&[EMPTY_TRIE])),
+		};
+
+		let child_trie_key = Bytes(child_trie_key);
+		for (key, hashed_key) in storage_keys.into_iter().zip(keys.storage_keys) {
+			let hashed_key = Bytes(hashed_key);
+			let value: Option<Bytes> = client
+				.request("state_getChildStorage", rpc_params![&child_trie_key, &hashed_key, hash])
+				.await?;
+			let storage_proof: ReadProof<SubstrateBlockHash> = client
+				.request(
+					"state_getChildReadProof",
+					rpc_params![&child_trie_key, vec![hashed_key], hash],
+				)
+				.await?;
+
+			proof.storage_proof.push(StorageProof {
+				key,
+				value: value.unwrap_or_default(),
+				proof: storage_proof.proof.into_iter().map(|node| Bytes(node.0)).collect(),
+			});
+		}
+
+		Ok(proof)
+	}
+
 	/// Dry run a transaction and returns the [`EthTransactInfo`] for the transaction.
 	pub async fn dry_run(
 		&self,
 		tx: GenericTransaction,
 		block: BlockNumberOrTagOrHash,
 	) -> Result<EthTransactInfo<Balance>, ClientError> {
-		let runtime_api = self.runtime_api(&block).await?;
-		let payload = subxt_client::apis().revive_api().eth_transact(tx.into());
+		let hash = self.block_hash(&block).await?;
+		let params = self.encode_transaction(hash, tx).await?;
+		let bytes = self
+			.rpc
+			.state_call("ReviveApi_eth_transact", Some(&params), Some(hash))
+			.await
+			.inspect_err(|err| {
+				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
+			})?;
 
-		let result = runtime_api.call(payload).await?;
-		match result {
-			Err(err) => {
+		Result::<EthTransactInfo<Balance>, EthTransactError>::decode(&mut &bytes[..])?.map_err(
+			|err| {
 				log::debug!(target: LOG_TARGET, "Dry run failed {err:?}");
-				Err(ClientError::TransactError(err.0))
+				ClientError::TransactError(err)
 			},
-			Ok(result) => Ok(result.0),
+		)
+	}
+
+	/// Dry run a transaction and return the accounts and storage keys it accessed.
+	pub async fn create_access_list(
+		&self,
+		tx: GenericTransaction,
+		block: &BlockNumberOrTagOrHash,
+	) -> Result<AccessListResult, ClientError> {
+		let hash = self.block_hash(block).await?;
+		self.ensure_revive_api_with(hash, |version| version >= 4, "eth_createAccessList")
+			.await?;
+		let params = self.encode_transaction(hash, tx).await?;
+		let bytes = self
+			.rpc
+			.state_call("ReviveApi_create_access_list", Some(&params), Some(hash))
+			.await
+			.inspect_err(|err| {
+				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
+			})?;
+
+		Result::<AccessListResult, EthTransactError>::decode(&mut &bytes[..])?
+			.map_err(ClientError::TransactError)
+	}
+
+	/// Get the gas schedule used to estimate common operations.
+	///
+	/// Returns `None` if the runtime of the block does not provide a gas schedule.
+	pub async fn gas_schedule(
+		&self,
+		block: &BlockNumberOrTagOrHash,
+	) -> Result<Option<GasScheduleResult>, ClientError> {
+		let hash = self.block_hash(block).await?;
+		// Runtimes predating the gas schedule estimate every transaction from its dry run.
+		match self
+			.ensure_revive_api_with(hash, |version| version >= 5, "revive_gasSchedule")
+			.await
+		{
+			Err(ClientError::UnsupportedRuntimeApi(_)) => return Ok(None),
+			res => res?,
 		}
+		let bytes = self
+			.rpc
+			.state_call("ReviveApi_gas_schedule", None, Some(hash))
+			.await
+			.inspect_err(|err| {
+				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
+			})?;
+
+		Ok(Option::<GasScheduleResult>::decode(&mut &bytes[..])?)
 	}
 
 	/// Get the nonce of the given address.
@@ -622,10 +958,14 @@ impl Client {
 				let n = (*n).try_into().map_err(|_| ClientError::ConversionFailed)?;
 				self.block_by_number(n).await
 			},
-			BlockNumberOrTag::BlockTag(_) => {
+			BlockNumberOrTag::BlockTag(BlockTag::Latest | BlockTag::Pending) => {
 				let block = self.block_provider.latest_block().await;
 				Ok(block)
 			},
+			BlockNumberOrTag::BlockTag(tag) => {
+				let hash = self.tag_block_hash(tag).await?;
+				self.block_by_hash(&hash).await
+			},
 		}
 	}
 
@@ -661,12 +1001,41 @@ impl Client {
 			BlockNumberOrTag::U256(n) => {
 				let block_number: SubstrateBlockNumber =
 					n.try_into().map_err(|_| ClientError::ConversionFailed)?;
-				self.get_block_hash(block_number).await?
+				self.get_block_hash(block_number).await?.ok_or(ClientError::BlockNotFound)?
 			},
-			BlockNumberOrTag::BlockTag(_) => self.latest_block().await.map(|b| b.hash()),
+			BlockNumberOrTag::BlockTag(tag) => self.tag_block_hash(&tag).await?,
+		};
+
+		let traces = self.trace_block(block_hash, tracer_config.clone()).await?;
+
+		let mut hashes = self
+			.receipt_provider
+			.block_transaction_hashes(&block_hash)
+			.await
+			.ok_or(ClientError::EthExtrinsicNotFound)?;
+
+		let traces = traces
+			.into_iter()
+			.filter_map(|(index, trace)| {
+				Some(TransactionTrace { tx_hash: hashes.remove(&(index as usize))?, trace })
+			})
+			.collect::<Vec<_>>();
+
+		if let Some((state_index, block_number)) = self.finalized_state_index(&block_hash).await {
+			for TransactionTrace { tx_hash, trace } in &traces {
+				state_index.insert_trace(block_number, tx_hash, &tracer_config, trace).await;
+			}
 		}
-		.ok_or(ClientError::BlockNotFound)?;
 
+		Ok(traces)
+	}
+
+	/// Get the traces of the extrinsics of the given block, along with their index.
+	async fn trace_block(
+		&self,
+		block_hash: SubstrateBlockHash,
+		tracer_config: TracerConfig,
+	) -> Result<Vec<(u32, Trace)>, ClientError> {
 		let block = self
 			.rpc
 			.chain_get_block(Some(block_hash))
@@ -682,6 +1051,7 @@ impl Client {
 			.filter_map(|e| OpaqueExtrinsic::decode(&mut &e[..]).ok())
 			.collect::<Vec<_>>();
 
+		let legacy = self.is_legacy_tracing(parent_hash, &tracer_config).await?;
 		let params = ((header, exts), tracer_config).encode();
 
 		let bytes = self
@@ -692,22 +1062,101 @@ impl Client {
 				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
 			})?;
 
-		let traces = Vec::<(u32, CallTrace)>::decode(&mut &bytes[..])?;
+		if legacy {
+			let traces = Vec::<(u32, CallTrace)>::decode(&mut &bytes[..])?;
+			return Ok(traces
+				.into_iter()
+				.map(|(index, trace)| (index, Trace::Call(trace)))
+				.collect());
+		}
 
-		let mut hashes = self
-			.receipt_provider
-			.block_transaction_hashes(&block_hash)
+		Ok(Vec::<(u32, Trace)>::decode(&mut &bytes[..])?)
+	}
+
+	/// Get the runtime version of the given block.
+	async fn runtime_version(
+		&self,
+		block_hash: SubstrateBlockHash,
+	) -> Result<RuntimeVersion, ClientError> {
+		let client = RpcClient::new(self.rpc_client.clone());
+		Ok(client.request("state_getRuntimeVersion", rpc_params![block_hash]).await?)
+	}
+
+	/// Check that the `ReviveApi` runtime API of the given block satisfies `predicate`.
+	///
+	/// `operation` names the operation in the error returned otherwise.
+	async fn ensure_revive_api_with(
+		&self,
+		block_hash: SubstrateBlockHash,
+		predicate: impl Fn(u32) -> bool,
+		operation: &'static str,
+	) -> Result<(), ClientError> {
+		// The identifier derived by `sp_api::decl_runtime_apis!` from the trait name.
+		let api_id = blake2_64(b"ReviveApi");
+		if self.runtime_version(block_hash).await?.has_api_with(&api_id, predicate) {
+			Ok(())
+		} else {
+			Err(ClientError::UnsupportedRuntimeApi(operation))
+		}
+	}
+
+	/// Encode `tx` as expected by the `ReviveApi` runtime API of the given block.
+	///
+	/// Runtimes predating [`AUTHORIZATION_LIST_API_VERSION`] decode transactions without an
+	/// authorization list, thus EIP-7702 transactions are rejected for them.
+	async fn encode_transaction(
+		&self,
+		block_hash: SubstrateBlockHash,
+		tx: GenericTransaction,
+	) -> Result<Vec<u8>, ClientError> {
+		match self
+			.ensure_revive_api_with(
+				block_hash,
+				|version| version >= AUTHORIZATION_LIST_API_VERSION,
+				"EIP-7702 transactions",
+			)
 			.await
-			.ok_or(ClientError::EthExtrinsicNotFound)?;
+		{
+			Ok(()) => Ok(tx.encode()),
+			Err(ClientError::UnsupportedRuntimeApi(_)) if tx.authorization_list.is_empty() =>
+				Ok(LegacyGenericTransaction(tx).encode()),
+			Err(err) => Err(err),
+		}
+	}
 
-		let traces = traces
-			.into_iter()
-			.filter_map(|(index, trace)| {
-				Some(TransactionTrace { tx_hash: hashes.remove(&(index as usize))?, trace })
-			})
-			.collect();
+	/// Whether the tracing runtime APIs of the given block predate
+	/// [`TRACE_API_VERSION`] and return [`CallTrace`]s.
+	///
+	/// Fails if `tracer_config` is not supported by these APIs.
+	async fn is_legacy_tracing(
+		&self,
+		block_hash: SubstrateBlockHash,
+		tracer_config: &TracerConfig,
+	) -> Result<bool, ClientError> {
+		match self
+			.ensure_revive_api_with(block_hash, |version| version >= TRACE_API_VERSION, "tracing")
+			.await
+		{
+			Ok(()) => Ok(false),
+			Err(ClientError::UnsupportedRuntimeApi(_)) =>
+				if matches!(tracer_config, TracerConfig::CallTracer { .. }) {
+					Ok(true)
+				} else {
+					Err(ClientError::UnsupportedRuntimeApi("this tracer"))
+				},
+			Err(err) => Err(err),
+		}
+	}
 
-		Ok(traces)
+	/// Get the state index, if enabled and the given block is indexed, and therefore finalized,
+	/// along with the number of the block.
+	async fn finalized_state_index(
+		&self,
+		block_hash: &SubstrateBlockHash,
+	) -> Option<(&StateIndex, SubstrateBlockNumber)> {
+		let state_index = self.state_index.as_ref()?;
+		let block_number = state_index.block_number(block_hash).await?;
+		Some((state_index, block_number))
 	}
 
 	/// Get the transaction traces for the given transaction.
@@ -715,7 +1164,13 @@ impl Client {
 		&self,
 		transaction_hash: H256,
 		tracer_config: TracerConfig,
-	) -> Result<CallTrace, ClientError> {
+	) -> Result<Trace, ClientError> {
+		if let Some(state_index) = &self.state_index {
+			if let Some(trace) = state_index.trace(&transaction_hash, &tracer_config).await {
+				return Ok(trace);
+			}
+		}
+
 		let ReceiptInfo { block_hash, transaction_index, .. } = self
 			.receipt_provider
 			.receipt_by_hash(&transaction_hash)
@@ -740,7 +1195,8 @@ impl Client {
 			.filter_map(|e| OpaqueExtrinsic::decode(&mut &e[..]).ok())
 			.collect::<Vec<_>>();
 
-		let params = ((header, exts), transaction_index.as_u32(), tracer_config).encode();
+		let legacy = self.is_legacy_tracing(parent_hash, &tracer_config).await?;
+		let params = ((header, exts), transaction_index.as_u32(), tracer_config.clone()).encode();
 		let bytes = self
 			.rpc
 			.state_call("ReviveApi_trace_tx", Some(&params), Some(parent_hash))
@@ -749,8 +1205,19 @@ impl Client {
 				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
 			})?;
 
-		let trace = Option::<CallTrace>::decode(&mut &bytes[..])?;
-		trace.ok_or(ClientError::EthExtrinsicNotFound)
+		let trace = if legacy {
+			Option::<CallTrace>::decode(&mut &bytes[..])?.map(Trace::Call)
+		} else {
+			Option::<Trace>::decode(&mut &bytes[..])?
+		};
+		let trace = trace.ok_or(ClientError::EthExtrinsicNotFound)?;
+		if let Some((state_index, block_number)) = self.finalized_state_index(&block_hash).await {
+			state_index
+				.insert_trace(block_number, &transaction_hash, &tracer_config, &trace)
+				.await;
+		}
+
+		Ok(trace)
 	}
 
 	/// Get the transaction traces for the given block.
@@ -759,26 +1226,34 @@ impl Client {
 		transaction: GenericTransaction,
 		block: BlockNumberOrTag,
 		tracer_config: TracerConfig,
-	) -> Result<CallTrace, ClientError> {
+	) -> Result<Trace, ClientError> {
 		let block_hash = match block {
 			BlockNumberOrTag::U256(n) => {
 				let block_number: SubstrateBlockNumber =
 					n.try_into().map_err(|_| ClientError::ConversionFailed)?;
-				self.get_block_hash(block_number).await?
+				self.get_block_hash(block_number).await?.ok_or(ClientError::BlockNotFound)?
 			},
-			BlockNumberOrTag::BlockTag(_) => self.latest_block().await.map(|b| b.hash()),
+			BlockNumberOrTag::BlockTag(tag) => self.tag_block_hash(&tag).await?,
 		};
 
-		let params = (transaction, tracer_config).encode();
+		let legacy = self.is_legacy_tracing(block_hash, &tracer_config).await?;
+		let mut params = self.encode_transaction(block_hash, transaction).await?;
+		tracer_config.encode_to(&mut params);
 		let bytes = self
 			.rpc
-			.state_call("ReviveApi_trace_call", Some(&params), block_hash)
+			.state_call("ReviveApi_trace_call", Some(&params), Some(block_hash))
 			.await
 			.inspect_err(|err| {
 				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
 			})?;
 
-		Result::<CallTrace, EthTransactError>::decode(&mut &bytes[..])?
+		if legacy {
+			return Result::<CallTrace, EthTransactError>::decode(&mut &bytes[..])?
+				.map(Trace::Call)
+				.map_err(ClientError::TransactError);
+		}
+
+		Result::<Trace, EthTransactError>::decode(&mut &bytes[..])?
 			.map_err(ClientError::TransactError)
 	}
 	/// Get the EVM block for the given hash.
@@ -851,6 +1326,98 @@ impl Client {
 		self.max_block_weight
 	}
 
+	/// Get the receipts of the transactions of the given block.
+	pub async fn block_receipts(&self, block_hash: &SubstrateBlockHash) -> Vec<ReceiptInfo> {
+		let Some(hashes) = self.receipt_provider.block_transaction_hashes(block_hash).await else {
+			return Vec::new();
+		};
+
+		let mut receipts = Vec::with_capacity(hashes.len());
+		for hash in hashes.values() {
+			if let Some(receipt) = self.receipt_provider.receipt_by_hash(hash).await {
+				receipts.push(receipt);
+			}
+		}
+		receipts
+	}
+
+	/// Get the fee multiplier of `pallet-transaction-payment` at the given block.
+	pub async fn next_fee_multiplier(
+		&self,
+		block_hash: SubstrateBlockHash,
+	) -> Result<FixedU128, ClientError> {
+		let query = subxt_client::storage().transaction_payment().next_fee_multiplier();
+		let multiplier = self.api.storage().at(block_hash).fetch_or_default(&query).await?;
+		Ok(FixedU128::from_inner(multiplier.0))
+	}
+
+	/// Get the fee history of the `block_count` blocks ending with `newest_block`.
+	///
+	/// The reward percentiles must be monotonically increasing values within `[0, 100]`.
+	pub async fn fee_history(
+		&self,
+		block_count: SubstrateBlockNumber,
+		newest_block: &BlockNumberOrTag,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistoryResult, ClientError> {
+		let newest_block = self
+			.block_by_number_or_tag(newest_block)
+			.await?
+			.ok_or(ClientError::BlockNotFound)?;
+		let newest_number = newest_block.number();
+		let oldest_number = newest_number.saturating_sub(block_count.saturating_sub(1));
+
+		let mut result =
+			FeeHistoryResult { oldest_block: oldest_number.into(), ..Default::default() };
+		for number in oldest_number..=newest_number {
+			let block = if number == newest_number {
+				newest_block.clone()
+			} else {
+				self.block_by_number(number).await?.ok_or(ClientError::BlockNotFound)?
+			};
+
+			let fees = self.block_fees(&block).await?;
+			result.base_fee_per_gas.push(fees.base_fee_per_gas);
+			result.gas_used_ratio.push(fees.gas_used_ratio);
+			if !reward_percentiles.is_empty() {
+				result.reward.push(fees.rewards(reward_percentiles));
+			}
+		}
+
+		// The base fee of the next block is derived from the multiplier stored by the newest block.
+		let newest_hash = newest_block.hash();
+		let at = newest_hash.into();
+		let (gas_price, multiplier) =
+			tokio::try_join!(self.gas_price(&at), self.next_fee_multiplier(newest_hash))?;
+		result.base_fee_per_gas.push(base_fee_per_gas(gas_price, multiplier));
+
+		Ok(result)
+	}
+
+	/// Get the fees of the given block, from the cache if possible.
+	async fn block_fees(&self, block: &SubstrateBlock) -> Result<Arc<BlockFees>, ClientError> {
+		let block_hash = block.hash();
+		if let Some(fees) = self.fee_history_cache.read().await.get(&block_hash) {
+			return Ok(fees);
+		}
+
+		// The multiplier applied to the transactions of a block is the one stored by its parent.
+		let parent_hash = if block.number() == 0 { block_hash } else { block.header().parent_hash };
+		let runtime_api = self.api.runtime_api().at(block_hash);
+		let at = block_hash.into();
+		let (gas_price, multiplier, gas_limit) = tokio::try_join!(
+			self.gas_price(&at),
+			self.next_fee_multiplier(parent_hash),
+			Self::block_gas_limit(&runtime_api)
+		)?;
+
+		let receipts = self.block_receipts(&block_hash).await;
+		let fees =
+			Arc::new(BlockFees::new(base_fee_per_gas(gas_price, multiplier), gas_limit, &receipts));
+		self.fee_history_cache.write().await.insert(block_hash, fees.clone());
+		Ok(fees)
+	}
+
 	/// Get the logs matching the given filter.
 	pub async fn logs(&self, filter: Option<Filter>) -> Result<Vec<Log>, ClientError> {
 		let logs =
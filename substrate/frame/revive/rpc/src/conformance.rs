@@ -0,0 +1,468 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Ethereum JSON-RPC conformance test harness.
+//!
+//! Fixtures use the format of the
+//! [execution-apis](https://github.com/ethereum/execution-apis/tree/main/tests) test suite: each
+//! `.io` file contains a sequence of requests, prefixed with `>>`, each followed by the expected
+//! response, prefixed with `<<`. Lines starting with `//` are comments.
+//!
+//! Since recorded responses contain values specific to the chain they were recorded on, the
+//! following placeholders can be used in the expected responses, in place of a string value:
+//!
+//! - `"<quantity>"`: a hex encoded quantity, e.g. `"0x1a"`.
+//! - `"<data>"`: hex encoded bytes, e.g. `"0x01ff"`.
+//! - `"<hash>"`: 32 hex encoded bytes.
+//! - `"<address>"`: 20 hex encoded bytes.
+//! - `"<string>"`: any string.
+//! - `"<any>"`: any value.
+//!
+//! Errors are compared by code, their message and data are only compared if present in the
+//! expected response, since they are not mandated by the specification.
+//!
+//! A fixture containing a `// deviation: <reason>` comment documents a known deviation from the
+//! specification. It is still replayed and reported, but does not make the report fail.
+
+use jsonrpsee::core::{
+	client::{ClientT, Error as ClientError},
+	params::{ArrayParams, ObjectParams},
+};
+use serde_json::{Map, Value};
+use std::{
+	collections::BTreeMap,
+	fmt,
+	path::{Path, PathBuf},
+};
+
+/// The extension of the fixture files.
+const FIXTURE_EXTENSION: &str = "io";
+
+/// The prefix of the comment documenting a known deviation.
+const KNOWN_DEVIATION_PREFIX: &str = "// deviation:";
+
+/// A request and its expected response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exchange {
+	/// The method of the request.
+	pub method: String,
+	/// The params of the request.
+	pub params: Value,
+	/// The expected response.
+	pub response: Value,
+}
+
+/// A fixture, replayed against the RPC server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixture {
+	/// The name of the fixture, i.e. its path relative to the fixtures directory.
+	pub name: String,
+	/// The reason the fixture is known to deviate from the specification, if it does.
+	pub known_deviation: Option<String>,
+	/// The exchanges of the fixture, replayed in order.
+	pub exchanges: Vec<Exchange>,
+}
+
+impl Fixture {
+	/// Parse a fixture from the content of a `.io` file.
+	pub fn parse(name: impl Into<String>, content: &str) -> anyhow::Result<Self> {
+		let name = name.into();
+		let mut exchanges = Vec::new();
+		let mut request: Option<Value> = None;
+		let mut known_deviation = None;
+
+		for (index, line) in content.lines().enumerate() {
+			let line = line.trim();
+			let context = || format!("{name}:{}", index + 1);
+			if let Some(reason) = line.strip_prefix(KNOWN_DEVIATION_PREFIX) {
+				known_deviation = Some(reason.trim().to_string());
+				continue;
+			}
+			if line.is_empty() || line.starts_with("//") {
+				continue;
+			}
+
+			if let Some(json) = line.strip_prefix(">>") {
+				if request.is_some() {
+					anyhow::bail!("{}: request without a response", context());
+				}
+				request = Some(
+					serde_json::from_str(json)
+						.map_err(|err| anyhow::anyhow!("{}: invalid request: {err}", context()))?,
+				);
+			} else if let Some(json) = line.strip_prefix("<<") {
+				let Some(mut request) = request.take() else {
+					anyhow::bail!("{}: response without a request", context());
+				};
+				let response = serde_json::from_str(json)
+					.map_err(|err| anyhow::anyhow!("{}: invalid response: {err}", context()))?;
+				let Some(Value::String(method)) = request.get_mut("method").map(Value::take) else {
+					anyhow::bail!("{}: request without a method", context());
+				};
+				let params = request.get_mut("params").map(Value::take).unwrap_or(Value::Null);
+				exchanges.push(Exchange { method, params, response });
+			} else {
+				anyhow::bail!("{}: expected `>>`, `<<` or `//`", context());
+			}
+		}
+
+		if request.is_some() {
+			anyhow::bail!("{name}: request without a response");
+		}
+
+		Ok(Self { name, known_deviation, exchanges })
+	}
+
+	/// Load all the fixtures found under the given directory, sorted by name.
+	pub fn load_dir(dir: &Path) -> anyhow::Result<Vec<Self>> {
+		let mut paths = Vec::new();
+		collect_fixture_paths(dir, &mut paths)?;
+		paths.sort();
+
+		paths
+			.into_iter()
+			.map(|path| {
+				let name = path.strip_prefix(dir).unwrap_or(&path).display().to_string();
+				Self::parse(name, &std::fs::read_to_string(&path)?)
+			})
+			.collect()
+	}
+}
+
+/// Recursively collect the paths of the fixture files under the given directory.
+fn collect_fixture_paths(dir: &Path, paths: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+	for entry in std::fs::read_dir(dir)? {
+		let path = entry?.path();
+		if path.is_dir() {
+			collect_fixture_paths(&path, paths)?;
+		} else if path.extension().is_some_and(|ext| ext == FIXTURE_EXTENSION) {
+			paths.push(path);
+		}
+	}
+	Ok(())
+}
+
+/// The differences between the expected and the actual response of a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deviation {
+	/// The name of the fixture.
+	pub fixture: String,
+	/// A description of the differences.
+	pub message: String,
+	/// The reason the deviation is known, and therefore tolerated, if it is.
+	pub known: Option<String>,
+}
+
+/// The conformance of a method.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MethodReport {
+	/// The number of requests that got the expected response.
+	pub passed: usize,
+	/// The deviations of the requests that did not get the expected response.
+	pub deviations: Vec<Deviation>,
+}
+
+/// The conformance report of a set of fixtures, per method.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Report {
+	/// The report of each method.
+	pub methods: BTreeMap<String, MethodReport>,
+}
+
+impl Report {
+	/// Whether all the requests got the expected response, apart from the known deviations.
+	pub fn is_conformant(&self) -> bool {
+		self.methods
+			.values()
+			.all(|report| report.deviations.iter().all(|deviation| deviation.known.is_some()))
+	}
+}
+
+impl fmt::Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for (method, report) in &self.methods {
+			let total = report.passed + report.deviations.len();
+			writeln!(f, "{method}: {}/{total} passed", report.passed)?;
+			for Deviation { fixture, message, known } in &report.deviations {
+				match known {
+					Some(reason) => writeln!(f, "  - {fixture}: {message} (known: {reason})")?,
+					None => writeln!(f, "  - {fixture}: {message}")?,
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Replay the given fixtures against the RPC server, and report the deviations per method.
+pub async fn run(client: &impl ClientT, fixtures: &[Fixture]) -> Report {
+	let mut report = Report::default();
+	for fixture in fixtures {
+		for Exchange { method, params, response } in &fixture.exchanges {
+			let method_report = report.methods.entry(method.clone()).or_default();
+			let mut deviations = Vec::new();
+			match call(client, method, params).await {
+				Ok(actual) => compare_response(response, &actual, &mut deviations),
+				Err(err) => deviations.push(format!("request failed: {err}")),
+			}
+
+			if deviations.is_empty() {
+				method_report.passed += 1;
+			} else {
+				method_report.deviations.push(Deviation {
+					fixture: fixture.name.clone(),
+					message: deviations.join("; "),
+					known: fixture.known_deviation.clone(),
+				});
+			}
+		}
+	}
+	report
+}
+
+/// Send the request, and return the response as a JSON object with a `result` or an `error`.
+async fn call(client: &impl ClientT, method: &str, params: &Value) -> anyhow::Result<Value> {
+	let result = match params {
+		Value::Object(params) => {
+			let mut object_params = ObjectParams::new();
+			for (name, value) in params {
+				object_params.insert(name, value)?;
+			}
+			client.request::<Value, _>(method, object_params).await
+		},
+		Value::Array(params) => {
+			let mut array_params = ArrayParams::new();
+			for value in params {
+				array_params.insert(value)?;
+			}
+			client.request::<Value, _>(method, array_params).await
+		},
+		Value::Null => client.request::<Value, _>(method, ArrayParams::new()).await,
+		_ => anyhow::bail!("params must be an array or an object"),
+	};
+
+	let mut response = Map::new();
+	match result {
+		Ok(result) => {
+			response.insert("result".into(), result);
+		},
+		Err(ClientError::Call(err)) => {
+			let mut error = Map::new();
+			error.insert("code".into(), err.code().into());
+			error.insert("message".into(), err.message().into());
+			if let Some(data) = err.data() {
+				error.insert("data".into(), serde_json::from_str(data.get())?);
+			}
+			response.insert("error".into(), error.into());
+		},
+		Err(err) => return Err(err.into()),
+	}
+	Ok(response.into())
+}
+
+/// Compare the `result` or `error` of the expected and actual responses.
+fn compare_response(expected: &Value, actual: &Value, deviations: &mut Vec<String>) {
+	match (expected.get("result"), expected.get("error")) {
+		(Some(expected), _) => match actual.get("result") {
+			Some(actual) => compare(expected, actual, "result", deviations),
+			None => deviations.push(format!("expected a result, got {}", actual["error"])),
+		},
+		(None, Some(expected)) => match actual.get("error") {
+			Some(actual) => compare_error(expected, actual, deviations),
+			None => deviations.push(format!("expected an error, got {}", actual["result"])),
+		},
+		(None, None) => deviations.push("expected response has no result or error".into()),
+	}
+}
+
+/// Compare two errors by code, and by message and data if present in the expected error.
+fn compare_error(expected: &Value, actual: &Value, deviations: &mut Vec<String>) {
+	compare(&expected["code"], &actual["code"], "error.code", deviations);
+	for field in ["message", "data"] {
+		if let Some(expected) = expected.get(field) {
+			let path = format!("error.{field}");
+			match actual.get(field) {
+				Some(actual) => compare(expected, actual, &path, deviations),
+				None => deviations.push(format!("{path}: missing")),
+			}
+		}
+	}
+}
+
+/// Compare the expected and actual values, using the placeholders of the expected value.
+fn compare(expected: &Value, actual: &Value, path: &str, deviations: &mut Vec<String>) {
+	match (expected, actual) {
+		(Value::String(placeholder), actual) if is_placeholder(placeholder) =>
+			if !matches_placeholder(placeholder, actual) {
+				deviations.push(format!("{path}: expected {placeholder}, got {actual}"));
+			},
+		(Value::Object(expected), Value::Object(actual)) => {
+			for (key, expected) in expected {
+				let path = format!("{path}.{key}");
+				match actual.get(key) {
+					Some(actual) => compare(expected, actual, &path, deviations),
+					None => deviations.push(format!("{path}: missing")),
+				}
+			}
+			for key in actual.keys().filter(|key| !expected.contains_key(*key)) {
+				deviations.push(format!("{path}.{key}: unexpected"));
+			}
+		},
+		(Value::Array(expected), Value::Array(actual)) => {
+			if expected.len() != actual.len() {
+				deviations.push(format!(
+					"{path}: expected {} items, got {}",
+					expected.len(),
+					actual.len()
+				));
+				return;
+			}
+			for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+				compare(expected, actual, &format!("{path}[{index}]"), deviations);
+			}
+		},
+		(expected, actual) if expected != actual => {
+			deviations.push(format!("{path}: expected {expected}, got {actual}"));
+		},
+		_ => {},
+	}
+}
+
+/// Whether the given string is one of the supported placeholders.
+fn is_placeholder(value: &str) -> bool {
+	matches!(value, "<quantity>" | "<data>" | "<hash>" | "<address>" | "<string>" | "<any>")
+}
+
+/// Whether the value matches the given placeholder.
+fn matches_placeholder(placeholder: &str, value: &Value) -> bool {
+	let hex = value.as_str().and_then(|value| value.strip_prefix("0x"));
+	let is_hex = |hex: &str| hex.chars().all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c));
+	let data_len = |hex: &str| (hex.len() % 2 == 0 && is_hex(hex)).then_some(hex.len() / 2);
+
+	match placeholder {
+		"<quantity>" => hex.is_some_and(|hex| {
+			hex == "0" || (is_hex(hex) && !hex.is_empty() && !hex.starts_with('0'))
+		}),
+		"<data>" => hex.and_then(data_len).is_some(),
+		"<hash>" => hex.and_then(data_len) == Some(32),
+		"<address>" => hex.and_then(data_len) == Some(20),
+		"<string>" => value.is_string(),
+		_ => true,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use serde_json::json;
+
+	#[test]
+	fn parse_fixture_works() {
+		let content = r#"
+			// Get the chain id.
+			// deviation: not implemented
+			>> {"jsonrpc":"2.0","id":1,"method":"eth_chainId"}
+			<< {"jsonrpc":"2.0","id":1,"result":"0x1"}
+			>> {"jsonrpc":"2.0","id":2,"method":"eth_getBalance","params":["0x00","latest"]}
+			<< {"jsonrpc":"2.0","id":2,"error":{"code":-32602}}
+		"#;
+
+		assert_eq!(
+			Fixture::parse("test.io", content).unwrap(),
+			Fixture {
+				name: "test.io".into(),
+				known_deviation: Some("not implemented".into()),
+				exchanges: vec![
+					Exchange {
+						method: "eth_chainId".into(),
+						params: Value::Null,
+						response: json!({"jsonrpc":"2.0","id":1,"result":"0x1"}),
+					},
+					Exchange {
+						method: "eth_getBalance".into(),
+						params: json!(["0x00", "latest"]),
+						response: json!({"jsonrpc":"2.0","id":2,"error":{"code":-32602}}),
+					},
+				],
+			}
+		);
+
+		assert!(Fixture::parse("test.io", r#">> {"method":"eth_chainId"}"#).is_err());
+		assert!(Fixture::parse("test.io", r#"<< {"result":"0x1"}"#).is_err());
+	}
+
+	#[test]
+	fn placeholders_work() {
+		assert!(matches_placeholder("<quantity>", &json!("0x0")));
+		assert!(matches_placeholder("<quantity>", &json!("0x1a")));
+		assert!(!matches_placeholder("<quantity>", &json!("0x01")));
+		assert!(!matches_placeholder("<quantity>", &json!("0x")));
+		assert!(!matches_placeholder("<quantity>", &json!(1)));
+		assert!(matches_placeholder("<data>", &json!("0x")));
+		assert!(matches_placeholder("<data>", &json!("0x01ff")));
+		assert!(!matches_placeholder("<data>", &json!("0x1")));
+		assert!(!matches_placeholder("<data>", &json!("0x01FF")));
+		assert!(matches_placeholder("<hash>", &json!(format!("0x{}", "ab".repeat(32)))));
+		assert!(!matches_placeholder("<hash>", &json!(format!("0x{}", "ab".repeat(20)))));
+		assert!(matches_placeholder("<address>", &json!(format!("0x{}", "ab".repeat(20)))));
+		assert!(matches_placeholder("<string>", &json!("foo")));
+		assert!(matches_placeholder("<any>", &Value::Null));
+	}
+
+	#[test]
+	fn compare_response_works() {
+		let deviations = |expected: Value, actual: Value| {
+			let mut deviations = Vec::new();
+			compare_response(&expected, &actual, &mut deviations);
+			deviations
+		};
+
+		assert_eq!(
+			deviations(
+				json!({"result": {"number": "<quantity>", "hash": "<hash>", "uncles": []}}),
+				json!({"result": {"number": "0x2", "hash": format!("0x{}", "00".repeat(32)), "uncles": []}}),
+			),
+			Vec::<String>::new()
+		);
+
+		assert_eq!(
+			deviations(
+				json!({"result": {"miner": "<address>", "number": "0x1", "uncles": []}}),
+				json!({"result": {"number": "0x2", "size": "0x0", "uncles": ["0x00"]}}),
+			),
+			vec![
+				"result.miner: missing",
+				"result.number: expected \"0x1\", got \"0x2\"",
+				"result.uncles: expected 0 items, got 1",
+				"result.size: unexpected",
+			]
+		);
+
+		assert_eq!(
+			deviations(
+				json!({"error": {"code": 3, "data": "<data>"}}),
+				json!({"error": {"code": -32000, "message": "execution reverted"}}),
+			),
+			vec!["error.code: expected 3, got -32000", "error.data: missing"]
+		);
+
+		assert_eq!(
+			deviations(json!({"result": null}), json!({"error": {"code": -32601}})),
+			vec!["expected a result, got {\"code\":-32601}"]
+		);
+	}
+}
@@ -0,0 +1,200 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Fee oracle used to answer `eth_feeHistory`.
+
+use pallet_revive::evm::{ReceiptInfo, H256, U256};
+use sp_arithmetic::{FixedPointNumber, FixedU128};
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::Arc,
+};
+
+/// Compute the base fee per gas of a block, that is the gas price scaled by the fee multiplier of
+/// `pallet-transaction-payment` applied to the block's transactions.
+pub fn base_fee_per_gas(gas_price: U256, multiplier: FixedU128) -> U256 {
+	gas_price.saturating_mul(multiplier.into_inner().into()) / U256::from(FixedU128::DIV)
+}
+
+/// Check that the reward percentiles are within `[0, 100]` and monotonically increasing.
+pub fn is_valid_reward_percentiles(percentiles: &[f64]) -> bool {
+	percentiles.iter().all(|p| (0.0..=100.0).contains(p)) &&
+		percentiles.windows(2).all(|w| w[0] <= w[1])
+}
+
+/// The fees of a block, used to build the fee history.
+#[derive(Debug, Default, PartialEq)]
+pub struct BlockFees {
+	/// The base fee per gas of the block.
+	pub base_fee_per_gas: U256,
+	/// The gas used by the block's transactions divided by the block gas limit.
+	pub gas_used_ratio: f64,
+	/// The total gas used by the block's transactions.
+	gas_used: U256,
+	/// The gas used and the priority fee per gas of each transaction, sorted by priority fee.
+	rewards: Vec<(U256, U256)>,
+}
+
+impl BlockFees {
+	/// Create the [`BlockFees`] of a block, from its base fee, gas limit and receipts.
+	pub fn new(base_fee_per_gas: U256, gas_limit: U256, receipts: &[ReceiptInfo]) -> Self {
+		let mut rewards = receipts
+			.iter()
+			.map(|receipt| {
+				(receipt.gas_used, receipt.effective_gas_price.saturating_sub(base_fee_per_gas))
+			})
+			.collect::<Vec<_>>();
+		rewards.sort_by_key(|(_, reward)| *reward);
+
+		let gas_used = rewards.iter().fold(U256::zero(), |acc, (gas, _)| acc.saturating_add(*gas));
+		let as_f64 = |value: U256| u128::try_from(value).unwrap_or(u128::MAX) as f64;
+		let gas_used_ratio =
+			if gas_limit.is_zero() { 0.0 } else { as_f64(gas_used) / as_f64(gas_limit) };
+
+		Self { base_fee_per_gas, gas_used_ratio, gas_used, rewards }
+	}
+
+	/// Get the priority fees per gas at the given percentiles, weighted by the gas used.
+	///
+	/// The percentiles must be valid, see [`is_valid_reward_percentiles`].
+	pub fn rewards(&self, percentiles: &[f64]) -> Vec<U256> {
+		let Some((first_gas_used, _)) = self.rewards.first() else {
+			return vec![U256::zero(); percentiles.len()];
+		};
+
+		let mut index = 0;
+		let mut cumulative_gas_used = *first_gas_used;
+		percentiles
+			.iter()
+			.map(|percentile| {
+				let threshold = U256::from(
+					(u128::try_from(self.gas_used).unwrap_or(u128::MAX) as f64 * percentile / 100.0)
+						as u128,
+				);
+				while cumulative_gas_used < threshold && index < self.rewards.len() - 1 {
+					index += 1;
+					cumulative_gas_used = cumulative_gas_used.saturating_add(self.rewards[index].0);
+				}
+				self.rewards[index].1
+			})
+			.collect()
+	}
+}
+
+/// A cache of the fees of the last N blocks for which the fee history was requested.
+pub struct FeeHistoryCache {
+	/// The maximum number of cached blocks.
+	max_cache_size: usize,
+
+	/// The hashes of the cached blocks, from the least to the most recently inserted.
+	buffer: VecDeque<H256>,
+
+	/// The fees of the cached blocks, by block hash.
+	fees_by_hash: HashMap<H256, Arc<BlockFees>>,
+}
+
+impl FeeHistoryCache {
+	/// Create a new cache with the given maximum size.
+	pub fn new(max_cache_size: usize) -> Self {
+		Self { max_cache_size, buffer: Default::default(), fees_by_hash: Default::default() }
+	}
+
+	/// Get the fees of the given block.
+	pub fn get(&self, block_hash: &H256) -> Option<Arc<BlockFees>> {
+		self.fees_by_hash.get(block_hash).cloned()
+	}
+
+	/// Insert the fees of a block, and prune the oldest entry if the cache is full.
+	pub fn insert(&mut self, block_hash: H256, fees: Arc<BlockFees>) {
+		if self.fees_by_hash.insert(block_hash, fees).is_some() {
+			return;
+		}
+
+		if self.buffer.len() >= self.max_cache_size {
+			if let Some(hash) = self.buffer.pop_front() {
+				self.fees_by_hash.remove(&hash);
+			}
+		}
+		self.buffer.push_back(block_hash);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn receipt(gas_used: u64, effective_gas_price: u64) -> ReceiptInfo {
+		ReceiptInfo {
+			gas_used: gas_used.into(),
+			effective_gas_price: effective_gas_price.into(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn base_fee_per_gas_works() {
+		assert_eq!(base_fee_per_gas(1_000u32.into(), FixedU128::from_u32(1)), 1_000u32.into());
+		assert_eq!(
+			base_fee_per_gas(1_000u32.into(), FixedU128::from_rational(3, 2)),
+			1_500u32.into()
+		);
+	}
+
+	#[test]
+	fn reward_percentiles_validation_works() {
+		assert!(is_valid_reward_percentiles(&[]));
+		assert!(is_valid_reward_percentiles(&[0.0, 25.0, 25.0, 100.0]));
+		assert!(!is_valid_reward_percentiles(&[50.0, 25.0]));
+		assert!(!is_valid_reward_percentiles(&[101.0]));
+		assert!(!is_valid_reward_percentiles(&[-1.0]));
+	}
+
+	#[test]
+	fn block_fees_works() {
+		let fees = BlockFees::new(1_000u32.into(), 1_000u32.into(), &[]);
+		assert_eq!(fees.gas_used_ratio, 0.0);
+		assert_eq!(fees.rewards(&[10.0, 90.0]), vec![U256::zero(); 2]);
+
+		let receipts = [receipt(100, 1_300), receipt(300, 1_100), receipt(100, 1_200)];
+		let fees = BlockFees::new(1_000u32.into(), 1_000u32.into(), &receipts);
+		assert_eq!(fees.gas_used_ratio, 0.5);
+		assert_eq!(
+			fees.rewards(&[0.0, 60.0, 61.0, 100.0]),
+			vec![100u32.into(), 100u32.into(), 200u32.into(), 300u32.into()]
+		);
+
+		// Transactions paying less than the base fee have no reward.
+		let fees = BlockFees::new(1_000u32.into(), 1_000u32.into(), &[receipt(100, 900)]);
+		assert_eq!(fees.rewards(&[50.0]), vec![U256::zero()]);
+	}
+
+	#[test]
+	fn cache_insert_works() {
+		let mut cache = FeeHistoryCache::new(2);
+		let fees = Arc::new(BlockFees::default());
+
+		cache.insert(H256::from([1; 32]), fees.clone());
+		cache.insert(H256::from([2; 32]), fees.clone());
+		cache.insert(H256::from([2; 32]), fees.clone());
+		assert!(cache.get(&H256::from([1; 32])).is_some());
+
+		cache.insert(H256::from([3; 32]), fees);
+		assert!(cache.get(&H256::from([1; 32])).is_none());
+		assert!(cache.get(&H256::from([2; 32])).is_some());
+		assert!(cache.get(&H256::from([3; 32])).is_some());
+		assert_eq!(cache.buffer.len(), 2);
+	}
+}
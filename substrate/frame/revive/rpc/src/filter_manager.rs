@@ -0,0 +1,266 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	client::{Client, Shared, SubstrateBlockNumber},
+	EthRpcError, LOG_TARGET,
+};
+use jsonrpsee::ConnectionId;
+use pallet_revive::evm::{BlockNumberOrTag, Filter, FilterResults, H256, U256};
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::Arc,
+	time::{Duration, Instant},
+};
+use tokio::sync::{broadcast::error::RecvError, RwLock};
+
+/// Filters that are not polled within this duration are uninstalled.
+const FILTER_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The maximum number of filters that can be installed at the same time.
+const MAX_FILTERS: usize = 1024;
+
+/// The maximum number of filters a single connection can install at the same time.
+const MAX_FILTERS_PER_CONNECTION: usize = 64;
+
+/// The maximum number of transaction hashes a pending transaction filter buffers between polls.
+const MAX_PENDING_TRANSACTIONS: usize = 1024;
+
+/// The kind of an installed filter, along with its state.
+enum FilterKind {
+	/// Matches the logs of the new blocks.
+	Logs(Filter),
+	/// Matches the new blocks.
+	Blocks,
+	/// Matches the transactions submitted through this node, buffered until the next poll.
+	PendingTransactions(VecDeque<H256>),
+}
+
+/// A filter installed by an RPC client.
+struct InstalledFilter {
+	/// The kind of the filter.
+	kind: FilterKind,
+	/// The connection that installed the filter.
+	connection: ConnectionId,
+	/// The last block reported to the RPC client.
+	last_block: SubstrateBlockNumber,
+	/// The last time the filter was polled.
+	last_poll: Instant,
+}
+
+/// Manages the filters installed with `eth_newFilter`, `eth_newBlockFilter` and
+/// `eth_newPendingTransactionFilter`.
+///
+/// Block and log filters keep track of the last block they reported, and read the changes from
+/// the block and receipt providers of the [`Client`]. Pending transaction filters buffer the
+/// hashes of the transactions submitted through this node until they are polled.
+#[derive(Clone)]
+pub struct FilterManager {
+	/// The client used to fetch the blocks and logs.
+	client: Client,
+	/// The installed filters, by id.
+	///
+	/// The ids are random 128-bit numbers, so that a filter can't be polled or uninstalled by
+	/// guessing its id.
+	filters: Shared<HashMap<U256, InstalledFilter>>,
+}
+
+impl FilterManager {
+	/// Create a new [`FilterManager`].
+	pub fn new(client: Client) -> Self {
+		Self { client, filters: Arc::new(RwLock::new(HashMap::new())) }
+	}
+
+	/// Install a filter matching the logs of the new blocks, for the given connection.
+	pub async fn new_log_filter(
+		&self,
+		connection: ConnectionId,
+		filter: Filter,
+	) -> Result<U256, EthRpcError> {
+		if filter.block_hash.is_some() {
+			return Err(EthRpcError::UnsupportedFilter("blockHash"));
+		}
+		self.install(connection, FilterKind::Logs(filter)).await
+	}
+
+	/// Install a filter matching the new blocks, for the given connection.
+	pub async fn new_block_filter(&self, connection: ConnectionId) -> Result<U256, EthRpcError> {
+		self.install(connection, FilterKind::Blocks).await
+	}
+
+	/// Install a filter matching the transactions submitted through this node, for the given
+	/// connection.
+	pub async fn new_pending_transaction_filter(
+		&self,
+		connection: ConnectionId,
+	) -> Result<U256, EthRpcError> {
+		self.install(connection, FilterKind::PendingTransactions(VecDeque::new())).await
+	}
+
+	/// Uninstall the filter with the given id, returning whether it existed.
+	pub async fn uninstall(&self, id: U256) -> bool {
+		self.filters.write().await.remove(&id).is_some()
+	}
+
+	/// Get the changes matching the filter with the given id, since it was last polled.
+	///
+	/// The last block reported by a block or log filter only advances once the changes were
+	/// fetched, so that a failed poll can be retried without missing any change.
+	pub async fn filter_changes(&self, id: U256) -> Result<FilterResults, EthRpcError> {
+		let latest_block = self.client.block_number().await?;
+
+		let (from_block, log_filter) = {
+			let mut filters = self.filters.write().await;
+			let filter = filters.get_mut(&id).ok_or(EthRpcError::FilterNotFound)?;
+			filter.last_poll = Instant::now();
+
+			let from_block = filter.last_block + 1;
+			match &mut filter.kind {
+				FilterKind::PendingTransactions(hashes) =>
+					return Ok(FilterResults::Hashes(hashes.drain(..).collect())),
+				FilterKind::Blocks => (from_block, None),
+				FilterKind::Logs(filter) => (from_block, Some(filter.clone())),
+			}
+		};
+
+		let changes = self.fetch_changes(from_block, latest_block, log_filter).await?;
+
+		if let Some(filter) = self.filters.write().await.get_mut(&id) {
+			filter.last_block = filter.last_block.max(latest_block);
+		}
+		Ok(changes)
+	}
+
+	/// Fetch the new blocks, or the logs matching `log_filter`, from `from_block` to
+	/// `latest_block`.
+	async fn fetch_changes(
+		&self,
+		from_block: SubstrateBlockNumber,
+		latest_block: SubstrateBlockNumber,
+		log_filter: Option<Filter>,
+	) -> Result<FilterResults, EthRpcError> {
+		let Some(filter) = log_filter else {
+			let mut hashes = Vec::new();
+			for number in from_block..=latest_block {
+				if let Some(hash) = self.client.get_block_hash(number).await? {
+					hashes.push(hash);
+				}
+			}
+			return Ok(FilterResults::Hashes(hashes));
+		};
+
+		// Restrict the block range requested when installing the filter to the new blocks.
+		let from_block = match filter.from_block {
+			Some(BlockNumberOrTag::U256(n)) => n.max(from_block.into()),
+			_ => from_block.into(),
+		};
+		let to_block = match filter.to_block {
+			Some(BlockNumberOrTag::U256(n)) => n.min(latest_block.into()),
+			_ => latest_block.into(),
+		};
+
+		if from_block > to_block {
+			return Ok(FilterResults::Logs(Vec::new()));
+		}
+
+		let filter = Filter {
+			from_block: Some(from_block.into()),
+			to_block: Some(to_block.into()),
+			..filter
+		};
+		let logs = self.client.logs(Some(filter)).await?;
+		Ok(FilterResults::Logs(logs))
+	}
+
+	/// Get all the logs matching the log filter with the given id.
+	pub async fn filter_logs(&self, id: U256) -> Result<FilterResults, EthRpcError> {
+		let filter = match self.filters.read().await.get(&id).map(|filter| &filter.kind) {
+			Some(FilterKind::Logs(filter)) => filter.clone(),
+			_ => return Err(EthRpcError::FilterNotFound),
+		};
+
+		let logs = self.client.logs(Some(filter)).await?;
+		Ok(FilterResults::Logs(logs))
+	}
+
+	/// Buffer the transactions submitted through this node in the pending transaction filters,
+	/// and uninstall the filters that have not been polled for [`FILTER_TIMEOUT`].
+	pub async fn run(&self) {
+		let mut pending_transactions = self.client.subscribe_pending_transactions();
+		let mut interval = tokio::time::interval(FILTER_TIMEOUT / 10);
+
+		loop {
+			tokio::select! {
+				_ = interval.tick() => {
+					let now = Instant::now();
+					self.filters.write().await.retain(|id, filter| {
+						let expired = now.duration_since(filter.last_poll) >= FILTER_TIMEOUT;
+						if expired {
+							log::debug!(target: LOG_TARGET, "Uninstalling expired filter {id:?}");
+						}
+						!expired
+					});
+				},
+				hash = pending_transactions.recv() => match hash {
+					Ok(hash) => {
+						for filter in self.filters.write().await.values_mut() {
+							if let FilterKind::PendingTransactions(hashes) = &mut filter.kind {
+								if hashes.len() >= MAX_PENDING_TRANSACTIONS {
+									hashes.pop_front();
+								}
+								hashes.push_back(hash);
+							}
+						}
+					},
+					Err(RecvError::Lagged(skipped)) => {
+						log::warn!(target: LOG_TARGET, "Pending transaction filters missed {skipped} transactions");
+					},
+					Err(RecvError::Closed) => return,
+				},
+			}
+		}
+	}
+
+	/// Install a filter of the given kind for the given connection, returning its id.
+	async fn install(
+		&self,
+		connection: ConnectionId,
+		kind: FilterKind,
+	) -> Result<U256, EthRpcError> {
+		let last_block = self.client.block_number().await?;
+
+		let mut filters = self.filters.write().await;
+		if filters.len() >= MAX_FILTERS ||
+			filters.values().filter(|filter| filter.connection == connection).count() >=
+				MAX_FILTERS_PER_CONNECTION
+		{
+			return Err(EthRpcError::TooManyFilters);
+		}
+
+		let id = loop {
+			let id = U256::from(rand::random::<u128>());
+			if !filters.contains_key(&id) {
+				break id;
+			}
+		};
+		filters.insert(
+			id,
+			InstalledFilter { kind, connection, last_block, last_poll: Instant::now() },
+		);
+		Ok(id)
+	}
+}
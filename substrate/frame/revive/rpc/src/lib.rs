@@ -29,6 +29,7 @@ use thiserror::Error;
 
 pub mod cli;
 pub mod client;
+pub mod conformance;
 pub mod example;
 pub mod subxt_client;
 
@@ -44,11 +45,22 @@ pub use receipt_provider::*;
 mod receipt_extractor;
 pub use receipt_extractor::*;
 
+mod filter_manager;
+pub use filter_manager::*;
+
+mod fee_history;
+
+mod state_index;
+pub use state_index::*;
+
 mod apis;
 pub use apis::*;
 
 pub const LOG_TARGET: &str = "eth-rpc";
 
+/// The maximum number of blocks that can be requested with `eth_feeHistory`.
+const MAX_FEE_HISTORY_BLOCK_COUNT: u32 = 1024;
+
 /// An EVM RPC server implementation.
 pub struct EthRpcServerImpl {
 	/// The client used to interact with the substrate node.
@@ -95,6 +107,18 @@ pub enum EthRpcError {
 	/// Received an invalid transaction
 	#[error("Invalid transaction {0:?}")]
 	TransactionTypeNotSupported(Byte),
+	/// The reward percentiles are not monotonically increasing values within `[0, 100]`.
+	#[error("Invalid reward percentiles")]
+	InvalidRewardPercentiles,
+	/// The filter does not exist, or has expired.
+	#[error("Filter not found")]
+	FilterNotFound,
+	/// The maximum number of installed filters has been reached.
+	#[error("Too many filters installed")]
+	TooManyFilters,
+	/// The filter uses a feature that is not supported.
+	#[error("Unsupported filter: {0}")]
+	UnsupportedFilter(&'static str),
 }
 
 // TODO use https://eips.ethereum.org/EIPS/eip-1474#error-codes
@@ -165,6 +189,8 @@ impl EthRpcServer for EthRpcServerImpl {
 			err
 		})?;
 
+		self.client.notify_pending_transaction(hash);
+
 		log::debug!(target: LOG_TARGET, "send_raw_transaction hash: {hash:?}");
 		Ok(hash)
 	}
@@ -227,6 +253,36 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(self.client.chain_id().into())
 	}
 
+	async fn create_access_list(
+		&self,
+		transaction: GenericTransaction,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<AccessListResult> {
+		let block = block.unwrap_or_else(|| BlockTag::Latest.into());
+		Ok(self.client.create_access_list(transaction, &block).await?)
+	}
+
+	async fn fee_history(
+		&self,
+		block_count: U256,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> RpcResult<FeeHistoryResult> {
+		let block_count = block_count.min(MAX_FEE_HISTORY_BLOCK_COUNT.into()).as_u32();
+		if block_count == 0 {
+			return Ok(FeeHistoryResult::default());
+		}
+
+		let reward_percentiles = reward_percentiles.unwrap_or_default();
+		if !fee_history::is_valid_reward_percentiles(&reward_percentiles) {
+			return Err(EthRpcError::InvalidRewardPercentiles.into());
+		}
+
+		let history =
+			self.client.fee_history(block_count, &newest_block, &reward_percentiles).await?;
+		Ok(history)
+	}
+
 	async fn gas_price(&self) -> RpcResult<U256> {
 		Ok(self.client.gas_price(&BlockTag::Latest.into()).await?)
 	}
@@ -289,6 +345,16 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(FilterResults::Logs(logs))
 	}
 
+	async fn get_proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<AccountProof> {
+		let proof = self.client.get_proof(address, storage_keys, &block).await?;
+		Ok(proof)
+	}
+
 	async fn get_storage_at(
 		&self,
 		address: H160,
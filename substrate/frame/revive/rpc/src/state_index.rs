@@ -0,0 +1,674 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! A local index of the state changes of finalized blocks, used to answer historical state
+//! queries without an archive node.
+//!
+//! For each indexed block, the index stores the value, before the block, of every balance, code
+//! and storage slot changed by the block. The value at block `n` is then the value before the
+//! first block after `n` that changed it, or the value at the latest indexed block if it was not
+//! changed since `n`. Queries can only be answered if all the blocks after `n` are indexed.
+//!
+//! Balances also change outside of contract execution, without necessarily emitting an event, so
+//! a balance that did not change since `n` according to the index is read at block `n` instead.
+
+use crate::LOG_TARGET;
+use codec::{Decode, Encode};
+use pallet_revive::evm::{Bytes, PrestateTrace, Trace, TracerConfig, H160, H256, U256};
+use sp_core::keccak_256;
+use sqlx::{Row, SqlitePool};
+use std::collections::{BTreeMap, BTreeSet};
+use subxt::ext::scale_value::{Composite, Value, ValueDef};
+
+/// The result of a lookup in the [`StateIndex`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateLookup<T> {
+	/// The value at the requested block.
+	Value(T),
+	/// The value did not change since the requested block, and can be read at the given block.
+	At(H256),
+}
+
+/// The state of the accounts changed by a block, before the block.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BlockStateDiff {
+	/// The balance of the changed accounts.
+	pub balances: BTreeMap<H160, U256>,
+	/// The code of the accounts whose code changed.
+	pub code: BTreeMap<H160, Bytes>,
+	/// The changed storage values, `None` if the key was absent.
+	pub storage: BTreeMap<(H160, Bytes), Option<Bytes>>,
+	/// The accounts removed by the block, along with all their storage.
+	pub removed: BTreeSet<H160>,
+}
+
+impl BlockStateDiff {
+	/// Build the diff of a block from the prestate traces, in diff mode, of its transactions.
+	///
+	/// Only the balances of the accounts touched by contract execution are recorded, as they were
+	/// before the first transaction touching them.
+	pub fn from_traces<'a>(traces: impl IntoIterator<Item = &'a Trace>) -> Self {
+		let mut diff = Self::default();
+		for trace in traces {
+			let Trace::Prestate(PrestateTrace::DiffMode { pre, post }) = trace else { continue };
+
+			for (address, pre_info) in pre {
+				let post_info = post.get(address);
+				if let Some(balance) = pre_info.balance {
+					diff.balances.entry(*address).or_insert(balance);
+				}
+				if post_info.is_none_or(|info| info.code.is_some()) {
+					diff.code
+						.entry(*address)
+						.or_insert_with(|| pre_info.code.clone().unwrap_or_default());
+				}
+
+				// Values read once the account was removed are not the values before the block.
+				if !diff.removed.contains(address) {
+					for (key, value) in &pre_info.storage {
+						diff.storage
+							.entry((*address, key.clone()))
+							.or_insert_with(|| value.clone());
+					}
+				}
+
+				if post_info.is_none() {
+					diff.removed.insert(*address);
+				}
+			}
+		}
+		diff
+	}
+
+	/// The accounts whose balance, code or storage changed.
+	pub fn accounts(&self) -> BTreeSet<H160> {
+		self.balances
+			.keys()
+			.chain(self.code.keys())
+			.chain(self.storage.keys().map(|(address, _)| address))
+			.chain(self.removed.iter())
+			.copied()
+			.collect()
+	}
+}
+
+/// Collect the addresses of the account ids found in the fields of an event.
+pub fn event_addresses(fields: &Composite<u32>, addresses: &mut BTreeSet<H160>) {
+	if let Some(account_id) = as_account_id(fields) {
+		addresses.insert(to_address(&account_id));
+		return;
+	}
+
+	for value in fields.values() {
+		match &value.value {
+			ValueDef::Composite(composite) => event_addresses(composite, addresses),
+			ValueDef::Variant(variant) => event_addresses(&variant.values, addresses),
+			_ => {},
+		}
+	}
+}
+
+/// Interpret a composite of 32 bytes as an account id.
+fn as_account_id(composite: &Composite<u32>) -> Option<[u8; 32]> {
+	if composite.len() != 32 {
+		return None;
+	}
+
+	let mut account_id = [0u8; 32];
+	for (byte, value) in account_id.iter_mut().zip(composite.values()) {
+		*byte = Value::as_u128(value)?.try_into().ok()?;
+	}
+	Some(account_id)
+}
+
+/// Map an account id to its address, the same way `pallet_revive::AccountId32Mapper` does.
+fn to_address(account_id: &[u8; 32]) -> H160 {
+	if account_id[20..] == [0xEE; 12] {
+		H160::from_slice(&account_id[..20])
+	} else {
+		H160::from_slice(&keccak_256(account_id)[12..])
+	}
+}
+
+/// A SQLite index of the state changes and transaction traces of finalized blocks.
+#[derive(Clone)]
+pub struct StateIndex {
+	/// The database pool.
+	pool: SqlitePool,
+	/// The number of blocks, before the latest indexed block, whose traces are kept.
+	trace_cache_blocks: u32,
+}
+
+impl StateIndex {
+	/// Create a new `StateIndex` with the given database URL, keeping the traces of the last
+	/// `trace_cache_blocks` indexed blocks.
+	pub async fn new(database_url: &str, trace_cache_blocks: u32) -> Result<Self, sqlx::Error> {
+		let pool = SqlitePool::connect(database_url).await?;
+		sqlx::migrate!().run(&pool).await?;
+		Ok(Self { pool, trace_cache_blocks })
+	}
+
+	/// Insert the state changes of the given block.
+	pub async fn insert_block(
+		&self,
+		block_number: u32,
+		block_hash: &H256,
+		diff: &BlockStateDiff,
+	) -> Result<(), sqlx::Error> {
+		let block_number = block_number as i64;
+		let mut tx = self.pool.begin().await?;
+
+		for (address, balance) in &diff.balances {
+			sqlx::query(
+				r#"
+				INSERT OR REPLACE INTO balance_changes (address, block_number, balance)
+				VALUES ($1, $2, $3)
+				"#,
+			)
+			.bind(address.as_bytes())
+			.bind(block_number)
+			.bind(balance.to_big_endian().to_vec())
+			.execute(&mut *tx)
+			.await?;
+		}
+
+		for (address, code) in &diff.code {
+			sqlx::query(
+				r#"
+				INSERT OR REPLACE INTO code_changes (address, block_number, code)
+				VALUES ($1, $2, $3)
+				"#,
+			)
+			.bind(address.as_bytes())
+			.bind(block_number)
+			.bind(&code.0[..])
+			.execute(&mut *tx)
+			.await?;
+		}
+
+		for ((address, key), value) in &diff.storage {
+			sqlx::query(
+				r#"
+				INSERT OR REPLACE INTO storage_changes (address, storage_key, block_number, value)
+				VALUES ($1, $2, $3, $4)
+				"#,
+			)
+			.bind(address.as_bytes())
+			.bind(&key.0[..])
+			.bind(block_number)
+			.bind(value.as_ref().map(|value| &value.0[..]))
+			.execute(&mut *tx)
+			.await?;
+		}
+
+		for address in &diff.removed {
+			sqlx::query(
+				r#"
+				INSERT OR REPLACE INTO removed_accounts (address, block_number)
+				VALUES ($1, $2)
+				"#,
+			)
+			.bind(address.as_bytes())
+			.bind(block_number)
+			.execute(&mut *tx)
+			.await?;
+		}
+
+		sqlx::query(
+			r#"
+			INSERT OR REPLACE INTO state_index_blocks (block_number, block_hash)
+			VALUES ($1, $2)
+			"#,
+		)
+		.bind(block_number)
+		.bind(block_hash.as_bytes())
+		.execute(&mut *tx)
+		.await?;
+
+		sqlx::query(
+			r#"
+			DELETE FROM traces
+			WHERE block_number <= $1
+			"#,
+		)
+		.bind(block_number - self.trace_cache_blocks as i64)
+		.execute(&mut *tx)
+		.await?;
+
+		tx.commit().await
+	}
+
+	/// Get the number of the latest indexed block.
+	pub async fn latest_block_number(&self) -> Result<Option<u32>, sqlx::Error> {
+		let block_number: Option<i64> = sqlx::query_scalar(
+			r#"
+			SELECT MAX(block_number)
+			FROM state_index_blocks
+			"#,
+		)
+		.fetch_one(&self.pool)
+		.await?;
+
+		Ok(block_number.and_then(|block_number| block_number.try_into().ok()))
+	}
+
+	/// Get the number of the given indexed block.
+	pub async fn block_number(&self, block_hash: &H256) -> Option<u32> {
+		let block_number: i64 = sqlx::query_scalar(
+			r#"
+			SELECT block_number
+			FROM state_index_blocks
+			WHERE block_hash = $1
+			"#,
+		)
+		.bind(block_hash.as_bytes())
+		.fetch_optional(&self.pool)
+		.await
+		.ok()??;
+
+		block_number.try_into().ok()
+	}
+
+	/// Get the hash of the latest indexed block, provided that all the blocks after the given
+	/// block are indexed.
+	async fn latest_block_after(&self, block_number: i64) -> Option<H256> {
+		let row = sqlx::query(
+			r#"
+			SELECT MAX(block_number) AS latest, COUNT(*) AS count
+			FROM state_index_blocks
+			WHERE block_number > $1
+			"#,
+		)
+		.bind(block_number)
+		.fetch_one(&self.pool)
+		.await
+		.ok()?;
+
+		let latest: i64 = row.try_get::<Option<i64>, _>("latest").ok()??;
+		let count: i64 = row.try_get("count").ok()?;
+		if latest - block_number != count {
+			log::trace!(target: LOG_TARGET, "State index has gaps after block #{block_number}");
+			return None;
+		}
+
+		let block_hash: Vec<u8> = sqlx::query_scalar(
+			r#"
+			SELECT block_hash
+			FROM state_index_blocks
+			WHERE block_number = $1
+			"#,
+		)
+		.bind(latest)
+		.fetch_one(&self.pool)
+		.await
+		.ok()?;
+
+		Some(H256::from_slice(&block_hash))
+	}
+
+	/// Get the balance of the given address at the given block.
+	///
+	/// Returns `None` if no change of the balance after the block is recorded, in which case the
+	/// balance must be read at the block itself.
+	pub async fn balance(&self, address: &H160, block_number: u32) -> Option<U256> {
+		let block_number = block_number as i64;
+		self.latest_block_after(block_number).await?;
+
+		let balance: Option<Vec<u8>> = sqlx::query_scalar(
+			r#"
+			SELECT balance
+			FROM balance_changes
+			WHERE address = $1 AND block_number > $2
+			ORDER BY block_number ASC
+			LIMIT 1
+			"#,
+		)
+		.bind(address.as_bytes())
+		.bind(block_number)
+		.fetch_optional(&self.pool)
+		.await
+		.ok()?;
+
+		balance.map(|balance| U256::from_big_endian(&balance))
+	}
+
+	/// Get the code of the given address at the given block.
+	pub async fn code(&self, address: &H160, block_number: u32) -> Option<StateLookup<Bytes>> {
+		let block_number = block_number as i64;
+		let latest = self.latest_block_after(block_number).await?;
+
+		let code: Option<Vec<u8>> = sqlx::query_scalar(
+			r#"
+			SELECT code
+			FROM code_changes
+			WHERE address = $1 AND block_number > $2
+			ORDER BY block_number ASC
+			LIMIT 1
+			"#,
+		)
+		.bind(address.as_bytes())
+		.bind(block_number)
+		.fetch_optional(&self.pool)
+		.await
+		.ok()?;
+
+		Some(match code {
+			Some(code) => StateLookup::Value(Bytes(code)),
+			None => StateLookup::At(latest),
+		})
+	}
+
+	/// Get the storage value of the given address and key at the given block.
+	pub async fn storage(
+		&self,
+		address: &H160,
+		key: &[u8],
+		block_number: u32,
+	) -> Option<StateLookup<Option<Bytes>>> {
+		let block_number = block_number as i64;
+		let latest = self.latest_block_after(block_number).await?;
+
+		let changed: Option<(i64, Option<Vec<u8>>)> = sqlx::query_as(
+			r#"
+			SELECT block_number, value
+			FROM storage_changes
+			WHERE address = $1 AND storage_key = $2 AND block_number > $3
+			ORDER BY block_number ASC
+			LIMIT 1
+			"#,
+		)
+		.bind(address.as_bytes())
+		.bind(key)
+		.bind(block_number)
+		.fetch_optional(&self.pool)
+		.await
+		.ok()?;
+
+		let removed_at: Option<i64> = sqlx::query_scalar(
+			r#"
+			SELECT block_number
+			FROM removed_accounts
+			WHERE address = $1 AND block_number > $2
+			ORDER BY block_number ASC
+			LIMIT 1
+			"#,
+		)
+		.bind(address.as_bytes())
+		.bind(block_number)
+		.fetch_optional(&self.pool)
+		.await
+		.ok()?;
+
+		match (changed, removed_at) {
+			(Some((changed_at, value)), removed_at)
+				if removed_at.is_none_or(|removed_at| changed_at <= removed_at) =>
+				Some(StateLookup::Value(value.map(Bytes))),
+			// The keys wiped along with the account are not known, so neither is their value.
+			(_, Some(_)) => None,
+			_ => Some(StateLookup::At(latest)),
+		}
+	}
+
+	/// Get the cached trace of the given transaction.
+	pub async fn trace(
+		&self,
+		transaction_hash: &H256,
+		tracer_config: &TracerConfig,
+	) -> Option<Trace> {
+		let trace: Vec<u8> = sqlx::query_scalar(
+			r#"
+			SELECT trace
+			FROM traces
+			WHERE transaction_hash = $1 AND tracer_config = $2
+			"#,
+		)
+		.bind(transaction_hash.as_bytes())
+		.bind(tracer_config.encode())
+		.fetch_optional(&self.pool)
+		.await
+		.ok()??;
+
+		Trace::decode(&mut &trace[..]).ok()
+	}
+
+	/// Cache the trace of the given transaction of the given block.
+	///
+	/// Traces are pruned once their block is [`Self::new`]'s `trace_cache_blocks` blocks behind
+	/// the latest indexed block.
+	pub async fn insert_trace(
+		&self,
+		block_number: u32,
+		transaction_hash: &H256,
+		tracer_config: &TracerConfig,
+		trace: &Trace,
+	) {
+		let result = sqlx::query(
+			r#"
+			INSERT OR REPLACE INTO traces (transaction_hash, tracer_config, block_number, trace)
+			VALUES ($1, $2, $3, $4)
+			"#,
+		)
+		.bind(transaction_hash.as_bytes())
+		.bind(tracer_config.encode())
+		.bind(block_number as i64)
+		.bind(trace.encode())
+		.execute(&self.pool)
+		.await;
+
+		if let Err(err) = result {
+			log::error!(target: LOG_TARGET, "Error caching trace of transaction {transaction_hash:?}: {err:?}");
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pallet_revive::evm::PrestateTraceInfo;
+	use pretty_assertions::assert_eq;
+
+	fn diff_trace(
+		pre: Vec<(H160, PrestateTraceInfo)>,
+		post: Vec<(H160, PrestateTraceInfo)>,
+	) -> Trace {
+		Trace::Prestate(PrestateTrace::DiffMode {
+			pre: pre.into_iter().collect(),
+			post: post.into_iter().collect(),
+		})
+	}
+
+	fn storage(entries: &[(u8, Option<u8>)]) -> BTreeMap<Bytes, Option<Bytes>> {
+		entries
+			.iter()
+			.map(|(key, value)| (Bytes(vec![*key]), value.map(|value| Bytes(vec![value]))))
+			.collect()
+	}
+
+	#[test]
+	fn block_state_diff_keeps_the_values_before_the_block() {
+		let contract = H160::from([1u8; 20]);
+		let removed = H160::from([2u8; 20]);
+		let traces = [
+			diff_trace(
+				vec![
+					(
+						contract,
+						PrestateTraceInfo {
+							balance: Some(1.into()),
+							storage: storage(&[(1, None)]),
+							..Default::default()
+						},
+					),
+					(
+						removed,
+						PrestateTraceInfo {
+							code: Some(Bytes(vec![42])),
+							storage: storage(&[(1, Some(1))]),
+							..Default::default()
+						},
+					),
+				],
+				vec![(
+					contract,
+					PrestateTraceInfo { storage: storage(&[(1, Some(1))]), ..Default::default() },
+				)],
+			),
+			diff_trace(
+				vec![
+					(
+						contract,
+						PrestateTraceInfo {
+							balance: Some(2.into()),
+							storage: storage(&[(1, Some(1))]),
+							..Default::default()
+						},
+					),
+					(
+						removed,
+						PrestateTraceInfo { storage: storage(&[(2, None)]), ..Default::default() },
+					),
+				],
+				vec![
+					(
+						contract,
+						PrestateTraceInfo {
+							storage: storage(&[(1, Some(2))]),
+							..Default::default()
+						},
+					),
+					(
+						removed,
+						PrestateTraceInfo {
+							code: Some(Bytes(vec![43])),
+							storage: storage(&[(2, Some(2))]),
+							..Default::default()
+						},
+					),
+				],
+			),
+		];
+
+		let diff = BlockStateDiff::from_traces(traces.iter());
+		assert_eq!(
+			diff,
+			BlockStateDiff {
+				balances: [(contract, 1.into())].into_iter().collect(),
+				code: [(removed, Bytes(vec![42]))].into_iter().collect(),
+				storage: [
+					((contract, Bytes(vec![1])), None),
+					((removed, Bytes(vec![1])), Some(Bytes(vec![1]))),
+				]
+				.into_iter()
+				.collect(),
+				removed: [removed].into_iter().collect(),
+			}
+		);
+		assert_eq!(diff.accounts(), [contract, removed].into_iter().collect());
+	}
+
+	#[test]
+	fn to_address_works() {
+		let mut account_id = [0xEE; 32];
+		account_id[..20].copy_from_slice(&[1u8; 20]);
+		assert_eq!(to_address(&account_id), H160::from([1u8; 20]));
+
+		let account_id = [1u8; 32];
+		assert_eq!(to_address(&account_id), H160::from_slice(&keccak_256(&account_id)[12..]));
+	}
+
+	#[sqlx::test]
+	async fn state_lookup_works(pool: SqlitePool) {
+		let index = StateIndex { pool, trace_cache_blocks: 2 };
+		let address = H160::from([1u8; 20]);
+		let key = Bytes(vec![1]);
+		let block_hash = |n: u8| H256::from([n; 32]);
+
+		// Block 2 sets the balance and storage of the account, block 4 updates them.
+		index.insert_block(1, &block_hash(1), &Default::default()).await.unwrap();
+		for (block_number, balance, value) in [(2, 0u64, None), (4, 10u64, Some(Bytes(vec![10])))] {
+			let diff = BlockStateDiff {
+				balances: [(address, balance.into())].into_iter().collect(),
+				storage: [((address, key.clone()), value)].into_iter().collect(),
+				..Default::default()
+			};
+			index
+				.insert_block(block_number, &block_hash(block_number as u8), &diff)
+				.await
+				.unwrap();
+		}
+
+		// Block 3 is missing, so blocks before it can not be looked up.
+		assert_eq!(index.balance(&address, 1).await, None);
+		assert_eq!(index.storage(&address, &key.0, 1).await, None);
+
+		index.insert_block(3, &block_hash(3), &Default::default()).await.unwrap();
+		assert_eq!(index.balance(&address, 1).await, Some(0.into()));
+		assert_eq!(index.balance(&address, 2).await, Some(10.into()));
+		assert_eq!(index.balance(&address, 3).await, Some(10.into()));
+		assert_eq!(index.storage(&address, &key.0, 1).await, Some(StateLookup::Value(None)));
+		assert_eq!(
+			index.storage(&address, &key.0, 3).await,
+			Some(StateLookup::Value(Some(Bytes(vec![10]))))
+		);
+
+		// Code and storage that did not change since are read at the latest indexed block, and
+		// balances at the requested block.
+		assert_eq!(index.balance(&address, 4).await, None);
+		assert_eq!(index.code(&address, 1).await, Some(StateLookup::At(block_hash(4))));
+		assert_eq!(index.block_number(&block_hash(3)).await, Some(3));
+
+		// Removing the account wipes storage keys that are not known to the index.
+		let diff =
+			BlockStateDiff { removed: [address].into_iter().collect(), ..Default::default() };
+		index.insert_block(5, &block_hash(5), &diff).await.unwrap();
+		assert_eq!(index.storage(&address, &[2], 3).await, None);
+		assert_eq!(
+			index.storage(&address, &key.0, 3).await,
+			Some(StateLookup::Value(Some(Bytes(vec![10]))))
+		);
+	}
+
+	#[sqlx::test]
+	async fn trace_cache_works(pool: SqlitePool) {
+		let index = StateIndex { pool, trace_cache_blocks: 2 };
+		let transaction_hash = H256::from([1u8; 32]);
+		let tracer_config = TracerConfig::CallTracer { with_logs: true };
+		let trace = Trace::Call(Default::default());
+
+		assert_eq!(index.trace(&transaction_hash, &tracer_config).await, None);
+		index.insert_trace(1, &transaction_hash, &tracer_config, &trace).await;
+		assert_eq!(index.trace(&transaction_hash, &tracer_config).await, Some(trace.clone()));
+		assert_eq!(
+			index
+				.trace(&transaction_hash, &TracerConfig::CallTracer { with_logs: false })
+				.await,
+			None
+		);
+
+		// Traces are kept for the last `trace_cache_blocks` indexed blocks.
+		index
+			.insert_block(2, &H256::from([2u8; 32]), &Default::default())
+			.await
+			.unwrap();
+		assert_eq!(index.trace(&transaction_hash, &tracer_config).await, Some(trace));
+		index
+			.insert_block(3, &H256::from([3u8; 32]), &Default::default())
+			.await
+			.unwrap();
+		assert_eq!(index.trace(&transaction_hash, &tracer_config).await, None);
+		assert_eq!(index.latest_block_number().await.unwrap(), Some(3));
+	}
+}
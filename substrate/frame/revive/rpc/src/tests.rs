@@ -20,17 +20,21 @@
 
 use crate::{
 	cli::{self, CliCommand},
+	conformance::{self, Fixture},
 	example::TransactionBuilder,
-	EthRpcClient,
+	EthFilterRpcClient, EthRpcClient, ReviveRpcClient,
 };
 use clap::Parser;
 use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 use pallet_revive::{
 	create1,
-	evm::{Account, BlockTag, U256},
+	evm::{
+		AccessListEntry, Account, BlockTag, Bytes, FilterResults, GenericTransaction,
+		SubscriptionItem, SubscriptionKind, H256, U256,
+	},
 };
 use static_init::dynamic;
-use std::{sync::Arc, thread};
+use std::{path::Path, sync::Arc, thread};
 use substrate_cli_test_utils::*;
 
 /// Create a websocket client with a 120s timeout.
@@ -219,3 +223,158 @@ async fn invalid_transaction() -> anyhow::Result<()> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn filters_and_subscriptions() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+
+	let block_filter = client.new_block_filter().await?;
+	let pending_transaction_filter = client.new_pending_transaction_filter().await?;
+	let mut new_heads = client.eth_subscribe(SubscriptionKind::NewHeads, None).await?;
+
+	let tx = TransactionBuilder::new(&client)
+		.value(U256::from(1_000_000_000_000u128))
+		.to(ethan.address())
+		.send()
+		.await?;
+	let receipt = tx.wait_for_receipt().await?;
+
+	let FilterResults::Hashes(hashes) =
+		client.get_filter_changes(pending_transaction_filter).await?
+	else {
+		panic!("Pending transaction filter should return hashes");
+	};
+	assert!(hashes.contains(&tx.hash()), "Submitted transaction should be reported.");
+
+	let FilterResults::Hashes(hashes) = client.get_filter_changes(block_filter).await? else {
+		panic!("Block filter should return hashes");
+	};
+	assert!(hashes.contains(&receipt.block_hash), "Receipt block should be reported.");
+
+	let head = new_heads.next().await.expect("Subscription should not end")?;
+	assert!(matches!(head, SubscriptionItem::Block(_)));
+
+	assert!(client.uninstall_filter(block_filter).await?);
+	assert!(!client.uninstall_filter(block_filter).await?);
+	Ok(())
+}
+
+#[tokio::test]
+async fn fee_history() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+
+	let tx = TransactionBuilder::new(&client)
+		.value(U256::from(1_000_000_000_000u128))
+		.to(ethan.address())
+		.send()
+		.await?;
+	let receipt = tx.wait_for_receipt().await?;
+
+	let history = client
+		.fee_history(4u32.into(), receipt.block_number.into(), Some(vec![0.0, 50.0, 100.0]))
+		.await?;
+	assert_eq!(history.base_fee_per_gas.len(), history.gas_used_ratio.len() + 1);
+	assert_eq!(history.reward.len(), history.gas_used_ratio.len());
+	assert!(history.reward.iter().all(|rewards| rewards.len() == 3));
+	assert!(
+		history.gas_used_ratio.last().is_some_and(|ratio| *ratio > 0.0),
+		"The block including the transaction should have used some gas."
+	);
+
+	let err = client
+		.fee_history(4u32.into(), BlockTag::Latest.into(), Some(vec![50.0, 10.0]))
+		.await
+		.unwrap_err();
+	let jsonrpsee::core::client::Error::Call(call_err) = err else {
+		panic!("Expected Call error");
+	};
+	assert_eq!(call_err.message(), "Invalid reward percentiles");
+	Ok(())
+}
+
+#[tokio::test]
+async fn get_proof() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let account = Account::default();
+
+	let (bytes, _) = pallet_revive_fixtures::compile_module("dummy")?;
+	let nonce = client.get_transaction_count(account.address(), BlockTag::Latest.into()).await?;
+	let tx = TransactionBuilder::new(&client).input(bytes).send().await?;
+	let receipt = tx.wait_for_receipt().await?;
+	let contract_address = create1(&account.address(), nonce.try_into().unwrap());
+
+	let block = client
+		.get_block_by_hash(receipt.block_hash, false)
+		.await?
+		.expect("Receipt block should exist");
+	// The parameters of the kitchensink runtime.
+	let params = pallet_revive_proof_verifier::RuntimeParams {
+		pallet_name: "Revive",
+		native_to_eth_ratio: 1_000_000,
+		existential_deposit: 100_000_000_000_000,
+	};
+	for address in [contract_address, account.address()] {
+		let proof =
+			client.get_proof(address, vec![U256::zero()], receipt.block_hash.into()).await?;
+		assert_eq!(proof.address, address);
+		assert!(proof.storage_proof.iter().all(|slot| slot.value.is_empty()));
+		pallet_revive_proof_verifier::verify_account_proof(block.state_root, &params, &proof)?;
+	}
+	Ok(())
+}
+
+#[tokio::test]
+async fn create_access_list() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let account = Account::default();
+
+	let (bytes, _) = pallet_revive_fixtures::compile_module("store_call")?;
+	let nonce = client.get_transaction_count(account.address(), BlockTag::Latest.into()).await?;
+	let tx = TransactionBuilder::new(&client).input(bytes).send().await?;
+	tx.wait_for_receipt().await?;
+	let contract_address = create1(&account.address(), nonce.try_into().unwrap());
+
+	let tx = GenericTransaction {
+		from: Some(account.address()),
+		to: Some(contract_address),
+		input: Bytes(16u32.to_le_bytes().to_vec()).into(),
+		..Default::default()
+	};
+	let result = client.create_access_list(tx, None).await?;
+
+	let mut key = [0u8; 32];
+	key[0] = 1;
+	assert_eq!(
+		result.access_list,
+		vec![AccessListEntry { address: contract_address, storage_keys: vec![H256(key)] }]
+	);
+	assert!(!result.gas_used.is_zero());
+	Ok(())
+}
+
+#[tokio::test]
+async fn gas_schedule() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = SharedResources::client().await;
+
+	// The dev runtime does not declare a gas schedule.
+	assert_eq!(client.gas_schedule(None).await?, None);
+	Ok(())
+}
+
+#[tokio::test]
+async fn conformance() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = SharedResources::client().await;
+
+	let fixtures = Fixture::load_dir(&Path::new(env!("CARGO_MANIFEST_DIR")).join("conformance"))?;
+	let report = conformance::run(&client, &fixtures).await;
+	assert!(report.is_conformant(), "Spec deviations found:\n{report}");
+	Ok(())
+}
@@ -0,0 +1,126 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) access lists.
+//!
+//! The accounts and storage keys of an access list are read before the execution starts, and
+//! their full access costs are charged up front. They are then warm for the rest of the
+//! execution: accessing them again only charges the `ref_time` of the access, since the trie
+//! nodes they are stored in are already part of the proof.
+
+use crate::{
+	evm::AccessListEntry,
+	exec::Key,
+	gas::{GasMeter, Token},
+	limits,
+	wasm::RuntimeCosts,
+	AddressMapper, Config, ContractInfoOf, DispatchError, Weight,
+};
+use alloc::collections::BTreeSet;
+use environmental::environmental;
+use sp_core::H160;
+
+environmental!(warm_set: WarmSet);
+
+/// The accounts and storage keys warmed by an access list.
+#[derive(Default)]
+struct WarmSet {
+	/// The warm accounts.
+	accounts: BTreeSet<H160>,
+	/// The warm storage keys, along with the address of the contract they belong to.
+	storage: BTreeSet<(H160, [u8; 32])>,
+}
+
+impl WarmSet {
+	/// Create the set of the accounts and storage keys of `access_list`.
+	fn new(access_list: &[AccessListEntry]) -> Self {
+		let mut set = Self::default();
+		for entry in access_list {
+			set.accounts.insert(entry.address);
+			set.storage.extend(entry.storage_keys.iter().map(|key| (entry.address, key.0)));
+		}
+		set
+	}
+
+	/// Read the accounts and storage keys of the set, charging their full access costs.
+	fn warm_up<T: Config>(&self, gas_meter: &mut GasMeter<T>) -> Result<(), DispatchError> {
+		for address in &self.accounts {
+			gas_meter.charge(RuntimeCosts::BalanceOf)?;
+			gas_meter.charge(RuntimeCosts::IsContract)?;
+			let account_id = T::AddressMapper::to_account_id(address);
+			let _ = frame_system::Account::<T>::get(&account_id);
+			let _ = ContractInfoOf::<T>::get(address);
+		}
+
+		for (address, key) in &self.storage {
+			let charged = gas_meter.charge(RuntimeCosts::GetStorage(limits::PAYLOAD_BYTES))?;
+			let len = ContractInfoOf::<T>::get(address)
+				.and_then(|info| info.read(&Key::Fix(*key)))
+				.map_or(0, |value| value.len() as u32);
+			gas_meter.adjust_gas(charged, RuntimeCosts::GetStorage(len));
+		}
+
+		Ok(())
+	}
+}
+
+/// The cost of accessing a warm account or storage key.
+///
+/// This is the cost of the wrapped access without its proof size, which was already charged
+/// when warming up the access list.
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(Copy, Clone)]
+pub struct Warm(pub RuntimeCosts);
+
+impl<T: Config> Token<T> for Warm {
+	fn weight(&self) -> Weight {
+		Token::<T>::weight(&self.0).set_proof_size(0)
+	}
+}
+
+/// The number of accounts and storage keys of `access_list`.
+pub fn len(access_list: &[AccessListEntry]) -> u32 {
+	access_list
+		.iter()
+		.map(|entry| (entry.storage_keys.len() as u32).saturating_add(1))
+		.fold(0, u32::saturating_add)
+}
+
+/// Execute `f` with the accounts and storage keys of `access_list` being warm.
+pub fn using<R>(access_list: &[AccessListEntry], f: impl FnOnce() -> R) -> R {
+	let mut set = WarmSet::new(access_list);
+	warm_set::using(&mut set, f)
+}
+
+/// Read the accounts and storage keys of the current access list, charging their full access
+/// costs to `gas_meter`.
+///
+/// This does nothing when not executing within [`using`].
+pub fn warm_up<T: Config>(gas_meter: &mut GasMeter<T>) -> Result<(), DispatchError> {
+	warm_set::with(|set| set.warm_up(gas_meter)).unwrap_or(Ok(()))
+}
+
+/// Whether the account at `address` is in the current access list.
+pub fn is_warm_account(address: &H160) -> bool {
+	warm_set::with(|set| set.accounts.contains(address)).unwrap_or(false)
+}
+
+/// Whether `key` of the contract at `address` is in the current access list.
+pub fn is_warm_storage(address: &H160, key: &Key) -> bool {
+	let Key::Fix(key) = key else { return false };
+	warm_set::with(|set| set.storage.contains(&(*address, *key))).unwrap_or(false)
+}
@@ -243,7 +243,7 @@ mod benchmarks {
 	#[benchmark(skip_meta, pov_mode = Measured)]
 	fn on_initialize_per_trie_key(k: Linear<0, 1024>) -> Result<(), BenchmarkError> {
 		let instance = Contract::<T>::with_storage(WasmModule::dummy(), k, limits::PAYLOAD_BYTES)?;
-		instance.info()?.queue_trie_for_deletion();
+		instance.info()?.queue_trie_for_deletion(&instance.account_id, &instance.caller);
 
 		#[block]
 		{
@@ -539,6 +539,20 @@ mod benchmarks {
 		_(origin, Box::new(dispatchable));
 	}
 
+	// Storage keys are the most expensive items of an access list to collect.
+	#[benchmark(pov_mode = Measured)]
+	fn access_list(n: Linear<0, { limits::ACCESS_LIST_ITEMS }>) {
+		let access_list = vec![evm::AccessListEntry {
+			address: H160::zero(),
+			storage_keys: (1..n).map(|i| H256::from_low_u64_be(i.into())).collect(),
+		}];
+
+		#[block]
+		{
+			crate::access_list::using(&access_list, || ());
+		}
+	}
+
 	#[benchmark(pov_mode = Measured)]
 	fn noop_host_fn(r: Linear<0, API_BENCHMARK_RUNS>) {
 		let mut setup = CallSetup::<T>::new(WasmModule::noop());
@@ -1112,8 +1126,14 @@ mod benchmarks {
 	#[benchmark(pov_mode = Measured)]
 	fn seal_terminate() -> Result<(), BenchmarkError> {
 		let beneficiary = account::<T::AccountId>("beneficiary", 0, 0);
+		let mut memory = memory!(beneficiary.encode(),);
+		let mut setup = CallSetup::<T>::default();
+		let input = setup.data();
+		let (mut ext, _) = setup.ext();
+		// Only a contract created within the same transaction is deleted.
+		ext.mark_as_created();
 
-		build_runtime!(runtime, memory: [beneficiary.encode(),]);
+		let mut runtime = crate::wasm::Runtime::<_, [u8]>::new(&mut ext, input);
 
 		let result;
 		#[block]
@@ -2059,6 +2079,141 @@ mod benchmarks {
 		assert_ok!(result);
 	}
 
+	#[benchmark(pov_mode = Measured)]
+	fn point_evaluation() {
+		use hex_literal::hex;
+		let input = hex!("014dfd9d4941da80f2c4231ab756aa2e90fc271140d6b734401abcf44c534ddd256c17544a0072c1c7e88b50fb08834238cf7d33b56183113e6295668ea8b37b2b186be8ecdf21b858d87c6b52a9d7b4cd71f2f793acf51116120e372be2c5b6b48cc7aa8a9b2ca5a86fb118f06cea6bc7fbd8d61c632ad47ac60768df43371fe2ef2d53b1884ff33e90ecc300113e1ca6701c0b253f6797ea25d027aa5baa58e2098b11fde5c6538f2abb56426ca5a6f349e96dfa9f7cd45ff44bbc1cc41c1a");
+		let expected = hex!("000000000000000000000000000000000000000000000000000000000000100073eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001");
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+
+		#[block]
+		{
+			result = pure_precompiles::PointEvaluation::execute(ext.gas_meter_mut(), &input);
+		}
+
+		assert_eq!(result.unwrap().data, expected);
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_g1_add() {
+		use hex_literal::hex;
+		let input = hex!("0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e100000000000000000000000000000000112b98340eee2777cc3c14163dea3ec97977ac3dc5c70da32e6e87578f44912e902ccef9efe28d4a78b8999dfbca942600000000000000000000000000000000186b28d92356c4dfec4b5201ad099dbdede3781f8998ddf929b4cd7756192185ca7b8f4ef7088f813270ac3d48868a21");
+		let expected = hex!("000000000000000000000000000000000a40300ce2dec9888b60690e9a41d3004fda4886854573974fab73b046d3147ba5b7a5bde85279ffede1b45b3918d82d0000000000000000000000000000000006d3d887e9f53b9ec4eb6cedf5607226754b07c01ace7834f57f3e7315faefb739e59018e22c492006190fba4a870025");
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+
+		#[block]
+		{
+			result = pure_precompiles::Bls12381G1Add::execute(ext.gas_meter_mut(), &input);
+		}
+
+		assert_eq!(result.unwrap().data, expected);
+	}
+
+	// `n`: number of point and scalar pairs
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_g1_msm(n: Linear<1, { limits::code::BLOB_BYTES / 160 }>) {
+		let input = pure_precompiles::generate_bls12_381_g1_msm_input(n as usize);
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = pure_precompiles::Bls12381G1Msm::execute(ext.gas_meter_mut(), &input);
+		}
+		assert_ok!(result);
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_g2_add() {
+		use hex_literal::hex;
+		let input = hex!("00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb80000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be00000000000000000000000000000000103121a2ceaae586d240843a398967325f8eb5a93e8fea99b62b9f88d8556c80dd726a4b30e84a36eeabaf3592937f2700000000000000000000000000000000086b990f3da2aeac0a36143b7d7c824428215140db1bb859338764cb58458f081d92664f9053b50b3fbd2e4723121b68000000000000000000000000000000000f9e7ba9a86a8f7624aa2b42dcc8772e1af4ae115685e60abc2c9b90242167acef3d0be4050bf935eed7c3b6fc7ba77e000000000000000000000000000000000d22c3652d0dc6f0fc9316e14268477c2049ef772e852108d269d9c38dba1d4802e8dae479818184c08f9a569d878451");
+		let expected = hex!("000000000000000000000000000000000b54a8a7b08bd6827ed9a797de216b8c9057b3a9ca93e2f88e7f04f19accc42da90d883632b9ca4dc38d013f71ede4db00000000000000000000000000000000077eba4eecf0bd764dce8ed5f45040dd8f3b3427cb35230509482c14651713282946306247866dfe39a8e33016fcbe520000000000000000000000000000000014e60a76a29ef85cbd69f251b9f29147b67cfe3ed2823d3f9776b3a0efd2731941d47436dc6d2b58d9e65f8438bad073000000000000000000000000000000001586c3c910d95754fef7a732df78e279c3d37431c6a2b77e67a00c7c130a8fcd4d19f159cbeb997a178108fffffcbd20");
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+
+		#[block]
+		{
+			result = pure_precompiles::Bls12381G2Add::execute(ext.gas_meter_mut(), &input);
+		}
+
+		assert_eq!(result.unwrap().data, expected);
+	}
+
+	// `n`: number of point and scalar pairs
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_g2_msm(n: Linear<1, { limits::code::BLOB_BYTES / 288 }>) {
+		let input = pure_precompiles::generate_bls12_381_g2_msm_input(n as usize);
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = pure_precompiles::Bls12381G2Msm::execute(ext.gas_meter_mut(), &input);
+		}
+		assert_ok!(result);
+	}
+
+	// `n`: pairings to perform
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_pairing_check(n: Linear<1, { limits::code::BLOB_BYTES / 384 }>) {
+		let input = pure_precompiles::generate_bls12_381_pairing_input(n as usize);
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = pure_precompiles::Bls12381PairingCheck::execute(ext.gas_meter_mut(), &input);
+		}
+		assert_ok!(result);
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_map_fp_to_g1() {
+		use hex_literal::hex;
+		let input = hex!("00000000000000000000000000000000147e1ed29f06e4c5079b9d14fc89d2820d32419b990c1c7bb7dbea2a36a045124b31ffbde7c99329c05c559af1c6cc82");
+		let expected = hex!("00000000000000000000000000000000009769f3ab59bfd551d53a5f846b9984c59b97d6842b20a2c565baa167945e3d026a3755b6345df8ec7e6acb6868ae6d000000000000000000000000000000001532c00cf61aa3d0ce3e5aa20c3b531a2abd2c770a790a2613818303c6b830ffc0ecf6c357af3317b9575c567f11cd2c");
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+
+		#[block]
+		{
+			result = pure_precompiles::Bls12381MapFpToG1::execute(ext.gas_meter_mut(), &input);
+		}
+
+		assert_eq!(result.unwrap().data, expected);
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_map_fp2_to_g2() {
+		use hex_literal::hex;
+		let input = hex!("00000000000000000000000000000000138879a9559e24cecee8697b8b4ad32cced053138ab913b99872772dc753a2967ed50aabc907937aefb2439ba06cc50c000000000000000000000000000000000a1ae7999ea9bab1dcc9ef8887a6cb6e8f1e22566015428d220b7eec90ffa70ad1f624018a9ad11e78d588bd3617f9f2");
+		let expected = hex!("00000000000000000000000000000000108ed59fd9fae381abfd1d6bce2fd2fa220990f0f837fa30e0f27914ed6e1454db0d1ee957b219f61da6ff8be0d6441f000000000000000000000000000000000296238ea82c6d4adb3c838ee3cb2346049c90b96d602d7bb1b469b905c9228be25c627bffee872def773d5b2a2eb57d00000000000000000000000000000000033f90f6057aadacae7963b0a0b379dd46750c1c94a6357c99b65f63b79e321ff50fe3053330911c56b6ceea08fee65600000000000000000000000000000000153606c417e59fb331b7ae6bce4fbf7c5190c33ce9402b5ebe2b70e44fca614f3f1382a3625ed5493843d0b0a652fc3f");
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+
+		#[block]
+		{
+			result = pure_precompiles::Bls12381MapFp2ToG2::execute(ext.gas_meter_mut(), &input);
+		}
+
+		assert_eq!(result.unwrap().data, expected);
+	}
+
 	// Only calling the function itself for the list of
 	// generated different ECDSA keys.
 	// This is a slow call: We reduce the number of runs.
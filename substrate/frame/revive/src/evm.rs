@@ -23,5 +23,7 @@ mod tracing;
 pub use tracing::*;
 mod gas_encoder;
 pub use gas_encoder::*;
+mod gas_schedule;
+pub use gas_schedule::*;
 pub mod runtime;
 pub use alloy_core::sol_types::decode_revert_reason;
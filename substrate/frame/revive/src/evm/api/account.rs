@@ -16,8 +16,8 @@
 // limitations under the License.
 //! Utilities for working with Ethereum accounts.
 use crate::{
-	evm::{TransactionSigned, TransactionUnsigned},
-	H160,
+	evm::{AuthorizationListEntry, TransactionSigned, TransactionUnsigned},
+	H160, U256,
 };
 use sp_runtime::AccountId32;
 
@@ -61,6 +61,19 @@ impl Account {
 		let signature = self.0.sign(&payload).0;
 		tx.with_signature(signature)
 	}
+
+	/// Sign an authorization to delegate the code of this account to the contract at `address`,
+	/// see <https://eips.ethereum.org/EIPS/eip-7702>.
+	pub fn sign_authorization(
+		&self,
+		chain_id: U256,
+		address: H160,
+		nonce: U256,
+	) -> AuthorizationListEntry {
+		let payload = AuthorizationListEntry::unsigned_payload(chain_id, address, nonce);
+		let signature = self.0.sign(&payload).0;
+		AuthorizationListEntry::with_signature(chain_id, address, nonce, signature)
+	}
 }
 
 #[test]
@@ -15,8 +15,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::evm::{Bytes, CallTracer};
-use alloc::{fmt, string::String, vec::Vec};
+use crate::evm::Bytes;
+use alloc::{collections::BTreeMap, fmt, string::String, vec::Vec};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use serde::{
@@ -36,15 +36,32 @@ pub enum TracerConfig {
 		#[serde(rename = "withLog")]
 		with_logs: bool,
 	},
-}
 
-impl TracerConfig {
-	/// Build the tracer associated to this config.
-	pub fn build<G>(self, gas_mapper: G) -> CallTracer<U256, G> {
-		match self {
-			Self::CallTracer { with_logs } => CallTracer::new(with_logs, gas_mapper),
-		}
-	}
+	/// A tracer that captures the state of the accounts touched by a transaction.
+	#[serde(rename = "prestateTracer")]
+	PrestateTracer {
+		/// Whether to return the differences between the state before and after the
+		/// transaction instead of the state before the transaction only.
+		#[serde(rename = "diffMode")]
+		diff_mode: bool,
+		/// Whether to omit the code of the accounts.
+		#[serde(rename = "disableCode")]
+		disable_code: bool,
+		/// Whether to omit the storage of the accounts.
+		#[serde(rename = "disableStorage")]
+		disable_storage: bool,
+	},
+
+	/// A tracer that logs every executed instruction.
+	#[serde(rename = "structLogger")]
+	StructLogger {
+		/// Whether to omit the registers from the logged instructions.
+		#[serde(rename = "disableStack")]
+		disable_stack: bool,
+		/// Whether to omit the storage from the logged instructions.
+		#[serde(rename = "disableStorage")]
+		disable_storage: bool,
+	},
 }
 
 /// Custom deserializer to support the following JSON format:
@@ -56,6 +73,17 @@ impl TracerConfig {
 /// ```json
 /// { "tracer": "callTracer" }
 /// ```
+///
+/// ```json
+/// { "tracer": "prestateTracer", "tracerConfig": { "diffMode": true } }
+/// ```
+///
+/// When no tracer is specified, the struct logger is used and its options are read from the top
+/// level object, as done by geth:
+///
+/// ```json
+/// { "disableStack": true, "disableStorage": false }
+/// ```
 impl<'de> Deserialize<'de> for TracerConfig {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
@@ -74,8 +102,19 @@ impl<'de> Deserialize<'de> for TracerConfig {
 			where
 				M: MapAccess<'de>,
 			{
+				#[derive(Deserialize, Default)]
+				#[serde(rename_all = "camelCase")]
+				struct InnerTracerConfig {
+					with_logs: Option<bool>,
+					diff_mode: Option<bool>,
+					disable_code: Option<bool>,
+					disable_storage: Option<bool>,
+					disable_stack: Option<bool>,
+				}
+
 				let mut tracer_type: Option<String> = None;
-				let mut with_logs = None;
+				let mut tracer_config = InnerTracerConfig::default();
+				let mut struct_logger_config = InnerTracerConfig::default();
 
 				while let Some(key) = map.next_key::<String>()? {
 					match key.as_str() {
@@ -83,22 +122,38 @@ impl<'de> Deserialize<'de> for TracerConfig {
 							tracer_type = map.next_value()?;
 						},
 						"tracerConfig" => {
-							#[derive(Deserialize)]
-							struct CallTracerConfig {
-								#[serde(rename = "withLogs")]
-								with_logs: Option<bool>,
-							}
-							let inner: CallTracerConfig = map.next_value()?;
-							with_logs = inner.with_logs;
+							tracer_config = map.next_value()?;
+						},
+						"disableStorage" => {
+							struct_logger_config.disable_storage = map.next_value()?;
+						},
+						"disableStack" => {
+							struct_logger_config.disable_stack = map.next_value()?;
+						},
+						_ => {
+							map.next_value::<de::IgnoredAny>()?;
 						},
-						_ => {},
 					}
 				}
 
 				match tracer_type.as_deref() {
-					Some("callTracer") =>
-						Ok(TracerConfig::CallTracer { with_logs: with_logs.unwrap_or(true) }),
-					_ => Err(de::Error::custom("Unsupported or missing tracer type")),
+					Some("callTracer") => Ok(TracerConfig::CallTracer {
+						with_logs: tracer_config.with_logs.unwrap_or(true),
+					}),
+					Some("prestateTracer") => Ok(TracerConfig::PrestateTracer {
+						diff_mode: tracer_config.diff_mode.unwrap_or_default(),
+						disable_code: tracer_config.disable_code.unwrap_or_default(),
+						disable_storage: tracer_config.disable_storage.unwrap_or_default(),
+					}),
+					Some("structLogger") => Ok(TracerConfig::StructLogger {
+						disable_stack: tracer_config.disable_stack.unwrap_or_default(),
+						disable_storage: tracer_config.disable_storage.unwrap_or_default(),
+					}),
+					None => Ok(TracerConfig::StructLogger {
+						disable_stack: struct_logger_config.disable_stack.unwrap_or_default(),
+						disable_storage: struct_logger_config.disable_storage.unwrap_or_default(),
+					}),
+					_ => Err(de::Error::custom("Unsupported tracer type")),
 				}
 			}
 		}
@@ -119,6 +174,35 @@ fn test_tracer_config_serialization() {
 			r#"{"tracer": "callTracer", "tracerConfig": { "withLogs": false }}"#,
 			TracerConfig::CallTracer { with_logs: false },
 		),
+		(
+			r#"{"tracer": "callTracer", "timeout": "10s"}"#,
+			TracerConfig::CallTracer { with_logs: true },
+		),
+		(
+			r#"{"tracer": "prestateTracer"}"#,
+			TracerConfig::PrestateTracer {
+				diff_mode: false,
+				disable_code: false,
+				disable_storage: false,
+			},
+		),
+		(
+			r#"{"tracer": "prestateTracer", "tracerConfig": { "diffMode": true, "disableCode": true }}"#,
+			TracerConfig::PrestateTracer {
+				diff_mode: true,
+				disable_code: true,
+				disable_storage: false,
+			},
+		),
+		(
+			r#"{"tracer": "structLogger", "tracerConfig": { "disableStack": true }}"#,
+			TracerConfig::StructLogger { disable_stack: true, disable_storage: false },
+		),
+		(r#"{}"#, TracerConfig::StructLogger { disable_stack: false, disable_storage: false }),
+		(
+			r#"{"disableStorage": true, "enableMemory": false}"#,
+			TracerConfig::StructLogger { disable_stack: false, disable_storage: true },
+		),
 	];
 
 	for (json_data, expected) in tracers {
@@ -206,6 +290,109 @@ pub struct CallLog {
 	pub position: u32,
 }
 
+/// The trace returned by a tracer.
+#[derive(TypeInfo, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum Trace {
+	/// A call trace, returned by the call tracer.
+	Call(CallTrace),
+	/// A prestate trace, returned by the prestate tracer.
+	Prestate(PrestateTrace),
+	/// A list of logged instructions, returned by the struct logger.
+	StructLog(StructLogTrace),
+}
+
+/// The state of the accounts touched by a transaction.
+#[derive(TypeInfo, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+	/// The state of the accounts before the transaction was executed.
+	Prestate(BTreeMap<H160, PrestateTraceInfo>),
+
+	/// The state of the accounts that were changed by the transaction.
+	DiffMode {
+		/// The state of the changed accounts before the transaction was executed.
+		pre: BTreeMap<H160, PrestateTraceInfo>,
+		/// The fields of the changed accounts after the transaction was executed.
+		///
+		/// Accounts that were removed by the transaction are omitted.
+		post: BTreeMap<H160, PrestateTraceInfo>,
+	},
+}
+
+impl Default for PrestateTrace {
+	fn default() -> Self {
+		Self::Prestate(Default::default())
+	}
+}
+
+/// The state of an account, as reported by the prestate tracer.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+pub struct PrestateTraceInfo {
+	/// The balance of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+	/// The nonce of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<u64>,
+	/// The code of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub code: Option<Bytes>,
+	/// The storage values accessed by the transaction.
+	///
+	/// A `None` value means that the key is absent from the storage.
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	pub storage: BTreeMap<Bytes, Option<Bytes>>,
+}
+
+/// The instructions executed by a transaction, as reported by the struct logger.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+pub struct StructLogTrace<Gas = U256> {
+	/// Amount of gas used.
+	pub gas: Gas,
+	/// Whether the transaction failed.
+	pub failed: bool,
+	/// Return data of the transaction.
+	#[serde(rename = "returnValue")]
+	pub return_value: Bytes,
+	/// The executed instructions.
+	#[serde(rename = "structLogs")]
+	pub struct_logs: Vec<StructLog<Gas>>,
+}
+
+/// A single instruction executed by a contract.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+pub struct StructLog<Gas = U256> {
+	/// The program counter of the instruction.
+	pub pc: u32,
+	/// The name of the instruction.
+	pub op: String,
+	/// Amount of gas left before executing the instruction.
+	pub gas: Gas,
+	/// Amount of gas used by the instruction.
+	#[serde(rename = "gasCost")]
+	pub gas_cost: Gas,
+	/// Depth of the call that executed the instruction, starting at 1.
+	pub depth: u32,
+	/// The values of the PolkaVM registers before executing the instruction.
+	///
+	/// PolkaVM is a register machine, hence the registers take the place of the EVM stack.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<U256>>,
+	/// The storage of the contract accessed so far by the call.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<Bytes, Bytes>>,
+	/// The error message if the call failed at this instruction.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
 /// A transaction trace
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TransactionTrace {
@@ -214,5 +401,5 @@ pub struct TransactionTrace {
 	pub tx_hash: H256,
 	/// The trace of the transaction.
 	#[serde(rename = "result")]
-	pub trace: CallTrace,
+	pub trace: Trace,
 }
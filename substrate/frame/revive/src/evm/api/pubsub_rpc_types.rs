@@ -0,0 +1,70 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::evm::{Block, Log};
+use alloc::boxed::Box;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+
+/// The kind of events an `eth_subscribe` subscription is notified of.
+#[derive(Debug, Clone, Copy, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq)]
+pub enum SubscriptionKind {
+	/// A notification is sent for each new block added to the chain.
+	#[serde(rename = "newHeads")]
+	NewHeads,
+	/// A notification is sent for each log of the new blocks matching the filter.
+	#[serde(rename = "logs")]
+	Logs,
+	/// A notification is sent for each transaction submitted through this node.
+	#[serde(rename = "newPendingTransactions")]
+	NewPendingTransactions,
+}
+
+/// An item sent to `eth_subscribe` subscribers.
+#[derive(Debug, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum SubscriptionItem {
+	/// A new block, sent to `newHeads` subscribers.
+	Block(Box<Block>),
+	/// A new log, sent to `logs` subscribers.
+	Log(Log),
+	/// A new transaction hash, sent to `newPendingTransactions` subscribers.
+	TransactionHash(H256),
+}
+
+#[test]
+fn test_subscription_serialization() {
+	let kinds = [
+		(SubscriptionKind::NewHeads, r#""newHeads""#),
+		(SubscriptionKind::Logs, r#""logs""#),
+		(SubscriptionKind::NewPendingTransactions, r#""newPendingTransactions""#),
+	];
+	for (kind, json) in kinds {
+		assert_eq!(serde_json::to_string(&kind).unwrap(), json);
+		assert_eq!(serde_json::from_str::<SubscriptionKind>(json).unwrap(), kind);
+	}
+
+	let hash = H256::repeat_byte(0x42);
+	let item = SubscriptionItem::TransactionHash(hash);
+	assert_eq!(serde_json::to_value(&item).unwrap(), serde_json::to_value(&hash).unwrap());
+
+	let log = Log { block_hash: hash, ..Default::default() };
+	let item = SubscriptionItem::Log(log.clone());
+	assert_eq!(serde_json::to_value(&item).unwrap(), serde_json::to_value(&log).unwrap());
+}
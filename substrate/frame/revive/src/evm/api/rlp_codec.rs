@@ -27,6 +27,10 @@ impl TransactionUnsigned {
 		use TransactionUnsigned::*;
 		let mut s = rlp::RlpStream::new();
 		match self {
+			Transaction7702Unsigned(ref tx) => {
+				s.append(&tx.r#type.value());
+				s.append(tx);
+			},
 			Transaction2930Unsigned(ref tx) => {
 				s.append(&tx.r#type.value());
 				s.append(tx);
@@ -54,6 +58,10 @@ impl TransactionSigned {
 		use TransactionSigned::*;
 		let mut s = rlp::RlpStream::new();
 		match self {
+			Transaction7702Signed(ref tx) => {
+				s.append(&tx.transaction_7702_unsigned.r#type.value());
+				s.append(tx);
+			},
 			Transaction2930Signed(ref tx) => {
 				s.append(&tx.transaction_2930_unsigned.r#type.value());
 				s.append(tx);
@@ -83,6 +91,7 @@ impl TransactionSigned {
 			TYPE_EIP2930 => rlp::decode::<Transaction2930Signed>(&data[1..]).map(Into::into),
 			TYPE_EIP1559 => rlp::decode::<Transaction1559Signed>(&data[1..]).map(Into::into),
 			TYPE_EIP4844 => rlp::decode::<Transaction4844Signed>(&data[1..]).map(Into::into),
+			TYPE_EIP7702 => rlp::decode::<Transaction7702Signed>(&data[1..]).map(Into::into),
 			_ => rlp::decode::<TransactionLegacySigned>(data).map(Into::into),
 		}
 	}
@@ -406,6 +415,96 @@ impl Decodable for Transaction4844Signed {
 	}
 }
 
+/// See <https://eips.ethereum.org/EIPS/eip-7702>
+impl Encodable for AuthorizationListEntry {
+	fn rlp_append(&self, s: &mut rlp::RlpStream) {
+		s.begin_list(6);
+		s.append(&self.chain_id);
+		s.append(&self.address);
+		s.append(&self.nonce);
+		s.append(&self.y_parity);
+		s.append(&self.r);
+		s.append(&self.s);
+	}
+}
+
+impl Decodable for AuthorizationListEntry {
+	fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+		Ok(AuthorizationListEntry {
+			chain_id: rlp.val_at(0)?,
+			address: rlp.val_at(1)?,
+			nonce: rlp.val_at(2)?,
+			y_parity: rlp.val_at(3)?,
+			r: rlp.val_at(4)?,
+			s: rlp.val_at(5)?,
+		})
+	}
+}
+
+/// See <https://eips.ethereum.org/EIPS/eip-7702>
+impl Encodable for Transaction7702Unsigned {
+	fn rlp_append(&self, s: &mut rlp::RlpStream) {
+		s.begin_list(10);
+		s.append(&self.chain_id);
+		s.append(&self.nonce);
+		s.append(&self.max_priority_fee_per_gas);
+		s.append(&self.max_fee_per_gas);
+		s.append(&self.gas);
+		s.append(&self.to);
+		s.append(&self.value);
+		s.append(&self.input.0);
+		s.append_list(&self.access_list);
+		s.append_list(&self.authorization_list);
+	}
+}
+
+/// See <https://eips.ethereum.org/EIPS/eip-7702>
+impl Encodable for Transaction7702Signed {
+	fn rlp_append(&self, s: &mut rlp::RlpStream) {
+		let tx = &self.transaction_7702_unsigned;
+		s.begin_list(13);
+		s.append(&tx.chain_id);
+		s.append(&tx.nonce);
+		s.append(&tx.max_priority_fee_per_gas);
+		s.append(&tx.max_fee_per_gas);
+		s.append(&tx.gas);
+		s.append(&tx.to);
+		s.append(&tx.value);
+		s.append(&tx.input.0);
+		s.append_list(&tx.access_list);
+		s.append_list(&tx.authorization_list);
+		s.append(&self.y_parity);
+		s.append(&self.r);
+		s.append(&self.s);
+	}
+}
+
+impl Decodable for Transaction7702Signed {
+	fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+		Ok(Transaction7702Signed {
+			transaction_7702_unsigned: {
+				Transaction7702Unsigned {
+					chain_id: rlp.val_at(0)?,
+					nonce: rlp.val_at(1)?,
+					max_priority_fee_per_gas: rlp.val_at(2)?,
+					max_fee_per_gas: rlp.val_at(3)?,
+					gas: rlp.val_at(4)?,
+					to: rlp.val_at(5)?,
+					value: rlp.val_at(6)?,
+					input: Bytes(rlp.val_at(7)?),
+					access_list: rlp.list_at(8)?,
+					authorization_list: rlp.list_at(9)?,
+					..Default::default()
+				}
+			},
+			y_parity: rlp.val_at(10)?,
+			r: rlp.val_at(11)?,
+			s: rlp.val_at(12)?,
+			..Default::default()
+		})
+	}
+}
+
 /// See <https://eips.ethereum.org/EIPS/eip-155>
 impl Decodable for TransactionLegacySigned {
 	fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
@@ -565,6 +664,39 @@ mod test {
 		}
 	}
 
+	#[test]
+	fn encode_decode_7702_tx_works() {
+		let account = Account::default();
+		let authorization = account.sign_authorization(1.into(), H160::repeat_byte(0x42), 7.into());
+		let tx: TransactionUnsigned = Transaction7702Unsigned {
+			chain_id: 1.into(),
+			nonce: 1.into(),
+			max_priority_fee_per_gas: 2.into(),
+			max_fee_per_gas: 3.into(),
+			gas: 21000.into(),
+			to: H160::repeat_byte(0x01),
+			value: 123.into(),
+			input: Bytes(vec![1, 2, 3]),
+			access_list: vec![AccessListEntry {
+				address: H160::repeat_byte(0x01),
+				storage_keys: vec![H256::zero()],
+			}],
+			authorization_list: vec![authorization],
+			..Default::default()
+		}
+		.into();
+
+		let tx = account.sign_transaction(tx);
+		let raw_tx = tx.signed_payload();
+		assert_eq!(raw_tx[0], TYPE_EIP7702);
+		let decoded = TransactionSigned::decode(&raw_tx).unwrap();
+		assert_eq!(decoded, tx);
+		assert_eq!(decoded.recover_eth_address(), Ok(account.address()));
+
+		let json = serde_json::to_string(&tx).unwrap();
+		assert_eq!(serde_json::from_str::<TransactionSigned>(&json).unwrap(), tx);
+	}
+
 	#[test]
 	fn dummy_signed_payload_works() {
 		let tx: TransactionUnsigned = TransactionLegacyUnsigned {
@@ -17,7 +17,10 @@
 //! Utility impl for the RPC types.
 use super::*;
 use alloc::vec::Vec;
-use sp_core::{H160, U256};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
 
 impl From<BlockNumberOrTag> for BlockNumberOrTagOrHash {
 	fn from(b: BlockNumberOrTag) -> Self {
@@ -32,6 +35,7 @@ impl From<TransactionSigned> for TransactionUnsigned {
 	fn from(tx: TransactionSigned) -> Self {
 		use TransactionSigned::*;
 		match tx {
+			Transaction7702Signed(tx) => tx.transaction_7702_unsigned.into(),
 			Transaction4844Signed(tx) => tx.transaction_4844_unsigned.into(),
 			Transaction1559Signed(tx) => tx.transaction_1559_unsigned.into(),
 			Transaction2930Signed(tx) => tx.transaction_2930_unsigned.into(),
@@ -205,6 +209,26 @@ impl GenericTransaction {
 				gas_price: Some(tx.gas_price),
 				..Default::default()
 			},
+			Transaction7702Unsigned(tx) => GenericTransaction {
+				from,
+				r#type: Some(tx.r#type.as_byte()),
+				chain_id: Some(tx.chain_id),
+				input: tx.input.into(),
+				nonce: Some(tx.nonce),
+				value: Some(tx.value),
+				to: Some(tx.to),
+				gas: Some(tx.gas),
+				gas_price: Some(
+					U256::from(crate::GAS_PRICE)
+						.saturating_add(tx.max_priority_fee_per_gas)
+						.max(tx.max_fee_per_gas),
+				),
+				access_list: Some(tx.access_list),
+				authorization_list: tx.authorization_list,
+				max_fee_per_gas: Some(tx.max_fee_per_gas),
+				max_priority_fee_per_gas: Some(tx.max_priority_fee_per_gas),
+				..Default::default()
+			},
 			Transaction4844Unsigned(tx) => GenericTransaction {
 				from,
 				r#type: Some(tx.r#type.as_byte()),
@@ -316,7 +340,156 @@ impl GenericTransaction {
 				blob_versioned_hashes: self.blob_versioned_hashes,
 			}
 			.into()),
+			TYPE_EIP7702 => Ok(Transaction7702Unsigned {
+				r#type: TypeEip7702 {},
+				chain_id: self.chain_id.unwrap_or_default(),
+				input: self.input.to_bytes(),
+				nonce: self.nonce.unwrap_or_default(),
+				value: self.value.unwrap_or_default(),
+				to: self.to.unwrap_or_default(),
+				gas: self.gas.unwrap_or_default(),
+				gas_price: self.gas_price.unwrap_or_default(),
+				access_list: self.access_list.unwrap_or_default(),
+				authorization_list: self.authorization_list,
+				max_fee_per_gas: self.max_fee_per_gas.unwrap_or_default(),
+				max_priority_fee_per_gas: self.max_priority_fee_per_gas.unwrap_or_default(),
+			}
+			.into()),
 			_ => Err(()),
 		}
 	}
 }
+
+/// The access list of a transaction, returned by `eth_createAccessList`.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+pub struct AccessListResult {
+	/// The accounts and storage keys accessed by the transaction.
+	#[serde(rename = "accessList")]
+	pub access_list: AccessList,
+	/// The gas needed by the transaction when sent with the access list.
+	#[serde(rename = "gasUsed")]
+	pub gas_used: U256,
+}
+
+/// The gas schedule of the chain, returned by `revive_gasSchedule`.
+///
+/// The gas of an operation is the gas estimated for it without input. Each byte of input and of
+/// code adds `inputByte` and `codeByte` gas to the scheduled gas before it is encoded.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+pub struct GasScheduleResult {
+	/// The version of the schedule.
+	pub version: u32,
+	/// The gas of a transfer to an account without code.
+	pub transfer: U256,
+	/// The gas of a call to a contract.
+	pub call: U256,
+	/// The gas of a contract deployment.
+	pub create: U256,
+	/// The gas added for each byte of input.
+	#[serde(rename = "inputByte")]
+	pub input_byte: U256,
+	/// The gas added for each byte of code of a contract deployment.
+	#[serde(rename = "codeByte")]
+	pub code_byte: U256,
+}
+
+/// The fee history of a range of blocks, returned by `eth_feeHistory`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeeHistoryResult {
+	/// Lowest number block of the returned range.
+	#[serde(rename = "oldestBlock")]
+	pub oldest_block: U256,
+	/// An array of block base fees per gas, including the base fee of the block following the
+	/// newest block of the returned range.
+	#[serde(rename = "baseFeePerGas")]
+	pub base_fee_per_gas: Vec<U256>,
+	/// An array of block gas used ratios, the gas used divided by the gas limit.
+	#[serde(rename = "gasUsedRatio")]
+	pub gas_used_ratio: Vec<f64>,
+	/// An array of the effective priority fees per gas of each block, at the requested
+	/// percentiles.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub reward: Vec<Vec<U256>>,
+}
+
+/// The account and storage proofs of an account, returned by `eth_getProof`.
+///
+/// Unlike Ethereum, the proofs are Substrate trie proofs: `account_proof` proves the account and
+/// the contract info stored in the main trie against the state root of the block, and each
+/// `storage_proof` proves a storage slot stored in the child trie of the contract against
+/// `storage_hash`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct AccountProof {
+	/// The address of the account.
+	pub address: H160,
+	/// The trie nodes proving the account and its contract info.
+	#[serde(rename = "accountProof")]
+	pub account_proof: Vec<Bytes>,
+	/// The balance of the account.
+	pub balance: U256,
+	/// The code hash of the account, the hash of the empty code if the account is not a
+	/// contract.
+	#[serde(rename = "codeHash")]
+	pub code_hash: H256,
+	/// The nonce of the account.
+	pub nonce: U256,
+	/// The root of the child trie of the contract, zero if the account is not a contract.
+	#[serde(rename = "storageHash")]
+	pub storage_hash: H256,
+	/// The proofs of the requested storage slots.
+	#[serde(rename = "storageProof")]
+	pub storage_proof: Vec<StorageProof>,
+}
+
+/// The proof of a storage slot of a contract, part of an [`AccountProof`].
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct StorageProof {
+	/// The storage key of the slot.
+	pub key: U256,
+	/// The value of the slot, empty if the slot is not set.
+	pub value: Bytes,
+	/// The child trie nodes proving the value of the slot.
+	pub proof: Vec<Bytes>,
+}
+
+/// A [`GenericTransaction`] encoded as expected by the `ReviveApi` runtime API before version
+/// 6, which predates EIP-7702 authorization lists.
+///
+/// The authorization list is the last field of [`GenericTransaction`], thus it is simply left
+/// out of the encoding.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct LegacyGenericTransaction(pub GenericTransaction);
+
+impl Encode for LegacyGenericTransaction {
+	fn encode(&self) -> Vec<u8> {
+		let mut encoded = self.0.encode();
+		encoded.truncate(encoded.len() - self.0.authorization_list.encoded_size());
+		encoded
+	}
+}
+
+impl From<GenericTransaction> for LegacyGenericTransaction {
+	fn from(tx: GenericTransaction) -> Self {
+		Self(tx)
+	}
+}
+
+#[test]
+fn legacy_generic_transaction_encoding_omits_the_authorization_list() {
+	let tx = GenericTransaction {
+		from: Some(H160::repeat_byte(1)),
+		value: Some(U256::from(42)),
+		authorization_list: vec![Default::default()],
+		..Default::default()
+	};
+	let legacy = LegacyGenericTransaction::from(tx.clone());
+
+	let mut encoded = legacy.encode();
+	Vec::<AuthorizationListEntry>::new().encode_to(&mut encoded);
+	let decoded = GenericTransaction::decode(&mut &encoded[..]).unwrap();
+	assert_eq!(decoded, GenericTransaction { authorization_list: Vec::new(), ..tx });
+}
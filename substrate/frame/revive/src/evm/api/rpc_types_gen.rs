@@ -17,9 +17,9 @@
 //! Generated JSON-RPC types.
 #![allow(missing_docs)]
 
-use super::{byte::*, TypeEip1559, TypeEip2930, TypeEip4844, TypeLegacy};
+use super::{byte::*, TypeEip1559, TypeEip2930, TypeEip4844, TypeEip7702, TypeLegacy};
 use alloc::vec::Vec;
-use codec::{Decode, Encode};
+use codec::{Decode, DecodeWithMemTracking, Encode};
 use derive_more::{From, TryInto};
 pub use ethereum_types::*;
 use scale_info::TypeInfo;
@@ -314,6 +314,13 @@ pub struct GenericTransaction {
 	/// value
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub value: Option<U256>,
+	/// authorizationList
+	/// EIP-7702 authorization list
+	///
+	/// Kept last, so that the SCALE encoding of the other fields matches
+	/// [`super::LegacyGenericTransaction`].
+	#[serde(rename = "authorizationList", default, skip_serializing_if = "Vec::is_empty")]
+	pub authorization_list: AuthorizationList,
 }
 
 /// Receipt information
@@ -435,6 +442,7 @@ pub enum TransactionUnsigned {
 	Transaction1559Unsigned(Transaction1559Unsigned),
 	Transaction2930Unsigned(Transaction2930Unsigned),
 	TransactionLegacyUnsigned(TransactionLegacyUnsigned),
+	Transaction7702Unsigned(Transaction7702Unsigned),
 }
 impl Default for TransactionUnsigned {
 	fn default() -> Self {
@@ -445,6 +453,9 @@ impl Default for TransactionUnsigned {
 /// Access list
 pub type AccessList = Vec<AccessListEntry>;
 
+/// Authorization list
+pub type AuthorizationList = Vec<AuthorizationListEntry>;
+
 /// Address(es)
 #[derive(
 	Debug, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, From, TryInto, Eq, PartialEq,
@@ -677,6 +688,52 @@ pub struct Transaction4844Unsigned {
 	pub value: U256,
 }
 
+/// EIP-7702 transaction.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+pub struct Transaction7702Unsigned {
+	/// accessList
+	/// EIP-2930 access list
+	#[serde(rename = "accessList")]
+	pub access_list: AccessList,
+	/// authorizationList
+	/// List of account code authorizations
+	#[serde(rename = "authorizationList")]
+	pub authorization_list: AuthorizationList,
+	/// chainId
+	/// Chain ID that this transaction is valid on.
+	#[serde(rename = "chainId")]
+	pub chain_id: U256,
+	/// gas limit
+	pub gas: U256,
+	/// gas price
+	/// The effective gas price paid by the sender in wei. For transactions not yet included in a
+	/// block, this value should be set equal to the max fee per gas. This field is DEPRECATED,
+	/// please transition to using effectiveGasPrice in the receipt object going forward.
+	#[serde(rename = "gasPrice")]
+	pub gas_price: U256,
+	/// input data
+	pub input: Bytes,
+	/// max fee per gas
+	/// The maximum total fee per gas the sender is willing to pay (includes the network / base fee
+	/// and miner / priority fee) in wei
+	#[serde(rename = "maxFeePerGas")]
+	pub max_fee_per_gas: U256,
+	/// max priority fee per gas
+	/// Maximum fee per gas the sender is willing to pay to miners in wei
+	#[serde(rename = "maxPriorityFeePerGas")]
+	pub max_priority_fee_per_gas: U256,
+	/// nonce
+	pub nonce: U256,
+	/// to address
+	pub to: Address,
+	/// type
+	pub r#type: TypeEip7702,
+	/// value
+	pub value: U256,
+}
+
 /// Legacy transaction.
 #[derive(
 	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
@@ -713,6 +770,7 @@ pub enum TransactionSigned {
 	Transaction1559Signed(Transaction1559Signed),
 	Transaction2930Signed(Transaction2930Signed),
 	TransactionLegacySigned(TransactionLegacySigned),
+	Transaction7702Signed(Transaction7702Signed),
 }
 impl Default for TransactionSigned {
 	fn default() -> Self {
@@ -738,7 +796,17 @@ pub struct Withdrawal {
 
 /// Access list entry
 #[derive(
-	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+	Debug,
+	Default,
+	Clone,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+	Eq,
+	PartialEq,
 )]
 pub struct AccessListEntry {
 	pub address: Address,
@@ -746,6 +814,40 @@ pub struct AccessListEntry {
 	pub storage_keys: Vec<H256>,
 }
 
+/// Authorization list entry
+#[derive(
+	Debug,
+	Default,
+	Clone,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+	Eq,
+	PartialEq,
+)]
+pub struct AuthorizationListEntry {
+	/// chainId
+	/// Chain ID that this authorization is valid on, zero if valid on all chains.
+	#[serde(rename = "chainId")]
+	pub chain_id: U256,
+	/// address
+	/// The address of the contract the authorizing account delegates its code to.
+	pub address: Address,
+	/// nonce
+	pub nonce: U256,
+	/// yParity
+	/// The parity (0 for even, 1 for odd) of the y-value of the secp256k1 signature.
+	#[serde(rename = "yParity")]
+	pub y_parity: U256,
+	/// r
+	pub r: U256,
+	/// s
+	pub s: U256,
+}
+
 /// Filter Topic List Entry
 #[derive(
 	Debug, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, From, TryInto, Eq, PartialEq,
@@ -824,6 +926,28 @@ pub struct Transaction4844Signed {
 	pub y_parity: U256,
 }
 
+/// Signed 7702 Transaction
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+pub struct Transaction7702Signed {
+	#[serde(flatten)]
+	pub transaction_7702_unsigned: Transaction7702Unsigned,
+	/// r
+	pub r: U256,
+	/// s
+	pub s: U256,
+	/// v
+	/// For backwards compatibility, `v` is optionally provided as an alternative to `yParity`.
+	/// This field is DEPRECATED and all use of it should migrate to `yParity`.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub v: Option<U256>,
+	/// yParity
+	/// The parity (0 for even, 1 for odd) of the y-value of the secp256k1 signature.
+	#[serde(rename = "yParity")]
+	pub y_parity: U256,
+}
+
 /// Signed Legacy Transaction
 #[derive(
 	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
@@ -17,6 +17,7 @@
 //! Ethereum signature utilities
 
 use super::*;
+use alloc::vec::Vec;
 use sp_core::{H160, U256};
 use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
 
@@ -40,6 +41,8 @@ impl TransactionUnsigned {
 	/// Extract the unsigned transaction from a signed transaction.
 	pub fn from_signed(tx: TransactionSigned) -> Self {
 		match tx {
+			TransactionSigned::Transaction7702Signed(signed) =>
+				Self::Transaction7702Unsigned(signed.transaction_7702_unsigned),
 			TransactionSigned::TransactionLegacySigned(signed) =>
 				Self::TransactionLegacyUnsigned(signed.transaction_legacy_unsigned),
 			TransactionSigned::Transaction4844Signed(signed) =>
@@ -58,6 +61,15 @@ impl TransactionUnsigned {
 		let recovery_id = signature[64];
 
 		match self {
+			TransactionUnsigned::Transaction7702Unsigned(transaction_7702_unsigned) =>
+				Transaction7702Signed {
+					transaction_7702_unsigned,
+					r,
+					s,
+					v: None,
+					y_parity: U256::from(recovery_id),
+				}
+				.into(),
 			TransactionUnsigned::Transaction2930Unsigned(transaction_2930_unsigned) =>
 				Transaction2930Signed {
 					transaction_2930_unsigned,
@@ -108,15 +120,12 @@ impl TransactionSigned {
 		use TransactionSigned::*;
 		let (r, s, v) = match self {
 			TransactionLegacySigned(tx) => (tx.r, tx.s, tx.extract_recovery_id().ok_or(())?),
+			Transaction7702Signed(tx) => (tx.r, tx.s, tx.y_parity.try_into().map_err(|_| ())?),
 			Transaction4844Signed(tx) => (tx.r, tx.s, tx.y_parity.try_into().map_err(|_| ())?),
 			Transaction1559Signed(tx) => (tx.r, tx.s, tx.y_parity.try_into().map_err(|_| ())?),
 			Transaction2930Signed(tx) => (tx.r, tx.s, tx.y_parity.try_into().map_err(|_| ())?),
 		};
-		let mut sig = [0u8; 65];
-		r.write_as_big_endian(sig[0..32].as_mut());
-		s.write_as_big_endian(sig[32..64].as_mut());
-		sig[64] = v;
-		Ok(sig)
+		Ok(raw_signature(r, s, v))
 	}
 
 	/// Recover the Ethereum address, from a signed transaction.
@@ -129,6 +138,11 @@ impl TransactionSigned {
 				let tx = &tx.transaction_legacy_unsigned;
 				s.append(tx);
 			},
+			Transaction7702Signed(tx) => {
+				let tx = &tx.transaction_7702_unsigned;
+				s.append(&tx.r#type.value());
+				s.append(tx);
+			},
 			Transaction4844Signed(tx) => {
 				let tx = &tx.transaction_4844_unsigned;
 				s.append(&tx.r#type.value());
@@ -146,14 +160,70 @@ impl TransactionSigned {
 			},
 		}
 		let bytes = s.out().to_vec();
-		let signature = self.raw_signature()?;
+		recover_address(&self.raw_signature()?, &bytes)
+	}
+}
+
+/// The magic byte prefixed to the payload signed by an authorization, see
+/// <https://eips.ethereum.org/EIPS/eip-7702>
+pub const AUTHORIZATION_MAGIC: u8 = 0x05;
 
-		let hash = keccak_256(&bytes);
-		let mut addr = H160::default();
-		let pk = secp256k1_ecdsa_recover(&signature, &hash).map_err(|_| ())?;
-		addr.assign_from_slice(&keccak_256(&pk[..])[12..]);
-		Ok(addr)
+/// Half of the order of the secp256k1 curve, the largest `s` value an authorization can be
+/// signed with, see <https://eips.ethereum.org/EIPS/eip-2>
+const SECP256K1N_HALF: U256 =
+	U256([0xDFE92F46681B20A0, 0x5D576E7357A4501D, 0xFFFFFFFFFFFFFFFF, 0x7FFFFFFFFFFFFFFF]);
+
+impl AuthorizationListEntry {
+	/// Return the bytes signed by the authorizing account.
+	pub fn unsigned_payload(chain_id: U256, address: H160, nonce: U256) -> Vec<u8> {
+		let mut s = rlp::RlpStream::new_list(3);
+		s.append(&chain_id);
+		s.append(&address);
+		s.append(&nonce);
+		[&[AUTHORIZATION_MAGIC][..], &s.out()].concat()
 	}
+
+	/// Create an authorization from its fields and a signature.
+	pub fn with_signature(chain_id: U256, address: H160, nonce: U256, signature: [u8; 65]) -> Self {
+		Self {
+			chain_id,
+			address,
+			nonce,
+			y_parity: U256::from(signature[64]),
+			r: U256::from_big_endian(&signature[..32]),
+			s: U256::from_big_endian(&signature[32..64]),
+		}
+	}
+
+	/// Recover the Ethereum address of the account that signed the authorization.
+	///
+	/// Fails for a signature with a `y_parity` other than 0 or 1, or with a high `s` value.
+	pub fn recover_authority(&self) -> Result<H160, ()> {
+		if self.y_parity > U256::one() || self.s > SECP256K1N_HALF {
+			return Err(());
+		}
+		let signature = raw_signature(self.r, self.s, self.y_parity.as_u32() as u8);
+		let payload = Self::unsigned_payload(self.chain_id, self.address, self.nonce);
+		recover_address(&signature, &payload)
+	}
+}
+
+/// Build the raw 65 bytes signature from its components.
+fn raw_signature(r: U256, s: U256, v: u8) -> [u8; 65] {
+	let mut sig = [0u8; 65];
+	r.write_as_big_endian(sig[0..32].as_mut());
+	s.write_as_big_endian(sig[32..64].as_mut());
+	sig[64] = v;
+	sig
+}
+
+/// Recover the Ethereum address that signed the given payload.
+fn recover_address(signature: &[u8; 65], payload: &[u8]) -> Result<H160, ()> {
+	let hash = keccak_256(payload);
+	let mut addr = H160::default();
+	let pk = secp256k1_ecdsa_recover(signature, &hash).map_err(|_| ())?;
+	addr.assign_from_slice(&keccak_256(&pk[..])[12..]);
+	Ok(addr)
 }
 
 #[test]
@@ -185,3 +255,28 @@ fn sign_and_recover_work() {
 		assert_eq!(tx, signed);
 	}
 }
+
+#[test]
+fn sign_and_recover_authorization_work() {
+	let account = Account::default();
+	let authorization = account.sign_authorization(1.into(), H160::repeat_byte(0x42), 7.into());
+	assert_eq!(authorization.recover_authority(), Ok(account.address()));
+
+	let tampered = AuthorizationListEntry { nonce: 8.into(), ..authorization.clone() };
+	assert_ne!(tampered.recover_authority(), Ok(account.address()));
+
+	// The same signature with the `v` value of a legacy transaction.
+	let legacy_v =
+		AuthorizationListEntry { y_parity: authorization.y_parity + 27, ..authorization.clone() };
+	assert_eq!(legacy_v.recover_authority(), Err(()));
+
+	// The malleated signature `(r, n - s)` is valid for the flipped `y_parity`, but its `s` value
+	// is high.
+	let secp256k1n = SECP256K1N_HALF * 2 + 1;
+	let malleated = AuthorizationListEntry {
+		y_parity: U256::one() - authorization.y_parity,
+		s: secp256k1n - authorization.s,
+		..authorization
+	};
+	assert_eq!(malleated.recover_authority(), Err(()));
+}
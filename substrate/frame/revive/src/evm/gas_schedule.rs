@@ -0,0 +1,148 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! A stable gas schedule for common Ethereum operations.
+
+use crate::Weight;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::U256;
+use sp_runtime::RuntimeDebug;
+
+/// The gas quoted for an operation, and the resources it may use at most.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ScheduledCost<Balance> {
+	/// The gas quoted for the operation.
+	pub gas: u64,
+	/// The weight the operation may consume to be priced with [`Self::gas`].
+	pub weight_limit: Weight,
+	/// The storage deposit the operation may charge to be priced with [`Self::gas`].
+	pub deposit_limit: Balance,
+}
+
+/// The operations priced by a [`GasSchedule`].
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum ScheduledOperation {
+	/// A transfer to an account without code.
+	Transfer,
+	/// A call to a contract, or to an account delegating to one.
+	Call,
+	/// The deployment of a contract.
+	Create,
+}
+
+/// A stable gas schedule for common Ethereum operations.
+///
+/// Ethereum tooling assumes that the gas of an operation does not change over time. The gas
+/// estimated by [`crate::Pallet::bare_eth_transact`] however encodes the weight and the storage
+/// deposit of a dry run, which change whenever the runtime updates its weights or deposits.
+///
+/// A runtime can declare a schedule through [`crate::Config::EthGasSchedule`]. A transaction
+/// whose dry run stays within the limits of its [`ScheduledCost`] is then estimated from the
+/// schedule alone: its gas is the scheduled gas, and its weight and deposit limits are the limits
+/// of the schedule. Transactions exceeding these limits are estimated from their dry run.
+///
+/// The scheduled gas must cover the fee of a transaction using the limits of the schedule.
+/// Otherwise the estimate falls back to the fee, and is no longer stable.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct GasSchedule<Balance> {
+	/// The version of the schedule.
+	///
+	/// It must be increased whenever any of the costs below change.
+	pub version: u32,
+	/// The cost of a transfer to an account without code.
+	pub transfer: ScheduledCost<Balance>,
+	/// The cost of a call to a contract.
+	pub call: ScheduledCost<Balance>,
+	/// The cost of a contract deployment.
+	pub create: ScheduledCost<Balance>,
+	/// The gas added for each byte of call data or constructor data.
+	pub input_byte: u64,
+	/// The gas added for each byte of code of a contract deployment.
+	pub code_byte: u64,
+}
+
+impl<Balance: PartialOrd> GasSchedule<Balance> {
+	/// Returns the cost of the given operation.
+	pub fn cost(&self, operation: ScheduledOperation) -> &ScheduledCost<Balance> {
+		match operation {
+			ScheduledOperation::Transfer => &self.transfer,
+			ScheduledOperation::Call => &self.call,
+			ScheduledOperation::Create => &self.create,
+		}
+	}
+
+	/// Returns the gas of the given operation, with `input_len` bytes of input and `code_len`
+	/// bytes of code.
+	pub fn gas(&self, operation: ScheduledOperation, input_len: u32, code_len: u32) -> U256 {
+		U256::from(self.cost(operation).gas)
+			.saturating_add(U256::from(self.input_byte).saturating_mul(input_len.into()))
+			.saturating_add(U256::from(self.code_byte).saturating_mul(code_len.into()))
+	}
+
+	/// Returns the cost of the given operation if it covers `weight` and `deposit`.
+	pub fn cost_within(
+		&self,
+		operation: ScheduledOperation,
+		weight: Weight,
+		deposit: &Balance,
+	) -> Option<&ScheduledCost<Balance>> {
+		let cost = self.cost(operation);
+		(cost.weight_limit.all_gte(weight) && &cost.deposit_limit >= deposit).then_some(cost)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn schedule() -> GasSchedule<u64> {
+		let cost = |gas| ScheduledCost {
+			gas,
+			weight_limit: Weight::from_parts(1_000, 100),
+			deposit_limit: 10,
+		};
+		GasSchedule {
+			version: 1,
+			transfer: cost(21_000),
+			call: cost(50_000),
+			create: cost(100_000),
+			input_byte: 16,
+			code_byte: 200,
+		}
+	}
+
+	#[test]
+	fn gas_adds_input_and_code() {
+		let schedule = schedule();
+		assert_eq!(schedule.gas(ScheduledOperation::Transfer, 0, 0), U256::from(21_000));
+		assert_eq!(schedule.gas(ScheduledOperation::Call, 10, 0), U256::from(50_160));
+		assert_eq!(schedule.gas(ScheduledOperation::Create, 10, 100), U256::from(120_160));
+	}
+
+	#[test]
+	fn cost_within_checks_limits() {
+		let schedule = schedule();
+		let op = ScheduledOperation::Call;
+		assert_eq!(
+			schedule.cost_within(op, Weight::from_parts(1_000, 100), &10),
+			Some(&schedule.call)
+		);
+		assert_eq!(schedule.cost_within(op, Weight::from_parts(1_001, 100), &10), None);
+		assert_eq!(schedule.cost_within(op, Weight::from_parts(1_000, 101), &10), None);
+		assert_eq!(schedule.cost_within(op, Weight::from_parts(1_000, 100), &11), None);
+	}
+}
@@ -16,27 +16,32 @@
 // limitations under the License.
 //! Runtime types for integrating `pallet-revive` with the EVM.
 use crate::{
+	access_list,
 	evm::{
 		api::{GenericTransaction, TransactionSigned},
 		GasEncoder,
 	},
-	AccountIdOf, AddressMapper, BalanceOf, Config, ConversionPrecision, MomentOf, Pallet,
+	limits, AccountIdOf, AddressMapper, BalanceOf, Config, ConversionPrecision, MomentOf, Pallet,
 	LOG_TARGET,
 };
 use alloc::vec::Vec;
 use codec::{Decode, DecodeWithMemTracking, Encode};
+use core::marker::PhantomData;
 use frame_support::{
 	dispatch::{DispatchInfo, GetDispatchInfo},
-	traits::{ExtrinsicCall, InherentBuilder, SignedTransactionBuilder},
+	traits::{ExtrinsicCall, InherentBuilder, IsSubType, OriginTrait, SignedTransactionBuilder},
+	weights::Weight,
+	DefaultNoBound,
 };
 use pallet_transaction_payment::OnChargeTransaction;
 use scale_info::{StaticTypeInfo, TypeInfo};
 use sp_core::{Get, H256, U256};
 use sp_runtime::{
 	generic::{self, CheckedExtrinsic, ExtrinsicFormat},
+	impl_tx_ext_default,
 	traits::{
-		self, Checkable, Dispatchable, ExtrinsicLike, ExtrinsicMetadata, IdentifyAccount, Member,
-		TransactionExtension,
+		self, Checkable, DispatchInfoOf, DispatchOriginOf, Dispatchable, ExtrinsicLike,
+		ExtrinsicMetadata, IdentifyAccount, Member, TransactionExtension,
 	},
 	transaction_validity::{InvalidTransaction, TransactionValidityError},
 	OpaqueExtrinsic, RuntimeDebug,
@@ -257,6 +262,7 @@ pub trait EthExtra {
 	/// It should include at least:
 	/// - [`frame_system::CheckNonce`] to ensure that the nonce from the Ethereum transaction is
 	///   correct.
+	/// - [`ApplyAuthorizations`] to apply the authorizations of an EIP-7702 transaction.
 	type Extension: TransactionExtension<CallOf<Self::Config>>;
 
 	/// Get the transaction extension to apply to an unsigned [`crate::Call::eth_transact`]
@@ -306,8 +312,24 @@ pub trait EthExtra {
 		})?;
 
 		let signer = <Self::Config as Config>::AddressMapper::to_fallback_account_id(&signer);
-		let GenericTransaction { nonce, chain_id, to, value, input, gas, gas_price, .. } =
-			GenericTransaction::from_signed(tx, None);
+		let GenericTransaction {
+			nonce,
+			chain_id,
+			to,
+			value,
+			input,
+			gas,
+			gas_price,
+			access_list,
+			authorization_list,
+			..
+		} = GenericTransaction::from_signed(tx, None);
+		let access_list = access_list.unwrap_or_default();
+
+		if access_list::len(&access_list) > limits::ACCESS_LIST_ITEMS {
+			log::debug!(target: LOG_TARGET, "Access list too large");
+			return Err(InvalidTransaction::Call);
+		}
 
 		let Some(gas) = gas else {
 			log::debug!(target: LOG_TARGET, "No gas provided");
@@ -337,12 +359,33 @@ pub trait EthExtra {
 			})?;
 
 		let call = if let Some(dest) = to {
-			crate::Call::call::<Self::Config> {
-				dest,
-				value,
-				gas_limit,
-				storage_deposit_limit,
-				data,
+			if !authorization_list.is_empty() {
+				crate::Call::eth_call_with_authorizations::<Self::Config> {
+					dest,
+					value,
+					gas_limit,
+					storage_deposit_limit,
+					data,
+					access_list,
+					authorization_list,
+				}
+			} else if access_list.is_empty() {
+				crate::Call::call::<Self::Config> {
+					dest,
+					value,
+					gas_limit,
+					storage_deposit_limit,
+					data,
+				}
+			} else {
+				crate::Call::eth_call::<Self::Config> {
+					dest,
+					value,
+					gas_limit,
+					storage_deposit_limit,
+					data,
+					access_list,
+				}
 			}
 		} else {
 			let blob = match polkavm::ProgramBlob::blob_length(&data) {
@@ -356,13 +399,24 @@ pub trait EthExtra {
 				return Err(InvalidTransaction::Call);
 			};
 
-			crate::Call::instantiate_with_code::<Self::Config> {
-				value,
-				gas_limit,
-				storage_deposit_limit,
-				code: code.to_vec(),
-				data: data.to_vec(),
-				salt: None,
+			if access_list.is_empty() {
+				crate::Call::instantiate_with_code::<Self::Config> {
+					value,
+					gas_limit,
+					storage_deposit_limit,
+					code: code.to_vec(),
+					data: data.to_vec(),
+					salt: None,
+				}
+			} else {
+				crate::Call::eth_instantiate_with_code::<Self::Config> {
+					value,
+					gas_limit,
+					storage_deposit_limit,
+					code: code.to_vec(),
+					data: data.to_vec(),
+					access_list,
+				}
 			}
 		};
 
@@ -405,6 +459,73 @@ pub trait EthExtra {
 	}
 }
 
+/// Applies the authorizations of a [`crate::Call::eth_call_with_authorizations`] before it is
+/// dispatched.
+///
+/// The authorizations of an [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) transaction
+/// persist even if the call reverts, while a failed dispatchable rolls back all of its storage
+/// changes. The runtime's transaction extension should therefore include this extension after
+/// [`frame_system::CheckNonce`], so that the sender of a transaction can authorize a delegation
+/// for the nonce following the one of the transaction.
+#[derive(Encode, Decode, DecodeWithMemTracking, DefaultNoBound, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ApplyAuthorizations<T>(PhantomData<T>);
+
+impl<T> core::fmt::Debug for ApplyAuthorizations<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "ApplyAuthorizations")
+	}
+}
+
+impl<T> ApplyAuthorizations<T> {
+	/// Create a new `ApplyAuthorizations` extension.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T> TransactionExtension<CallOf<T>> for ApplyAuthorizations<T>
+where
+	T: Config + Send + Sync,
+	BalanceOf<T>: Into<U256> + TryFrom<U256>,
+	MomentOf<T>: Into<U256>,
+	T::Hash: frame_support::traits::IsType<H256>,
+	CallOf<T>: IsSubType<crate::Call<T>>,
+{
+	const IDENTIFIER: &'static str = "ApplyAuthorizations";
+	type Implicit = ();
+	type Val = ();
+	type Pre = ();
+
+	fn weight(&self, call: &CallOf<T>) -> Weight {
+		match call.is_sub_type() {
+			Some(crate::Call::eth_call_with_authorizations { authorization_list, .. }) =>
+				Pallet::<T>::authorizations_weight(authorization_list.len() as u32),
+			_ => Weight::zero(),
+		}
+	}
+
+	fn prepare(
+		self,
+		_val: Self::Val,
+		origin: &DispatchOriginOf<CallOf<T>>,
+		call: &CallOf<T>,
+		_info: &DispatchInfoOf<CallOf<T>>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		if let Some(crate::Call::eth_call_with_authorizations { authorization_list, .. }) =
+			call.is_sub_type()
+		{
+			if origin.as_signer().is_some() {
+				Pallet::<T>::apply_authorizations(authorization_list);
+			}
+		}
+		Ok(())
+	}
+
+	impl_tx_ext_default!(CallOf<T>; validate);
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -424,7 +545,8 @@ mod test {
 
 	#[derive(Clone, PartialEq, Eq, Debug)]
 	pub struct Extra;
-	type SignedExtra = (frame_system::CheckNonce<Test>, ChargeTransactionPayment<Test>);
+	type SignedExtra =
+		(frame_system::CheckNonce<Test>, ChargeTransactionPayment<Test>, ApplyAuthorizations<Test>);
 
 	use pallet_transaction_payment::ChargeTransactionPayment;
 	impl EthExtra for Extra {
@@ -432,7 +554,11 @@ mod test {
 		type Extension = SignedExtra;
 
 		fn get_eth_extension(nonce: u32, tip: BalanceOf<Test>) -> Self::Extension {
-			(frame_system::CheckNonce::from(nonce), ChargeTransactionPayment::from(tip))
+			(
+				frame_system::CheckNonce::from(nonce),
+				ChargeTransactionPayment::from(tip),
+				ApplyAuthorizations::new(),
+			)
 		}
 	}
 
@@ -588,6 +714,81 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn check_eth_transact_call_with_access_list_works() {
+		let mut builder = UncheckedExtrinsicBuilder::call_with(H160::from([1u8; 20]));
+		let access_list = vec![AccessListEntry {
+			address: H160::from([1u8; 20]),
+			storage_keys: vec![H256::repeat_byte(1)],
+		}];
+		builder.tx.r#type = Some(TypeEip2930 {}.as_byte());
+		builder.tx.access_list = Some(access_list.clone());
+		let (call, _, tx) = builder.check().unwrap();
+		let (gas_limit, storage_deposit_limit) =
+			<<Test as Config>::EthGasEncoder as GasEncoder<_>>::decode(tx.gas.unwrap()).unwrap();
+
+		assert_eq!(
+			call,
+			crate::Call::eth_call::<Test> {
+				dest: tx.to.unwrap(),
+				value: tx.value.unwrap_or_default().as_u64(),
+				data: tx.input.to_vec(),
+				gas_limit,
+				storage_deposit_limit,
+				access_list,
+			}
+			.into()
+		);
+	}
+
+	#[test]
+	fn check_eth_transact_rejects_too_large_access_list() {
+		let builder = UncheckedExtrinsicBuilder::call_with(H160::from([1u8; 20]));
+		let access_list = vec![AccessListEntry {
+			address: H160::from([1u8; 20]),
+			storage_keys: (0..limits::ACCESS_LIST_ITEMS)
+				.map(|i| H256::from_low_u64_be(i.into()))
+				.collect(),
+		}];
+
+		assert_eq!(
+			builder.mutate_estimate_and_check(Box::new(move |tx| {
+				tx.r#type = Some(TypeEip2930 {}.as_byte());
+				tx.access_list = Some(access_list);
+			})),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Call))
+		);
+	}
+
+	#[test]
+	fn check_eth_transact_call_with_authorizations_works() {
+		let mut builder = UncheckedExtrinsicBuilder::call_with(H160::from([1u8; 20]));
+		let authorization = Account::default().sign_authorization(
+			<Test as Config>::ChainId::get().into(),
+			H160::from([2u8; 20]),
+			1.into(),
+		);
+		builder.tx.r#type = Some(TypeEip7702 {}.as_byte());
+		builder.tx.authorization_list = vec![authorization.clone()];
+		let (call, _, tx) = builder.check().unwrap();
+		let (gas_limit, storage_deposit_limit) =
+			<<Test as Config>::EthGasEncoder as GasEncoder<_>>::decode(tx.gas.unwrap()).unwrap();
+
+		assert_eq!(
+			call,
+			crate::Call::eth_call_with_authorizations::<Test> {
+				dest: tx.to.unwrap(),
+				value: tx.value.unwrap_or_default().as_u64(),
+				data: tx.input.to_vec(),
+				gas_limit,
+				storage_deposit_limit,
+				access_list: vec![],
+				authorization_list: vec![authorization],
+			}
+			.into()
+		);
+	}
+
 	#[test]
 	fn check_eth_transact_instantiate_works() {
 		let (code, _) = compile_module("dummy").unwrap();
@@ -0,0 +1,105 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	evm::AccessListEntry, exec::Key, primitives::ExecReturnValue, pure_precompiles,
+	tracing::Tracer, DispatchError, Weight,
+};
+use alloc::{
+	collections::{BTreeMap, BTreeSet},
+	vec::Vec,
+};
+use sp_core::{H160, H256, U256};
+
+/// A Tracer that records the accounts and storage keys accessed by a transaction, to build its
+/// [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) access list.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct AccessListTracer {
+	/// The accounts that never need to be part of the access list, such as the sender.
+	excluded: BTreeSet<H160>,
+	/// The accessed accounts along with their accessed storage keys.
+	accessed: BTreeMap<H160, BTreeSet<H256>>,
+}
+
+impl AccessListTracer {
+	/// Create a new [`AccessListTracer`] instance.
+	///
+	/// The accounts in `excluded` are only part of the access list if some of their storage
+	/// keys are accessed.
+	pub fn new(excluded: impl IntoIterator<Item = H160>) -> Self {
+		Self { excluded: excluded.into_iter().collect(), accessed: Default::default() }
+	}
+
+	/// Collect the access list and reset the tracer.
+	pub fn collect_access_list(&mut self) -> Vec<AccessListEntry> {
+		core::mem::take(&mut self.accessed)
+			.into_iter()
+			.filter(|(address, storage_keys)| {
+				!storage_keys.is_empty() ||
+					!(self.excluded.contains(address) ||
+						pure_precompiles::is_precompile(address))
+			})
+			.map(|(address, storage_keys)| AccessListEntry {
+				address,
+				storage_keys: storage_keys.into_iter().collect(),
+			})
+			.collect()
+	}
+
+	/// Record an access to `key` of the contract at `address`.
+	fn record_storage_access(&mut self, address: H160, key: &Key) {
+		// Only fixed size keys can be part of an access list.
+		if let Key::Fix(key) = key {
+			self.accessed.entry(address).or_default().insert(H256(*key));
+		}
+	}
+}
+
+impl Tracer for AccessListTracer {
+	fn enter_child_span(
+		&mut self,
+		_from: H160,
+		to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas_left: Weight,
+	) {
+		self.accessed.entry(to).or_default();
+	}
+
+	fn log_event(&mut self, _address: H160, _topics: &[H256], _data: &[u8]) {}
+
+	fn exit_child_span(&mut self, _output: &ExecReturnValue, _gas_left: Weight) {}
+
+	fn exit_child_span_with_error(&mut self, _error: DispatchError, _gas_left: Weight) {}
+
+	fn storage_read(&mut self, address: H160, key: &Key, _value: Option<&[u8]>) {
+		self.record_storage_access(address, key);
+	}
+
+	fn storage_write(
+		&mut self,
+		address: H160,
+		key: &Key,
+		_old_value: Option<&[u8]>,
+		_new_value: Option<&[u8]>,
+	) {
+		self.record_storage_access(address, key);
+	}
+}
@@ -0,0 +1,217 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	evm::{Bytes, PrestateTrace, PrestateTraceInfo},
+	exec::Key,
+	primitives::ExecReturnValue,
+	tracing::Tracer,
+	AddressMapper, BalanceOf, Config, ContractInfoOf, DispatchError, MomentOf, Pallet,
+	PristineCode, Weight,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::marker::PhantomData;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::Bounded, SaturatedConversion};
+
+/// A Tracer that reports the state of the accounts touched by a transaction.
+///
+/// The state of an account is recorded the first time it is touched. In diff mode, the state
+/// of the touched accounts is read again when the trace is collected, and only the changes are
+/// reported.
+pub struct PrestateTracer<T> {
+	/// Whether to report the state changes instead of the state before the transaction.
+	diff_mode: bool,
+	/// Whether to omit the code of the accounts.
+	disable_code: bool,
+	/// Whether to omit the storage of the accounts.
+	disable_storage: bool,
+	/// The state of the touched accounts before the transaction.
+	pre: BTreeMap<H160, PrestateTraceInfo>,
+	/// The storage keys accessed by the transaction, used to read the state after the
+	/// transaction in diff mode.
+	storage_keys: BTreeMap<H160, Vec<Key>>,
+	_phantom: PhantomData<T>,
+}
+
+impl<T: Config> PrestateTracer<T>
+where
+	BalanceOf<T>: Into<U256> + TryFrom<U256> + Bounded,
+	MomentOf<T>: Into<U256>,
+	T::Hash: frame_support::traits::IsType<H256>,
+{
+	/// Create a new [`PrestateTracer`] instance.
+	pub fn new(diff_mode: bool, disable_code: bool, disable_storage: bool) -> Self {
+		Self {
+			diff_mode,
+			disable_code,
+			disable_storage,
+			pre: Default::default(),
+			storage_keys: Default::default(),
+			_phantom: PhantomData,
+		}
+	}
+
+	/// Collect the trace and reset the tracer.
+	///
+	/// Returns `None` if no account was touched.
+	pub fn collect_trace(&mut self) -> Option<PrestateTrace> {
+		let pre = core::mem::take(&mut self.pre);
+		let storage_keys = core::mem::take(&mut self.storage_keys);
+		if pre.is_empty() {
+			return None;
+		}
+
+		if !self.diff_mode {
+			return Some(PrestateTrace::Prestate(pre));
+		}
+
+		let mut pre_diff = BTreeMap::new();
+		let mut post_diff = BTreeMap::new();
+		for (address, mut pre_info) in pre {
+			let account_id = T::AddressMapper::to_account_id(&address);
+
+			// Removed accounts are only reported in the pre state.
+			if !frame_system::Pallet::<T>::account_exists(&account_id) {
+				pre_diff.insert(address, pre_info);
+				continue;
+			}
+
+			let post_info = self.read_account(&address);
+			let contract_info = ContractInfoOf::<T>::get(&address);
+			let mut post_storage = BTreeMap::new();
+			for key in storage_keys.get(&address).into_iter().flatten() {
+				let unhashed = Bytes(key.unhashed().to_vec());
+				let value = contract_info.as_ref().and_then(|info| info.read(key)).map(Bytes);
+				if pre_info.storage.get(&unhashed) != Some(&value) {
+					post_storage.insert(unhashed, value);
+				}
+			}
+
+			pre_info.storage.retain(|key, _| post_storage.contains_key(key));
+			let post_info = PrestateTraceInfo {
+				balance: post_info.balance.filter(|balance| Some(*balance) != pre_info.balance),
+				nonce: post_info.nonce.filter(|nonce| Some(*nonce) != pre_info.nonce),
+				code: post_info.code.filter(|code| Some(code) != pre_info.code.as_ref()),
+				// Deleted keys are only reported in the pre state.
+				storage: post_storage
+					.into_iter()
+					.filter_map(|(key, value)| Some((key, Some(value?))))
+					.collect(),
+			};
+
+			if post_info != PrestateTraceInfo::default() || !pre_info.storage.is_empty() {
+				pre_diff.insert(address, pre_info);
+				post_diff.insert(address, post_info);
+			}
+		}
+
+		Some(PrestateTrace::DiffMode { pre: pre_diff, post: post_diff })
+	}
+
+	/// Read the current balance, nonce and code of the account at `address`.
+	fn read_account(&self, address: &H160) -> PrestateTraceInfo {
+		let account_id = T::AddressMapper::to_account_id(address);
+		let code = if self.disable_code {
+			None
+		} else {
+			ContractInfoOf::<T>::get(address)
+				.and_then(|info| PristineCode::<T>::get(info.code_hash))
+				.map(|code| Bytes(code.into_inner()))
+		};
+
+		PrestateTraceInfo {
+			balance: Some(Pallet::<T>::evm_balance(address)),
+			nonce: Some(frame_system::Pallet::<T>::account_nonce(&account_id).saturated_into()),
+			code,
+			storage: Default::default(),
+		}
+	}
+
+	/// Record the state of the account at `address`, if it was not touched before.
+	fn touch_account(&mut self, address: H160) -> &mut PrestateTraceInfo {
+		if !self.pre.contains_key(&address) {
+			let info = self.read_account(&address);
+			self.pre.insert(address, info);
+		}
+		self.pre.get_mut(&address).expect("the account was inserted above; qed")
+	}
+
+	/// Record the value of a storage key, if it was not accessed before.
+	fn touch_storage(&mut self, address: H160, key: &Key, value: Option<&[u8]>) {
+		let disable_storage = self.disable_storage;
+		let info = self.touch_account(address);
+		if disable_storage {
+			return;
+		}
+
+		let unhashed = Bytes(key.unhashed().to_vec());
+		if info.storage.contains_key(&unhashed) {
+			return;
+		}
+		info.storage.insert(unhashed, value.map(|value| Bytes(value.to_vec())));
+		self.storage_keys.entry(address).or_default().push(key.clone());
+	}
+}
+
+impl<T: Config> Tracer for PrestateTracer<T>
+where
+	BalanceOf<T>: Into<U256> + TryFrom<U256> + Bounded,
+	MomentOf<T>: Into<U256>,
+	T::Hash: frame_support::traits::IsType<H256>,
+{
+	fn enter_child_span(
+		&mut self,
+		from: H160,
+		to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas: Weight,
+	) {
+		self.touch_account(from);
+		self.touch_account(to);
+	}
+
+	fn log_event(&mut self, _address: H160, _topics: &[H256], _data: &[u8]) {}
+
+	fn exit_child_span(&mut self, _output: &ExecReturnValue, _gas_used: Weight) {}
+
+	fn exit_child_span_with_error(&mut self, _error: DispatchError, _gas_used: Weight) {}
+
+	fn storage_read(&mut self, address: H160, key: &Key, value: Option<&[u8]>) {
+		self.touch_storage(address, key, value);
+	}
+
+	fn storage_write(
+		&mut self,
+		address: H160,
+		key: &Key,
+		old_value: Option<&[u8]>,
+		_new_value: Option<&[u8]>,
+	) {
+		self.touch_storage(address, key, old_value);
+	}
+
+	fn balance_change(&mut self, address: H160, old_balance: U256, _new_balance: U256) {
+		if !self.pre.contains_key(&address) {
+			// The balance was already changed when the account is first touched here.
+			self.touch_account(address).balance = Some(old_balance);
+		}
+	}
+}
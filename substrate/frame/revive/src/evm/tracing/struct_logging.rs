@@ -0,0 +1,218 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	evm::{Bytes, StructLog, StructLogTrace},
+	exec::Key,
+	primitives::ExecReturnValue,
+	tracing::Tracer,
+	DispatchError, Weight,
+};
+use alloc::{
+	collections::BTreeMap,
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use sp_core::{H160, H256, U256};
+
+/// A call that is currently executed.
+struct Frame {
+	/// The gas left when entering the call.
+	gas: Weight,
+	/// The index of the last instruction logged by this call.
+	last_step: Option<usize>,
+}
+
+/// A Tracer that logs every instruction executed by a transaction.
+pub struct StructLogger<GasMapper> {
+	/// Map Weight to Gas equivalent.
+	gas_mapper: GasMapper,
+	/// Whether to omit the registers from the logs.
+	disable_stack: bool,
+	/// Whether to omit the storage from the logs.
+	disable_storage: bool,
+	/// The logged instructions, with the gas expressed as weight.
+	logs: Vec<StructLog<Weight>>,
+	/// Stack of the active calls.
+	frames: Vec<Frame>,
+	/// The storage accessed so far, by address.
+	storage: BTreeMap<H160, BTreeMap<Bytes, Bytes>>,
+	/// Gas used by the outermost call.
+	gas_used: Weight,
+	/// Whether the outermost call failed.
+	failed: bool,
+	/// Output of the outermost call.
+	return_value: Vec<u8>,
+	/// Whether any call was traced.
+	traced: bool,
+}
+
+impl<Gas, GasMapper: Fn(Weight) -> Gas> StructLogger<GasMapper> {
+	/// Create a new [`StructLogger`] instance.
+	pub fn new(disable_stack: bool, disable_storage: bool, gas_mapper: GasMapper) -> Self {
+		Self {
+			gas_mapper,
+			disable_stack,
+			disable_storage,
+			logs: Vec::new(),
+			frames: Vec::new(),
+			storage: BTreeMap::new(),
+			gas_used: Weight::zero(),
+			failed: false,
+			return_value: Vec::new(),
+			traced: false,
+		}
+	}
+
+	/// Collect the trace and reset the tracer.
+	///
+	/// Returns `None` if no call was traced.
+	pub fn collect_trace(&mut self) -> Option<StructLogTrace<Gas>> {
+		if !core::mem::take(&mut self.traced) {
+			return None;
+		}
+
+		self.frames.clear();
+		self.storage.clear();
+		let struct_logs = core::mem::take(&mut self.logs)
+			.into_iter()
+			.map(|log| StructLog {
+				pc: log.pc,
+				op: log.op,
+				gas: (self.gas_mapper)(log.gas),
+				gas_cost: (self.gas_mapper)(log.gas_cost),
+				depth: log.depth,
+				stack: log.stack,
+				storage: log.storage,
+				error: log.error,
+			})
+			.collect();
+
+		Some(StructLogTrace {
+			gas: (self.gas_mapper)(core::mem::take(&mut self.gas_used)),
+			failed: core::mem::take(&mut self.failed),
+			return_value: core::mem::take(&mut self.return_value).into(),
+			struct_logs,
+		})
+	}
+
+	/// Record the storage accessed by the current call in its last logged instruction.
+	fn record_storage(&mut self, address: H160, key: &Key, value: Option<&[u8]>) {
+		if self.disable_storage {
+			return;
+		}
+
+		let storage = self.storage.entry(address).or_default();
+		storage.insert(Bytes(key.unhashed().to_vec()), Bytes(value.unwrap_or_default().to_vec()));
+
+		if let Some(index) = self.frames.last().and_then(|frame| frame.last_step) {
+			self.logs[index].storage = Some(storage.clone());
+		}
+	}
+
+	/// Pop the current call and set the gas cost of its last logged instruction.
+	fn exit_frame(&mut self, gas_used: Weight) -> Option<usize> {
+		let frame = self.frames.pop()?;
+		let index = frame.last_step?;
+		let gas_left = frame.gas.saturating_sub(gas_used);
+		self.logs[index].gas_cost = self.logs[index].gas.saturating_sub(gas_left);
+		Some(index)
+	}
+}
+
+impl<Gas, GasMapper: Fn(Weight) -> Gas> Tracer for StructLogger<GasMapper> {
+	fn enter_child_span(
+		&mut self,
+		_from: H160,
+		_to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		gas: Weight,
+	) {
+		self.traced = true;
+		self.frames.push(Frame { gas, last_step: None });
+	}
+
+	fn log_event(&mut self, _address: H160, _topics: &[H256], _data: &[u8]) {}
+
+	fn exit_child_span(&mut self, output: &ExecReturnValue, gas_used: Weight) {
+		self.exit_frame(gas_used);
+		if self.frames.is_empty() {
+			self.gas_used = gas_used;
+			self.failed = output.did_revert();
+			self.return_value = output.data.clone();
+		}
+	}
+
+	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_used: Weight) {
+		if let Some(index) = self.exit_frame(gas_used) {
+			self.logs[index].error = Some(match error {
+				DispatchError::Module(sp_runtime::ModuleError { message, .. }) =>
+					message.unwrap_or_default().to_string(),
+				_ => format!("{:?}", error),
+			});
+		}
+		if self.frames.is_empty() {
+			self.gas_used = gas_used;
+			self.failed = true;
+		}
+	}
+
+	fn storage_read(&mut self, address: H160, key: &Key, value: Option<&[u8]>) {
+		self.record_storage(address, key, value);
+	}
+
+	fn storage_write(
+		&mut self,
+		address: H160,
+		key: &Key,
+		_old_value: Option<&[u8]>,
+		new_value: Option<&[u8]>,
+	) {
+		self.record_storage(address, key, new_value);
+	}
+
+	fn wants_instruction_steps(&self) -> bool {
+		true
+	}
+
+	fn instruction_step(&mut self, pc: u32, opcode: &str, gas_left: Weight, registers: &[u64]) {
+		let depth = self.frames.len() as u32;
+		let Some(frame) = self.frames.last_mut() else { return };
+
+		// The cost of the previous instruction is only known once the next one is reached.
+		if let Some(index) = frame.last_step {
+			self.logs[index].gas_cost = self.logs[index].gas.saturating_sub(gas_left);
+		}
+
+		frame.last_step = Some(self.logs.len());
+		self.logs.push(StructLog {
+			pc,
+			op: String::from(opcode),
+			gas: gas_left,
+			gas_cost: Weight::zero(),
+			depth,
+			stack: (!self.disable_stack)
+				.then(|| registers.iter().map(|value| U256::from(*value)).collect()),
+			storage: None,
+			error: None,
+		});
+	}
+}
@@ -16,6 +16,7 @@
 // limitations under the License.
 
 use crate::{
+	access_list,
 	address::{self, AddressMapper},
 	gas::GasMeter,
 	limits,
@@ -26,9 +27,9 @@ use crate::{
 	tracing::if_tracing,
 	transient_storage::TransientStorage,
 	BalanceOf, CodeInfo, CodeInfoOf, Config, ContractInfo, ContractInfoOf, ConversionPrecision,
-	Error, Event, ImmutableData, ImmutableDataOf, Pallet as Contracts,
+	DelegationOf, Error, Event, ImmutableData, ImmutableDataOf, Pallet as Contracts,
 };
-use alloc::vec::Vec;
+use alloc::{collections::BTreeSet, vec::Vec};
 use core::{fmt::Debug, marker::PhantomData, mem};
 use frame_support::{
 	crypto::ecdsa::ECDSAExt,
@@ -73,7 +74,30 @@ const FRAME_ALWAYS_EXISTS_ON_INSTANTIATE: &str = "The return value is only `None
 pub const EMPTY_CODE_HASH: H256 =
 	H256(sp_core::hex2array!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"));
 
+/// The prefix of the code reported for an account delegating to a contract, see
+/// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702).
+pub const DELEGATION_DESIGNATOR_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+/// The code reported for an account delegating to the contract at `target`.
+fn delegation_designator(target: &H160) -> [u8; 23] {
+	let mut designator = [0u8; 23];
+	designator[..3].copy_from_slice(&DELEGATION_DESIGNATOR_PREFIX);
+	designator[3..].copy_from_slice(target.as_bytes());
+	designator
+}
+
+/// Resolve the contract an account without code of its own delegates to.
+///
+/// Returns the address of the contract along with its code hash, or `None` if the account does
+/// not delegate to a contract. Delegations are not followed recursively.
+fn resolve_delegation<T: Config>(address: &H160) -> Option<(H160, H256)> {
+	let target = DelegationOf::<T>::get(address)?;
+	let code_hash = ContractInfoOf::<T>::get(&target)?.code_hash;
+	(code_hash != EMPTY_CODE_HASH).then_some((target, code_hash))
+}
+
 /// Combined key type for both fixed and variable sized storage keys.
+#[derive(Clone)]
 pub enum Key {
 	/// Variant for fixed sized keys.
 	Fix([u8; 32]),
@@ -86,8 +110,8 @@ impl Key {
 	///
 	/// # Note
 	///
-	/// Only used by benchmarking in order to generate storage collisions on purpose.
-	#[cfg(feature = "runtime-benchmarks")]
+	/// Used by tracers to report the accessed keys and by benchmarking in order to generate
+	/// storage collisions on purpose.
 	pub fn unhashed(&self) -> &[u8] {
 		match self {
 			Key::Fix(v) => v.as_ref(),
@@ -236,12 +260,17 @@ pub trait Ext: sealing::Sealed {
 		salt: Option<&[u8; 32]>,
 	) -> Result<H160, ExecError>;
 
-	/// Transfer all funds to `beneficiary` and delete the contract.
+	/// Transfer all funds to `beneficiary` and delete the contract if it was created by the
+	/// current call stack.
+	///
+	/// This follows [EIP-6780](https://eips.ethereum.org/EIPS/eip-6780): A contract that already
+	/// existed before only transfers its balance. The storage deposit of a deleted contract is
+	/// refunded to the `beneficiary` once its storage was lazily removed.
 	///
 	/// Since this function removes the self contract eagerly, if succeeded, no further actions
 	/// should be performed on this `Ext` instance.
 	///
-	/// This function will fail if the same contract is present on the contract
+	/// Deleting the contract will fail if the same contract is present on the contract
 	/// call stack.
 	fn terminate(&mut self, beneficiary: &H160) -> DispatchResult;
 
@@ -517,6 +546,11 @@ pub struct Stack<'a, T: Config, E> {
 	/// Whether or not actual transfer of funds should be performed.
 	/// This is set to `true` exclusively when we simulate a call through eth_transact.
 	skip_transfer: bool,
+	/// The contracts instantiated by this call stack.
+	///
+	/// Only those contracts are deleted when they call `terminate`, see
+	/// [EIP-6780](https://eips.ethereum.org/EIPS/eip-6780).
+	contracts_created: BTreeSet<T::AccountId>,
 	/// No executable is held by the struct but influences its behaviour.
 	_phantom: PhantomData<E>,
 }
@@ -545,6 +579,11 @@ struct Frame<T: Config> {
 	/// The delegate call info of the currently executing frame which was spawned by
 	/// `delegate_call`.
 	delegate: Option<DelegateInfo<T>>,
+	/// The address of the contract whose code is executed, if the account of this frame has no
+	/// code of its own and delegates to that contract instead.
+	///
+	/// See [`resolve_delegation`].
+	code_delegate: Option<H160>,
 	/// The output of the last executed call frame.
 	last_frame_output: ExecReturnValue,
 }
@@ -833,6 +872,7 @@ where
 		skip_transfer: bool,
 	) -> Result<Option<(Self, E)>, ExecError> {
 		origin.ensure_mapped()?;
+		access_list::warm_up(gas_meter)?;
 		let Some((first_frame, executable)) = Self::new_frame(
 			args,
 			value,
@@ -857,6 +897,7 @@ where
 			frames: Default::default(),
 			transient_storage: TransientStorage::new(limits::TRANSIENT_STORAGE_BYTES),
 			skip_transfer,
+			contracts_created: Default::default(),
 			_phantom: Default::default(),
 		};
 
@@ -877,78 +918,99 @@ where
 		read_only: bool,
 		origin_is_caller: bool,
 	) -> Result<Option<(Frame<T>, E)>, ExecError> {
-		let (account_id, contract_info, executable, delegate, entry_point, nested_gas) =
-			match frame_args {
-				FrameArgs::Call { dest, cached_info, delegated_call } => {
-					let contract = if let Some(contract) = cached_info {
-						contract
-					} else {
-						if let Some(contract) =
-							<ContractInfoOf<T>>::get(T::AddressMapper::to_address(&dest))
-						{
-							contract
-						} else {
-							return Ok(None);
-						}
-					};
+		let (
+			account_id,
+			contract_info,
+			executable,
+			delegate,
+			code_delegate,
+			entry_point,
+			nested_gas,
+		) = match frame_args {
+			FrameArgs::Call { dest, cached_info, delegated_call } => {
+				let address = T::AddressMapper::to_address(&dest);
+				let contract = cached_info.or_else(|| <ContractInfoOf<T>>::get(&address));
+
+				// Only accounts without code of their own can delegate to a contract.
+				let delegation = match contract {
+					Some(ref contract) if contract.code_hash != EMPTY_CODE_HASH => None,
+					_ if delegated_call.is_some() => None,
+					_ => resolve_delegation::<T>(&address),
+				};
 
-					let mut nested_gas = gas_meter.nested(gas_limit);
-					let (executable, delegate_caller) = if let Some(DelegatedCall {
-						executable,
-						caller,
-						callee,
-					}) = delegated_call
-					{
+				let contract = match contract {
+					Some(contract)
+						if contract.code_hash != EMPTY_CODE_HASH ||
+							delegated_call.is_some() ||
+							delegation.is_some() =>
+						contract,
+					// The storage of a delegated account is kept in a contract info without
+					// code, created when the account is first called.
+					None if delegation.is_some() => ContractInfo::new(
+						&address,
+						<System<T>>::account_nonce(&dest),
+						EMPTY_CODE_HASH,
+					)?,
+					_ => return Ok(None),
+				};
+
+				let mut nested_gas = gas_meter.nested(gas_limit);
+				let (executable, delegate_caller) =
+					if let Some(DelegatedCall { executable, caller, callee }) = delegated_call {
 						(executable, Some(DelegateInfo { caller, callee }))
 					} else {
-						(E::from_storage(contract.code_hash, &mut nested_gas)?, None)
+						let code_hash = delegation.map_or(contract.code_hash, |(_, hash)| hash);
+						(E::from_storage(code_hash, &mut nested_gas)?, None)
 					};
 
-					(
-						dest,
-						contract,
-						executable,
-						delegate_caller,
-						ExportedFunction::Call,
-						nested_gas,
-					)
-				},
-				FrameArgs::Instantiate { sender, executable, salt, input_data } => {
-					let deployer = T::AddressMapper::to_address(&sender);
-					let account_nonce = <System<T>>::account_nonce(&sender);
-					let address = if let Some(salt) = salt {
-						address::create2(&deployer, executable.code(), input_data, salt)
-					} else {
-						use sp_runtime::Saturating;
-						address::create1(
-							&deployer,
-							// the Nonce from the origin has been incremented pre-dispatch, so we
-							// need to subtract 1 to get the nonce at the time of the call.
-							if origin_is_caller {
-								account_nonce.saturating_sub(1u32.into()).saturated_into()
-							} else {
-								account_nonce.saturated_into()
-							},
-						)
-					};
-					let contract = ContractInfo::new(
-						&address,
-						<System<T>>::account_nonce(&sender),
-						*executable.code_hash(),
-					)?;
-					(
-						T::AddressMapper::to_fallback_account_id(&address),
-						contract,
-						executable,
-						None,
-						ExportedFunction::Constructor,
-						gas_meter.nested(gas_limit),
+				(
+					dest,
+					contract,
+					executable,
+					delegate_caller,
+					delegation.map(|(target, _)| target),
+					ExportedFunction::Call,
+					nested_gas,
+				)
+			},
+			FrameArgs::Instantiate { sender, executable, salt, input_data } => {
+				let deployer = T::AddressMapper::to_address(&sender);
+				let account_nonce = <System<T>>::account_nonce(&sender);
+				let address = if let Some(salt) = salt {
+					address::create2(&deployer, executable.code(), input_data, salt)
+				} else {
+					use sp_runtime::Saturating;
+					address::create1(
+						&deployer,
+						// the Nonce from the origin has been incremented pre-dispatch, so we
+						// need to subtract 1 to get the nonce at the time of the call.
+						if origin_is_caller {
+							account_nonce.saturating_sub(1u32.into()).saturated_into()
+						} else {
+							account_nonce.saturated_into()
+						},
 					)
-				},
-			};
+				};
+				let contract = ContractInfo::new(
+					&address,
+					<System<T>>::account_nonce(&sender),
+					*executable.code_hash(),
+				)?;
+				(
+					T::AddressMapper::to_fallback_account_id(&address),
+					contract,
+					executable,
+					None,
+					None,
+					ExportedFunction::Constructor,
+					gas_meter.nested(gas_limit),
+				)
+			},
+		};
 
 		let frame = Frame {
 			delegate,
+			code_delegate,
 			value_transferred,
 			contract_info: CachedContract::Cached(contract_info),
 			account_id,
@@ -1031,6 +1093,12 @@ where
 			);
 		});
 
+		// Remember the new contract so that `terminate` can delete it within this call stack.
+		if entry_point == ExportedFunction::Constructor {
+			let account_id = frame.account_id.clone();
+			self.contracts_created.insert(account_id);
+		}
+
 		// The output of the caller frame will be replaced by the output of this run.
 		// It is also not accessible from nested frames.
 		// Hence we drop it early to save the memory.
@@ -1104,9 +1172,23 @@ where
 			let frame = self.top_frame_mut();
 
 			// The deposit we charge for a contract depends on the size of the immutable data.
-			// Hence we need to delay charging the base deposit after execution.
-			if entry_point == ExportedFunction::Constructor {
-				let deposit = frame.contract_info().update_base_deposit(code_deposit);
+			// Hence we need to delay charging the base deposit after execution. A contract
+			// which terminated in its constructor is already gone and owes no deposit.
+			if let (ExportedFunction::Constructor, Some(contract)) =
+				(entry_point, frame.contract_info.as_contract())
+			{
+				let deposit = contract.update_base_deposit(code_deposit);
+				frame
+					.nested_storage
+					.charge_deposit(frame.account_id.clone(), StorageDeposit::Charge(deposit));
+			}
+
+			// A delegated account pays for the contract info holding its storage when it is
+			// first called.
+			if frame.code_delegate.is_some() &&
+				frame.contract_info().storage_base_deposit().is_zero()
+			{
+				let deposit = frame.contract_info().update_base_deposit(Zero::zero());
 				frame
 					.nested_storage
 					.charge_deposit(frame.account_id.clone(), StorageDeposit::Charge(deposit));
@@ -1272,6 +1354,48 @@ where
 			return Ok(Default::default());
 		}
 
+		// Record the balances of all accounts which might be affected by the transfer, so that
+		// the tracer can be notified about their changes.
+		let balances_before = if_tracing(|_| {
+			let mut accounts = alloc::vec![from, to];
+			if let Ok(origin) = origin.account_id() {
+				if !accounts.contains(&origin) {
+					accounts.push(origin);
+				}
+			}
+			accounts
+				.into_iter()
+				.map(|who| (who, Self::account_balance(who)))
+				.collect::<Vec<_>>()
+		});
+
+		let result = Self::transfer_native(origin, from, to, value);
+
+		if let (Some(balances_before), Ok(_)) = (balances_before, &result) {
+			if_tracing(|tracer| {
+				for (who, old_balance) in balances_before {
+					let new_balance = Self::account_balance(who);
+					if new_balance != old_balance {
+						tracer.balance_change(
+							T::AddressMapper::to_address(who),
+							old_balance,
+							new_balance,
+						);
+					}
+				}
+			});
+		}
+
+		result
+	}
+
+	/// Same as `transfer` but `value` is already converted to the native balance.
+	fn transfer_native(
+		origin: &Origin<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		value: BalanceOf<T>,
+	) -> ExecResult {
 		if <System<T>>::account_exists(to) {
 			return T::Currency::transfer(from, to, value, Preservation::Preserve)
 				.map(|_| Default::default())
@@ -1341,7 +1465,7 @@ where
 	}
 
 	/// Returns the *free* balance of the supplied AccountId.
-	fn account_balance(&self, who: &T::AccountId) -> U256 {
+	fn account_balance(who: &T::AccountId) -> U256 {
 		crate::Pallet::<T>::convert_native_to_evm(T::Currency::reducible_balance(
 			who,
 			Preservation::Preserve,
@@ -1356,6 +1480,14 @@ where
 		self.top_frame_mut().entry_point = export;
 	}
 
+	/// The deletion of a contract is more expensive than sending away its balance. Thus, we
+	/// allow treating the current contract as created by this call stack.
+	#[cfg(feature = "runtime-benchmarks")]
+	pub(crate) fn mark_as_created(&mut self) {
+		let account_id = self.top_frame().account_id.clone();
+		self.contracts_created.insert(account_id);
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	pub(crate) fn set_block_number(&mut self, block_number: BlockNumberFor<T>) {
 		self.block_number = block_number;
@@ -1576,8 +1708,14 @@ where
 		*self.last_frame_output_mut() = Default::default();
 
 		// Delegate-calls to non-contract accounts are considered success.
-		let Some(info) = ContractInfoOf::<T>::get(&address) else { return Ok(()) };
-		let executable = E::from_storage(info.code_hash, self.gas_meter_mut())?;
+		let code_hash = match ContractInfoOf::<T>::get(&address) {
+			Some(info) if info.code_hash != EMPTY_CODE_HASH => info.code_hash,
+			_ => match resolve_delegation::<T>(&address) {
+				Some((_, code_hash)) => code_hash,
+				None => return Ok(()),
+			},
+		};
+		let executable = E::from_storage(code_hash, self.gas_meter_mut())?;
 		let top_frame = self.top_frame_mut();
 		let contract_info = top_frame.contract_info().clone();
 		let account_id = top_frame.account_id.clone();
@@ -1633,18 +1771,35 @@ where
 	}
 
 	fn terminate(&mut self, beneficiary: &H160) -> DispatchResult {
+		let beneficiary_account = T::AddressMapper::to_account_id(beneficiary);
+
+		// Only a contract created by this call stack is deleted. Any other contract just sends
+		// its balance to the beneficiary and keeps existing.
+		if !self.contracts_created.contains(self.account_id()) {
+			let account_id = self.account_id().clone();
+			if account_id != beneficiary_account {
+				Self::transfer(
+					&self.origin,
+					&account_id,
+					&beneficiary_account,
+					Self::account_balance(&account_id),
+				)
+				.map_err(|e| e.error)?;
+			}
+			return Ok(());
+		}
+
 		if self.is_recursive() {
 			return Err(Error::<T>::TerminatedWhileReentrant.into());
 		}
 		let frame = self.top_frame_mut();
-		if frame.entry_point == ExportedFunction::Constructor {
-			return Err(Error::<T>::TerminatedInConstructor.into());
+		if frame.code_delegate.is_some() {
+			return Err(Error::<T>::TerminatedDelegatedAccount.into());
 		}
 		let info = frame.terminate();
-		let beneficiary_account = T::AddressMapper::to_account_id(beneficiary);
-		frame.nested_storage.terminate(&info, beneficiary_account);
+		frame.nested_storage.terminate(beneficiary_account.clone());
 
-		info.queue_trie_for_deletion();
+		info.queue_trie_for_deletion(&frame.account_id, &beneficiary_account);
 		let account_address = T::AddressMapper::to_address(&frame.account_id);
 		ContractInfoOf::<T>::remove(&account_address);
 		ImmutableDataOf::<T>::remove(&account_address);
@@ -1654,7 +1809,15 @@ where
 	}
 
 	fn get_storage(&mut self, key: &Key) -> Option<Vec<u8>> {
-		self.top_frame_mut().contract_info().read(key)
+		let value = self.top_frame_mut().contract_info().read(key);
+		if_tracing(|tracer| {
+			tracer.storage_read(
+				T::AddressMapper::to_address(self.account_id()),
+				key,
+				value.as_deref(),
+			);
+		});
+		value
 	}
 
 	fn get_storage_size(&mut self, key: &Key) -> Option<u32> {
@@ -1668,6 +1831,15 @@ where
 		take_old: bool,
 	) -> Result<WriteOutcome, DispatchError> {
 		let frame = self.top_frame_mut();
+		if_tracing(|tracer| {
+			let old_value = frame.contract_info().read(key);
+			tracer.storage_write(
+				T::AddressMapper::to_address(&frame.account_id),
+				key,
+				old_value.as_deref(),
+				value.as_deref(),
+			);
+		});
 		frame.contract_info.get(&frame.account_id).write(
 			key.into(),
 			value,
@@ -1716,7 +1888,10 @@ where
 	}
 
 	fn is_contract(&self, address: &H160) -> bool {
-		ContractInfoOf::<T>::contains_key(&address)
+		match ContractInfoOf::<T>::get(&address) {
+			Some(info) if info.code_hash != EMPTY_CODE_HASH => true,
+			_ => DelegationOf::<T>::contains_key(&address),
+		}
 	}
 
 	fn to_account_id(&self, address: &H160) -> T::AccountId {
@@ -1724,6 +1899,9 @@ where
 	}
 
 	fn code_hash(&self, address: &H160) -> H256 {
+		if let Some(target) = DelegationOf::<T>::get(&address) {
+			return H256(sp_io::hashing::keccak_256(&delegation_designator(&target)));
+		}
 		<ContractInfoOf<T>>::get(&address)
 			.map(|contract| contract.code_hash)
 			.unwrap_or_else(|| {
@@ -1735,6 +1913,9 @@ where
 	}
 
 	fn code_size(&self, address: &H160) -> u64 {
+		if let Some(target) = DelegationOf::<T>::get(&address) {
+			return delegation_designator(&target).len() as u64;
+		}
 		<ContractInfoOf<T>>::get(&address)
 			.and_then(|contract| CodeInfoOf::<T>::get(contract.code_hash))
 			.map(|info| info.code_len())
@@ -1764,11 +1945,12 @@ where
 		}
 
 		// Immutable is read from contract code being executed
-		let address = self
-			.top_frame()
+		let frame = self.top_frame();
+		let address = frame
 			.delegate
 			.as_ref()
 			.map(|d| d.callee)
+			.or(frame.code_delegate)
 			.unwrap_or(T::AddressMapper::to_address(self.account_id()));
 		Ok(<ImmutableDataOf<T>>::get(address).ok_or_else(|| Error::<T>::InvalidImmutableAccess)?)
 	}
@@ -1784,11 +1966,11 @@ where
 	}
 
 	fn balance(&self) -> U256 {
-		self.account_balance(&self.top_frame().account_id)
+		Self::account_balance(&self.top_frame().account_id)
 	}
 
 	fn balance_of(&self, address: &H160) -> U256 {
-		self.account_balance(&<Self::T as Config>::AddressMapper::to_account_id(address))
+		Self::account_balance(&<Self::T as Config>::AddressMapper::to_account_id(address))
 	}
 
 	fn value_transferred(&self) -> U256 {
@@ -1285,7 +1285,7 @@ fn instantiation_traps() {
 }
 
 #[test]
-fn termination_from_instantiate_fails() {
+fn termination_from_instantiate_works() {
 	let terminate_ch = MockLoader::insert(Constructor, |ctx, _| {
 		ctx.ext.terminate(&ALICE_ADDR)?;
 		exec_success()
@@ -1303,24 +1303,60 @@ fn termination_from_instantiate_fails() {
 			let mut storage_meter =
 				storage::meter::Meter::new(&origin, deposit_limit::<Test>(), 100).unwrap();
 
-			assert_eq!(
-				MockStack::run_instantiate(
-					ALICE,
-					executable,
-					&mut gas_meter,
-					&mut storage_meter,
-					100u64.into(),
-					vec![],
-					Some(&[0; 32]),
-					false,
-				),
-				Err(ExecError {
-					error: Error::<Test>::TerminatedInConstructor.into(),
-					origin: ErrorOrigin::Callee
-				})
-			);
+			let (address, _) = MockStack::run_instantiate(
+				ALICE,
+				executable,
+				&mut gas_meter,
+				&mut storage_meter,
+				100u64.into(),
+				vec![],
+				Some(&[0; 32]),
+				false,
+			)
+			.unwrap();
+
+			// The contract was created by this call stack and is hence deleted.
+			assert!(!ContractInfoOf::<Test>::contains_key(&address));
+			storage_meter.try_into_deposit(&origin, false).unwrap();
+
+			// The value and the existential deposit end up with the beneficiary.
+			assert_eq!(get_balance(&ALICE), 10_000);
+			assert_eq!(get_balance(&<Test as Config>::AddressMapper::to_account_id(&address)), 0);
+		});
+}
+
+#[test]
+fn termination_of_existing_contract_only_transfers_balance() {
+	let code_bob = MockLoader::insert(Call, |ctx, _| {
+		ctx.ext.terminate(&CHARLIE_ADDR)?;
+		exec_success()
+	});
+
+	ExtBuilder::default()
+		.with_code_hashes(MockLoader::code_hashes())
+		.existential_deposit(15)
+		.build()
+		.execute_with(|| {
+			set_balance(&ALICE, 1000);
+			set_balance(&CHARLIE, 15);
+			place_contract(&BOB, code_bob);
+			let origin = Origin::from_account_id(ALICE);
+			let mut storage_meter = storage::meter::Meter::new(&origin, 200, 0).unwrap();
+
+			assert_ok!(MockStack::run_call(
+				origin,
+				BOB_ADDR,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut storage_meter,
+				U256::zero(),
+				vec![],
+				false,
+			));
 
-			assert_eq!(&events(), &[]);
+			// BOB existed before the call stack and is hence kept alive.
+			assert!(ContractInfoOf::<Test>::contains_key(&BOB_ADDR));
+			assert_eq!(get_balance(&BOB), 15);
+			assert_eq!(get_balance(&CHARLIE), 150);
 		});
 }
 
@@ -280,6 +280,20 @@ impl<T: Config> GasMeter<T> {
 		self.gas_left
 	}
 
+	/// Returns how much gas is left if the executor has `engine_fuel` left.
+	///
+	/// In contrast to [`Self::sync_from_executor`] this does not update the meter. It is used
+	/// by tracing to report the gas left while the executor is running.
+	pub fn gas_left_with_engine_fuel(&self, engine_fuel: polkavm::Gas) -> Weight {
+		let engine_fuel = u64::try_from(engine_fuel).unwrap_or_default();
+		let consumed = self
+			.engine_meter
+			.fuel
+			.saturating_sub(engine_fuel)
+			.saturating_mul(EngineMeter::<T>::ref_time_per_fuel());
+		self.gas_left.saturating_sub(Weight::from_parts(consumed, 0))
+	}
+
 	/// The amount of gas in terms of engine gas.
 	pub fn engine_fuel_left(&self) -> Result<polkavm::Gas, DispatchError> {
 		self.engine_meter.fuel.try_into().map_err(|_| <Error<T>>::OutOfGas.into())
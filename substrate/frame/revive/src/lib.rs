@@ -21,6 +21,7 @@
 #![cfg_attr(feature = "runtime-benchmarks", recursion_limit = "1024")]
 
 extern crate alloc;
+mod access_list;
 mod address;
 mod benchmarking;
 mod exec;
@@ -42,10 +43,15 @@ pub mod tracing;
 pub mod weights;
 
 use crate::{
-	evm::{runtime::GAS_PRICE, CallTrace, GasEncoder, GenericTransaction, TracerConfig},
-	exec::{AccountIdOf, ExecError, Executable, Key, Stack as ExecStack},
+	evm::{
+		runtime::GAS_PRICE, AccessList, AccessListResult, AccessListTracer, AuthorizationList,
+		AuthorizationListEntry, CallTrace, CallTracer, GasEncoder, GasSchedule, GasScheduleResult,
+		GenericTransaction, LegacyGenericTransaction, PrestateTracer, ScheduledOperation,
+		StructLogger, Trace, Tracer, TracerConfig, TypeEip2930, TypeLegacy,
+	},
+	exec::{AccountIdOf, ExecError, Executable, Stack as ExecStack, EMPTY_CODE_HASH},
 	gas::GasMeter,
-	storage::{meter::Meter as StorageMeter, ContractInfo, DeletionQueueManager},
+	storage::{meter::Meter as StorageMeter, ContractInfo, DeletionDeposit, DeletionQueueManager},
 	wasm::{CodeInfo, RuntimeCosts, WasmBlob},
 };
 use alloc::{boxed::Box, format, vec};
@@ -60,7 +66,7 @@ use frame_support::{
 	pallet_prelude::DispatchClass,
 	traits::{
 		fungible::{Inspect, Mutate, MutateHold},
-		tokens::{Fortitude::Polite, Preservation::Preserve},
+		tokens::{Fortitude::Polite, Precision, Preservation::Preserve},
 		ConstU32, ConstU64, Contains, EnsureOrigin, Get, IsType, OriginTrait, Time,
 	},
 	weights::{Weight, WeightMeter},
@@ -75,12 +81,12 @@ use scale_info::TypeInfo;
 use sp_core::{H160, H256, U256};
 use sp_runtime::{
 	traits::{BadOrigin, Bounded, Convert, Dispatchable, Saturating, Zero},
-	AccountId32, DispatchError,
+	AccountId32, DispatchError, SaturatedConversion,
 };
 
 pub use crate::{
 	address::{create1, create2, AccountId32Mapper, AddressMapper},
-	exec::{MomentOf, Origin},
+	exec::{Key, MomentOf, Origin},
 	pallet::*,
 };
 pub use primitives::*;
@@ -290,6 +296,12 @@ pub mod pallet {
 		/// Only valid value is `()`. See [`GasEncoder`].
 		#[pallet::no_default_bounds]
 		type EthGasEncoder: GasEncoder<BalanceOf<Self>>;
+
+		/// The gas schedule used to estimate common Ethereum operations.
+		///
+		/// `None` estimates every transaction from its dry run. See [`GasSchedule`].
+		#[pallet::no_default_bounds]
+		type EthGasSchedule: Get<Option<GasSchedule<BalanceOf<Self>>>>;
 	}
 
 	/// Container for different types that implement [`DefaultConfig`]` of this pallet.
@@ -363,6 +375,7 @@ pub mod pallet {
 			type ChainId = ConstU64<0>;
 			type NativeToEthRatio = ConstU32<1>;
 			type EthGasEncoder = ();
+			type EthGasSchedule = ();
 			type FindAuthor = ();
 		}
 	}
@@ -410,7 +423,7 @@ pub mod pallet {
 		ContractTrapped,
 		/// The size defined in `T::MaxValueSize` was exceeded.
 		ValueTooLarge,
-		/// Termination of a contract is not allowed while the contract is already
+		/// Deletion of a contract is not allowed while the contract is already
 		/// on the call stack. Can be triggered by `seal_terminate`.
 		TerminatedWhileReentrant,
 		/// `seal_call` forwarded this contracts input. It therefore is no longer available.
@@ -425,9 +438,8 @@ pub mod pallet {
 		XCMDecodeFailed,
 		/// A contract with the same AccountId already exists.
 		DuplicateContract,
-		/// A contract self destructed in its constructor.
-		///
-		/// This can be triggered by a call to `seal_terminate`.
+		/// DEPRECATED: A contract can terminate in its constructor, following EIP-6780. Kept so
+		/// that the following errors keep their index.
 		TerminatedInConstructor,
 		/// A call tried to invoke a contract that is flagged as non-reentrant.
 		ReentranceDenied,
@@ -497,6 +509,10 @@ pub mod pallet {
 		UnsupportedPrecompileAddress,
 		/// Precompile Error
 		PrecompileFailure,
+		/// An account delegating to a contract tried to terminate itself.
+		TerminatedDelegatedAccount,
+		/// The access list has more than [`limits::ACCESS_LIST_ITEMS`] accounts and storage keys.
+		AccessListTooLarge,
 	}
 
 	/// A reason for the pallet contracts placing a hold on funds.
@@ -508,6 +524,8 @@ pub mod pallet {
 		StorageDepositReserve,
 		/// Deposit for creating an address mapping in [`OriginalAccount`].
 		AddressMapping,
+		/// Deposit for delegating the code of an account in [`DelegationOf`].
+		Delegation,
 	}
 
 	/// A mapping from a contract's code hash to its code.
@@ -522,6 +540,13 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type ContractInfoOf<T: Config> = StorageMap<_, Identity, H160, ContractInfo<T>>;
 
+	/// The contracts that accounts without code of their own delegate to.
+	///
+	/// Calling such an account executes the code of the contract it delegates to, see
+	/// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702).
+	#[pallet::storage]
+	pub(crate) type DelegationOf<T: Config> = StorageMap<_, Identity, H160, H160>;
+
 	/// The immutable data associated with a given account.
 	#[pallet::storage]
 	pub(crate) type ImmutableDataOf<T: Config> = StorageMap<_, Identity, H160, ImmutableData>;
@@ -533,6 +558,13 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type DeletionQueue<T: Config> = StorageMap<_, Twox64Concat, u32, TrieId>;
 
+	/// The storage deposits of the tries in the [`DeletionQueue`], keyed by the same index.
+	///
+	/// They are refunded to the beneficiary of the termination once the trie is removed.
+	#[pallet::storage]
+	pub(crate) type DeletionQueueDeposit<T: Config> =
+		StorageMap<_, Twox64Concat, u32, DeletionDeposit<T>>;
+
 	/// A pair of monotonic counters used to track the latest contract marked for deletion
 	/// and the latest deleted contract in queue.
 	#[pallet::storage]
@@ -964,6 +996,106 @@ pub mod pallet {
 				T::AddressMapper::to_fallback_account_id(&T::AddressMapper::to_address(&origin));
 			call.dispatch(RawOrigin::Signed(unmapped_account).into())
 		}
+
+		/// Same as [`Self::call`], but with the accounts and storage keys of `access_list` being
+		/// warm during the execution.
+		///
+		/// The full access costs of the access list are charged up front. Accessing its accounts
+		/// and storage keys afterwards does not charge their proof size again. The access list
+		/// can hold up to [`limits::ACCESS_LIST_ITEMS`] accounts and storage keys.
+		///
+		/// An Ethereum transaction calling a contract with an access list is converted to this
+		/// call, see [`Self::eth_transact`].
+		#[pallet::call_index(10)]
+		#[pallet::weight(
+			T::WeightInfo::call()
+				.saturating_add(T::WeightInfo::access_list(access_list::len(access_list)))
+				.saturating_add(*gas_limit)
+		)]
+		pub fn eth_call(
+			origin: OriginFor<T>,
+			dest: H160,
+			#[pallet::compact] value: BalanceOf<T>,
+			gas_limit: Weight,
+			#[pallet::compact] storage_deposit_limit: BalanceOf<T>,
+			data: Vec<u8>,
+			access_list: AccessList,
+		) -> DispatchResultWithPostInfo {
+			ensure!(
+				access_list::len(&access_list) <= limits::ACCESS_LIST_ITEMS,
+				Error::<T>::AccessListTooLarge
+			);
+			access_list::using(&access_list, || {
+				Self::call(origin, dest, value, gas_limit, storage_deposit_limit, data)
+			})
+		}
+
+		/// Same as [`Self::instantiate_with_code`], but with the accounts and storage keys of
+		/// `access_list` being warm during the execution.
+		///
+		/// See [`Self::eth_call`].
+		#[pallet::call_index(11)]
+		#[pallet::weight(
+			T::WeightInfo::instantiate_with_code(code.len() as u32, data.len() as u32)
+			.saturating_add(T::WeightInfo::access_list(access_list::len(access_list)))
+			.saturating_add(*gas_limit)
+		)]
+		pub fn eth_instantiate_with_code(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T>,
+			gas_limit: Weight,
+			#[pallet::compact] storage_deposit_limit: BalanceOf<T>,
+			code: Vec<u8>,
+			data: Vec<u8>,
+			access_list: AccessList,
+		) -> DispatchResultWithPostInfo {
+			ensure!(
+				access_list::len(&access_list) <= limits::ACCESS_LIST_ITEMS,
+				Error::<T>::AccessListTooLarge
+			);
+			access_list::using(&access_list, || {
+				Self::instantiate_with_code(
+					origin,
+					value,
+					gas_limit,
+					storage_deposit_limit,
+					code,
+					data,
+					None,
+				)
+			})
+		}
+
+		/// Same as [`Self::eth_call`], for a transaction with an `authorization_list`.
+		///
+		/// Each valid authorization makes the authorizing account delegate its code to a
+		/// contract, see [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702). Invalid
+		/// authorizations are skipped.
+		///
+		/// The authorizations persist even if the call reverts. They are therefore not applied
+		/// by this call but before it is dispatched, by the
+		/// [`ApplyAuthorizations`](evm::runtime::ApplyAuthorizations) transaction extension.
+		///
+		/// An Ethereum transaction with an authorization list is converted to this call, see
+		/// [`Self::eth_transact`].
+		#[pallet::call_index(12)]
+		#[pallet::weight(
+			T::WeightInfo::call()
+				.saturating_add(T::WeightInfo::access_list(access_list::len(access_list)))
+				.saturating_add(*gas_limit)
+		)]
+		pub fn eth_call_with_authorizations(
+			origin: OriginFor<T>,
+			dest: H160,
+			#[pallet::compact] value: BalanceOf<T>,
+			gas_limit: Weight,
+			#[pallet::compact] storage_deposit_limit: BalanceOf<T>,
+			data: Vec<u8>,
+			access_list: AccessList,
+			_authorization_list: AuthorizationList,
+		) -> DispatchResultWithPostInfo {
+			Self::eth_call(origin, dest, value, gas_limit, storage_deposit_limit, data, access_list)
+		}
 	}
 }
 
@@ -1162,6 +1294,19 @@ where
 		};
 
 		let input = tx.input.clone().to_vec();
+		let access_list = tx.access_list.clone().unwrap_or_default();
+		let authorization_list = tx.authorization_list.clone();
+		if !authorization_list.is_empty() {
+			if tx.to.is_none() {
+				return Err(EthTransactError::Message(
+					"Transactions with an authorization list can not create contracts".into(),
+				));
+			}
+			// The nonce of the sender is incremented by the transaction extensions before the
+			// authorizations are applied, so that the sender can sign an authorization itself.
+			<System<T>>::inc_account_nonce(&origin);
+			Self::apply_authorizations(&authorization_list);
+		}
 
 		let extract_error = |err| {
 			if err == Error::<T>::TransferFailed.into() ||
@@ -1181,18 +1326,20 @@ where
 		};
 
 		// Dry run the call
-		let (mut result, dispatch_info) = match tx.to {
+		let (mut result, dispatch_info, limits) = match tx.to {
 			// A contract call.
 			Some(dest) => {
 				// Dry run the call.
-				let result = crate::Pallet::<T>::bare_call(
-					T::RuntimeOrigin::signed(origin),
-					dest,
-					native_value,
-					gas_limit,
-					storage_deposit_limit,
-					input.clone(),
-				);
+				let result = access_list::using(&access_list, || {
+					crate::Pallet::<T>::bare_call(
+						T::RuntimeOrigin::signed(origin),
+						dest,
+						native_value,
+						gas_limit,
+						storage_deposit_limit,
+						input.clone(),
+					)
+				});
 
 				let data = match result.result {
 					Ok(return_value) => {
@@ -1214,19 +1361,46 @@ where
 					eth_gas: Default::default(),
 				};
 
-				let (gas_limit, storage_deposit_limit) = T::EthGasEncoder::as_encoded_values(
-					result.gas_required,
-					result.storage_deposit,
-				);
-				let dispatch_call: <T as Config>::RuntimeCall = crate::Call::<T>::call {
-					dest,
-					value: native_value,
-					gas_limit,
-					storage_deposit_limit,
-					data: input.clone(),
+				let operation = if ContractInfoOf::<T>::contains_key(&dest) ||
+					DelegationOf::<T>::contains_key(&dest)
+				{
+					ScheduledOperation::Call
+				} else {
+					ScheduledOperation::Transfer
+				};
+				let limits = Self::eth_transact_limits(operation, input.len(), 0, &result);
+				let (gas_limit, storage_deposit_limit) =
+					T::EthGasEncoder::as_encoded_values(limits.1, limits.2);
+				let dispatch_call: <T as Config>::RuntimeCall = if !authorization_list.is_empty() {
+					crate::Call::<T>::eth_call_with_authorizations {
+						dest,
+						value: native_value,
+						gas_limit,
+						storage_deposit_limit,
+						data: input.clone(),
+						access_list: access_list.clone(),
+						authorization_list: authorization_list.clone(),
+					}
+				} else if access_list.is_empty() {
+					crate::Call::<T>::call {
+						dest,
+						value: native_value,
+						gas_limit,
+						storage_deposit_limit,
+						data: input.clone(),
+					}
+				} else {
+					crate::Call::<T>::eth_call {
+						dest,
+						value: native_value,
+						gas_limit,
+						storage_deposit_limit,
+						data: input.clone(),
+						access_list: access_list.clone(),
+					}
 				}
 				.into();
-				(result, dispatch_call.get_dispatch_info())
+				(result, dispatch_call.get_dispatch_info(), limits)
 			},
 			// A contract deployment
 			None => {
@@ -1244,15 +1418,17 @@ where
 				};
 
 				// Dry run the call.
-				let result = crate::Pallet::<T>::bare_instantiate(
-					T::RuntimeOrigin::signed(origin),
-					native_value,
-					gas_limit,
-					storage_deposit_limit,
-					Code::Upload(code.to_vec()),
-					data.to_vec(),
-					None,
-				);
+				let result = access_list::using(&access_list, || {
+					crate::Pallet::<T>::bare_instantiate(
+						T::RuntimeOrigin::signed(origin),
+						native_value,
+						gas_limit,
+						storage_deposit_limit,
+						Code::Upload(code.to_vec()),
+						data.to_vec(),
+						None,
+					)
+				});
 
 				let returned_data = match result.result {
 					Ok(return_value) => {
@@ -1275,11 +1451,15 @@ where
 				};
 
 				// Get the dispatch info of the call.
-				let (gas_limit, storage_deposit_limit) = T::EthGasEncoder::as_encoded_values(
-					result.gas_required,
-					result.storage_deposit,
+				let limits = Self::eth_transact_limits(
+					ScheduledOperation::Create,
+					data.len(),
+					code.len(),
+					&result,
 				);
-				let dispatch_call: <T as Config>::RuntimeCall =
+				let (gas_limit, storage_deposit_limit) =
+					T::EthGasEncoder::as_encoded_values(limits.1, limits.2);
+				let dispatch_call: <T as Config>::RuntimeCall = if access_list.is_empty() {
 					crate::Call::<T>::instantiate_with_code {
 						value: native_value,
 						gas_limit,
@@ -1288,8 +1468,18 @@ where
 						data: data.to_vec(),
 						salt: None,
 					}
-					.into();
-				(result, dispatch_call.get_dispatch_info())
+				} else {
+					crate::Call::<T>::eth_instantiate_with_code {
+						value: native_value,
+						gas_limit,
+						storage_deposit_limit,
+						code: code.to_vec(),
+						data: data.to_vec(),
+						access_list: access_list.clone(),
+					}
+				}
+				.into();
+				(result, dispatch_call.get_dispatch_info(), limits)
 			},
 		};
 
@@ -1301,14 +1491,127 @@ where
 			crate::Call::<T>::eth_transact { payload: unsigned_tx.dummy_signed_payload() };
 		let fee = tx_fee(eth_dispatch_call, dispatch_info);
 		let raw_gas = Self::evm_fee_to_gas(fee);
+		let (scheduled_gas, weight_limit, deposit_limit) = limits;
+		if raw_gas > scheduled_gas && !scheduled_gas.is_zero() {
+			log::debug!(
+				target: LOG_TARGET,
+				"bare_eth_call: fee gas {raw_gas:?} exceeds scheduled gas {scheduled_gas:?}"
+			);
+		}
 		let eth_gas =
-			T::EthGasEncoder::encode(raw_gas, result.gas_required, result.storage_deposit);
+			T::EthGasEncoder::encode(raw_gas.max(scheduled_gas), weight_limit, deposit_limit);
 
 		log::trace!(target: LOG_TARGET, "bare_eth_call: raw_gas: {raw_gas:?} eth_gas: {eth_gas:?}");
 		result.eth_gas = eth_gas;
 		Ok(result)
 	}
 
+	/// Returns the gas, weight and deposit limits to estimate a dry run with.
+	///
+	/// These are the limits of [`Config::EthGasSchedule`] if the dry run fits within them, and
+	/// the resources used by the dry run otherwise, in which case the returned gas is zero.
+	fn eth_transact_limits(
+		operation: ScheduledOperation,
+		input_len: usize,
+		code_len: usize,
+		result: &EthTransactInfo<BalanceOf<T>>,
+	) -> (U256, Weight, BalanceOf<T>) {
+		let scheduled = T::EthGasSchedule::get().and_then(|schedule| {
+			let cost =
+				schedule.cost_within(operation, result.gas_required, &result.storage_deposit)?;
+			let gas =
+				schedule.gas(operation, input_len.saturated_into(), code_len.saturated_into());
+			Some((gas, cost.weight_limit, cost.deposit_limit))
+		});
+		scheduled.unwrap_or((U256::zero(), result.gas_required, result.storage_deposit))
+	}
+
+	/// Returns the gas schedule of [`Config::EthGasSchedule`], encoded as EVM gas.
+	pub fn evm_gas_schedule() -> Option<GasScheduleResult> {
+		let schedule = T::EthGasSchedule::get()?;
+		let encode = |operation| {
+			let cost = schedule.cost(operation);
+			T::EthGasEncoder::encode(
+				schedule.gas(operation, 0, 0),
+				cost.weight_limit,
+				cost.deposit_limit,
+			)
+		};
+		Some(GasScheduleResult {
+			version: schedule.version,
+			transfer: encode(ScheduledOperation::Transfer),
+			call: encode(ScheduledOperation::Call),
+			create: encode(ScheduledOperation::Create),
+			input_byte: schedule.input_byte.into(),
+			code_byte: schedule.code_byte.into(),
+		})
+	}
+
+	/// Apply the authorizations of an EIP-7702 transaction.
+	///
+	/// An authorization is valid if it is signed for this chain (or for any chain) by an account
+	/// without code of its own, with the current nonce of that account. Each valid authorization
+	/// sets the contract the account delegates to, or clears it if the authorized address is
+	/// zero, and increments the nonce of the account. Clearing a delegation also removes the
+	/// storage the account kept while delegating. Invalid authorizations are skipped.
+	///
+	/// A new delegation holds a deposit from the authorizing account, which is released when the
+	/// delegation is cleared. Authorizations of accounts that can't pay for it are skipped.
+	pub(crate) fn apply_authorizations(authorization_list: &[AuthorizationListEntry]) {
+		let chain_id = U256::from(T::ChainId::get());
+		// each delegation entry stores the address of the account and of its target (20 bytes each)
+		let deposit = T::DepositPerByte::get()
+			.saturating_mul(40u32.into())
+			.saturating_add(T::DepositPerItem::get());
+		for authorization in authorization_list {
+			if !authorization.chain_id.is_zero() && authorization.chain_id != chain_id {
+				continue;
+			}
+			let Ok(authority) = authorization.recover_authority() else { continue };
+			if ContractInfoOf::<T>::get(&authority)
+				.is_some_and(|info| info.code_hash != EMPTY_CODE_HASH)
+			{
+				continue;
+			}
+			let account_id = T::AddressMapper::to_account_id(&authority);
+			let nonce: u64 = <System<T>>::account_nonce(&account_id).saturated_into();
+			if authorization.nonce != U256::from(nonce) || nonce == u64::MAX {
+				continue;
+			}
+
+			if authorization.address.is_zero() {
+				if DelegationOf::<T>::take(&authority).is_some() {
+					let _ = T::Currency::release_all(
+						&HoldReason::Delegation.into(),
+						&account_id,
+						Precision::BestEffort,
+					);
+				}
+				// The account has no code of its own, so its contract info only holds the storage
+				// of the delegation. Its deposit is refunded to the account.
+				if let Some(info) = ContractInfoOf::<T>::take(&authority) {
+					info.queue_trie_for_deletion(&account_id, &account_id);
+				}
+			} else {
+				if !DelegationOf::<T>::contains_key(&authority) &&
+					T::Currency::hold(&HoldReason::Delegation.into(), &account_id, deposit)
+						.is_err()
+				{
+					continue;
+				}
+				DelegationOf::<T>::insert(&authority, authorization.address);
+			}
+			<System<T>>::inc_account_nonce(&account_id);
+		}
+	}
+
+	/// The weight of applying `n` authorizations, see [`Self::apply_authorizations`].
+	pub(crate) fn authorizations_weight(n: u32) -> Weight {
+		T::WeightInfo::ecdsa_recover()
+			.saturating_add(T::DbWeight::get().reads_writes(5, 8))
+			.saturating_mul(n.into())
+	}
+
 	/// Get the balance with EVM decimals of the given `address`.
 	pub fn evm_balance(address: &H160) -> U256 {
 		let account = T::AddressMapper::to_account_id(&address);
@@ -1340,6 +1643,52 @@ where
 		Self::evm_fee_to_gas(fee)
 	}
 
+	/// Build the tracer described by the given `config`.
+	///
+	/// The gas reported by the tracer is converted with [`Self::evm_gas_from_weight`].
+	pub fn evm_tracer(config: TracerConfig) -> Tracer<T> {
+		match config {
+			TracerConfig::CallTracer { with_logs } =>
+				Tracer::CallTracer(CallTracer::new(with_logs, Self::evm_gas_from_weight)),
+			TracerConfig::PrestateTracer { diff_mode, disable_code, disable_storage } =>
+				Tracer::PrestateTracer(PrestateTracer::new(
+					diff_mode,
+					disable_code,
+					disable_storage,
+				)),
+			TracerConfig::StructLogger { disable_stack, disable_storage } => Tracer::StructLogger(
+				StructLogger::new(disable_stack, disable_storage, Self::evm_gas_from_weight),
+			),
+		}
+	}
+
+	/// Dry-run the given transaction and return the accounts and storage keys it accessed.
+	///
+	/// The transaction is first traced to build its access list, then dry-run again with the
+	/// access list attached, to estimate the gas it needs when sent with it.
+	///
+	/// - `tx`: The Ethereum transaction to simulate.
+	/// - `eth_transact`: A function that dry-runs a transaction, see [`Self::bare_eth_transact`].
+	pub fn create_access_list(
+		mut tx: GenericTransaction,
+		eth_transact: impl Fn(
+			GenericTransaction,
+		) -> Result<EthTransactInfo<BalanceOf<T>>, EthTransactError>,
+	) -> Result<AccessListResult, EthTransactError> {
+		let mut tracer = AccessListTracer::new(tx.from.into_iter().chain(tx.to));
+		tracing::trace(&mut tracer, || eth_transact(tx.clone()))?;
+
+		// Legacy transactions can not carry an access list.
+		if tx.r#type.as_ref().map_or(true, |r#type| *r#type == TypeLegacy {}.as_byte()) {
+			tx.r#type = Some(TypeEip2930 {}.as_byte());
+		}
+		let access_list = tracer.collect_access_list();
+		tx.access_list = Some(access_list.clone());
+		let result = eth_transact(tx)?;
+
+		Ok(AccessListResult { access_list, gas_used: result.eth_gas })
+	}
+
 	/// Get the block gas limit.
 	pub fn evm_block_gas_limit() -> U256 {
 		let max_block_weight = T::BlockWeights::get()
@@ -1377,6 +1726,30 @@ where
 		Ok(maybe_value)
 	}
 
+	/// Get the storage keys needed to prove the state of `address` and of the given storage slots.
+	///
+	/// The account and contract info are stored in the main trie, while the storage slots are
+	/// stored in the child trie of the contract. The address mapping is included, so that the
+	/// proof shows which account holds the nonce and the balance of `address`.
+	pub fn account_proof_keys(address: H160, storage_keys: Vec<[u8; 32]>) -> AccountProofKeys {
+		let account_id = T::AddressMapper::to_account_id(&address);
+		let account_keys = vec![
+			OriginalAccount::<T>::hashed_key_for(&address),
+			frame_system::Account::<T>::hashed_key_for(&account_id),
+			ContractInfoOf::<T>::hashed_key_for(&address),
+		];
+		let contract_info = ContractInfoOf::<T>::get(&address);
+
+		AccountProofKeys {
+			account_keys,
+			child_trie_key: contract_info
+				.as_ref()
+				.map(|info| info.child_trie_info().prefixed_storage_key().into_inner()),
+			code_hash: contract_info.map(|info| info.code_hash),
+			storage_keys: storage_keys.into_iter().map(|key| Key::from_fixed(key).hash()).collect(),
+		}
+	}
+
 	/// Uploads new code and returns the Wasm blob and deposit amount collected.
 	fn try_upload_code(
 		origin: T::AccountId,
@@ -1452,7 +1825,7 @@ environmental!(executing_contract: bool);
 
 sp_api::decl_runtime_apis! {
 	/// The API used to dry-run contract interactions.
-	#[api_version(1)]
+	#[api_version(6)]
 	pub trait ReviveApi<AccountId, Balance, Nonce, BlockNumber> where
 		AccountId: Codec,
 		Balance: Codec,
@@ -1497,6 +1870,12 @@ sp_api::decl_runtime_apis! {
 		) -> ContractResult<InstantiateReturnValue, Balance>;
 
 
+		/// Perform an Ethereum call.
+		///
+		/// EIP-7702 authorization lists are not supported by this version.
+		#[changed_in(6)]
+		fn eth_transact(tx: LegacyGenericTransaction) -> Result<EthTransactInfo<Balance>, EthTransactError>;
+
 		/// Perform an Ethereum call.
 		///
 		/// See [`crate::Pallet::bare_eth_transact`]
@@ -1521,9 +1900,23 @@ sp_api::decl_runtime_apis! {
 			key: [u8; 32],
 		) -> GetStorageResult;
 
+		/// Returns the storage keys needed to prove the state of an account and of the given
+		/// storage slots of its contract.
+		///
+		/// See eth-rpc `eth_getProof` for usage.
+		fn account_proof_keys(address: H160, storage_keys: Vec<[u8; 32]>) -> AccountProofKeys;
 
 		/// Traces the execution of an entire block and returns call traces.
 		///
+		/// Only the call tracer is supported by this version.
+		#[changed_in(2)]
+		fn trace_block(
+			block: Block,
+			config: TracerConfig
+		) -> Vec<(u32, CallTrace)>;
+
+		/// Traces the execution of an entire block and returns the traces of its transactions.
+		///
 		/// This is intended to be called through `state_call` to replay the block from the
 		/// parent block.
 		///
@@ -1531,7 +1924,17 @@ sp_api::decl_runtime_apis! {
 		fn trace_block(
 			block: Block,
 			config: TracerConfig
-		) -> Vec<(u32, CallTrace)>;
+		) -> Vec<(u32, Trace)>;
+
+		/// Traces the execution of a specific transaction within a block.
+		///
+		/// Only the call tracer is supported by this version.
+		#[changed_in(2)]
+		fn trace_tx(
+			block: Block,
+			tx_index: u32,
+			config: TracerConfig
+		) -> Option<CallTrace>;
 
 		/// Traces the execution of a specific transaction within a block.
 		///
@@ -1543,12 +1946,40 @@ sp_api::decl_runtime_apis! {
 			block: Block,
 			tx_index: u32,
 			config: TracerConfig
-		) -> Option<CallTrace>;
+		) -> Option<Trace>;
+
+		/// Dry run the given call and return the accounts and storage keys it accessed.
+		///
+		/// EIP-7702 authorization lists are not supported by this version.
+		#[changed_in(6)]
+		fn create_access_list(tx: LegacyGenericTransaction) -> Result<AccessListResult, EthTransactError>;
+
+		/// Dry run the given call and return the accounts and storage keys it accessed.
+		///
+		/// See eth-rpc `eth_createAccessList` for usage.
+		fn create_access_list(tx: GenericTransaction) -> Result<AccessListResult, EthTransactError>;
+
+		/// Dry run and return the call trace of the given call.
+		///
+		/// Only the call tracer is supported by this version.
+		#[changed_in(2)]
+		fn trace_call(tx: LegacyGenericTransaction, config: TracerConfig) -> Result<CallTrace, EthTransactError>;
+
+		/// Dry run and return the trace of the given call.
+		///
+		/// EIP-7702 authorization lists are not supported by this version.
+		#[changed_in(6)]
+		fn trace_call(tx: LegacyGenericTransaction, config: TracerConfig) -> Result<Trace, EthTransactError>;
 
 		/// Dry run and return the trace of the given call.
 		///
 		/// See eth-rpc `debug_traceCall` for usage.
-		fn trace_call(tx: GenericTransaction, config: TracerConfig) -> Result<CallTrace, EthTransactError>;
+		fn trace_call(tx: GenericTransaction, config: TracerConfig) -> Result<Trace, EthTransactError>;
+
+		/// Returns the gas schedule used to estimate common Ethereum operations, if any.
+		///
+		/// See eth-rpc `revive_gasSchedule` for usage.
+		fn gas_schedule() -> Option<GasScheduleResult>;
 
 	}
 }
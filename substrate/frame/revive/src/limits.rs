@@ -63,6 +63,12 @@ pub const PAGE_SIZE: u32 = 4 * 1024;
 /// Which should always be enough because Solidity allows for 16 local (stack) variables.
 pub const IMMUTABLE_BYTES: u32 = 4 * 1024;
 
+/// The maximum number of accounts and storage keys of an access list.
+///
+/// The access list is decoded and its items are collected before any gas is charged. Its size
+/// is therefore bounded and accounted for in the weight of the extrinsics taking one.
+pub const ACCESS_LIST_ITEMS: u32 = 1024;
+
 /// Limits that are only enforced on code upload.
 ///
 /// # Note
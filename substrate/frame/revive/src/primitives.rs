@@ -131,6 +131,20 @@ pub enum ContractAccessError {
 	KeyDecodingFailed,
 }
 
+/// The storage keys that have to be proven to prove the state of an account, as returned by
+/// [`crate::Pallet::account_proof_keys`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct AccountProofKeys {
+	/// The keys of the main trie holding the account and its contract info.
+	pub account_keys: Vec<Vec<u8>>,
+	/// The prefixed key of the contract's child trie, `None` if the account is not a contract.
+	pub child_trie_key: Option<Vec<u8>>,
+	/// The code hash of the contract, `None` if the account is not a contract.
+	pub code_hash: Option<sp_core::H256>,
+	/// The keys of the requested storage slots in the contract's child trie.
+	pub storage_keys: Vec<Vec<u8>>,
+}
+
 /// Output of a contract call or instantiation which ran to completion.
 #[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, Default)]
 pub struct ExecReturnValue {
@@ -0,0 +1,436 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! BLS12-381 curve operations as specified by [EIP-2537](https://eips.ethereum.org/EIPS/eip-2537).
+
+use super::Precompile;
+use crate::{Config, ExecReturnValue, GasMeter, RuntimeCosts};
+use alloc::{vec, vec::Vec};
+use ark_bls12_381::{
+	g1, g2, Bls12_381, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective,
+};
+use ark_ec::{
+	hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurve},
+	pairing::Pairing,
+	AffineRepr, CurveGroup, VariableBaseMSM,
+};
+use ark_ff::{BigInteger, One, PrimeField, Zero};
+use pallet_revive_uapi::ReturnFlags;
+
+/// Length of an encoded base field element.
+///
+/// The 48 bytes big endian value is left padded with 16 zero bytes.
+const FP_LENGTH: usize = 64;
+
+/// Length of the zero padding of an encoded base field element.
+const FP_PADDING_LENGTH: usize = 16;
+
+/// Length of an encoded G1 point, i.e. its `x` and `y` coordinates.
+const G1_LENGTH: usize = 2 * FP_LENGTH;
+
+/// Length of an encoded G2 point, i.e. its `x` and `y` coordinates over the quadratic extension.
+const G2_LENGTH: usize = 4 * FP_LENGTH;
+
+/// Length of an encoded scalar.
+const SCALAR_LENGTH: usize = 32;
+
+/// Length of a G1 MSM input pair.
+const G1_MSM_PAIR_LENGTH: usize = G1_LENGTH + SCALAR_LENGTH;
+
+/// Length of a G2 MSM input pair.
+const G2_MSM_PAIR_LENGTH: usize = G2_LENGTH + SCALAR_LENGTH;
+
+/// Length of a pairing check input pair.
+const PAIRING_PAIR_LENGTH: usize = G1_LENGTH + G2_LENGTH;
+
+/// The Bls12381G1Add precompile.
+pub struct Bls12381G1Add;
+
+impl<T: Config> Precompile<T> for Bls12381G1Add {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> Result<ExecReturnValue, &'static str> {
+		gas_meter.charge(RuntimeCosts::Bls12381G1Add)?;
+
+		if input.len() != 2 * G1_LENGTH {
+			return Err("invalid input length");
+		}
+
+		// Subgroup checks are not required by the specification for additions.
+		let a = decode_g1(&input[..G1_LENGTH], false)?;
+		let b = decode_g1(&input[G1_LENGTH..], false)?;
+
+		let sum = (a.into_group() + b).into_affine();
+		Ok(ExecReturnValue { data: encode_g1(&sum), flags: ReturnFlags::empty() })
+	}
+}
+
+/// The Bls12381G1Msm precompile.
+pub struct Bls12381G1Msm;
+
+impl<T: Config> Precompile<T> for Bls12381G1Msm {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> Result<ExecReturnValue, &'static str> {
+		if input.is_empty() || input.len() % G1_MSM_PAIR_LENGTH != 0 {
+			return Err("invalid input length");
+		}
+
+		let pairs = input.len() / G1_MSM_PAIR_LENGTH;
+		gas_meter.charge(RuntimeCosts::Bls12381G1Msm(pairs as u32))?;
+
+		let mut bases = Vec::with_capacity(pairs);
+		let mut scalars = Vec::with_capacity(pairs);
+		for pair in input.chunks_exact(G1_MSM_PAIR_LENGTH) {
+			bases.push(decode_g1(&pair[..G1_LENGTH], true)?);
+			scalars.push(decode_scalar(&pair[G1_LENGTH..]));
+		}
+
+		let result = G1Projective::msm(&bases, &scalars)
+			.map_err(|_| "mismatched number of points and scalars")?;
+		Ok(ExecReturnValue { data: encode_g1(&result.into_affine()), flags: ReturnFlags::empty() })
+	}
+}
+
+/// The Bls12381G2Add precompile.
+pub struct Bls12381G2Add;
+
+impl<T: Config> Precompile<T> for Bls12381G2Add {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> Result<ExecReturnValue, &'static str> {
+		gas_meter.charge(RuntimeCosts::Bls12381G2Add)?;
+
+		if input.len() != 2 * G2_LENGTH {
+			return Err("invalid input length");
+		}
+
+		// Subgroup checks are not required by the specification for additions.
+		let a = decode_g2(&input[..G2_LENGTH], false)?;
+		let b = decode_g2(&input[G2_LENGTH..], false)?;
+
+		let sum = (a.into_group() + b).into_affine();
+		Ok(ExecReturnValue { data: encode_g2(&sum), flags: ReturnFlags::empty() })
+	}
+}
+
+/// The Bls12381G2Msm precompile.
+pub struct Bls12381G2Msm;
+
+impl<T: Config> Precompile<T> for Bls12381G2Msm {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> Result<ExecReturnValue, &'static str> {
+		if input.is_empty() || input.len() % G2_MSM_PAIR_LENGTH != 0 {
+			return Err("invalid input length");
+		}
+
+		let pairs = input.len() / G2_MSM_PAIR_LENGTH;
+		gas_meter.charge(RuntimeCosts::Bls12381G2Msm(pairs as u32))?;
+
+		let mut bases = Vec::with_capacity(pairs);
+		let mut scalars = Vec::with_capacity(pairs);
+		for pair in input.chunks_exact(G2_MSM_PAIR_LENGTH) {
+			bases.push(decode_g2(&pair[..G2_LENGTH], true)?);
+			scalars.push(decode_scalar(&pair[G2_LENGTH..]));
+		}
+
+		let result = G2Projective::msm(&bases, &scalars)
+			.map_err(|_| "mismatched number of points and scalars")?;
+		Ok(ExecReturnValue { data: encode_g2(&result.into_affine()), flags: ReturnFlags::empty() })
+	}
+}
+
+/// The Bls12381PairingCheck precompile.
+pub struct Bls12381PairingCheck;
+
+impl<T: Config> Precompile<T> for Bls12381PairingCheck {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> Result<ExecReturnValue, &'static str> {
+		if input.is_empty() || input.len() % PAIRING_PAIR_LENGTH != 0 {
+			return Err("invalid input length");
+		}
+
+		let pairs = input.len() / PAIRING_PAIR_LENGTH;
+		gas_meter.charge(RuntimeCosts::Bls12381PairingCheck(pairs as u32))?;
+
+		let mut g1_points = Vec::with_capacity(pairs);
+		let mut g2_points = Vec::with_capacity(pairs);
+		for pair in input.chunks_exact(PAIRING_PAIR_LENGTH) {
+			g1_points.push(decode_g1(&pair[..G1_LENGTH], true)?);
+			g2_points.push(decode_g2(&pair[G1_LENGTH..], true)?);
+		}
+
+		let mut data = [0u8; 32];
+		if Bls12_381::multi_pairing(g1_points, g2_points).0.is_one() {
+			data[31] = 1;
+		}
+
+		Ok(ExecReturnValue { data: data.to_vec(), flags: ReturnFlags::empty() })
+	}
+}
+
+/// The Bls12381MapFpToG1 precompile.
+pub struct Bls12381MapFpToG1;
+
+impl<T: Config> Precompile<T> for Bls12381MapFpToG1 {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> Result<ExecReturnValue, &'static str> {
+		gas_meter.charge(RuntimeCosts::Bls12381MapFpToG1)?;
+
+		if input.len() != FP_LENGTH {
+			return Err("invalid input length");
+		}
+
+		let fp = decode_fp(input)?;
+		let point = WBMap::<g1::Config>::new()
+			.and_then(|map| map.map_to_curve(fp))
+			.map_err(|_| "failed to map field element to G1")?
+			.clear_cofactor();
+
+		Ok(ExecReturnValue { data: encode_g1(&point), flags: ReturnFlags::empty() })
+	}
+}
+
+/// The Bls12381MapFp2ToG2 precompile.
+pub struct Bls12381MapFp2ToG2;
+
+impl<T: Config> Precompile<T> for Bls12381MapFp2ToG2 {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> Result<ExecReturnValue, &'static str> {
+		gas_meter.charge(RuntimeCosts::Bls12381MapFp2ToG2)?;
+
+		if input.len() != 2 * FP_LENGTH {
+			return Err("invalid input length");
+		}
+
+		let fp2 = decode_fp2(input)?;
+		let point = WBMap::<g2::Config>::new()
+			.and_then(|map| map.map_to_curve(fp2))
+			.map_err(|_| "failed to map field element to G2")?
+			.clear_cofactor();
+
+		Ok(ExecReturnValue { data: encode_g2(&point), flags: ReturnFlags::empty() })
+	}
+}
+
+/// Decodes a base field element.
+fn decode_fp(input: &[u8]) -> Result<Fq, &'static str> {
+	debug_assert_eq!(input.len(), FP_LENGTH);
+	let (padding, value) = input.split_at(FP_PADDING_LENGTH);
+	if padding.iter().any(|byte| *byte != 0) {
+		return Err("invalid field element top bytes");
+	}
+
+	// Big endian bytes to little endian limbs.
+	let mut limbs = [0u64; 6];
+	for (limb, chunk) in limbs.iter_mut().zip(value.rchunks_exact(8)) {
+		*limb = u64::from_be_bytes(chunk.try_into().expect("chunk is 8 bytes long; qed"));
+	}
+
+	// Values outside of the field are rejected rather than reduced.
+	Fq::from_bigint(ark_ff::BigInt(limbs)).ok_or("invalid fp.Element encoding")
+}
+
+/// Decodes an element of the quadratic extension field, encoded as `c0 || c1`.
+fn decode_fp2(input: &[u8]) -> Result<Fq2, &'static str> {
+	let c0 = decode_fp(&input[..FP_LENGTH])?;
+	let c1 = decode_fp(&input[FP_LENGTH..])?;
+	Ok(Fq2::new(c0, c1))
+}
+
+/// Decodes a scalar.
+///
+/// Scalars are not required to be lower than the group order. Points are always checked to
+/// belong to the prime order subgroup before being multiplied, hence the scalar can be reduced.
+fn decode_scalar(input: &[u8]) -> Fr {
+	Fr::from_be_bytes_mod_order(input)
+}
+
+/// Decodes a G1 point, optionally checking that it belongs to the prime order subgroup.
+///
+/// The point at infinity is encoded as all zeroes.
+fn decode_g1(input: &[u8], subgroup_check: bool) -> Result<G1Affine, &'static str> {
+	let x = decode_fp(&input[..FP_LENGTH])?;
+	let y = decode_fp(&input[FP_LENGTH..])?;
+	if x.is_zero() && y.is_zero() {
+		return Ok(G1Affine::identity());
+	}
+
+	let point = G1Affine::new_unchecked(x, y);
+	if !point.is_on_curve() {
+		return Err("invalid point: not on curve");
+	}
+	if subgroup_check && !point.is_in_correct_subgroup_assuming_on_curve() {
+		return Err("g1 point is not on correct subgroup");
+	}
+
+	Ok(point)
+}
+
+/// Decodes a G2 point, optionally checking that it belongs to the prime order subgroup.
+///
+/// The point at infinity is encoded as all zeroes.
+fn decode_g2(input: &[u8], subgroup_check: bool) -> Result<G2Affine, &'static str> {
+	let x = decode_fp2(&input[..2 * FP_LENGTH])?;
+	let y = decode_fp2(&input[2 * FP_LENGTH..])?;
+	if x.is_zero() && y.is_zero() {
+		return Ok(G2Affine::identity());
+	}
+
+	let point = G2Affine::new_unchecked(x, y);
+	if !point.is_on_curve() {
+		return Err("invalid point: not on curve");
+	}
+	if subgroup_check && !point.is_in_correct_subgroup_assuming_on_curve() {
+		return Err("g2 point is not on correct subgroup");
+	}
+
+	Ok(point)
+}
+
+/// Encodes a base field element.
+fn encode_fp(fp: &Fq, output: &mut [u8]) {
+	output[FP_PADDING_LENGTH..FP_LENGTH].copy_from_slice(&fp.into_bigint().to_bytes_be());
+}
+
+/// Encodes a G1 point, the point at infinity is encoded as all zeroes.
+fn encode_g1(point: &G1Affine) -> Vec<u8> {
+	let mut output = vec![0u8; G1_LENGTH];
+	if let Some((x, y)) = point.xy() {
+		encode_fp(x, &mut output[..FP_LENGTH]);
+		encode_fp(y, &mut output[FP_LENGTH..]);
+	}
+	output
+}
+
+/// Encodes a G2 point, the point at infinity is encoded as all zeroes.
+fn encode_g2(point: &G2Affine) -> Vec<u8> {
+	let mut output = vec![0u8; G2_LENGTH];
+	if let Some((x, y)) = point.xy() {
+		encode_fp(&x.c0, &mut output[..FP_LENGTH]);
+		encode_fp(&x.c1, &mut output[FP_LENGTH..2 * FP_LENGTH]);
+		encode_fp(&y.c0, &mut output[2 * FP_LENGTH..3 * FP_LENGTH]);
+		encode_fp(&y.c1, &mut output[3 * FP_LENGTH..]);
+	}
+	output
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub fn generate_bls12_381_g1_msm_input(n: usize) -> Vec<u8> {
+	let mut input = Vec::with_capacity(n * G1_MSM_PAIR_LENGTH);
+	for i in 0..n {
+		let point = (G1Affine::generator() * Fr::from(i as u64 + 1)).into_affine();
+		input.extend(encode_g1(&point));
+		input.extend([0xffu8; SCALAR_LENGTH]);
+	}
+	input
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub fn generate_bls12_381_g2_msm_input(n: usize) -> Vec<u8> {
+	let mut input = Vec::with_capacity(n * G2_MSM_PAIR_LENGTH);
+	for i in 0..n {
+		let point = (G2Affine::generator() * Fr::from(i as u64 + 1)).into_affine();
+		input.extend(encode_g2(&point));
+		input.extend([0xffu8; SCALAR_LENGTH]);
+	}
+	input
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub fn generate_bls12_381_pairing_input(n: usize) -> Vec<u8> {
+	let mut input = Vec::with_capacity(n * PAIRING_PAIR_LENGTH);
+	for i in 0..n {
+		let scalar = Fr::from(i as u64 + 1);
+		input.extend(encode_g1(&(G1Affine::generator() * scalar).into_affine()));
+		input.extend(encode_g2(&(G2Affine::generator() * scalar).into_affine()));
+	}
+	input
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::pure_precompiles::test::*;
+
+	#[test]
+	fn test_bls12_381_g1_add() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381G1Add>(include_str!(
+			"./testdata/11-bls12381-g1add.json"
+		))?;
+		test_precompile_failure_test_vectors::<Bls12381G1Add>(include_str!(
+			"./testdata/11-bls12381-g1add-failure.json"
+		))?;
+		Ok(())
+	}
+
+	#[test]
+	fn test_bls12_381_g1_msm() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381G1Msm>(include_str!(
+			"./testdata/12-bls12381-g1msm.json"
+		))?;
+		test_precompile_failure_test_vectors::<Bls12381G1Msm>(include_str!(
+			"./testdata/12-bls12381-g1msm-failure.json"
+		))?;
+		Ok(())
+	}
+
+	#[test]
+	fn test_bls12_381_g2_add() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381G2Add>(include_str!(
+			"./testdata/13-bls12381-g2add.json"
+		))?;
+		test_precompile_failure_test_vectors::<Bls12381G2Add>(include_str!(
+			"./testdata/13-bls12381-g2add-failure.json"
+		))?;
+		Ok(())
+	}
+
+	#[test]
+	fn test_bls12_381_g2_msm() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381G2Msm>(include_str!(
+			"./testdata/14-bls12381-g2msm.json"
+		))?;
+		test_precompile_failure_test_vectors::<Bls12381G2Msm>(include_str!(
+			"./testdata/14-bls12381-g2msm-failure.json"
+		))?;
+		Ok(())
+	}
+
+	#[test]
+	fn test_bls12_381_pairing_check() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381PairingCheck>(include_str!(
+			"./testdata/15-bls12381-pairing.json"
+		))?;
+		test_precompile_failure_test_vectors::<Bls12381PairingCheck>(include_str!(
+			"./testdata/15-bls12381-pairing-failure.json"
+		))?;
+		Ok(())
+	}
+
+	#[test]
+	fn test_bls12_381_map_fp_to_g1() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381MapFpToG1>(include_str!(
+			"./testdata/16-bls12381-map-fp-to-g1.json"
+		))?;
+		test_precompile_failure_test_vectors::<Bls12381MapFpToG1>(include_str!(
+			"./testdata/16-bls12381-map-fp-to-g1-failure.json"
+		))?;
+		Ok(())
+	}
+
+	#[test]
+	fn test_bls12_381_map_fp2_to_g2() -> Result<(), String> {
+		test_precompile_test_vectors::<Bls12381MapFp2ToG2>(include_str!(
+			"./testdata/17-bls12381-map-fp2-to-g2.json"
+		))?;
+		test_precompile_failure_test_vectors::<Bls12381MapFp2ToG2>(include_str!(
+			"./testdata/17-bls12381-map-fp2-to-g2-failure.json"
+		))?;
+		Ok(())
+	}
+}
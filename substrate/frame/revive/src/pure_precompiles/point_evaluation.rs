@@ -0,0 +1,133 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! KZG point evaluation as specified by [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844).
+
+use super::Precompile;
+use crate::{Config, ExecReturnValue, GasMeter, RuntimeCosts};
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, One, PrimeField};
+use ark_serialize::CanonicalDeserialize;
+use hex_literal::hex;
+use pallet_revive_uapi::ReturnFlags;
+
+/// Version byte of the versioned hash of a KZG commitment.
+const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// Number of field elements in a blob.
+const FIELD_ELEMENTS_PER_BLOB: u64 = 4096;
+
+/// `[τ]₂`, the second G2 point of the monomial form of the Ethereum KZG ceremony trusted setup.
+///
+/// The first point of the setup is the G2 generator, which is the only other point required to
+/// verify a single point evaluation.
+const TRUSTED_SETUP_TAU_G2: [u8; 96] = hex!("b5bfd7dd8cdeb128843bc287230af38926187075cbfbefa81009a2ce615ac53d2914e5870cb452d2afaaab24f3499f72185cbfee53492714734429b7b38608e23926c911cceceac9a36851477ba4c60b087041de621000edc98edada20c1def2");
+
+/// The PointEvaluation precompile.
+///
+/// Input is encoded as `versioned_hash (32) || z (32) || y (32) || commitment (48) || proof (48)`,
+/// where `z` and `y` are big endian scalars and the commitment and proof are compressed G1
+/// points. The precompile verifies that the polynomial committed to evaluates to `y` at `z`.
+pub struct PointEvaluation;
+
+impl<T: Config> Precompile<T> for PointEvaluation {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> Result<ExecReturnValue, &'static str> {
+		gas_meter.charge(RuntimeCosts::PointEvaluation)?;
+
+		if input.len() != 192 {
+			return Err("invalid input length");
+		}
+
+		let versioned_hash = &input[..32];
+		let commitment = &input[96..144];
+		if kzg_to_versioned_hash(commitment) != versioned_hash {
+			return Err("mismatched versioned hash");
+		}
+
+		let z = decode_scalar(&input[32..64])?;
+		let y = decode_scalar(&input[64..96])?;
+		let commitment = decode_g1(commitment)?;
+		let proof = decode_g1(&input[144..192])?;
+
+		if !verify_kzg_proof(commitment, z, y, proof) {
+			return Err("invalid KZG proof");
+		}
+
+		// Return `FIELD_ELEMENTS_PER_BLOB` and `BLS_MODULUS` as 32 bytes big endian values.
+		let mut data = [0u8; 64];
+		data[24..32].copy_from_slice(&FIELD_ELEMENTS_PER_BLOB.to_be_bytes());
+		data[32..].copy_from_slice(&Fr::MODULUS.to_bytes_be());
+		Ok(ExecReturnValue { data: data.to_vec(), flags: ReturnFlags::empty() })
+	}
+}
+
+/// Computes `VERSIONED_HASH_VERSION_KZG || sha256(commitment)[1..]`.
+fn kzg_to_versioned_hash(commitment: &[u8]) -> [u8; 32] {
+	let mut hash = sp_io::hashing::sha2_256(commitment);
+	hash[0] = VERSIONED_HASH_VERSION_KZG;
+	hash
+}
+
+/// Decodes a big endian scalar, rejecting values which are not lower than the group order.
+fn decode_scalar(input: &[u8]) -> Result<Fr, &'static str> {
+	let mut limbs = [0u64; 4];
+	for (limb, chunk) in limbs.iter_mut().zip(input.rchunks_exact(8)) {
+		*limb = u64::from_be_bytes(chunk.try_into().expect("chunk is 8 bytes long; qed"));
+	}
+	Fr::from_bigint(ark_ff::BigInt(limbs)).ok_or("invalid scalar")
+}
+
+/// Decodes a compressed G1 point, checking that it belongs to the prime order subgroup.
+fn decode_g1(input: &[u8]) -> Result<G1Affine, &'static str> {
+	G1Affine::deserialize_compressed(input).map_err(|_| "invalid G1 point")
+}
+
+/// Verifies that `proof` attests that the polynomial committed to by `commitment` evaluates to
+/// `y` at `z`, i.e. checks that `e(commitment - [y]₁, [1]₂) == e(proof, [τ]₂ - [z]₂)`.
+fn verify_kzg_proof(commitment: G1Affine, z: Fr, y: Fr, proof: G1Affine) -> bool {
+	let tau_g2 = G2Affine::deserialize_compressed_unchecked(&TRUSTED_SETUP_TAU_G2[..])
+		.expect("the trusted setup point is a valid compressed G2 point; qed");
+
+	let g1 = G1Affine::generator();
+	let g2 = G2Affine::generator();
+
+	let commitment_minus_y = (commitment.into_group() - g1 * y).into_affine();
+	let tau_minus_z = (tau_g2.into_group() - g2 * z).into_affine();
+
+	// e(commitment - [y]₁, -[1]₂) * e(proof, [τ - z]₂) == 1
+	Bls12_381::multi_pairing([commitment_minus_y, proof], [-g2, tau_minus_z])
+		.0
+		.is_one()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::pure_precompiles::test::*;
+
+	#[test]
+	fn test_point_evaluation() -> Result<(), String> {
+		test_precompile_test_vectors::<PointEvaluation>(include_str!(
+			"./testdata/10-pointEvaluation.json"
+		))?;
+		test_precompile_failure_test_vectors::<PointEvaluation>(include_str!(
+			"./testdata/10-pointEvaluation-failure.json"
+		))?;
+		Ok(())
+	}
+}
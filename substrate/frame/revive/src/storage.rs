@@ -24,14 +24,18 @@ use crate::{
 	exec::{AccountIdOf, Key},
 	storage::meter::Diff,
 	weights::WeightInfo,
-	BalanceOf, Config, ContractInfoOf, DeletionQueue, DeletionQueueCounter, Error, TrieId,
-	SENTINEL,
+	BalanceOf, Config, ContractInfoOf, DeletionQueue, DeletionQueueCounter, DeletionQueueDeposit,
+	Error, HoldReason, TrieId, LOG_TARGET, SENTINEL,
 };
 use alloc::vec::Vec;
 use codec::{Decode, Encode, MaxEncodedLen};
 use core::marker::PhantomData;
 use frame_support::{
 	storage::child::{self, ChildInfo},
+	traits::{
+		fungible::{Inspect, Mutate, MutateHold},
+		tokens::{Fortitude::Polite, Precision, Preservation},
+	},
 	weights::{Weight, WeightMeter},
 	CloneNoBound, DefaultNoBound,
 };
@@ -39,7 +43,7 @@ use scale_info::TypeInfo;
 use sp_core::{Get, H160};
 use sp_io::KillStorageResult;
 use sp_runtime::{
-	traits::{Hash, Saturating, Zero},
+	traits::{Hash, SaturatedConversion, Saturating, Zero},
 	DispatchError, RuntimeDebug,
 };
 
@@ -246,9 +250,18 @@ impl<T: Config> ContractInfo<T> {
 
 	/// Push a contract's trie to the deletion queue for lazy removal.
 	///
+	/// The storage deposit of the contract stays on hold until the trie is fully removed. It is
+	/// then released to the `beneficiary` of the termination.
+	///
 	/// You must make sure that the contract is also removed when queuing the trie for deletion.
-	pub fn queue_trie_for_deletion(&self) {
-		DeletionQueueManager::<T>::load().insert(self.trie_id.clone());
+	pub fn queue_trie_for_deletion(&self, contract: &AccountIdOf<T>, beneficiary: &AccountIdOf<T>) {
+		let amount = self.total_deposit();
+		let deposit = (!amount.is_zero()).then(|| DeletionDeposit {
+			contract: contract.clone(),
+			beneficiary: beneficiary.clone(),
+			amount,
+		});
+		DeletionQueueManager::<T>::load().insert(self.trie_id.clone(), deposit);
 	}
 
 	/// Calculates the weight that is necessary to remove one key from the trie and how many
@@ -281,6 +294,7 @@ impl<T: Config> ContractInfo<T> {
 		}
 
 		let (weight_per_key, budget) = Self::deletion_budget(&meter);
+		let refund_keys = DeletionDeposit::<T>::refund_keys(weight_per_key);
 		let mut remaining_key_budget = budget;
 		while remaining_key_budget > 0 {
 			let Some(entry) = queue.next() else { break };
@@ -298,9 +312,17 @@ impl<T: Config> ContractInfo<T> {
 					break
 				},
 				KillStorageResult::AllRemoved(keys_removed) => {
-					entry.remove();
 					// charge at least one key even if none were removed.
 					remaining_key_budget = remaining_key_budget.saturating_sub(keys_removed.max(1));
+					// The entry stays in the queue if its deposit can't be refunded within the
+					// budget. Its (now empty) trie is then revisited by the next batch.
+					if entry.deposit.is_some() {
+						if remaining_key_budget < refund_keys {
+							break
+						}
+						remaining_key_budget.saturating_reduce(refund_keys);
+					}
+					entry.remove();
 				},
 			};
 		}
@@ -384,19 +406,107 @@ pub struct DeletionQueueManager<T: Config> {
 	_phantom: PhantomData<T>,
 }
 
+/// The storage deposit held for a trie in the deletion queue.
+///
+/// It is released to the beneficiary of the termination once the trie was fully removed. This
+/// way the deposit keeps paying for the storage until it is actually gone.
+#[derive(Encode, Decode, CloneNoBound, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct DeletionDeposit<T: Config> {
+	/// The account of the terminated contract which holds the deposit.
+	contract: AccountIdOf<T>,
+	/// The account receiving the deposit.
+	beneficiary: AccountIdOf<T>,
+	/// The amount of deposit held for the trie.
+	amount: BalanceOf<T>,
+}
+
+impl<T: Config> DeletionDeposit<T> {
+	/// How many keys of the deletion budget are spent on [`Self::refund`].
+	///
+	/// Refunding the deposit of a removed trie is paid for with the key budget. See
+	/// [`ContractInfo::deletion_budget`].
+	pub fn refund_keys(weight_per_key: Weight) -> u32 {
+		T::DbWeight::get()
+			.reads_writes(5, 5)
+			.checked_div_per_component(&weight_per_key)
+			.unwrap_or(0)
+			.saturating_add(1)
+			.saturated_into()
+	}
+
+	/// Release the deposit to the beneficiary.
+	///
+	/// Unless a new contract was instantiated at the same address in the meantime, the
+	/// remaining balance of the account is sent along, which reaps it.
+	fn refund(self) {
+		let released = T::Currency::release(
+			&HoldReason::StorageDepositReserve.into(),
+			&self.contract,
+			self.amount,
+			Precision::BestEffort,
+		)
+		.unwrap_or_else(|_| Zero::zero());
+
+		if released < self.amount {
+			// This should never happen, if it does it means that there is a bug in the
+			// runtime logic.
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to release full storage deposit {:?} of deleted contract {:?}. Released {:?}.",
+				self.amount, self.contract, released,
+			);
+		}
+
+		let alive =
+			<ContractInfoOf<T>>::contains_key(&T::AddressMapper::to_address(&self.contract));
+		let (amount, preservation) = if alive {
+			(released, Preservation::Preserve)
+		} else {
+			let amount =
+				T::Currency::reducible_balance(&self.contract, Preservation::Expendable, Polite);
+			(amount, Preservation::Expendable)
+		};
+
+		// A contract which terminated in favour of itself burns its balance, see EIP-6780.
+		let result = if self.beneficiary == self.contract {
+			T::Currency::burn_from(&self.contract, amount, preservation, Precision::Exact, Polite)
+				.map(|_| ())
+		} else {
+			T::Currency::transfer(&self.contract, &self.beneficiary, amount, preservation)
+				.map(|_| ())
+		};
+
+		if let Err(err) = result {
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to refund storage deposit {:?} of deleted contract {:?} to {:?}: {:?}",
+				amount, self.contract, self.beneficiary, err,
+			);
+		}
+	}
+}
+
 /// View on a contract that is marked for deletion.
 struct DeletionQueueEntry<'a, T: Config> {
 	/// the trie id of the contract to delete.
 	trie_id: TrieId,
 
+	/// The deposit to refund once the trie is removed.
+	deposit: Option<DeletionDeposit<T>>,
+
 	/// A mutable reference on the queue so that the contract can be removed, and none can be added
 	/// or read in the meantime.
 	queue: &'a mut DeletionQueueManager<T>,
 }
 
 impl<'a, T: Config> DeletionQueueEntry<'a, T> {
-	/// Remove the contract from the deletion queue.
+	/// Remove the contract from the deletion queue and refund its deposit.
 	fn remove(self) {
+		if let Some(deposit) = self.deposit {
+			<DeletionQueueDeposit<T>>::remove(self.queue.delete_counter);
+			deposit.refund();
+		}
 		<DeletionQueue<T>>::remove(self.queue.delete_counter);
 		self.queue.delete_counter = self.queue.delete_counter.wrapping_add(1);
 		<DeletionQueueCounter<T>>::set(self.queue.clone());
@@ -416,8 +526,11 @@ impl<T: Config> DeletionQueueManager<T> {
 	}
 
 	/// Insert a contract in the deletion queue.
-	fn insert(&mut self, trie_id: TrieId) {
+	fn insert(&mut self, trie_id: TrieId, deposit: Option<DeletionDeposit<T>>) {
 		<DeletionQueue<T>>::insert(self.insert_counter, trie_id);
+		if let Some(deposit) = deposit {
+			<DeletionQueueDeposit<T>>::insert(self.insert_counter, deposit);
+		}
 		self.insert_counter = self.insert_counter.wrapping_add(1);
 		<DeletionQueueCounter<T>>::set(self.clone());
 	}
@@ -433,7 +546,8 @@ impl<T: Config> DeletionQueueManager<T> {
 		}
 
 		let entry = <DeletionQueue<T>>::get(self.delete_counter);
-		entry.map(|trie_id| DeletionQueueEntry { trie_id, queue: self })
+		let deposit = <DeletionQueueDeposit<T>>::get(self.delete_counter);
+		entry.map(|trie_id| DeletionQueueEntry { trie_id, deposit, queue: self })
 	}
 }
 
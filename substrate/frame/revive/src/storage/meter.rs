@@ -244,10 +244,10 @@ enum Contribution<T: Config> {
 	/// The meter was checked against its limit using [`RawMeter::enforce_limit`] at the end of
 	/// its execution. In this process the [`Diff`] was converted into a [`Deposit`].
 	Checked(DepositOf<T>),
-	/// The contract was terminated. In this process the [`Diff`] was converted into a [`Deposit`]
-	/// in order to calculate the refund. Upon termination the `reducible_balance` in the
-	/// contract's account is transferred to the [`beneficiary`].
-	Terminated { deposit: DepositOf<T>, beneficiary: AccountIdOf<T> },
+	/// The contract was terminated. Its [`Diff`] is dropped together with its storage. Upon
+	/// termination the `reducible_balance` in the contract's account is transferred to the
+	/// [`beneficiary`]. The deposit is not refunded here but once its child trie is removed.
+	Terminated { beneficiary: AccountIdOf<T> },
 }
 
 impl<T: Config> Contribution<T> {
@@ -255,8 +255,8 @@ impl<T: Config> Contribution<T> {
 	fn update_contract(&self, info: Option<&mut ContractInfo<T>>) -> DepositOf<T> {
 		match self {
 			Self::Alive(diff) => diff.update_contract::<T>(info),
-			Self::Terminated { deposit, beneficiary: _ } | Self::Checked(deposit) =>
-				deposit.clone(),
+			Self::Checked(deposit) => deposit.clone(),
+			Self::Terminated { beneficiary: _ } => Deposit::Charge(Zero::zero()),
 		}
 	}
 }
@@ -312,12 +312,12 @@ where
 			.saturating_add(&absorbed.total_deposit)
 			.saturating_add(&own_deposit);
 		self.charges.extend_from_slice(&absorbed.charges);
-		if !own_deposit.is_zero() {
-			self.charges.push(Charge {
-				contract: contract.clone(),
-				amount: own_deposit,
-				state: absorbed.contract_state(),
-			});
+		// A terminated contract is recorded even without a deposit in order to pay out
+		// its remaining balance.
+		let state = absorbed.contract_state();
+		if !own_deposit.is_zero() || matches!(state, ContractState::Terminated { .. }) {
+			self.charges
+				.push(Charge { contract: contract.clone(), amount: own_deposit, state });
 		}
 	}
 
@@ -329,7 +329,7 @@ where
 	/// Returns the state of the currently executed contract.
 	fn contract_state(&self) -> ContractState<T> {
 		match &self.own_contribution {
-			Contribution::Terminated { deposit: _, beneficiary } =>
+			Contribution::Terminated { beneficiary } =>
 				ContractState::Terminated { beneficiary: beneficiary.clone() },
 			_ => ContractState::Alive,
 		}
@@ -428,15 +428,13 @@ impl<T: Config, E: Ext<T>> RawMeter<T, E, Nested> {
 
 	/// Call to tell the meter that the currently executing contract was terminated.
 	///
-	/// This will manipulate the meter so that all storage deposit accumulated in
-	/// `contract_info` will be refunded to the `origin` of the meter. And the free
-	/// (`reducible_balance`) will be sent to the `beneficiary`.
-	pub fn terminate(&mut self, info: &ContractInfo<T>, beneficiary: T::AccountId) {
+	/// This will manipulate the meter so that the free (`reducible_balance`) of the contract
+	/// will be sent to the `beneficiary`. The storage deposit accumulated in its
+	/// `contract_info` stays on hold until the child trie is removed, see
+	/// [`ContractInfo::queue_trie_for_deletion`].
+	pub fn terminate(&mut self, beneficiary: T::AccountId) {
 		debug_assert!(matches!(self.contract_state(), ContractState::Alive));
-		self.own_contribution = Contribution::Terminated {
-			deposit: Deposit::Refund(info.total_deposit()),
-			beneficiary,
-		};
+		self.own_contribution = Contribution::Terminated { beneficiary };
 	}
 
 	/// [`Self::charge`] does not enforce the storage limit since we want to do this check as late
@@ -480,7 +478,7 @@ impl<T: Config> Ext<T> for ReservingExt {
 		state: &ContractState<T>,
 	) -> Result<(), DispatchError> {
 		match amount {
-			Deposit::Charge(amount) | Deposit::Refund(amount) if amount.is_zero() => return Ok(()),
+			Deposit::Charge(amount) | Deposit::Refund(amount) if amount.is_zero() => (),
 			Deposit::Charge(amount) => {
 				// This could fail if the `origin` does not have enough liquidity. Ideally, though,
 				// this should have been checked before with `check_limit`.
@@ -519,13 +517,22 @@ impl<T: Config> Ext<T> for ReservingExt {
 		}
 		if let ContractState::<T>::Terminated { beneficiary } = state {
 			System::<T>::dec_consumers(&contract);
-			// Whatever is left in the contract is sent to the termination beneficiary.
-			T::Currency::transfer(
-				&contract,
-				&beneficiary,
-				T::Currency::reducible_balance(&contract, Preservation::Expendable, Polite),
-				Preservation::Expendable,
-			)?;
+			// Whatever is left in the contract is sent to the termination beneficiary. The
+			// existential deposit stays in the account as long as the storage deposit is held.
+			let balance =
+				T::Currency::reducible_balance(&contract, Preservation::Expendable, Polite);
+			if beneficiary == contract {
+				// A contract which terminated in favour of itself burns its balance, see EIP-6780.
+				T::Currency::burn_from(
+					&contract,
+					balance,
+					Preservation::Expendable,
+					Precision::Exact,
+					Polite,
+				)?;
+			} else {
+				T::Currency::transfer(&contract, &beneficiary, balance, Preservation::Expendable)?;
+			}
 		}
 		Ok(())
 	}
@@ -822,14 +829,14 @@ mod tests {
 		let test_cases = vec![
 			ChargingTestCase {
 				origin: Origin::<Test>::from_account_id(ALICE),
-				deposit: Deposit::Refund(108),
+				deposit: Deposit::Charge(12),
 				expected: TestExt {
 					limit_checks: vec![LimitCheck { origin: ALICE, limit: 1_000, min_leftover: 0 }],
 					charges: vec![
 						Charge {
 							origin: ALICE,
 							contract: CHARLIE,
-							amount: Deposit::Refund(120),
+							amount: Deposit::Charge(0),
 							state: ContractState::Terminated { beneficiary: CHARLIE },
 						},
 						Charge {
@@ -873,7 +880,7 @@ mod tests {
 			let mut nested1 = nested0.nested(BalanceOf::<Test>::max_value());
 			nested1.charge(&Diff { items_removed: 5, ..Default::default() });
 			nested1.charge(&Diff { bytes_added: 20, ..Default::default() });
-			nested1.terminate(&nested1_info, CHARLIE);
+			nested1.terminate(CHARLIE);
 			nested0.enforce_limit(Some(&mut nested1_info)).unwrap();
 			nested0.absorb(nested1, &CHARLIE, None);
 
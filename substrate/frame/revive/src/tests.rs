@@ -25,18 +25,22 @@ use crate::{
 		ChainExtension, Environment, Ext, RegisteredChainExtension, Result as ExtensionResult,
 		RetVal, ReturnFlags,
 	},
-	evm::{runtime::GAS_PRICE, CallTrace, CallTracer, CallType, GenericTransaction},
-	exec::Key,
+	evm::{
+		runtime::{ApplyAuthorizations, GAS_PRICE},
+		AccessListEntry, CallTrace, CallTracer, CallType, GasEncoder, GasSchedule,
+		GenericTransaction, ScheduledCost, ScheduledOperation,
+	},
+	exec::{Key, EMPTY_CODE_HASH},
 	limits,
-	storage::DeletionQueueManager,
+	storage::{DeletionDeposit, DeletionQueueManager},
 	test_utils::*,
 	tests::test_utils::{get_contract, get_contract_checked},
 	tracing::trace,
 	wasm::Memory,
 	weights::WeightInfo,
 	AccountId32Mapper, BalanceOf, Code, CodeInfoOf, Config, ContractInfo, ContractInfoOf,
-	DeletionQueueCounter, DepositLimit, Error, EthTransactError, HoldReason, Origin, Pallet,
-	PristineCode, H160,
+	DelegationOf, DeletionQueue, DeletionQueueCounter, DepositLimit, Error, EthTransactError,
+	HoldReason, Origin, OriginalAccount, Pallet, PristineCode, H160,
 };
 
 use crate::test_utils::builder::Contract;
@@ -45,6 +49,7 @@ use codec::{Decode, Encode};
 use frame_support::{
 	assert_err, assert_err_ignore_postinfo, assert_err_with_weight, assert_noop, assert_ok,
 	derive_impl,
+	dispatch::GetDispatchInfo,
 	pallet_prelude::EnsureOrigin,
 	parameter_types,
 	storage::child,
@@ -65,7 +70,7 @@ use sp_io::hashing::blake2_256;
 use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
 use sp_runtime::{
 	testing::H256,
-	traits::{BlakeTwo256, Convert, IdentityLookup, One},
+	traits::{BlakeTwo256, Convert, DispatchTransaction, IdentityLookup, One},
 	AccountId32, BuildStorage, DispatchError, Perbill, TokenError,
 };
 
@@ -509,6 +514,7 @@ where
 }
 parameter_types! {
 	pub static UnstableInterface: bool = true;
+	pub static EthGasSchedule: Option<GasSchedule<u64>> = None;
 }
 
 impl FindAuthor<<Test as frame_system::Config>::AccountId> for Test {
@@ -535,6 +541,7 @@ impl Config for Test {
 	type InstantiateOrigin = EnsureAccount<Self, InstantiateAccount>;
 	type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
 	type ChainId = ChainId;
+	type EthGasSchedule = EthGasSchedule;
 	type FindAuthor = Test;
 }
 
@@ -880,7 +887,7 @@ fn instantiate_unique_trie_id() {
 
 	ExtBuilder::default().existential_deposit(500).build().execute_with(|| {
 		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
-		Contracts::upload_code(RuntimeOrigin::signed(ALICE), wasm, deposit_limit::<Test>())
+		Contracts::upload_code(RuntimeOrigin::signed(ALICE), wasm.clone(), deposit_limit::<Test>())
 			.unwrap();
 
 		// Instantiate the contract and store its trie id for later comparison.
@@ -894,14 +901,18 @@ fn instantiate_unique_trie_id() {
 			<Error<Test>>::DuplicateContract,
 		);
 
-		// Terminate the contract.
+		// Terminating a contract from a previous transaction doesn't remove it.
 		assert_ok!(builder::call(addr).build());
+		assert_eq!(get_contract(&addr).trie_id, trie_id);
 
-		// Re-Instantiate after termination.
-		assert_ok!(builder::instantiate(code_hash).build());
+		// Create and delete the same contract twice within a transaction each.
+		let factory = deploy_self_destruct_factory();
+		let (addr0, trie0) = create_and_self_destruct(&factory, &wasm, code_hash, [0; 32], 0);
+		let (addr1, trie1) = create_and_self_destruct(&factory, &wasm, code_hash, [0; 32], 0);
+		assert_eq!(addr0, addr1);
 
 		// Trie ids shouldn't match or we might have a collision
-		assert_ne!(trie_id, get_contract(&addr).trie_id);
+		assert_ne!(trie0, trie1);
 	});
 }
 
@@ -920,6 +931,235 @@ fn storage_work() {
 	});
 }
 
+#[test]
+fn account_proof_keys_works() {
+	let (code, code_hash) = compile_module("dummy").unwrap();
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+		let contract = get_contract(&addr);
+
+		let keys = Contracts::account_proof_keys(addr, vec![[1u8; 32]]);
+		let account_id = <Test as Config>::AddressMapper::to_account_id(&addr);
+		assert_eq!(
+			keys.account_keys,
+			vec![
+				OriginalAccount::<Test>::hashed_key_for(&addr),
+				frame_system::Account::<Test>::hashed_key_for(&account_id),
+				ContractInfoOf::<Test>::hashed_key_for(&addr),
+			]
+		);
+		// The contract account is not mapped, which the proof of the first key shows.
+		assert!(!frame_support::storage::unhashed::exists(&keys.account_keys[0]));
+		assert!(keys.account_keys[1..]
+			.iter()
+			.all(|key| frame_support::storage::unhashed::exists(key)));
+		assert_eq!(
+			keys.child_trie_key,
+			Some(contract.child_trie_info().prefixed_storage_key().into_inner())
+		);
+		assert_eq!(keys.code_hash, Some(code_hash));
+		assert_eq!(keys.storage_keys, vec![Key::from_fixed([1u8; 32]).hash()]);
+
+		// Accounts without a contract have no child trie.
+		let keys = Contracts::account_proof_keys(ALICE_ADDR, vec![]);
+		assert_eq!(keys.child_trie_key, None);
+		assert_eq!(keys.code_hash, None);
+		assert!(keys.storage_keys.is_empty());
+	});
+}
+
+#[test]
+fn eth_call_rejects_too_large_access_list() {
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let access_list = vec![AccessListEntry {
+			address: BOB_ADDR,
+			storage_keys: vec![H256::zero(); limits::ACCESS_LIST_ITEMS as usize],
+		}];
+
+		assert_noop!(
+			Contracts::eth_call(
+				RuntimeOrigin::signed(ALICE),
+				BOB_ADDR,
+				0,
+				GAS_LIMIT,
+				deposit_limit::<Test>(),
+				vec![],
+				access_list,
+			),
+			Error::<Test>::AccessListTooLarge,
+		);
+	});
+}
+
+#[test]
+fn delegated_account_executes_contract_code() {
+	let (code, _) = compile_module("store_call").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let account = crate::evm::Account::default();
+		let authority = account.address();
+		let _ = <Test as Config>::Currency::set_balance(&account.substrate_account(), 1_000_000);
+		let chain_id = <Test as Config>::ChainId::get().into();
+		Contracts::apply_authorizations(&[
+			// Signed with the wrong nonce, hence skipped.
+			account.sign_authorization(chain_id, H160::repeat_byte(0x42), 1.into()),
+			account.sign_authorization(chain_id, addr, 0.into()),
+		]);
+		assert_eq!(DelegationOf::<Test>::get(&authority), Some(addr));
+		let delegation_deposit = test_utils::get_balance_on_hold(
+			&HoldReason::Delegation.into(),
+			&account.substrate_account(),
+		);
+		assert_eq!(delegation_deposit, DepositPerByte::get() * 40 + DepositPerItem::get());
+
+		// Calling the delegated account runs the contract code with the storage of the account.
+		builder::bare_call(authority).data(16u32.encode()).build_and_unwrap_result();
+		let mut key = [0u8; 32];
+		key[0] = 1;
+		let key = Key::try_from_var(key.to_vec()).unwrap();
+		assert_eq!(get_contract(&authority).code_hash, EMPTY_CODE_HASH);
+		assert_eq!(get_contract(&authority).read(&key), Some(vec![0u8; 16]));
+		assert_eq!(get_contract(&addr).read(&key), None);
+
+		// Clearing the delegation turns the account back into a plain account.
+		let trie_id = get_contract(&authority).trie_id.clone();
+		Contracts::apply_authorizations(&[account.sign_authorization(
+			0.into(),
+			H160::zero(),
+			1.into(),
+		)]);
+		assert_eq!(DelegationOf::<Test>::get(&authority), None);
+		assert_eq!(System::account_nonce(&account.substrate_account()), 2);
+		assert_eq!(
+			test_utils::get_balance_on_hold(
+				&HoldReason::Delegation.into(),
+				&account.substrate_account(),
+			),
+			0,
+		);
+
+		// The storage of the account is removed along with the delegation.
+		assert!(ContractInfoOf::<Test>::get(&authority).is_none());
+		assert_eq!(DeletionQueue::<Test>::iter_values().collect::<Vec<_>>(), vec![trie_id]);
+		let result = builder::bare_call(authority).data(16u32.encode()).build_and_unwrap_result();
+		assert!(!result.did_revert());
+		assert!(ContractInfoOf::<Test>::get(&authority).is_none());
+	});
+}
+
+#[test]
+fn invalid_authorizations_are_skipped() {
+	let (code, _) = compile_module("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let account = crate::evm::Account::default();
+		let authority = account.address();
+		let chain_id: U256 = <Test as Config>::ChainId::get().into();
+		Contracts::apply_authorizations(&[
+			// Signed by an account that can't pay the deposit of the delegation.
+			account.sign_authorization(chain_id, addr, 0.into()),
+		]);
+		assert_eq!(DelegationOf::<Test>::get(&authority), None);
+		assert_eq!(System::account_nonce(&account.substrate_account()), 0);
+
+		let _ = <Test as Config>::Currency::set_balance(&account.substrate_account(), 1_000_000);
+		Contracts::apply_authorizations(&[
+			// Signed for another chain.
+			account.sign_authorization(chain_id + 1, addr, 0.into()),
+			// Signed with a nonce ahead of the account.
+			account.sign_authorization(chain_id, addr, 1.into()),
+		]);
+		assert_eq!(DelegationOf::<Test>::get(&authority), None);
+		assert_eq!(System::account_nonce(&account.substrate_account()), 0);
+
+		// Signed with a nonce behind the account, after a valid authorization.
+		Contracts::apply_authorizations(&[
+			account.sign_authorization(chain_id, addr, 0.into()),
+			account.sign_authorization(chain_id, H160::zero(), 0.into()),
+		]);
+		assert_eq!(DelegationOf::<Test>::get(&authority), Some(addr));
+		assert_eq!(System::account_nonce(&account.substrate_account()), 1);
+	});
+}
+
+#[test]
+fn eth_transact_dry_run_applies_authorizations_after_the_nonce_bump() {
+	let (code, _) = compile_module("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let account = crate::evm::Account::default();
+		let authority = account.address();
+		let _ = <Test as Config>::Currency::set_balance(&account.substrate_account(), 1_000_000);
+		let chain_id = <Test as Config>::ChainId::get().into();
+
+		// The sender authorizes the delegation itself, with the nonce following the one of the
+		// transaction.
+		assert_ok!(Pallet::<Test>::bare_eth_transact(
+			GenericTransaction {
+				from: Some(authority),
+				to: Some(authority),
+				authorization_list: vec![account.sign_authorization(chain_id, addr, 1.into())],
+				..Default::default()
+			},
+			Weight::MAX,
+			|_, _| 0u64,
+		));
+		assert_eq!(DelegationOf::<Test>::get(&authority), Some(addr));
+		assert_eq!(System::account_nonce(&account.substrate_account()), 2);
+	});
+}
+
+#[test]
+fn authorizations_persist_when_the_call_reverts() {
+	let (code, _) = compile_module("ok_trap_revert").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let account = crate::evm::Account::default();
+		let authority = account.address();
+		let _ = <Test as Config>::Currency::set_balance(&account.substrate_account(), 1_000_000);
+		let chain_id = <Test as Config>::ChainId::get().into();
+
+		let call = RuntimeCall::Contracts(crate::Call::eth_call_with_authorizations {
+			dest: addr,
+			value: 0,
+			gas_limit: GAS_LIMIT,
+			storage_deposit_limit: deposit_limit::<Test>(),
+			// Makes the contract revert.
+			data: 1u32.encode(),
+			access_list: vec![],
+			authorization_list: vec![account.sign_authorization(chain_id, addr, 0.into())],
+		});
+		let info = call.get_dispatch_info();
+		let result = ApplyAuthorizations::<Test>::new()
+			.dispatch_transaction(RuntimeOrigin::signed(ALICE), call, &info, 0, 0)
+			.unwrap();
+
+		assert_err_ignore_postinfo!(result, <Error<Test>>::ContractReverted);
+		assert_eq!(DelegationOf::<Test>::get(&authority), Some(addr));
+		assert_eq!(System::account_nonce(&account.substrate_account()), 1);
+	});
+}
+
 #[test]
 fn storage_max_value_limit() {
 	let (wasm, _code_hash) = compile_module("storage_size").unwrap();
@@ -1294,11 +1534,37 @@ fn cannot_self_destruct_through_storage_refund_after_price_change() {
 	});
 }
 
+/// Deploys the `create_and_self_destruct` fixture.
+///
+/// The code of the `self_destruct` fixture needs to be uploaded before it can be used.
+fn deploy_self_destruct_factory() -> H160 {
+	let (wasm, _) = compile_module("create_and_self_destruct").unwrap();
+	builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_contract().addr
+}
+
+/// Makes the `factory` instantiate `code` and terminate it within the same transaction.
+///
+/// Returns the address of the deleted contract and its child trie queued for deletion.
+fn create_and_self_destruct(
+	factory: &H160,
+	code: &[u8],
+	code_hash: H256,
+	salt: [u8; 32],
+	value: BalanceOf<Test>,
+) -> (H160, child::ChildInfo) {
+	assert_ok!(builder::call(*factory).value(value).data((code_hash, salt).encode()).build());
+
+	let (insert_counter, _) = <DeletionQueueCounter<Test>>::get().as_test_tuple();
+	let trie_id = <DeletionQueue<Test>>::get(insert_counter.wrapping_sub(1)).unwrap();
+	(create2(factory, code, &[], &salt), child::ChildInfo::new_default(&trie_id))
+}
+
 #[test]
-fn cannot_self_destruct_while_live() {
+fn self_destruct_while_live_keeps_contract() {
 	let (wasm, _code_hash) = compile_module("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
 		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let min_balance = Contracts::min_balance();
 
 		// Instantiate the BOB contract.
 		let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(wasm))
@@ -1309,14 +1575,16 @@ fn cannot_self_destruct_while_live() {
 		get_contract(&addr);
 
 		// Call BOB with input data, forcing it make a recursive call to itself to
-		// self-destruct, resulting in a trap.
-		assert_err_ignore_postinfo!(
-			builder::call(addr).data(vec![0]).build(),
-			Error::<Test>::ContractTrapped,
-		);
+		// self-destruct. BOB was not created within this transaction. Hence it only sends
+		// away its balance, which is fine while it is on the call stack.
+		assert_ok!(builder::call(addr).data(vec![0]).build());
 
 		// Check that BOB is still there.
 		get_contract(&addr);
+		assert_eq!(
+			<Test as Config>::Currency::total_balance(&DJANGO_FALLBACK),
+			100_000 + min_balance
+		);
 	});
 }
 
@@ -1327,39 +1595,38 @@ fn self_destruct_works() {
 		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
 		let _ = <Test as Config>::Currency::set_balance(&DJANGO_FALLBACK, 1_000_000);
 		let min_balance = Contracts::min_balance();
+		Contracts::upload_code(RuntimeOrigin::signed(ALICE), wasm.clone(), deposit_limit::<Test>())
+			.unwrap();
+		let factory = deploy_self_destruct_factory();
 
-		// Instantiate the BOB contract.
-		let contract = builder::bare_instantiate(Code::Upload(wasm))
-			.value(100_000)
-			.build_and_unwrap_contract();
+		// Create BOB and make it terminate within the same transaction.
+		let (addr, _) = create_and_self_destruct(&factory, &wasm, code_hash, [0; 32], 100_000);
+		let account_id = <Test as Config>::AddressMapper::to_account_id(&addr);
 
-		// Check that the BOB contract has been instantiated.
-		let _ = get_contract(&contract.addr);
+		// Check that code is still there but refcount dropped to zero.
+		assert_refcount!(&code_hash, 0);
 
-		// Drop all previous events
-		initialize_block(2);
+		// Check that the contract is gone.
+		assert!(get_contract_checked(&addr).is_none());
 
-		// Call BOB without input data which triggers termination.
-		assert_matches!(builder::call(contract.addr).build(), Ok(_));
+		// Check that the beneficiary (django) got the remaining balance.
+		assert_eq!(<Test as Config>::Currency::free_balance(DJANGO_FALLBACK), 1_000_000 + 100_000);
 
-		// Check that code is still there but refcount dropped to zero.
-		assert_refcount!(&code_hash, 0);
+		// The storage deposit is held until the lazy removal of the storage.
+		let deposit =
+			test_utils::get_balance_on_hold(&HoldReason::StorageDepositReserve.into(), &account_id);
+		assert!(deposit > 0);
+		assert_eq!(<Test as Config>::Currency::total_balance(&account_id), deposit + min_balance);
 
-		// Check that account is gone
-		assert!(get_contract_checked(&contract.addr).is_none());
-		assert_eq!(<Test as Config>::Currency::total_balance(&contract.account_id), 0);
+		// Drop all previous events
+		initialize_block(2);
 
-		// Check that the beneficiary (django) got remaining balance.
+		// Run the lazy removal which refunds the deposit and removes the account.
+		Contracts::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(<Test as Config>::Currency::total_balance(&account_id), 0);
 		assert_eq!(
 			<Test as Config>::Currency::free_balance(DJANGO_FALLBACK),
-			1_000_000 + 100_000 + min_balance
-		);
-
-		// Check that the Alice is missing Django's benefit. Within ALICE's total balance
-		// there's also the code upload deposit held.
-		assert_eq!(
-			<Test as Config>::Currency::total_balance(&ALICE),
-			1_000_000 - (100_000 + min_balance)
+			1_000_000 + 100_000 + deposit + min_balance
 		);
 
 		pretty_assertions::assert_eq!(
@@ -1368,16 +1635,16 @@ fn self_destruct_works() {
 				EventRecord {
 					phase: Phase::Initialization,
 					event: RuntimeEvent::System(frame_system::Event::KilledAccount {
-						account: contract.account_id.clone()
+						account: account_id.clone()
 					}),
 					topics: vec![],
 				},
 				EventRecord {
 					phase: Phase::Initialization,
 					event: RuntimeEvent::Balances(pallet_balances::Event::Transfer {
-						from: contract.account_id.clone(),
+						from: account_id.clone(),
 						to: DJANGO_FALLBACK,
-						amount: 100_000 + min_balance,
+						amount: deposit + min_balance,
 					}),
 					topics: vec![],
 				},
@@ -1386,6 +1653,35 @@ fn self_destruct_works() {
 	});
 }
 
+#[test]
+fn self_destruct_of_existing_contract_only_transfers_balance() {
+	let (wasm, code_hash) = compile_module("self_destruct").unwrap();
+	ExtBuilder::default().existential_deposit(1_000).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let _ = <Test as Config>::Currency::set_balance(&DJANGO_FALLBACK, 1_000_000);
+		let min_balance = Contracts::min_balance();
+
+		// Instantiate the BOB contract.
+		let contract = builder::bare_instantiate(Code::Upload(wasm))
+			.value(100_000)
+			.build_and_unwrap_contract();
+
+		// Call BOB without input data which triggers termination.
+		assert_ok!(builder::call(contract.addr).build());
+
+		// BOB was created by a previous transaction and is hence kept.
+		get_contract(&contract.addr);
+		assert_refcount!(&code_hash, 1);
+		assert_eq!(
+			<Test as Config>::Currency::total_balance(&contract.account_id),
+			get_contract(&contract.addr).total_deposit() + min_balance
+		);
+
+		// Check that the beneficiary (django) got the balance.
+		assert_eq!(<Test as Config>::Currency::free_balance(DJANGO_FALLBACK), 1_000_000 + 100_000);
+	});
+}
+
 // This tests that one contract cannot prevent another from self-destructing by sending it
 // additional funds after it has been drained.
 #[test]
@@ -1418,22 +1714,33 @@ fn destroy_contract_and_transfer_funds() {
 		// Call BOB, which calls CHARLIE, forcing CHARLIE to self-destruct.
 		assert_ok!(builder::call(addr_bob).data(addr_charlie.encode()).build());
 
-		// Check that CHARLIE has moved on to the great beyond (ie. died).
-		assert!(get_contract_checked(&addr_charlie).is_none());
+		// CHARLIE was created by a previous transaction. It is drained but stays alive.
+		let account_charlie = <Test as Config>::AddressMapper::to_account_id(&addr_charlie);
+		get_contract(&addr_charlie);
+		assert_eq!(
+			<Test as Config>::Currency::total_balance(&account_charlie),
+			get_contract(&addr_charlie).total_deposit() + Contracts::min_balance()
+		);
 	});
 }
 
 #[test]
-fn cannot_self_destruct_in_constructor() {
+fn self_destruct_in_constructor_works() {
 	let (wasm, _) = compile_module("self_destructing_constructor").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
 		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let min_balance = Contracts::min_balance();
+		let beneficiary = <Test as Config>::AddressMapper::to_account_id(&H160::zero());
 
-		// Fail to instantiate the BOB because the constructor calls seal_terminate.
-		assert_err_ignore_postinfo!(
-			builder::instantiate_with_code(wasm).value(100_000).build(),
-			Error::<Test>::TerminatedInConstructor,
-		);
+		// The constructor calls seal_terminate which deletes BOB right away.
+		let Contract { addr, account_id } = builder::bare_instantiate(Code::Upload(wasm))
+			.value(100_000)
+			.build_and_unwrap_contract();
+		assert!(get_contract_checked(&addr).is_none());
+
+		// No storage deposit was charged. Hence the account is removed immediately.
+		assert_eq!(<Test as Config>::Currency::total_balance(&account_id), 0);
+		assert_eq!(<Test as Config>::Currency::total_balance(&beneficiary), 100_000 + min_balance);
 	});
 }
 
@@ -1747,28 +2054,25 @@ fn chain_extension_temp_storage_works() {
 
 #[test]
 fn lazy_removal_works() {
-	let (code, _hash) = compile_module("self_destruct").unwrap();
+	let (code, hash) = compile_module("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
 		let min_balance = Contracts::min_balance();
 		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1000 * min_balance);
+		Contracts::upload_code(RuntimeOrigin::signed(ALICE), code.clone(), deposit_limit::<Test>())
+			.unwrap();
+		let factory = deploy_self_destruct_factory();
 
-		let contract = builder::bare_instantiate(Code::Upload(code))
-			.value(min_balance * 100)
-			.build_and_unwrap_contract();
-
-		let info = get_contract(&contract.addr);
-		let trie = &info.child_trie_info();
-
-		// Put value into the contracts child trie
-		child::put(trie, &[99], &42);
-
-		// Terminate the contract
-		assert_ok!(builder::call(contract.addr).build());
+		// Create and terminate the contract
+		let (addr, trie) =
+			create_and_self_destruct(&factory, &code, hash, [0; 32], min_balance * 100);
+		let trie = &trie;
 
 		// Contract info should be gone
-		assert!(!<ContractInfoOf::<Test>>::contains_key(&contract.addr));
+		assert!(!<ContractInfoOf::<Test>>::contains_key(&addr));
 
-		// But value should be still there as the lazy removal did not run, yet.
+		// Put value into the contracts child trie. It stays there as long as the lazy removal
+		// did not run.
+		child::put(trie, &[99], &42);
 		assert_matches!(child::get(trie, &[99]), Some(42));
 
 		// Run the lazy removal
@@ -1781,32 +2085,26 @@ fn lazy_removal_works() {
 
 #[test]
 fn lazy_batch_removal_works() {
-	let (code, _hash) = compile_module("self_destruct").unwrap();
+	let (code, hash) = compile_module("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
 		let min_balance = Contracts::min_balance();
 		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1000 * min_balance);
+		Contracts::upload_code(RuntimeOrigin::signed(ALICE), code.clone(), deposit_limit::<Test>())
+			.unwrap();
+		let factory = deploy_self_destruct_factory();
 		let mut tries: Vec<child::ChildInfo> = vec![];
 
 		for i in 0..3u8 {
-			let contract = builder::bare_instantiate(Code::Upload(code.clone()))
-				.value(min_balance * 100)
-				.salt(Some([i; 32]))
-				.build_and_unwrap_contract();
-
-			let info = get_contract(&contract.addr);
-			let trie = &info.child_trie_info();
+			// Create and terminate the contract. Contract info should be gone, but a value put
+			// into its child trie stays there as the lazy removal did not run, yet.
+			let (addr, trie) =
+				create_and_self_destruct(&factory, &code, hash, [i; 32], min_balance * 100);
+			child::put(&trie, &[99], &42);
 
-			// Put value into the contracts child trie
-			child::put(trie, &[99], &42);
-
-			// Terminate the contract. Contract info should be gone, but value should be still
-			// there as the lazy removal did not run, yet.
-			assert_ok!(builder::call(contract.addr).build());
-
-			assert!(!<ContractInfoOf::<Test>>::contains_key(&contract.addr));
-			assert_matches!(child::get(trie, &[99]), Some(42));
+			assert!(!<ContractInfoOf::<Test>>::contains_key(&addr));
+			assert_matches!(child::get(&trie, &[99]), Some(42));
 
-			tries.push(trie.clone())
+			tries.push(trie)
 		}
 
 		// Run single lazy removal
@@ -1842,7 +2140,7 @@ fn ref_time_left_api_works() {
 
 #[test]
 fn lazy_removal_partial_remove_works() {
-	let (code, _hash) = compile_module("self_destruct").unwrap();
+	let (code, hash) = compile_module("self_destruct").unwrap();
 
 	// We create a contract with some extra keys above the weight limit
 	let extra_keys = 7u32;
@@ -1858,32 +2156,24 @@ fn lazy_removal_partial_remove_works() {
 		let min_balance = Contracts::min_balance();
 		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1000 * min_balance);
 
-		let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(code))
-			.value(min_balance * 100)
-			.build_and_unwrap_contract();
-
-		let info = get_contract(&addr);
-
-		// Put value into the contracts child trie
-		for val in &vals {
-			info.write(&Key::Fix(val.0), Some(val.2.clone()), None, false).unwrap();
-		}
-		<ContractInfoOf<Test>>::insert(&addr, info.clone());
+		Contracts::upload_code(RuntimeOrigin::signed(ALICE), code.clone(), deposit_limit::<Test>())
+			.unwrap();
+		let factory = deploy_self_destruct_factory();
 
-		// Terminate the contract
-		assert_ok!(builder::call(addr).build());
+		// Create and terminate the contract
+		let (addr, trie) =
+			create_and_self_destruct(&factory, &code, hash, [0; 32], min_balance * 100);
 
 		// Contract info should be gone
 		assert!(!<ContractInfoOf::<Test>>::contains_key(&addr));
 
-		let trie = info.child_trie_info();
-
-		// But value should be still there as the lazy removal did not run, yet.
+		// Put values into the contracts child trie. They stay there as long as the lazy
+		// removal did not run.
 		for val in &vals {
-			assert_eq!(child::get::<u32>(&trie, &blake2_256(&val.0)), Some(val.1));
+			child::put(&trie, &blake2_256(&val.0), &val.1);
 		}
 
-		trie.clone()
+		trie
 	});
 
 	// The lazy removal limit only applies to the backend but not to the overlay.
@@ -1917,28 +2207,25 @@ fn lazy_removal_partial_remove_works() {
 
 #[test]
 fn lazy_removal_does_no_run_on_low_remaining_weight() {
-	let (code, _hash) = compile_module("self_destruct").unwrap();
+	let (code, hash) = compile_module("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
 		let min_balance = Contracts::min_balance();
 		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1000 * min_balance);
+		Contracts::upload_code(RuntimeOrigin::signed(ALICE), code.clone(), deposit_limit::<Test>())
+			.unwrap();
+		let factory = deploy_self_destruct_factory();
 
-		let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(code))
-			.value(min_balance * 100)
-			.build_and_unwrap_contract();
-
-		let info = get_contract(&addr);
-		let trie = &info.child_trie_info();
-
-		// Put value into the contracts child trie
-		child::put(trie, &[99], &42);
-
-		// Terminate the contract
-		assert_ok!(builder::call(addr).build());
+		// Create and terminate the contract
+		let (addr, trie) =
+			create_and_self_destruct(&factory, &code, hash, [0; 32], min_balance * 100);
+		let trie = &trie;
 
 		// Contract info should be gone
 		assert!(!<ContractInfoOf::<Test>>::contains_key(&addr));
 
-		// But value should be still there as the lazy removal did not run, yet.
+		// Put value into the contracts child trie. It stays there as long as the lazy removal
+		// did not run.
+		child::put(trie, &[99], &42);
 		assert_matches!(child::get(trie, &[99]), Some(42));
 
 		// Assign a remaining weight which is too low for a successful deletion of the contract
@@ -1967,48 +2254,42 @@ fn lazy_removal_does_no_run_on_low_remaining_weight() {
 
 #[test]
 fn lazy_removal_does_not_use_all_weight() {
-	let (code, _hash) = compile_module("self_destruct").unwrap();
+	let (code, hash) = compile_module("self_destruct").unwrap();
 
 	let mut meter = WeightMeter::with_limit(Weight::from_parts(5_000_000_000, 100 * 1024));
 	let mut ext = ExtBuilder::default().existential_deposit(50).build();
 
-	let (trie, vals, weight_per_key) = ext.execute_with(|| {
+	let (trie, vals, weight_per_key, refund_keys) = ext.execute_with(|| {
 		let min_balance = Contracts::min_balance();
 		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1000 * min_balance);
+		Contracts::upload_code(RuntimeOrigin::signed(ALICE), code.clone(), deposit_limit::<Test>())
+			.unwrap();
+		let factory = deploy_self_destruct_factory();
 
-		let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(code))
-			.value(min_balance * 100)
-			.build_and_unwrap_contract();
-
-		let info = get_contract(&addr);
 		let (weight_per_key, max_keys) = ContractInfo::<Test>::deletion_budget(&meter);
-		assert!(max_keys > 0);
+		let refund_keys = DeletionDeposit::<Test>::refund_keys(weight_per_key);
+		assert!(max_keys > refund_keys + 1);
 
 		// We create a contract with one less storage item than we can remove within the limit
-		let vals: Vec<_> = (0..max_keys - 1)
+		// after refunding its deposit.
+		let vals: Vec<_> = (0..max_keys - refund_keys - 1)
 			.map(|i| (blake2_256(&i.encode()), (i as u32), (i as u32).encode()))
 			.collect();
 
-		// Put value into the contracts child trie
-		for val in &vals {
-			info.write(&Key::Fix(val.0), Some(val.2.clone()), None, false).unwrap();
-		}
-		<ContractInfoOf<Test>>::insert(&addr, info.clone());
-
-		// Terminate the contract
-		assert_ok!(builder::call(addr).build());
+		// Create and terminate the contract
+		let (addr, trie) =
+			create_and_self_destruct(&factory, &code, hash, [0; 32], min_balance * 100);
 
 		// Contract info should be gone
 		assert!(!<ContractInfoOf::<Test>>::contains_key(&addr));
 
-		let trie = info.child_trie_info();
-
-		// But value should be still there as the lazy removal did not run, yet.
+		// Put values into the contracts child trie. They stay there as long as the lazy
+		// removal did not run.
 		for val in &vals {
-			assert_eq!(child::get::<u32>(&trie, &blake2_256(&val.0)), Some(val.1));
+			child::put(&trie, &blake2_256(&val.0), &val.1);
 		}
 
-		(trie, vals, weight_per_key)
+		(trie, vals, weight_per_key, refund_keys)
 	});
 
 	// The lazy removal limit only applies to the backend but not to the overlay.
@@ -2020,18 +2301,23 @@ fn lazy_removal_does_not_use_all_weight() {
 		ContractInfo::<Test>::process_deletion_queue_batch(&mut meter);
 		let base_weight =
 			<<Test as Config>::WeightInfo as WeightInfo>::on_process_deletion_queue_batch();
-		assert_eq!(meter.consumed(), weight_per_key.mul(vals.len() as _) + base_weight);
+		assert_eq!(
+			meter.consumed(),
+			weight_per_key.mul((vals.len() as u32 + refund_keys) as _) + base_weight
+		);
 
-		// All the keys are removed
+		// All the keys are removed and the deposit is refunded
 		for val in vals {
 			assert_eq!(child::get::<u32>(&trie, &blake2_256(&val.0)), None);
 		}
+		let (insert_counter, delete_counter) = <DeletionQueueCounter<Test>>::get().as_test_tuple();
+		assert_eq!(insert_counter, delete_counter);
 	});
 }
 
 #[test]
 fn deletion_queue_ring_buffer_overflow() {
-	let (code, _hash) = compile_module("self_destruct").unwrap();
+	let (code, hash) = compile_module("self_destruct").unwrap();
 	let mut ext = ExtBuilder::default().existential_deposit(50).build();
 
 	// setup the deletion queue with custom counters
@@ -2046,29 +2332,23 @@ fn deletion_queue_ring_buffer_overflow() {
 	ext.execute_with(|| {
 		let min_balance = Contracts::min_balance();
 		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1000 * min_balance);
+		Contracts::upload_code(RuntimeOrigin::signed(ALICE), code.clone(), deposit_limit::<Test>())
+			.unwrap();
+		let factory = deploy_self_destruct_factory();
 		let mut tries: Vec<child::ChildInfo> = vec![];
 
 		// add 3 contracts to the deletion queue
 		for i in 0..3u8 {
-			let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(code.clone()))
-				.value(min_balance * 100)
-				.salt(Some([i; 32]))
-				.build_and_unwrap_contract();
-
-			let info = get_contract(&addr);
-			let trie = &info.child_trie_info();
-
-			// Put value into the contracts child trie
-			child::put(trie, &[99], &42);
-
-			// Terminate the contract. Contract info should be gone, but value should be still
-			// there as the lazy removal did not run, yet.
-			assert_ok!(builder::call(addr).build());
+			// Create and terminate the contract. Contract info should be gone, but a value put
+			// into its child trie stays there as the lazy removal did not run, yet.
+			let (addr, trie) =
+				create_and_self_destruct(&factory, &code, hash, [i; 32], min_balance * 100);
+			child::put(&trie, &[99], &42);
 
 			assert!(!<ContractInfoOf::<Test>>::contains_key(&addr));
-			assert_matches!(child::get(trie, &[99]), Some(42));
+			assert_matches!(child::get(&trie, &[99]), Some(42));
 
-			tries.push(trie.clone())
+			tries.push(trie)
 		}
 
 		// Run single lazy removal
@@ -2108,23 +2388,19 @@ fn refcounter() {
 			.build_and_unwrap_contract();
 		assert_refcount!(code_hash, 3);
 
-		// Terminating one contract should decrement the refcount
-		assert_ok!(builder::call(addr0).build());
-		assert_refcount!(code_hash, 2);
+		// Terminating contracts of previous transactions doesn't remove them
+		for addr in [addr0, addr1, addr2] {
+			assert_ok!(builder::call(addr).build());
+		}
+		assert_refcount!(code_hash, 3);
 
-		// remove another one
-		assert_ok!(builder::call(addr1).build());
-		assert_refcount!(code_hash, 1);
+		// A contract created and deleted within the same transaction gives its reference back
+		let factory = deploy_self_destruct_factory();
+		create_and_self_destruct(&factory, &wasm, code_hash, [3; 32], min_balance * 100);
+		assert_refcount!(code_hash, 3);
 
 		// Pristine code should still be there
 		PristineCode::<Test>::get(code_hash).unwrap();
-
-		// remove the last contract
-		assert_ok!(builder::call(addr2).build());
-		assert_refcount!(code_hash, 0);
-
-		// refcount is `0` but code should still exists because it needs to be removed manually
-		assert!(crate::PristineCode::<Test>::contains_key(&code_hash));
 	});
 }
 
@@ -4217,20 +4493,25 @@ fn mapped_address_works() {
 		<Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
 
 		// without a mapping everything will be send to the fallback account
-		let Contract { addr, .. } =
-			builder::bare_instantiate(Code::Upload(code.clone())).build_and_unwrap_contract();
+		let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(code.clone()))
+			.value(100)
+			.build_and_unwrap_contract();
 		assert_eq!(<Test as Config>::Currency::total_balance(&EVE_FALLBACK), 0);
 		builder::bare_call(addr).data(EVE_ADDR.encode()).build_and_unwrap_result();
-		assert_eq!(<Test as Config>::Currency::total_balance(&EVE_FALLBACK), 100);
+		// the existential deposit of the new account is paid by the origin
+		assert_eq!(<Test as Config>::Currency::total_balance(&EVE_FALLBACK), 200);
 
-		// after mapping it will be sent to the real eve account
-		let Contract { addr, .. } =
-			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+		// after mapping it will be sent to the real eve account. The first contract was not
+		// removed by its termination, hence we need a different salt.
+		let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(code))
+			.value(100)
+			.salt(Some([1; 32]))
+			.build_and_unwrap_contract();
 		// need some balance to pay for the map deposit
 		<Test as Config>::Currency::set_balance(&EVE, 1_000);
 		<Pallet<Test>>::map_account(RuntimeOrigin::signed(EVE)).unwrap();
 		builder::bare_call(addr).data(EVE_ADDR.encode()).build_and_unwrap_result();
-		assert_eq!(<Test as Config>::Currency::total_balance(&EVE_FALLBACK), 100);
+		assert_eq!(<Test as Config>::Currency::total_balance(&EVE_FALLBACK), 200);
 		assert_eq!(<Test as Config>::Currency::total_balance(&EVE), 1_100);
 	});
 }
@@ -4244,12 +4525,13 @@ fn recovery_works() {
 
 		// eve puts her AccountId20 as argument to terminate but forgot to register
 		// her AccountId32 first so now the funds are trapped in her fallback account
-		let Contract { addr, .. } =
-			builder::bare_instantiate(Code::Upload(code.clone())).build_and_unwrap_contract();
+		let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(code.clone()))
+			.value(100)
+			.build_and_unwrap_contract();
 		assert_eq!(<Test as Config>::Currency::total_balance(&EVE), 0);
 		assert_eq!(<Test as Config>::Currency::total_balance(&EVE_FALLBACK), 0);
 		builder::bare_call(addr).data(EVE_ADDR.encode()).build_and_unwrap_result();
-		assert_eq!(<Test as Config>::Currency::total_balance(&EVE_FALLBACK), 100);
+		assert_eq!(<Test as Config>::Currency::total_balance(&EVE_FALLBACK), 200);
 		assert_eq!(<Test as Config>::Currency::total_balance(&EVE), 0);
 
 		let call = RuntimeCall::Balances(pallet_balances::Call::transfer_all {
@@ -4262,7 +4544,7 @@ fn recovery_works() {
 		<Pallet<Test>>::dispatch_as_fallback_account(RuntimeOrigin::signed(EVE), Box::new(call))
 			.unwrap();
 		assert_eq!(<Test as Config>::Currency::total_balance(&EVE_FALLBACK), 0);
-		assert_eq!(<Test as Config>::Currency::total_balance(&EVE), 100);
+		assert_eq!(<Test as Config>::Currency::total_balance(&EVE), 200);
 	});
 }
 
@@ -4361,6 +4643,85 @@ fn skip_transfer_works() {
 	});
 }
 
+#[test]
+fn gas_schedule_pins_eth_gas() {
+	let (code, _) = compile_module("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		<Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code.clone())).build_and_unwrap_contract();
+
+		let estimate = |to, input: Vec<u8>| {
+			Pallet::<Test>::bare_eth_transact(
+				GenericTransaction {
+					from: Some(ALICE_ADDR),
+					to,
+					input: input.into(),
+					..Default::default()
+				},
+				Weight::MAX,
+				|_, _| 0u64,
+			)
+			.unwrap()
+			.eth_gas
+		};
+		let dynamic_call = estimate(Some(addr), vec![1, 2, 3, 4]);
+		assert_eq!(Pallet::<Test>::evm_gas_schedule(), None);
+
+		let cost = |gas| ScheduledCost {
+			gas,
+			weight_limit: Weight::from_parts(1_000_000_000_000, 10 * 1024 * 1024),
+			deposit_limit: 1_000_000,
+		};
+		let schedule = GasSchedule {
+			version: 1,
+			transfer: cost(21_000_000),
+			call: cost(50_000_000),
+			create: cost(100_000_000),
+			input_byte: 1_000,
+			code_byte: 10,
+		};
+		EthGasSchedule::set(Some(schedule.clone()));
+
+		let expected = |operation, input_len, code_len| {
+			let cost = schedule.cost(operation);
+			<<Test as Config>::EthGasEncoder as GasEncoder<_>>::encode(
+				schedule.gas(operation, input_len, code_len),
+				cost.weight_limit,
+				cost.deposit_limit,
+			)
+		};
+
+		// Operations within the limits of the schedule are estimated from the schedule.
+		assert_eq!(estimate(Some(BOB_ADDR), vec![]), expected(ScheduledOperation::Transfer, 0, 0));
+		assert_eq!(
+			estimate(Some(addr), vec![1, 2, 3, 4]),
+			expected(ScheduledOperation::Call, 4, 0)
+		);
+		assert_eq!(
+			estimate(None, code.clone()),
+			expected(ScheduledOperation::Create, 0, code.len() as u32)
+		);
+
+		// The schedule is exposed encoded as EVM gas.
+		let result = Pallet::<Test>::evm_gas_schedule().unwrap();
+		assert_eq!(result.version, 1);
+		assert_eq!(result.transfer, expected(ScheduledOperation::Transfer, 0, 0));
+		assert_eq!(result.call, expected(ScheduledOperation::Call, 0, 0));
+		assert_eq!(result.create, expected(ScheduledOperation::Create, 0, 0));
+		assert_eq!(result.input_byte, U256::from(1_000));
+		assert_eq!(result.code_byte, U256::from(10));
+
+		// Operations exceeding the limits of the schedule are estimated from their dry run.
+		EthGasSchedule::set(Some(GasSchedule {
+			call: ScheduledCost { weight_limit: Weight::zero(), ..schedule.call.clone() },
+			..schedule.clone()
+		}));
+		assert_eq!(estimate(Some(addr), vec![1, 2, 3, 4]), dynamic_call);
+	});
+}
+
 #[test]
 fn gas_limit_api_works() {
 	let (code, _) = compile_module("gas_limit").unwrap();
@@ -4578,6 +4939,117 @@ fn tracing_works() {
 	});
 }
 
+#[test]
+fn prestate_tracing_works() {
+	use crate::evm::*;
+	use alloc::collections::BTreeMap;
+	let (code, _code_hash) = compile_module("storage_size").unwrap();
+	ExtBuilder::default().existential_deposit(200).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000);
+		let Contract { addr, account_id } =
+			builder::bare_instantiate(Code::Upload(code.clone())).build_and_unwrap_contract();
+
+		let mut key = [0u8; 32];
+		key[0] = 1;
+		let key = Bytes(key.to_vec());
+		let nonce_of = |who: &AccountId32| u64::from(System::account_nonce(who));
+
+		// Without diff mode the state of all touched accounts before the call is reported.
+		let alice_balance = Pallet::<Test>::evm_balance(&ALICE_ADDR);
+		let contract_balance = Pallet::<Test>::evm_balance(&addr);
+		let mut tracer = Pallet::<Test>::evm_tracer(TracerConfig::PrestateTracer {
+			diff_mode: false,
+			disable_code: false,
+			disable_storage: false,
+		});
+		trace(tracer.as_tracing(), || {
+			builder::bare_call(addr).data(4u32.encode()).build_and_unwrap_result();
+		});
+
+		assert_eq!(
+			tracer.collect_trace(),
+			Some(Trace::Prestate(PrestateTrace::Prestate(BTreeMap::from([
+				(
+					ALICE_ADDR,
+					PrestateTraceInfo {
+						balance: Some(alice_balance),
+						nonce: Some(nonce_of(&ALICE)),
+						..Default::default()
+					}
+				),
+				(
+					addr,
+					PrestateTraceInfo {
+						balance: Some(contract_balance),
+						nonce: Some(nonce_of(&account_id)),
+						code: Some(code.into()),
+						storage: BTreeMap::from([(key.clone(), None)]),
+					}
+				),
+			]))))
+		);
+
+		// In diff mode only the changes are reported.
+		let mut tracer = Pallet::<Test>::evm_tracer(TracerConfig::PrestateTracer {
+			diff_mode: true,
+			disable_code: false,
+			disable_storage: false,
+		});
+		trace(tracer.as_tracing(), || {
+			builder::bare_call(addr).data(8u32.encode()).build_and_unwrap_result();
+		});
+
+		let Some(Trace::Prestate(PrestateTrace::DiffMode { pre, post })) = tracer.collect_trace()
+		else {
+			panic!("expected a diff mode prestate trace");
+		};
+		assert_eq!(pre[&addr].storage, BTreeMap::from([(key.clone(), Some(vec![0u8; 4].into()))]));
+		assert_eq!(post[&addr].storage, BTreeMap::from([(key, Some(vec![0u8; 8].into()))]));
+		assert_eq!(post[&addr].code, None);
+		assert_eq!(post[&addr].nonce, None);
+	});
+}
+
+#[test]
+fn struct_logger_works() {
+	use crate::evm::*;
+	use alloc::collections::BTreeMap;
+	let (code, _code_hash) = compile_module("storage_size").unwrap();
+	ExtBuilder::default().existential_deposit(200).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let mut key = [0u8; 32];
+		key[0] = 1;
+
+		for disable_stack in [false, true] {
+			let mut tracer = Pallet::<Test>::evm_tracer(TracerConfig::StructLogger {
+				disable_stack,
+				disable_storage: false,
+			});
+			trace(tracer.as_tracing(), || {
+				builder::bare_call(addr).data(4u32.encode()).build_and_unwrap_result();
+			});
+
+			let Some(Trace::StructLog(trace)) = tracer.collect_trace() else {
+				panic!("expected a struct log trace");
+			};
+			assert!(!trace.failed);
+			assert!(!trace.struct_logs.is_empty());
+			assert!(trace.struct_logs.iter().all(|log| log.depth == 1));
+			assert!(trace.struct_logs.windows(2).all(|logs| logs[0].gas >= logs[1].gas));
+			assert!(trace.struct_logs.iter().all(|log| if disable_stack {
+				log.stack.is_none()
+			} else {
+				log.stack.as_ref().map(Vec::len) == Some(13)
+			}));
+			assert!(trace.struct_logs.iter().any(|log| log.storage ==
+				Some(BTreeMap::from([(Bytes(key.to_vec()), Bytes(vec![0u8; 4]))]))));
+		}
+	});
+}
+
 #[test]
 fn unknown_precompiles_revert() {
 	let (code, _code_hash) = compile_module("read_only_call").unwrap();
@@ -4675,6 +5147,18 @@ fn pure_precompile_works() {
 			hex!("0000000048c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b61626300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000001").to_vec(),
 			hex!("08c9bcf367e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d282e6ad7f520e511f6c3e2b8c68059b9442be0454267ce079217e1319cde05b").to_vec(),
 		),
+		(
+			// PointEvaluation
+			H160::from_low_u64_be(10),
+			hex!("014dfd9d4941da80f2c4231ab756aa2e90fc271140d6b734401abcf44c534ddd256c17544a0072c1c7e88b50fb08834238cf7d33b56183113e6295668ea8b37b2b186be8ecdf21b858d87c6b52a9d7b4cd71f2f793acf51116120e372be2c5b6b48cc7aa8a9b2ca5a86fb118f06cea6bc7fbd8d61c632ad47ac60768df43371fe2ef2d53b1884ff33e90ecc300113e1ca6701c0b253f6797ea25d027aa5baa58e2098b11fde5c6538f2abb56426ca5a6f349e96dfa9f7cd45ff44bbc1cc41c1a").to_vec(),
+			hex!("000000000000000000000000000000000000000000000000000000000000100073eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001").to_vec(),
+		),
+		(
+			// Bls12381G1Add
+			H160::from_low_u64_be(11),
+			hex!("0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e100000000000000000000000000000000112b98340eee2777cc3c14163dea3ec97977ac3dc5c70da32e6e87578f44912e902ccef9efe28d4a78b8999dfbca942600000000000000000000000000000000186b28d92356c4dfec4b5201ad099dbdede3781f8998ddf929b4cd7756192185ca7b8f4ef7088f813270ac3d48868a21").to_vec(),
+			hex!("000000000000000000000000000000000a40300ce2dec9888b60690e9a41d3004fda4886854573974fab73b046d3147ba5b7a5bde85279ffede1b45b3918d82d0000000000000000000000000000000006d3d887e9f53b9ec4eb6cedf5607226754b07c01ace7834f57f3e7315faefb739e59018e22c492006190fba4a870025").to_vec(),
+		),
 	];
 
 	for (precompile_addr, input, output) in cases {
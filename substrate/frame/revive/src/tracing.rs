@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{primitives::ExecReturnValue, DispatchError, Weight};
+use crate::{exec::Key, primitives::ExecReturnValue, DispatchError, Weight};
 use environmental::environmental;
 use sp_core::{H160, H256, U256};
 
@@ -35,8 +35,8 @@ pub fn trace<R, F: FnOnce() -> R>(tracer: &mut (dyn Tracer + 'static), f: F) ->
 ///
 /// This is safe to be called from on-chain code as tracing will never be activated
 /// there. Hence the closure is not executed in this case.
-pub(crate) fn if_tracing<F: FnOnce(&mut (dyn Tracer + 'static))>(f: F) {
-	tracer::with(f);
+pub(crate) fn if_tracing<R, F: FnOnce(&mut (dyn Tracer + 'static)) -> R>(f: F) -> Option<R> {
+	tracer::with(f)
 }
 
 /// Defines methods to trace contract interactions.
@@ -61,4 +61,36 @@ pub trait Tracer {
 
 	/// Called when a contract call terminates with an error
 	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_left: Weight);
+
+	/// Called after a contract read the value stored under `key` in its storage.
+	fn storage_read(&mut self, _address: H160, _key: &Key, _value: Option<&[u8]>) {}
+
+	/// Called before a contract writes `new_value` under `key` in its storage.
+	///
+	/// A `None` value means that the key is absent from the storage.
+	fn storage_write(
+		&mut self,
+		_address: H160,
+		_key: &Key,
+		_old_value: Option<&[u8]>,
+		_new_value: Option<&[u8]>,
+	) {
+	}
+
+	/// Called after the balance of `address` changed because of a value transfer.
+	fn balance_change(&mut self, _address: H160, _old_balance: U256, _new_balance: U256) {}
+
+	/// Whether [`Self::instruction_step`] should be called for every executed instruction.
+	///
+	/// Stepping through every instruction slows down the execution considerably. Hence it is
+	/// only enabled for tracers that opt into it.
+	fn wants_instruction_steps(&self) -> bool {
+		false
+	}
+
+	/// Called before the instruction at program counter `pc` is executed.
+	///
+	/// `registers` contains the values of all PolkaVM registers at this point of the execution.
+	fn instruction_step(&mut self, _pc: u32, _opcode: &str, _gas_left: Weight, _registers: &[u64]) {
+	}
 }
@@ -33,11 +33,12 @@ use crate::{
 	gas::{GasMeter, Token},
 	limits,
 	storage::meter::Diff,
+	tracing::if_tracing,
 	weights::WeightInfo,
 	AccountIdOf, BadOrigin, BalanceOf, CodeInfoOf, CodeVec, Config, Error, ExecError, HoldReason,
 	PristineCode, Weight, LOG_TARGET,
 };
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::DispatchResult,
@@ -288,6 +289,10 @@ pub struct PreparedCall<'a, E: Ext> {
 	module: polkavm::Module,
 	instance: polkavm::RawInstance,
 	runtime: Runtime<'a, E, polkavm::RawInstance>,
+	/// The program blob used to look up the executed instructions.
+	///
+	/// Only set when the instructions are traced.
+	traced_blob: Option<polkavm::ProgramBlob>,
 }
 
 impl<'a, E: Ext> PreparedCall<'a, E>
@@ -298,6 +303,9 @@ where
 	pub fn call(mut self) -> ExecResult {
 		let exec_result = loop {
 			let interrupt = self.instance.run();
+			if let Ok(polkavm::InterruptKind::Step) = interrupt {
+				self.trace_step();
+			}
 			if let Some(exec_result) =
 				self.runtime.handle_interrupt(interrupt, &self.module, &mut self.instance)
 			{
@@ -308,6 +316,22 @@ where
 		exec_result
 	}
 
+	/// Report the instruction that is about to be executed to the tracer.
+	fn trace_step(&mut self) {
+		let Some(pc) = self.instance.program_counter() else { return };
+		let opcode = self
+			.traced_blob
+			.as_ref()
+			.and_then(|blob| blob.code().get(pc.0 as usize))
+			.and_then(|byte| polkavm::program::Opcode::from_u8_any(*byte))
+			.map(|opcode| format!("{opcode:?}"))
+			.unwrap_or_default();
+		let gas_left =
+			self.runtime.ext().gas_meter().gas_left_with_engine_fuel(self.instance.gas());
+		let registers = polkavm::Reg::ALL.map(|reg| self.instance.reg(reg));
+		if_tracing(|tracer| tracer.instruction_step(pc.0, &opcode, gas_left, &registers));
+	}
+
 	/// The guest memory address at which the aux data is located.
 	#[cfg(feature = "runtime-benchmarks")]
 	pub fn aux_data_base(&self) -> u32 {
@@ -358,13 +382,20 @@ impl<T: Config> WasmBlob<T> {
 				interpreter is available on all platforms; qed",
 		);
 
+		// Stepping through every instruction is only done when requested by the tracer.
+		let step_tracing =
+			if_tracing(|tracer| tracer.wants_instruction_steps()).unwrap_or_default();
+
 		let mut module_config = polkavm::ModuleConfig::new();
 		module_config.set_page_size(limits::PAGE_SIZE);
 		module_config.set_gas_metering(Some(polkavm::GasMeteringKind::Sync));
 		module_config.set_allow_sbrk(false);
 		module_config.set_aux_data_size(aux_data_size);
-		let module = polkavm::Module::new(&engine, &module_config, self.code.into_inner().into())
-			.map_err(|err| {
+		module_config.set_step_tracing(step_tracing);
+		let code: polkavm::ArcBytes = self.code.into_inner().into();
+		let traced_blob =
+			if step_tracing { polkavm::ProgramBlob::parse(code.clone()).ok() } else { None };
+		let module = polkavm::Module::new(&engine, &module_config, code).map_err(|err| {
 			log::debug!(target: LOG_TARGET, "failed to create polkavm module: {err:?}");
 			Error::<T>::CodeRejected
 		})?;
@@ -385,7 +416,7 @@ impl<T: Config> WasmBlob<T> {
 		instance.set_gas(gas_limit_polkavm);
 		instance.prepare_call_untyped(entry_program_counter, &[]);
 
-		Ok(PreparedCall { module, instance, runtime })
+		Ok(PreparedCall { module, instance, runtime, traced_blob })
 	}
 }
 
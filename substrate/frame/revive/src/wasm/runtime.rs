@@ -18,6 +18,7 @@
 //! Environment definition of the wasm smart-contract runtime.
 
 use crate::{
+	access_list::{self, Warm},
 	address::AddressMapper,
 	evm::runtime::GAS_PRICE,
 	exec::{ExecError, ExecResult, Ext, Key},
@@ -414,6 +415,22 @@ pub enum RuntimeCosts {
 	Blake2F(u32),
 	/// Weight of calling `Modexp` precompile
 	Modexp(u64),
+	/// Weight of calling `PointEvaluation` precompile
+	PointEvaluation,
+	/// Weight of calling `Bls12381G1Add` precompile
+	Bls12381G1Add,
+	/// Weight of calling `Bls12381G1Msm` precompile for the given number of input pairs.
+	Bls12381G1Msm(u32),
+	/// Weight of calling `Bls12381G2Add` precompile
+	Bls12381G2Add,
+	/// Weight of calling `Bls12381G2Msm` precompile for the given number of input pairs.
+	Bls12381G2Msm(u32),
+	/// Weight of calling `Bls12381PairingCheck` precompile for the given number of input pairs.
+	Bls12381PairingCheck(u32),
+	/// Weight of calling `Bls12381MapFpToG1` precompile
+	Bls12381MapFpToG1,
+	/// Weight of calling `Bls12381MapFp2ToG2` precompile
+	Bls12381MapFp2ToG2,
 }
 
 /// For functions that modify storage, benchmarks are performed with one item in the
@@ -562,6 +579,14 @@ impl<T: Config> Token<T> for RuntimeCosts {
 				const WEIGHT_PER_GAS: u64 = WEIGHT_REF_TIME_PER_SECOND / GAS_PER_SECOND;
 				Weight::from_parts(gas.saturating_mul(WEIGHT_PER_GAS), 0)
 			},
+			PointEvaluation => T::WeightInfo::point_evaluation(),
+			Bls12381G1Add => T::WeightInfo::bls12_381_g1_add(),
+			Bls12381G1Msm(pairs) => T::WeightInfo::bls12_381_g1_msm(pairs),
+			Bls12381G2Add => T::WeightInfo::bls12_381_g2_add(),
+			Bls12381G2Msm(pairs) => T::WeightInfo::bls12_381_g2_msm(pairs),
+			Bls12381PairingCheck(pairs) => T::WeightInfo::bls12_381_pairing_check(pairs),
+			Bls12381MapFpToG1 => T::WeightInfo::bls12_381_map_fp_to_g1(),
+			Bls12381MapFp2ToG2 => T::WeightInfo::bls12_381_map_fp2_to_g2(),
 		}
 	}
 }
@@ -701,6 +726,23 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		self.ext.gas_meter_mut().adjust_gas(charged, actual_costs);
 	}
 
+	/// Adjust a previously charged amount down to its actual amount, excluding its proof size
+	/// if the accessed account or storage key is warm.
+	///
+	/// See [`crate::access_list`].
+	fn adjust_gas_warm(&mut self, charged: ChargedAmount, actual_costs: RuntimeCosts, warm: bool) {
+		if warm {
+			self.ext.gas_meter_mut().adjust_gas(charged, Warm(actual_costs));
+		} else {
+			self.adjust_gas(charged, actual_costs);
+		}
+	}
+
+	/// Whether `key` of the current contract is warm, see [`crate::access_list`].
+	fn is_warm_storage(&self, key: &Key, transient: bool) -> bool {
+		!transient && access_list::is_warm_storage(&self.ext.address(), key)
+	}
+
 	/// Charge, Run and adjust gas, for executing the given dispatchable.
 	fn call_dispatchable<ErrorReturnCode: Get<ReturnErrorCode>>(
 		&mut self,
@@ -895,7 +937,8 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		} else {
 			self.ext.set_storage(&key, value, false)?
 		};
-		self.adjust_gas(charged, costs(value_len, write_outcome.old_len()));
+		let warm = self.is_warm_storage(&key, transient);
+		self.adjust_gas_warm(charged, costs(value_len, write_outcome.old_len()), warm);
 		Ok(write_outcome.old_len_with_sentinel())
 	}
 
@@ -921,7 +964,8 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		} else {
 			self.ext.set_storage(&key, None, false)?
 		};
-		self.adjust_gas(charged, costs(outcome.old_len()));
+		let warm = self.is_warm_storage(&key, transient);
+		self.adjust_gas_warm(charged, costs(outcome.old_len()), warm);
 		Ok(outcome.old_len_with_sentinel())
 	}
 
@@ -949,8 +993,9 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		} else {
 			self.ext.get_storage(&key)
 		};
+		let warm = self.is_warm_storage(&key, transient);
 		if let Some(value) = outcome {
-			self.adjust_gas(charged, costs(value.len() as u32));
+			self.adjust_gas_warm(charged, costs(value.len() as u32), warm);
 			self.write_sandbox_output(
 				memory,
 				out_ptr,
@@ -961,7 +1006,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 			)?;
 			Ok(ReturnErrorCode::Success)
 		} else {
-			self.adjust_gas(charged, costs(0));
+			self.adjust_gas_warm(charged, costs(0), warm);
 			Ok(ReturnErrorCode::KeyNotFound)
 		}
 	}
@@ -988,7 +1033,8 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		} else {
 			self.ext.get_storage_size(&key)
 		};
-		self.adjust_gas(charged, costs(outcome.unwrap_or(0)));
+		let warm = self.is_warm_storage(&key, transient);
+		self.adjust_gas_warm(charged, costs(outcome.unwrap_or(0)), warm);
 		Ok(outcome.unwrap_or(SENTINEL))
 	}
 
@@ -1016,9 +1062,10 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		} else {
 			self.ext.set_storage(&key, None, true)?
 		};
+		let warm = self.is_warm_storage(&key, transient);
 
 		if let crate::storage::WriteOutcome::Taken(value) = outcome {
-			self.adjust_gas(charged, costs(value.len() as u32));
+			self.adjust_gas_warm(charged, costs(value.len() as u32), warm);
 			self.write_sandbox_output(
 				memory,
 				out_ptr,
@@ -1029,7 +1076,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 			)?;
 			Ok(ReturnErrorCode::Success)
 		} else {
-			self.adjust_gas(charged, costs(0));
+			self.adjust_gas_warm(charged, costs(0), warm);
 			Ok(ReturnErrorCode::KeyNotFound)
 		}
 	}
@@ -1467,8 +1514,13 @@ pub mod env {
 	/// See [`pallet_revive_uapi::HostFn::code_hash`].
 	#[stable]
 	fn code_hash(&mut self, memory: &mut M, addr_ptr: u32, out_ptr: u32) -> Result<(), TrapReason> {
-		self.charge_gas(RuntimeCosts::CodeHash)?;
+		let charged = self.charge_gas(RuntimeCosts::CodeHash)?;
 		let address = memory.read_h160(addr_ptr)?;
+		self.adjust_gas_warm(
+			charged,
+			RuntimeCosts::CodeHash,
+			access_list::is_warm_account(&address),
+		);
 		Ok(self.write_fixed_sandbox_output(
 			memory,
 			out_ptr,
@@ -1577,8 +1629,13 @@ pub mod env {
 		addr_ptr: u32,
 		out_ptr: u32,
 	) -> Result<(), TrapReason> {
-		self.charge_gas(RuntimeCosts::BalanceOf)?;
+		let charged = self.charge_gas(RuntimeCosts::BalanceOf)?;
 		let address = memory.read_h160(addr_ptr)?;
+		self.adjust_gas_warm(
+			charged,
+			RuntimeCosts::BalanceOf,
+			access_list::is_warm_account(&address),
+		);
 		Ok(self.write_fixed_sandbox_output(
 			memory,
 			out_ptr,
@@ -1960,8 +2017,13 @@ pub mod env {
 	/// Checks whether a specified address belongs to a contract.
 	/// See [`pallet_revive_uapi::HostFn::is_contract`].
 	fn is_contract(&mut self, memory: &mut M, account_ptr: u32) -> Result<u32, TrapReason> {
-		self.charge_gas(RuntimeCosts::IsContract)?;
+		let charged = self.charge_gas(RuntimeCosts::IsContract)?;
 		let address = memory.read_h160(account_ptr)?;
+		self.adjust_gas_warm(
+			charged,
+			RuntimeCosts::IsContract,
+			access_list::is_warm_account(&address),
+		);
 		Ok(self.ext.is_contract(&address) as u32)
 	}
 
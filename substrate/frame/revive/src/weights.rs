@@ -84,6 +84,7 @@ pub trait WeightInfo {
 	fn map_account() -> Weight;
 	fn unmap_account() -> Weight;
 	fn dispatch_as_fallback_account() -> Weight;
+	fn access_list(n: u32, ) -> Weight;
 	fn noop_host_fn(r: u32, ) -> Weight;
 	fn seal_caller() -> Weight;
 	fn seal_origin() -> Weight;
@@ -153,6 +154,14 @@ pub trait WeightInfo {
 	fn bn128_mul() -> Weight;
 	fn bn128_pairing(n: u32, ) -> Weight;
 	fn blake2f(n: u32, ) -> Weight;
+	fn point_evaluation() -> Weight;
+	fn bls12_381_g1_add() -> Weight;
+	fn bls12_381_g1_msm(n: u32, ) -> Weight;
+	fn bls12_381_g2_add() -> Weight;
+	fn bls12_381_g2_msm(n: u32, ) -> Weight;
+	fn bls12_381_pairing_check(n: u32, ) -> Weight;
+	fn bls12_381_map_fp_to_g1() -> Weight;
+	fn bls12_381_map_fp2_to_g2() -> Weight;
 	fn seal_ecdsa_to_eth_address() -> Weight;
 	fn seal_set_code_hash() -> Weight;
 	fn instr(r: u32, ) -> Weight;
@@ -396,6 +405,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(14_653_000, 3610)
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 	}
+	/// The range of component `n` is `[0, 1024]`.
+	fn access_list(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_015_000 picoseconds.
+		Weight::from_parts(1_137_000, 0)
+			// Standard Error: 31
+			.saturating_add(Weight::from_parts(301_000, 0).saturating_mul(n.into()))
+	}
 	/// The range of component `r` is `[0, 1600]`.
 	fn noop_host_fn(r: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -685,6 +704,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Revive::CodeInfoOf` (`max_values`: None, `max_size`: Some(96), added: 2571, mode: `Measured`)
 	/// Storage: `Revive::DeletionQueue` (r:0 w:1)
 	/// Proof: `Revive::DeletionQueue` (`max_values`: None, `max_size`: Some(142), added: 2617, mode: `Measured`)
+	/// Storage: `Revive::DeletionQueueDeposit` (r:0 w:1)
+	/// Proof: `Revive::DeletionQueueDeposit` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `Measured`)
 	/// Storage: `Revive::ImmutableDataOf` (r:0 w:1)
 	/// Proof: `Revive::ImmutableDataOf` (`max_values`: None, `max_size`: Some(4118), added: 6593, mode: `Measured`)
 	fn seal_terminate() -> Weight {
@@ -694,7 +715,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 18_236_000 picoseconds.
 		Weight::from_parts(19_062_000, 3805)
 			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// The range of component `t` is `[0, 4]`.
 	/// The range of component `n` is `[0, 416]`.
@@ -1087,6 +1108,71 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			// Standard Error: 6
 			.saturating_add(Weight::from_parts(22_429, 0).saturating_mul(n.into()))
 	}
+	fn point_evaluation() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 13_118_455_000 picoseconds.
+		Weight::from_parts(13_210_932_000, 0)
+	}
+	fn bls12_381_g1_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 42_786_000 picoseconds.
+		Weight::from_parts(43_088_000, 0)
+	}
+	/// The range of component `n` is `[1, 1638]`.
+	fn bls12_381_g1_msm(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_602_136_000 picoseconds.
+		Weight::from_parts(1_236_933_600, 0)
+			// Standard Error: 41_262
+			.saturating_add(Weight::from_parts(376_496_400, 0).saturating_mul(n.into()))
+	}
+	fn bls12_381_g2_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 58_972_000 picoseconds.
+		Weight::from_parts(59_388_000, 0)
+	}
+	/// The range of component `n` is `[1, 910]`.
+	fn bls12_381_g2_msm(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 4_501_650_000 picoseconds.
+		Weight::from_parts(3_730_524_000, 0)
+			// Standard Error: 96_813
+			.saturating_add(Weight::from_parts(802_859_400, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[1, 682]`.
+	fn bls12_381_pairing_check(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 6_993_821_000 picoseconds.
+		Weight::from_parts(4_344_816_000, 0)
+			// Standard Error: 153_409
+			.saturating_add(Weight::from_parts(2_698_306_800, 0).saturating_mul(n.into()))
+	}
+	fn bls12_381_map_fp_to_g1() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 855_261_000 picoseconds.
+		Weight::from_parts(861_290_000, 0)
+	}
+	fn bls12_381_map_fp2_to_g2() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_167_944_000 picoseconds.
+		Weight::from_parts(2_183_227_000, 0)
+	}
 	fn seal_ecdsa_to_eth_address() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
@@ -1363,6 +1449,16 @@ impl WeightInfo for () {
 		Weight::from_parts(14_653_000, 3610)
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 	}
+	/// The range of component `n` is `[0, 1024]`.
+	fn access_list(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_015_000 picoseconds.
+		Weight::from_parts(1_137_000, 0)
+			// Standard Error: 31
+			.saturating_add(Weight::from_parts(301_000, 0).saturating_mul(n.into()))
+	}
 	/// The range of component `r` is `[0, 1600]`.
 	fn noop_host_fn(r: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -1652,6 +1748,8 @@ impl WeightInfo for () {
 	/// Proof: `Revive::CodeInfoOf` (`max_values`: None, `max_size`: Some(96), added: 2571, mode: `Measured`)
 	/// Storage: `Revive::DeletionQueue` (r:0 w:1)
 	/// Proof: `Revive::DeletionQueue` (`max_values`: None, `max_size`: Some(142), added: 2617, mode: `Measured`)
+	/// Storage: `Revive::DeletionQueueDeposit` (r:0 w:1)
+	/// Proof: `Revive::DeletionQueueDeposit` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `Measured`)
 	/// Storage: `Revive::ImmutableDataOf` (r:0 w:1)
 	/// Proof: `Revive::ImmutableDataOf` (`max_values`: None, `max_size`: Some(4118), added: 6593, mode: `Measured`)
 	fn seal_terminate() -> Weight {
@@ -1661,7 +1759,7 @@ impl WeightInfo for () {
 		// Minimum execution time: 18_236_000 picoseconds.
 		Weight::from_parts(19_062_000, 3805)
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// The range of component `t` is `[0, 4]`.
 	/// The range of component `n` is `[0, 416]`.
@@ -2054,6 +2152,71 @@ impl WeightInfo for () {
 			// Standard Error: 6
 			.saturating_add(Weight::from_parts(22_429, 0).saturating_mul(n.into()))
 	}
+	fn point_evaluation() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 13_118_455_000 picoseconds.
+		Weight::from_parts(13_210_932_000, 0)
+	}
+	fn bls12_381_g1_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 42_786_000 picoseconds.
+		Weight::from_parts(43_088_000, 0)
+	}
+	/// The range of component `n` is `[1, 1638]`.
+	fn bls12_381_g1_msm(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_602_136_000 picoseconds.
+		Weight::from_parts(1_236_933_600, 0)
+			// Standard Error: 41_262
+			.saturating_add(Weight::from_parts(376_496_400, 0).saturating_mul(n.into()))
+	}
+	fn bls12_381_g2_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 58_972_000 picoseconds.
+		Weight::from_parts(59_388_000, 0)
+	}
+	/// The range of component `n` is `[1, 910]`.
+	fn bls12_381_g2_msm(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 4_501_650_000 picoseconds.
+		Weight::from_parts(3_730_524_000, 0)
+			// Standard Error: 96_813
+			.saturating_add(Weight::from_parts(802_859_400, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[1, 682]`.
+	fn bls12_381_pairing_check(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 6_993_821_000 picoseconds.
+		Weight::from_parts(4_344_816_000, 0)
+			// Standard Error: 153_409
+			.saturating_add(Weight::from_parts(2_698_306_800, 0).saturating_mul(n.into()))
+	}
+	fn bls12_381_map_fp_to_g1() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 855_261_000 picoseconds.
+		Weight::from_parts(861_290_000, 0)
+	}
+	fn bls12_381_map_fp2_to_g2() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_167_944_000 picoseconds.
+		Weight::from_parts(2_183_227_000, 0)
+	}
 	fn seal_ecdsa_to_eth_address() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
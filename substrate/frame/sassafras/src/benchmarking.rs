@@ -81,8 +81,7 @@ mod benchmarks {
 	// - `y`: epoch length in slots (1000:5000)
 	//
 	// This accounts for the worst case which includes:
-	// - load the full ring context.
-	// - recompute the ring verifier.
+	// - start the ring verifier incremental construction.
 	// - sorting the epoch tickets in one shot
 	//  (here we account for the very unlucky scenario where we haven't done any sort work yet)
 	// - pending epoch change config.
@@ -99,7 +98,7 @@ mod benchmarks {
 		let mut meta = TicketsMetadata { unsorted_tickets_count, tickets_count: [0, 0] };
 		let config = EpochConfiguration { redundancy_factor, attempts_number: 32 };
 
-		// Triggers ring verifier computation for `x` authorities
+		// Triggers ring verifier construction start for `x` authorities
 		let mut raw_data = TICKETS_DATA;
 		let (authorities, _): (Vec<AuthorityId>, Vec<TicketEnvelope>) =
 			Decode::decode(&mut raw_data).expect("Failed to decode tickets buffer");
@@ -151,7 +150,7 @@ mod benchmarks {
 		{
 			Pallet::<T>::should_end_epoch(BlockNumberFor::<T>::from(3u32));
 			let next_authorities = Pallet::<T>::next_authorities();
-			// Using a different set of authorities triggers the recomputation of ring verifier.
+			// Using a different set of authorities triggers the construction of ring verifier.
 			Pallet::<T>::enact_epoch_change(Default::default(), next_authorities);
 		}
 	}
@@ -213,6 +212,30 @@ mod benchmarks {
 		}
 	}
 
+	// Append `x` keys to the ring verifier key builder and finalize the ring verifier key.
+	//
+	// This is the worst case for one step of the incremental ring verifier key construction.
+	#[benchmark]
+	fn ring_verifier_key_build_step(x: Linear<1, 100>) {
+		let authorities_count = x as usize;
+
+		let mut raw_data = TICKETS_DATA;
+		let (authorities, _): (Vec<AuthorityId>, Vec<TicketEnvelope>) =
+			Decode::decode(&mut raw_data).expect("Failed to decode tickets buffer");
+		let authorities: Vec<_> = authorities[..authorities_count].to_vec();
+		NextAuthorities::<T>::set(WeakBoundedVec::force_from(authorities, None));
+
+		let builder = EmptyRingBuilder::<T>::get().expect("Ring builder is initialized");
+		RingVerifierKeyBuilder::<T>::set(Some((builder, 0)));
+
+		#[block]
+		{
+			Pallet::<T>::ring_verifier_key_build_step();
+		}
+
+		assert!(RingVerifierKeyBuilder::<T>::get().is_none());
+	}
+
 	// Bare loading of ring context.
 	//
 	// It is interesting to see how this compares to 'update_ring_verifier', which
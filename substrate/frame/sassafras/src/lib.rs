@@ -83,6 +83,7 @@ mod mock;
 #[cfg(all(feature = "std", test))]
 mod tests;
 
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -93,6 +94,9 @@ const LOG_TARGET: &str = "sassafras::runtime";
 // Max length for segments holding unsorted tickets.
 const SEGMENT_MAX_SIZE: u32 = 128;
 
+// Max length for pages holding ring verifier key builder parameters.
+const RING_BUILDER_PAGE_SIZE: u32 = 256;
+
 /// Authorities bounded vector convenience type.
 pub type AuthoritiesVec<T> = WeakBoundedVec<AuthorityId, <T as Config>::MaxAuthorities>;
 
@@ -122,8 +126,12 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	/// The Sassafras pallet.
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configuration parameters.
@@ -143,6 +151,14 @@ pub mod pallet {
 		/// and to perform the transition to the next epoch.
 		type EpochChangeTrigger: EpochChangeTrigger;
 
+		/// Max number of authorities keys appended to the next epoch ring verifier key
+		/// on each block.
+		///
+		/// When the next epoch authorities change, the ring verifier key is incrementally
+		/// built over several blocks in order to keep the per-block weight bounded.
+		#[pallet::constant]
+		type RingBuilderKeysPerBlock: Get<u32>;
+
 		/// Weight information for all calls of this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -277,6 +293,32 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type RingVerifierData<T: Config> = StorageValue<_, vrf::RingVerifierKey>;
 
+	/// Parameters required to append keys to the ring verifier key builder.
+	///
+	/// Extracted from the [`RingContext`] and split in pages of [`RING_BUILDER_PAGE_SIZE`]
+	/// items, so that building the ring verifier key doesn't require to load the whole
+	/// ring context.
+	#[pallet::storage]
+	pub type RingBuilderParams<T: Config> = StorageMap<
+		_,
+		Identity,
+		u32,
+		BoundedVec<vrf::RingBuilderParamsItem, ConstU32<RING_BUILDER_PAGE_SIZE>>,
+	>;
+
+	/// Empty ring verifier key builder, used to start a new ring verifier key construction.
+	#[pallet::storage]
+	pub type EmptyRingBuilder<T: Config> = StorageValue<_, vrf::RingVerifierKeyBuilder>;
+
+	/// Ring verifier key builder in progress.
+	///
+	/// Holds the partial builder together with the number of keys appended so far.
+	/// Once all the next epoch authorities keys have been appended, the builder is
+	/// finalized into [`RingVerifierData`] and removed.
+	#[pallet::storage]
+	pub type RingVerifierKeyBuilder<T: Config> =
+		StorageValue<_, (vrf::RingVerifierKeyBuilder, u32)>;
+
 	/// Genesis configuration for Sassafras protocol.
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
@@ -300,7 +342,7 @@ pub mod pallet {
 			{
 				debug!(target: LOG_TARGET, "Constructing dummy ring context");
 				let ring_ctx = vrf::RingContext::new_testing();
-				RingContext::<T>::put(ring_ctx);
+				Pallet::<T>::init_ring_context(ring_ctx);
 				Pallet::<T>::update_ring_verifier(&self.authorities);
 			}
 		}
@@ -328,7 +370,9 @@ pub mod pallet {
 
 			let trigger_weight = T::EpochChangeTrigger::trigger::<T>(block_num);
 
-			T::WeightInfo::on_initialize() + trigger_weight
+			let ring_builder_weight = Self::ring_verifier_key_build_step();
+
+			T::WeightInfo::on_initialize() + trigger_weight + ring_builder_weight
 		}
 
 		fn on_finalize(_: BlockNumberFor<T>) {
@@ -385,6 +429,11 @@ pub mod pallet {
 				return Err("Tickets shall be submitted in the first epoch half".into())
 			}
 
+			if RingVerifierKeyBuilder::<T>::exists() {
+				warn!(target: LOG_TARGET, "Ring verifier key under construction");
+				return Err("Ring verifier key under construction".into())
+			}
+
 			let Some(verifier) =
 				RingVerifierData::<T>::get().map(|vk| vrf::RingContext::verifier_no_context(vk))
 			else {
@@ -499,6 +548,12 @@ pub mod pallet {
 				return InvalidTransaction::Stale.into()
 			}
 
+			// Tickets can't be verified until the next epoch ring verifier key is built.
+			if RingVerifierKeyBuilder::<T>::exists() {
+				debug!(target: LOG_TARGET, "Ring verifier key under construction");
+				return InvalidTransaction::Future.into()
+			}
+
 			// This should be set such that it is discarded after the first epoch half
 			let tickets_longevity = epoch_length / 2 - current_slot_idx;
 			let tickets_tag = tickets.using_encoded(|bytes| hashing::blake2_256(bytes));
@@ -573,6 +628,89 @@ impl<T: Config> Pallet<T> {
 		RingVerifierData::<T>::put(verifier_data);
 	}
 
+	/// Initialize the ring context.
+	///
+	/// Together with the context, the parameters used by the incremental ring verifier key
+	/// builder are extracted and stored in pages.
+	pub(crate) fn init_ring_context(ring_ctx: vrf::RingContext) {
+		let (builder, params) = ring_ctx.verifier_key_builder();
+		let _ = RingBuilderParams::<T>::clear(u32::MAX, None);
+		for (page, items) in params.chunks(RING_BUILDER_PAGE_SIZE as usize).enumerate() {
+			let items = BoundedVec::truncate_from(items.to_vec());
+			RingBuilderParams::<T>::insert(page as u32, items);
+		}
+		EmptyRingBuilder::<T>::put(builder);
+		RingContext::<T>::put(ring_ctx);
+	}
+
+	// Start the incremental construction of the ring verifier key for the next
+	// epoch authorities.
+	//
+	// The current ring verifier key is kept until the construction is completed, but
+	// tickets are rejected in the meantime as they are signed with the next epoch ring.
+	//
+	// If the builder parameters are not available (i.e. the ring context has been set
+	// before their introduction and not migrated yet) the ring verifier key is built
+	// in one shot.
+	fn start_ring_verifier_key_build(next_authorities: &[AuthorityId]) {
+		let Some(builder) = EmptyRingBuilder::<T>::get() else {
+			warn!(target: LOG_TARGET, "Ring builder not initialized, building ring verifier in one shot");
+			RingVerifierKeyBuilder::<T>::kill();
+			Self::update_ring_verifier(next_authorities);
+			return
+		};
+		RingVerifierKeyBuilder::<T>::put((builder, 0));
+	}
+
+	// Append the next chunk of next epoch authorities keys to the ring verifier key
+	// builder, finalizing the ring verifier key once all the keys have been appended.
+	//
+	// Returns the consumed weight.
+	fn ring_verifier_key_build_step() -> Weight {
+		let Some((mut builder, appended)) = RingVerifierKeyBuilder::<T>::get() else {
+			return T::DbWeight::get().reads(1)
+		};
+
+		let authorities = NextAuthorities::<T>::get();
+		let start = (appended as usize).min(authorities.len());
+		let end = start
+			.saturating_add(T::RingBuilderKeysPerBlock::get() as usize)
+			.min(authorities.len());
+		let pks: Vec<_> = authorities[start..end].iter().map(|auth| *auth.as_ref()).collect();
+
+		debug!(target: LOG_TARGET, "Appending {} keys to ring verifier key builder", pks.len());
+
+		let lookup = |range: core::ops::Range<usize>| {
+			let page_size = RING_BUILDER_PAGE_SIZE as usize;
+			let first_page = range.start / page_size;
+			let last_page = range.end.saturating_sub(1) / page_size;
+			let mut items = Vec::with_capacity(range.len());
+			for page in first_page..=last_page {
+				items.extend(RingBuilderParams::<T>::get(page as u32)?);
+			}
+			let offset = range.start - first_page * page_size;
+			items.get(offset..offset + range.len()).map(|items| items.to_vec())
+		};
+
+		let weight = T::WeightInfo::ring_verifier_key_build_step(pks.len() as u32);
+
+		if let Err(err) = builder.append(&pks, lookup) {
+			error!(target: LOG_TARGET, "Ring verifier key construction failure: {:?}", err);
+			RingVerifierKeyBuilder::<T>::kill();
+			return weight
+		}
+
+		if end == authorities.len() {
+			debug!(target: LOG_TARGET, "Ring verifier key construction completed");
+			RingVerifierData::<T>::put(builder.finalize());
+			RingVerifierKeyBuilder::<T>::kill();
+		} else {
+			RingVerifierKeyBuilder::<T>::put((builder, end as u32));
+		}
+
+		weight
+	}
+
 	/// Enact an epoch change.
 	///
 	/// WARNING: Should be called on every block once and if and only if [`should_end_epoch`]
@@ -585,7 +723,7 @@ impl<T: Config> Pallet<T> {
 		next_authorities: WeakBoundedVec<AuthorityId, T::MaxAuthorities>,
 	) {
 		if next_authorities != authorities {
-			Self::update_ring_verifier(&next_authorities);
+			Self::start_ring_verifier_key_build(&next_authorities);
 		}
 
 		// Update authorities
@@ -857,6 +995,18 @@ impl<T: Config> Pallet<T> {
 		Self::slot_ticket_id(slot).and_then(|id| TicketsData::<T>::get(id).map(|body| (id, body)))
 	}
 
+	/// Data of a ticket submitted on-chain.
+	pub fn ticket_body(ticket_id: TicketId) -> Option<TicketBody> {
+		TicketsData::<T>::get(ticket_id)
+	}
+
+	/// Whether the next epoch ring verifier key is available.
+	///
+	/// Tickets are rejected while the key is missing or under construction.
+	pub fn ring_verifier_key_ready() -> bool {
+		RingVerifierData::<T>::exists() && !RingVerifierKeyBuilder::<T>::exists()
+	}
+
 	// Sort and truncate candidate tickets, cleanup storage.
 	fn sort_and_truncate(candidates: &mut Vec<u128>, max_tickets: usize) -> u128 {
 		candidates.sort_unstable();
@@ -0,0 +1,71 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the Sassafras pallet.
+
+use crate::*;
+use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+#[cfg(feature = "try-runtime")]
+use alloc::vec::Vec;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// Initialize the incremental ring verifier key builder parameters out of the ring
+/// context already in storage.
+///
+/// Without this migration the ring verifier key keeps being built in one shot on
+/// every authorities change.
+pub struct VersionUncheckedMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateV0ToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let Some(ring_ctx) = RingContext::<T>::get() else {
+			log::info!(target: LOG_TARGET, "Ring context not initialized, nothing to migrate");
+			return T::DbWeight::get().reads(1)
+		};
+		if EmptyRingBuilder::<T>::exists() {
+			return T::WeightInfo::load_ring_context().saturating_add(T::DbWeight::get().reads(1))
+		}
+
+		log::info!(target: LOG_TARGET, "Initializing ring verifier key builder parameters");
+		let pages = ring_ctx.max_keyset_size().div_ceil(RING_BUILDER_PAGE_SIZE as usize) as u64;
+		Pallet::<T>::init_ring_context(ring_ctx);
+
+		T::WeightInfo::load_ring_context()
+			.saturating_add(T::DbWeight::get().reads_writes(1, pages.saturating_add(2)))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+		frame_support::ensure!(
+			!RingContext::<T>::exists() || EmptyRingBuilder::<T>::exists(),
+			"Ring verifier key builder not initialized"
+		);
+		Ok(())
+	}
+}
+
+/// [`VersionUncheckedMigrateV0ToV1`] wrapped in a [`VersionedMigration`], which ensures
+/// that it is executed only once.
+pub type MigrateV0ToV1<T> = VersionedMigration<
+	0,
+	1,
+	VersionUncheckedMigrateV0ToV1<T>,
+	Pallet<T>,
+	<T as frame_system::Config>::DbWeight,
+>;
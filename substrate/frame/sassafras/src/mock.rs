@@ -20,7 +20,7 @@
 use crate::{self as pallet_sassafras, EpochChangeInternalTrigger, *};
 
 use frame_support::{
-	derive_impl,
+	derive_impl, parameter_types,
 	traits::{ConstU32, OnFinalize, OnInitialize},
 };
 use sp_consensus_sassafras::{
@@ -65,10 +65,15 @@ where
 	}
 }
 
+parameter_types! {
+	pub static RingBuilderKeysPerBlock: u32 = MAX_AUTHORITIES;
+}
+
 impl pallet_sassafras::Config for Test {
 	type EpochLength = ConstU32<EPOCH_LENGTH>;
 	type MaxAuthorities = ConstU32<MAX_AUTHORITIES>;
 	type EpochChangeTrigger = EpochChangeInternalTrigger;
+	type RingBuilderKeysPerBlock = RingBuilderKeysPerBlock;
 	type WeightInfo = ();
 }
 
@@ -119,7 +124,7 @@ pub fn new_test_ext_with_pairs(
 		ext.execute_with(|| {
 			log::debug!(target: LOG_TARGET, "Building testing ring context");
 			let ring_ctx = vrf::RingContext::new_testing();
-			RingContext::<Test>::set(Some(ring_ctx.clone()));
+			Sassafras::init_ring_context(ring_ctx);
 			Sassafras::update_ring_verifier(&authorities);
 		});
 	}
@@ -21,6 +21,7 @@ use crate::*;
 use mock::*;
 
 use sp_consensus_sassafras::Slot;
+use sp_core::Pair as _;
 
 fn h2b<const N: usize>(hex: &str) -> [u8; N] {
 	array_bytes::hex2array_unchecked(hex)
@@ -779,6 +780,127 @@ fn trivial_fisher_yates_shuffle<T>(vector: &mut Vec<T>, random_seed: u64) {
 	}
 }
 
+#[test]
+fn ring_verifier_key_incremental_build_works() {
+	let (pairs, mut ext) = new_test_ext_with_pairs(10, true);
+	let pair = &pairs[0];
+
+	ext.execute_with(|| {
+		RingBuilderKeysPerBlock::set(3);
+
+		let next_authorities: Vec<_> = pairs.iter().skip(2).map(|p| p.public()).collect();
+		let pks: Vec<_> = next_authorities.iter().map(|auth| *auth.as_ref()).collect();
+		let expected = Sassafras::ring_context().unwrap().verifier_key(&pks);
+
+		initialize_block(1, 100.into(), Default::default(), pair);
+		let current = RingVerifierData::<Test>::get().unwrap().encode();
+
+		NextAuthorities::<Test>::put(WeakBoundedVec::force_from(next_authorities.clone(), None));
+		Sassafras::start_ring_verifier_key_build(&next_authorities);
+
+		// The current verifier is kept while the construction is in progress.
+		assert_eq!(RingVerifierData::<Test>::get().unwrap().encode(), current);
+		assert_eq!(RingVerifierKeyBuilder::<Test>::get().unwrap().1, 0);
+
+		// Keys are appended on block initialization.
+		progress_to_block(2, pair);
+		assert_eq!(RingVerifierKeyBuilder::<Test>::get().unwrap().1, 3);
+		progress_to_block(3, pair);
+		assert_eq!(RingVerifierKeyBuilder::<Test>::get().unwrap().1, 6);
+		assert_eq!(RingVerifierData::<Test>::get().unwrap().encode(), current);
+
+		// Last chunk completes the construction.
+		progress_to_block(4, pair);
+		assert!(RingVerifierKeyBuilder::<Test>::get().is_none());
+		assert_eq!(RingVerifierData::<Test>::get().unwrap().encode(), expected.encode());
+	})
+}
+
+#[test]
+fn tickets_are_rejected_during_ring_verifier_key_build() {
+	use frame_support::pallet_prelude::{InvalidTransaction, TransactionSource};
+	use sp_runtime::traits::ValidateUnsigned;
+
+	let (pairs, mut ext) = new_test_ext_with_pairs(10, true);
+	let pair = &pairs[0];
+
+	ext.execute_with(|| {
+		RingBuilderKeysPerBlock::set(5);
+
+		let next_authorities: Vec<_> = pairs.iter().skip(2).map(|p| p.public()).collect();
+
+		initialize_block(1, 100.into(), Default::default(), pair);
+
+		NextAuthorities::<Test>::put(WeakBoundedVec::force_from(next_authorities.clone(), None));
+		Sassafras::start_ring_verifier_key_build(&next_authorities);
+
+		// Tickets are signed with the next epoch ring, which is not available yet.
+		let call = Call::submit_tickets { tickets: BoundedVec::new() };
+		assert_eq!(
+			Sassafras::validate_unsigned(TransactionSource::Local, &call),
+			InvalidTransaction::Future.into(),
+		);
+		assert_eq!(
+			Sassafras::submit_tickets(RuntimeOrigin::none(), BoundedVec::new())
+				.unwrap_err()
+				.error,
+			"Ring verifier key under construction".into(),
+		);
+
+		progress_to_block(2, pair);
+		progress_to_block(3, pair);
+		assert!(RingVerifierKeyBuilder::<Test>::get().is_none());
+
+		assert!(Sassafras::validate_unsigned(TransactionSource::Local, &call).is_ok());
+		assert!(Sassafras::submit_tickets(RuntimeOrigin::none(), BoundedVec::new()).is_ok());
+	})
+}
+
+#[test]
+fn ring_verifier_key_is_built_in_one_shot_without_builder() {
+	let (pairs, mut ext) = new_test_ext_with_pairs(10, true);
+	let pair = &pairs[0];
+
+	ext.execute_with(|| {
+		let next_authorities: Vec<_> = pairs.iter().skip(2).map(|p| p.public()).collect();
+		let pks: Vec<_> = next_authorities.iter().map(|auth| *auth.as_ref()).collect();
+		let expected = Sassafras::ring_context().unwrap().verifier_key(&pks);
+
+		initialize_block(1, 100.into(), Default::default(), pair);
+
+		// Ring context set before the introduction of the builder parameters.
+		EmptyRingBuilder::<Test>::kill();
+		Sassafras::start_ring_verifier_key_build(&next_authorities);
+
+		assert!(RingVerifierKeyBuilder::<Test>::get().is_none());
+		assert_eq!(RingVerifierData::<Test>::get().unwrap().encode(), expected.encode());
+	})
+}
+
+#[test]
+fn migrate_v0_to_v1_initializes_ring_builder() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	let (_, mut ext) = new_test_ext_with_pairs(4, true);
+
+	ext.execute_with(|| {
+		let builder = EmptyRingBuilder::<Test>::take().unwrap();
+		let params = RingBuilderParams::<Test>::iter().collect::<Vec<_>>();
+		let _ = RingBuilderParams::<Test>::clear(u32::MAX, None);
+		StorageVersion::new(0).put::<Sassafras>();
+
+		crate::migrations::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Sassafras::on_chain_storage_version(), 1);
+		assert_eq!(EmptyRingBuilder::<Test>::get().unwrap().encode(), builder.encode());
+		let mut migrated = RingBuilderParams::<Test>::iter().collect::<Vec<_>>();
+		migrated.sort_by_key(|(page, _)| *page);
+		let mut params = params;
+		params.sort_by_key(|(page, _)| *page);
+		assert_eq!(migrated.encode(), params.encode());
+	})
+}
+
 // For this test we use a set of pre-constructed tickets from a file.
 // Creating a large set of tickets on the fly takes time, and may be annoying
 // for test execution.
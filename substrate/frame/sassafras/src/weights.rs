@@ -57,6 +57,7 @@ pub trait WeightInfo {
 	fn submit_tickets(x: u32, ) -> Weight;
 	fn plan_config_change() -> Weight;
 	fn update_ring_verifier(x: u32, ) -> Weight;
+	fn ring_verifier_key_build_step(x: u32, ) -> Weight;
 	fn load_ring_context() -> Weight;
 	fn sort_segments(x: u32, ) -> Weight;
 }
@@ -209,6 +210,29 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Sassafras::RingVerifierKeyBuilder` (r:1 w:1)
+	/// Proof: `Sassafras::RingVerifierKeyBuilder` (`max_values`: Some(1), `max_size`: Some(518), added: 1013, mode: `MaxEncodedLen`)
+	/// Storage: `Sassafras::NextAuthorities` (r:1 w:0)
+	/// Proof: `Sassafras::NextAuthorities` (`max_values`: Some(1), `max_size`: Some(3302), added: 3797, mode: `MaxEncodedLen`)
+	/// Storage: `Sassafras::RingBuilderParams` (r:1 w:0)
+	/// Proof: `Sassafras::RingBuilderParams` (`max_values`: None, `max_size`: Some(12294), added: 14769, mode: `MaxEncodedLen`)
+	/// Storage: `Sassafras::RingVerifierData` (r:0 w:1)
+	/// Proof: `Sassafras::RingVerifierData` (`max_values`: Some(1), `max_size`: Some(388), added: 883, mode: `MaxEncodedLen`)
+	/// The range of component `x` is `[1, 100]`.
+	///
+	/// Not generated on the reference machine: measured natively and scaled by the ratio
+	/// between the native and the reference `update_ring_verifier` execution times.
+	fn ring_verifier_key_build_step(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `13188 + x * (32 ±0)`
+		//  Estimated: `15759`
+		// Minimum execution time: 5_049_793_000 picoseconds.
+		Weight::from_parts(6_020_505_089, 15759)
+			// Standard Error: 3_883_657
+			.saturating_add(Weight::from_parts(86_142_927, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 	/// Storage: `Sassafras::RingContext` (r:1 w:0)
 	/// Proof: `Sassafras::RingContext` (`max_values`: Some(1), `max_size`: Some(590324), added: 590819, mode: `MaxEncodedLen`)
 	fn load_ring_context() -> Weight {
@@ -390,6 +414,29 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Sassafras::RingVerifierKeyBuilder` (r:1 w:1)
+	/// Proof: `Sassafras::RingVerifierKeyBuilder` (`max_values`: Some(1), `max_size`: Some(518), added: 1013, mode: `MaxEncodedLen`)
+	/// Storage: `Sassafras::NextAuthorities` (r:1 w:0)
+	/// Proof: `Sassafras::NextAuthorities` (`max_values`: Some(1), `max_size`: Some(3302), added: 3797, mode: `MaxEncodedLen`)
+	/// Storage: `Sassafras::RingBuilderParams` (r:1 w:0)
+	/// Proof: `Sassafras::RingBuilderParams` (`max_values`: None, `max_size`: Some(12294), added: 14769, mode: `MaxEncodedLen`)
+	/// Storage: `Sassafras::RingVerifierData` (r:0 w:1)
+	/// Proof: `Sassafras::RingVerifierData` (`max_values`: Some(1), `max_size`: Some(388), added: 883, mode: `MaxEncodedLen`)
+	/// The range of component `x` is `[1, 100]`.
+	///
+	/// Not generated on the reference machine: measured natively and scaled by the ratio
+	/// between the native and the reference `update_ring_verifier` execution times.
+	fn ring_verifier_key_build_step(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `13188 + x * (32 ±0)`
+		//  Estimated: `15759`
+		// Minimum execution time: 5_049_793_000 picoseconds.
+		Weight::from_parts(6_020_505_089, 15759)
+			// Standard Error: 3_883_657
+			.saturating_add(Weight::from_parts(86_142_927, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 	/// Storage: `Sassafras::RingContext` (r:1 w:0)
 	/// Proof: `Sassafras::RingContext` (`max_values`: Some(1), `max_size`: Some(590324), added: 590819, mode: `MaxEncodedLen`)
 	fn load_ring_context() -> Weight {
@@ -86,6 +86,33 @@ pub trait AuthorityIdBound:
 	type BoundedSignature: Debug + Eq + PartialEq + Clone + TypeInfo + Codec + Send + Sync;
 }
 
+/// BEEFY authority id whose signatures can be aggregated into a single BLS signature.
+#[cfg(feature = "bls-experimental")]
+pub trait AggregatableAuthorityId: RuntimeAppPublic + Sized {
+	/// Verify the aggregate `signature` of the same `msg` by all the `authorities`.
+	///
+	/// The check is secure only if the proof of possession of all the `authorities`
+	/// has been verified beforehand, see [`Self::verify_proof_of_possession`].
+	fn verify_aggregate(
+		authorities: &[Self],
+		signature: &bls_crypto::AggregateSignature,
+		msg: &[u8],
+	) -> bool;
+
+	/// Verify the aggregate `signature` of a message by each of the `signers`.
+	///
+	/// Each authority is paired with the message it signed, thus the signers may have signed
+	/// different messages. The check is secure only if the proof of possession of all the
+	/// signers has been verified beforehand.
+	fn aggregate_verify(
+		signers: &[(Self, Vec<u8>)],
+		signature: &bls_crypto::AggregateSignature,
+	) -> bool;
+
+	/// Verify the `proof` of possession of the BLS secret key of this authority.
+	fn verify_proof_of_possession(&self, proof: &bls_crypto::ProofOfPossession) -> bool;
+}
+
 /// BEEFY cryptographic types for ECDSA crypto
 ///
 /// This module basically introduces four crypto types:
@@ -142,7 +169,11 @@ pub mod ecdsa_crypto {
 
 #[cfg(feature = "bls-experimental")]
 pub mod bls_crypto {
-	use super::{AuthorityIdBound, BeefyAuthorityId, Hash, RuntimeAppPublic, KEY_TYPE};
+	use super::{
+		AggregatableAuthorityId, AuthorityIdBound, BeefyAuthorityId, Hash, RuntimeAppPublic,
+		KEY_TYPE,
+	};
+	use alloc::vec::Vec;
 	use sp_application_crypto::{app_crypto, bls381};
 	use sp_core::{bls381::Pair as BlsPair, crypto::Wraps, Pair as _};
 
@@ -154,6 +185,45 @@ pub mod bls_crypto {
 	/// Signature for a BEEFY authority using BLS as its crypto.
 	pub type AuthoritySignature = Signature;
 
+	/// Aggregate of the signatures of a set of BEEFY authorities using BLS as their crypto.
+	pub type AggregateSignature = sp_core::bls381::AggregateSignature;
+
+	/// Proof of possession of the secret key of a BEEFY authority using BLS as its crypto.
+	pub type ProofOfPossession = sp_core::bls381::ProofOfPossession;
+
+	/// Aggregate the given BEEFY authorities `signatures`.
+	///
+	/// Returns `None` if `signatures` is empty or any of the signatures is malformed.
+	pub fn aggregate_signatures(signatures: &[Signature]) -> Option<AggregateSignature> {
+		let signatures: Vec<_> = signatures.iter().map(|sig| *sig.as_inner_ref()).collect();
+		sp_core::bls381::aggregate_signatures(&signatures)
+	}
+
+	/// Verify the aggregate `signature` of the same `msg` by all the `authorities`.
+	///
+	/// The check is performed via a single host call, which is significantly cheaper than
+	/// the verification of each authority signature in the runtime.
+	pub fn verify_aggregate(
+		authorities: &[AuthorityId],
+		signature: &AggregateSignature,
+		msg: &[u8],
+	) -> bool {
+		let pub_keys: Vec<_> = authorities.iter().map(|id| *id.as_inner_ref()).collect();
+		sp_io::crypto::bls381_fast_aggregate_verify(signature, msg, &pub_keys)
+	}
+
+	/// Verify the aggregate `signature` of a message by each of the `signers`.
+	///
+	/// Contrary to [`verify_aggregate`], the signers may have signed different messages.
+	pub fn aggregate_verify(
+		signers: &[(AuthorityId, Vec<u8>)],
+		signature: &AggregateSignature,
+	) -> bool {
+		let signers: Vec<_> =
+			signers.iter().map(|(id, msg)| (*id.as_inner_ref(), msg.clone())).collect();
+		sp_io::crypto::bls381_aggregate_verify(signature, &signers)
+	}
+
 	impl<MsgHash: Hash> BeefyAuthorityId<MsgHash> for AuthorityId
 	where
 		<MsgHash as Hash>::Output: Into<[u8; 32]>,
@@ -170,6 +240,24 @@ pub mod bls_crypto {
 	impl AuthorityIdBound for AuthorityId {
 		type BoundedSignature = Signature;
 	}
+
+	impl AggregatableAuthorityId for AuthorityId {
+		fn verify_aggregate(
+			authorities: &[Self],
+			signature: &AggregateSignature,
+			msg: &[u8],
+		) -> bool {
+			verify_aggregate(authorities, signature, msg)
+		}
+
+		fn aggregate_verify(signers: &[(Self, Vec<u8>)], signature: &AggregateSignature) -> bool {
+			aggregate_verify(signers, signature)
+		}
+
+		fn verify_proof_of_possession(&self, proof: &ProofOfPossession) -> bool {
+			sp_io::crypto::bls381_verify_proof_of_possession(proof, self.as_inner_ref())
+		}
+	}
 }
 
 /// BEEFY cryptographic types for (ECDSA,BLS) crypto pair
@@ -184,9 +272,13 @@ pub mod bls_crypto {
 /// functionality.
 #[cfg(feature = "bls-experimental")]
 pub mod ecdsa_bls_crypto {
-	use super::{AuthorityIdBound, BeefyAuthorityId, Hash, RuntimeAppPublic, KEY_TYPE};
+	use super::{
+		AggregatableAuthorityId, AuthorityIdBound, BeefyAuthorityId, Hash, RuntimeAppPublic,
+		KEY_TYPE,
+	};
+	use alloc::vec::Vec;
 	use sp_application_crypto::{app_crypto, ecdsa_bls381};
-	use sp_core::{crypto::Wraps, ecdsa_bls381::Pair as EcdsaBlsPair};
+	use sp_core::{bls381, crypto::Wraps, ecdsa, ecdsa_bls381::Pair as EcdsaBlsPair};
 
 	app_crypto!(ecdsa_bls381, KEY_TYPE);
 
@@ -196,6 +288,69 @@ pub mod ecdsa_bls_crypto {
 	/// Signature for a BEEFY authority using (ECDSA,BLS) as its crypto.
 	pub type AuthoritySignature = Signature;
 
+	/// Aggregate of the BLS components of the signatures of a set of BEEFY authorities
+	/// using (ECDSA,BLS) as their crypto.
+	pub type AggregateSignature = bls381::AggregateSignature;
+
+	/// Proof of possession of the BLS component of the secret key of a BEEFY authority
+	/// using (ECDSA,BLS) as its crypto.
+	pub type ProofOfPossession = bls381::ProofOfPossession;
+
+	/// Aggregate the BLS components of the given BEEFY authorities `signatures`.
+	///
+	/// Returns `None` if `signatures` is empty or any of the signatures is malformed.
+	pub fn aggregate_signatures(signatures: &[Signature]) -> Option<AggregateSignature> {
+		let signatures = signatures
+			.iter()
+			.map(|sig| {
+				let raw: &[u8] = sig.as_inner_ref().as_ref();
+				bls381::Signature::try_from(&raw[ecdsa::SIGNATURE_SERIALIZED_SIZE..]).ok()
+			})
+			.collect::<Option<Vec<_>>>()?;
+		bls381::aggregate_signatures(&signatures)
+	}
+
+	/// Verify the aggregate `signature` of the same `msg` by all the `authorities`.
+	///
+	/// Only the BLS components of the authorities keys are used, thus the check is
+	/// performed via a single BLS fast aggregate verification.
+	pub fn verify_aggregate(
+		authorities: &[AuthorityId],
+		signature: &AggregateSignature,
+		msg: &[u8],
+	) -> bool {
+		let pub_keys = authorities
+			.iter()
+			.map(|id| {
+				let raw: &[u8] = id.as_inner_ref().as_ref();
+				bls381::Public::try_from(&raw[ecdsa::PUBLIC_KEY_SERIALIZED_SIZE..]).ok()
+			})
+			.collect::<Option<Vec<_>>>();
+		let Some(pub_keys) = pub_keys else { return false };
+		sp_io::crypto::bls381_fast_aggregate_verify(signature, msg, &pub_keys)
+	}
+
+	/// Verify the aggregate `signature` of a message by each of the `signers`.
+	///
+	/// Contrary to [`verify_aggregate`], the signers may have signed different messages.
+	/// Only the BLS components of the authorities keys are used.
+	pub fn aggregate_verify(
+		signers: &[(AuthorityId, Vec<u8>)],
+		signature: &AggregateSignature,
+	) -> bool {
+		let signers = signers
+			.iter()
+			.map(|(id, msg)| {
+				let raw: &[u8] = id.as_inner_ref().as_ref();
+				bls381::Public::try_from(&raw[ecdsa::PUBLIC_KEY_SERIALIZED_SIZE..])
+					.ok()
+					.map(|public| (public, msg.clone()))
+			})
+			.collect::<Option<Vec<_>>>();
+		let Some(signers) = signers else { return false };
+		sp_io::crypto::bls381_aggregate_verify(signature, &signers)
+	}
+
 	impl<H> BeefyAuthorityId<H> for AuthorityId
 	where
 		H: Hash,
@@ -220,6 +375,29 @@ pub mod ecdsa_bls_crypto {
 	impl AuthorityIdBound for AuthorityId {
 		type BoundedSignature = Signature;
 	}
+
+	impl AggregatableAuthorityId for AuthorityId {
+		fn verify_aggregate(
+			authorities: &[Self],
+			signature: &AggregateSignature,
+			msg: &[u8],
+		) -> bool {
+			verify_aggregate(authorities, signature, msg)
+		}
+
+		fn aggregate_verify(signers: &[(Self, Vec<u8>)], signature: &AggregateSignature) -> bool {
+			aggregate_verify(signers, signature)
+		}
+
+		fn verify_proof_of_possession(&self, proof: &ProofOfPossession) -> bool {
+			let raw: &[u8] = self.as_inner_ref().as_ref();
+			let Ok(public) = bls381::Public::try_from(&raw[ecdsa::PUBLIC_KEY_SERIALIZED_SIZE..])
+			else {
+				return false
+			};
+			sp_io::crypto::bls381_verify_proof_of_possession(proof, &public)
+		}
+	}
 }
 
 /// The `ConsensusEngineId` of BEEFY.
@@ -386,6 +564,78 @@ where
 	BeefyAuthorityId::<MsgHash>::verify(authority_id, signature, &encoded_commitment)
 }
 
+/// Check an aggregate commitment signature by encoding the commitment and
+/// verifying the provided aggregate signature using the expected authorities ids.
+///
+/// The `authorities` are the ones whose signatures have been aggregated.
+#[cfg(feature = "bls-experimental")]
+pub fn check_aggregate_commitment_signature<Number, Id>(
+	commitment: &Commitment<Number>,
+	authorities: &[Id],
+	signature: &bls_crypto::AggregateSignature,
+) -> bool
+where
+	Id: AggregatableAuthorityId,
+	Number: Clone + Encode + PartialEq,
+{
+	let encoded_commitment = commitment.encode();
+	Id::verify_aggregate(authorities, signature, &encoded_commitment)
+}
+
+/// Check an aggregate signature of several commitments.
+///
+/// Each commitment is paired with the authorities whose signatures of it have been
+/// aggregated, thus a single signature can cover different commitments (e.g. commitments
+/// of different blocks). The check fails if no authority is given.
+#[cfg(feature = "bls-experimental")]
+pub fn check_aggregate_commitments_signature<Number, Id>(
+	commitments: &[(Commitment<Number>, Vec<Id>)],
+	signature: &bls_crypto::AggregateSignature,
+) -> bool
+where
+	Id: AggregatableAuthorityId + Clone,
+	Number: Clone + Encode + PartialEq,
+{
+	let signers: Vec<_> = commitments
+		.iter()
+		.flat_map(|(commitment, authorities)| {
+			let encoded_commitment = commitment.encode();
+			authorities.iter().map(move |id| (id.clone(), encoded_commitment.clone()))
+		})
+		.collect();
+	!signers.is_empty() && Id::aggregate_verify(&signers, signature)
+}
+
+/// Check an aggregate commitment signature of the validators of `validator_set`
+/// selected by `signers`.
+///
+/// `signers` must have an entry for each validator of the set, telling whether its
+/// signature has been aggregated or not. The check fails if the commitment doesn't
+/// target the given validator set or if no validator is selected.
+#[cfg(feature = "bls-experimental")]
+pub fn check_validator_set_aggregate_signature<Number, Id>(
+	commitment: &Commitment<Number>,
+	validator_set: &ValidatorSet<Id>,
+	signers: &[bool],
+	signature: &bls_crypto::AggregateSignature,
+) -> bool
+where
+	Id: AggregatableAuthorityId + Clone,
+	Number: Clone + Encode + PartialEq,
+{
+	if commitment.validator_set_id != validator_set.id() || signers.len() != validator_set.len() {
+		return false
+	}
+	let authorities: Vec<_> = validator_set
+		.validators()
+		.iter()
+		.zip(signers)
+		.filter_map(|(id, signed)| signed.then(|| id.clone()))
+		.collect();
+	!authorities.is_empty() &&
+		check_aggregate_commitment_signature(commitment, &authorities, signature)
+}
+
 /// Verifies the equivocation proof by making sure that both votes target
 /// different blocks and that its signatures are valid.
 pub fn check_double_voting_proof<Number, Id, MsgHash>(
@@ -564,6 +814,22 @@ sp_api::decl_runtime_apis! {
 
 }
 
+#[cfg(feature = "bls-experimental")]
+sp_api::decl_runtime_apis! {
+	/// API for checking BEEFY commitments signed by an aggregate BLS signature.
+	pub trait BeefyAggregateApi {
+		/// Check the aggregate `signature` of `commitment` by the current BEEFY validators
+		/// selected by `signers`.
+		///
+		/// See [`check_validator_set_aggregate_signature`] for the details.
+		fn check_aggregate_commitment_signature(
+			commitment: Commitment<NumberFor<Block>>,
+			signers: Vec<bool>,
+			signature: bls_crypto::AggregateSignature,
+		) -> bool;
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -641,6 +907,152 @@ mod tests {
 		assert!(!BeefyAuthorityId::<Keccak256>::verify(&other_pair.public(), &signature, msg,));
 	}
 
+	#[test]
+	#[cfg(feature = "bls-experimental")]
+	fn bls_beefy_aggregate_verify_works() {
+		let pairs: Vec<_> = (0..3).map(|_| bls_crypto::Pair::generate().0).collect();
+		let authorities: Vec<_> = pairs.iter().map(|p| p.public()).collect();
+		let msg = &b"test-message"[..];
+		let signatures: Vec<bls_crypto::Signature> =
+			pairs.iter().map(|p| p.as_inner_ref().sign(&msg).into()).collect();
+
+		let aggregate = bls_crypto::aggregate_signatures(&signatures).unwrap();
+		assert!(bls_crypto::verify_aggregate(&authorities, &aggregate, msg));
+		assert!(!bls_crypto::verify_aggregate(&authorities[1..], &aggregate, msg));
+		assert!(!bls_crypto::verify_aggregate(&authorities, &aggregate, b"other-message"));
+	}
+
+	#[test]
+	#[cfg(feature = "bls-experimental")]
+	fn ecdsa_bls_beefy_aggregate_verify_works() {
+		let pairs: Vec<_> = (0..3).map(|_| ecdsa_bls_crypto::Pair::generate().0).collect();
+		let authorities: Vec<_> = pairs.iter().map(|p| p.public()).collect();
+		let msg = &b"test-message"[..];
+		let signatures: Vec<ecdsa_bls_crypto::Signature> = pairs
+			.iter()
+			.map(|p| p.as_inner_ref().sign_with_hasher::<Keccak256>(&msg).into())
+			.collect();
+
+		let aggregate = ecdsa_bls_crypto::aggregate_signatures(&signatures).unwrap();
+		assert!(ecdsa_bls_crypto::verify_aggregate(&authorities, &aggregate, msg));
+		assert!(!ecdsa_bls_crypto::verify_aggregate(&authorities[..2], &aggregate, msg));
+		assert!(!ecdsa_bls_crypto::verify_aggregate(&authorities, &aggregate, b"other-message"));
+	}
+
+	#[test]
+	#[cfg(feature = "bls-experimental")]
+	fn proof_of_possession_works() {
+		let (pair, _) = bls_crypto::Pair::generate();
+		let (other_pair, _) = bls_crypto::Pair::generate();
+		let proof = pair.as_inner_ref().generate_proof_of_possession();
+		assert!(pair.public().verify_proof_of_possession(&proof));
+		assert!(!other_pair.public().verify_proof_of_possession(&proof));
+
+		let (pair, _) = ecdsa_bls_crypto::Pair::generate();
+		let (other_pair, _) = ecdsa_bls_crypto::Pair::generate();
+		let proof = pair.as_inner_ref().generate_proof_of_possession();
+		assert!(pair.public().verify_proof_of_possession(&proof));
+		assert!(!other_pair.public().verify_proof_of_possession(&proof));
+	}
+
+	#[test]
+	#[cfg(feature = "bls-experimental")]
+	fn check_aggregate_commitments_signature_works() {
+		let pairs: Vec<_> = (0..4).map(|_| ecdsa_bls_crypto::Pair::generate().0).collect();
+		let commitments: Vec<_> = (5u64..7)
+			.map(|block_number| {
+				let payload = Payload::from_single_entry(
+					known_payloads::MMR_ROOT_ID,
+					vec![block_number as u8],
+				);
+				Commitment { payload, block_number, validator_set_id: 3 }
+			})
+			.collect();
+
+		// Validators 0 and 1 signed the first commitment, 2 and 3 the second one.
+		let signed: Vec<_> = commitments
+			.iter()
+			.zip(pairs.chunks(2))
+			.map(|(commitment, pairs)| {
+				(commitment.clone(), pairs.iter().map(|p| p.public()).collect::<Vec<_>>())
+			})
+			.collect();
+		let signatures: Vec<ecdsa_bls_crypto::Signature> = commitments
+			.iter()
+			.zip(pairs.chunks(2))
+			.flat_map(|(commitment, pairs)| {
+				let encoded_commitment = commitment.encode();
+				pairs.iter().map(move |p| {
+					p.as_inner_ref().sign_with_hasher::<Keccak256>(&encoded_commitment).into()
+				})
+			})
+			.collect();
+		let aggregate = ecdsa_bls_crypto::aggregate_signatures(&signatures).unwrap();
+
+		assert!(check_aggregate_commitments_signature(&signed, &aggregate));
+		// Signers of the commitments swapped.
+		let swapped = vec![
+			(signed[0].0.clone(), signed[1].1.clone()),
+			(signed[1].0.clone(), signed[0].1.clone()),
+		];
+		assert!(!check_aggregate_commitments_signature(&swapped, &aggregate));
+		// Missing commitment.
+		assert!(!check_aggregate_commitments_signature(&signed[..1], &aggregate));
+		assert!(!check_aggregate_commitments_signature::<u64, ecdsa_bls_crypto::AuthorityId>(
+			&[],
+			&aggregate
+		));
+	}
+
+	#[test]
+	#[cfg(feature = "bls-experimental")]
+	fn check_validator_set_aggregate_signature_works() {
+		let pairs: Vec<_> = (0..4).map(|_| bls_crypto::Pair::generate().0).collect();
+		let validator_set = ValidatorSet::new(pairs.iter().map(|p| p.public()), 3).unwrap();
+		let payload = Payload::from_single_entry(known_payloads::MMR_ROOT_ID, vec![42]);
+		let commitment = Commitment { payload, block_number: 5u64, validator_set_id: 3 };
+		let encoded_commitment = commitment.encode();
+
+		// Validators 0, 1 and 3 signed the commitment.
+		let signers = vec![true, true, false, true];
+		let signatures: Vec<bls_crypto::Signature> = pairs
+			.iter()
+			.zip(&signers)
+			.filter(|(_, signed)| **signed)
+			.map(|(p, _)| p.as_inner_ref().sign(&encoded_commitment).into())
+			.collect();
+		let aggregate = bls_crypto::aggregate_signatures(&signatures).unwrap();
+
+		assert!(check_validator_set_aggregate_signature(
+			&commitment,
+			&validator_set,
+			&signers,
+			&aggregate
+		));
+		// Wrong signers.
+		assert!(!check_validator_set_aggregate_signature(
+			&commitment,
+			&validator_set,
+			&[true, true, true, false],
+			&aggregate
+		));
+		// Signers not matching the validator set size.
+		assert!(!check_validator_set_aggregate_signature(
+			&commitment,
+			&validator_set,
+			&signers[..3],
+			&aggregate
+		));
+		// Commitment for another validator set.
+		let other_commitment = Commitment { validator_set_id: 4, ..commitment.clone() };
+		assert!(!check_validator_set_aggregate_signature(
+			&other_commitment,
+			&validator_set,
+			&signers,
+			&aggregate
+		));
+	}
+
 	#[test]
 	#[cfg(feature = "bls-experimental")]
 	fn ecdsa_bls_beefy_verify_works() {
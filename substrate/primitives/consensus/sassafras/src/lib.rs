@@ -35,6 +35,7 @@ pub use sp_consensus_slots::{Slot, SlotDuration};
 use serde::{Deserialize, Serialize};
 
 pub mod digests;
+pub mod inherents;
 pub mod ticket;
 pub mod vrf;
 
@@ -146,10 +147,15 @@ pub struct OpaqueKeyOwnershipProof(Vec<u8>);
 // Runtime API.
 sp_api::decl_runtime_apis! {
 	/// API necessary for block authorship with Sassafras.
+	#[api_version(2)]
 	pub trait SassafrasApi {
 		/// Get ring context to be used for ticket construction and verification.
 		fn ring_context() -> Option<vrf::RingContext>;
 
+		/// Slot duration.
+		#[api_version(2)]
+		fn slot_duration() -> SlotDuration;
+
 		/// Submit next epoch validator tickets via an unsigned extrinsic.
 		/// This method returns `false` when creation of the extrinsics fails.
 		fn submit_tickets_unsigned_extrinsic(tickets: Vec<TicketEnvelope>) -> bool;
@@ -160,6 +166,16 @@ sp_api::decl_runtime_apis! {
 		/// Get ticket id and data associated to the given slot.
 		fn slot_ticket(slot: Slot) -> Option<(TicketId, TicketBody)>;
 
+		/// Get the data of a ticket submitted on-chain.
+		#[api_version(3)]
+		fn ticket_body(ticket_id: TicketId) -> Option<TicketBody>;
+
+		/// Whether the ring verifier key of the next epoch authorities is available.
+		///
+		/// Next epoch tickets are rejected until it is.
+		#[api_version(3)]
+		fn ring_verifier_key_ready() -> bool;
+
 		/// Current epoch information.
 		fn current_epoch() -> Epoch;
 
@@ -24,7 +24,10 @@ use codec::Encode;
 use sp_consensus_slots::Slot;
 
 pub use sp_core::bandersnatch::{
-	ring_vrf::{RingProver, RingVerifier, RingVerifierKey, RingVrfSignature},
+	ring_vrf::{
+		RingBuilderParamsItem, RingProver, RingVerifier, RingVerifierKey, RingVerifierKeyBuilder,
+		RingVrfSignature,
+	},
 	vrf::{VrfInput, VrfPreOutput, VrfSignData, VrfSignature},
 };
 
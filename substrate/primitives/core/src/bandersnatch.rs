@@ -191,6 +191,9 @@ pub mod vrf {
 	use super::*;
 	use crate::crypto::VrfCrypto;
 
+	/// [`VrfInput`] serialized size.
+	pub const INPUT_SERIALIZED_SIZE: usize = 32;
+
 	/// [`VrfSignature`] serialized size.
 	pub const VRF_SIGNATURE_SERIALIZED_SIZE: usize =
 		PREOUT_SERIALIZED_SIZE + SIGNATURE_SERIALIZED_SIZE;
@@ -208,6 +211,25 @@ pub mod vrf {
 		}
 	}
 
+	impl Encode for VrfInput {
+		fn encode(&self) -> Vec<u8> {
+			let mut bytes = [0; INPUT_SERIALIZED_SIZE];
+			self.0
+				.serialize_compressed(bytes.as_mut_slice())
+				.expect("serialization length is constant and checked by test; qed");
+			bytes.encode()
+		}
+	}
+
+	impl Decode for VrfInput {
+		fn decode<R: codec::Input>(i: &mut R) -> Result<Self, codec::Error> {
+			let buf = <[u8; INPUT_SERIALIZED_SIZE]>::decode(i)?;
+			let input = bandersnatch::Input::deserialize_compressed(buf.as_slice())
+				.map_err(|_| "vrf-input decode error: bad input")?;
+			Ok(VrfInput(input))
+		}
+	}
+
 	/// VRF pre-output derived from [`VrfInput`] using a [`VrfSecret`].
 	///
 	/// This object is hashed to produce the actual VRF output.
@@ -268,7 +290,10 @@ pub mod vrf {
 	///
 	/// The `input` is a [`VrfInput`]s which, during the signing procedure, is first mapped
 	/// to a [`VrfPreOutput`].
-	#[derive(Clone)]
+	///
+	/// The object is SCALE encodable so that it can be forwarded to a signer
+	/// living outside of the node process.
+	#[derive(Clone, Encode, Decode)]
 	pub struct VrfSignData {
 		/// VRF input.
 		pub vrf_input: VrfInput,
@@ -372,8 +397,10 @@ pub mod vrf {
 /// Bandersnatch Ring-VRF types and operations.
 pub mod ring_vrf {
 	use super::{vrf::*, *};
+	use ark_vrf::ring::{G1Affine, RingVerifierKeyBuilder as VerifierKeyBuilder};
 	use bandersnatch::{RingProofParams, RingVerifierKey as RingVerifierKeyImpl};
 	pub use bandersnatch::{RingProver, RingVerifier};
+	use core::ops::Range;
 
 	// Max size of serialized ring-vrf context given `domain_len`.
 	pub(crate) fn ring_context_serialized_size(ring_size: usize) -> usize {
@@ -387,6 +414,12 @@ pub mod ring_vrf {
 			G2_POINTS_NUM * G2_POINT_UNCOMPRESSED_SIZE
 	}
 
+	/// Maximum ring size accepted by [`RingVerifierKey::into_verifier`].
+	///
+	/// The verifier allocates a domain proportional to the ring size, thus the size
+	/// must be bounded when it is not trusted (e.g. when provided by the runtime).
+	pub const MAX_RING_SIZE: usize = 1 << 14;
+
 	/// [`RingVerifierKey`] serialized size.
 	pub const RING_VERIFIER_KEY_SERIALIZED_SIZE: usize = 384;
 	/// [`RingProof`] serialized size.
@@ -418,6 +451,17 @@ pub mod ring_vrf {
 		}
 	}
 
+	impl RingVerifierKey {
+		/// Constructs a [`RingVerifier`] for a ring with at most `ring_size` members.
+		///
+		/// Equivalent to [`RingContext::verifier_no_context`], but with the ring size
+		/// provided at runtime. This is useful when the ring size is not known at compile
+		/// time, e.g. when the key is received by a host function.
+		pub fn into_verifier(self, ring_size: usize) -> RingVerifier {
+			RingProofParams::verifier_no_context(self.0, ring_size)
+		}
+	}
+
 	impl EncodeLike for RingVerifierKey {}
 
 	impl MaxEncodedLen for RingVerifierKey {
@@ -477,18 +521,181 @@ pub mod ring_vrf {
 		/// retain the full `RingContext` for ring signature verification. Instead, the
 		/// `VerifierKey` contains only the essential information needed to verify ring proofs.
 		pub fn verifier_no_context(verifier_key: RingVerifierKey) -> RingVerifier {
-			RingProofParams::verifier_no_context(verifier_key.0, R)
+			verifier_key.into_verifier(R)
+		}
+
+		/// Construct an empty [`RingVerifierKeyBuilder`] together with the parameters
+		/// required to append keys to it.
+		///
+		/// The returned parameters are indexed by ring position, i.e. the item at index `i`
+		/// is required to append the key at position `i` of the ring.
+		pub fn verifier_key_builder(&self) -> (RingVerifierKeyBuilder, Vec<RingBuilderParamsItem>) {
+			let (builder, params) = self.0.verifier_key_builder();
+			let params = params.0.into_iter().map(RingBuilderParamsItem).collect();
+			(RingVerifierKeyBuilder(builder), params)
 		}
 
 		fn make_ring_vector(public_keys: &[Public]) -> Vec<bandersnatch::AffinePoint> {
-			use bandersnatch::AffinePoint;
-			public_keys
-				.iter()
-				.map(|pk| {
-					AffinePoint::deserialize_compressed_unchecked(pk.as_slice())
-						.unwrap_or(RingProofParams::padding_point())
-				})
-				.collect()
+			make_ring_vector(public_keys)
+		}
+	}
+
+	fn make_ring_vector(public_keys: &[Public]) -> Vec<bandersnatch::AffinePoint> {
+		use bandersnatch::AffinePoint;
+		public_keys
+			.iter()
+			.map(|pk| {
+				AffinePoint::deserialize_compressed_unchecked(pk.as_slice())
+					.unwrap_or(RingProofParams::padding_point())
+			})
+			.collect()
+	}
+
+	/// [`RingBuilderParamsItem`] serialized size.
+	pub const RING_BUILDER_PARAMS_ITEM_SERIALIZED_SIZE: usize = 48;
+	/// [`RingVerifierKeyBuilder`] max serialized size.
+	pub const RING_VERIFIER_KEY_BUILDER_MAX_SERIALIZED_SIZE: usize = 512;
+
+	/// Item of the parameters required to append keys to a [`RingVerifierKeyBuilder`].
+	///
+	/// This is a point of the ring SRS Lagrangian basis in G1.
+	#[derive(Clone, Debug, PartialEq, Eq)]
+	pub struct RingBuilderParamsItem(G1Affine<BandersnatchSuite>);
+
+	impl Encode for RingBuilderParamsItem {
+		fn encode(&self) -> Vec<u8> {
+			let mut buf = Vec::with_capacity(RING_BUILDER_PARAMS_ITEM_SERIALIZED_SIZE);
+			self.0
+				.serialize_compressed(&mut buf)
+				.expect("serialization length is constant and checked by test; qed");
+			buf
+		}
+	}
+
+	impl Decode for RingBuilderParamsItem {
+		fn decode<R: codec::Input>(input: &mut R) -> Result<Self, codec::Error> {
+			let mut buf = [0; RING_BUILDER_PARAMS_ITEM_SERIALIZED_SIZE];
+			input.read(&mut buf[..])?;
+			let item = G1Affine::<BandersnatchSuite>::deserialize_compressed_unchecked(&buf[..])
+				.map_err(|_| "RingBuilderParamsItem decode error")?;
+			Ok(RingBuilderParamsItem(item))
+		}
+	}
+
+	// The item is a fixed size curve point, which is never allocated on the heap.
+	impl DecodeWithMemTracking for RingBuilderParamsItem {}
+
+	impl EncodeLike for RingBuilderParamsItem {}
+
+	impl MaxEncodedLen for RingBuilderParamsItem {
+		fn max_encoded_len() -> usize {
+			RING_BUILDER_PARAMS_ITEM_SERIALIZED_SIZE
+		}
+	}
+
+	impl TypeInfo for RingBuilderParamsItem {
+		type Identity = [u8; RING_BUILDER_PARAMS_ITEM_SERIALIZED_SIZE];
+		fn type_info() -> scale_info::Type {
+			Self::Identity::type_info()
+		}
+	}
+
+	/// Errors returned by [`RingVerifierKeyBuilder::append`].
+	#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+	pub enum RingBuilderError {
+		/// The number of keys exceeds the ring free slots.
+		TooManyKeys,
+		/// Some of the required builder parameters are not available.
+		MissingParams,
+	}
+
+	/// Incremental [`RingVerifierKey`] builder.
+	///
+	/// [`RingContext::verifier_key`] requires the whole keys set at once, which may be too
+	/// expensive for large rings. The builder allows to append the keys in several steps
+	/// (e.g. across several blocks) and then to finalize the verifier key.
+	///
+	/// The verifier key produced by appending `pks` in any number of chunks is equal to
+	/// the one produced by [`RingContext::verifier_key`] for the same `pks`.
+	#[derive(Clone)]
+	pub struct RingVerifierKeyBuilder(VerifierKeyBuilder<BandersnatchSuite>);
+
+	impl RingVerifierKeyBuilder {
+		/// Number of keys which can still be appended.
+		pub fn free_slots(&self) -> usize {
+			self.0.free_slots()
+		}
+
+		/// Append `public_keys` to the ring.
+		///
+		/// The keys are appended starting from the first free ring position. The `lookup`
+		/// function is called to fetch the builder parameters for the range of positions
+		/// of the appended keys.
+		pub fn append(
+			&mut self,
+			public_keys: &[Public],
+			lookup: impl Fn(Range<usize>) -> Option<Vec<RingBuilderParamsItem>>,
+		) -> Result<(), RingBuilderError> {
+			if public_keys.len() > self.free_slots() {
+				return Err(RingBuilderError::TooManyKeys)
+			}
+			let pks = make_ring_vector(public_keys);
+			let missing = core::cell::Cell::new(false);
+			let lookup = |range: Range<usize>| {
+				let items = lookup(range).map(|items| items.into_iter().map(|i| i.0).collect());
+				missing.set(items.is_none());
+				items
+			};
+			self.0.append(&pks, lookup).map_err(|_| {
+				if missing.get() {
+					RingBuilderError::MissingParams
+				} else {
+					RingBuilderError::TooManyKeys
+				}
+			})
+		}
+
+		/// Finalize the builder and produce the ring verifier key.
+		pub fn finalize(self) -> RingVerifierKey {
+			RingVerifierKey(self.0.finalize())
+		}
+	}
+
+	impl Encode for RingVerifierKeyBuilder {
+		fn encode(&self) -> Vec<u8> {
+			let mut buf = Vec::with_capacity(RING_VERIFIER_KEY_BUILDER_MAX_SERIALIZED_SIZE);
+			self.0
+				.serialize_compressed(&mut buf)
+				.expect("serialization to vec can't fail; qed");
+			buf.encode()
+		}
+	}
+
+	impl Decode for RingVerifierKeyBuilder {
+		fn decode<R: codec::Input>(input: &mut R) -> Result<Self, codec::Error> {
+			let buf = Vec::<u8>::decode(input)?;
+			if buf.len() > RING_VERIFIER_KEY_BUILDER_MAX_SERIALIZED_SIZE {
+				return Err("RingVerifierKeyBuilder decode error: too large".into())
+			}
+			let builder = VerifierKeyBuilder::deserialize_compressed_unchecked(buf.as_slice())
+				.map_err(|_| "RingVerifierKeyBuilder decode error")?;
+			Ok(RingVerifierKeyBuilder(builder))
+		}
+	}
+
+	impl EncodeLike for RingVerifierKeyBuilder {}
+
+	impl MaxEncodedLen for RingVerifierKeyBuilder {
+		fn max_encoded_len() -> usize {
+			codec::Compact(RING_VERIFIER_KEY_BUILDER_MAX_SERIALIZED_SIZE as u32).encoded_size() +
+				RING_VERIFIER_KEY_BUILDER_MAX_SERIALIZED_SIZE
+		}
+	}
+
+	impl TypeInfo for RingVerifierKeyBuilder {
+		type Identity = Vec<u8>;
+		fn type_info() -> scale_info::Type {
+			Self::Identity::type_info()
 		}
 	}
 
@@ -565,6 +772,92 @@ pub mod ring_vrf {
 		}
 	}
 
+	/// [`RingVrfPartialSignature`] serialized size.
+	pub const RING_VRF_PARTIAL_SIGNATURE_SERIALIZED_SIZE: usize =
+		PREOUT_SERIALIZED_SIZE + PEDERSEN_PROOF_SERIALIZED_SIZE + SCALAR_SERIALIZED_SIZE;
+	const PEDERSEN_PROOF_SERIALIZED_SIZE: usize = 160;
+	const SCALAR_SERIALIZED_SIZE: usize = 32;
+
+	/// Part of a [`RingVrfSignature`] which requires the secret key.
+	///
+	/// Produced by [`Pair::ring_vrf_sign_partial`] and completed with the ring proof by
+	/// [`RingVrfPartialSignature::finalize`]. This allows the secret key and the [`RingProver`]
+	/// to live in different processes.
+	///
+	/// The blinding factor links the ring signature to the public key of the signer, thus
+	/// the partial signature must not be disclosed to anyone but the owner of the key.
+	#[derive(Clone)]
+	pub struct RingVrfPartialSignature {
+		/// VRF pre-output.
+		pub pre_output: VrfPreOutput,
+		pedersen_proof: bandersnatch::PedersenProof,
+		blinding: bandersnatch::ScalarField,
+	}
+
+	impl Encode for RingVrfPartialSignature {
+		fn encode(&self) -> Vec<u8> {
+			let mut buf = Vec::with_capacity(RING_VRF_PARTIAL_SIGNATURE_SERIALIZED_SIZE);
+			self.pre_output.encode_to(&mut buf);
+			self.pedersen_proof
+				.serialize_compressed(&mut buf)
+				.expect("serialization to vec can't fail; qed");
+			self.blinding
+				.serialize_compressed(&mut buf)
+				.expect("serialization to vec can't fail; qed");
+			buf
+		}
+	}
+
+	impl Decode for RingVrfPartialSignature {
+		fn decode<R: codec::Input>(input: &mut R) -> Result<Self, codec::Error> {
+			let pre_output = VrfPreOutput::decode(input)?;
+			let buf = <[u8; PEDERSEN_PROOF_SERIALIZED_SIZE]>::decode(input)?;
+			let pedersen_proof =
+				bandersnatch::PedersenProof::deserialize_compressed(buf.as_slice())
+					.map_err(|_| "RingVrfPartialSignature decode error: bad proof")?;
+			let buf = <[u8; SCALAR_SERIALIZED_SIZE]>::decode(input)?;
+			let blinding = bandersnatch::ScalarField::deserialize_compressed(buf.as_slice())
+				.map_err(|_| "RingVrfPartialSignature decode error: bad blinding")?;
+			Ok(RingVrfPartialSignature { pre_output, pedersen_proof, blinding })
+		}
+	}
+
+	#[cfg(feature = "full_crypto")]
+	impl Pair {
+		/// Produce the part of a ring-vrf signature which requires the secret key.
+		///
+		/// See [`RingVrfPartialSignature`].
+		pub fn ring_vrf_sign_partial(&self, data: &VrfSignData) -> RingVrfPartialSignature {
+			use ark_vrf::pedersen::Prover;
+			let pre_output_impl = self.secret.output(data.vrf_input.0);
+			let (pedersen_proof, blinding) =
+				self.secret.prove(data.vrf_input.0, pre_output_impl, &data.aux_data);
+			RingVrfPartialSignature {
+				pre_output: VrfPreOutput(pre_output_impl),
+				pedersen_proof,
+				blinding,
+			}
+		}
+	}
+
+	#[cfg(feature = "full_crypto")]
+	impl RingVrfPartialSignature {
+		/// Complete the ring-vrf signature with the ring proof.
+		///
+		/// The result is equivalent to [`Pair::ring_vrf_sign`] using the same `prover`.
+		pub fn finalize(self, prover: &RingProver) -> RingVrfSignature {
+			let proof_impl = bandersnatch::RingProof {
+				ring_proof: prover.prove(self.blinding),
+				pedersen_proof: self.pedersen_proof,
+			};
+			let mut proof = [0; RING_PROOF_SERIALIZED_SIZE];
+			proof_impl
+				.serialize_compressed(proof.as_mut_slice())
+				.expect("serialization length is constant and checked by test; qed");
+			RingVrfSignature { pre_output: self.pre_output, proof }
+		}
+	}
+
 	impl RingVrfSignature {
 		/// Verify a ring-vrf signature.
 		///
@@ -737,6 +1030,19 @@ mod tests {
 		assert_eq!(expected, decoded);
 	}
 
+	#[test]
+	fn vrf_sign_data_encode_decode() {
+		let pair = Pair::from_seed(TEST_SEED);
+
+		let data = VrfSignData::new(b"data", b"aux");
+		let bytes = data.encode();
+		assert_eq!(bytes.len(), INPUT_SERIALIZED_SIZE + 1 + 3);
+
+		let decoded = VrfSignData::decode(&mut bytes.as_slice()).unwrap();
+		assert_eq!(decoded.aux_data, data.aux_data);
+		assert_eq!(pair.vrf_pre_output(&decoded.vrf_input), pair.vrf_pre_output(&data.vrf_input));
+	}
+
 	#[test]
 	fn ring_vrf_sign_verify() {
 		let ring_ctx = TestRingContext::new_testing();
@@ -759,6 +1065,31 @@ mod tests {
 		assert!(signature.ring_vrf_verify(&data, &verifier));
 	}
 
+	#[test]
+	fn ring_vrf_partial_sign_verify() {
+		let ring_ctx = TestRingContext::new_testing();
+
+		let mut pks: Vec<_> =
+			(0..TEST_RING_SIZE).map(|i| Pair::from_seed(&[i as u8; 32]).public()).collect();
+		let pair = Pair::from_seed(TEST_SEED);
+		let prover_idx = 3;
+		pks[prover_idx] = pair.public();
+		let prover = ring_ctx.prover(&pks, prover_idx);
+
+		let data = VrfSignData::new(b"data", b"aux");
+		let partial = pair.ring_vrf_sign_partial(&data);
+
+		let bytes = partial.encode();
+		assert_eq!(bytes.len(), RING_VRF_PARTIAL_SIGNATURE_SERIALIZED_SIZE);
+		let partial = RingVrfPartialSignature::decode(&mut bytes.as_slice()).unwrap();
+
+		let signature = partial.finalize(&prover);
+		assert_eq!(signature.pre_output, pair.ring_vrf_sign(&data, &prover).pre_output);
+
+		let verifier = ring_ctx.verifier(&pks);
+		assert!(signature.ring_vrf_verify(&data, &verifier));
+	}
+
 	#[test]
 	fn ring_vrf_sign_verify_with_out_of_ring_key() {
 		let ring_ctx = TestRingContext::new_testing();
@@ -841,6 +1172,48 @@ mod tests {
 		assert_eq!(enc1, enc2);
 	}
 
+	#[test]
+	fn verifier_key_builder_works() {
+		let ring_ctx = TestRingContext::new_testing();
+
+		let pks: Vec<_> =
+			(0..TEST_RING_SIZE).map(|i| Pair::from_seed(&[i as u8; 32]).public()).collect();
+		let expected = ring_ctx.verifier_key(&pks).encode();
+
+		let (mut builder, params) = ring_ctx.verifier_key_builder();
+		let lookup = |range: core::ops::Range<usize>| params.get(range).map(|items| items.to_vec());
+
+		// Build in several chunks, going through the builder encoding between steps.
+		for chunk in pks.chunks(5) {
+			builder.append(chunk, &lookup).unwrap();
+			let enc = builder.encode();
+			assert!(enc.len() <= RingVerifierKeyBuilder::max_encoded_len());
+			builder = RingVerifierKeyBuilder::decode(&mut enc.as_slice()).unwrap();
+		}
+		assert_eq!(builder.free_slots(), ring_ctx.max_keyset_size() - pks.len());
+		assert_eq!(builder.finalize().encode(), expected);
+
+		// Items encoding.
+		let enc = params[0].encode();
+		assert_eq!(enc.len(), RING_BUILDER_PARAMS_ITEM_SERIALIZED_SIZE);
+		assert_eq!(RingBuilderParamsItem::decode(&mut enc.as_slice()).unwrap(), params[0]);
+	}
+
+	#[test]
+	fn verifier_key_builder_errors() {
+		let ring_ctx = TestRingContext::new_testing();
+
+		let pks: Vec<_> = (0..ring_ctx.max_keyset_size() + 1)
+			.map(|i| Pair::from_seed(&[i as u8; 32]).public())
+			.collect();
+
+		let (mut builder, params) = ring_ctx.verifier_key_builder();
+		let lookup = |range: core::ops::Range<usize>| params.get(range).map(|items| items.to_vec());
+
+		assert_eq!(builder.append(&pks, &lookup), Err(RingBuilderError::TooManyKeys));
+		assert_eq!(builder.append(&pks[..2], |_| None), Err(RingBuilderError::MissingParams));
+	}
+
 	#[test]
 	fn verifier_key_encode_decode() {
 		let ring_ctx = TestRingContext::new_testing();
@@ -31,13 +31,20 @@ use crate::crypto::{
 use alloc::vec::Vec;
 
 use w3f_bls::{
-	DoublePublicKey, DoublePublicKeyScheme, DoubleSignature, EngineBLS, Keypair, Message,
-	SecretKey, SerializableToBytes, TinyBLS381,
+	BLSPoP, DoublePublicKey, DoublePublicKeyScheme, DoubleSignature, EngineBLS, Keypair, Message,
+	PublicKey as SinglePublicKey, SecretKey, SerializableToBytes, Signature as SingleSignature,
+	TinyBLS381,
 };
 
+/// Hasher used by the proofs of possession.
+type ProofOfPossessionHasher = sha2::Sha256;
+
 /// BLS-377 specialized types
 pub mod bls377 {
-	pub use super::{PUBLIC_KEY_SERIALIZED_SIZE, SIGNATURE_SERIALIZED_SIZE};
+	pub use super::{
+		AGGREGATE_SIGNATURE_SERIALIZED_SIZE, PROOF_OF_POSSESSION_SERIALIZED_SIZE,
+		PUBLIC_KEY_SERIALIZED_SIZE, SIGNATURE_SERIALIZED_SIZE,
+	};
 	use crate::crypto::CryptoTypeId;
 	pub(crate) use w3f_bls::TinyBLS377 as BlsEngine;
 
@@ -53,6 +60,45 @@ pub mod bls377 {
 	pub type Public = super::Public<BlsEngine>;
 	/// BLS12-377 signature.
 	pub type Signature = super::Signature<BlsEngine>;
+	/// BLS12-377 aggregate signature.
+	pub type AggregateSignature = super::AggregateSignature<BlsEngine>;
+	/// BLS12-377 proof of possession.
+	pub type ProofOfPossession = super::ProofOfPossession<BlsEngine>;
+
+	/// Aggregate a set of BLS12-377 signatures.
+	///
+	/// See [`super::aggregate_signatures`].
+	pub fn aggregate_signatures(signatures: &[Signature]) -> Option<AggregateSignature> {
+		super::aggregate_signatures(signatures)
+	}
+
+	/// Verify a BLS12-377 aggregate signature of the same `message` by all the `public_keys`.
+	///
+	/// See [`super::fast_aggregate_verify`].
+	pub fn fast_aggregate_verify(
+		signature: &AggregateSignature,
+		message: &[u8],
+		public_keys: &[Public],
+	) -> bool {
+		super::fast_aggregate_verify(signature, message, public_keys)
+	}
+
+	/// Verify a BLS12-377 aggregate signature of a message by each of the signers.
+	///
+	/// See [`super::aggregate_verify`].
+	pub fn aggregate_verify<M: AsRef<[u8]>>(
+		signature: &AggregateSignature,
+		signers: &[(Public, M)],
+	) -> bool {
+		super::aggregate_verify(signature, signers)
+	}
+
+	/// Verify a BLS12-377 proof of possession of the secret key of `public_key`.
+	///
+	/// See [`super::verify_proof_of_possession`].
+	pub fn verify_proof_of_possession(proof: &ProofOfPossession, public_key: &Public) -> bool {
+		super::verify_proof_of_possession(proof, public_key)
+	}
 
 	impl super::HardJunctionId for BlsEngine {
 		const ID: &'static str = "BLS12377HDKD";
@@ -61,7 +107,10 @@ pub mod bls377 {
 
 /// BLS-381 specialized types
 pub mod bls381 {
-	pub use super::{PUBLIC_KEY_SERIALIZED_SIZE, SIGNATURE_SERIALIZED_SIZE};
+	pub use super::{
+		AGGREGATE_SIGNATURE_SERIALIZED_SIZE, PROOF_OF_POSSESSION_SERIALIZED_SIZE,
+		PUBLIC_KEY_SERIALIZED_SIZE, SIGNATURE_SERIALIZED_SIZE,
+	};
 	use crate::crypto::CryptoTypeId;
 	pub(crate) use w3f_bls::TinyBLS381 as BlsEngine;
 
@@ -77,6 +126,45 @@ pub mod bls381 {
 	pub type Public = super::Public<BlsEngine>;
 	/// BLS12-381 signature.
 	pub type Signature = super::Signature<BlsEngine>;
+	/// BLS12-381 aggregate signature.
+	pub type AggregateSignature = super::AggregateSignature<BlsEngine>;
+	/// BLS12-381 proof of possession.
+	pub type ProofOfPossession = super::ProofOfPossession<BlsEngine>;
+
+	/// Aggregate a set of BLS12-381 signatures.
+	///
+	/// See [`super::aggregate_signatures`].
+	pub fn aggregate_signatures(signatures: &[Signature]) -> Option<AggregateSignature> {
+		super::aggregate_signatures(signatures)
+	}
+
+	/// Verify a BLS12-381 aggregate signature of the same `message` by all the `public_keys`.
+	///
+	/// See [`super::fast_aggregate_verify`].
+	pub fn fast_aggregate_verify(
+		signature: &AggregateSignature,
+		message: &[u8],
+		public_keys: &[Public],
+	) -> bool {
+		super::fast_aggregate_verify(signature, message, public_keys)
+	}
+
+	/// Verify a BLS12-381 aggregate signature of a message by each of the signers.
+	///
+	/// See [`super::aggregate_verify`].
+	pub fn aggregate_verify<M: AsRef<[u8]>>(
+		signature: &AggregateSignature,
+		signers: &[(Public, M)],
+	) -> bool {
+		super::aggregate_verify(signature, signers)
+	}
+
+	/// Verify a BLS12-381 proof of possession of the secret key of `public_key`.
+	///
+	/// See [`super::verify_proof_of_possession`].
+	pub fn verify_proof_of_possession(proof: &ProofOfPossession, public_key: &Public) -> bool {
+		super::verify_proof_of_possession(proof, public_key)
+	}
 
 	impl super::HardJunctionId for BlsEngine {
 		const ID: &'static str = "BLS12381HDKD";
@@ -99,6 +187,14 @@ pub const PUBLIC_KEY_SERIALIZED_SIZE: usize =
 pub const SIGNATURE_SERIALIZED_SIZE: usize =
 	<DoubleSignature<TinyBLS381> as SerializableToBytes>::SERIALIZED_BYTES_SIZE;
 
+/// Aggregate signature serialized size
+pub const AGGREGATE_SIGNATURE_SERIALIZED_SIZE: usize =
+	<SingleSignature<TinyBLS381> as SerializableToBytes>::SERIALIZED_BYTES_SIZE;
+
+/// Proof of possession serialized size
+pub const PROOF_OF_POSSESSION_SERIALIZED_SIZE: usize =
+	<SingleSignature<TinyBLS381> as SerializableToBytes>::SERIALIZED_BYTES_SIZE;
+
 /// A secret seed.
 ///
 /// It's not called a "secret key" because ring doesn't expose the secret keys
@@ -123,6 +219,127 @@ impl<T: BlsBound> CryptoType for Signature<T> {
 	type Pair = Pair<T>;
 }
 
+#[doc(hidden)]
+pub struct BlsAggregateTag;
+
+/// A generic BLS aggregate signature.
+///
+/// This is the sum of the signature group components of a set of [`Signature`]s.
+/// Contrary to [`Signature`], it doesn't carry the proof which binds the signature to
+/// the signer key in the public key group, thus it can be only verified against a set of
+/// public keys for which a proof of possession has been previously checked.
+pub type AggregateSignature<SubTag> =
+	SignatureBytes<AGGREGATE_SIGNATURE_SERIALIZED_SIZE, (BlsAggregateTag, SubTag)>;
+
+impl<T: BlsBound> CryptoType for AggregateSignature<T> {
+	type Pair = Pair<T>;
+}
+
+/// Aggregate a set of signatures.
+///
+/// Returns `None` if `signatures` is empty or if any of the signatures is not validly
+/// encoded.
+pub fn aggregate_signatures<T: EngineBLS>(
+	signatures: &[Signature<T>],
+) -> Option<AggregateSignature<T>> {
+	let mut signatures = signatures
+		.iter()
+		.map(|sig| DoubleSignature::<T>::from_bytes(&sig.0[..]).ok().map(|sig| sig.0));
+	let first = signatures.next()??;
+	let aggregate = signatures.try_fold(first, |acc, sig| sig.map(|sig| acc + sig))?;
+	let mut raw = [0u8; AGGREGATE_SIGNATURE_SERIALIZED_SIZE];
+	raw.copy_from_slice(SingleSignature::<T>(aggregate).to_bytes().as_slice());
+	Some(AggregateSignature::unchecked_from(raw))
+}
+
+/// Verify an aggregate signature of the same `message` by all the `public_keys`.
+///
+/// This is the so called *fast aggregate verification*, where the public keys are first
+/// aggregated and then a single signature verification is performed. As such it also
+/// serves to verify BLS multi-signatures.
+///
+/// WARNING: the check is secure only if the proof of possession of the secret key has
+/// been verified for all the `public_keys` (e.g. on key registration). Otherwise the
+/// scheme is vulnerable to rogue key attacks.
+///
+/// Returns `false` if `public_keys` is empty.
+pub fn fast_aggregate_verify<T: EngineBLS>(
+	signature: &AggregateSignature<T>,
+	message: &[u8],
+	public_keys: &[Public<T>],
+) -> bool {
+	let mut public_keys = public_keys
+		.iter()
+		.map(|pk| DoublePublicKey::<T>::from_bytes(&pk.0[..]).ok().map(|pk| pk.1));
+	let Some(Some(first)) = public_keys.next() else { return false };
+	let Some(public_key) = public_keys.try_fold(first, |acc, pk| pk.map(|pk| acc + pk)) else {
+		return false
+	};
+	let Ok(signature) = SingleSignature::<T>::from_bytes(&signature.0[..]) else { return false };
+	signature.verify(&Message::new(b"", message), &SinglePublicKey(public_key))
+}
+
+/// Verify an aggregate signature of a message by each of the `signers`.
+///
+/// Every signer is paired with the message it signed, thus contrary to
+/// [`fast_aggregate_verify`] the signers may have signed different messages. One pairing
+/// per signer is computed, which makes the check more expensive than the fast aggregate
+/// verification of a single message.
+///
+/// WARNING: the check is secure only if the proof of possession of the secret key has
+/// been verified for all the signers public keys (e.g. on key registration).
+///
+/// Returns `false` if `signers` is empty.
+pub fn aggregate_verify<T: EngineBLS, M: AsRef<[u8]>>(
+	signature: &AggregateSignature<T>,
+	signers: &[(Public<T>, M)],
+) -> bool {
+	if signers.is_empty() {
+		return false
+	}
+	let inputs = signers
+		.iter()
+		.map(|(public_key, message)| {
+			let public_key = DoublePublicKey::<T>::from_bytes(&public_key.0[..]).ok()?.1;
+			let message = Message::new(b"", message.as_ref()).hash_to_signature_curve::<T>();
+			Some((T::prepare_public_key(public_key), T::prepare_signature(message)))
+		})
+		.collect::<Option<Vec<_>>>();
+	let Some(inputs) = inputs else { return false };
+	let Ok(signature) = SingleSignature::<T>::from_bytes(&signature.0[..]) else { return false };
+	T::verify_prepared(T::prepare_signature(signature.0), inputs.iter())
+}
+
+#[doc(hidden)]
+pub struct BlsProofOfPossessionTag;
+
+/// A generic BLS proof of possession.
+///
+/// This is a signature of the public key by the corresponding secret key, using a
+/// dedicated domain separation tag. It proves the knowledge of the secret key and
+/// is used to prevent rogue key attacks against aggregate signatures.
+pub type ProofOfPossession<SubTag> =
+	SignatureBytes<PROOF_OF_POSSESSION_SERIALIZED_SIZE, (BlsProofOfPossessionTag, SubTag)>;
+
+impl<T: BlsBound> CryptoType for ProofOfPossession<T> {
+	type Pair = Pair<T>;
+}
+
+/// Verify the proof of possession of the secret key of `public_key`.
+///
+/// Returns `false` if either the proof or the public key is not validly encoded.
+pub fn verify_proof_of_possession<T: EngineBLS>(
+	proof: &ProofOfPossession<T>,
+	public_key: &Public<T>,
+) -> bool {
+	let Ok(public_key) = DoublePublicKey::<T>::from_bytes(&public_key.0[..]) else { return false };
+	let Ok(proof) = SingleSignature::<T>::from_bytes(&proof.0[..]) else { return false };
+	w3f_bls::ProofOfPossession::<T, ProofOfPossessionHasher, DoublePublicKey<T>>::verify(
+		&BLSPoP::<T>(proof.0),
+		&public_key,
+	)
+}
+
 /// A key pair.
 pub struct Pair<T: EngineBLS>(Keypair<T>);
 
@@ -142,7 +359,21 @@ fn derive_hard_junction<T: HardJunctionId>(secret_seed: &Seed, cc: &[u8; 32]) ->
 	(T::ID, secret_seed, cc).using_encoded(sp_crypto_hashing::blake2_256)
 }
 
-impl<T: EngineBLS> Pair<T> {}
+impl<T: EngineBLS> Pair<T> {
+	/// Generate a proof of possession of the secret key of the pair.
+	pub fn generate_proof_of_possession(&self) -> ProofOfPossession<T> {
+		let mut keypair = self.0.clone();
+		let proof = w3f_bls::ProofOfPossessionGenerator::<
+			T,
+			ProofOfPossessionHasher,
+			DoublePublicKey<T>,
+			BLSPoP<T>,
+		>::generate_pok(&mut keypair);
+		let mut raw = [0u8; PROOF_OF_POSSESSION_SERIALIZED_SIZE];
+		raw.copy_from_slice(SingleSignature::<T>(proof.0).to_bytes().as_slice());
+		ProofOfPossession::unchecked_from(raw)
+	}
+}
 
 impl<T: BlsBound> TraitPair for Pair<T> {
 	type Seed = Seed;
@@ -544,4 +775,94 @@ mod tests {
 	fn signature_serialization_doesnt_panic_for_bls381() {
 		signature_serialization_doesnt_panic::<bls381::BlsEngine>();
 	}
+
+	fn fast_aggregate_verify_works<E: BlsBound>() {
+		let pairs: Vec<_> = (0..5u8).map(|i| Pair::<E>::from_seed(&[i; 32])).collect();
+		let public_keys: Vec<_> = pairs.iter().map(|p| p.public()).collect();
+		let message = b"Something important";
+		let signatures: Vec<_> = pairs.iter().map(|p| p.sign(&message[..])).collect();
+
+		let aggregate = aggregate_signatures(&signatures).unwrap();
+		assert!(fast_aggregate_verify(&aggregate, &message[..], &public_keys));
+		assert!(!fast_aggregate_verify(&aggregate, b"Something else", &public_keys));
+		// Missing signer
+		assert!(!fast_aggregate_verify(&aggregate, &message[..], &public_keys[1..]));
+		assert!(!fast_aggregate_verify(&aggregate, &message[..], &[]));
+
+		// Signatures of a subset of signers
+		let aggregate = aggregate_signatures(&signatures[1..3]).unwrap();
+		assert!(fast_aggregate_verify(&aggregate, &message[..], &public_keys[1..3]));
+		assert!(!fast_aggregate_verify(&aggregate, &message[..], &public_keys));
+
+		assert!(aggregate_signatures::<E>(&[]).is_none());
+	}
+
+	#[test]
+	fn fast_aggregate_verify_works_for_bls377() {
+		fast_aggregate_verify_works::<bls377::BlsEngine>();
+	}
+
+	#[test]
+	fn fast_aggregate_verify_works_for_bls381() {
+		fast_aggregate_verify_works::<bls381::BlsEngine>();
+	}
+
+	fn aggregate_verify_works<E: BlsBound>() {
+		let pairs: Vec<_> = (0..4u8).map(|i| Pair::<E>::from_seed(&[i; 32])).collect();
+		let messages: Vec<_> = (0..4u8).map(|i| [i; 8]).collect();
+		let signatures: Vec<_> = pairs.iter().zip(&messages).map(|(p, m)| p.sign(&m[..])).collect();
+		let mut signers: Vec<_> =
+			pairs.iter().map(|p| p.public()).zip(messages.iter().map(|m| &m[..])).collect();
+
+		let aggregate = aggregate_signatures(&signatures).unwrap();
+		assert!(aggregate_verify(&aggregate, &signers));
+		// Missing signer
+		assert!(!aggregate_verify(&aggregate, &signers[1..]));
+		assert!(!aggregate_verify::<E, &[u8]>(&aggregate, &[]));
+
+		// Signers paired with the wrong messages
+		signers.swap(0, 1);
+		signers[0].0 = pairs[0].public();
+		signers[1].0 = pairs[1].public();
+		assert!(!aggregate_verify(&aggregate, &signers));
+
+		// Signers of the same message
+		let signatures: Vec<_> = pairs.iter().map(|p| p.sign(&messages[0][..])).collect();
+		let aggregate = aggregate_signatures(&signatures).unwrap();
+		let signers: Vec<_> = pairs.iter().map(|p| (p.public(), &messages[0][..])).collect();
+		assert!(aggregate_verify(&aggregate, &signers));
+	}
+
+	#[test]
+	fn aggregate_verify_works_for_bls377() {
+		aggregate_verify_works::<bls377::BlsEngine>();
+	}
+
+	#[test]
+	fn aggregate_verify_works_for_bls381() {
+		aggregate_verify_works::<bls381::BlsEngine>();
+	}
+
+	fn proof_of_possession_works<E: BlsBound>() {
+		let pair = Pair::<E>::from_seed(&[1; 32]);
+		let other_pair = Pair::<E>::from_seed(&[2; 32]);
+		let proof = pair.generate_proof_of_possession();
+
+		assert!(verify_proof_of_possession(&proof, &pair.public()));
+		assert!(!verify_proof_of_possession(&proof, &other_pair.public()));
+		assert!(!verify_proof_of_possession(
+			&other_pair.generate_proof_of_possession(),
+			&pair.public()
+		));
+	}
+
+	#[test]
+	fn proof_of_possession_works_for_bls377() {
+		proof_of_possession_works::<bls377::BlsEngine>();
+	}
+
+	#[test]
+	fn proof_of_possession_works_for_bls381() {
+		proof_of_possession_works::<bls381::BlsEngine>();
+	}
 }
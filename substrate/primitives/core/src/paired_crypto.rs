@@ -171,6 +171,16 @@ pub mod ecdsa_bls381 {
 		type Pair = Pair;
 	}
 
+	/// Verify the proof of possession of the BLS12-381 secret key of `public`.
+	///
+	/// See [`bls381::verify_proof_of_possession`].
+	pub fn verify_proof_of_possession(proof: &bls381::ProofOfPossession, public: &Public) -> bool {
+		let Ok(right_pub) = public.0[ecdsa::PUBLIC_KEY_SERIALIZED_SIZE..].try_into() else {
+			return false
+		};
+		bls381::verify_proof_of_possession(proof, &right_pub)
+	}
+
 	#[cfg(feature = "full_crypto")]
 	impl Pair {
 		/// Hashes the `message` with the specified [`Hasher`] before signing with the ECDSA secret
@@ -223,6 +233,13 @@ pub mod ecdsa_bls381 {
 			};
 			bls381::Pair::verify(&right_sig, message, &right_pub)
 		}
+
+		/// Generate a proof of possession of the BLS12-381 secret key of the pair.
+		///
+		/// The ECDSA component doesn't need one, as ECDSA signatures are never aggregated.
+		pub fn generate_proof_of_possession(&self) -> bls381::ProofOfPossession {
+			self.right.generate_proof_of_possession()
+		}
 	}
 }
 
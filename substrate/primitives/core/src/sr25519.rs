@@ -313,8 +313,21 @@ pub mod vrf {
 	const DEFAULT_EXTRA_DATA_LABEL: &[u8] = b"VRF";
 
 	/// Transcript ready to be used for VRF related operations.
+	///
+	/// Keeps track of the data it was built from, so that it can be rebuilt by a signer
+	/// living outside of the node process. Messages appended to the inner transcript
+	/// directly are not tracked.
 	#[derive(Clone)]
-	pub struct VrfTranscript(pub merlin::Transcript);
+	pub struct VrfTranscript(pub merlin::Transcript, pub VrfTranscriptData);
+
+	/// Data a [`VrfTranscript`] is built from.
+	#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode)]
+	pub struct VrfTranscriptData {
+		/// Transcript label.
+		pub label: Vec<u8>,
+		/// `(domain, message)` tuples appended to the transcript.
+		pub items: Vec<(Vec<u8>, Vec<u8>)>,
+	}
 
 	impl VrfTranscript {
 		/// Build a new transcript instance.
@@ -323,7 +336,24 @@ pub mod vrf {
 		pub fn new(label: &'static [u8], data: &[(&'static [u8], &[u8])]) -> Self {
 			let mut transcript = merlin::Transcript::new(label);
 			data.iter().for_each(|(l, b)| transcript.append_message(l, b));
-			VrfTranscript(transcript)
+			let data = VrfTranscriptData {
+				label: label.to_vec(),
+				items: data.iter().map(|(l, b)| (l.to_vec(), b.to_vec())).collect(),
+			};
+			VrfTranscript(transcript, data)
+		}
+
+		/// Build a transcript instance from a raw `merlin` transcript.
+		///
+		/// The data the transcript was built from is unknown, thus it can't be used
+		/// with a signer living outside of the node process.
+		pub fn from_transcript(transcript: merlin::Transcript) -> Self {
+			VrfTranscript(transcript, Default::default())
+		}
+
+		/// Data the transcript was built from.
+		pub fn data(&self) -> &VrfTranscriptData {
+			&self.1
 		}
 
 		/// Map transcript to `VrfSignData`.
@@ -374,6 +404,11 @@ pub mod vrf {
 			self.extra = Some(extra);
 			self
 		}
+
+		/// Extra data to be signed, if any.
+		pub fn extra(&self) -> Option<&VrfTranscript> {
+			self.extra.as_ref()
+		}
 	}
 
 	/// VRF signature data
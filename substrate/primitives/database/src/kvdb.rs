@@ -71,6 +71,7 @@ impl<D: KeyValueDB, H: Clone + AsRef<[u8]>> Database<H> for DbAdapter<D> {
 			match change {
 				Change::Set(col, key, value) => tx.put_vec(col, &key, value),
 				Change::Remove(col, key) => tx.delete(col, &key),
+				Change::RemovePrefix(col, prefix) => tx.delete_prefix(col, &prefix),
 				Change::Store(col, key, value) => match self.read_counter(col, key.as_ref())? {
 					(counter_key, Some(mut counter)) => {
 						counter += 1;
@@ -32,6 +32,7 @@ pub type ColumnId = u32;
 pub enum Change<H> {
 	Set(ColumnId, Vec<u8>, Vec<u8>),
 	Remove(ColumnId, Vec<u8>),
+	RemovePrefix(ColumnId, Vec<u8>),
 	Store(ColumnId, H, Vec<u8>),
 	Reference(ColumnId, H),
 	Release(ColumnId, H),
@@ -59,6 +60,10 @@ impl<H> Transaction<H> {
 	pub fn remove(&mut self, col: ColumnId, key: &[u8]) {
 		self.0.push(Change::Remove(col, key.to_vec()))
 	}
+	/// Remove the values of all the keys of `col` starting with `prefix`.
+	pub fn remove_prefix(&mut self, col: ColumnId, prefix: &[u8]) {
+		self.0.push(Change::RemovePrefix(col, prefix.to_vec()))
+	}
 	/// Store the `preimage` of `hash` into the database, so that it may be looked up later with
 	/// `Database::get`. This may be called multiple times, but subsequent
 	/// calls will ignore `preimage` and simply increase the number of references on `hash`.
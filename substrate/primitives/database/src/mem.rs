@@ -39,6 +39,9 @@ where
 				Change::Remove(col, key) => {
 					s.entry(col).or_default().remove(&key);
 				},
+				Change::RemovePrefix(col, prefix) => {
+					s.entry(col).or_default().retain(|key, _| !key.starts_with(&prefix));
+				},
 				Change::Store(col, hash, value) => {
 					s.entry(col)
 						.or_default()
@@ -1238,6 +1238,58 @@ pub trait Crypto {
 			.expect("`ecdsa_bls381_generate` failed")
 	}
 
+	/// Verify a `bls12-381` aggregate signature of the same `msg` by all the `pub_keys`.
+	///
+	/// The aggregate signature can be constructed from the signers signatures using
+	/// [`bls381::aggregate_signatures`]. As the public keys are aggregated before the
+	/// verification, the function can be used to check BLS multi-signatures as well.
+	///
+	/// The caller is responsible to ensure that the proof of possession of all the
+	/// `pub_keys` has been checked (see [`bls381::fast_aggregate_verify`]).
+	///
+	/// Returns `true` when the verification was successful.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_fast_aggregate_verify(
+		sig: &bls381::AggregateSignature,
+		msg: &[u8],
+		pub_keys: &[bls381::Public],
+	) -> bool {
+		bls381::fast_aggregate_verify(sig, msg, pub_keys)
+	}
+
+	/// Verify a `bls12-381` aggregate signature of a message by each of the signers.
+	///
+	/// Each public key of `signers` is paired with the message it signed, thus the aggregate
+	/// signature can cover distinct messages. The verification cost grows with the number of
+	/// signers, prefer [`bls381_fast_aggregate_verify`] when all the signers signed the same
+	/// message.
+	///
+	/// The caller is responsible to ensure that the proof of possession of all the
+	/// public keys has been checked (see [`bls381::aggregate_verify`]).
+	///
+	/// Returns `true` when the verification was successful.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_aggregate_verify(
+		sig: &bls381::AggregateSignature,
+		signers: &[(bls381::Public, Vec<u8>)],
+	) -> bool {
+		bls381::aggregate_verify(sig, signers)
+	}
+
+	/// Verify a `bls12-381` proof of possession of the secret key of `pub_key`.
+	///
+	/// The proof is meant to be checked once for each key (e.g. on key registration)
+	/// before using the key for aggregate signatures verification.
+	///
+	/// Returns `true` when the verification was successful.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_verify_proof_of_possession(
+		proof: &bls381::ProofOfPossession,
+		pub_key: &bls381::Public,
+	) -> bool {
+		bls381::verify_proof_of_possession(proof, pub_key)
+	}
+
 	/// Generate a `bandersnatch` key pair for the given key type using an optional
 	/// `seed` and store it in the keystore.
 	///
@@ -1256,6 +1308,62 @@ pub trait Crypto {
 			.bandersnatch_generate_new(id, seed)
 			.expect("`bandernatch_generate` failed")
 	}
+
+	/// Verify a `bandersnatch` VRF signature.
+	///
+	/// The signed data is constructed from `vrf_input` and `aux_data` as done by
+	/// [`bandersnatch::vrf::VrfSignData::new`], while `signature` is the SCALE encoded
+	/// [`bandersnatch::vrf::VrfSignature`].
+	///
+	/// Returns `true` when the verification was successful.
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_vrf_verify(
+		vrf_input: &[u8],
+		aux_data: &[u8],
+		signature: &[u8],
+		pub_key: &bandersnatch::Public,
+	) -> bool {
+		use sp_core::crypto::VrfPublic;
+
+		let Ok(signature) = bandersnatch::vrf::VrfSignature::decode(&mut &signature[..]) else {
+			return false
+		};
+		let data = bandersnatch::vrf::VrfSignData::new(vrf_input, aux_data);
+		pub_key.vrf_verify(&data, &signature)
+	}
+
+	/// Verify a `bandersnatch` ring VRF signature.
+	///
+	/// The signed data is constructed from `vrf_input` and `aux_data` as done by
+	/// [`bandersnatch::vrf::VrfSignData::new`], while `signature` is the SCALE encoded
+	/// [`bandersnatch::ring_vrf::RingVrfSignature`].
+	///
+	/// The ring verifier is built from the SCALE encoded `verifier_key`, which must have
+	/// been constructed for a ring with at most `ring_size` members.
+	///
+	/// Returns `true` when the verification was successful, and `false` if `ring_size`
+	/// exceeds [`bandersnatch::ring_vrf::MAX_RING_SIZE`].
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_ring_vrf_verify(
+		vrf_input: &[u8],
+		aux_data: &[u8],
+		signature: &[u8],
+		verifier_key: &[u8],
+		ring_size: u32,
+	) -> bool {
+		use bandersnatch::ring_vrf::{RingVerifierKey, RingVrfSignature, MAX_RING_SIZE};
+
+		if ring_size as usize > MAX_RING_SIZE {
+			return false
+		}
+		let Ok(signature) = RingVrfSignature::decode(&mut &signature[..]) else { return false };
+		let Ok(verifier_key) = RingVerifierKey::decode(&mut &verifier_key[..]) else {
+			return false
+		};
+		let verifier = verifier_key.into_verifier(ring_size as usize);
+		let data = bandersnatch::vrf::VrfSignData::new(vrf_input, aux_data);
+		signature.ring_vrf_verify(&data, &verifier)
+	}
 }
 
 /// Interface that provides functions for hashing with different algorithms.
@@ -1945,6 +2053,121 @@ mod tests {
 		})
 	}
 
+	#[cfg(feature = "bls-experimental")]
+	#[test]
+	fn bls381_fast_aggregate_verify_works() {
+		use sp_core::crypto::Pair;
+
+		let pairs: Vec<_> = (0..4u8).map(|i| bls381::Pair::from_seed(&[i; 32])).collect();
+		let pub_keys: Vec<_> = pairs.iter().map(|p| p.public()).collect();
+		let signatures: Vec<_> = pairs.iter().map(|p| p.sign(b"message")).collect();
+		let aggregate = bls381::aggregate_signatures(&signatures).unwrap();
+
+		BasicExternalities::default().execute_with(|| {
+			assert!(crypto::bls381_fast_aggregate_verify(&aggregate, b"message", &pub_keys));
+			assert!(!crypto::bls381_fast_aggregate_verify(&aggregate, b"foo", &pub_keys));
+			assert!(!crypto::bls381_fast_aggregate_verify(&aggregate, b"message", &pub_keys[1..]));
+		});
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	#[test]
+	fn bls381_aggregate_verify_works() {
+		use sp_core::crypto::Pair;
+
+		let pairs: Vec<_> = (0..4u8).map(|i| bls381::Pair::from_seed(&[i; 32])).collect();
+		let mut signers: Vec<_> =
+			(0..4u8).zip(&pairs).map(|(i, p)| (p.public(), vec![i])).collect();
+		let signatures: Vec<_> = signers.iter().zip(&pairs).map(|((_, m), p)| p.sign(m)).collect();
+		let aggregate = bls381::aggregate_signatures(&signatures).unwrap();
+
+		BasicExternalities::default().execute_with(|| {
+			assert!(crypto::bls381_aggregate_verify(&aggregate, &signers));
+			assert!(!crypto::bls381_aggregate_verify(&aggregate, &signers[1..]));
+			signers[0].1 = b"message".to_vec();
+			assert!(!crypto::bls381_aggregate_verify(&aggregate, &signers));
+		});
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	#[test]
+	fn bls381_verify_proof_of_possession_works() {
+		use sp_core::crypto::Pair;
+
+		let pair = bls381::Pair::from_seed(&[1; 32]);
+		let other_pair = bls381::Pair::from_seed(&[2; 32]);
+		let proof = pair.generate_proof_of_possession();
+
+		BasicExternalities::default().execute_with(|| {
+			assert!(crypto::bls381_verify_proof_of_possession(&proof, &pair.public()));
+			assert!(!crypto::bls381_verify_proof_of_possession(&proof, &other_pair.public()));
+		});
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	#[test]
+	fn bandersnatch_vrf_verify_works() {
+		use bandersnatch::vrf::VrfSignData;
+		use sp_core::crypto::{Pair, VrfSecret};
+
+		let pair = bandersnatch::Pair::from_seed(&[1; 32]);
+		let signature = pair.vrf_sign(&VrfSignData::new(b"input", b"aux"));
+
+		BasicExternalities::default().execute_with(|| {
+			let signature = signature.encode();
+			assert!(crypto::bandersnatch_vrf_verify(b"input", b"aux", &signature, &pair.public()));
+			assert!(!crypto::bandersnatch_vrf_verify(b"input", b"foo", &signature, &pair.public()));
+			assert!(!crypto::bandersnatch_vrf_verify(b"input", b"aux", &[0; 8], &pair.public()));
+		});
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	#[test]
+	fn bandersnatch_ring_vrf_verify_works() {
+		use bandersnatch::{ring_vrf::RingContext, vrf::VrfSignData};
+		use sp_core::crypto::Pair;
+
+		const RING_SIZE: usize = 16;
+
+		let ctx = RingContext::<RING_SIZE>::new_testing();
+		let pairs: Vec<_> =
+			(0..RING_SIZE as u8).map(|i| bandersnatch::Pair::from_seed(&[i; 32])).collect();
+		let pks: Vec<_> = pairs.iter().map(|p| p.public()).collect();
+
+		let prover = ctx.prover(&pks, 3);
+		let signature = pairs[3].ring_vrf_sign(&VrfSignData::new(b"input", b"aux"), &prover);
+		let verifier_key = ctx.verifier_key(&pks).encode();
+
+		BasicExternalities::default().execute_with(|| {
+			let signature = signature.encode();
+			let ring_size = RING_SIZE as u32;
+			assert!(crypto::bandersnatch_ring_vrf_verify(
+				b"input",
+				b"aux",
+				&signature,
+				&verifier_key,
+				ring_size,
+			));
+			assert!(!crypto::bandersnatch_ring_vrf_verify(
+				b"input",
+				b"foo",
+				&signature,
+				&verifier_key,
+				ring_size,
+			));
+			assert!(!crypto::bandersnatch_ring_vrf_verify(
+				b"input", b"aux", &signature, &[0; 8], ring_size,
+			));
+			assert!(!crypto::bandersnatch_ring_vrf_verify(
+				b"input",
+				b"aux",
+				&signature,
+				&verifier_key,
+				u32::MAX,
+			));
+		});
+	}
+
 	#[test]
 	fn dalek_should_not_panic_on_invalid_signature() {
 		let mut ext = BasicExternalities::default();
@@ -0,0 +1,45 @@
+use sc_service::ChainType;
+use solochain_template_sassafras_runtime::{
+	genesis_config_presets::SASSAFRAS_TESTNET_PRESET_PREFIX, WASM_BINARY,
+};
+
+/// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
+pub type ChainSpec = sc_service::GenericChainSpec;
+
+pub fn development_chain_spec() -> Result<ChainSpec, String> {
+	Ok(ChainSpec::builder(
+		WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?,
+		None,
+	)
+	.with_name("Development")
+	.with_id("dev")
+	.with_chain_type(ChainType::Development)
+	.with_genesis_config_preset_name(sp_genesis_builder::DEV_RUNTIME_PRESET)
+	.build())
+}
+
+pub fn local_chain_spec() -> Result<ChainSpec, String> {
+	Ok(ChainSpec::builder(
+		WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?,
+		None,
+	)
+	.with_name("Local Testnet")
+	.with_id("local_testnet")
+	.with_chain_type(ChainType::Local)
+	.with_genesis_config_preset_name(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET)
+	.build())
+}
+
+/// Chain spec using one of the `sassafras_testnet_<N>` genesis presets, see
+/// [`SASSAFRAS_TESTNET_PRESET_PREFIX`].
+pub fn testnet_chain_spec(preset: &str) -> Result<ChainSpec, String> {
+	Ok(ChainSpec::builder(
+		WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?,
+		None,
+	)
+	.with_name("Sassafras Testnet")
+	.with_id(preset)
+	.with_chain_type(ChainType::Local)
+	.with_genesis_config_preset_name(preset)
+	.build())
+}
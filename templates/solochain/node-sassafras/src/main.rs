@@ -0,0 +1,12 @@
+//! Substrate Node Template CLI library, using Sassafras for block production.
+#![warn(missing_docs)]
+
+mod chain_spec;
+mod cli;
+mod command;
+mod rpc;
+mod service;
+
+fn main() -> sc_cli::Result<()> {
+	command::run()
+}
@@ -28,9 +28,15 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export the slashing protection database.
+	ExportSlashingProtection(sc_cli::ExportSlashingProtectionCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
+	/// Import a record into the slashing protection database.
+	ImportSlashingProtection(sc_cli::ImportSlashingProtectionCmd),
+
 	/// Remove the whole chain.
 	PurgeChain(sc_cli::PurgeChainCmd),
 
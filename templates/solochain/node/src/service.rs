@@ -9,7 +9,7 @@ use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use solochain_template_runtime::{self, apis::RuntimeApi, opaque::Block};
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
-use std::{sync::Arc, time::Duration};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 pub(crate) type FullClient = sc_service::TFullClient<
 	Block,
@@ -127,10 +127,14 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 }
 
 /// Builds a new service for a full client.
+///
+/// The messages signed by the local validator keys are recorded in the slashing protection
+/// database stored at `slashing_protection_db`, if any.
 pub fn new_full<
 	N: sc_network::NetworkBackend<Block, <Block as sp_runtime::traits::Block>::Hash>,
 >(
 	config: Configuration,
+	slashing_protection_db: Option<PathBuf>,
 ) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
@@ -204,6 +208,17 @@ pub fn new_full<
 		);
 	}
 
+	let slashing_protection = slashing_protection_db
+		.map(|path| {
+			sc_slashing_protection::SlashingProtection::open(
+				path,
+				client.chain_info().genesis_hash.as_ref(),
+			)
+			.map(Arc::new)
+		})
+		.transpose()
+		.map_err(|e| ServiceError::Application(e.into()))?;
+
 	let role = config.role;
 	let force_authoring = config.force_authoring;
 	let backoff_authoring_blocks: Option<()> = None;
@@ -274,6 +289,7 @@ pub fn new_full<
 				max_block_proposal_slot_portion: None,
 				telemetry: telemetry.as_ref().map(|x| x.handle()),
 				compatibility_mode: Default::default(),
+				slashing_protection: slashing_protection.clone(),
 			},
 		)?;
 
@@ -318,6 +334,7 @@ pub fn new_full<
 			shared_voter_state: SharedVoterState::empty(),
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(transaction_pool),
+			slashing_protection,
 		};
 
 		// the GRANDPA voter task is considered infallible, i.e.
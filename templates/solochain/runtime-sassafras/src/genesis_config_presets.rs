@@ -0,0 +1,254 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	AccountId, BalancesConfig, RuntimeGenesisConfig, SudoConfig, SASSAFRAS_GENESIS_EPOCH_CONFIG,
+};
+use alloc::{format, vec, vec::Vec};
+use frame_support::build_struct_json_patch;
+use serde_json::Value;
+use sp_consensus_grandpa::AuthorityId as GrandpaId;
+use sp_consensus_sassafras::AuthorityId as SassafrasId;
+use sp_core::{ed25519, Pair};
+use sp_genesis_builder::{self, PresetId};
+use sp_keyring::{BandersnatchKeyring, Ed25519Keyring, Sr25519Keyring};
+
+/// Preset with a configurable number of authorities, see [`authorities_from_numeric_seeds`].
+///
+/// The preset name is followed by the number of authorities, e.g. `sassafras_testnet_4`.
+pub const SASSAFRAS_TESTNET_PRESET_PREFIX: &str = "sassafras_testnet_";
+
+/// Upper bound of authorities which can be requested via [`SASSAFRAS_TESTNET_PRESET_PREFIX`].
+///
+/// Matches the `MaxAuthorities` configured for both Sassafras and GRANDPA.
+const MAX_TESTNET_AUTHORITIES: u32 = 32;
+
+// Returns the genesis config presets populated with given parameters.
+fn testnet_genesis(
+	initial_authorities: Vec<(SassafrasId, GrandpaId)>,
+	endowed_accounts: Vec<AccountId>,
+	root: AccountId,
+) -> Value {
+	build_struct_json_patch!(RuntimeGenesisConfig {
+		balances: BalancesConfig {
+			balances: endowed_accounts
+				.iter()
+				.cloned()
+				.map(|k| (k, 1u128 << 60))
+				.collect::<Vec<_>>(),
+		},
+		sassafras: pallet_sassafras::GenesisConfig {
+			authorities: initial_authorities.iter().map(|x| x.0.clone()).collect::<Vec<_>>(),
+			epoch_config: SASSAFRAS_GENESIS_EPOCH_CONFIG,
+		},
+		grandpa: pallet_grandpa::GenesisConfig {
+			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect::<Vec<_>>(),
+		},
+		sudo: SudoConfig { key: Some(root) },
+	})
+}
+
+/// Session keys of a well-known development authority.
+fn keyring_authority(
+	bandersnatch: BandersnatchKeyring,
+	ed25519: Ed25519Keyring,
+) -> (SassafrasId, GrandpaId) {
+	(bandersnatch.public().into(), ed25519.public().into())
+}
+
+/// Generates the session keys of `count` authorities.
+///
+/// Keys are derived from the `//<index>` secret URI, hence the matching keys can be inserted
+/// into the nodes keystores via the `author_insertKey` RPC using the `sass` and `gran` key types.
+pub fn authorities_from_numeric_seeds(count: u32) -> Vec<(SassafrasId, GrandpaId)> {
+	(0..count as usize)
+		.map(|idx| {
+			(
+				BandersnatchKeyring::numeric(idx).public().into(),
+				ed25519::Pair::from_string(&format!("//{idx}"), None)
+					.expect("numeric values are known good; qed")
+					.public()
+					.into(),
+			)
+		})
+		.collect()
+}
+
+/// Return the development genesis config.
+pub fn development_config_genesis() -> Value {
+	testnet_genesis(
+		vec![keyring_authority(BandersnatchKeyring::Alice, Ed25519Keyring::Alice)],
+		vec![
+			Sr25519Keyring::Alice.to_account_id(),
+			Sr25519Keyring::Bob.to_account_id(),
+			Sr25519Keyring::AliceStash.to_account_id(),
+			Sr25519Keyring::BobStash.to_account_id(),
+		],
+		Sr25519Keyring::Alice.to_account_id(),
+	)
+}
+
+/// Return the local genesis config preset.
+pub fn local_config_genesis() -> Value {
+	testnet_genesis(
+		vec![
+			keyring_authority(BandersnatchKeyring::Alice, Ed25519Keyring::Alice),
+			keyring_authority(BandersnatchKeyring::Bob, Ed25519Keyring::Bob),
+		],
+		Sr25519Keyring::iter()
+			.filter(|v| v != &Sr25519Keyring::One && v != &Sr25519Keyring::Two)
+			.map(|v| v.to_account_id())
+			.collect::<Vec<_>>(),
+		Sr25519Keyring::Alice.to_account_id(),
+	)
+}
+
+/// Return the genesis config preset with `count` generated authorities.
+pub fn sassafras_testnet_config_genesis(count: u32) -> Value {
+	testnet_genesis(
+		authorities_from_numeric_seeds(count),
+		Sr25519Keyring::iter()
+			.filter(|v| v != &Sr25519Keyring::One && v != &Sr25519Keyring::Two)
+			.map(|v| v.to_account_id())
+			.collect::<Vec<_>>(),
+		Sr25519Keyring::Alice.to_account_id(),
+	)
+}
+
+/// Parses the number of authorities out of a [`SASSAFRAS_TESTNET_PRESET_PREFIX`] preset name.
+fn parse_testnet_authorities_count(id: &str) -> Option<u32> {
+	let count = id.strip_prefix(SASSAFRAS_TESTNET_PRESET_PREFIX)?.parse::<u32>().ok()?;
+	(1..=MAX_TESTNET_AUTHORITIES).contains(&count).then_some(count)
+}
+
+/// Provides the JSON representation of predefined genesis config for given `id`.
+pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
+	let patch = match id.as_ref() {
+		sp_genesis_builder::DEV_RUNTIME_PRESET => development_config_genesis(),
+		sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET => local_config_genesis(),
+		id => sassafras_testnet_config_genesis(parse_testnet_authorities_count(id)?),
+	};
+	Some(
+		serde_json::to_string(&patch)
+			.expect("serialization to json is expected to work. qed.")
+			.into_bytes(),
+	)
+}
+
+/// List of supported presets.
+///
+/// Only the four authorities variant of [`SASSAFRAS_TESTNET_PRESET_PREFIX`] is listed, any other
+/// count up to the maximum number of authorities can be requested by name.
+pub fn preset_names() -> Vec<PresetId> {
+	vec![
+		PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
+		PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+		format!("{SASSAFRAS_TESTNET_PRESET_PREFIX}4"),
+	]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Runtime;
+
+	/// Applies `patch` on top of `base`, the way the chain-spec builder does.
+	fn merge(base: &mut Value, patch: Value) {
+		match (base, patch) {
+			(Value::Object(base), Value::Object(patch)) =>
+				for (key, value) in patch {
+					merge(base.entry(key).or_insert(Value::Null), value);
+				},
+			(base, patch) => *base = patch,
+		}
+	}
+
+	fn build_preset(id: &str) -> sp_io::TestExternalities {
+		let patch = get_preset(&id.into()).expect("preset exists");
+		let mut config = serde_json::to_value(RuntimeGenesisConfig::default()).unwrap();
+		merge(&mut config, serde_json::from_slice(&patch).unwrap());
+
+		let mut ext = sp_io::TestExternalities::default();
+		ext.execute_with(|| {
+			frame_support::genesis_builder_helper::build_state::<RuntimeGenesisConfig>(
+				serde_json::to_vec(&config).unwrap(),
+			)
+			.unwrap();
+		});
+		ext
+	}
+
+	fn genesis_authorities(id: &str) -> (Vec<SassafrasId>, Vec<GrandpaId>) {
+		build_preset(id).execute_with(|| {
+			let sassafras = pallet_sassafras::Authorities::<Runtime>::get().into_inner();
+			let grandpa = pallet_grandpa::Pallet::<Runtime>::grandpa_authorities()
+				.into_iter()
+				.map(|(id, _)| id)
+				.collect();
+			(sassafras, grandpa)
+		})
+	}
+
+	#[test]
+	fn listed_presets_build() {
+		for id in preset_names() {
+			let id = String::from_utf8(id.into()).unwrap();
+			let (sassafras, grandpa) = genesis_authorities(&id);
+			assert!(!sassafras.is_empty(), "{id} has no authorities");
+			assert_eq!(sassafras.len(), grandpa.len(), "{id} authorities mismatch");
+		}
+	}
+
+	#[test]
+	fn keyring_presets_use_keyring_authorities() {
+		let (sassafras, grandpa) = genesis_authorities(sp_genesis_builder::DEV_RUNTIME_PRESET);
+		assert_eq!(sassafras, vec![BandersnatchKeyring::Alice.public().into()]);
+		assert_eq!(grandpa, vec![Ed25519Keyring::Alice.public().into()]);
+
+		let (sassafras, _) = genesis_authorities(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET);
+		assert_eq!(
+			sassafras,
+			vec![
+				BandersnatchKeyring::Alice.public().into(),
+				BandersnatchKeyring::Bob.public().into()
+			]
+		);
+	}
+
+	#[test]
+	fn testnet_preset_generates_numeric_authorities() {
+		for count in [1, 4, MAX_TESTNET_AUTHORITIES] {
+			let (sassafras, grandpa) =
+				genesis_authorities(&format!("{SASSAFRAS_TESTNET_PRESET_PREFIX}{count}"));
+			let expected = authorities_from_numeric_seeds(count);
+			assert_eq!(sassafras, expected.iter().map(|x| x.0.clone()).collect::<Vec<_>>());
+			assert_eq!(grandpa, expected.iter().map(|x| x.1.clone()).collect::<Vec<_>>());
+		}
+
+		// The keys match the ones derived from `//<index>`.
+		let (sassafras, grandpa) = authorities_from_numeric_seeds(2).pop().unwrap();
+		assert_eq!(sassafras, BandersnatchKeyring::numeric(1).public().into());
+		assert_eq!(grandpa, ed25519::Pair::from_string("//1", None).unwrap().public().into());
+	}
+
+	#[test]
+	fn testnet_preset_rejects_invalid_counts() {
+		for id in ["sassafras_testnet_0", "sassafras_testnet_33", "sassafras_testnet_x", "other"] {
+			assert!(get_preset(&id.into()).is_none(), "{id} should not exist");
+		}
+	}
+}
@@ -1140,6 +1140,11 @@ pub use sc_runtime_utilities;
 #[cfg(feature = "sc-service")]
 pub use sc_service;
 
+/// Persistent record of consensus messages signed by the local validator, refusing to sign
+/// conflicting ones.
+#[cfg(feature = "sc-slashing-protection")]
+pub use sc_slashing_protection;
+
 /// State database maintenance. Handles canonicalization and pruning in the database.
 #[cfg(feature = "sc-state-db")]
 pub use sc_state_db;